@@ -8,11 +8,15 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 use the_foundation::TheAgitator;
-use the_foundation::{AgitatorType, CryptexError, CryptexResult, ThePackage, TheReply};
+use the_foundation::{
+    AgitatorType, CryptexError, CryptexResult, ThePackage, TheReply, DEFAULT_SYSTEM_PROMPT,
+};
 
 const CLAUDE_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const CLAUDE_API_VERSION: &str = "2023-06-01";
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_TEMPERATURE: f32 = 0.7;
+const DEFAULT_MAX_TOKENS: u32 = 4096;
 
 /// Claude Messages API Request
 #[derive(Debug, Serialize)]
@@ -34,6 +38,7 @@ struct Message {
 
 /// Claude Messages API Response
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct ClaudeResponse {
     id: String,
     #[serde(rename = "type")]
@@ -62,8 +67,11 @@ struct Usage {
 pub struct ClaudeAgitator {
     api_key: String,
     model: String,
+    api_url: String,
     client: Client,
     timeout: std::time::Duration,
+    temperature: f32,
+    max_tokens: u32,
 }
 
 impl ClaudeAgitator {
@@ -92,6 +100,14 @@ impl ClaudeAgitator {
     /// }
     /// ```
     pub async fn the_awakening(api_key: String, model: String) -> CryptexResult<Self> {
+        Self::with_client(api_key, model, crate::default_http_client()?)
+    }
+
+    /// Initialize the Claude agitator on an existing HTTP client
+    ///
+    /// Pass clones of one client (see [`crate::build_http_client`]) to share a
+    /// connection pool between agitators.
+    pub fn with_client(api_key: String, model: String, client: Client) -> CryptexResult<Self> {
         // Validate API key
         if api_key.is_empty() {
             return Err(CryptexError::agitator("Claude API key cannot be empty"));
@@ -102,19 +118,16 @@ impl ClaudeAgitator {
             return Err(CryptexError::agitator("Claude model name cannot be empty"));
         }
 
-        // Create HTTP client with timeout
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .build()
-            .map_err(|e| CryptexError::network(format!("Failed to create HTTP client: {}", e)))?;
-
         tracing::info!("Claude agitator awakened with model: {}", model);
 
         Ok(Self {
             api_key,
             model,
+            api_url: CLAUDE_API_URL.to_string(),
             client,
             timeout: std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            temperature: DEFAULT_TEMPERATURE,
+            max_tokens: DEFAULT_MAX_TOKENS,
         })
     }
 
@@ -124,12 +137,31 @@ impl ClaudeAgitator {
         self
     }
 
+    /// Send requests to a different endpoint (e.g. a proxy or compatible gateway)
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    /// Set sampling temperature (0.0 for deterministic output)
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Set maximum tokens in the reply
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
     /// Build the request payload for Claude API
     fn build_request(&self, package: &ThePackage) -> ClaudeRequest {
         // Extract system context if provided
-        let system = package.context.clone().or_else(|| {
-            Some("You are a security expert assistant helping to analyze vulnerabilities and security issues. Provide accurate, detailed, and actionable security analysis.".to_string())
-        });
+        let system = package
+            .context
+            .clone()
+            .or_else(|| Some(DEFAULT_SYSTEM_PROMPT.to_string()));
 
         // Build user message with package data
         let user_content = if package.data.is_string() {
@@ -147,27 +179,37 @@ impl ClaudeAgitator {
         ClaudeRequest {
             model: self.model.clone(),
             messages,
-            max_tokens: 4096, // Claude default max
+            max_tokens: self.max_tokens,
             system,
-            temperature: Some(0.7),
+            temperature: Some(self.temperature),
         }
     }
 
+    /// Build the HTTP request, applying the configured timeout
+    fn build_http_request(&self, request: &ClaudeRequest) -> CryptexResult<reqwest::Request> {
+        self.client
+            .post(&self.api_url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", CLAUDE_API_VERSION)
+            .header("Content-Type", "application/json")
+            .timeout(self.timeout)
+            .json(request)
+            .build()
+            .map_err(|e| CryptexError::agitator(format!("Failed to build Claude request: {}", e)))
+    }
+
     /// Send request to Claude API
     async fn send_request(&self, request: &ClaudeRequest) -> CryptexResult<ClaudeResponse> {
         let start = std::time::Instant::now();
 
         let response = self
             .client
-            .post(CLAUDE_API_URL)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", CLAUDE_API_VERSION)
-            .header("Content-Type", "application/json")
-            .json(request)
-            .send()
+            .execute(self.build_http_request(request)?)
             .await
             .map_err(|e| {
-                CryptexError::network(format!("Failed to send request to Claude: {}", e))
+                // Keep the transport error typed so timeouts surface as NetworkError
+                tracing::warn!("Failed to send request to Claude: {}", e);
+                CryptexError::from(e)
             })?;
 
         let duration = start.elapsed();
@@ -181,10 +223,7 @@ impl ClaudeAgitator {
                 .await
                 .unwrap_or_else(|_| "Unable to read error response".to_string());
 
-            return Err(CryptexError::agitator(format!(
-                "Claude API error ({}): {}",
-                status, error_body
-            )));
+            return Err(crate::status_error("Claude", status, &error_body));
         }
 
         // Parse response
@@ -391,4 +430,93 @@ mod tests {
         assert!(request.system.is_some());
         assert!(request.system.unwrap().contains("security expert"));
     }
+
+    #[test]
+    fn test_build_request_configured_sampling() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let agitator = rt
+            .block_on(ClaudeAgitator::the_awakening(
+                "test-key".to_string(),
+                "claude-3-sonnet-20240229".to_string(),
+            ))
+            .unwrap();
+
+        let package = ThePackage {
+            data: serde_json::json!("test data"),
+            context: None,
+        };
+
+        // Defaults are unchanged when nothing is configured
+        let request = agitator.build_request(&package);
+        assert_eq!(request.temperature, Some(0.7));
+        assert_eq!(request.max_tokens, 4096);
+
+        let agitator = agitator.with_temperature(0.0).with_max_tokens(1024);
+        let request = agitator.build_request(&package);
+        assert_eq!(request.temperature, Some(0.0));
+        assert_eq!(request.max_tokens, 1024);
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["temperature"], 0.0);
+        assert_eq!(json["max_tokens"], 1024);
+    }
+
+    #[test]
+    fn test_configured_timeout_reaches_request() {
+        let client = crate::build_http_client(&crate::HttpSettings {
+            connect_timeout: std::time::Duration::from_secs(2),
+            pool_idle_timeout: std::time::Duration::from_secs(30),
+            pool_max_idle_per_host: 4,
+            proxy: None,
+        })
+        .unwrap();
+        let agitator = ClaudeAgitator::with_client(
+            "sk-ant-test".to_string(),
+            "claude-3-sonnet-20240229".to_string(),
+            client,
+        )
+        .unwrap()
+        .with_timeout(5);
+
+        let package = ThePackage {
+            data: serde_json::json!("test data"),
+            context: None,
+        };
+
+        let request = agitator.build_http_request(&agitator.build_request(&package)).unwrap();
+        assert_eq!(request.timeout(), Some(&std::time::Duration::from_secs(5)));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_is_honored() {
+        // A server that accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+
+        let agitator = ClaudeAgitator::the_awakening("sk-ant-test".to_string(), "claude-3-sonnet-20240229".to_string())
+            .await
+            .unwrap()
+            .with_api_url(format!("http://{}/v1", addr))
+            .with_timeout(1);
+
+        let package = ThePackage {
+            data: serde_json::json!("test data"),
+            context: None,
+        };
+
+        let started = std::time::Instant::now();
+        let err = agitator
+            .send_request(&agitator.build_request(&package))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, CryptexError::NetworkError(_)), "unexpected error: {:?}", err);
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    }
 }
@@ -0,0 +1,457 @@
+//! Google Gemini Provider Implementation
+//!
+//! Provides integration with Google's Generative Language API for
+//! AI-enhanced security analysis using Gemini models.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use the_foundation::TheAgitator;
+use the_foundation::{
+    AgitatorType, CryptexError, CryptexResult, ThePackage, TheReply, DEFAULT_SYSTEM_PROMPT,
+};
+
+const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_TEMPERATURE: f32 = 0.7;
+
+/// Gemini generateContent API Request
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    contents: Vec<Content>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<Content>,
+    generation_config: GenerationConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Content {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Part {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+}
+
+/// Gemini generateContent API Response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    content: Option<Content>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    total_token_count: Option<u64>,
+}
+
+/// Gemini Agitator
+pub struct GeminiAgitator {
+    api_key: String,
+    model: String,
+    client: Client,
+    timeout: std::time::Duration,
+    temperature: f32,
+    max_tokens: Option<u32>,
+}
+
+impl GeminiAgitator {
+    /// The Awakening - Initialize Gemini agitator
+    ///
+    /// Creates a new Gemini agitator with the specified API key and model.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - Google AI Studio API key
+    /// * `model` - Model name (e.g., "gemini-1.5-pro", "gemini-1.5-flash")
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use the_agitator::GeminiAgitator;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let agitator = GeminiAgitator::the_awakening(
+    ///         std::env::var("GEMINI_API_KEY")?,
+    ///         "gemini-1.5-pro".to_string(),
+    ///     ).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn the_awakening(api_key: String, model: String) -> CryptexResult<Self> {
+        Self::with_client(api_key, model, crate::default_http_client()?)
+    }
+
+    /// Initialize the Gemini agitator on an existing HTTP client
+    ///
+    /// Pass clones of one client (see [`crate::build_http_client`]) to share a
+    /// connection pool between agitators.
+    pub fn with_client(api_key: String, model: String, client: Client) -> CryptexResult<Self> {
+        // Validate API key
+        if api_key.is_empty() {
+            return Err(CryptexError::agitator("Gemini API key cannot be empty"));
+        }
+
+        // Validate model name
+        if model.is_empty() {
+            return Err(CryptexError::agitator("Gemini model name cannot be empty"));
+        }
+
+        tracing::info!("Gemini agitator awakened with model: {}", model);
+
+        Ok(Self {
+            api_key,
+            model,
+            client,
+            timeout: std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            temperature: DEFAULT_TEMPERATURE,
+            max_tokens: None,
+        })
+    }
+
+    /// Set custom timeout for API requests
+    pub fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout = std::time::Duration::from_secs(timeout_secs);
+        self
+    }
+
+    /// Set sampling temperature (0.0 for deterministic output)
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Set maximum tokens in the reply
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Endpoint for this agitator's model
+    ///
+    /// The API key travels in the query string, so this URL must never be logged.
+    fn endpoint(&self) -> String {
+        format!(
+            "{}/{}:generateContent?key={}",
+            GEMINI_API_BASE, self.model, self.api_key
+        )
+    }
+
+    /// Build the request payload for Gemini API
+    fn build_request(&self, package: &ThePackage) -> GeminiRequest {
+        // System context, falling back to the default security prompt
+        let system = package
+            .context
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SYSTEM_PROMPT.to_string());
+
+        // Build user message with package data
+        let user_content = if package.data.is_string() {
+            package.data.as_str().unwrap_or("").to_string()
+        } else {
+            serde_json::to_string_pretty(&package.data)
+                .unwrap_or_else(|_| package.data.to_string())
+        };
+
+        GeminiRequest {
+            contents: vec![Content {
+                role: Some("user".to_string()),
+                parts: vec![Part { text: user_content }],
+            }],
+            system_instruction: Some(Content {
+                role: None,
+                parts: vec![Part { text: system }],
+            }),
+            generation_config: GenerationConfig {
+                temperature: Some(self.temperature),
+                max_output_tokens: self.max_tokens,
+            },
+        }
+    }
+
+    /// Build the HTTP request, applying the configured timeout
+    fn build_http_request(&self, request: &GeminiRequest) -> CryptexResult<reqwest::Request> {
+        self.client
+            .post(self.endpoint())
+            .header("Content-Type", "application/json")
+            .timeout(self.timeout)
+            .json(request)
+            .build()
+            .map_err(|e| {
+                CryptexError::agitator(format!("Failed to build Gemini request: {}", e.without_url()))
+            })
+    }
+
+    /// Send request to Gemini API
+    async fn send_request(&self, request: &GeminiRequest) -> CryptexResult<GeminiResponse> {
+        let start = std::time::Instant::now();
+
+        let response = self
+            .client
+            .execute(self.build_http_request(request)?)
+            .await
+            .map_err(|e| {
+                // reqwest errors embed the URL - strip it so the key is not leaked
+                let e = e.without_url();
+                tracing::warn!("Failed to send request to Gemini: {}", e);
+                CryptexError::from(e)
+            })?;
+
+        let duration = start.elapsed();
+        tracing::debug!("Gemini API request took {:?}", duration);
+
+        // Check for HTTP errors
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unable to read error response".to_string());
+
+            return Err(crate::status_error("Gemini", status, &error_body));
+        }
+
+        // Parse response
+        let gemini_response: GeminiResponse = response.json().await.map_err(|e| {
+            CryptexError::agitator(format!("Failed to parse Gemini response: {}", e.without_url()))
+        })?;
+
+        Ok(gemini_response)
+    }
+
+    /// Convert Gemini response to TheReply
+    fn convert_response(
+        &self,
+        response: GeminiResponse,
+        inquiry_id: String,
+        duration_ms: u64,
+    ) -> CryptexResult<TheReply> {
+        // Extract candidates[0].content.parts[0].text
+        let candidate = response.candidates.first().ok_or_else(|| {
+            CryptexError::agitator("Gemini response contains no candidates")
+        })?;
+
+        let content = candidate
+            .content
+            .as_ref()
+            .and_then(|c| c.parts.first())
+            .map(|p| p.text.clone())
+            .ok_or_else(|| CryptexError::agitator("Gemini response contains no text content"))?;
+
+        // Determine certainty based on finishReason
+        let certainty = match candidate.finish_reason.as_deref() {
+            Some("STOP") => 0.9,       // Normal completion
+            Some("MAX_TOKENS") => 0.7, // Hit token limit
+            Some("SAFETY") => 0.5,     // Safety filtered
+            _ => 0.6,                  // Unknown or null
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        Ok(TheReply {
+            inquiry_id,
+            content,
+            certainty,
+            agitator_used: AgitatorType::Gemini,
+            the_mind: self.model.clone(),
+            duration_ms,
+            tokens_used: response.usage_metadata.and_then(|u| u.total_token_count),
+            replied_at_ts: now,
+        })
+    }
+}
+
+#[async_trait]
+impl TheAgitator for GeminiAgitator {
+    fn agitator_type(&self) -> AgitatorType {
+        AgitatorType::Gemini
+    }
+
+    fn the_mind(&self) -> &str {
+        &self.model
+    }
+
+    /// The Agitation - Process a package through Gemini
+    ///
+    /// Sends the package to Google's generateContent API and returns
+    /// the AI-generated response as TheReply.
+    async fn the_agitation(&self, package: ThePackage) -> CryptexResult<TheReply> {
+        tracing::debug!("Processing package through Gemini with model: {}", self.model);
+
+        let start = std::time::Instant::now();
+
+        // Build request
+        let request = self.build_request(&package);
+
+        // Send to Gemini
+        let response = self.send_request(&request).await?;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        // Convert to TheReply
+        let inquiry_id = uuid::Uuid::new_v4().to_string();
+        let reply = self.convert_response(response, inquiry_id, duration_ms)?;
+
+        tracing::info!(
+            "Gemini agitation complete - certainty: {:.2}, tokens: {:?}",
+            reply.certainty,
+            reply.tokens_used
+        );
+
+        Ok(reply)
+    }
+
+    /// The Validation - Health check for Gemini API
+    ///
+    /// Sends a minimal test request to verify API connectivity and credentials.
+    async fn the_validation(&self) -> CryptexResult<bool> {
+        tracing::debug!("Validating Gemini agitator");
+
+        // Create a simple test package
+        let test_package = ThePackage {
+            data: serde_json::json!("test"),
+            context: Some("Respond with 'OK' if you can read this.".to_string()),
+        };
+
+        // Try to send request
+        match self.the_agitation(test_package).await {
+            Ok(_) => {
+                tracing::info!("Gemini agitator validation successful");
+                Ok(true)
+            }
+            Err(e) => {
+                tracing::error!("Gemini agitator validation failed: {}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_gemini_awakening_valid() {
+        let result = GeminiAgitator::the_awakening(
+            "test-key-12345".to_string(),
+            "gemini-1.5-pro".to_string(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        let agitator = result.unwrap();
+        assert_eq!(agitator.the_mind(), "gemini-1.5-pro");
+        assert_eq!(agitator.agitator_type(), AgitatorType::Gemini);
+    }
+
+    #[tokio::test]
+    async fn test_gemini_awakening_empty_key() {
+        let result =
+            GeminiAgitator::the_awakening("".to_string(), "gemini-1.5-pro".to_string()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gemini_awakening_empty_model() {
+        let result =
+            GeminiAgitator::the_awakening("test-key".to_string(), "".to_string()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_build_request_with_context() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let agitator = rt
+            .block_on(GeminiAgitator::the_awakening(
+                "test-key".to_string(),
+                "gemini-1.5-pro".to_string(),
+            ))
+            .unwrap()
+            .with_temperature(0.0);
+
+        let package = ThePackage {
+            data: serde_json::json!({"test": "data"}),
+            context: Some("Custom context".to_string()),
+        };
+
+        let request = agitator.build_request(&package);
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["contents"][0]["role"], "user");
+        assert!(json["contents"][0]["parts"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("\"test\""));
+        assert_eq!(json["systemInstruction"]["parts"][0]["text"], "Custom context");
+        assert_eq!(json["generationConfig"]["temperature"], 0.0);
+        assert!(json["generationConfig"].get("maxOutputTokens").is_none());
+
+        assert_eq!(
+            agitator.endpoint(),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-1.5-pro:generateContent?key=test-key"
+        );
+    }
+
+    #[test]
+    fn test_convert_response() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let agitator = rt
+            .block_on(GeminiAgitator::the_awakening(
+                "test-key".to_string(),
+                "gemini-1.5-pro".to_string(),
+            ))
+            .unwrap();
+
+        let response: GeminiResponse = serde_json::from_value(serde_json::json!({
+            "candidates": [{
+                "content": { "role": "model", "parts": [{ "text": "Patch immediately." }] },
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": { "totalTokenCount": 42 }
+        }))
+        .unwrap();
+
+        let reply = agitator
+            .convert_response(response, "inq-1".to_string(), 10)
+            .unwrap();
+
+        assert_eq!(reply.content, "Patch immediately.");
+        assert_eq!(reply.certainty, 0.9);
+        assert_eq!(reply.tokens_used, Some(42));
+        assert_eq!(reply.agitator_used, AgitatorType::Gemini);
+
+        let empty: GeminiResponse = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(agitator.convert_response(empty, "inq-2".to_string(), 10).is_err());
+    }
+}
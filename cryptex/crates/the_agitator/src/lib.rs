@@ -9,6 +9,7 @@
 //!
 //! - **OpenAI** - GPT-4 and other OpenAI models
 //! - **Claude** - Anthropic's Claude models
+//! - **Gemini** - Google's Gemini models
 //! - More to come...
 //!
 //! ## Example
@@ -52,7 +53,63 @@ pub use the_foundation::{
 // These will be implemented in Phase 1, Week 5
 pub mod openai;
 pub mod claude;
+pub mod gemini;
 
 // Re-exports
 pub use openai::OpenAIAgitator;
 pub use claude::ClaudeAgitator;
+pub use gemini::GeminiAgitator;
+
+use reqwest::Client;
+use the_foundation::redact_secrets;
+
+/// Agitators share the crate-wide outbound client builder
+///
+/// Passing clones of one built client to every agitator's `with_client` lets
+/// them reuse connections. Request (read) timeouts are applied per agitator.
+pub use the_foundation::http::{
+    build_http_client, HttpSettings, DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_POOL_IDLE_TIMEOUT_SECS,
+};
+
+/// Classify a non-success provider response
+///
+/// 429 becomes `RateLimited` so callers can back off; anything else is `UpstreamError`.
+/// Keys the provider echoes back (a 401 body often quotes the rejected one) are
+/// masked, since the message ends up in logs and API responses.
+pub(crate) fn status_error(provider: &str, status: reqwest::StatusCode, body: &str) -> CryptexError {
+    let message = format!("{} API error ({}): {}", provider, status, redact_secrets(body));
+
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        CryptexError::rate_limited(message)
+    } else {
+        CryptexError::upstream(message)
+    }
+}
+
+/// HTTP client with the default connect and pool settings
+pub(crate) fn default_http_client() -> CryptexResult<Client> {
+    build_http_client(&HttpSettings::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_error_classification() {
+        let err = status_error("OpenAI", reqwest::StatusCode::TOO_MANY_REQUESTS, "quota");
+        assert!(matches!(err, CryptexError::RateLimited(_)));
+
+        let err = status_error("Claude", reqwest::StatusCode::SERVICE_UNAVAILABLE, "overloaded");
+        assert!(matches!(err, CryptexError::UpstreamError(_)));
+        assert!(err.to_string().contains("Claude API error (503"));
+    }
+
+    #[test]
+    fn test_status_error_masks_echoed_key() {
+        let body = r#"{"error":{"message":"Incorrect API key provided: sk-abcdef1234567890","code":"invalid_api_key"}}"#;
+        let err = status_error("OpenAI", reqwest::StatusCode::UNAUTHORIZED, body);
+        assert!(!err.to_string().contains("sk-abcdef1234567890"));
+        assert!(err.to_string().contains("invalid_api_key"));
+    }
+}
@@ -8,10 +8,13 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 use the_foundation::TheAgitator;
-use the_foundation::{AgitatorType, CryptexError, CryptexResult, ThePackage, TheReply};
+use the_foundation::{
+    AgitatorType, CryptexError, CryptexResult, ThePackage, TheReply, DEFAULT_SYSTEM_PROMPT,
+};
 
 const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_TEMPERATURE: f32 = 0.7;
 
 /// OpenAI Chat Completions API Request
 #[derive(Debug, Serialize)]
@@ -32,6 +35,7 @@ struct Message {
 
 /// OpenAI Chat Completions API Response
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct OpenAIResponse {
     id: String,
     choices: Vec<Choice>,
@@ -53,8 +57,11 @@ struct Usage {
 pub struct OpenAIAgitator {
     api_key: String,
     model: String,
+    api_url: String,
     client: Client,
     timeout: std::time::Duration,
+    temperature: f32,
+    max_tokens: Option<u32>,
 }
 
 impl OpenAIAgitator {
@@ -83,6 +90,14 @@ impl OpenAIAgitator {
     /// }
     /// ```
     pub async fn the_awakening(api_key: String, model: String) -> CryptexResult<Self> {
+        Self::with_client(api_key, model, crate::default_http_client()?)
+    }
+
+    /// Initialize the OpenAI agitator on an existing HTTP client
+    ///
+    /// Pass clones of one client (see [`crate::build_http_client`]) to share a
+    /// connection pool between agitators.
+    pub fn with_client(api_key: String, model: String, client: Client) -> CryptexResult<Self> {
         // Validate API key format
         if api_key.is_empty() {
             return Err(CryptexError::agitator("OpenAI API key cannot be empty"));
@@ -97,19 +112,16 @@ impl OpenAIAgitator {
             return Err(CryptexError::agitator("OpenAI model name cannot be empty"));
         }
 
-        // Create HTTP client with timeout
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .build()
-            .map_err(|e| CryptexError::network(format!("Failed to create HTTP client: {}", e)))?;
-
         tracing::info!("OpenAI agitator awakened with model: {}", model);
 
         Ok(Self {
             api_key,
             model,
+            api_url: OPENAI_API_URL.to_string(),
             client,
             timeout: std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            temperature: DEFAULT_TEMPERATURE,
+            max_tokens: None,
         })
     }
 
@@ -119,6 +131,24 @@ impl OpenAIAgitator {
         self
     }
 
+    /// Send requests to a different endpoint (e.g. a proxy or compatible gateway)
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    /// Set sampling temperature (0.0 for deterministic output)
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Set maximum tokens in the reply
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
     /// Build the request payload for OpenAI API
     fn build_request(&self, package: &ThePackage) -> OpenAIRequest {
         let mut messages = Vec::new();
@@ -133,7 +163,7 @@ impl OpenAIAgitator {
             // Default system prompt for security analysis
             messages.push(Message {
                 role: "system".to_string(),
-                content: "You are a security expert assistant helping to analyze vulnerabilities and security issues. Provide accurate, detailed, and actionable security analysis.".to_string(),
+                content: DEFAULT_SYSTEM_PROMPT.to_string(),
             });
         }
 
@@ -152,25 +182,35 @@ impl OpenAIAgitator {
         OpenAIRequest {
             model: self.model.clone(),
             messages,
-            temperature: Some(0.7),
-            max_tokens: None,
+            temperature: Some(self.temperature),
+            max_tokens: self.max_tokens,
         }
     }
 
+    /// Build the HTTP request, applying the configured timeout
+    fn build_http_request(&self, request: &OpenAIRequest) -> CryptexResult<reqwest::Request> {
+        self.client
+            .post(&self.api_url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .timeout(self.timeout)
+            .json(request)
+            .build()
+            .map_err(|e| CryptexError::agitator(format!("Failed to build OpenAI request: {}", e)))
+    }
+
     /// Send request to OpenAI API
     async fn send_request(&self, request: &OpenAIRequest) -> CryptexResult<OpenAIResponse> {
         let start = std::time::Instant::now();
 
         let response = self
             .client
-            .post(OPENAI_API_URL)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(request)
-            .send()
+            .execute(self.build_http_request(request)?)
             .await
             .map_err(|e| {
-                CryptexError::network(format!("Failed to send request to OpenAI: {}", e))
+                // Keep the transport error typed so timeouts surface as NetworkError
+                tracing::warn!("Failed to send request to OpenAI: {}", e);
+                CryptexError::from(e)
             })?;
 
         let duration = start.elapsed();
@@ -184,10 +224,7 @@ impl OpenAIAgitator {
                 .await
                 .unwrap_or_else(|_| "Unable to read error response".to_string());
 
-            return Err(CryptexError::agitator(format!(
-                "OpenAI API error ({}): {}",
-                status, error_body
-            )));
+            return Err(crate::status_error("OpenAI", status, &error_body));
         }
 
         // Parse response
@@ -381,4 +418,93 @@ mod tests {
         assert_eq!(request.messages[0].role, "system");
         assert!(request.messages[0].content.contains("security expert"));
     }
+
+    #[test]
+    fn test_build_request_configured_sampling() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let agitator = rt
+            .block_on(OpenAIAgitator::the_awakening(
+                "sk-test".to_string(),
+                "gpt-4".to_string(),
+            ))
+            .unwrap();
+
+        let package = ThePackage {
+            data: serde_json::json!("test data"),
+            context: None,
+        };
+
+        // Defaults are unchanged when nothing is configured
+        let request = agitator.build_request(&package);
+        assert_eq!(request.temperature, Some(0.7));
+        assert_eq!(request.max_tokens, None);
+
+        let agitator = agitator.with_temperature(0.0).with_max_tokens(512);
+        let request = agitator.build_request(&package);
+        assert_eq!(request.temperature, Some(0.0));
+        assert_eq!(request.max_tokens, Some(512));
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["temperature"], 0.0);
+        assert_eq!(json["max_tokens"], 512);
+    }
+
+    #[test]
+    fn test_configured_timeout_reaches_request() {
+        let client = crate::build_http_client(&crate::HttpSettings {
+            connect_timeout: std::time::Duration::from_secs(2),
+            pool_idle_timeout: std::time::Duration::from_secs(30),
+            pool_max_idle_per_host: 4,
+            proxy: None,
+        })
+        .unwrap();
+        let agitator = OpenAIAgitator::with_client(
+            "sk-test".to_string(),
+            "gpt-4".to_string(),
+            client,
+        )
+        .unwrap()
+        .with_timeout(5);
+
+        let package = ThePackage {
+            data: serde_json::json!("test data"),
+            context: None,
+        };
+
+        let request = agitator.build_http_request(&agitator.build_request(&package)).unwrap();
+        assert_eq!(request.timeout(), Some(&std::time::Duration::from_secs(5)));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_is_honored() {
+        // A server that accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+
+        let agitator = OpenAIAgitator::the_awakening("sk-test".to_string(), "gpt-4".to_string())
+            .await
+            .unwrap()
+            .with_api_url(format!("http://{}/v1", addr))
+            .with_timeout(1);
+
+        let package = ThePackage {
+            data: serde_json::json!("test data"),
+            context: None,
+        };
+
+        let started = std::time::Instant::now();
+        let err = agitator
+            .send_request(&agitator.build_request(&package))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, CryptexError::NetworkError(_)), "unexpected error: {:?}", err);
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    }
 }
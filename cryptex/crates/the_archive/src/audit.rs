@@ -0,0 +1,151 @@
+//! Audit log
+//!
+//! Traditional name: `AuditTrail`
+//!
+//! Every mutating API operation appends who did what to which object. Entries
+//! are keyed by a sequence number assigned inside the write transaction, so
+//! the log reads back in the order operations happened even when two of them
+//! share a timestamp. Entries are never pruned with scans; the log outlives
+//! the objects it describes.
+
+use chrono::{DateTime, Utc};
+use redb::{ReadableTable, TableError};
+use serde::{Deserialize, Serialize};
+
+use crate::{TheArchive, AUDIT_TABLE};
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Actor recorded when a request carries no authenticated identity
+pub const ANONYMOUS_ACTOR: &str = "anonymous";
+
+/// One audited operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Who performed the operation
+    pub actor: String,
+    /// What was done, e.g. `scan.start`
+    pub action: String,
+    /// The object acted on, e.g. a scan id or CVE ID
+    pub target: String,
+}
+
+impl TheArchive {
+    /// Append an entry to the audit log
+    pub fn record_audit(&self, actor: &str, action: &str, target: &str) -> CryptexResult<AuditEntry> {
+        let write_txn = self.begin_write()?;
+
+        let entry = {
+            let mut table = write_txn.open_table(AUDIT_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open audit table: {}", e))
+            })?;
+
+            let sequence = match table.last().map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read audit log: {}", e))
+            })? {
+                Some((key, _)) => key.value() + 1,
+                None => 0,
+            };
+
+            // Stamped under the write lock, so timestamps never go backwards
+            let entry = AuditEntry {
+                timestamp: Utc::now(),
+                actor: actor.to_string(),
+                action: action.to_string(),
+                target: target.to_string(),
+            };
+            let data = self.format.encode(&entry).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to serialize audit entry: {}", e))
+            })?;
+            table.insert(sequence, data.as_slice()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to insert audit entry: {}", e))
+            })?;
+
+            entry
+        };
+
+        write_txn.commit().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to commit audit entry: {}", e))
+        })?;
+
+        tracing::debug!("Audit: {} {} {}", entry.actor, entry.action, entry.target);
+        Ok(entry)
+    }
+
+    /// Read the audit log, oldest first
+    ///
+    /// Only entries recorded strictly after `since` are returned, at most
+    /// `limit` of them.
+    pub fn audit_log(
+        &self,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> CryptexResult<Vec<AuditEntry>> {
+        let read_txn = self.begin_read()?;
+
+        let table = match read_txn.open_table(AUDIT_TABLE) {
+            Ok(table) => table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(CryptexError::ArchiveError(format!(
+                    "Failed to open audit table: {}",
+                    e
+                )))
+            }
+        };
+
+        let iter = table.iter().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to iterate audit log: {}", e))
+        })?;
+
+        let mut entries = Vec::new();
+        for item in iter {
+            if entries.len() >= limit {
+                break;
+            }
+
+            let (_, value) = item.map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read audit entry: {}", e))
+            })?;
+            let entry: AuditEntry = self.format.decode(value.value()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to deserialize audit entry: {}", e))
+            })?;
+
+            if since.is_none_or(|since| entry.timestamp > since) {
+                entries.push(entry);
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_audit_log_order_since_and_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        assert!(archive.audit_log(None, 10).unwrap().is_empty());
+
+        let first = archive.record_audit("alice", "scan.start", "scan_1").unwrap();
+        archive.record_audit(ANONYMOUS_ACTOR, "scan.end", "scan_1").unwrap();
+        archive.record_audit("alice", "scan.delete", "scan_1").unwrap();
+
+        let actions: Vec<String> = archive
+            .audit_log(None, 10)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect();
+        assert_eq!(actions, ["scan.start", "scan.end", "scan.delete"]);
+
+        assert_eq!(archive.audit_log(None, 2).unwrap().len(), 2);
+        let later = archive.audit_log(Some(first.timestamp), 10).unwrap();
+        assert!(later.iter().all(|entry| entry.timestamp > first.timestamp));
+        assert!(!later.contains(&first));
+    }
+}
@@ -0,0 +1,254 @@
+//! Write batches
+//!
+//! Traditional name: `UnitOfWork`
+//!
+//! Each `store_*` call on the archive commits its own transaction, which is
+//! the right default for scattered writes but makes bulk loads pay one disk
+//! sync per row. A batch keeps one write transaction open across any number
+//! of writes and commits them together. redb admits a single writer, so
+//! other writers wait until the batch commits or is dropped; keep batches to
+//! the duration of a load.
+
+use redb::{ReadableTable, WriteTransaction};
+
+use crate::metrics::TimedWrite;
+use crate::retention::delete_scan_in;
+use crate::{
+    decode_scan_metadata, next_cached_at, put_scan_metadata_in, put_scan_result_in,
+    put_vulnerability_in, ArchiveEvent, ScanMetadata, ScanResult, StoredVulnerability, TheArchive,
+    VulnerabilityScore, SCANS_TABLE,
+};
+use the_foundation::{CryptexError, CryptexResult};
+
+/// An open write transaction collecting writes until `commit`
+///
+/// Dropping a batch without committing rolls every write in it back.
+/// Archive events for the writes are sent only after the commit.
+pub struct ArchiveBatch<'a> {
+    archive: &'a TheArchive,
+    /// `None` once committed
+    write_txn: Option<TimedWrite<'a>>,
+    events: Vec<ArchiveEvent>,
+}
+
+impl TheArchive {
+    /// Start a batch of writes that commit together
+    ///
+    /// Blocks while another write transaction is open.
+    pub fn begin_batch(&self) -> CryptexResult<ArchiveBatch<'_>> {
+        let write_txn = self.begin_write()?;
+
+        Ok(ArchiveBatch {
+            archive: self,
+            write_txn: Some(write_txn),
+            events: Vec::new(),
+        })
+    }
+}
+
+impl ArchiveBatch<'_> {
+    fn txn(&self) -> &WriteTransaction {
+        self.write_txn
+            .as_deref()
+            .expect("the transaction is only taken by commit, which consumes the batch")
+    }
+
+    /// Scan metadata as this batch sees it, queued writes included
+    ///
+    /// Other writers wait for the batch, so metadata read here and stored
+    /// back in the same batch cannot overwrite a concurrent update.
+    pub fn scan_metadata(&self, scan_id: &str) -> CryptexResult<Option<ScanMetadata>> {
+        let table = self.txn().open_table(SCANS_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
+        })?;
+        let data = table.get(scan_id).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to read scan metadata: {}", e))
+        })?;
+
+        data.map(|data| decode_scan_metadata(self.archive.format, data.value())).transpose()
+    }
+
+    /// Queue scan metadata
+    pub fn store_scan_metadata(&mut self, metadata: &ScanMetadata) -> CryptexResult<()> {
+        put_scan_metadata_in(self.txn(), self.archive.format, metadata)?;
+        self.events.push(ArchiveEvent::ScanStored {
+            scan_id: metadata.scan_id.clone(),
+        });
+        Ok(())
+    }
+
+    /// Queue a scan result
+    pub fn store_scan_result(&mut self, scan_id: &str, result: &ScanResult) -> CryptexResult<()> {
+        put_scan_result_in(self.txn(), self.archive.format, scan_id, result)?;
+        self.events.push(ArchiveEvent::ResultStored {
+            scan_id: scan_id.to_string(),
+            cve_id: result.cve_id.clone(),
+            host: result.host.clone(),
+            port: result.port,
+        });
+        Ok(())
+    }
+
+    /// Queue the deletion of a scan and its results, returning how many
+    /// results it had
+    ///
+    /// Lets a scan be replaced atomically: delete and store it again in one batch.
+    pub fn delete_scan(&mut self, scan_id: &str) -> CryptexResult<usize> {
+        let removed = delete_scan_in(self.txn(), self.archive.format, scan_id)?;
+        self.events.push(ArchiveEvent::ScanDeleted {
+            scan_id: scan_id.to_string(),
+        });
+        Ok(removed)
+    }
+
+    /// Queue a vulnerability assessment, cached as of now
+    ///
+    /// The time is taken inside the batch's transaction; see [`next_cached_at`].
+    pub fn store_vulnerability(&mut self, score: &VulnerabilityScore) -> CryptexResult<()> {
+        let cached_at = next_cached_at(self.txn())?;
+        self.put_vulnerability(&StoredVulnerability {
+            cve_id: score.cve_id.clone(),
+            score: score.clone(),
+            cached_at,
+        })
+    }
+
+    /// Queue a stored vulnerability record as-is
+    pub(crate) fn put_vulnerability(&mut self, stored: &StoredVulnerability) -> CryptexResult<()> {
+        put_vulnerability_in(self.txn(), self.archive.format, stored)?;
+        self.events.push(ArchiveEvent::VulnerabilityStored {
+            cve_id: stored.cve_id.clone(),
+        });
+        Ok(())
+    }
+
+    /// Number of writes queued so far
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether nothing has been queued
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Make every queued write durable at once, returning how many there were
+    pub fn commit(mut self) -> CryptexResult<usize> {
+        let write_txn = self.write_txn.take().expect("a batch is committed at most once");
+        write_txn.commit().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to commit batch: {}", e))
+        })?;
+
+        let written = self.events.len();
+        for event in self.events.drain(..) {
+            self.archive.emit(event);
+        }
+        Ok(written)
+    }
+}
+
+impl Drop for ArchiveBatch<'_> {
+    fn drop(&mut self) {
+        if let Some(write_txn) = self.write_txn.take() {
+            if !self.events.is_empty() {
+                tracing::debug!("Rolling back {} uncommitted archive writes", self.events.len());
+            }
+            if let Err(e) = write_txn.abort() {
+                tracing::warn!("Failed to roll back archive batch: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn result(port: u16) -> ScanResult {
+        ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "10.0.0.5".to_string(),
+            port,
+            "1.3.6.1.4.1.25623".to_string(),
+            "Log4Shell".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_batch_commits_all_writes_together() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let mut events = archive.subscribe();
+
+        let mut batch = archive.begin_batch().unwrap();
+        batch
+            .store_scan_metadata(&ScanMetadata::new("scan-001".to_string(), "10.0.0.0/24".to_string()))
+            .unwrap();
+        for port in 1..100 {
+            batch.store_scan_result("scan-001", &result(port)).unwrap();
+        }
+        assert_eq!(batch.len(), 100);
+
+        // Nothing is visible or announced before the commit
+        assert!(events.try_recv().is_err());
+        assert_eq!(batch.commit().unwrap(), 100);
+
+        assert!(archive.get_scan_metadata("scan-001").unwrap().is_some());
+        assert_eq!(archive.get_scan_results("scan-001").unwrap().len(), 99);
+        assert_eq!(events.len(), 100);
+    }
+
+    #[test]
+    fn test_dropped_batch_rolls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let mut events = archive.subscribe();
+
+        {
+            let mut batch = archive.begin_batch().unwrap();
+            batch
+                .store_scan_metadata(&ScanMetadata::new("scan-001".to_string(), "10.0.0.0/24".to_string()))
+                .unwrap();
+            batch.store_scan_result("scan-001", &result(443)).unwrap();
+            batch
+                .store_vulnerability(&VulnerabilityScore::new("CVE-2021-44228".to_string()))
+                .unwrap();
+        }
+
+        assert!(archive.get_scan_metadata("scan-001").unwrap().is_none());
+        assert!(archive.get_scan_results("scan-001").unwrap().is_empty());
+        assert!(archive.get_vulnerability("CVE-2021-44228").unwrap().is_none());
+        assert!(events.try_recv().is_err());
+
+        // The writer is free again
+        archive.store_scan_result("scan-001", &result(443)).unwrap();
+    }
+
+    #[test]
+    fn test_cached_at_moves_forward_in_commit_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+
+        // An entry stamped ahead of the clock, as after a clock step back
+        let ahead = Utc::now() + chrono::Duration::hours(1);
+        archive
+            .put_vulnerability(&StoredVulnerability {
+                cve_id: "CVE-2021-44228".to_string(),
+                score: VulnerabilityScore::new("CVE-2021-44228".to_string()),
+                cached_at: ahead,
+            })
+            .unwrap();
+
+        let mut batch = archive.begin_batch().unwrap();
+        batch.store_vulnerability(&VulnerabilityScore::new("CVE-2023-4966".to_string())).unwrap();
+        batch.store_vulnerability(&VulnerabilityScore::new("CVE-2024-3400".to_string())).unwrap();
+        batch.commit().unwrap();
+
+        let first = archive.get_vulnerability("CVE-2023-4966").unwrap().unwrap();
+        let second = archive.get_vulnerability("CVE-2024-3400").unwrap().unwrap();
+        assert!(first.cached_at > ahead);
+        assert!(second.cached_at > first.cached_at);
+    }
+}
@@ -0,0 +1,303 @@
+//! Portable scan bundles
+//!
+//! Traditional name: `ScanExport`
+//!
+//! A bundle is one JSON document holding a scan's metadata, its results and
+//! the cached assessment of every CVE those results reference, so a finished
+//! engagement can be handed over as a single file and loaded into another
+//! archive. Bundles carry a version number; unknown fields are ignored on
+//! import, and a bundle newer than this build understands is rejected rather
+//! than half-read.
+
+use chrono::{DateTime, Utc};
+use redb::ReadableTable;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::{
+    decode_vulnerability, index_scan_start_in, put_vulnerability_in, scan_result_key, ArchiveEvent,
+    ArchiveQuery,
+    ScanMetadata, ScanResult, StoredScanResult, StoredVulnerability, TheArchive, SCANS_TABLE,
+    SCAN_RESULTS_TABLE, VULNERABILITIES_TABLE,
+};
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Bundle version written by this build, and the newest it can import
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// One scan with everything needed to reproduce it in another archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub metadata: ScanMetadata,
+    pub results: Vec<ScanResult>,
+    /// Cached assessments of the CVEs referenced by `results`
+    pub assessments: Vec<StoredVulnerability>,
+}
+
+impl ArchiveQuery {
+    /// Export a scan as a pretty-printed JSON bundle
+    pub fn export_scan_bundle(&self, scan_id: &str) -> CryptexResult<String> {
+        let metadata = self
+            .archive
+            .get_scan_metadata(scan_id)?
+            .ok_or_else(|| CryptexError::not_found(format!("Scan {} not found", scan_id)))?;
+        let results = self.archive.get_scan_results(scan_id)?;
+
+        let cve_ids: BTreeSet<&str> = results.iter().map(|r| r.cve_id.as_str()).collect();
+        let mut assessments = Vec::new();
+        for cve_id in cve_ids {
+            if let Some(stored) = self.archive.get_vulnerability(cve_id)? {
+                assessments.push(stored);
+            }
+        }
+
+        let bundle = ScanBundle {
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            metadata,
+            results,
+            assessments,
+        };
+
+        serde_json::to_string_pretty(&bundle)
+            .map_err(|e| CryptexError::validation(format!("JSON serialization failed: {}", e)))
+    }
+}
+
+impl TheArchive {
+    /// Load a bundle produced by `ArchiveQuery::export_scan_bundle`
+    ///
+    /// Everything is written in one transaction, so a bundle is imported
+    /// whole or not at all. Importing a scan id that already exists is
+    /// rejected, and so is an id a scan could not have been started with.
+    /// An assessment only replaces an existing one cached earlier.
+    /// Returns the imported scan id.
+    pub fn import_scan_bundle(&self, json: &str) -> CryptexResult<String> {
+        let bundle: ScanBundle = serde_json::from_str(json)
+            .map_err(|e| CryptexError::validation(format!("Invalid scan bundle: {}", e)))?;
+
+        if bundle.version > BUNDLE_VERSION {
+            return Err(CryptexError::validation(format!(
+                "Scan bundle version {} is newer than the supported version {}",
+                bundle.version, BUNDLE_VERSION
+            )));
+        }
+
+        // Result keys are `scan_id:cve:host:port`; an id holding `:` would
+        // read as a prefix of another scan's keys
+        the_infiltrator::validate_scan_id(&bundle.metadata.scan_id)?;
+
+        let scan_id = bundle.metadata.scan_id.clone();
+        let mut events = vec![ArchiveEvent::ScanStored {
+            scan_id: scan_id.clone(),
+        }];
+
+        let write_txn = self.begin_write()?;
+
+        {
+            let mut scans = write_txn.open_table(SCANS_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
+            })?;
+
+            let exists = scans
+                .get(scan_id.as_str())
+                .map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to read scan metadata: {}", e))
+                })?
+                .is_some();
+            if exists {
+                return Err(CryptexError::validation(format!(
+                    "Scan {} already exists",
+                    scan_id
+                )));
+            }
+
+            let data = self.format.encode(&bundle.metadata).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to serialize scan metadata: {}", e))
+            })?;
+            scans.insert(scan_id.as_str(), data.as_slice()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to insert scan metadata: {}", e))
+            })?;
+            index_scan_start_in(&write_txn, None, &bundle.metadata)?;
+        }
+
+        {
+            let mut table = write_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+            })?;
+
+            for result in bundle.results {
+                let key = scan_result_key(&scan_id, &result);
+                events.push(ArchiveEvent::ResultStored {
+                    scan_id: scan_id.clone(),
+                    cve_id: result.cve_id.clone(),
+                    host: result.host.clone(),
+                    port: result.port,
+                });
+                let stored = StoredScanResult {
+                    scan_id: scan_id.clone(),
+                    result,
+                };
+                let data = self.format.encode(&stored).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to serialize scan result: {}", e))
+                })?;
+                table.insert(key.as_str(), data.as_slice()).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to insert scan result: {}", e))
+                })?;
+            }
+        }
+
+        for stored in &bundle.assessments {
+            let newer_exists = {
+                let table = write_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+                })?;
+                let existing = table.get(stored.cve_id.as_str()).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to read vulnerability: {}", e))
+                })?;
+                match existing {
+                    Some(data) => {
+                        decode_vulnerability(self.format, data.value())?.cached_at
+                            >= stored.cached_at
+                    }
+                    None => false,
+                }
+            };
+
+            if !newer_exists {
+                put_vulnerability_in(&write_txn, self.format, stored)?;
+                events.push(ArchiveEvent::VulnerabilityStored {
+                    cve_id: stored.cve_id.clone(),
+                });
+            }
+        }
+
+        write_txn.commit().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to commit scan bundle: {}", e))
+        })?;
+
+        tracing::info!("Imported scan bundle for {}", scan_id);
+        for event in events {
+            self.emit(event);
+        }
+        Ok(scan_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VulnerabilityScore;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn result(cve_id: &str, host: &str, port: u16) -> ScanResult {
+        ScanResult::new(
+            cve_id.to_string(),
+            host.to_string(),
+            port,
+            "https".to_string(),
+            format!("{} on {}", cve_id, host),
+        )
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let source_dir = TempDir::new().unwrap();
+        let source = Arc::new(TheArchive::the_awakening(source_dir.path().join("source.db")).unwrap());
+
+        let mut metadata = ScanMetadata::new("engagement_1".to_string(), "10.0.0.0/24".to_string());
+        metadata.total_vulnerabilities = 3;
+        source.store_scan_metadata(&metadata).unwrap();
+        for result in [
+            result("CVE-2021-44228", "10.0.0.5", 443),
+            result("CVE-2021-44228", "10.0.0.6", 8443),
+            result("CVE-2023-4966", "10.0.0.7", 443),
+        ] {
+            source.store_scan_result("engagement_1", &result).unwrap();
+        }
+        source.store_vulnerability(&VulnerabilityScore::new("CVE-2021-44228".to_string())).unwrap();
+        source.store_vulnerability(&VulnerabilityScore::new("CVE-2023-4966".to_string())).unwrap();
+        // Not referenced by the scan, so not bundled
+        source.store_vulnerability(&VulnerabilityScore::new("CVE-2024-3400".to_string())).unwrap();
+
+        let json = ArchiveQuery::from_archive(source.clone())
+            .export_scan_bundle("engagement_1")
+            .unwrap();
+        let bundle: ScanBundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert_eq!(bundle.results.len(), 3);
+        assert_eq!(bundle.assessments.len(), 2);
+
+        let target_dir = TempDir::new().unwrap();
+        let target = TheArchive::the_awakening(target_dir.path().join("target.db")).unwrap();
+        assert_eq!(target.import_scan_bundle(&json).unwrap(), "engagement_1");
+
+        let imported = target.get_scan_metadata("engagement_1").unwrap().unwrap();
+        assert_eq!(
+            serde_json::to_value(&imported).unwrap(),
+            serde_json::to_value(&metadata).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(target.get_scan_results("engagement_1").unwrap()).unwrap(),
+            serde_json::to_value(source.get_scan_results("engagement_1").unwrap()).unwrap()
+        );
+        for cve_id in ["CVE-2021-44228", "CVE-2023-4966"] {
+            assert_eq!(
+                serde_json::to_value(target.get_vulnerability(cve_id).unwrap()).unwrap(),
+                serde_json::to_value(source.get_vulnerability(cve_id).unwrap()).unwrap()
+            );
+        }
+        assert!(target.get_vulnerability("CVE-2024-3400").unwrap().is_none());
+        assert!(target.verify().unwrap().is_clean());
+
+        // A second import of the same scan is refused and changes nothing
+        let err = target.import_scan_bundle(&json).unwrap_err();
+        assert!(matches!(err, CryptexError::ValidationError(_)));
+        assert_eq!(target.get_scan_results("engagement_1").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_newer_bundle_version_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+
+        let bundle = ScanBundle {
+            version: BUNDLE_VERSION + 1,
+            exported_at: Utc::now(),
+            metadata: ScanMetadata::new("future".to_string(), "10.0.0.1".to_string()),
+            results: Vec::new(),
+            assessments: Vec::new(),
+        };
+        let json = serde_json::to_string(&bundle).unwrap();
+
+        assert!(matches!(
+            archive.import_scan_bundle(&json),
+            Err(CryptexError::ValidationError(_))
+        ));
+        assert!(archive.get_scan_metadata("future").unwrap().is_none());
+
+        // An id that would overlap another scan's result keys is refused
+        let bundle = ScanBundle {
+            version: BUNDLE_VERSION,
+            metadata: ScanMetadata::new(
+                "scan_1:CVE-2021-44228".to_string(),
+                "10.0.0.1".to_string(),
+            ),
+            ..bundle
+        };
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(matches!(
+            archive.import_scan_bundle(&json),
+            Err(CryptexError::ValidationError(_))
+        ));
+        assert!(archive.list_scans().unwrap().is_empty());
+
+        let err = ArchiveQuery::from_archive(Arc::new(archive))
+            .export_scan_bundle("missing")
+            .unwrap_err();
+        assert!(matches!(err, CryptexError::NotFound(_)));
+    }
+}
@@ -0,0 +1,114 @@
+//! Archive events
+//!
+//! Traditional name: `ChangeFeed`
+//!
+//! Every committed write is announced on a broadcast channel, so a UI can
+//! follow the archive live instead of polling it. Events are sent only after
+//! the transaction commits, never for a write that was rolled back. Sending
+//! never waits: a subscriber that falls more than `ARCHIVE_EVENT_CAPACITY`
+//! events behind loses the oldest ones and is told how many it missed, while
+//! writers carry on.
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::TheArchive;
+
+/// Events buffered per subscriber before the oldest are dropped
+pub const ARCHIVE_EVENT_CAPACITY: usize = 256;
+
+/// A committed change to the archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ArchiveEvent {
+    /// Scan metadata was created or updated
+    ScanStored { scan_id: String },
+    /// A scan result was written
+    ResultStored {
+        scan_id: String,
+        cve_id: String,
+        host: String,
+        port: u16,
+    },
+    /// A vulnerability assessment was cached
+    VulnerabilityStored { cve_id: String },
+    /// A scan and its results were removed
+    ScanDeleted { scan_id: String },
+}
+
+impl TheArchive {
+    /// Follow changes to the archive from now on
+    ///
+    /// A receiver that lags too far behind gets `RecvError::Lagged` and then
+    /// continues with the oldest event still buffered.
+    pub fn subscribe(&self) -> broadcast::Receiver<ArchiveEvent> {
+        self.events.subscribe()
+    }
+
+    /// Announce a committed change; having no subscribers is not an error
+    pub(crate) fn emit(&self, event: ArchiveEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScanMetadata;
+    use tempfile::TempDir;
+    use the_infiltrator::ScanResult;
+    use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+    #[test]
+    fn test_storing_a_scan_notifies_subscribers() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let mut events = archive.subscribe();
+
+        archive
+            .store_scan_metadata(&ScanMetadata::new("scan-001".to_string(), "10.0.0.0/24".to_string()))
+            .unwrap();
+        let result = ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "10.0.0.5".to_string(),
+            443,
+            "1.3.6.1.4.1.25623".to_string(),
+            "Log4Shell".to_string(),
+        );
+        archive.store_scan_result("scan-001", &result).unwrap();
+        archive.delete_scan("scan-001").unwrap();
+
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ArchiveEvent::ScanStored {
+                scan_id: "scan-001".to_string()
+            }
+        );
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            ArchiveEvent::ResultStored { port: 443, .. }
+        ));
+        assert!(matches!(events.try_recv().unwrap(), ArchiveEvent::ScanDeleted { .. }));
+        assert_eq!(events.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_does_not_block_writers() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let mut events = archive.subscribe();
+
+        for i in 0..ARCHIVE_EVENT_CAPACITY + 10 {
+            let score = the_assessor::VulnerabilityScore::new(format!("CVE-2024-{:04}", i));
+            archive.store_vulnerability(&score).unwrap();
+        }
+
+        assert_eq!(events.recv().await, Err(RecvError::Lagged(10)));
+        assert_eq!(
+            events.recv().await.unwrap(),
+            ArchiveEvent::VulnerabilityStored {
+                cve_id: "CVE-2024-0010".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Export of archived findings
+//!
+//! Serializes stored scan results as JSON, NDJSON, CSV or SARIF 2.1.0 so
+//! they can be handed to ticketing systems, spreadsheets or code-scanning
+//! dashboards.
+//!
+//! All but SARIF can also be written one finding at a time through
+//! [`StreamingExport`], which is how exports to files avoid holding the
+//! whole payload in memory.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io::{self, Write};
+
+use crate::StoredScanResult;
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Export Format - Output formats for archived findings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    Csv,
+    Sarif,
+}
+
+impl ExportFormat {
+    /// Parse a format name, case-insensitively
+    pub fn parse(format: &str) -> CryptexResult<Self> {
+        match format.to_ascii_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "ndjson" => Ok(ExportFormat::Ndjson),
+            "csv" => Ok(ExportFormat::Csv),
+            "sarif" => Ok(ExportFormat::Sarif),
+            other => Err(CryptexError::validation(format!(
+                "Unsupported export format: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Column order for CSV exports
+pub const CSV_HEADER: &str = "scan_id,cve_id,host,port,severity,cvss_base_score,is_kev,description";
+
+/// Export findings in the requested format
+pub fn export_findings(findings: &[StoredScanResult], format: ExportFormat) -> CryptexResult<String> {
+    match format {
+        ExportFormat::Json => export_json(findings),
+        ExportFormat::Ndjson => export_ndjson(findings),
+        ExportFormat::Csv => Ok(export_csv(findings)),
+        ExportFormat::Sarif => export_sarif(findings),
+    }
+}
+
+/// Export findings as a pretty-printed JSON array
+pub fn export_json(findings: &[StoredScanResult]) -> CryptexResult<String> {
+    serde_json::to_string_pretty(findings)
+        .map_err(|e| CryptexError::validation(format!("JSON serialization failed: {}", e)))
+}
+
+/// Export findings as newline-delimited JSON, one finding per line
+pub fn export_ndjson(findings: &[StoredScanResult]) -> CryptexResult<String> {
+    let mut ndjson = String::new();
+    for finding in findings {
+        ndjson.push_str(&serde_json::to_string(finding).map_err(|e| {
+            CryptexError::validation(format!("JSON serialization failed: {}", e))
+        })?);
+        ndjson.push('\n');
+    }
+    Ok(ndjson)
+}
+
+/// Export findings as CSV with a header row
+pub fn export_csv(findings: &[StoredScanResult]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+
+    for finding in findings {
+        csv.push_str(&csv_row(finding));
+    }
+
+    csv
+}
+
+/// One finding as a CSV line, newline included
+fn csv_row(finding: &StoredScanResult) -> String {
+    let result = &finding.result;
+    let severity = result
+        .vulnerability_score
+        .as_ref()
+        .map(|s| s.severity().as_str())
+        .unwrap_or("Unknown");
+
+    format!(
+        "{},{},{},{},{},{},{},{}\n",
+        csv_field(&finding.scan_id),
+        csv_field(&result.cve_id),
+        csv_field(&result.host),
+        result.port,
+        severity,
+        result
+            .cvss_base_score()
+            .map(|s| format!("{:.1}", s))
+            .unwrap_or_default(),
+        result.is_kev(),
+        csv_field(&result.description),
+    )
+}
+
+/// Streaming Export - Writes findings to `out` as they are read
+///
+/// Produces the same documents as the in-memory exports, except that JSON
+/// arrays hold one compact object per line rather than pretty-printed ones.
+/// SARIF lists every rule ahead of the results, so it cannot be streamed.
+pub struct StreamingExport<W: Write> {
+    out: W,
+    format: ExportFormat,
+    rows: usize,
+}
+
+impl<W: Write> StreamingExport<W> {
+    /// Start an export, writing any header the format has
+    pub fn begin(mut out: W, format: ExportFormat) -> CryptexResult<Self> {
+        match format {
+            ExportFormat::Sarif => {
+                return Err(CryptexError::validation("SARIF exports cannot be streamed"))
+            }
+            ExportFormat::Json => out.write_all(b"[\n").map_err(write_error)?,
+            ExportFormat::Csv => writeln!(out, "{}", CSV_HEADER).map_err(write_error)?,
+            ExportFormat::Ndjson => {}
+        }
+        Ok(Self { out, format, rows: 0 })
+    }
+
+    /// Append one finding
+    pub fn push(&mut self, finding: &StoredScanResult) -> CryptexResult<()> {
+        match self.format {
+            ExportFormat::Csv => {
+                self.out.write_all(csv_row(finding).as_bytes()).map_err(write_error)?
+            }
+            _ => {
+                if self.format == ExportFormat::Json && self.rows > 0 {
+                    self.out.write_all(b",\n").map_err(write_error)?;
+                }
+                serde_json::to_writer(&mut self.out, finding).map_err(|e| {
+                    CryptexError::validation(format!("JSON serialization failed: {}", e))
+                })?;
+                if self.format == ExportFormat::Ndjson {
+                    self.out.write_all(b"\n").map_err(write_error)?;
+                }
+            }
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Findings written so far
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Close the document, returning the writer and how many findings it holds
+    pub fn finish(mut self) -> CryptexResult<(W, usize)> {
+        if self.format == ExportFormat::Json {
+            self.out.write_all(b"\n]\n").map_err(write_error)?;
+        }
+        Ok((self.out, self.rows))
+    }
+}
+
+fn write_error(e: io::Error) -> CryptexError {
+    CryptexError::ArchiveError(format!("Failed to write export: {}", e))
+}
+
+/// Export findings as a SARIF 2.1.0 log
+pub fn export_sarif(findings: &[StoredScanResult]) -> CryptexResult<String> {
+    let mut rule_ids: Vec<&str> = findings.iter().map(|f| f.result.cve_id.as_str()).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+
+    let rules: Vec<serde_json::Value> = rule_ids
+        .iter()
+        .map(|cve_id| {
+            json!({
+                "id": cve_id,
+                "helpUri": format!("https://nvd.nist.gov/vuln/detail/{}", cve_id),
+            })
+        })
+        .collect();
+
+    let results: Vec<serde_json::Value> = findings
+        .iter()
+        .map(|finding| {
+            let result = &finding.result;
+            let level = match result.vulnerability_score.as_ref().map(|s| s.severity()) {
+                Some(the_assessor::CvssSeverity::Critical) | Some(the_assessor::CvssSeverity::High) => "error",
+                Some(the_assessor::CvssSeverity::Medium) => "warning",
+                _ => "note",
+            };
+
+            json!({
+                "ruleId": result.cve_id,
+                "level": level,
+                "message": { "text": result.description },
+                "locations": [{
+                    "logicalLocations": [{
+                        "name": format!("{}:{}", result.host, result.port),
+                        "kind": "host",
+                    }]
+                }],
+                "properties": {
+                    "scan_id": finding.scan_id,
+                    "cvss_base_score": result.cvss_base_score(),
+                    "is_kev": result.is_kev(),
+                },
+            })
+        })
+        .collect();
+
+    let sarif = json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "CRYPTEX",
+                    "informationUri": "https://github.com/Ununp3ntium115/build_openvas",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    });
+
+    serde_json::to_string_pretty(&sarif)
+        .map_err(|e| CryptexError::validation(format!("SARIF serialization failed: {}", e)))
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScanResult;
+
+    fn finding(cve_id: &str, description: &str) -> StoredScanResult {
+        StoredScanResult {
+            scan_id: "scan_1".to_string(),
+            result: ScanResult::new(
+                cve_id.to_string(),
+                "10.0.0.5".to_string(),
+                443,
+                "unknown".to_string(),
+                description.to_string(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_export_format_parse() {
+        assert_eq!(ExportFormat::parse("CSV").unwrap(), ExportFormat::Csv);
+        assert_eq!(ExportFormat::parse("sarif").unwrap(), ExportFormat::Sarif);
+        assert!(ExportFormat::parse("xlsx").is_err());
+    }
+
+    #[test]
+    fn test_export_csv_escapes_fields() {
+        let csv = export_csv(&[finding("CVE-2024-0001", "Overflow, \"remote\"")]);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "scan_1,CVE-2024-0001,10.0.0.5,443,Unknown,,false,\"Overflow, \"\"remote\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_export_sarif_structure() {
+        let sarif = export_sarif(&[
+            finding("CVE-2024-0001", "first"),
+            finding("CVE-2024-0001", "second"),
+        ])
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+
+        assert_eq!(value["version"], "2.1.0");
+        assert_eq!(value["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+        assert_eq!(value["runs"][0]["results"].as_array().unwrap().len(), 2);
+        assert_eq!(value["runs"][0]["results"][0]["level"], "note");
+    }
+}
@@ -0,0 +1,138 @@
+//! Value encoding
+//!
+//! Traditional name: `Codec`
+//!
+//! Rows are CBOR by default: compact, but opaque to anything outside this
+//! crate. A development archive can be created with JSON values instead so
+//! it can be inspected with generic database tools. The format is chosen
+//! once, when the archive is created, and recorded in the meta table; every
+//! later open uses the recorded format, so one database never mixes the two.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use the_foundation::{CryptexError, CryptexResult};
+
+/// How stored values are serialized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueFormat {
+    #[default]
+    Cbor,
+    Json,
+}
+
+impl ValueFormat {
+    /// Name used in the meta marker and in configuration
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ValueFormat::Cbor => "cbor",
+            ValueFormat::Json => "json",
+        }
+    }
+
+    /// Parse a format name, case-insensitively
+    pub fn parse(format: &str) -> CryptexResult<Self> {
+        match format.to_ascii_lowercase().as_str() {
+            "cbor" => Ok(ValueFormat::Cbor),
+            "json" => Ok(ValueFormat::Json),
+            other => Err(CryptexError::validation(format!(
+                "Unknown archive value format '{}' (expected cbor or json)",
+                other
+            ))),
+        }
+    }
+
+    /// Serialize a value; the error is left as text for the caller's message
+    pub(crate) fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            ValueFormat::Cbor => serde_cbor::to_vec(value).map_err(|e| e.to_string()),
+            ValueFormat::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Deserialize a value; the error is left as text for the caller's message
+    pub(crate) fn decode<T: DeserializeOwned>(self, data: &[u8]) -> Result<T, String> {
+        match self {
+            ValueFormat::Cbor => serde_cbor::from_slice(data).map_err(|e| e.to_string()),
+            ValueFormat::Json => serde_json::from_slice(data).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ValueFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScanMetadata, ScanResult, TheArchive, VulnerabilityScore, SCANS_TABLE};
+    use tempfile::TempDir;
+
+    fn round_trip(format: ValueFormat) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("format.db");
+
+        {
+            let archive = TheArchive::the_awakening_with_format(path.clone(), format).unwrap();
+            archive
+                .store_scan_metadata(&ScanMetadata::new("scan_1".to_string(), "10.0.0.5".to_string()))
+                .unwrap();
+            let result = ScanResult::new(
+                "CVE-2021-44228".to_string(),
+                "10.0.0.5".to_string(),
+                443,
+                "https".to_string(),
+                "Log4Shell".to_string(),
+            );
+            archive.store_scan_result("scan_1", &result).unwrap();
+            archive
+                .store_vulnerability(&VulnerabilityScore::new("CVE-2021-44228".to_string()))
+                .unwrap();
+        }
+
+        // Reopening without asking for a format picks up the recorded one
+        let archive = TheArchive::the_awakening(path).unwrap();
+        assert_eq!(archive.value_format(), format);
+        assert_eq!(archive.get_scan_metadata("scan_1").unwrap().unwrap().target, "10.0.0.5");
+        assert_eq!(archive.get_scan_results("scan_1").unwrap()[0].port, 443);
+        assert!(archive.get_vulnerability("CVE-2021-44228").unwrap().is_some());
+        assert!(archive.verify().unwrap().is_clean());
+
+        let read_txn = archive.db.begin_read().unwrap();
+        let scans = read_txn.open_table(SCANS_TABLE).unwrap();
+        let raw = scans.get("scan_1").unwrap().unwrap();
+        let is_json = serde_json::from_slice::<serde_json::Value>(raw.value()).is_ok();
+        assert_eq!(is_json, format == ValueFormat::Json);
+    }
+
+    #[test]
+    fn test_cbor_round_trip() {
+        round_trip(ValueFormat::Cbor);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        round_trip(ValueFormat::Json);
+    }
+
+    #[test]
+    fn test_format_mismatch_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("json.db");
+        drop(TheArchive::the_awakening_with_format(path.clone(), ValueFormat::Json).unwrap());
+
+        let mismatch = TheArchive::the_awakening_with_format(path.clone(), ValueFormat::Cbor);
+        assert!(matches!(mismatch, Err(CryptexError::ValidationError(_))));
+        assert_eq!(
+            TheArchive::open_existing(path).unwrap().value_format(),
+            ValueFormat::Json
+        );
+
+        assert_eq!(ValueFormat::parse("JSON").unwrap(), ValueFormat::Json);
+        assert!(ValueFormat::parse("yaml").is_err());
+    }
+}
@@ -0,0 +1,416 @@
+//! Resumable bulk import
+//!
+//! Traditional name: `BulkLoader`
+//!
+//! Seeding the archive from a full NVD feed takes long enough that it will
+//! sometimes be interrupted. Records are written in chunks, and each chunk's
+//! transaction also advances a cursor in the meta table: how far into the
+//! feed the import got and the last CVE written. The cursor can never claim
+//! rows that were not committed, or the reverse. Running the same import again
+//! reads past what the cursor covers, checks that it ends on the cursor's
+//! CVE and carries on; finishing clears the cursor.
+//!
+//! CVEs already in the archive are left as they are, so an import never
+//! replaces an assessment fetched since.
+
+use chrono::{DateTime, Utc};
+use redb::{ReadableTable, TableError, WriteTransaction};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    next_cached_at, put_vulnerability_in, ArchiveEvent, StoredVulnerability, TheArchive,
+    VulnerabilityScore, META_TABLE, VULNERABILITIES_TABLE,
+};
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Records written per transaction unless a chunk size is given
+pub const DEFAULT_IMPORT_CHUNK: usize = 500;
+
+/// Meta table key holding the cursor of an unfinished import
+const IMPORT_PROGRESS_KEY: &str = "import_progress";
+
+/// Cursor of an unfinished import
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportProgress {
+    /// Identifies the feed, e.g. its file name; progress on another feed is discarded
+    pub feed: String,
+    /// Feed records consumed so far, inserted or skipped
+    pub offset: usize,
+    /// Last CVE consumed
+    pub last_cve_id: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Outcome of `TheArchive::import_vulnerabilities`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSummary {
+    /// Records skipped because an earlier run already consumed them
+    pub resumed_from: usize,
+    /// Records written by this run
+    pub inserted: usize,
+    /// Records this run found already in the archive
+    pub already_present: usize,
+}
+
+impl TheArchive {
+    /// The cursor of an unfinished import, if there is one
+    pub fn import_progress(&self) -> CryptexResult<Option<ImportProgress>> {
+        let read_txn = self.begin_read()?;
+
+        let table = match read_txn.open_table(META_TABLE) {
+            Ok(table) => table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(e) => {
+                return Err(CryptexError::ArchiveError(format!(
+                    "Failed to open meta table: {}",
+                    e
+                )))
+            }
+        };
+
+        let value = table.get(IMPORT_PROGRESS_KEY).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to read import progress: {}", e))
+        })?;
+        value
+            .map(|v| {
+                serde_cbor::from_slice(v.value()).map_err(|e| {
+                    CryptexError::ArchiveError(format!(
+                        "Failed to deserialize import progress: {}",
+                        e
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Import a feed of assessments in chunks of `DEFAULT_IMPORT_CHUNK`
+    ///
+    /// See [`import_vulnerabilities_in_chunks`](Self::import_vulnerabilities_in_chunks).
+    pub fn import_vulnerabilities<I>(
+        &self,
+        feed: &str,
+        records: I,
+    ) -> CryptexResult<ImportSummary>
+    where
+        I: IntoIterator<Item = CryptexResult<VulnerabilityScore>>,
+    {
+        self.import_vulnerabilities_in_chunks(feed, records, DEFAULT_IMPORT_CHUNK)
+    }
+
+    /// Import a feed of assessments, committing every `chunk_size` records
+    ///
+    /// `records` must yield the feed in the same order on every run. If an
+    /// earlier run of the same `feed` was interrupted, the records it got
+    /// through are skipped. They are still read: an error among them, or a
+    /// last skipped record other than the cursor's CVE, fails the import
+    /// with the cursor left in place. An error from `records` stops the
+    /// import after the last committed chunk, and the next run resumes from
+    /// there.
+    pub fn import_vulnerabilities_in_chunks<I>(
+        &self,
+        feed: &str,
+        records: I,
+        chunk_size: usize,
+    ) -> CryptexResult<ImportSummary>
+    where
+        I: IntoIterator<Item = CryptexResult<VulnerabilityScore>>,
+    {
+        if chunk_size == 0 {
+            return Err(CryptexError::validation("Import chunk size must be positive"));
+        }
+
+        let resume = match self.import_progress()? {
+            Some(progress) if progress.feed == feed => {
+                tracing::info!(
+                    "Resuming import of {} after {} records ({:?})",
+                    feed,
+                    progress.offset,
+                    progress.last_cve_id
+                );
+                progress
+            }
+            stale => {
+                if let Some(stale) = stale {
+                    tracing::warn!(
+                        "Discarding unfinished import of {} to import {}",
+                        stale.feed,
+                        feed
+                    );
+                }
+                ImportProgress {
+                    feed: feed.to_string(),
+                    offset: 0,
+                    last_cve_id: None,
+                    updated_at: Utc::now(),
+                }
+            }
+        };
+
+        let mut summary = ImportSummary {
+            resumed_from: resume.offset,
+            ..Default::default()
+        };
+        let mut records = records.into_iter();
+        skip_imported(&mut records, &resume)?;
+        let mut progress = resume;
+        let mut chunk = Vec::with_capacity(chunk_size);
+
+        for record in records {
+            chunk.push(record?);
+            if chunk.len() == chunk_size {
+                self.commit_import_chunk(&mut progress, &mut chunk, &mut summary)?;
+            }
+        }
+        if !chunk.is_empty() {
+            self.commit_import_chunk(&mut progress, &mut chunk, &mut summary)?;
+        }
+
+        self.clear_import_progress()?;
+        tracing::info!(
+            "Imported {}: {} inserted, {} already present",
+            feed,
+            summary.inserted,
+            summary.already_present
+        );
+
+        Ok(summary)
+    }
+
+    /// Write one chunk and advance the cursor past it in the same transaction
+    fn commit_import_chunk(
+        &self,
+        progress: &mut ImportProgress,
+        chunk: &mut Vec<VulnerabilityScore>,
+        summary: &mut ImportSummary,
+    ) -> CryptexResult<()> {
+        let write_txn = self.begin_write()?;
+
+        let cached_at = next_cached_at(&write_txn)?;
+        let mut inserted = Vec::new();
+        for score in chunk.iter() {
+            if vulnerability_exists_in(&write_txn, &score.cve_id)? {
+                continue;
+            }
+            put_vulnerability_in(
+                &write_txn,
+                self.format,
+                &StoredVulnerability {
+                    cve_id: score.cve_id.clone(),
+                    score: score.clone(),
+                    cached_at,
+                },
+            )?;
+            inserted.push(score.cve_id.clone());
+        }
+
+        let mut next = progress.clone();
+        next.offset += chunk.len();
+        next.last_cve_id = chunk.last().map(|score| score.cve_id.clone());
+        next.updated_at = cached_at;
+        write_progress_in(&write_txn, &next)?;
+
+        write_txn.commit().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to commit import chunk: {}", e))
+        })?;
+
+        summary.inserted += inserted.len();
+        summary.already_present += chunk.len() - inserted.len();
+        for cve_id in inserted {
+            self.emit(ArchiveEvent::VulnerabilityStored { cve_id });
+        }
+        *progress = next;
+        chunk.clear();
+        Ok(())
+    }
+
+    fn clear_import_progress(&self) -> CryptexResult<()> {
+        let write_txn = self.begin_write()?;
+        {
+            let mut table = write_txn.open_table(META_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open meta table: {}", e))
+            })?;
+            table.remove(IMPORT_PROGRESS_KEY).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to clear import progress: {}", e))
+            })?;
+        }
+        write_txn.commit().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to commit import completion: {}", e))
+        })
+    }
+}
+
+/// Read past the records an interrupted run already consumed
+///
+/// The feed must still end that stretch on the cursor's CVE; otherwise it
+/// changed since, and resuming would skip or repeat records.
+fn skip_imported<I>(records: &mut I, progress: &ImportProgress) -> CryptexResult<()>
+where
+    I: Iterator<Item = CryptexResult<VulnerabilityScore>>,
+{
+    let mut last_cve_id = None;
+    for consumed in 0..progress.offset {
+        let Some(record) = records.next() else {
+            return Err(CryptexError::validation(format!(
+                "Feed {} has only {} records, but an interrupted import got through {}; \
+                 import it under another feed name to start over",
+                progress.feed, consumed, progress.offset
+            )));
+        };
+        last_cve_id = Some(record?.cve_id);
+    }
+
+    if last_cve_id != progress.last_cve_id {
+        return Err(CryptexError::validation(format!(
+            "Feed {} changed since its import was interrupted: record {} is {:?}, not {:?}; \
+             import it under another feed name to start over",
+            progress.feed, progress.offset, last_cve_id, progress.last_cve_id
+        )));
+    }
+    Ok(())
+}
+
+fn vulnerability_exists_in(write_txn: &WriteTransaction, cve_id: &str) -> CryptexResult<bool> {
+    let table = write_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+    })?;
+    let existing = table.get(cve_id).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to read vulnerability: {}", e))
+    })?;
+    Ok(existing.is_some())
+}
+
+fn write_progress_in(write_txn: &WriteTransaction, progress: &ImportProgress) -> CryptexResult<()> {
+    let data = serde_cbor::to_vec(progress).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to serialize import progress: {}", e))
+    })?;
+
+    let mut table = write_txn.open_table(META_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open meta table: {}", e))
+    })?;
+    table.insert(IMPORT_PROGRESS_KEY, data.as_slice()).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to store import progress: {}", e))
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn feed(count: usize) -> Vec<VulnerabilityScore> {
+        (0..count)
+            .map(|i| VulnerabilityScore::new(format!("CVE-2024-{:04}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_interrupted_import_resumes_from_cursor() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let records = feed(7);
+
+        // The feed breaks while the second chunk is being read
+        let interrupted = records
+            .iter()
+            .cloned()
+            .map(Ok)
+            .take(4)
+            .chain(std::iter::once(Err(CryptexError::validation("truncated feed"))));
+        assert!(archive
+            .import_vulnerabilities_in_chunks("nvd-2024.json", interrupted, 3)
+            .is_err());
+
+        let progress = archive.import_progress().unwrap().unwrap();
+        assert_eq!(progress.offset, 3);
+        assert_eq!(progress.last_cve_id.as_deref(), Some("CVE-2024-0002"));
+        assert_eq!(archive.list_vulnerabilities().unwrap().len(), 3);
+        let cached_at =
+            |cve_id: &str| archive.get_vulnerability(cve_id).unwrap().unwrap().cached_at;
+        let first_cached_at = cached_at("CVE-2024-0000");
+
+        let summary = archive
+            .import_vulnerabilities_in_chunks("nvd-2024.json", records.into_iter().map(Ok), 3)
+            .unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                resumed_from: 3,
+                inserted: 4,
+                already_present: 0,
+            }
+        );
+        assert_eq!(archive.list_vulnerabilities().unwrap().len(), 7);
+        // Rows from the first run were not written again
+        assert_eq!(cached_at("CVE-2024-0000"), first_cached_at);
+        assert!(archive.import_progress().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_resume_checks_the_skipped_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let records = feed(7);
+
+        let interrupted = records
+            .iter()
+            .cloned()
+            .map(Ok)
+            .take(3)
+            .chain(std::iter::once(Err(CryptexError::validation("truncated feed"))));
+        assert!(archive
+            .import_vulnerabilities_in_chunks("nvd-2024.json", interrupted, 3)
+            .is_err());
+        let cursor = archive.import_progress().unwrap().unwrap();
+
+        // A feed reordered since would skip CVE-2024-0002 and repeat another
+        let mut reordered = records.clone();
+        reordered.swap(2, 5);
+        let err = archive
+            .import_vulnerabilities_in_chunks("nvd-2024.json", reordered.into_iter().map(Ok), 3)
+            .unwrap_err();
+        assert!(err.to_string().contains("changed since"), "{}", err);
+
+        // So would one that got shorter
+        let err = archive
+            .import_vulnerabilities_in_chunks("nvd-2024.json", feed(2).into_iter().map(Ok), 3)
+            .unwrap_err();
+        assert!(err.to_string().contains("has only 2 records"), "{}", err);
+
+        // A bad record among the skipped ones is reported, not passed over
+        let broken = records
+            .iter()
+            .cloned()
+            .map(Ok)
+            .enumerate()
+            .map(|(i, r)| if i == 1 { Err(CryptexError::validation("bad record")) } else { r });
+        let err = archive
+            .import_vulnerabilities_in_chunks("nvd-2024.json", broken, 3)
+            .unwrap_err();
+        assert!(err.to_string().contains("bad record"), "{}", err);
+
+        // None of that moved the cursor or wrote anything
+        assert_eq!(archive.import_progress().unwrap(), Some(cursor));
+        assert_eq!(archive.list_vulnerabilities().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_import_keeps_existing_assessments() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+
+        let mut fresh = VulnerabilityScore::new("CVE-2024-0001".to_string());
+        fresh.description = Some("Assessed before the import".to_string());
+        archive.store_vulnerability(&fresh).unwrap();
+
+        let summary = archive
+            .import_vulnerabilities("nvd-2024.json", feed(3).into_iter().map(Ok))
+            .unwrap();
+        assert_eq!(summary.inserted, 2);
+        assert_eq!(summary.already_present, 1);
+        assert_eq!(
+            archive.get_vulnerability("CVE-2024-0001").unwrap().unwrap().score.description,
+            fresh.description
+        );
+    }
+}
@@ -13,6 +13,18 @@
 //! - **ACID Compliance**: Guaranteed data consistency
 //! - **Thread-Safe**: Concurrent read/write support
 //!
+//! ## One Handle Per Database
+//!
+//! redb takes an exclusive lock on the database file, so a process must open
+//! a given archive exactly once. Open it with [`TheArchive::the_awakening`],
+//! wrap it in an `Arc`, and hand clones of that `Arc` to every component that
+//! needs it - request handlers, [`ArchiveQuery::from_archive`], and so on.
+//! Opening a second `TheArchive` on the same path in the same process fails.
+//!
+//! Processes that only read, such as dashboards, can open the archive with
+//! [`TheArchive::the_awakening_readonly`]: it never creates tables and
+//! refuses every write, though the file lock still applies.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -35,25 +47,327 @@
 //! ```
 
 use chrono::{DateTime, Utc};
-use redb::{Database, ReadableTable, ReadableTableMetadata, TableDefinition};
+use redb::{
+    Database, MultimapTableDefinition, ReadableTable, ReadableTableMetadata, TableDefinition,
+    WriteTransaction,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::Bound;
 use std::path::PathBuf;
-use std::sync::Arc;
-
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+use tokio::sync::broadcast;
+
+pub mod audit;
+pub mod batch;
+pub mod bundle;
+pub mod events;
+pub mod export;
+pub mod format;
+pub mod metrics;
+pub mod import;
+pub mod passphrase;
+pub mod query;
+pub mod retention;
+pub mod verify;
+
+pub use audit::{AuditEntry, ANONYMOUS_ACTOR};
+pub use batch::ArchiveBatch;
+pub use bundle::{ScanBundle, BUNDLE_VERSION};
+pub use events::{ArchiveEvent, ARCHIVE_EVENT_CAPACITY};
+pub use export::{ExportFormat, StreamingExport};
+pub use format::ValueFormat;
+pub use metrics::ArchiveMetrics;
+use metrics::{TimedRead, TimedWrite, TxnTimings};
+pub use import::{ImportProgress, ImportSummary, DEFAULT_IMPORT_CHUNK};
+pub use passphrase::{ArchiveKey, KdfParams, PassphraseProvider};
+pub use query::{
+    ActivityEvent, ArchiveQuery, ExportedFile, KevCoverage, Page, QueryFilters, ScanDelta,
+    ScanFilters, VulnerabilitySort,
+};
+pub use retention::{retain_days_from_env, retention_cutoff, spawn_retention, PruneStats};
+pub use verify::{VerifyFailure, VerifyReport};
 pub use the_foundation::{CryptexError, CryptexResult};
 pub use the_assessor::VulnerabilityScore;
+use the_assessor::{AssessmentStore, CvssSeverity};
 pub use the_infiltrator::ScanResult;
+use the_infiltrator::{CheckpointStore, ScanCheckpoint, ScanOptions, ScanReport, ScanStatus};
 
 // Table definitions
 const SCANS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("scans");
 const VULNERABILITIES_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("vulnerabilities");
 const SCAN_RESULTS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("scan_results");
+/// Archive-level settings such as the value format and the passphrase KDF salt
+const META_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("meta");
+/// Secondary index: `cached_at_key` -> CVE ID, ordered by assessment time
+const VULNERABILITIES_BY_TIME_TABLE: TableDefinition<&str, &str> =
+    TableDefinition::new("vulnerabilities_by_cached_at");
+/// Secondary index: severity name (`critical`, ...) -> CVE IDs assessed at that severity
+const VULNERABILITIES_BY_SEVERITY_TABLE: MultimapTableDefinition<&str, &str> =
+    MultimapTableDefinition::new("vulnerabilities_by_severity");
+/// Secondary index: CVE IDs of stored assessments in the KEV catalog
+const KEV_VULNERABILITIES_TABLE: TableDefinition<&str, ()> =
+    TableDefinition::new("kev_vulnerabilities");
+/// Secondary index: `scan_start_key` -> scan ID, most recently started first
+const SCANS_BY_START_TABLE: TableDefinition<&str, &str> = TableDefinition::new("scans_by_started_at");
+/// Audit log: sequence number -> `AuditEntry`, in the order operations happened
+const AUDIT_TABLE: TableDefinition<u64, &[u8]> = TableDefinition::new("audit");
+
+/// Sort key for the cached-at index
+///
+/// Zero-padded nanoseconds sort lexically in time order; the CVE ID breaks ties.
+fn cached_at_key(cached_at: &DateTime<Utc>, cve_id: &str) -> String {
+    format!(
+        "{:020}|{}",
+        cached_at.timestamp_nanos_opt().unwrap_or(0).max(0),
+        cve_id
+    )
+}
+
+/// Sort key for the scan start index
+///
+/// The timestamp is subtracted from `i64::MAX`, so ascending key order is
+/// newest first; the scan ID breaks ties.
+pub(crate) fn scan_start_key(started_at: &DateTime<Utc>, scan_id: &str) -> String {
+    format!(
+        "{:020}|{}",
+        i64::MAX - started_at.timestamp_nanos_opt().unwrap_or(0).max(0),
+        scan_id
+    )
+}
+
+/// Severity index key, the lower-case severity name
+fn severity_key(severity: CvssSeverity) -> String {
+    severity.as_str().to_ascii_lowercase()
+}
+
+fn index_error(e: impl std::fmt::Display) -> CryptexError {
+    CryptexError::ArchiveError(format!("Failed to update vulnerability index: {}", e))
+}
+
+/// Add a stored vulnerability to the severity and KEV indexes
+fn index_vulnerability_in(
+    write_txn: &WriteTransaction,
+    stored: &StoredVulnerability,
+) -> CryptexResult<()> {
+    let mut by_severity = write_txn
+        .open_multimap_table(VULNERABILITIES_BY_SEVERITY_TABLE)
+        .map_err(index_error)?;
+    by_severity
+        .insert(severity_key(stored.score.severity()).as_str(), stored.cve_id.as_str())
+        .map_err(index_error)?;
+
+    if stored.score.is_kev() {
+        let mut kev = write_txn
+            .open_table(KEV_VULNERABILITIES_TABLE)
+            .map_err(index_error)?;
+        kev.insert(stored.cve_id.as_str(), ()).map_err(index_error)?;
+    }
+    Ok(())
+}
+
+/// Remove a stored vulnerability from the severity and KEV indexes
+fn unindex_vulnerability_in(
+    write_txn: &WriteTransaction,
+    stored: &StoredVulnerability,
+) -> CryptexResult<()> {
+    let mut by_severity = write_txn
+        .open_multimap_table(VULNERABILITIES_BY_SEVERITY_TABLE)
+        .map_err(index_error)?;
+    by_severity
+        .remove(severity_key(stored.score.severity()).as_str(), stored.cve_id.as_str())
+        .map_err(index_error)?;
+
+    let mut kev = write_txn.open_table(KEV_VULNERABILITIES_TABLE).map_err(index_error)?;
+    kev.remove(stored.cve_id.as_str()).map_err(index_error)?;
+    Ok(())
+}
+
+/// Smallest cached-at index key for assessments cached strictly after `since`
+pub(crate) fn cached_after_key(since: &DateTime<Utc>) -> String {
+    format!("{:020}|", since.timestamp_nanos_opt().unwrap_or(0).max(0) + 1)
+}
+
+/// Key of a scan result: `scan_id:cve_id:host:port`
+pub(crate) fn scan_result_key(scan_id: &str, result: &ScanResult) -> String {
+    format!("{}:{}:{}:{}", scan_id, result.cve_id, result.host, result.port)
+}
+
+/// Cache time for an assessment written inside `write_txn`
+///
+/// Stamped while the write lock is held, and later than every time already
+/// in the index even if the clock stepped back. Writes therefore commit in
+/// `cached_at` order, so a reader that resumes strictly after the newest
+/// time it has seen misses none of them.
+pub(crate) fn next_cached_at(write_txn: &WriteTransaction) -> CryptexResult<DateTime<Utc>> {
+    let index = write_txn.open_table(VULNERABILITIES_BY_TIME_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open vulnerability index: {}", e))
+    })?;
+    let newest = index
+        .last()
+        .map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to read vulnerability index: {}", e))
+        })?
+        .and_then(|(key, _)| key.value().split('|').next()?.parse::<i64>().ok());
+
+    let now = Utc::now();
+    Ok(match newest {
+        Some(newest) if newest >= now.timestamp_nanos_opt().unwrap_or(i64::MAX) => {
+            DateTime::from_timestamp_nanos(newest.saturating_add(1))
+        }
+        _ => now,
+    })
+}
+
+/// Write a stored vulnerability inside `write_txn`, keeping the cached-at index in step
+pub(crate) fn put_vulnerability_in(
+    write_txn: &WriteTransaction,
+    format: ValueFormat,
+    stored: &StoredVulnerability,
+) -> CryptexResult<()> {
+    let mut table = write_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+    })?;
+
+    let mut index = write_txn.open_table(VULNERABILITIES_BY_TIME_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open vulnerability index: {}", e))
+    })?;
+
+    let data = format.encode(stored).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to serialize vulnerability: {}", e))
+    })?;
+
+    let previous = table
+        .insert(stored.cve_id.as_str(), data.as_slice())
+        .map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to insert vulnerability: {}", e))
+        })?
+        .map(|old| decode_vulnerability(format, old.value()))
+        .transpose()?;
+
+    // Re-assessment moves the record to its new position in the indexes
+    if let Some(previous) = previous {
+        index
+            .remove(cached_at_key(&previous.cached_at, &previous.cve_id).as_str())
+            .map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to update vulnerability index: {}", e))
+            })?;
+        unindex_vulnerability_in(write_txn, &previous)?;
+    }
+    index
+        .insert(cached_at_key(&stored.cached_at, &stored.cve_id).as_str(), stored.cve_id.as_str())
+        .map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to update vulnerability index: {}", e))
+        })?;
+    index_vulnerability_in(write_txn, stored)?;
+
+    Ok(())
+}
+
+/// Write scan metadata inside `write_txn`, keeping the start index in step
+pub(crate) fn put_scan_metadata_in(
+    write_txn: &WriteTransaction,
+    format: ValueFormat,
+    metadata: &ScanMetadata,
+) -> CryptexResult<()> {
+    let mut table = write_txn.open_table(SCANS_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
+    })?;
+
+    let data = format.encode(metadata).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to serialize scan metadata: {}", e))
+    })?;
+
+    let previous = table
+        .insert(metadata.scan_id.as_str(), data.as_slice())
+        .map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to insert scan metadata: {}", e))
+        })?
+        .map(|old| decode_scan_metadata(format, old.value()))
+        .transpose()?;
+
+    index_scan_start_in(write_txn, previous.map(|previous| previous.started_at), metadata)
+}
+
+/// Point the start index at `metadata`, dropping the entry for its old start time
+pub(crate) fn index_scan_start_in(
+    write_txn: &WriteTransaction,
+    previous_start: Option<DateTime<Utc>>,
+    metadata: &ScanMetadata,
+) -> CryptexResult<()> {
+    let mut index = write_txn.open_table(SCANS_BY_START_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scan index: {}", e))
+    })?;
+
+    if let Some(previous_start) = previous_start {
+        index
+            .remove(scan_start_key(&previous_start, &metadata.scan_id).as_str())
+            .map_err(|e| CryptexError::ArchiveError(format!("Failed to update scan index: {}", e)))?;
+    }
+    index
+        .insert(
+            scan_start_key(&metadata.started_at, &metadata.scan_id).as_str(),
+            metadata.scan_id.as_str(),
+        )
+        .map_err(|e| CryptexError::ArchiveError(format!("Failed to update scan index: {}", e)))?;
+
+    Ok(())
+}
+
+/// Write a scan result inside `write_txn`
+pub(crate) fn put_scan_result_in(
+    write_txn: &WriteTransaction,
+    format: ValueFormat,
+    scan_id: &str,
+    result: &ScanResult,
+) -> CryptexResult<()> {
+    let mut table = write_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+    })?;
+
+    let stored = StoredScanResult {
+        scan_id: scan_id.to_string(),
+        result: result.clone(),
+    };
+
+    let data = format.encode(&stored).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to serialize scan result: {}", e))
+    })?;
+
+    let key = scan_result_key(scan_id, result);
+
+    table.insert(key.as_str(), data.as_slice()).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to insert scan result: {}", e))
+    })?;
+
+    Ok(())
+}
+
+/// Meta key recording the archive's `ValueFormat`
+const VALUE_FORMAT_KEY: &str = "value_format";
+
+pub(crate) fn decode_scan_metadata(format: ValueFormat, data: &[u8]) -> CryptexResult<ScanMetadata> {
+    format.decode(data).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to deserialize scan metadata: {}", e))
+    })
+}
+
+fn decode_vulnerability(format: ValueFormat, data: &[u8]) -> CryptexResult<StoredVulnerability> {
+    format.decode(data).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to deserialize vulnerability: {}", e))
+    })
+}
 
 /// Stored scan metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanMetadata {
     pub scan_id: String,
     pub target: String,
+    /// Individual targets of a multi-target scan; empty for single-target scans
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
     pub started_at: DateTime<Utc>,
     pub ended_at: Option<DateTime<Utc>>,
     pub status: String,
@@ -62,6 +376,12 @@ pub struct ScanMetadata {
     pub high: usize,
     pub medium: usize,
     pub low: usize,
+    /// Enrichment settings the scan was started with
+    #[serde(default)]
+    pub options: ScanOptions,
+    /// Labels such as "prod" or "pci-scope", for filtering
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl ScanMetadata {
@@ -69,6 +389,7 @@ impl ScanMetadata {
         Self {
             scan_id,
             target,
+            targets: Vec::new(),
             started_at: Utc::now(),
             ended_at: None,
             status: "running".to_string(),
@@ -77,7 +398,35 @@ impl ScanMetadata {
             high: 0,
             medium: 0,
             low: 0,
+            options: ScanOptions::default(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Trim tags and drop repeats, keeping first-seen order
+    ///
+    /// Tags are matched exactly. Empty tags and tags containing a comma (the
+    /// separator in `?tags=` filters) are rejected.
+    pub fn normalize_tags(tags: Vec<String>) -> CryptexResult<Vec<String>> {
+        let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags {
+            let tag = tag.trim();
+            if tag.is_empty() || tag.contains(',') {
+                return Err(CryptexError::validation(format!(
+                    "Invalid tag '{}': tags must be non-empty and contain no commas",
+                    tag
+                )));
+            }
+            if !normalized.iter().any(|existing| existing == tag) {
+                normalized.push(tag.to_string());
+            }
         }
+        Ok(normalized)
+    }
+
+    /// Whether the scan carries every one of `tags`
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter().all(|tag| self.tags.contains(tag))
     }
 }
 
@@ -99,11 +448,88 @@ pub struct StoredVulnerability {
 /// The Archive - Main database interface
 pub struct TheArchive {
     db: Arc<Database>,
+    /// Encoding of every stored value, as recorded in the meta table
+    format: ValueFormat,
+    /// Key unlocked by passphrase, held for the life of the handle
+    key: OnceLock<ArchiveKey>,
+    /// Committed changes, for subscribers following the archive
+    events: broadcast::Sender<ArchiveEvent>,
+    /// Opened with [`TheArchive::the_awakening_readonly`]; every write is refused
+    read_only: bool,
+    /// Stored vulnerabilities deserialized by reads since the handle opened
+    vulnerabilities_decoded: AtomicU64,
+    /// Scan metadata records deserialized by listings since the handle opened
+    scans_decoded: AtomicU64,
+    /// Transaction latency since the handle opened
+    timings: TxnTimings,
+}
+
+/// Resolve where the archive lives
+///
+/// An explicit path is used exactly as given, relative or absolute. Without
+/// one, the archive goes to the platform data directory (see
+/// [`the_foundation::platform::get_default_db_path`]) rather than wherever
+/// the process happened to be started.
+pub fn resolve_archive_path(path: Option<PathBuf>) -> PathBuf {
+    path.unwrap_or_else(the_foundation::platform::get_default_db_path)
+}
+
+fn parse_format_marker(value: &[u8]) -> CryptexResult<ValueFormat> {
+    let name = std::str::from_utf8(value).map_err(|e| {
+        CryptexError::ArchiveError(format!("Corrupt value format marker: {}", e))
+    })?;
+    ValueFormat::parse(name)
+}
+
+/// The recorded value format of an archive opened without migration
+///
+/// No meta table or no marker means the archive predates formats: CBOR.
+fn read_format_marker(db: &Database) -> CryptexResult<ValueFormat> {
+    let read_txn = db.begin_read().map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to begin read transaction: {}", e))
+    })?;
+    let meta = match read_txn.open_table(META_TABLE) {
+        Ok(table) => table,
+        Err(redb::TableError::TableDoesNotExist(_)) => return Ok(ValueFormat::Cbor),
+        Err(e) => {
+            return Err(CryptexError::ArchiveError(format!(
+                "Failed to open meta table: {}",
+                e
+            )))
+        }
+    };
+    match meta.get(VALUE_FORMAT_KEY).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to read value format: {}", e))
+    })? {
+        Some(value) => parse_format_marker(value.value()),
+        None => Ok(ValueFormat::Cbor),
+    }
 }
 
 impl TheArchive {
     /// The Awakening - Initialize the archive
-    pub fn the_awakening(path: PathBuf) -> CryptexResult<Self> {
+    ///
+    /// Pass `None` to use the platform default location. A new archive
+    /// stores CBOR values; an existing one keeps the format it was created
+    /// with.
+    pub fn the_awakening(path: impl Into<Option<PathBuf>>) -> CryptexResult<Self> {
+        Self::awaken(resolve_archive_path(path.into()), None)
+    }
+
+    /// Initialize the archive, insisting on a value format
+    ///
+    /// A new archive is created with `format`. Opening an existing archive
+    /// that records a different format is a validation error, as is asking
+    /// for JSON on an archive written before formats were recorded (those
+    /// are CBOR).
+    pub fn the_awakening_with_format(
+        path: impl Into<Option<PathBuf>>,
+        format: ValueFormat,
+    ) -> CryptexResult<Self> {
+        Self::awaken(resolve_archive_path(path.into()), Some(format))
+    }
+
+    fn awaken(path: PathBuf, expected: Option<ValueFormat>) -> CryptexResult<Self> {
         tracing::info!("The Archive awakening at {:?}", path);
 
         // Create parent directory if it doesn't exist
@@ -123,54 +549,263 @@ impl TheArchive {
             CryptexError::ArchiveError(format!("Failed to begin write transaction: {}", e))
         })?;
 
-        {
-            let _ = write_txn.open_table(SCANS_TABLE).map_err(|e| {
+        let format = {
+            let scans = write_txn.open_table(SCANS_TABLE).map_err(|e| {
                 CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
             })?;
 
-            let _ = write_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+            let results = write_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+            })?;
+
+            let vulnerabilities = write_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
                 CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
             })?;
+            let mut index = write_txn.open_table(VULNERABILITIES_BY_TIME_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open vulnerability index: {}", e))
+            })?;
 
-            let _ = write_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
-                CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+            let mut meta = write_txn.open_table(META_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open meta table: {}", e))
             })?;
-        }
+            let recorded = match meta.get(VALUE_FORMAT_KEY).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read value format: {}", e))
+            })? {
+                Some(value) => Some(parse_format_marker(value.value())?),
+                None => None,
+            };
+
+            let format = match recorded {
+                Some(recorded) => recorded,
+                None => {
+                    // Archives that predate the marker hold CBOR
+                    let has_rows = !scans.is_empty().unwrap_or(true)
+                        || !results.is_empty().unwrap_or(true)
+                        || !vulnerabilities.is_empty().unwrap_or(true);
+                    let format = if has_rows {
+                        ValueFormat::Cbor
+                    } else {
+                        expected.unwrap_or_default()
+                    };
+                    meta.insert(VALUE_FORMAT_KEY, format.as_str().as_bytes())
+                        .map_err(|e| {
+                            CryptexError::ArchiveError(format!("Failed to record value format: {}", e))
+                        })?;
+                    format
+                }
+            };
+            if let Some(expected) = expected {
+                if expected != format {
+                    return Err(CryptexError::validation(format!(
+                        "Archive at {} stores {} values, not {}",
+                        path.display(),
+                        format,
+                        expected
+                    )));
+                }
+            }
+
+            // Databases written before the index existed are backfilled once
+            let needs_backfill = index.is_empty().unwrap_or(false)
+                && !vulnerabilities.is_empty().unwrap_or(true);
+            if needs_backfill {
+                let iter = vulnerabilities.iter().map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to iterate vulnerabilities: {}", e))
+                })?;
+                for entry in iter {
+                    let (_key, value) = entry.map_err(|e| {
+                        CryptexError::ArchiveError(format!("Failed to read vulnerability entry: {}", e))
+                    })?;
+                    let stored = decode_vulnerability(format, value.value())?;
+                    index
+                        .insert(cached_at_key(&stored.cached_at, &stored.cve_id).as_str(), stored.cve_id.as_str())
+                        .map_err(|e| {
+                            CryptexError::ArchiveError(format!("Failed to index vulnerability: {}", e))
+                        })?;
+                }
+            }
+
+            // And those written before the scan start index
+            let mut scan_index = write_txn.open_table(SCANS_BY_START_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open scan index: {}", e))
+            })?;
+            if scan_index.is_empty().unwrap_or(false) && !scans.is_empty().unwrap_or(true) {
+                let iter = scans.iter().map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to iterate scans: {}", e))
+                })?;
+                for entry in iter {
+                    let (key, value) = entry.map_err(|e| {
+                        CryptexError::ArchiveError(format!("Failed to read scan entry: {}", e))
+                    })?;
+                    let metadata = decode_scan_metadata(format, value.value())?;
+                    scan_index
+                        .insert(scan_start_key(&metadata.started_at, key.value()).as_str(), key.value())
+                        .map_err(|e| {
+                            CryptexError::ArchiveError(format!("Failed to index scan: {}", e))
+                        })?;
+                }
+            }
+
+            // So are databases written before the severity and KEV indexes
+            let severity_index_empty = write_txn
+                .open_multimap_table(VULNERABILITIES_BY_SEVERITY_TABLE)
+                .map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to open severity index: {}", e))
+                })?
+                .is_empty()
+                .unwrap_or(false);
+            write_txn.open_table(KEV_VULNERABILITIES_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open KEV index: {}", e))
+            })?;
+            if severity_index_empty && !vulnerabilities.is_empty().unwrap_or(true) {
+                let iter = vulnerabilities.iter().map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to iterate vulnerabilities: {}", e))
+                })?;
+                for entry in iter {
+                    let (_key, value) = entry.map_err(|e| {
+                        CryptexError::ArchiveError(format!("Failed to read vulnerability entry: {}", e))
+                    })?;
+                    let stored = decode_vulnerability(format, value.value())?;
+                    index_vulnerability_in(&write_txn, &stored)?;
+                }
+            }
+
+            format
+        };
 
         write_txn.commit().map_err(|e| {
             CryptexError::ArchiveError(format!("Failed to commit table creation: {}", e))
         })?;
 
-        tracing::info!("The Archive ready");
-
-        Ok(Self { db: Arc::new(db) })
+        tracing::info!("The Archive ready ({} values)", format);
+
+        Ok(Self {
+            db: Arc::new(db),
+            format,
+            key: OnceLock::new(),
+            events: broadcast::channel(ARCHIVE_EVENT_CAPACITY).0,
+            read_only: false,
+            vulnerabilities_decoded: AtomicU64::new(0),
+            scans_decoded: AtomicU64::new(0),
+            timings: TxnTimings::default(),
+        })
     }
 
-    /// Store scan metadata
-    pub fn store_scan_metadata(&self, metadata: &ScanMetadata) -> CryptexResult<()> {
-        let write_txn = self.db.begin_write().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to begin write transaction: {}", e))
+    /// Open an existing archive without creating or migrating anything
+    ///
+    /// For diagnostics such as [`TheArchive::verify`]: a missing file is an
+    /// error rather than a new empty database, and no tables are created.
+    pub fn open_existing(path: PathBuf) -> CryptexResult<Self> {
+        if !path.is_file() {
+            return Err(CryptexError::not_found(format!(
+                "No archive at {}",
+                path.display()
+            )));
+        }
+
+        let db = Database::open(&path).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open database: {}", e))
         })?;
+        let format = read_format_marker(&db)?;
+
+        Ok(Self {
+            db: Arc::new(db),
+            format,
+            key: OnceLock::new(),
+            events: broadcast::channel(ARCHIVE_EVENT_CAPACITY).0,
+            read_only: false,
+            vulnerabilities_decoded: AtomicU64::new(0),
+            scans_decoded: AtomicU64::new(0),
+            timings: TxnTimings::default(),
+        })
+    }
 
-        {
-            let mut table = write_txn.open_table(SCANS_TABLE).map_err(|e| {
-                CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
-            })?;
+    /// Open an existing archive for reading only
+    ///
+    /// For dashboards and query services that never write. Nothing is
+    /// created or migrated, and every write (`store_*`, batches, imports,
+    /// deletes) fails with an archive error instead of taking the write
+    /// lock. redb still locks the file itself, so the archive cannot be
+    /// opened this way while another process has it open.
+    pub fn the_awakening_readonly(path: impl Into<Option<PathBuf>>) -> CryptexResult<Self> {
+        let path = resolve_archive_path(path.into());
+        tracing::info!("The Archive awakening read-only at {:?}", path);
+
+        let mut archive = Self::open_existing(path)?;
+        archive.read_only = true;
+        Ok(archive)
+    }
 
-            let data = serde_cbor::to_vec(metadata).map_err(|e| {
-                CryptexError::ArchiveError(format!("Failed to serialize scan metadata: {}", e))
-            })?;
+    /// Whether the archive was opened read-only
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
 
-            table
-                .insert(metadata.scan_id.as_str(), data.as_slice())
-                .map_err(|e| {
-                    CryptexError::ArchiveError(format!("Failed to insert scan metadata: {}", e))
-                })?;
+    /// How many stored vulnerabilities reads have deserialized so far
+    ///
+    /// Shows how much of the table a query had to touch.
+    pub fn vulnerability_decode_count(&self) -> u64 {
+        self.vulnerabilities_decoded.load(Ordering::Relaxed)
+    }
+
+    /// How many scan metadata records listings have deserialized so far
+    pub fn scan_decode_count(&self) -> u64 {
+        self.scans_decoded.load(Ordering::Relaxed)
+    }
+
+    /// Deserialize a stored vulnerability for a reader, counting it
+    fn read_vulnerability(&self, data: &[u8]) -> CryptexResult<StoredVulnerability> {
+        self.vulnerabilities_decoded.fetch_add(1, Ordering::Relaxed);
+        decode_vulnerability(self.format, data)
+    }
+
+    /// Begin a write transaction, unless the archive is read-only
+    ///
+    /// The transaction is timed for [`TheArchive::archive_metrics`] from this
+    /// call, so time spent waiting for another writer counts.
+    pub(crate) fn begin_write(&self) -> CryptexResult<TimedWrite<'_>> {
+        if self.read_only {
+            return Err(CryptexError::ArchiveError(
+                "Archive is open read-only; writes are not allowed".to_string(),
+            ));
         }
+        let requested = Instant::now();
+        let txn = self.db.begin_write().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to begin write transaction: {}", e))
+        })?;
+        Ok(TimedWrite::new(txn, requested, &self.timings))
+    }
 
-        write_txn.commit().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to commit scan metadata: {}", e))
+    /// Begin a read transaction, timed until it is dropped
+    pub(crate) fn begin_read(&self) -> CryptexResult<TimedRead<'_>> {
+        let opened = Instant::now();
+        let txn = self.db.begin_read().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to begin read transaction: {}", e))
         })?;
+        Ok(TimedRead::new(txn, opened, &self.timings))
+    }
+
+    /// Transaction latency since this handle was opened
+    ///
+    /// Writes are timed from the request to the end of the commit, so a
+    /// high `max_write_ms` next to a low average points at lock contention
+    /// or a stalled disk rather than slow queries.
+    pub fn archive_metrics(&self) -> ArchiveMetrics {
+        self.timings.snapshot()
+    }
+
+    /// How this archive's values are encoded
+    pub fn value_format(&self) -> ValueFormat {
+        self.format
+    }
+
+    /// Store scan metadata in its own transaction
+    ///
+    /// Use [`TheArchive::begin_batch`] to group many writes into one.
+    pub fn store_scan_metadata(&self, metadata: &ScanMetadata) -> CryptexResult<()> {
+        let mut batch = self.begin_batch()?;
+        batch.store_scan_metadata(metadata)?;
+        batch.commit()?;
 
         tracing::debug!("Stored scan metadata for {}", metadata.scan_id);
         Ok(())
@@ -178,9 +813,7 @@ impl TheArchive {
 
     /// Retrieve scan metadata
     pub fn get_scan_metadata(&self, scan_id: &str) -> CryptexResult<Option<ScanMetadata>> {
-        let read_txn = self.db.begin_read().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to begin read transaction: {}", e))
-        })?;
+        let read_txn = self.begin_read()?;
 
         let table = read_txn.open_table(SCANS_TABLE).map_err(|e| {
             CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
@@ -192,7 +825,7 @@ impl TheArchive {
 
         match result {
             Some(data) => {
-                let metadata: ScanMetadata = serde_cbor::from_slice(data.value()).map_err(
+                let metadata: ScanMetadata = self.format.decode(data.value()).map_err(
                     |e| {
                         CryptexError::ArchiveError(format!(
                             "Failed to deserialize scan metadata: {}",
@@ -206,50 +839,81 @@ impl TheArchive {
         }
     }
 
-    /// Store scan result
-    pub fn store_scan_result(&self, scan_id: &str, result: &ScanResult) -> CryptexResult<()> {
-        let write_txn = self.db.begin_write().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to begin write transaction: {}", e))
-        })?;
-
-        {
-            let mut table = write_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
-                CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+    /// Apply `update` to a scan's metadata and store the result atomically
+    ///
+    /// The read, the update and the write happen in one write transaction,
+    /// and redb admits one writer at a time, so concurrent updates to the
+    /// same scan are applied one after the other rather than overwriting
+    /// each other. Returns the metadata as stored.
+    pub fn update_scan_metadata<F>(&self, scan_id: &str, update: F) -> CryptexResult<ScanMetadata>
+    where
+        F: FnOnce(&mut ScanMetadata),
+    {
+        let write_txn = self.begin_write()?;
+
+        let metadata = {
+            let mut table = write_txn.open_table(SCANS_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
             })?;
 
-            let stored = StoredScanResult {
-                scan_id: scan_id.to_string(),
-                result: result.clone(),
+            // Decode in the same statement so the read guard is released
+            // before the table is written to
+            let mut metadata: ScanMetadata = match table.get(scan_id).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read scan metadata: {}", e))
+            })? {
+                Some(data) => self.format.decode(data.value()).map_err(|e| {
+                    CryptexError::ArchiveError(format!(
+                        "Failed to deserialize scan metadata: {}",
+                        e
+                    ))
+                })?,
+                None => {
+                    return Err(CryptexError::not_found(format!("Scan not found: {}", scan_id)))
+                }
             };
 
-            let data = serde_cbor::to_vec(&stored).map_err(|e| {
-                CryptexError::ArchiveError(format!("Failed to serialize scan result: {}", e))
-            })?;
-
-            // Key format: scan_id:cve_id:host:port
-            let key = format!(
-                "{}:{}:{}:{}",
-                scan_id, result.cve_id, result.host, result.port
-            );
+            let started_at = metadata.started_at;
+            update(&mut metadata);
 
-            table.insert(key.as_str(), data.as_slice()).map_err(|e| {
-                CryptexError::ArchiveError(format!("Failed to insert scan result: {}", e))
+            let data = self.format.encode(&metadata).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to serialize scan metadata: {}", e))
             })?;
-        }
+            table.insert(scan_id, data.as_slice()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to insert scan metadata: {}", e))
+            })?;
+            if metadata.started_at != started_at {
+                index_scan_start_in(&write_txn, Some(started_at), &metadata)?;
+            }
+
+            metadata
+        };
 
         write_txn.commit().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to commit scan result: {}", e))
+            CryptexError::ArchiveError(format!("Failed to commit scan metadata: {}", e))
         })?;
 
+        tracing::debug!("Updated scan metadata for {}", scan_id);
+        self.emit(ArchiveEvent::ScanStored {
+            scan_id: scan_id.to_string(),
+        });
+        Ok(metadata)
+    }
+
+    /// Store a scan result in its own transaction
+    ///
+    /// Use [`TheArchive::begin_batch`] to group many writes into one.
+    pub fn store_scan_result(&self, scan_id: &str, result: &ScanResult) -> CryptexResult<()> {
+        let mut batch = self.begin_batch()?;
+        batch.store_scan_result(scan_id, result)?;
+        batch.commit()?;
+
         tracing::debug!("Stored scan result for {} in scan {}", result.cve_id, scan_id);
         Ok(())
     }
 
     /// Get all scan results for a scan
     pub fn get_scan_results(&self, scan_id: &str) -> CryptexResult<Vec<ScanResult>> {
-        let read_txn = self.db.begin_read().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to begin read transaction: {}", e))
-        })?;
+        let read_txn = self.begin_read()?;
 
         let table = read_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
             CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
@@ -270,7 +934,7 @@ impl TheArchive {
 
             let key_str = key.value();
             if key_str.starts_with(&prefix) {
-                let stored: StoredScanResult = serde_cbor::from_slice(value.value()).map_err(|e| {
+                let stored: StoredScanResult = self.format.decode(value.value()).map_err(|e| {
                     CryptexError::ArchiveError(format!(
                         "Failed to deserialize scan result: {}",
                         e
@@ -283,47 +947,248 @@ impl TheArchive {
         Ok(results)
     }
 
-    /// Store vulnerability assessment
+    /// Rebuild a scan's report from its metadata and archived results
+    ///
+    /// Severity and KEV counts are recomputed from the stored results, so a
+    /// scan whose results were never archived reports none.
+    pub fn load_scan_report(&self, scan_id: &str) -> CryptexResult<Option<ScanReport>> {
+        let Some(metadata) = self.get_scan_metadata(scan_id)? else {
+            return Ok(None);
+        };
+
+        let mut report = ScanReport::new(metadata.scan_id, metadata.target);
+        if !metadata.targets.is_empty() {
+            report.targets = metadata.targets;
+        }
+        report.start_time = metadata.started_at.timestamp().max(0) as u64;
+
+        let mut hosts = std::collections::HashSet::new();
+        for result in self.get_scan_results(scan_id)? {
+            hosts.insert(result.host.clone());
+            report.add_result(result);
+        }
+        report.total_hosts = hosts.len();
+
+        if let Some(ended_at) = metadata.ended_at {
+            report.end_time = Some(ended_at.timestamp().max(0) as u64);
+            report.status = ScanStatus::Completed;
+        }
+
+        Ok(Some(report))
+    }
+
+    /// List stored scan results across all scans
+    pub fn list_scan_results(&self) -> CryptexResult<Vec<StoredScanResult>> {
+        let read_txn = self.begin_read()?;
+
+        let table = read_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+        })?;
+
+        let mut results = Vec::new();
+
+        let iter = table.iter().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to iterate scan results: {}", e))
+        })?;
+
+        for entry in iter {
+            let (_key, value) = entry.map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read scan result entry: {}", e))
+            })?;
+
+            let stored: StoredScanResult = self.format.decode(value.value()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to deserialize scan result: {}", e))
+            })?;
+            results.push(stored);
+        }
+
+        Ok(results)
+    }
+
+    /// Walk stored scan results one at a time, optionally only one scan's
+    ///
+    /// `visit` returns `false` to stop; results after that are never decoded.
+    pub(crate) fn visit_scan_results(
+        &self,
+        scan_id: Option<&str>,
+        mut visit: impl FnMut(StoredScanResult) -> bool,
+    ) -> CryptexResult<()> {
+        let read_txn = self.begin_read()?;
+
+        let table = read_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+        })?;
+
+        // Keys start with `scan_id:` (see `scan_result_key`), so a scan's results are contiguous
+        let prefix = scan_id.map(|scan_id| format!("{}:", scan_id));
+        let iter = table.range(prefix.as_deref().unwrap_or("")..).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to iterate scan results: {}", e))
+        })?;
+
+        for entry in iter {
+            let (key, value) = entry.map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read scan result entry: {}", e))
+            })?;
+            if prefix.as_deref().is_some_and(|prefix| !key.value().starts_with(prefix)) {
+                break;
+            }
+
+            let stored: StoredScanResult = self.format.decode(value.value()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to deserialize scan result: {}", e))
+            })?;
+            if !visit(stored) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// CVE IDs with an archived assessment
+    ///
+    /// Only keys are read, so no assessment is deserialized.
+    pub(crate) fn vulnerability_ids(&self) -> CryptexResult<HashSet<String>> {
+        let read_txn = self.begin_read()?;
+
+        let table = read_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+        })?;
+
+        let iter = table.iter().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to iterate vulnerabilities: {}", e))
+        })?;
+
+        let mut ids = HashSet::new();
+        for entry in iter {
+            let (key, _) = entry.map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read vulnerability entry: {}", e))
+            })?;
+            ids.insert(key.value().to_string());
+        }
+
+        Ok(ids)
+    }
+
+    /// Store a vulnerability assessment in its own transaction
+    ///
+    /// Use [`TheArchive::begin_batch`] to group many writes into one.
     pub fn store_vulnerability(&self, score: &VulnerabilityScore) -> CryptexResult<()> {
-        let write_txn = self.db.begin_write().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to begin write transaction: {}", e))
+        let mut batch = self.begin_batch()?;
+        batch.store_vulnerability(score)?;
+        batch.commit()?;
+
+        tracing::debug!("Stored vulnerability assessment for {}", score.cve_id);
+        Ok(())
+    }
+
+    /// Write a stored vulnerability record as-is
+    #[cfg(test)]
+    pub(crate) fn put_vulnerability(&self, stored: &StoredVulnerability) -> CryptexResult<()> {
+        let mut batch = self.begin_batch()?;
+        batch.put_vulnerability(stored)?;
+        batch.commit()?;
+
+        tracing::debug!("Stored vulnerability assessment for {}", stored.cve_id);
+        Ok(())
+    }
+
+    /// Remove one cached assessment, returning whether it was stored
+    pub fn delete_vulnerability(&self, cve_id: &str) -> CryptexResult<bool> {
+        let write_txn = self.begin_write()?;
+
+        let existed = {
+            let mut table = write_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+            })?;
+
+            let mut index = write_txn.open_table(VULNERABILITIES_BY_TIME_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open vulnerability index: {}", e))
+            })?;
+
+            let removed = table
+                .remove(cve_id)
+                .map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to remove vulnerability: {}", e))
+                })?
+                .map(|old| decode_vulnerability(self.format, old.value()))
+                .transpose()?;
+
+            if let Some(removed) = &removed {
+                index
+                    .remove(cached_at_key(&removed.cached_at, &removed.cve_id).as_str())
+                    .map_err(|e| {
+                        CryptexError::ArchiveError(format!("Failed to update vulnerability index: {}", e))
+                    })?;
+                unindex_vulnerability_in(&write_txn, removed)?;
+            }
+            removed.is_some()
+        };
+
+        write_txn.commit().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to commit vulnerability deletion: {}", e))
         })?;
 
-        {
+        if existed {
+            tracing::debug!("Deleted vulnerability assessment for {}", cve_id);
+        }
+        Ok(existed)
+    }
+
+    /// Remove every cached assessment, returning how many there were
+    ///
+    /// Scans and their results are left untouched.
+    pub fn clear_vulnerabilities(&self) -> CryptexResult<usize> {
+        let write_txn = self.begin_write()?;
+
+        let removed = {
             let mut table = write_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
                 CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
             })?;
 
-            let stored = StoredVulnerability {
-                cve_id: score.cve_id.clone(),
-                score: score.clone(),
-                cached_at: Utc::now(),
-            };
+            let mut index = write_txn.open_table(VULNERABILITIES_BY_TIME_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open vulnerability index: {}", e))
+            })?;
 
-            let data = serde_cbor::to_vec(&stored).map_err(|e| {
-                CryptexError::ArchiveError(format!("Failed to serialize vulnerability: {}", e))
+            let count = table.len().map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to count vulnerabilities: {}", e))
+            })? as usize;
+
+            table.retain(|_, _| false).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to clear vulnerabilities: {}", e))
+            })?;
+            index.retain(|_, _| false).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to clear vulnerability index: {}", e))
             })?;
 
-            table
-                .insert(score.cve_id.as_str(), data.as_slice())
-                .map_err(|e| {
-                    CryptexError::ArchiveError(format!("Failed to insert vulnerability: {}", e))
+            let mut by_severity =
+                write_txn.open_multimap_table(VULNERABILITIES_BY_SEVERITY_TABLE).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to open severity index: {}", e))
                 })?;
-        }
+            for severity in CvssSeverity::ALL {
+                by_severity.remove_all(severity_key(severity).as_str()).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to clear severity index: {}", e))
+                })?;
+            }
+            let mut kev = write_txn.open_table(KEV_VULNERABILITIES_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open KEV index: {}", e))
+            })?;
+            kev.retain(|_, _| false).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to clear KEV index: {}", e))
+            })?;
+            count
+        };
 
         write_txn.commit().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to commit vulnerability: {}", e))
+            CryptexError::ArchiveError(format!("Failed to commit vulnerability clear: {}", e))
         })?;
 
-        tracing::debug!("Stored vulnerability assessment for {}", score.cve_id);
-        Ok(())
+        tracing::info!("Cleared {} cached vulnerability assessments", removed);
+        Ok(removed)
     }
 
     /// Retrieve vulnerability assessment
     pub fn get_vulnerability(&self, cve_id: &str) -> CryptexResult<Option<StoredVulnerability>> {
-        let read_txn = self.db.begin_read().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to begin read transaction: {}", e))
-        })?;
+        let read_txn = self.begin_read()?;
 
         let table = read_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
             CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
@@ -334,59 +1199,333 @@ impl TheArchive {
         })?;
 
         match result {
-            Some(data) => {
-                let stored: StoredVulnerability = serde_cbor::from_slice(data.value()).map_err(
-                    |e| {
-                        CryptexError::ArchiveError(format!(
-                            "Failed to deserialize vulnerability: {}",
-                            e
-                        ))
-                    },
-                )?;
-                Ok(Some(stored))
-            }
+            Some(data) => Ok(Some(self.read_vulnerability(data.value())?)),
             None => Ok(None),
         }
     }
 
-    /// List all scans
+    /// Look up several assessments in one read transaction
+    ///
+    /// Returns the ids that were found with their records; misses are left out.
+    pub(crate) fn get_vulnerabilities_by_id<'a>(
+        &self,
+        cve_ids: impl IntoIterator<Item = &'a str>,
+    ) -> CryptexResult<Vec<(&'a str, StoredVulnerability)>> {
+        let read_txn = self.begin_read()?;
+
+        let table = read_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+        })?;
+
+        let mut found = Vec::new();
+        for cve_id in cve_ids {
+            let data = table.get(cve_id).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read vulnerability: {}", e))
+            })?;
+            if let Some(data) = data {
+                found.push((cve_id, self.read_vulnerability(data.value())?));
+            }
+        }
+        Ok(found)
+    }
+
+    /// List all stored vulnerability assessments
+    ///
+    /// Loads every row; prefer `list_vulnerabilities_page` unless all of
+    /// them are really needed.
+    pub fn list_vulnerabilities(&self) -> CryptexResult<Vec<StoredVulnerability>> {
+        let read_txn = self.begin_read()?;
+
+        let table = read_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+        })?;
+
+        let mut vulnerabilities = Vec::new();
+
+        let iter = table.iter().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to iterate vulnerabilities: {}", e))
+        })?;
+
+        for entry in iter {
+            let (_key, value) = entry.map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read vulnerability entry: {}", e))
+            })?;
+
+            let stored: StoredVulnerability = self.format.decode(value.value()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to deserialize vulnerability: {}", e))
+            })?;
+            vulnerabilities.push(stored);
+        }
+
+        Ok(vulnerabilities)
+    }
+
+    /// List up to `limit` stored assessments in CVE ID order, skipping the first `offset`
+    ///
+    /// Skipped rows are stepped over without being decoded, and reading stops
+    /// once the page is full.
+    pub fn list_vulnerabilities_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> CryptexResult<Vec<StoredVulnerability>> {
+        let read_txn = self.begin_read()?;
+
+        let table = read_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+        })?;
+
+        let range = table.range::<&str>(..).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to range vulnerabilities: {}", e))
+        })?;
+
+        range
+            .skip(offset)
+            .take(limit)
+            .map(|entry| {
+                let (_key, value) = entry.map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to read vulnerability entry: {}", e))
+                })?;
+                self.read_vulnerability(value.value())
+            })
+            .collect()
+    }
+
+    /// CVE IDs that the severity and KEV indexes allow
+    ///
+    /// Narrows to the given severities and, with `kev_only`, to known-exploited
+    /// entries. `None` means no narrowing: nothing was asked for, or the archive
+    /// predates the indexes and was opened read-only so they were never built.
+    pub(crate) fn indexed_vulnerability_ids(
+        &self,
+        severities: Option<&[CvssSeverity]>,
+        kev_only: bool,
+    ) -> CryptexResult<Option<HashSet<String>>> {
+        if severities.is_none() && !kev_only {
+            return Ok(None);
+        }
+
+        let read_txn = self.begin_read()?;
+        let read_error = |e: &dyn std::fmt::Display| {
+            CryptexError::ArchiveError(format!("Failed to read vulnerability index: {}", e))
+        };
+
+        let mut candidates: Option<HashSet<String>> = None;
+        if let Some(severities) = severities {
+            let by_severity = match read_txn
+                .open_multimap_table(VULNERABILITIES_BY_SEVERITY_TABLE)
+            {
+                Ok(table) => table,
+                Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+                Err(e) => return Err(read_error(&e)),
+            };
+            let mut ids = HashSet::new();
+            for severity in severities {
+                let values = by_severity
+                    .get(severity_key(*severity).as_str())
+                    .map_err(|e| read_error(&e))?;
+                for value in values {
+                    ids.insert(value.map_err(|e| read_error(&e))?.value().to_string());
+                }
+            }
+            candidates = Some(ids);
+        }
+
+        if kev_only {
+            let kev = match read_txn.open_table(KEV_VULNERABILITIES_TABLE) {
+                Ok(table) => table,
+                Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+                Err(e) => return Err(read_error(&e)),
+            };
+            let mut ids = HashSet::new();
+            for entry in kev.iter().map_err(|e| read_error(&e))? {
+                let (cve_id, _) = entry.map_err(|e| read_error(&e))?;
+                let cve_id = cve_id.value().to_string();
+                if candidates.as_ref().is_none_or(|c| c.contains(&cve_id)) {
+                    ids.insert(cve_id);
+                }
+            }
+            candidates = Some(ids);
+        }
+
+        Ok(candidates)
+    }
+
+    /// Walk stored vulnerabilities in sort-key order, starting at `lower`
+    ///
+    /// `visit` receives each record with its sort key and returns `false` to stop.
+    /// Reading begins at the bound (e.g. just after a cursor), so a cursor page
+    /// does not re-read the rows before it. Nothing is buffered here; memory
+    /// use is whatever the visitor keeps. With `candidates`, other CVE IDs are
+    /// skipped without being deserialized.
+    pub(crate) fn visit_vulnerabilities(
+        &self,
+        sort: VulnerabilitySort,
+        lower: Bound<&str>,
+        candidates: Option<&HashSet<String>>,
+        mut visit: impl FnMut(String, StoredVulnerability) -> bool,
+    ) -> CryptexResult<()> {
+        let read_txn = self.begin_read()?;
+
+        let table = read_txn.open_table(VULNERABILITIES_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open vulnerabilities table: {}", e))
+        })?;
+
+        match sort {
+            VulnerabilitySort::CveId => {
+                let range = table.range::<&str>((lower, Bound::Unbounded)).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to range vulnerabilities: {}", e))
+                })?;
+
+                for entry in range {
+                    let (key, value) = entry.map_err(|e| {
+                        CryptexError::ArchiveError(format!("Failed to read vulnerability entry: {}", e))
+                    })?;
+                    if candidates.is_some_and(|c| !c.contains(key.value())) {
+                        continue;
+                    }
+
+                    if !visit(key.value().to_string(), self.read_vulnerability(value.value())?) {
+                        break;
+                    }
+                }
+            }
+            VulnerabilitySort::CachedAt => {
+                let index = read_txn.open_table(VULNERABILITIES_BY_TIME_TABLE).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to open vulnerability index: {}", e))
+                })?;
+                let range = index.range::<&str>((lower, Bound::Unbounded)).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to range vulnerability index: {}", e))
+                })?;
+
+                for entry in range {
+                    let (key, cve_id) = entry.map_err(|e| {
+                        CryptexError::ArchiveError(format!("Failed to read vulnerability index: {}", e))
+                    })?;
+                    if candidates.is_some_and(|c| !c.contains(cve_id.value())) {
+                        continue;
+                    }
+
+                    let Some(value) = table.get(cve_id.value()).map_err(|e| {
+                        CryptexError::ArchiveError(format!("Failed to read vulnerability: {}", e))
+                    })?
+                    else {
+                        continue;
+                    };
+
+                    if !visit(key.value().to_string(), self.read_vulnerability(value.value())?) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// List all scans, most recently started first
     pub fn list_scans(&self) -> CryptexResult<Vec<ScanMetadata>> {
-        let read_txn = self.db.begin_read().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to begin read transaction: {}", e))
+        let mut scans = Vec::new();
+        self.visit_scans(|scan| {
+            scans.push(scan);
+            true
         })?;
 
+        Ok(scans)
+    }
+
+    /// The `limit` most recently started scans, newest first
+    ///
+    /// Only those scans are read from the table.
+    pub fn list_recent_scans(&self, limit: usize) -> CryptexResult<Vec<ScanMetadata>> {
+        let mut scans = Vec::with_capacity(limit.min(1024));
+        if limit > 0 {
+            self.visit_scans(|scan| {
+                scans.push(scan);
+                scans.len() < limit
+            })?;
+        }
+
+        Ok(scans)
+    }
+
+    /// Walk scans most recently started first, via the start index
+    ///
+    /// `visit` returns `false` to stop; scans after that are never read.
+    pub(crate) fn visit_scans(&self, mut visit: impl FnMut(ScanMetadata) -> bool) -> CryptexResult<()> {
+        let read_txn = self.begin_read()?;
+
         let table = read_txn.open_table(SCANS_TABLE).map_err(|e| {
             CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
         })?;
+        let index = match read_txn.open_table(SCANS_BY_START_TABLE) {
+            Ok(index) => index,
+            // A read-only handle on an archive that predates the index
+            Err(redb::TableError::TableDoesNotExist(_)) => {
+                return self.visit_scans_unindexed(&table, visit);
+            }
+            Err(e) => {
+                return Err(CryptexError::ArchiveError(format!("Failed to open scan index: {}", e)))
+            }
+        };
+
+        let iter = index.iter().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to iterate scan index: {}", e))
+        })?;
+        for entry in iter {
+            let (_key, scan_id) = entry.map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read scan index: {}", e))
+            })?;
+
+            let Some(value) = table.get(scan_id.value()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read scan metadata: {}", e))
+            })?
+            else {
+                continue;
+            };
+
+            self.scans_decoded.fetch_add(1, Ordering::Relaxed);
+            if !visit(decode_scan_metadata(self.format, value.value())?) {
+                break;
+            }
+        }
 
+        Ok(())
+    }
+
+    /// Read and sort every scan, for archives without the start index
+    fn visit_scans_unindexed(
+        &self,
+        table: &redb::ReadOnlyTable<&str, &[u8]>,
+        mut visit: impl FnMut(ScanMetadata) -> bool,
+    ) -> CryptexResult<()> {
         let mut scans = Vec::new();
 
         let iter = table.iter().map_err(|e| {
             CryptexError::ArchiveError(format!("Failed to iterate scans: {}", e))
         })?;
-
         for entry in iter {
             let (_key, value) = entry.map_err(|e| {
                 CryptexError::ArchiveError(format!("Failed to read scan entry: {}", e))
             })?;
 
-            let metadata: ScanMetadata = serde_cbor::from_slice(value.value()).map_err(|e| {
-                CryptexError::ArchiveError(format!("Failed to deserialize scan metadata: {}", e))
-            })?;
-            scans.push(metadata);
+            self.scans_decoded.fetch_add(1, Ordering::Relaxed);
+            scans.push(decode_scan_metadata(self.format, value.value())?);
         }
 
-        // Sort by started_at descending (most recent first)
-        scans.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        scans.sort_by_key(|s| std::cmp::Reverse(s.started_at));
+        for scan in scans {
+            if !visit(scan) {
+                break;
+            }
+        }
 
-        Ok(scans)
+        Ok(())
     }
 
     /// Get database statistics
     pub fn get_stats(&self) -> CryptexResult<ArchiveStats> {
-        let read_txn = self.db.begin_read().map_err(|e| {
-            CryptexError::ArchiveError(format!("Failed to begin read transaction: {}", e))
-        })?;
+        let read_txn = self.begin_read()?;
 
         let scans_table = read_txn.open_table(SCANS_TABLE).map_err(|e| {
             CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
@@ -420,6 +1559,101 @@ impl TheArchive {
     }
 }
 
+/// Scans are checkpointed as their metadata (status "running" or "paused") plus
+/// the results gathered so far; completed scans are never recovered.
+impl CheckpointStore for TheArchive {
+    /// Metadata and new results are written in one transaction, so the
+    /// metadata is updated in place without losing concurrent edits and a
+    /// crash never leaves half a checkpoint behind.
+    fn save_checkpoint(&self, checkpoint: &ScanCheckpoint) -> CryptexResult<()> {
+        let mut batch = self.begin_batch()?;
+        let mut metadata = match batch.scan_metadata(&checkpoint.scan_id)? {
+            Some(metadata) => metadata,
+            None => {
+                let mut metadata =
+                    ScanMetadata::new(checkpoint.scan_id.clone(), checkpoint.target.clone());
+                if checkpoint.targets.len() > 1 {
+                    metadata.targets = checkpoint.targets.clone();
+                }
+                metadata.options = checkpoint.options;
+                if let Some(started_at) = DateTime::from_timestamp(checkpoint.start_time as i64, 0) {
+                    metadata.started_at = started_at;
+                }
+                metadata
+            }
+        };
+
+        if metadata.status != "completed" {
+            metadata.status = if checkpoint.paused { "paused" } else { "running" }.to_string();
+        }
+        metadata.total_vulnerabilities = checkpoint.total_results();
+        batch.store_scan_metadata(&metadata)?;
+
+        for result in &checkpoint.results {
+            batch.store_scan_result(&checkpoint.scan_id, result)?;
+        }
+
+        batch.commit()?;
+        Ok(())
+    }
+
+    fn load_incomplete(&self) -> CryptexResult<Vec<ScanCheckpoint>> {
+        let mut checkpoints = Vec::new();
+
+        for metadata in self.list_scans()? {
+            if metadata.status != "running" && metadata.status != "paused" {
+                continue;
+            }
+
+            checkpoints.push(ScanCheckpoint {
+                results: self.get_scan_results(&metadata.scan_id)?,
+                persisted: 0,
+                start_time: metadata.started_at.timestamp().max(0) as u64,
+                paused: metadata.status == "paused",
+                scan_id: metadata.scan_id,
+                target: metadata.target,
+                targets: metadata.targets,
+                options: metadata.options,
+            });
+        }
+
+        Ok(checkpoints)
+    }
+
+    fn mark_complete(&self, scan_id: &str) -> CryptexResult<()> {
+        if let Some(mut metadata) = self.get_scan_metadata(scan_id)? {
+            if metadata.status != "completed" {
+                metadata.status = "completed".to_string();
+                metadata.ended_at.get_or_insert_with(Utc::now);
+                self.store_scan_metadata(&metadata)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Stored vulnerabilities are the assessments; their age is `cached_at`, which
+/// the time index already keeps in order.
+impl AssessmentStore for TheArchive {
+    fn stale_assessments(&self, cutoff: DateTime<Utc>) -> CryptexResult<Vec<String>> {
+        let mut stale = Vec::new();
+        self.visit_vulnerabilities(VulnerabilitySort::CachedAt, Bound::Unbounded, None, |_, stored| {
+            if stored.cached_at >= cutoff {
+                return false;
+            }
+            stale.push(stored.cve_id);
+            true
+        })?;
+
+        Ok(stale)
+    }
+
+    fn store_assessment(&self, score: &VulnerabilityScore) -> CryptexResult<()> {
+        self.store_vulnerability(score)
+    }
+}
+
 /// Archive statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArchiveStats {
@@ -446,6 +1680,66 @@ mod tests {
         assert_eq!(stats.total_results, 0);
     }
 
+    #[test]
+    fn test_concurrent_metadata_updates_are_not_lost() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = Arc::new(TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap());
+        archive
+            .store_scan_metadata(&ScanMetadata::new("scan_1".to_string(), "10.0.0.0/24".to_string()))
+            .unwrap();
+
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let spawn = |update: fn(&mut ScanMetadata)| {
+            let (archive, barrier) = (archive.clone(), barrier.clone());
+            std::thread::spawn(move || {
+                barrier.wait();
+                archive
+                    .update_scan_metadata("scan_1", |metadata| {
+                        // Hold the read value long enough for the other update to race it
+                        std::thread::sleep(std::time::Duration::from_millis(20));
+                        update(metadata);
+                    })
+                    .unwrap();
+            })
+        };
+
+        let ending = spawn(|metadata| {
+            metadata.status = "completed".to_string();
+            metadata.critical = 3;
+        });
+        let counting = spawn(|metadata| metadata.high = 5);
+        ending.join().unwrap();
+        counting.join().unwrap();
+
+        let stored = archive.get_scan_metadata("scan_1").unwrap().unwrap();
+        assert_eq!(stored.status, "completed");
+        assert_eq!((stored.critical, stored.high), (3, 5));
+
+        let missing = archive.update_scan_metadata("scan_2", |_| {});
+        assert!(matches!(missing, Err(CryptexError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_default_archive_path_is_platform_data_dir() {
+        let path = resolve_archive_path(None);
+        assert_eq!(path, the_foundation::platform::get_default_db_path());
+
+        if std::env::var_os("CRYPTEX_DB_PATH").is_none() {
+            assert!(path.starts_with(the_foundation::platform::get_data_dir()));
+        }
+    }
+
+    #[test]
+    fn test_explicit_archive_path_is_respected() {
+        let relative = PathBuf::from("data/cryptex.db");
+        assert_eq!(resolve_archive_path(Some(relative.clone())), relative);
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("nested").join("explicit.db");
+        let _archive = TheArchive::the_awakening(db_path.clone()).unwrap();
+        assert!(db_path.exists());
+    }
+
     #[test]
     fn test_store_and_retrieve_scan_metadata() {
         let temp_dir = TempDir::new().unwrap();
@@ -467,6 +1761,30 @@ mod tests {
         assert_eq!(retrieved.status, "running");
     }
 
+    #[test]
+    fn test_archive_metrics_count_transactions() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let before = archive.archive_metrics();
+
+        for i in 0..5 {
+            let metadata = ScanMetadata::new(format!("scan_{}", i), "10.0.0.1".to_string());
+            archive.store_scan_metadata(&metadata).unwrap();
+        }
+        let after_writes = archive.archive_metrics();
+        assert_eq!(after_writes.write_count, before.write_count + 5);
+        assert!(after_writes.avg_write_ms >= 0.0);
+        assert!(after_writes.max_write_ms >= after_writes.avg_write_ms);
+        assert!(after_writes.write_seconds >= before.write_seconds);
+
+        archive.get_scan_metadata("scan_0").unwrap();
+        let after_read = archive.archive_metrics();
+        assert_eq!(after_read.read_count, after_writes.read_count + 1);
+        assert!(after_read.avg_read_ms >= 0.0);
+        assert!(after_read.read_seconds >= after_writes.read_seconds);
+        assert_eq!(after_read.write_count, after_writes.write_count);
+    }
+
     #[test]
     fn test_store_and_retrieve_vulnerability() {
         let temp_dir = TempDir::new().unwrap();
@@ -486,6 +1804,96 @@ mod tests {
         assert_eq!(retrieved.cve_id, "CVE-2021-44228");
     }
 
+    #[test]
+    fn test_delete_vulnerability() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+
+        archive.store_vulnerability(&VulnerabilityScore::new("CVE-2021-44228".to_string())).unwrap();
+        archive.store_vulnerability(&VulnerabilityScore::new("CVE-2023-4966".to_string())).unwrap();
+
+        assert!(archive.delete_vulnerability("CVE-2021-44228").unwrap());
+        assert!(archive.get_vulnerability("CVE-2021-44228").unwrap().is_none());
+        assert!(archive.get_vulnerability("CVE-2023-4966").unwrap().is_some());
+
+        // Already gone, and never stored
+        assert!(!archive.delete_vulnerability("CVE-2021-44228").unwrap());
+        assert!(!archive.delete_vulnerability("CVE-1999-0001").unwrap());
+
+        // The cached-at index entry went with the row
+        assert!(archive.verify().unwrap().is_clean());
+    }
+
+    #[test]
+    fn test_clear_vulnerabilities() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+
+        for cve in ["CVE-2021-44228", "CVE-2023-4966", "CVE-2024-3400"] {
+            archive.store_vulnerability(&VulnerabilityScore::new(cve.to_string())).unwrap();
+        }
+        archive
+            .store_scan_metadata(&ScanMetadata::new("scan_1".to_string(), "10.0.0.1".to_string()))
+            .unwrap();
+
+        assert_eq!(archive.clear_vulnerabilities().unwrap(), 3);
+        assert!(archive.list_vulnerabilities().unwrap().is_empty());
+        assert!(archive.get_scan_metadata("scan_1").unwrap().is_some());
+        assert!(archive.verify().unwrap().is_clean());
+
+        assert_eq!(archive.clear_vulnerabilities().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_severity_index_follows_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let indexed = |severity: CvssSeverity, kev_only: bool| {
+            let mut ids: Vec<String> = archive
+                .indexed_vulnerability_ids(Some(&[severity]), kev_only)
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        let mut score = VulnerabilityScore::new("CVE-2021-44228".to_string());
+        score.cvss_v3 = Some(
+            the_assessor::cvss_v3_from_vector("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H")
+                .unwrap(),
+        );
+        score.kev = Some(the_assessor::KevInfo {
+            is_kev: true,
+            date_added: None,
+            due_date: None,
+            required_action: None,
+            known_ransomware_use: false,
+        });
+        archive.store_vulnerability(&score).unwrap();
+        archive.store_vulnerability(&VulnerabilityScore::new("CVE-2023-4966".to_string())).unwrap();
+        assert_eq!(indexed(CvssSeverity::Critical, true), ["CVE-2021-44228"]);
+        assert_eq!(indexed(CvssSeverity::None, false), ["CVE-2023-4966"]);
+
+        // Re-assessed without the KEV listing and at a lower score
+        score.kev = None;
+        score.cvss_v3 = Some(
+            the_assessor::cvss_v3_from_vector("CVSS:3.1/AV:N/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N")
+                .unwrap(),
+        );
+        archive.store_vulnerability(&score).unwrap();
+        assert!(indexed(CvssSeverity::Critical, false).is_empty());
+        assert!(archive.indexed_vulnerability_ids(None, true).unwrap().unwrap().is_empty());
+        assert_eq!(indexed(CvssSeverity::Low, false), ["CVE-2021-44228"]);
+
+        assert!(archive.delete_vulnerability("CVE-2021-44228").unwrap());
+        assert!(indexed(CvssSeverity::Low, false).is_empty());
+
+        assert_eq!(archive.clear_vulnerabilities().unwrap(), 1);
+        assert!(indexed(CvssSeverity::None, false).is_empty());
+    }
+
     #[test]
     fn test_list_scans() {
         let temp_dir = TempDir::new().unwrap();
@@ -504,6 +1912,68 @@ mod tests {
         assert_eq!(scans.len(), 2);
     }
 
+    fn store_dated_scans(archive: &TheArchive, count: i64) {
+        let base = Utc::now() - chrono::Duration::days(count);
+        // Scan IDs run against start order, so key order alone would be wrong
+        for i in 0..count {
+            let mut scan = ScanMetadata::new(format!("scan_{:03}", count - i), "10.0.0.0/8".to_string());
+            scan.started_at = base + chrono::Duration::hours(i);
+            archive.store_scan_metadata(&scan).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_recent_scans_read_only_what_is_returned() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        store_dated_scans(&archive, 100);
+
+        let before = archive.scan_decode_count();
+        let recent = archive.list_recent_scans(5).unwrap();
+        assert_eq!(archive.scan_decode_count() - before, 5);
+        let ids: Vec<&str> = recent.iter().map(|s| s.scan_id.as_str()).collect();
+        assert_eq!(ids, ["scan_001", "scan_002", "scan_003", "scan_004", "scan_005"]);
+
+        let all = archive.list_scans().unwrap();
+        assert_eq!(all.len(), 100);
+        assert!(all.windows(2).all(|pair| pair[0].started_at >= pair[1].started_at));
+
+        // Moving a scan's start moves it in the listing
+        archive
+            .update_scan_metadata("scan_100", |scan| scan.started_at = Utc::now())
+            .unwrap();
+        assert_eq!(archive.list_recent_scans(1).unwrap()[0].scan_id, "scan_100");
+        assert_eq!(archive.list_scans().unwrap().len(), 100);
+
+        archive.delete_scan("scan_100").unwrap();
+        assert_eq!(archive.list_recent_scans(1).unwrap()[0].scan_id, "scan_001");
+        assert_eq!(archive.list_scans().unwrap().len(), 99);
+        assert!(archive.list_recent_scans(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_scan_index_backfilled_on_open() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.db");
+        {
+            let archive = TheArchive::the_awakening(path.clone()).unwrap();
+            store_dated_scans(&archive, 10);
+
+            // As an archive written before the index existed
+            let write_txn = archive.db.begin_write().unwrap();
+            write_txn.delete_table(SCANS_BY_START_TABLE).unwrap();
+            write_txn.commit().unwrap();
+
+            // Listing still works, by reading and sorting everything
+            assert_eq!(archive.list_scans().unwrap()[0].scan_id, "scan_001");
+        }
+
+        let archive = TheArchive::the_awakening(path).unwrap();
+        let before = archive.scan_decode_count();
+        assert_eq!(archive.list_recent_scans(3).unwrap()[2].scan_id, "scan_003");
+        assert_eq!(archive.scan_decode_count() - before, 3);
+    }
+
     #[test]
     fn test_get_stats() {
         let temp_dir = TempDir::new().unwrap();
@@ -532,4 +2002,188 @@ mod tests {
         let result = archive.get_scan_metadata("nonexistent").unwrap();
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_readonly_archive_refuses_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        assert!(TheArchive::the_awakening_readonly(db_path.clone()).is_err());
+        assert!(!db_path.exists());
+
+        {
+            let archive = TheArchive::the_awakening(db_path.clone()).unwrap();
+            archive
+                .store_scan_metadata(&ScanMetadata::new("scan_1".to_string(), "10.0.0.1".to_string()))
+                .unwrap();
+        }
+
+        let archive = TheArchive::the_awakening_readonly(db_path).unwrap();
+        assert!(archive.is_read_only());
+        assert_eq!(archive.get_stats().unwrap().total_scans, 1);
+        assert_eq!(archive.list_scans().unwrap()[0].scan_id, "scan_1");
+
+        let stored = archive
+            .store_scan_metadata(&ScanMetadata::new("scan_2".to_string(), "10.0.0.2".to_string()));
+        assert!(matches!(stored, Err(CryptexError::ArchiveError(_))));
+        assert!(archive.delete_scan("scan_1").is_err());
+        assert!(archive.begin_batch().is_err());
+        assert_eq!(archive.list_scans().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_recovery_after_restart() {
+        use the_infiltrator::TheInfiltrator;
+
+        let temp_dir = TempDir::new().unwrap();
+        let archive = Arc::new(TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap());
+
+        let infiltrator = TheInfiltrator::the_awakening_with_checkpoints(archive.clone(), 1)
+            .await
+            .unwrap();
+        let options = ScanOptions {
+            ai_enhancement: false,
+            assess: true,
+            max_assessments: Some(1),
+            max_ai_enhancements: None,
+        };
+        let scan_id = infiltrator
+            .start_scan_with_options(&["10.0.0.0/24".to_string()], options)
+            .await
+            .unwrap();
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "10.0.0.5", 443)
+            .await
+            .unwrap();
+        assert_eq!(archive.load_incomplete().unwrap()[0].options, options);
+
+        // Drop mid-scan and start a fresh infiltrator on the same archive
+        drop(infiltrator);
+        let infiltrator = TheInfiltrator::the_awakening_with_checkpoints(archive.clone(), 1)
+            .await
+            .unwrap();
+
+        let report = infiltrator.get_scan_context(&scan_id).await.unwrap();
+        assert_eq!(report.total_vulnerabilities, 1);
+        assert_eq!(report.scan_results[0].cve_id, "CVE-2021-44228");
+        assert_eq!(report.target, "10.0.0.0/24");
+        assert!(report.scan_results[0].vulnerability_score.is_some());
+
+        // The one assessment allowed was spent before the restart
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.6", 80)
+            .await
+            .unwrap();
+        let report = infiltrator.get_scan_context(&scan_id).await.unwrap();
+        let fresh = &report.scan_results[1];
+        assert_eq!(fresh.cve_id, "CVE-2024-0001");
+        assert!(fresh.vulnerability_score.is_none());
+        assert!(fresh.enrichment_skipped);
+
+        infiltrator.end_scan(&scan_id).await.unwrap();
+        let metadata = archive.get_scan_metadata(&scan_id).unwrap().unwrap();
+        assert_eq!(metadata.status, "completed");
+        assert!(archive.load_incomplete().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_writes_only_new_results() {
+        use the_infiltrator::TheInfiltrator;
+
+        let temp_dir = TempDir::new().unwrap();
+        let archive = Arc::new(TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap());
+        let infiltrator = TheInfiltrator::the_awakening_with_checkpoints(archive.clone(), 1)
+            .await
+            .unwrap();
+        let scan_id = infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "10.0.0.5", 443)
+            .await
+            .unwrap();
+
+        // Tags set between checkpoints survive the next one
+        archive
+            .update_scan_metadata(&scan_id, |metadata| metadata.tags = vec!["prod".to_string()])
+            .unwrap();
+        let mut events = archive.subscribe();
+        let writes = archive.archive_metrics().write_count;
+
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.6", 80)
+            .await
+            .unwrap();
+
+        assert_eq!(archive.archive_metrics().write_count, writes + 1);
+        let mut stored = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let ArchiveEvent::ResultStored { cve_id, .. } = event {
+                stored.push(cve_id);
+            }
+        }
+        assert_eq!(stored, ["CVE-2024-0001"]);
+
+        let metadata = archive.get_scan_metadata(&scan_id).unwrap().unwrap();
+        assert_eq!(metadata.tags, ["prod"]);
+        assert_eq!(metadata.total_vulnerabilities, 2);
+        assert_eq!(archive.get_scan_results(&scan_id).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reassess_picks_up_new_kev_listing() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+        use the_assessor::{KevInfo, KevSource, TheAssessor};
+
+        /// A KEV catalog that lists every CVE once `listed` is set
+        struct FlippingKev {
+            listed: AtomicBool,
+        }
+
+        #[async_trait::async_trait]
+        impl KevSource for FlippingKev {
+            async fn lookup(&self, _cve_id: &str) -> CryptexResult<Option<KevInfo>> {
+                Ok(self.listed.load(Ordering::SeqCst).then(|| KevInfo {
+                    is_kev: true,
+                    date_added: Some("2026-10-01".to_string()),
+                    due_date: None,
+                    required_action: None,
+                    known_ransomware_use: false,
+                }))
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        let kev = Arc::new(FlippingKev {
+            listed: AtomicBool::new(false),
+        });
+        let assessor = TheAssessor::the_awakening()
+            .await
+            .unwrap()
+            .with_kev_source(kev.clone())
+            .with_reassess_interval(Duration::ZERO);
+
+        let old = assessor.assess_vulnerability("CVE-2024-1111").await.unwrap();
+        archive
+            .put_vulnerability(&StoredVulnerability {
+                cve_id: old.cve_id.clone(),
+                score: old,
+                cached_at: Utc::now() - chrono::Duration::days(3),
+            })
+            .unwrap();
+        let fresh = assessor.assess_vulnerability("CVE-2024-2222").await.unwrap();
+        archive.store_vulnerability(&fresh).unwrap();
+
+        kev.listed.store(true, Ordering::SeqCst);
+        let day = Duration::from_secs(24 * 60 * 60);
+        assert_eq!(assessor.reassess_stale(&archive, day).await.unwrap(), 1);
+
+        let old = archive.get_vulnerability("CVE-2024-1111").unwrap().unwrap();
+        assert!(old.score.is_kev());
+        assert!(old.cached_at > Utc::now() - chrono::Duration::minutes(1));
+        let fresh = archive.get_vulnerability("CVE-2024-2222").unwrap().unwrap();
+        assert!(!fresh.score.is_kev());
+
+        // Everything is fresh now
+        assert_eq!(assessor.reassess_stale(&archive, day).await.unwrap(), 0);
+    }
 }
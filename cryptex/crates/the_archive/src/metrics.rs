@@ -0,0 +1,158 @@
+//! Transaction latency metrics
+//!
+//! Traditional name: `ArchiveTelemetry`
+//!
+//! redb admits one writer at a time, so a slow commit or a long write holds
+//! up every writer queued behind it. Each write transaction is timed from
+//! the moment it is requested, which includes waiting for the lock, until
+//! its commit returns; each read transaction from being opened until it is
+//! dropped. Rolled-back and failed writes are not counted.
+
+use redb::{CommitError, ReadTransaction, StorageError, WriteTransaction};
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Archive Metrics - Transaction latency since the handle was opened
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveMetrics {
+    /// Committed write transactions
+    pub write_count: u64,
+    /// Total time spent in committed writes, lock wait included
+    pub write_seconds: f64,
+    /// Mean write latency, lock wait included
+    pub avg_write_ms: f64,
+    /// Slowest write so far
+    pub max_write_ms: f64,
+    /// Read transactions finished
+    pub read_count: u64,
+    /// Total time read transactions stayed open
+    pub read_seconds: f64,
+    /// Mean time a read transaction stayed open
+    pub avg_read_ms: f64,
+}
+
+/// Running totals behind [`ArchiveMetrics`]
+#[derive(Debug, Default)]
+pub(crate) struct TxnTimings {
+    write_count: AtomicU64,
+    write_nanos: AtomicU64,
+    max_write_nanos: AtomicU64,
+    read_count: AtomicU64,
+    read_nanos: AtomicU64,
+}
+
+impl TxnTimings {
+    fn record_write(&self, elapsed: Duration) {
+        let nanos = duration_nanos(elapsed);
+        self.write_count.fetch_add(1, Ordering::Relaxed);
+        self.write_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_write_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    fn record_read(&self, elapsed: Duration) {
+        self.read_count.fetch_add(1, Ordering::Relaxed);
+        self.read_nanos.fetch_add(duration_nanos(elapsed), Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ArchiveMetrics {
+        let average = |nanos: &AtomicU64, count: u64| {
+            if count == 0 {
+                0.0
+            } else {
+                nanos_to_ms(nanos.load(Ordering::Relaxed)) / count as f64
+            }
+        };
+        let write_count = self.write_count.load(Ordering::Relaxed);
+        let read_count = self.read_count.load(Ordering::Relaxed);
+
+        ArchiveMetrics {
+            write_count,
+            write_seconds: nanos_to_seconds(self.write_nanos.load(Ordering::Relaxed)),
+            avg_write_ms: average(&self.write_nanos, write_count),
+            max_write_ms: nanos_to_ms(self.max_write_nanos.load(Ordering::Relaxed)),
+            read_count,
+            read_seconds: nanos_to_seconds(self.read_nanos.load(Ordering::Relaxed)),
+            avg_read_ms: average(&self.read_nanos, read_count),
+        }
+    }
+}
+
+fn duration_nanos(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX)
+}
+
+fn nanos_to_ms(nanos: u64) -> f64 {
+    nanos as f64 / 1_000_000.0
+}
+
+fn nanos_to_seconds(nanos: u64) -> f64 {
+    nanos as f64 / 1_000_000_000.0
+}
+
+/// A write transaction timed until it commits
+///
+/// Dereferences to the redb transaction, so tables are opened as usual.
+pub(crate) struct TimedWrite<'a> {
+    txn: WriteTransaction,
+    requested: Instant,
+    timings: &'a TxnTimings,
+}
+
+impl<'a> TimedWrite<'a> {
+    pub(crate) fn new(txn: WriteTransaction, requested: Instant, timings: &'a TxnTimings) -> Self {
+        Self {
+            txn,
+            requested,
+            timings,
+        }
+    }
+
+    /// Commit, recording how long the transaction took if it committed
+    pub(crate) fn commit(self) -> Result<(), CommitError> {
+        self.txn.commit()?;
+        self.timings.record_write(self.requested.elapsed());
+        Ok(())
+    }
+
+    /// Roll back; aborted transactions are not counted
+    pub(crate) fn abort(self) -> Result<(), StorageError> {
+        self.txn.abort()
+    }
+}
+
+impl Deref for TimedWrite<'_> {
+    type Target = WriteTransaction;
+
+    fn deref(&self) -> &WriteTransaction {
+        &self.txn
+    }
+}
+
+/// A read transaction timed until it is dropped
+pub(crate) struct TimedRead<'a> {
+    txn: ReadTransaction,
+    opened: Instant,
+    timings: &'a TxnTimings,
+}
+
+impl<'a> TimedRead<'a> {
+    pub(crate) fn new(txn: ReadTransaction, opened: Instant, timings: &'a TxnTimings) -> Self {
+        Self { txn, opened, timings }
+    }
+}
+
+impl Deref for TimedRead<'_> {
+    type Target = ReadTransaction;
+
+    fn deref(&self) -> &ReadTransaction {
+        &self.txn
+    }
+}
+
+impl Drop for TimedRead<'_> {
+    fn drop(&mut self) {
+        self.timings.record_read(self.opened.elapsed());
+    }
+}
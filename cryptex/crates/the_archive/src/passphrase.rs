@@ -0,0 +1,325 @@
+//! Passphrase-derived archive keys
+//!
+//! Traditional name: `KeyManager`
+//!
+//! Operators unlock a protected archive with a passphrase instead of handing
+//! a raw key to the CLI. The key is derived with argon2id from the passphrase
+//! and a random salt kept in the archive's meta table, together with the KDF
+//! parameters, so every later open derives the same key. A verification token
+//! (a hash of the key, never the key itself) lets a wrong passphrase be
+//! rejected before anything else touches the archive. Once unlocked, the key
+//! stays in memory for the lifetime of the `TheArchive` handle.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use redb::TableError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{TheArchive, META_TABLE};
+use the_foundation::{CryptexError, CryptexResult};
+
+const SALT_KEY: &str = "kdf_salt";
+const PARAMS_KEY: &str = "kdf_params";
+const CHECK_KEY: &str = "key_check";
+
+/// Domain separator so the verification token is never a bare hash of the key
+const CHECK_CONTEXT: &[u8] = b"cryptex-archive-key-check\0";
+
+const SALT_LEN: usize = 16;
+
+/// Argon2id cost parameters
+///
+/// Chosen when an archive is first protected and stored with it; later opens
+/// always use the stored values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes
+    pub iterations: u32,
+    /// Degree of parallelism (lanes)
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    /// The argon2 crate's recommended defaults (19 MiB, 2 passes, 1 lane)
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// A derived 256-bit archive key
+#[derive(Clone, PartialEq, Eq)]
+pub struct ArchiveKey([u8; 32]);
+
+impl ArchiveKey {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    fn check_token(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(CHECK_CONTEXT);
+        hasher.update(self.0);
+        hasher.finalize().into()
+    }
+}
+
+impl std::fmt::Debug for ArchiveKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ArchiveKey(..)")
+    }
+}
+
+/// Source of the passphrase, e.g. a terminal prompt
+///
+/// `first_use` is true when the archive is being protected for the first
+/// time, so an interactive provider can ask for confirmation.
+pub trait PassphraseProvider {
+    fn passphrase(&self, first_use: bool) -> CryptexResult<String>;
+}
+
+impl<F> PassphraseProvider for F
+where
+    F: Fn(bool) -> CryptexResult<String>,
+{
+    fn passphrase(&self, first_use: bool) -> CryptexResult<String> {
+        self(first_use)
+    }
+}
+
+/// What the meta table holds for a protected archive
+struct KeyMeta {
+    salt: Vec<u8>,
+    params: KdfParams,
+    check: Vec<u8>,
+}
+
+impl TheArchive {
+    /// Whether the archive has been protected with a passphrase
+    pub fn is_passphrase_protected(&self) -> CryptexResult<bool> {
+        Ok(self.read_key_meta()?.is_some())
+    }
+
+    /// Refuse a passphrase-protected archive
+    ///
+    /// For processes that run unattended and cannot prompt, such as the
+    /// servers: they must not serve a protected archive without its key.
+    pub fn ensure_unprotected(&self) -> CryptexResult<()> {
+        if self.is_passphrase_protected()? {
+            return Err(CryptexError::validation(
+                "Archive is passphrase-protected and cannot be unlocked without a prompt",
+            ));
+        }
+        Ok(())
+    }
+
+    /// The key unlocked earlier in this process, if any
+    pub fn archive_key(&self) -> Option<&ArchiveKey> {
+        self.key.get()
+    }
+
+    /// Unlock the archive with a passphrase, deriving and caching its key
+    ///
+    /// On an unprotected archive the passphrase protects it from now on,
+    /// using `params` and a fresh salt. On a protected archive the stored
+    /// parameters are used and a wrong passphrase is a validation error.
+    /// Once unlocked, later calls return the cached key without asking again.
+    pub fn unlock(
+        &self,
+        provider: &dyn PassphraseProvider,
+        params: KdfParams,
+    ) -> CryptexResult<&ArchiveKey> {
+        if let Some(key) = self.key.get() {
+            return Ok(key);
+        }
+
+        let key = match self.read_key_meta()? {
+            Some(meta) => {
+                let passphrase = provider.passphrase(false)?;
+                let key = derive_key(&passphrase, &meta.salt, meta.params)?;
+                if !constant_time_eq(&key.check_token(), &meta.check) {
+                    return Err(CryptexError::validation("Wrong passphrase for archive"));
+                }
+                key
+            }
+            None => {
+                let passphrase = provider.passphrase(true)?;
+                if passphrase.is_empty() {
+                    return Err(CryptexError::validation("Archive passphrase must not be empty"));
+                }
+
+                let mut salt = [0u8; SALT_LEN];
+                getrandom::getrandom(&mut salt).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to generate salt: {}", e))
+                })?;
+                let key = derive_key(&passphrase, &salt, params)?;
+                self.write_key_meta(&KeyMeta {
+                    salt: salt.to_vec(),
+                    params,
+                    check: key.check_token().to_vec(),
+                })?;
+                tracing::info!("Archive is now passphrase-protected");
+                key
+            }
+        };
+
+        Ok(self.key.get_or_init(|| key))
+    }
+
+    fn read_key_meta(&self) -> CryptexResult<Option<KeyMeta>> {
+        let read_txn = self.begin_read()?;
+
+        // Archives that were never protected may not have the table at all
+        let table = match read_txn.open_table(META_TABLE) {
+            Ok(table) => table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(e) => {
+                return Err(CryptexError::ArchiveError(format!(
+                    "Failed to open meta table: {}",
+                    e
+                )))
+            }
+        };
+
+        let read = |key: &str| -> CryptexResult<Option<Vec<u8>>> {
+            let value = table.get(key).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to read {}: {}", key, e))
+            })?;
+            Ok(value.map(|v| v.value().to_vec()))
+        };
+
+        let (Some(salt), Some(params), Some(check)) =
+            (read(SALT_KEY)?, read(PARAMS_KEY)?, read(CHECK_KEY)?)
+        else {
+            return Ok(None);
+        };
+        let params = serde_cbor::from_slice(&params).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to deserialize KDF parameters: {}", e))
+        })?;
+
+        Ok(Some(KeyMeta { salt, params, check }))
+    }
+
+    fn write_key_meta(&self, meta: &KeyMeta) -> CryptexResult<()> {
+        let params = serde_cbor::to_vec(&meta.params).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to serialize KDF parameters: {}", e))
+        })?;
+
+        let write_txn = self.begin_write()?;
+
+        {
+            let mut table = write_txn.open_table(META_TABLE).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to open meta table: {}", e))
+            })?;
+
+            for (key, value) in [
+                (SALT_KEY, meta.salt.as_slice()),
+                (PARAMS_KEY, params.as_slice()),
+                (CHECK_KEY, meta.check.as_slice()),
+            ] {
+                table.insert(key, value).map_err(|e| {
+                    CryptexError::ArchiveError(format!("Failed to store {}: {}", key, e))
+                })?;
+            }
+        }
+
+        write_txn.commit().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to commit key metadata: {}", e))
+        })
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], params: KdfParams) -> CryptexResult<ArchiveKey> {
+    let params = Params::new(params.memory_kib, params.iterations, params.parallelism, Some(32))
+        .map_err(|e| CryptexError::validation(format!("Invalid KDF parameters: {}", e)))?;
+
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| CryptexError::ArchiveError(format!("Key derivation failed: {}", e)))?;
+
+    Ok(ArchiveKey(key))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+
+    /// Cheap parameters so the tests don't spend seconds in argon2
+    const TEST_PARAMS: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn fixed(passphrase: &'static str) -> impl Fn(bool) -> CryptexResult<String> {
+        move |_| Ok(passphrase.to_string())
+    }
+
+    #[test]
+    fn test_unlock_with_correct_and_wrong_passphrase() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("protected.db");
+
+        let first_key = {
+            let archive = TheArchive::the_awakening(path.clone()).unwrap();
+            assert!(!archive.is_passphrase_protected().unwrap());
+
+            let asked_first_use = Cell::new(false);
+            let provider = |first_use: bool| {
+                asked_first_use.set(first_use);
+                Ok("correct horse".to_string())
+            };
+            let key = archive.unlock(&provider, TEST_PARAMS).unwrap().clone();
+            assert!(asked_first_use.get());
+            assert!(archive.is_passphrase_protected().unwrap());
+
+            // Cached for the life of the handle: no second prompt
+            let never = |_: bool| -> CryptexResult<String> { panic!("prompted twice") };
+            assert_eq!(archive.unlock(&never, TEST_PARAMS).unwrap(), &key);
+            key
+        };
+
+        // Each new handle prompts again; the stored salt gives the same key
+        let archive = TheArchive::the_awakening(path.clone()).unwrap();
+        assert!(archive.archive_key().is_none());
+        let wrong = archive.unlock(&fixed("battery staple"), TEST_PARAMS);
+        assert!(matches!(wrong, Err(CryptexError::ValidationError(_))));
+        assert!(archive.archive_key().is_none());
+
+        // The stored parameters win over whatever the caller passes later
+        let key = archive.unlock(&fixed("correct horse"), KdfParams::default()).unwrap();
+        assert_eq!(key, &first_key);
+    }
+
+    #[test]
+    fn test_empty_passphrase_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("empty.db")).unwrap();
+
+        assert!(archive.unlock(&fixed(""), TEST_PARAMS).is_err());
+        assert!(!archive.is_passphrase_protected().unwrap());
+    }
+
+    #[test]
+    fn test_ensure_unprotected() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("protected.db")).unwrap();
+        archive.ensure_unprotected().unwrap();
+
+        archive.unlock(&fixed("correct horse"), TEST_PARAMS).unwrap();
+        let err = archive.ensure_unprotected().unwrap_err();
+        assert!(err.to_string().contains("passphrase-protected"));
+    }
+}
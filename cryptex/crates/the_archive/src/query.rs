@@ -0,0 +1,1809 @@
+//! Read-side queries over The Archive
+//!
+//! Traditional name: `QueryService` or `Repository`
+//!
+//! Combines the raw tables into views the API and dashboards need, without
+//! touching the storage layout.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::{write::GzEncoder, Compression};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufWriter, Write};
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::export::{self, ExportFormat, StreamingExport};
+use crate::{ScanMetadata, ScanResult, StoredScanResult, StoredVulnerability, TheArchive};
+use the_assessor::CvssSeverity;
+use the_foundation::{CryptexError, CryptexResult};
+use the_infiltrator::ConfidenceLevel;
+
+/// Maximum number of findings returned when a query does not set a limit
+pub const DEFAULT_QUERY_LIMIT: usize = 1000;
+
+/// Query Filters - Criteria for selecting archived findings
+///
+/// All criteria are optional and combined with AND.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryFilters {
+    /// Only findings from this scan
+    pub scan_id: Option<String>,
+    /// Exactly this severity; deserialized from a case-insensitive name
+    /// (e.g. "critical"), and unknown names are rejected
+    ///
+    /// Findings that have not been assessed have no severity and never match
+    /// either severity criterion.
+    #[serde(deserialize_with = "deserialize_named")]
+    pub severity: Option<CvssSeverity>,
+    /// This severity or higher
+    #[serde(deserialize_with = "deserialize_named")]
+    pub min_severity: Option<CvssSeverity>,
+    /// Detected with this confidence or higher, e.g. "high"
+    ///
+    /// Findings without a confidence never match.
+    #[serde(deserialize_with = "deserialize_named")]
+    pub min_confidence: Option<ConfidenceLevel>,
+    /// Only Known Exploited Vulnerabilities
+    pub kev_only: bool,
+    /// Only findings on this host
+    pub host: Option<String>,
+    /// Only findings on this port
+    pub port: Option<u16>,
+    /// Only this CVE
+    pub cve_id: Option<String>,
+    /// Maximum number of findings (defaults to `DEFAULT_QUERY_LIMIT`)
+    pub limit: Option<usize>,
+    /// Rows to skip before the first result
+    ///
+    /// Kept for compatibility; each page re-reads the skipped rows, so prefer
+    /// `cursor` for deep pagination. Ignored when a cursor is set.
+    pub offset: Option<usize>,
+    /// Order of paginated vulnerability queries
+    pub sort: VulnerabilitySort,
+    /// Resume after this cursor (from a previous page's `next_cursor`)
+    pub cursor: Option<String>,
+    /// Only assessments cached strictly after this instant (delta sync)
+    pub changed_since: Option<DateTime<Utc>>,
+}
+
+/// Deserialize an optional level from its case-insensitive name
+fn deserialize_named<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|name| name.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// Sort order for paginated vulnerability queries
+///
+/// Both orders follow an ordered key in the archive, which is what makes
+/// cursor pagination a range read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VulnerabilitySort {
+    /// Ascending CVE ID
+    #[default]
+    CveId,
+    /// Oldest assessment first
+    CachedAt,
+}
+
+impl VulnerabilitySort {
+    fn as_str(self) -> &'static str {
+        match self {
+            VulnerabilitySort::CveId => "cve_id",
+            VulnerabilitySort::CachedAt => "cached_at",
+        }
+    }
+}
+
+/// One page of query results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Pass to `QueryFilters::after_cursor` for the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Cursors are opaque to callers: the sort they were issued for, then the last key
+fn encode_cursor(sort: VulnerabilitySort, key: &str) -> String {
+    format!("{}:{}", sort.as_str(), key)
+}
+
+fn decode_cursor(cursor: &str, sort: VulnerabilitySort) -> CryptexResult<String> {
+    match cursor.split_once(':') {
+        Some((tag, key)) if tag == sort.as_str() => Ok(key.to_string()),
+        _ => Err(CryptexError::validation(format!(
+            "Cursor is not valid for sort order {}",
+            sort.as_str()
+        ))),
+    }
+}
+
+impl QueryFilters {
+    /// Require exactly the named severity (e.g. "high")
+    pub fn severity(mut self, name: &str) -> CryptexResult<Self> {
+        self.severity = Some(name.parse()?);
+        Ok(self)
+    }
+
+    /// Require the named severity or higher (e.g. "high" also admits critical)
+    pub fn min_severity(mut self, name: &str) -> CryptexResult<Self> {
+        self.min_severity = Some(name.parse()?);
+        Ok(self)
+    }
+
+    /// Require the named confidence or higher (e.g. "medium" also admits high)
+    pub fn min_confidence(mut self, name: &str) -> CryptexResult<Self> {
+        self.min_confidence = Some(name.parse()?);
+        Ok(self)
+    }
+
+    /// Continue from a cursor returned by a previous page
+    pub fn after_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// True if a vulnerability query can be paged by position in the table
+    ///
+    /// That needs CVE ID order, no cursor, and no criterion that applies to
+    /// assessments, so that every stored row is part of the result.
+    fn pages_at_storage(&self) -> bool {
+        self.sort == VulnerabilitySort::CveId
+            && self.cursor.is_none()
+            && self.severity.is_none()
+            && self.min_severity.is_none()
+            && !self.kev_only
+            && self.cve_id.is_none()
+            && self.changed_since.is_none()
+    }
+
+    /// Check a finding's severity (`None` if unassessed) against both criteria
+    fn matches_severity(&self, actual: Option<CvssSeverity>) -> bool {
+        if self.severity.is_some_and(|wanted| actual != Some(wanted)) {
+            return false;
+        }
+
+        self.min_severity
+            .is_none_or(|minimum| actual.is_some_and(|a| a.meets_minimum(minimum)))
+    }
+
+    /// The severities the severity criteria admit, or `None` if there are none
+    fn admitted_severities(&self) -> Option<Vec<CvssSeverity>> {
+        if self.severity.is_none() && self.min_severity.is_none() {
+            return None;
+        }
+
+        Some(
+            CvssSeverity::ALL
+                .into_iter()
+                .filter(|severity| self.matches_severity(Some(*severity)))
+                .collect(),
+        )
+    }
+
+    /// Check whether a stored assessment satisfies the criteria that apply to it
+    ///
+    /// Scan and host filters have no meaning for assessments and are ignored.
+    pub fn matches_vulnerability(&self, stored: &StoredVulnerability) -> bool {
+        if !self.matches_severity(Some(stored.score.severity())) {
+            return false;
+        }
+
+        if self.kev_only && !stored.score.is_kev() {
+            return false;
+        }
+
+        if self
+            .cve_id
+            .as_ref()
+            .is_some_and(|c| !c.eq_ignore_ascii_case(&stored.cve_id))
+        {
+            return false;
+        }
+
+        if self.changed_since.is_some_and(|since| stored.cached_at <= since) {
+            return false;
+        }
+
+        true
+    }
+
+    /// Check whether a stored finding satisfies every criterion
+    pub fn matches(&self, stored: &StoredScanResult) -> bool {
+        let result = &stored.result;
+
+        if self.scan_id.as_ref().is_some_and(|id| *id != stored.scan_id) {
+            return false;
+        }
+
+        if !self.matches_severity(result.severity()) {
+            return false;
+        }
+
+        if self.kev_only && !result.is_kev() {
+            return false;
+        }
+
+        if self
+            .min_confidence
+            .is_some_and(|minimum| !result.meets_confidence(minimum))
+        {
+            return false;
+        }
+
+        if self.host.as_ref().is_some_and(|h| *h != result.host) {
+            return false;
+        }
+
+        if self.port.is_some_and(|p| p != result.port) {
+            return false;
+        }
+
+        if self
+            .cve_id
+            .as_ref()
+            .is_some_and(|c| !c.eq_ignore_ascii_case(&result.cve_id))
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Scan Filters - Criteria for selecting archived scans
+///
+/// All criteria are optional and combined with AND.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanFilters {
+    /// At least this many vulnerabilities in total
+    pub min_vulnerabilities: Option<usize>,
+    /// At least this many critical findings
+    pub min_critical: Option<usize>,
+    /// At least this many high findings
+    pub min_high: Option<usize>,
+    /// Whether the scan found a Known Exploited Vulnerability
+    ///
+    /// Scan metadata carries no KEV count, so this looks at the scan's archived
+    /// findings. Scans whose findings were never archived count as KEV-free.
+    pub has_kev: Option<bool>,
+    /// Scans carrying all of these tags; comma-separated in a query string
+    #[serde(deserialize_with = "deserialize_tag_list")]
+    pub tags: Option<Vec<String>>,
+    /// At most this many scans, the most recently started
+    pub limit: Option<usize>,
+}
+
+/// Deserialize a comma-separated tag list such as `prod,pci-scope`
+fn deserialize_tag_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|list| ScanMetadata::normalize_tags(list.split(',').map(str::to_string).collect()))
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+impl ScanFilters {
+    /// Require at least `count` vulnerabilities in total
+    pub fn min_vulnerabilities(mut self, count: usize) -> Self {
+        self.min_vulnerabilities = Some(count);
+        self
+    }
+
+    /// Require at least `count` critical findings
+    pub fn min_critical(mut self, count: usize) -> Self {
+        self.min_critical = Some(count);
+        self
+    }
+
+    /// Require at least `count` high findings
+    pub fn min_high(mut self, count: usize) -> Self {
+        self.min_high = Some(count);
+        self
+    }
+
+    /// Require the scan to have (or not have) a KEV finding
+    pub fn has_kev(mut self, has_kev: bool) -> Self {
+        self.has_kev = Some(has_kev);
+        self
+    }
+
+    /// Require the scan to carry all of `tags`
+    pub fn tags<I, T>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.tags = Some(tags.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Return at most `limit` scans
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Check the criteria answered by the metadata alone
+    pub fn matches_counts(&self, scan: &ScanMetadata) -> bool {
+        let at_least = |minimum: Option<usize>, actual: usize| minimum.is_none_or(|m| actual >= m);
+
+        at_least(self.min_vulnerabilities, scan.total_vulnerabilities)
+            && at_least(self.min_critical, scan.critical)
+            && at_least(self.min_high, scan.high)
+            && self.tags.as_ref().is_none_or(|tags| scan.has_tags(tags))
+    }
+}
+
+/// A single entry in the recent activity feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ActivityEvent {
+    ScanStarted {
+        scan_id: String,
+        timestamp: DateTime<Utc>,
+        summary: String,
+    },
+    ScanCompleted {
+        scan_id: String,
+        timestamp: DateTime<Utc>,
+        summary: String,
+    },
+    VulnerabilityAssessed {
+        cve_id: String,
+        timestamp: DateTime<Utc>,
+        summary: String,
+    },
+}
+
+impl ActivityEvent {
+    /// When the event happened
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            ActivityEvent::ScanStarted { timestamp, .. }
+            | ActivityEvent::ScanCompleted { timestamp, .. }
+            | ActivityEvent::VulnerabilityAssessed { timestamp, .. } => *timestamp,
+        }
+    }
+
+    /// Human-readable one-line summary
+    pub fn summary(&self) -> &str {
+        match self {
+            ActivityEvent::ScanStarted { summary, .. }
+            | ActivityEvent::ScanCompleted { summary, .. }
+            | ActivityEvent::VulnerabilityAssessed { summary, .. } => summary,
+        }
+    }
+}
+
+/// How much of the estate's exposure is in CISA's KEV catalog
+///
+/// Findings are counted once per `(cve_id, host, port)`, however many scans
+/// reported them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KevCoverage {
+    pub total_findings: usize,
+    pub kev_findings: usize,
+    /// Distinct hosts with at least one KEV finding
+    pub kev_hosts_affected: usize,
+    /// KEV findings whose CISA remediation due date has passed
+    pub overdue_kev: usize,
+}
+
+/// How a scan's findings differ from the previous completed scan of its target
+///
+/// Findings are matched on `(cve_id, host, port)`. When there is no previous
+/// scan every finding of the current one counts as added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanDelta {
+    pub scan_id: String,
+    /// The scan compared against, if the target had been scanned before
+    pub previous_scan_id: Option<String>,
+    /// Findings only in the current scan
+    pub added: Vec<ScanResult>,
+    /// Findings of the previous scan the current one no longer reports
+    pub removed: Vec<ScanResult>,
+    /// Findings reported by both, as the current scan reported them
+    pub unchanged: Vec<ScanResult>,
+}
+
+/// A finished export on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedFile {
+    pub path: PathBuf,
+    /// Findings written
+    pub rows: usize,
+    /// Size of the file, after compression
+    pub bytes: u64,
+}
+
+/// Archive Query - Aggregated read views over The Archive
+pub struct ArchiveQuery {
+    pub(crate) archive: Arc<TheArchive>,
+}
+
+impl ArchiveQuery {
+    /// Build a query view over an existing archive handle
+    pub fn from_archive(archive: Arc<TheArchive>) -> Self {
+        Self { archive }
+    }
+
+    /// Build a query view over an archive opened read-only
+    ///
+    /// For processes that only serve reads, such as dashboards; see
+    /// [`TheArchive::the_awakening_readonly`].
+    pub fn open_readonly(path: impl Into<Option<std::path::PathBuf>>) -> CryptexResult<Self> {
+        Ok(Self::from_archive(Arc::new(TheArchive::the_awakening_readonly(path)?)))
+    }
+
+    /// Find archived findings matching the filters
+    ///
+    /// Results are capped at the filter limit, or `DEFAULT_QUERY_LIMIT` when unset.
+    pub fn find_results(&self, filters: &QueryFilters) -> CryptexResult<Vec<StoredScanResult>> {
+        let limit = filters.limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+
+        let results = if let Some(scan_id) = &filters.scan_id {
+            self.archive
+                .get_scan_results(scan_id)?
+                .into_iter()
+                .map(|result| StoredScanResult {
+                    scan_id: scan_id.clone(),
+                    result,
+                })
+                .collect()
+        } else {
+            self.archive.list_scan_results()?
+        };
+
+        Ok(results
+            .into_iter()
+            .filter(|stored| filters.matches(stored))
+            .skip(filters.offset.unwrap_or(0))
+            .take(limit)
+            .collect())
+    }
+
+    /// Page through archived assessments matching the filters
+    ///
+    /// With a cursor, reading starts directly after the last key of the previous
+    /// page. Without one, `offset` rows are skipped from the start. When sorted
+    /// by `CachedAt`, `changed_since` also starts the read at that instant rather
+    /// than filtering from the beginning.
+    pub fn query_vulnerabilities(&self, filters: &QueryFilters) -> CryptexResult<Page<StoredVulnerability>> {
+        self.vulnerabilities_page(filters, Some(filters.limit.unwrap_or(DEFAULT_QUERY_LIMIT)))
+    }
+
+    /// Every archived assessment matching the filters, ignoring their limit
+    ///
+    /// All matches are collected in memory, so a filter without
+    /// `changed_since` returns the whole vulnerability table. Meant for
+    /// delta syncs, where each call after the first is small.
+    pub fn all_vulnerabilities(&self, filters: &QueryFilters) -> CryptexResult<Vec<StoredVulnerability>> {
+        Ok(self.vulnerabilities_page(filters, None)?.items)
+    }
+
+    /// One page of matching assessments; `None` reads to the end
+    fn vulnerabilities_page(
+        &self,
+        filters: &QueryFilters,
+        limit: Option<usize>,
+    ) -> CryptexResult<Page<StoredVulnerability>> {
+        // Unfiltered offset pages can be cut straight from the table
+        if let Some(limit) = limit.filter(|_| filters.pages_at_storage()) {
+            let offset = filters.offset.unwrap_or(0);
+            let mut items = self
+                .archive
+                .list_vulnerabilities_page(offset, limit.saturating_add(1))?;
+
+            let next_cursor = if items.len() > limit {
+                items.truncate(limit);
+                items
+                    .last()
+                    .map(|last| encode_cursor(VulnerabilitySort::CveId, &last.cve_id))
+            } else {
+                None
+            };
+
+            return Ok(Page { items, next_cursor });
+        }
+
+        let after = filters
+            .cursor
+            .as_deref()
+            .map(|cursor| decode_cursor(cursor, filters.sort))
+            .transpose()?;
+
+        let since_key = match (filters.sort, &filters.changed_since) {
+            (VulnerabilitySort::CachedAt, Some(since)) => Some(crate::cached_after_key(since)),
+            _ => None,
+        };
+        let lower = match (&after, &since_key) {
+            (Some(after), _) => Bound::Excluded(after.as_str()),
+            (None, Some(since_key)) => Bound::Included(since_key.as_str()),
+            (None, None) => Bound::Unbounded,
+        };
+
+        // The severity and KEV indexes rule out most rows before they are read
+        let candidates = self
+            .archive
+            .indexed_vulnerability_ids(filters.admitted_severities().as_deref(), filters.kev_only)?;
+
+        let mut skip = if after.is_some() { 0 } else { filters.offset.unwrap_or(0) };
+        let mut items = Vec::new();
+        let mut last_key = None;
+        let mut has_more = false;
+
+        self.archive
+            .visit_vulnerabilities(filters.sort, lower, candidates.as_ref(), |key, stored| {
+                if !filters.matches_vulnerability(&stored) {
+                    return true;
+                }
+                if skip > 0 {
+                    skip -= 1;
+                    return true;
+                }
+                if Some(items.len()) == limit {
+                    has_more = true;
+                    return false;
+                }
+
+                last_key = Some(key);
+                items.push(stored);
+                true
+            })?;
+
+        let next_cursor = if has_more {
+            last_key.map(|key| encode_cursor(filters.sort, &key))
+        } else {
+            None
+        };
+
+        Ok(Page { items, next_cursor })
+    }
+
+    /// Fetch the archived assessments of a set of CVEs, keyed by CVE ID
+    ///
+    /// All ids are read in one transaction. IDs must be given as archived
+    /// (normalized); ones without an assessment are absent from the map.
+    pub fn get_vulnerabilities(
+        &self,
+        cve_ids: &[String],
+    ) -> CryptexResult<HashMap<String, StoredVulnerability>> {
+        let unique: HashSet<&str> = cve_ids.iter().map(String::as_str).collect();
+
+        Ok(self
+            .archive
+            .get_vulnerabilities_by_id(unique)?
+            .into_iter()
+            .map(|(cve_id, stored)| (cve_id.to_string(), stored))
+            .collect())
+    }
+
+    /// Find archived scans matching the filters, in `list_scans` order
+    ///
+    /// Scans are read newest first and reading stops once `limit` match.
+    ///
+    /// `has_kev` decides a finding's KEV status as [`Self::kev_coverage`]
+    /// does: from the archived assessment of its CVE when there is one, read
+    /// through the KEV index, and from the score stored with the finding
+    /// otherwise. Scan archiving stores only the latter, so a scan's own KEV
+    /// findings count without a separate assessment. A read-only archive that
+    /// predates the index uses the stored scores alone.
+    pub fn find_scans(&self, filters: &ScanFilters) -> CryptexResult<Vec<ScanMetadata>> {
+        let limit = filters.limit.unwrap_or(usize::MAX);
+        let mut scans = Vec::new();
+        let mut failure = None;
+        if limit == 0 {
+            return Ok(scans);
+        }
+        let assessed_kev = match filters.has_kev {
+            Some(_) => self
+                .archive
+                .indexed_vulnerability_ids(None, true)?
+                .map(|kev_ids| self.archive.vulnerability_ids().map(|ids| (ids, kev_ids)))
+                .transpose()?,
+            None => None,
+        };
+        let is_kev = |result: &ScanResult| match &assessed_kev {
+            Some((assessed, kev_ids)) if assessed.contains(&result.cve_id) => {
+                kev_ids.contains(&result.cve_id)
+            }
+            _ => result.is_kev(),
+        };
+
+        self.archive.visit_scans(|scan| {
+            if !filters.matches_counts(&scan) {
+                return true;
+            }
+
+            // Only read findings for scans that passed the cheap checks
+            if let Some(has_kev) = filters.has_kev {
+                let found_kev = self
+                    .archive
+                    .get_scan_results(&scan.scan_id)
+                    .map(|results| results.iter().any(is_kev));
+                let found_kev = match found_kev {
+                    Ok(found_kev) => found_kev,
+                    Err(e) => {
+                        failure = Some(e);
+                        return false;
+                    }
+                };
+                if found_kev != has_kev {
+                    return true;
+                }
+            }
+
+            scans.push(scan);
+            scans.len() < limit
+        })?;
+
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(scans),
+        }
+    }
+
+    /// Run a filtered query and export the findings
+    pub fn export_findings(&self, filters: &QueryFilters, format: ExportFormat) -> CryptexResult<String> {
+        let findings = self.find_results(filters)?;
+        export::export_findings(&findings, format)
+    }
+
+    /// Export the findings matching the filters to a file
+    ///
+    /// Findings are written as they are read, so memory use does not grow
+    /// with the export. Unlike the other queries, an unset limit exports
+    /// every match. With `compress` the file is gzip-compressed. SARIF is
+    /// built in memory first, since its rules come before its results.
+    ///
+    /// The export is written to a temporary file beside `path` and renamed
+    /// into place once complete, so a failed export leaves whatever was at
+    /// `path` untouched.
+    pub fn export_to_file(
+        &self,
+        filters: &QueryFilters,
+        format: ExportFormat,
+        path: impl AsRef<Path>,
+        compress: bool,
+    ) -> CryptexResult<ExportedFile> {
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| {
+            CryptexError::ArchiveError(format!("Failed to write export {}: {}", path.display(), e))
+        };
+
+        // Dropping the temporary file on an error deletes it
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let temp = tempfile::NamedTempFile::new_in(dir).map_err(io_error)?;
+        let file = BufWriter::new(temp);
+        let (file, rows) = if compress {
+            let (gzip, rows) =
+                self.write_export(filters, format, GzEncoder::new(file, Compression::default()))?;
+            (gzip.finish().map_err(io_error)?, rows)
+        } else {
+            self.write_export(filters, format, file)?
+        };
+        file.into_inner()
+            .map_err(|e| io_error(e.into_error()))?
+            .persist(path)
+            .map_err(|e| io_error(e.error))?;
+
+        let bytes = std::fs::metadata(path).map_err(io_error)?.len();
+        tracing::info!("Exported {} findings to {} ({} bytes)", rows, path.display(), bytes);
+        Ok(ExportedFile {
+            path: path.to_path_buf(),
+            rows,
+            bytes,
+        })
+    }
+
+    /// Write the matching findings to `out`, returning it and the row count
+    fn write_export<W: Write>(
+        &self,
+        filters: &QueryFilters,
+        format: ExportFormat,
+        mut out: W,
+    ) -> CryptexResult<(W, usize)> {
+        let limit = filters.limit.unwrap_or(usize::MAX);
+        let mut skip = filters.offset.unwrap_or(0);
+
+        if format == ExportFormat::Sarif {
+            let filters = QueryFilters {
+                limit: Some(limit),
+                ..filters.clone()
+            };
+            let findings = self.find_results(&filters)?;
+            let sarif = export::export_sarif(&findings)?;
+            out.write_all(sarif.as_bytes()).map_err(|e| {
+                CryptexError::ArchiveError(format!("Failed to write export: {}", e))
+            })?;
+            return Ok((out, findings.len()));
+        }
+
+        let mut stream = StreamingExport::begin(out, format)?;
+        let mut failure = None;
+        if limit > 0 {
+            self.archive.visit_scan_results(filters.scan_id.as_deref(), |stored| {
+                if !filters.matches(&stored) {
+                    return true;
+                }
+                if skip > 0 {
+                    skip -= 1;
+                    return true;
+                }
+                if let Err(e) = stream.push(&stored) {
+                    failure = Some(e);
+                    return false;
+                }
+                stream.rows() < limit
+            })?;
+        }
+
+        match failure {
+            Some(e) => Err(e),
+            None => stream.finish(),
+        }
+    }
+
+    /// KEV exposure across every archived finding
+    ///
+    /// A finding's KEV status comes from the archived assessment of its CVE
+    /// when there is one, since re-assessment keeps that current, and from the
+    /// score stored with the finding otherwise.
+    pub fn kev_coverage(&self) -> CryptexResult<KevCoverage> {
+        let assessments: HashMap<String, StoredVulnerability> = self
+            .archive
+            .list_vulnerabilities()?
+            .into_iter()
+            .map(|stored| (stored.cve_id.clone(), stored))
+            .collect();
+        let today = Utc::now().date_naive();
+
+        let mut coverage = KevCoverage::default();
+        let mut seen = HashSet::new();
+        let mut kev_hosts = HashSet::new();
+        for stored in self.archive.list_scan_results()? {
+            let result = stored.result;
+            if !seen.insert((result.cve_id.clone(), result.host.clone(), result.port)) {
+                continue;
+            }
+            coverage.total_findings += 1;
+
+            let kev = assessments
+                .get(&result.cve_id)
+                .map(|stored| &stored.score)
+                .or(result.vulnerability_score.as_ref())
+                .and_then(|score| score.kev.as_ref())
+                .filter(|kev| kev.is_kev);
+            let Some(kev) = kev else {
+                continue;
+            };
+
+            coverage.kev_findings += 1;
+            let overdue = kev
+                .due_date
+                .as_deref()
+                .and_then(|due| NaiveDate::parse_from_str(due, "%Y-%m-%d").ok())
+                .is_some_and(|due| due < today);
+            if overdue {
+                coverage.overdue_kev += 1;
+            }
+            kev_hosts.insert(result.host);
+        }
+        coverage.kev_hosts_affected = kev_hosts.len();
+
+        Ok(coverage)
+    }
+
+    /// The latest completed scan of exactly `target` started before `before`
+    pub fn previous_scan_for_target(
+        &self,
+        target: &str,
+        before: DateTime<Utc>,
+    ) -> CryptexResult<Option<ScanMetadata>> {
+        // Newest first, so the first match is the latest
+        let mut previous = None;
+        self.archive.visit_scans(|scan| {
+            if scan.target == target && scan.status == "completed" && scan.started_at < before {
+                previous = Some(scan);
+                return false;
+            }
+            true
+        })?;
+
+        Ok(previous)
+    }
+
+    /// Findings added, removed and unchanged since the previous scan of the
+    /// same target
+    pub fn scan_delta(&self, scan_id: &str) -> CryptexResult<ScanDelta> {
+        let current = self
+            .archive
+            .get_scan_metadata(scan_id)?
+            .ok_or_else(|| CryptexError::not_found(format!("Scan {} not found", scan_id)))?;
+        let previous = self.previous_scan_for_target(&current.target, current.started_at)?;
+
+        let key = |result: &ScanResult| (result.cve_id.clone(), result.host.clone(), result.port);
+        let previous_results = match &previous {
+            Some(scan) => self.archive.get_scan_results(&scan.scan_id)?,
+            None => Vec::new(),
+        };
+        let previous_keys: HashSet<_> = previous_results.iter().map(key).collect();
+
+        let mut delta = ScanDelta {
+            scan_id: current.scan_id,
+            previous_scan_id: previous.map(|scan| scan.scan_id),
+            added: Vec::new(),
+            removed: Vec::new(),
+            unchanged: Vec::new(),
+        };
+        let mut current_keys = HashSet::new();
+        for result in self.archive.get_scan_results(scan_id)? {
+            if !current_keys.insert(key(&result)) {
+                continue;
+            }
+            if previous_keys.contains(&key(&result)) {
+                delta.unchanged.push(result);
+            } else {
+                delta.added.push(result);
+            }
+        }
+        let mut removed_keys = HashSet::new();
+        for result in previous_results {
+            let result_key = key(&result);
+            if !current_keys.contains(&result_key) && removed_keys.insert(result_key) {
+                delta.removed.push(result);
+            }
+        }
+
+        Ok(delta)
+    }
+
+    /// Recent activity across scans and assessments, newest first
+    ///
+    /// Scans contribute a start event and, once ended, a completion event;
+    /// assessments contribute an event at the time they were cached.
+    pub fn recent_activity(&self, limit: usize) -> CryptexResult<Vec<ActivityEvent>> {
+        let mut events = Vec::new();
+
+        for scan in self.archive.list_scans()? {
+            events.push(ActivityEvent::ScanStarted {
+                scan_id: scan.scan_id.clone(),
+                timestamp: scan.started_at,
+                summary: format!("Scan started on {}", scan.target),
+            });
+
+            if let Some(ended_at) = scan.ended_at {
+                events.push(ActivityEvent::ScanCompleted {
+                    scan_id: scan.scan_id.clone(),
+                    timestamp: ended_at,
+                    summary: format!(
+                        "Scan of {} completed with {} vulnerabilities ({} critical)",
+                        scan.target, scan.total_vulnerabilities, scan.critical
+                    ),
+                });
+            }
+        }
+
+        for stored in self.archive.list_vulnerabilities()? {
+            events.push(ActivityEvent::VulnerabilityAssessed {
+                cve_id: stored.cve_id.clone(),
+                timestamp: stored.cached_at,
+                summary: format!(
+                    "{} assessed as {} (CVSS {:.1})",
+                    stored.cve_id,
+                    stored.score.severity().as_str(),
+                    stored.score.cvss_base_score()
+                ),
+            });
+        }
+
+        events.sort_by_key(|e| std::cmp::Reverse(e.timestamp()));
+        events.truncate(limit);
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ScanMetadata, ScanResult, StoredScanResult, StoredVulnerability, VulnerabilityScore,
+    };
+    use chrono::Duration;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn archive() -> (TempDir, Arc<TheArchive>) {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("test.db")).unwrap();
+        (temp_dir, Arc::new(archive))
+    }
+
+    #[test]
+    fn test_get_vulnerabilities_omits_misses() {
+        let (_temp_dir, archive) = archive();
+        for cve_id in ["CVE-2021-44228", "CVE-2023-4966"] {
+            archive.store_vulnerability(&VulnerabilityScore::new(cve_id.to_string())).unwrap();
+        }
+        let query = ArchiveQuery::from_archive(archive.clone());
+        let reads = archive.archive_metrics().read_count;
+
+        let cve_ids = ["CVE-2021-44228", "CVE-2099-0001", "CVE-2023-4966"].map(String::from);
+        let found = query.get_vulnerabilities(&cve_ids).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found["CVE-2023-4966"].cve_id, "CVE-2023-4966");
+        assert!(!found.contains_key("CVE-2099-0001"));
+        assert_eq!(archive.archive_metrics().read_count, reads + 1);
+
+        assert!(query.get_vulnerabilities(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_readonly_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        TheArchive::the_awakening(db_path.clone())
+            .unwrap()
+            .store_scan_metadata(&ScanMetadata::new("scan_a".to_string(), "10.0.0.1".to_string()))
+            .unwrap();
+
+        let query = ArchiveQuery::open_readonly(db_path).unwrap();
+        assert!(query.archive.is_read_only());
+        assert_eq!(query.recent_activity(10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_recent_activity_merges_chronologically() {
+        let (_temp_dir, archive) = archive();
+        let base = Utc::now() - Duration::hours(10);
+
+        let mut scan_a = ScanMetadata::new("scan_a".to_string(), "10.0.0.0/24".to_string());
+        scan_a.started_at = base;
+        scan_a.ended_at = Some(base + Duration::hours(3));
+        scan_a.status = "completed".to_string();
+        archive.store_scan_metadata(&scan_a).unwrap();
+
+        let mut scan_b = ScanMetadata::new("scan_b".to_string(), "10.0.1.0/24".to_string());
+        scan_b.started_at = base + Duration::hours(2);
+        archive.store_scan_metadata(&scan_b).unwrap();
+
+        archive
+            .put_vulnerability(&StoredVulnerability {
+                cve_id: "CVE-2021-44228".to_string(),
+                score: VulnerabilityScore::new("CVE-2021-44228".to_string()),
+                cached_at: base + Duration::hours(1),
+            })
+            .unwrap();
+
+        archive
+            .put_vulnerability(&StoredVulnerability {
+                cve_id: "CVE-2024-0001".to_string(),
+                score: VulnerabilityScore::new("CVE-2024-0001".to_string()),
+                cached_at: base + Duration::hours(4),
+            })
+            .unwrap();
+
+        let query = ArchiveQuery::from_archive(archive);
+        let events = query.recent_activity(10).unwrap();
+
+        let order: Vec<String> = events
+            .iter()
+            .map(|e| match e {
+                ActivityEvent::ScanStarted { scan_id, .. } => format!("started:{}", scan_id),
+                ActivityEvent::ScanCompleted { scan_id, .. } => format!("completed:{}", scan_id),
+                ActivityEvent::VulnerabilityAssessed { cve_id, .. } => format!("assessed:{}", cve_id),
+            })
+            .collect();
+
+        assert_eq!(
+            order,
+            vec![
+                "assessed:CVE-2024-0001",
+                "completed:scan_a",
+                "started:scan_b",
+                "assessed:CVE-2021-44228",
+                "started:scan_a",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_to_gzip_file() {
+        let (temp_dir, archive) = archive();
+
+        // More findings than an unlimited query would return, plus a scan
+        // whose id extends the other's
+        let mut batch = archive.begin_batch().unwrap();
+        for i in 0..DEFAULT_QUERY_LIMIT + 200 {
+            let result = ScanResult::new(
+                format!("CVE-2024-{:04}", i % 400),
+                format!("10.0.{}.{}", i / 250, i % 250),
+                443,
+                "unknown".to_string(),
+                "Outdated TLS library, \"remote\"".to_string(),
+            );
+            batch.store_scan_result("scan_a", &result).unwrap();
+        }
+        let stray = ScanResult::new(
+            "CVE-2024-9999".to_string(),
+            "10.9.9.9".to_string(),
+            80,
+            "unknown".to_string(),
+            "Other scan".to_string(),
+        );
+        batch.store_scan_result("scan_ab", &stray).unwrap();
+        batch.commit().unwrap();
+
+        let query = ArchiveQuery::from_archive(archive);
+        let scan_a = QueryFilters {
+            scan_id: Some("scan_a".to_string()),
+            ..Default::default()
+        };
+
+        let path = temp_dir.path().join("findings.csv.gz");
+        let exported = query.export_to_file(&scan_a, ExportFormat::Csv, &path, true).unwrap();
+        assert_eq!(exported.rows, DEFAULT_QUERY_LIMIT + 200);
+        assert_eq!(exported.bytes, std::fs::metadata(&path).unwrap().len());
+
+        let compressed = std::fs::read(&path).unwrap();
+        let mut csv = String::new();
+        flate2::read::GzDecoder::new(compressed.as_slice()).read_to_string(&mut csv).unwrap();
+        assert!(compressed.len() * 4 < csv.len(), "{} of {}", compressed.len(), csv.len());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], export::CSV_HEADER);
+        assert_eq!(lines.len(), exported.rows + 1);
+        assert!(!csv.contains("scan_ab"));
+
+        // Uncompressed NDJSON, with an offset and a limit
+        let page = QueryFilters {
+            offset: Some(10),
+            limit: Some(5),
+            ..Default::default()
+        };
+        let ndjson_path = temp_dir.path().join("findings.ndjson");
+        let exported =
+            query.export_to_file(&page, ExportFormat::Ndjson, &ndjson_path, false).unwrap();
+        let ndjson = std::fs::read_to_string(&ndjson_path).unwrap();
+        let rows: Vec<StoredScanResult> =
+            ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(exported.rows, 5);
+        assert_eq!(rows.len(), 5);
+        assert_eq!(exported.bytes, ndjson.len() as u64);
+
+        // A JSON export is one array
+        let path = temp_dir.path().join("findings.json");
+        query.export_to_file(&page, ExportFormat::Json, &path, false).unwrap();
+        let json: Vec<StoredScanResult> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json.len(), 5);
+
+        // Exporting again replaces the file whole
+        let exported =
+            query.export_to_file(&page, ExportFormat::Ndjson, &ndjson_path, false).unwrap();
+        assert_eq!(exported.rows, 5);
+        assert_eq!(std::fs::read_to_string(&ndjson_path).unwrap(), ndjson);
+
+        let missing_dir = temp_dir.path().join("absent").join("findings.csv");
+        assert!(query.export_to_file(&page, ExportFormat::Csv, &missing_dir, true).is_err());
+
+        // No temporary file is left behind
+        let mut names: Vec<String> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with("findings") || name.starts_with(".tmp"))
+            .collect();
+        names.sort();
+        assert_eq!(names, ["findings.csv.gz", "findings.json", "findings.ndjson"]);
+    }
+
+    #[test]
+    fn test_find_results_filters() {
+        let (_temp_dir, archive) = archive();
+
+        let mut kev = ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "10.0.0.5".to_string(),
+            443,
+            "unknown".to_string(),
+            "Log4Shell".to_string(),
+        );
+        let mut score = VulnerabilityScore::new("CVE-2021-44228".to_string());
+        score.kev = Some(the_assessor::KevInfo {
+            is_kev: true,
+            date_added: None,
+            due_date: None,
+            required_action: None,
+            known_ransomware_use: false,
+        });
+        kev.vulnerability_score = Some(score);
+        archive.store_scan_result("scan_a", &kev).unwrap();
+
+        let other = ScanResult::new(
+            "CVE-2024-0001".to_string(),
+            "10.0.0.6".to_string(),
+            80,
+            "unknown".to_string(),
+            "Other".to_string(),
+        );
+        archive.store_scan_result("scan_b", &other).unwrap();
+
+        let query = ArchiveQuery::from_archive(archive);
+
+        assert_eq!(query.find_results(&QueryFilters::default()).unwrap().len(), 2);
+
+        let kev_only = QueryFilters {
+            kev_only: true,
+            ..Default::default()
+        };
+        let found = query.find_results(&kev_only).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].scan_id, "scan_a");
+
+        let by_scan = QueryFilters {
+            scan_id: Some("scan_b".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query.find_results(&by_scan).unwrap()[0].result.cve_id, "CVE-2024-0001");
+
+        let limited = QueryFilters {
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(query.find_results(&limited).unwrap().len(), 1);
+
+        let csv = query.export_findings(&kev_only, ExportFormat::Csv).unwrap();
+        assert_eq!(csv.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_kev_coverage_counts_overdue() {
+        let (_temp_dir, archive) = archive();
+
+        let finding = |cve_id: &str, host: &str| {
+            ScanResult::new(
+                cve_id.to_string(),
+                host.to_string(),
+                443,
+                "unknown".to_string(),
+                cve_id.to_string(),
+            )
+        };
+        let kev_score = |cve_id: &str, due_date: &str| {
+            let mut score = VulnerabilityScore::new(cve_id.to_string());
+            score.kev = Some(the_assessor::KevInfo {
+                is_kev: true,
+                date_added: Some("2021-12-10".to_string()),
+                due_date: Some(due_date.to_string()),
+                required_action: None,
+                known_ransomware_use: false,
+            });
+            score
+        };
+
+        // Past due, reported by two scans and on two hosts
+        let mut overdue = finding("CVE-2021-44228", "10.0.0.5");
+        overdue.vulnerability_score = Some(kev_score("CVE-2021-44228", "2021-12-24"));
+        archive.store_scan_result("scan_a", &overdue).unwrap();
+        archive.store_scan_result("scan_b", &overdue).unwrap();
+        overdue.host = "10.0.0.6".to_string();
+        archive.store_scan_result("scan_b", &overdue).unwrap();
+
+        // Listed but not yet due; only the archived assessment knows it is KEV
+        let due = (Utc::now() + Duration::days(14)).format("%Y-%m-%d").to_string();
+        archive.store_vulnerability(&kev_score("CVE-2026-1000", &due)).unwrap();
+        archive.store_scan_result("scan_b", &finding("CVE-2026-1000", "10.0.0.7")).unwrap();
+
+        archive.store_scan_result("scan_a", &finding("CVE-2024-0001", "10.0.0.5")).unwrap();
+
+        let coverage = ArchiveQuery::from_archive(archive).kev_coverage().unwrap();
+        assert_eq!(
+            coverage,
+            KevCoverage {
+                total_findings: 4,
+                kev_findings: 3,
+                kev_hosts_affected: 3,
+                overdue_kev: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_matches_severity_host_and_port() {
+        let mut assessed = ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "10.0.0.5".to_string(),
+            443,
+            "unknown".to_string(),
+            "Log4Shell".to_string(),
+        );
+        let mut score = VulnerabilityScore::new("CVE-2021-44228".to_string());
+        score.cvss_v3 = Some(
+            the_assessor::cvss_v3_from_vector("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H")
+                .unwrap(),
+        );
+        assessed.vulnerability_score = Some(score);
+        let assessed = StoredScanResult {
+            scan_id: "scan_a".to_string(),
+            result: assessed,
+        };
+
+        let unassessed = StoredScanResult {
+            scan_id: "scan_a".to_string(),
+            result: ScanResult::new(
+                "CVE-2024-0001".to_string(),
+                "10.0.0.5".to_string(),
+                80,
+                "unknown".to_string(),
+                "Other".to_string(),
+            ),
+        };
+
+        let critical = QueryFilters::default().severity("CRITICAL").unwrap();
+        assert!(critical.matches(&assessed));
+        assert!(!critical.matches(&unassessed));
+
+        let high = QueryFilters::default().severity("high").unwrap();
+        assert!(!high.matches(&assessed));
+
+        let on_host = QueryFilters {
+            host: Some("10.0.0.5".to_string()),
+            ..Default::default()
+        };
+        assert!(on_host.matches(&assessed) && on_host.matches(&unassessed));
+
+        let on_port = QueryFilters {
+            host: Some("10.0.0.5".to_string()),
+            port: Some(80),
+            ..Default::default()
+        };
+        assert!(!on_port.matches(&assessed));
+        assert!(on_port.matches(&unassessed));
+
+        let other_host = QueryFilters {
+            host: Some("10.0.0.6".to_string()),
+            port: Some(80),
+            ..Default::default()
+        };
+        assert!(!other_host.matches(&unassessed));
+    }
+
+    #[test]
+    fn test_severity_filters_are_typed() {
+        let finding = |cve_id: &str, vector: &str| {
+            let mut result = ScanResult::new(
+                cve_id.to_string(),
+                "10.0.0.5".to_string(),
+                443,
+                "https".to_string(),
+                cve_id.to_string(),
+            );
+            let mut score = VulnerabilityScore::new(cve_id.to_string());
+            score.cvss_v3 = Some(the_assessor::cvss_v3_from_vector(vector).unwrap());
+            result.vulnerability_score = Some(score);
+            StoredScanResult {
+                scan_id: "scan_a".to_string(),
+                result,
+            }
+        };
+        let critical = finding("CVE-2021-44228", "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H");
+        let high = finding("CVE-2024-0002", "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:N/A:N");
+        let medium = finding("CVE-2024-0003", "CVSS:3.1/AV:N/AC:L/PR:L/UI:R/S:U/C:L/I:L/A:N");
+
+        // A fragment of a name is not a severity
+        assert!(matches!(
+            QueryFilters::default().severity("i"),
+            Err(CryptexError::ValidationError(_))
+        ));
+        assert!(serde_json::from_value::<QueryFilters>(serde_json::json!({ "severity": "i" })).is_err());
+
+        let at_least_high = QueryFilters::default().min_severity("high").unwrap();
+        assert!(at_least_high.matches(&critical));
+        assert!(at_least_high.matches(&high));
+        assert!(!at_least_high.matches(&medium));
+
+        let parsed: QueryFilters =
+            serde_json::from_value(serde_json::json!({ "min_severity": "HIGH" })).unwrap();
+        assert_eq!(parsed.min_severity, Some(CvssSeverity::High));
+    }
+
+    #[test]
+    fn test_min_confidence_filter() {
+        let finding = |confidence: Option<ConfidenceLevel>| {
+            let mut result = ScanResult::new(
+                "CVE-2024-0001".to_string(),
+                "10.0.0.5".to_string(),
+                443,
+                "unknown".to_string(),
+                "Banner check".to_string(),
+            );
+            result.confidence = confidence;
+            StoredScanResult {
+                scan_id: "scan_a".to_string(),
+                result,
+            }
+        };
+        let low = finding(Some(ConfidenceLevel::Low));
+        let high = finding(Some(ConfidenceLevel::High));
+        let unrated = finding(None);
+
+        let high_only = QueryFilters::default().min_confidence("high").unwrap();
+        assert!(high_only.matches(&high));
+        assert!(!high_only.matches(&low));
+        assert!(!high_only.matches(&unrated));
+
+        let parsed: QueryFilters =
+            serde_json::from_value(serde_json::json!({ "min_confidence": "LOW" })).unwrap();
+        assert!(parsed.matches(&low) && parsed.matches(&high));
+        assert!(QueryFilters::default().min_confidence("certain").is_err());
+    }
+
+    #[test]
+    fn test_recent_activity_limit() {
+        let (_temp_dir, archive) = archive();
+
+        for i in 0..5 {
+            let metadata = ScanMetadata::new(format!("scan_{}", i), "10.0.0.0/24".to_string());
+            archive.store_scan_metadata(&metadata).unwrap();
+        }
+
+        let query = ArchiveQuery::from_archive(archive);
+        assert_eq!(query.recent_activity(3).unwrap().len(), 3);
+        assert!(query.recent_activity(0).unwrap().is_empty());
+    }
+
+    fn store_assessments(archive: &TheArchive, count: usize) {
+        let base = Utc::now() - Duration::hours(count as i64);
+
+        // Stored out of CVE order so the two sort orders differ
+        for i in (0..count).rev() {
+            archive
+                .put_vulnerability(&StoredVulnerability {
+                    cve_id: format!("CVE-2024-{:04}", i),
+                    score: VulnerabilityScore::new(format!("CVE-2024-{:04}", i)),
+                    cached_at: base + Duration::hours((count - i) as i64),
+                })
+                .unwrap();
+        }
+    }
+
+    fn collect_pages(query: &ArchiveQuery, filters: QueryFilters) -> (usize, Vec<String>) {
+        let mut pages = 0;
+        let mut seen = Vec::new();
+        let mut filters = filters;
+
+        loop {
+            let page = query.query_vulnerabilities(&filters).unwrap();
+            pages += 1;
+            seen.extend(page.items.into_iter().map(|v| v.cve_id));
+
+            match page.next_cursor {
+                Some(cursor) => filters = filters.after_cursor(cursor),
+                None => return (pages, seen),
+            }
+        }
+    }
+
+    #[test]
+    fn test_severity_queries_read_only_indexed_rows() {
+        let (_temp_dir, archive) = archive();
+        store_assessments(&archive, 20);
+
+        let critical = |cve_id: &str, known_exploited: bool| {
+            let mut score = VulnerabilityScore::new(cve_id.to_string());
+            score.cvss_v3 = Some(
+                the_assessor::cvss_v3_from_vector("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H")
+                    .unwrap(),
+            );
+            score.kev = known_exploited.then_some(the_assessor::KevInfo {
+                is_kev: true,
+                date_added: None,
+                due_date: None,
+                required_action: None,
+                known_ransomware_use: false,
+            });
+            score
+        };
+        archive.store_vulnerability(&critical("CVE-2021-44228", true)).unwrap();
+        archive.store_vulnerability(&critical("CVE-2024-3400", false)).unwrap();
+
+        let query = ArchiveQuery::from_archive(archive.clone());
+        let expect = |filters: QueryFilters, wanted: &[&str]| {
+            let before = archive.vulnerability_decode_count();
+            let (_, seen) = collect_pages(&query, filters);
+            assert_eq!(seen, wanted);
+            assert_eq!(archive.vulnerability_decode_count() - before, wanted.len() as u64);
+        };
+
+        expect(
+            QueryFilters::default().severity("critical").unwrap(),
+            &["CVE-2021-44228", "CVE-2024-3400"],
+        );
+        expect(
+            QueryFilters {
+                kev_only: true,
+                ..Default::default()
+            },
+            &["CVE-2021-44228"],
+        );
+        expect(
+            QueryFilters {
+                sort: VulnerabilitySort::CachedAt,
+                ..QueryFilters::default().min_severity("high").unwrap()
+            },
+            &["CVE-2021-44228", "CVE-2024-3400"],
+        );
+        expect(QueryFilters::default().severity("low").unwrap(), &[]);
+    }
+
+    #[test]
+    fn test_storage_pages_respect_boundaries() {
+        let (_temp_dir, archive) = archive();
+        store_assessments(&archive, 7);
+
+        let page = |offset, limit| -> Vec<String> {
+            archive
+                .list_vulnerabilities_page(offset, limit)
+                .unwrap()
+                .into_iter()
+                .map(|v| v.cve_id)
+                .collect()
+        };
+        assert_eq!(page(0, 3), ["CVE-2024-0000", "CVE-2024-0001", "CVE-2024-0002"]);
+        assert_eq!(page(3, 3), ["CVE-2024-0003", "CVE-2024-0004", "CVE-2024-0005"]);
+        assert_eq!(page(6, 3), ["CVE-2024-0006"]);
+        assert!(page(7, 3).is_empty());
+        assert!(page(0, 0).is_empty());
+        assert_eq!(page(0, 100).len(), 7);
+
+        // An exactly full last page has no cursor; a short one neither
+        let query = ArchiveQuery::from_archive(archive.clone());
+        let at = |offset, limit| QueryFilters {
+            offset: Some(offset),
+            limit: Some(limit),
+            ..Default::default()
+        };
+        assert!(query.query_vulnerabilities(&at(4, 3)).unwrap().next_cursor.is_none());
+        assert!(query.query_vulnerabilities(&at(5, 3)).unwrap().next_cursor.is_none());
+
+        // A page with rows after it hands over to cursor paging seamlessly
+        let first = query.query_vulnerabilities(&at(2, 2)).unwrap();
+        assert_eq!(first.next_cursor.as_deref(), Some("cve_id:CVE-2024-0003"));
+        let rest = query
+            .query_vulnerabilities(&at(0, 10).after_cursor(first.next_cursor.unwrap()))
+            .unwrap();
+        assert_eq!(rest.items.len(), 3);
+        assert_eq!(rest.items[0].cve_id, "CVE-2024-0004");
+    }
+
+    #[test]
+    fn test_cursor_pagination_yields_each_row_once() {
+        let (_temp_dir, archive) = archive();
+        store_assessments(&archive, 7);
+        let query = ArchiveQuery::from_archive(archive.clone());
+
+        let by_cve = QueryFilters {
+            limit: Some(3),
+            ..Default::default()
+        };
+        let (pages, seen) = collect_pages(&query, by_cve);
+        let expected: Vec<String> = (0..7).map(|i| format!("CVE-2024-{:04}", i)).collect();
+        assert_eq!(pages, 3);
+        assert_eq!(seen, expected);
+
+        // Oldest assessment first: CVE-2024-0006 was cached earliest
+        let by_time = QueryFilters {
+            limit: Some(3),
+            sort: VulnerabilitySort::CachedAt,
+            ..Default::default()
+        };
+        let (pages, seen) = collect_pages(&query, by_time.clone());
+        assert_eq!(pages, 3);
+        assert_eq!(seen, expected.iter().rev().cloned().collect::<Vec<_>>());
+
+        // Re-assessing moves a record to the end without duplicating it
+        archive.store_vulnerability(&VulnerabilityScore::new("CVE-2024-0006".to_string())).unwrap();
+        let (_, seen) = collect_pages(&query, by_time);
+        assert_eq!(seen.len(), 7);
+        assert_eq!(seen.last().unwrap(), "CVE-2024-0006");
+
+        // Offset pagination still works
+        let offset = QueryFilters {
+            limit: Some(2),
+            offset: Some(5),
+            ..Default::default()
+        };
+        let page = query.query_vulnerabilities(&offset).unwrap();
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.items[0].cve_id, "CVE-2024-0005");
+        assert!(page.next_cursor.is_none());
+
+        // A cursor from one sort order is rejected by the other
+        let wrong = QueryFilters {
+            sort: VulnerabilitySort::CachedAt,
+            ..Default::default()
+        }
+        .after_cursor("cve_id:CVE-2024-0002");
+        assert!(query.query_vulnerabilities(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_changed_since_delta() {
+        let (_temp_dir, archive) = archive();
+        let cached_at = Utc::now() - Duration::minutes(5);
+        archive
+            .put_vulnerability(&StoredVulnerability {
+                cve_id: "CVE-2021-44228".to_string(),
+                score: VulnerabilityScore::new("CVE-2021-44228".to_string()),
+                cached_at,
+            })
+            .unwrap();
+        let query = ArchiveQuery::from_archive(archive);
+
+        for sort in [VulnerabilitySort::CachedAt, VulnerabilitySort::CveId] {
+            let since = |instant| QueryFilters {
+                sort,
+                changed_since: Some(instant),
+                ..Default::default()
+            };
+
+            let after = query
+                .query_vulnerabilities(&since(cached_at + Duration::milliseconds(1)))
+                .unwrap();
+            assert!(after.items.is_empty());
+
+            let before = query
+                .query_vulnerabilities(&since(cached_at - Duration::milliseconds(1)))
+                .unwrap();
+            assert_eq!(before.items.len(), 1);
+            assert_eq!(before.items[0].cve_id, "CVE-2021-44228");
+        }
+    }
+
+    #[test]
+    fn test_all_vulnerabilities_ignores_limit() {
+        let (_temp_dir, archive) = archive();
+        for i in 0..3 {
+            let score = VulnerabilityScore::new(format!("CVE-2024-000{}", i));
+            archive.store_vulnerability(&score).unwrap();
+        }
+        let query = ArchiveQuery::from_archive(archive);
+
+        for sort in [VulnerabilitySort::CachedAt, VulnerabilitySort::CveId] {
+            let filters = QueryFilters {
+                sort,
+                limit: Some(1),
+                ..Default::default()
+            };
+            assert_eq!(query.query_vulnerabilities(&filters).unwrap().items.len(), 1);
+            assert_eq!(query.all_vulnerabilities(&filters).unwrap().len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_find_scans_by_severity_composition() {
+        let (_temp_dir, archive) = archive();
+
+        let scan = |scan_id: &str, critical: usize, high: usize, medium: usize| {
+            let mut scan = ScanMetadata::new(scan_id.to_string(), "10.0.0.0/24".to_string());
+            scan.critical = critical;
+            scan.high = high;
+            scan.medium = medium;
+            scan.total_vulnerabilities = critical + high + medium;
+            scan
+        };
+        archive.store_scan_metadata(&scan("all_medium", 0, 0, 4)).unwrap();
+        archive.store_scan_metadata(&scan("one_critical", 1, 2, 1)).unwrap();
+        archive.store_scan_metadata(&scan("kev_high", 0, 1, 0)).unwrap();
+
+        let mut kev = ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "10.0.0.5".to_string(),
+            443,
+            "unknown".to_string(),
+            "Log4Shell".to_string(),
+        );
+        let mut score = VulnerabilityScore::new("CVE-2021-44228".to_string());
+        score.kev = Some(the_assessor::KevInfo {
+            is_kev: true,
+            date_added: None,
+            due_date: None,
+            required_action: None,
+            known_ransomware_use: false,
+        });
+        kev.vulnerability_score = Some(score);
+        // KEV status only on the archived result, as scan archiving stores it
+        archive.store_scan_result("kev_high", &kev).unwrap();
+        // Scan ids that are prefixes of one another keep their own results
+        archive.store_scan_metadata(&scan("kev", 0, 0, 0)).unwrap();
+
+        let query = ArchiveQuery::from_archive(archive);
+        let ids = |filters: ScanFilters| {
+            let mut ids: Vec<String> = query
+                .find_scans(&filters)
+                .unwrap()
+                .into_iter()
+                .map(|scan| scan.scan_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(ids(ScanFilters::default()).len(), 4);
+        assert_eq!(ids(ScanFilters::default().min_critical(1)), vec!["one_critical"]);
+        assert_eq!(
+            ids(ScanFilters::default().min_high(1)),
+            vec!["kev_high", "one_critical"]
+        );
+        assert_eq!(ids(ScanFilters::default().has_kev(true)), vec!["kev_high"]);
+        assert_eq!(
+            ids(ScanFilters::default().has_kev(false)),
+            vec!["all_medium", "kev", "one_critical"]
+        );
+        assert_eq!(
+            ids(ScanFilters::default().min_vulnerabilities(4).min_critical(0)),
+            vec!["all_medium", "one_critical"]
+        );
+        assert!(ids(ScanFilters::default().min_critical(1).has_kev(true)).is_empty());
+
+        // An archived assessment decides over the score stored with the finding
+        let mut reassessed = VulnerabilityScore::new("CVE-2021-44228".to_string());
+        reassessed.kev = None;
+        query.archive.store_vulnerability(&reassessed).unwrap();
+        assert!(ids(ScanFilters::default().has_kev(true)).is_empty());
+
+        let mut citrix_bleed = VulnerabilityScore::new("CVE-2023-4966".to_string());
+        citrix_bleed.kev = kev.vulnerability_score.as_ref().and_then(|score| score.kev.clone());
+        query.archive.store_vulnerability(&citrix_bleed).unwrap();
+        query.archive.store_scan_metadata(&scan("reassessed", 0, 0, 1)).unwrap();
+        let mut finding = kev.clone();
+        finding.cve_id = "CVE-2023-4966".to_string();
+        finding.vulnerability_score = None;
+        query.archive.store_scan_result("reassessed", &finding).unwrap();
+        assert_eq!(ids(ScanFilters::default().has_kev(true)), vec!["reassessed"]);
+    }
+
+    #[test]
+    fn test_find_scans_by_tags() {
+        let (_temp_dir, archive) = archive();
+
+        for (scan_id, tags) in [
+            ("untagged", vec![]),
+            ("prod_pci", vec!["prod", "pci-scope"]),
+            ("staging_pci", vec!["staging", "pci-scope"]),
+        ] {
+            let mut scan = ScanMetadata::new(scan_id.to_string(), "10.0.0.0/24".to_string());
+            scan.tags = tags.into_iter().map(String::from).collect();
+            archive.store_scan_metadata(&scan).unwrap();
+        }
+
+        let query = ArchiveQuery::from_archive(archive);
+        let ids = |filters: ScanFilters| {
+            let mut ids: Vec<String> = query
+                .find_scans(&filters)
+                .unwrap()
+                .into_iter()
+                .map(|scan| scan.scan_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            ids(ScanFilters::default().tags(["pci-scope"])),
+            vec!["prod_pci", "staging_pci"]
+        );
+        assert_eq!(ids(ScanFilters::default().tags(["pci-scope", "prod"])), vec!["prod_pci"]);
+        assert!(ids(ScanFilters::default().tags(["customer-acme"])).is_empty());
+        assert_eq!(ids(ScanFilters::default().tags(["pci-scope"]).limit(1)).len(), 1);
+        assert!(ids(ScanFilters::default().limit(0)).is_empty());
+
+        let filters: ScanFilters = serde_json::from_str(r#"{"tags": "prod, pci-scope"}"#).unwrap();
+        assert_eq!(ids(filters), vec!["prod_pci"]);
+    }
+
+    #[test]
+    fn test_scan_delta_against_previous_scan_of_target() {
+        let (_temp_dir, archive) = archive();
+        let base = Utc::now() - Duration::days(7);
+        let finding = |cve: &str, port: u16| {
+            ScanResult::new(
+                cve.to_string(),
+                "10.0.0.5".to_string(),
+                port,
+                "unknown".to_string(),
+                String::new(),
+            )
+        };
+        let store = |scan_id: &str, target: &str, hours: i64, status: &str| {
+            let mut scan = ScanMetadata::new(scan_id.to_string(), target.to_string());
+            scan.started_at = base + Duration::hours(hours);
+            scan.status = status.to_string();
+            archive.store_scan_metadata(&scan).unwrap();
+        };
+
+        store("first", "10.0.0.5", 0, "completed");
+        store("failed", "10.0.0.5", 1, "failed");
+        store("elsewhere", "10.0.0.6", 2, "completed");
+        store("second", "10.0.0.5", 3, "completed");
+        for (cve, port) in [("CVE-2021-44228", 443), ("CVE-2014-0160", 443)] {
+            archive.store_scan_result("first", &finding(cve, port)).unwrap();
+        }
+        archive.store_scan_result("failed", &finding("CVE-2023-0001", 22)).unwrap();
+        for (cve, port) in [("CVE-2021-44228", 443), ("CVE-2024-3094", 22)] {
+            archive.store_scan_result("second", &finding(cve, port)).unwrap();
+        }
+
+        let query = ArchiveQuery::from_archive(archive.clone());
+        let cves = |results: &[ScanResult]| {
+            results.iter().map(|r| r.cve_id.clone()).collect::<Vec<_>>()
+        };
+
+        let delta = query.scan_delta("second").unwrap();
+        assert_eq!(delta.previous_scan_id.as_deref(), Some("first"));
+        assert_eq!(cves(&delta.added), vec!["CVE-2024-3094"]);
+        assert_eq!(cves(&delta.removed), vec!["CVE-2014-0160"]);
+        assert_eq!(cves(&delta.unchanged), vec!["CVE-2021-44228"]);
+
+        let previous = query
+            .previous_scan_for_target("10.0.0.5", base + Duration::hours(3))
+            .unwrap();
+        assert_eq!(previous.map(|scan| scan.scan_id).as_deref(), Some("first"));
+        assert!(matches!(
+            query.scan_delta("missing"),
+            Err(CryptexError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_scan_delta_without_previous_scan() {
+        let (_temp_dir, archive) = archive();
+        let mut scan = ScanMetadata::new("only".to_string(), "10.0.0.7".to_string());
+        scan.status = "completed".to_string();
+        archive.store_scan_metadata(&scan).unwrap();
+        archive
+            .store_scan_result(
+                "only",
+                &ScanResult::new(
+                    "CVE-2021-44228".to_string(),
+                    "10.0.0.7".to_string(),
+                    443,
+                    "unknown".to_string(),
+                    String::new(),
+                ),
+            )
+            .unwrap();
+
+        let query = ArchiveQuery::from_archive(archive);
+        assert!(query
+            .previous_scan_for_target("10.0.0.7", Utc::now())
+            .unwrap()
+            .is_some_and(|previous| previous.scan_id == "only"));
+        assert!(query
+            .previous_scan_for_target("10.0.0.7", scan.started_at)
+            .unwrap()
+            .is_none());
+
+        let delta = query.scan_delta("only").unwrap();
+        assert!(delta.previous_scan_id.is_none());
+        assert_eq!(delta.added.len(), 1);
+        assert!(delta.removed.is_empty() && delta.unchanged.is_empty());
+    }
+}
@@ -0,0 +1,393 @@
+//! Result retention
+//!
+//! Traditional name: `RetentionPolicy`
+//!
+//! Scans age out once they are older than the configured retention window,
+//! taking their stored results with them. A scan's age is measured from
+//! `ended_at`, or from `started_at` if it never ended. Cached vulnerability
+//! assessments are not scans and are left alone.
+//!
+//! Single scans can also be deleted outright, e.g. before a pipeline re-runs
+//! a scan under the same id.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use redb::{ReadableTable, Table, WriteTransaction};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{
+    decode_scan_metadata, scan_start_key, ArchiveEvent, ScanMetadata, TheArchive, ValueFormat,
+    SCANS_BY_START_TABLE, SCANS_TABLE, SCAN_RESULTS_TABLE,
+};
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Environment variable the servers read the retention window from, in days
+///
+/// Mirrors `archive.retain_days` in the charter.
+pub const RETAIN_DAYS_ENV: &str = "CRYPTEX_ARCHIVE__RETAIN_DAYS";
+
+/// How often the background task prunes
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What a prune removed, or would remove on a dry run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneStats {
+    pub scans: usize,
+    pub results: usize,
+    /// True if nothing was actually deleted
+    pub dry_run: bool,
+}
+
+impl TheArchive {
+    /// Delete scans that ended (or started, if never ended) before `cutoff`,
+    /// along with their results
+    pub fn prune_older_than(&self, cutoff: DateTime<Utc>) -> CryptexResult<PruneStats> {
+        self.prune(cutoff, false)
+    }
+
+    /// Count what `prune_older_than` would delete, without deleting it
+    pub fn count_older_than(&self, cutoff: DateTime<Utc>) -> CryptexResult<PruneStats> {
+        self.prune(cutoff, true)
+    }
+
+    fn prune(&self, cutoff: DateTime<Utc>, dry_run: bool) -> CryptexResult<PruneStats> {
+        if dry_run {
+            return self.count_expired(cutoff);
+        }
+
+        let write_txn = self.begin_write()?;
+        let (stats, pruned) = prune_in(&write_txn, self.format, cutoff)?;
+        write_txn
+            .commit()
+            .map_err(|e| CryptexError::ArchiveError(format!("Failed to commit prune: {}", e)))?;
+
+        if stats.scans > 0 {
+            tracing::info!(
+                "Pruned {} scans and {} results older than {}",
+                stats.scans,
+                stats.results,
+                cutoff
+            );
+        }
+        for scan_id in pruned {
+            self.emit(ArchiveEvent::ScanDeleted { scan_id });
+        }
+
+        Ok(stats)
+    }
+
+    /// Dry run: count expired scans and their results in a read transaction,
+    /// so previewing a prune never waits on or blocks writers
+    fn count_expired(&self, cutoff: DateTime<Utc>) -> CryptexResult<PruneStats> {
+        let read_txn = self.begin_read()?;
+        let scans = read_txn.open_table(SCANS_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
+        })?;
+        let results = read_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+        })?;
+
+        let mut stats = PruneStats {
+            dry_run: true,
+            ..PruneStats::default()
+        };
+        for scan_id in expired_scan_ids(&scans, self.format, cutoff)? {
+            stats.scans += 1;
+            stats.results += result_keys(&results, &scan_id)?.len();
+        }
+
+        Ok(stats)
+    }
+
+    /// Delete one scan and its results, returning how many results it had
+    ///
+    /// Deleting a scan that is not stored removes nothing and is not an error.
+    pub fn delete_scan(&self, scan_id: &str) -> CryptexResult<usize> {
+        let write_txn = self.begin_write()?;
+        let removed = delete_scan_in(&write_txn, self.format, scan_id)?;
+        write_txn.commit().map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to commit scan deletion: {}", e))
+        })?;
+        self.emit(ArchiveEvent::ScanDeleted {
+            scan_id: scan_id.to_string(),
+        });
+
+        Ok(removed)
+    }
+}
+
+/// The tables a scan lives in, opened in one write transaction
+struct ScanTables<'a, 'txn> {
+    scans: &'a mut Table<'txn, &'static str, &'static [u8]>,
+    results: &'a mut Table<'txn, &'static str, &'static [u8]>,
+    index: &'a mut Table<'txn, &'static str, &'static str>,
+}
+
+/// Remove `scan_id` and its results within `write_txn`
+pub(crate) fn delete_scan_in(
+    write_txn: &WriteTransaction,
+    format: ValueFormat,
+    scan_id: &str,
+) -> CryptexResult<usize> {
+    let mut scans = write_txn.open_table(SCANS_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
+    })?;
+    let mut results = write_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+    })?;
+    let mut index = write_txn.open_table(SCANS_BY_START_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scan index: {}", e))
+    })?;
+    let mut tables = ScanTables {
+        scans: &mut scans,
+        results: &mut results,
+        index: &mut index,
+    };
+    remove_scan_in(&mut tables, format, scan_id)
+}
+
+/// Ids of scans that ended (or started, if never ended) before `cutoff`
+fn expired_scan_ids(
+    scans: &impl ReadableTable<&'static str, &'static [u8]>,
+    format: ValueFormat,
+    cutoff: DateTime<Utc>,
+) -> CryptexResult<Vec<String>> {
+    let mut expired = Vec::new();
+    let iter = scans.iter().map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to iterate scans: {}", e))
+    })?;
+    for entry in iter {
+        let (key, value) = entry.map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to read scan entry: {}", e))
+        })?;
+
+        let metadata: ScanMetadata = format.decode(value.value()).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to deserialize scan metadata: {}", e))
+        })?;
+        if metadata.ended_at.unwrap_or(metadata.started_at) < cutoff {
+            expired.push(key.value().to_string());
+        }
+    }
+    Ok(expired)
+}
+
+/// Keys of the results stored under `scan_id`
+fn result_keys(
+    results: &impl ReadableTable<&'static str, &'static [u8]>,
+    scan_id: &str,
+) -> CryptexResult<Vec<String>> {
+    // Result keys are "scan_id:cve:host:port"; ';' sorts right after ':'
+    let (start, end) = (format!("{}:", scan_id), format!("{};", scan_id));
+    results
+        .range::<&str>(start.as_str()..end.as_str())
+        .map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to range scan results: {}", e))
+        })?
+        .map(|entry| entry.map(|(key, _)| key.value().to_string()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to read scan result entry: {}", e))
+        })
+}
+
+/// Remove `scan_id` and its results, returning the number of results
+fn remove_scan_in(
+    tables: &mut ScanTables,
+    format: ValueFormat,
+    scan_id: &str,
+) -> CryptexResult<usize> {
+    let ScanTables {
+        scans,
+        results,
+        index,
+    } = tables;
+    let keys = result_keys(&**results, scan_id)?;
+
+    for key in &keys {
+        results.remove(key.as_str()).map_err(|e| {
+            CryptexError::ArchiveError(format!("Failed to remove scan result: {}", e))
+        })?;
+    }
+    let removed = scans
+        .remove(scan_id)
+        .map_err(|e| CryptexError::ArchiveError(format!("Failed to remove scan: {}", e)))?
+        .map(|old| decode_scan_metadata(format, old.value()))
+        .transpose()?;
+    if let Some(removed) = removed {
+        index
+            .remove(scan_start_key(&removed.started_at, scan_id).as_str())
+            .map_err(|e| CryptexError::ArchiveError(format!("Failed to update scan index: {}", e)))?;
+    }
+
+    Ok(keys.len())
+}
+
+fn prune_in(
+    write_txn: &WriteTransaction,
+    format: ValueFormat,
+    cutoff: DateTime<Utc>,
+) -> CryptexResult<(PruneStats, Vec<String>)> {
+    let mut stats = PruneStats::default();
+
+    let mut scans = write_txn.open_table(SCANS_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scans table: {}", e))
+    })?;
+    let mut results = write_txn.open_table(SCAN_RESULTS_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scan_results table: {}", e))
+    })?;
+    let mut index = write_txn.open_table(SCANS_BY_START_TABLE).map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to open scan index: {}", e))
+    })?;
+
+    let expired = expired_scan_ids(&scans, format, cutoff)?;
+    for scan_id in &expired {
+        stats.scans += 1;
+        let mut tables = ScanTables {
+            scans: &mut scans,
+            results: &mut results,
+            index: &mut index,
+        };
+        stats.results += remove_scan_in(&mut tables, format, scan_id)?;
+    }
+
+    Ok((stats, expired))
+}
+
+/// The moment scans kept for `retain_days` expire at, as of now
+///
+/// Windows too long to subtract from the current time are a validation
+/// error rather than a panic.
+pub fn retention_cutoff(retain_days: u64) -> CryptexResult<DateTime<Utc>> {
+    i64::try_from(retain_days)
+        .ok()
+        .and_then(TimeDelta::try_days)
+        .and_then(|window| Utc::now().checked_sub_signed(window))
+        .ok_or_else(|| {
+            CryptexError::validation(format!(
+                "Retention window of {} days is out of range",
+                retain_days
+            ))
+        })
+}
+
+/// Retention window from `CRYPTEX_ARCHIVE__RETAIN_DAYS`, if set and valid
+pub fn retain_days_from_env() -> Option<u64> {
+    let value = std::env::var(RETAIN_DAYS_ENV).ok()?;
+    match value.parse() {
+        Ok(days) => Some(days),
+        Err(_) => {
+            tracing::warn!("Ignoring {}={}: not a number of days", RETAIN_DAYS_ENV, value);
+            None
+        }
+    }
+}
+
+/// Prune scans older than `retain_days` now and once a day thereafter
+///
+/// Fails up front if the window is out of range.
+pub fn spawn_retention(
+    archive: Arc<TheArchive>,
+    retain_days: u64,
+) -> CryptexResult<tokio::task::JoinHandle<()>> {
+    retention_cutoff(retain_days)?;
+    tracing::info!("Archive retention enabled: keeping {} days of scans", retain_days);
+
+    Ok(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let pruned = retention_cutoff(retain_days)
+                .and_then(|cutoff| archive.prune_older_than(cutoff));
+            if let Err(e) = pruned {
+                tracing::warn!("Archive retention prune failed: {}", e);
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScanResult;
+    use chrono::Duration as ChronoDuration;
+    use tempfile::TempDir;
+
+    fn store_scan(archive: &TheArchive, scan_id: &str, age_days: i64, ended: bool, results: u16) {
+        let mut metadata = ScanMetadata::new(scan_id.to_string(), "10.0.0.0/24".to_string());
+        metadata.started_at = Utc::now() - ChronoDuration::days(age_days + 1);
+        if ended {
+            metadata.ended_at = Some(Utc::now() - ChronoDuration::days(age_days));
+        }
+        archive.store_scan_metadata(&metadata).unwrap();
+
+        for port in 0..results {
+            let result = ScanResult::new(
+                "CVE-2021-44228".to_string(),
+                "10.0.0.5".to_string(),
+                port,
+                "unknown".to_string(),
+                "Log4Shell".to_string(),
+            );
+            archive.store_scan_result(scan_id, &result).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_prune_removes_only_old_scans_and_their_results() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("retention.db")).unwrap();
+
+        store_scan(&archive, "scan_old", 90, true, 3);
+        store_scan(&archive, "scan_stale", 45, false, 1);
+        store_scan(&archive, "scan_recent", 2, true, 2);
+        // Shares a prefix with an expired scan but must survive
+        store_scan(&archive, "scan_old2", 1, true, 1);
+
+        let cutoff = Utc::now() - ChronoDuration::days(30);
+
+        let preview = archive.count_older_than(cutoff).unwrap();
+        assert_eq!(
+            preview,
+            PruneStats {
+                scans: 2,
+                results: 4,
+                dry_run: true
+            }
+        );
+        assert_eq!(archive.get_stats().unwrap().total_scans, 4);
+
+        let stats = archive.prune_older_than(cutoff).unwrap();
+        assert_eq!((stats.scans, stats.results, stats.dry_run), (2, 4, false));
+
+        assert!(archive.get_scan_metadata("scan_old").unwrap().is_none());
+        assert!(archive.get_scan_metadata("scan_stale").unwrap().is_none());
+        assert!(archive.get_scan_results("scan_old").unwrap().is_empty());
+        assert_eq!(archive.get_scan_results("scan_recent").unwrap().len(), 2);
+        assert_eq!(archive.get_scan_results("scan_old2").unwrap().len(), 1);
+
+        let archive_stats = archive.get_stats().unwrap();
+        assert_eq!(archive_stats.total_scans, 2);
+        assert_eq!(archive_stats.total_results, 3);
+
+        // Nothing left to prune
+        assert_eq!(archive.prune_older_than(cutoff).unwrap().scans, 0);
+
+        assert_eq!(archive.delete_scan("scan_recent").unwrap(), 2);
+        assert!(archive.get_scan_metadata("scan_recent").unwrap().is_none());
+        assert_eq!(archive.get_scan_results("scan_old2").unwrap().len(), 1);
+        assert_eq!(archive.delete_scan("scan_recent").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_retention_cutoff_rejects_out_of_range_windows() {
+        let cutoff = retention_cutoff(30).unwrap();
+        let expected = Utc::now() - ChronoDuration::days(30);
+        assert!((expected - cutoff).num_seconds().abs() < 5);
+
+        for days in [u64::MAX, i64::MAX as u64, 1 << 40] {
+            let err = retention_cutoff(days).unwrap_err();
+            assert!(matches!(err, CryptexError::ValidationError(_)), "{}: {:?}", days, err);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Integrity self-check
+//!
+//! Traditional name: `fsck`
+//!
+//! Reads every row of every table and checks that it decodes, collecting
+//! failures instead of stopping at the first one. Nothing is written, so it is
+//! safe to run against an archive suspected of corruption.
+
+use redb::{Key, ReadTransaction, ReadableTable, TableDefinition, TableError, TableHandle};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AuditEntry, ScanMetadata, StoredScanResult, StoredVulnerability, TheArchive, ValueFormat,
+    AUDIT_TABLE, SCANS_TABLE, SCAN_RESULTS_TABLE, VULNERABILITIES_BY_TIME_TABLE,
+    VULNERABILITIES_TABLE,
+};
+use the_foundation::{CryptexError, CryptexResult};
+
+/// A row that failed to decode
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyFailure {
+    pub table: String,
+    pub key: String,
+    pub error: String,
+}
+
+/// Outcome of `TheArchive::verify`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerifyReport {
+    /// Rows examined across all tables
+    pub total: usize,
+    pub ok: usize,
+    pub failed: usize,
+    pub failures: Vec<VerifyFailure>,
+}
+
+impl VerifyReport {
+    /// True when every row decoded
+    pub fn is_clean(&self) -> bool {
+        self.failed == 0
+    }
+
+    fn pass(&mut self) {
+        self.total += 1;
+        self.ok += 1;
+    }
+
+    fn fail(&mut self, table: &str, key: impl Into<String>, error: impl ToString) {
+        self.total += 1;
+        self.failed += 1;
+        self.failures.push(VerifyFailure {
+            table: table.to_string(),
+            key: key.into(),
+            error: error.to_string(),
+        });
+    }
+}
+
+impl TheArchive {
+    /// Check that every stored row deserializes
+    ///
+    /// Errors only if the database itself cannot be read; bad rows are listed
+    /// in the report. Tables that were never created count as empty.
+    pub fn verify(&self) -> CryptexResult<VerifyReport> {
+        let read_txn = self.begin_read()?;
+
+        let mut report = VerifyReport::default();
+        let format = self.format;
+        verify_blobs::<ScanMetadata, _>(&read_txn, format, SCANS_TABLE, &mut report)?;
+        verify_blobs::<StoredVulnerability, _>(
+            &read_txn,
+            format,
+            VULNERABILITIES_TABLE,
+            &mut report,
+        )?;
+        verify_blobs::<StoredScanResult, _>(&read_txn, format, SCAN_RESULTS_TABLE, &mut report)?;
+        verify_blobs::<AuditEntry, _>(&read_txn, format, AUDIT_TABLE, &mut report)?;
+        verify_time_index(&read_txn, &mut report)?;
+
+        if report.is_clean() {
+            tracing::info!("Archive verified: {} rows ok", report.ok);
+        } else {
+            tracing::warn!(
+                "Archive verification found {} bad rows out of {}",
+                report.failed,
+                report.total
+            );
+        }
+
+        Ok(report)
+    }
+}
+
+/// Decode every value of a table as `T`, in the archive's value format
+fn verify_blobs<T, K>(
+    read_txn: &ReadTransaction,
+    format: ValueFormat,
+    definition: TableDefinition<K, &[u8]>,
+    report: &mut VerifyReport,
+) -> CryptexResult<()>
+where
+    T: DeserializeOwned,
+    K: Key + 'static,
+    for<'a> K::SelfType<'a>: ToString,
+{
+    let name = definition.name();
+    let table = match read_txn.open_table(definition) {
+        Ok(table) => table,
+        Err(TableError::TableDoesNotExist(_)) => return Ok(()),
+        Err(e) => {
+            return Err(CryptexError::ArchiveError(format!(
+                "Failed to open {} table: {}",
+                name, e
+            )))
+        }
+    };
+
+    let iter = table.iter().map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to iterate {} table: {}", name, e))
+    })?;
+
+    for entry in iter {
+        match entry {
+            Ok((key, value)) => match format.decode::<T>(value.value()) {
+                Ok(_) => report.pass(),
+                Err(e) => report.fail(name, key.value().to_string(), e),
+            },
+            Err(e) => report.fail(name, "<unreadable>", e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that every cached-at index key is well formed and names its CVE
+fn verify_time_index(read_txn: &ReadTransaction, report: &mut VerifyReport) -> CryptexResult<()> {
+    let name = VULNERABILITIES_BY_TIME_TABLE.name();
+    let table = match read_txn.open_table(VULNERABILITIES_BY_TIME_TABLE) {
+        Ok(table) => table,
+        Err(TableError::TableDoesNotExist(_)) => return Ok(()),
+        Err(e) => {
+            return Err(CryptexError::ArchiveError(format!(
+                "Failed to open {} table: {}",
+                name, e
+            )))
+        }
+    };
+
+    let iter = table.iter().map_err(|e| {
+        CryptexError::ArchiveError(format!("Failed to iterate {} table: {}", name, e))
+    })?;
+
+    for entry in iter {
+        let (key, value) = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.fail(name, "<unreadable>", e);
+                continue;
+            }
+        };
+
+        let (key, cve_id) = (key.value(), value.value());
+        match key.split_once('|') {
+            Some((nanos, suffix))
+                if nanos.len() == 20 && nanos.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                if suffix == cve_id {
+                    report.pass();
+                } else {
+                    report.fail(name, key, format!("key names {} but value is {}", suffix, cve_id));
+                }
+            }
+            _ => report.fail(name, key, "malformed index key"),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScanResult;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_verify_flags_corrupt_row() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = TheArchive::the_awakening(temp_dir.path().join("verify.db")).unwrap();
+
+        archive
+            .store_scan_metadata(&ScanMetadata::new(
+                "scan_ok".to_string(),
+                "10.0.0.0/24".to_string(),
+            ))
+            .unwrap();
+        archive
+            .store_vulnerability(&crate::VulnerabilityScore::new("CVE-2021-44228".to_string()))
+            .unwrap();
+        let result = ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "10.0.0.5".to_string(),
+            443,
+            "unknown".to_string(),
+            "Log4Shell".to_string(),
+        );
+        archive.store_scan_result("scan_ok", &result).unwrap();
+        archive.record_audit("alice", "scan.start", "scan_ok").unwrap();
+
+        let report = archive.verify().unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.total, 5);
+
+        // Write garbage directly, bypassing the typed API
+        let write_txn = archive.db.begin_write().unwrap();
+        {
+            let mut table = write_txn.open_table(SCANS_TABLE).unwrap();
+            table.insert("scan_bad", &[0xff, 0x00, 0x13][..]).unwrap();
+            let mut audit = write_txn.open_table(AUDIT_TABLE).unwrap();
+            audit.insert(1, &[0xff, 0x00, 0x13][..]).unwrap();
+        }
+        write_txn.commit().unwrap();
+
+        let report = archive.verify().unwrap();
+        assert_eq!(report.total, 7);
+        assert_eq!(report.ok, 5);
+        assert_eq!(report.failed, 2);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(report.failures[0].table, "scans");
+        assert_eq!(report.failures[0].key, "scan_bad");
+        assert_eq!(report.failures[1].table, "audit");
+        assert_eq!(report.failures[1].key, "1");
+    }
+}
@@ -0,0 +1,169 @@
+//! CPE Matching
+//!
+//! Traditional name: `CpeDictionary`
+//!
+//! Scanners often identify the software on a port without knowing which
+//! CVEs apply to it. A CPE name (`cpe:2.3:a:apache:http_server:2.4.49:...`)
+//! says what the software is; a [`CpeSource`] says which CVEs affect it, so
+//! detections can be driven by what was found rather than by precomputed CVE
+//! lists. The built-in index covers a handful of well-known products and
+//! stands in until NVD's CPE match feed is wired up.
+
+use async_trait::async_trait;
+use the_foundation::{CryptexError, CryptexResult};
+
+/// A parsed CPE name, reduced to the parts matching needs
+///
+/// Vendor, product and version are lower-cased. A version of `*` or `-`
+/// (any, not applicable) is stored as `None` and matches every version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cpe {
+    /// `a` (application), `o` (operating system) or `h` (hardware)
+    pub part: char,
+    pub vendor: String,
+    pub product: String,
+    pub version: Option<String>,
+}
+
+impl Cpe {
+    /// Parse a CPE 2.3 formatted string or a CPE 2.2 URI
+    ///
+    /// Both `cpe:2.3:a:apache:http_server:2.4.49:*:*:*:*:*:*:*` and
+    /// `cpe:/a:apache:http_server:2.4.49` are accepted. Vendor and product
+    /// are required; anything after the version is ignored.
+    pub fn parse(raw: &str) -> CryptexResult<Self> {
+        let trimmed = raw.trim();
+        let invalid = |why: &str| {
+            CryptexError::validation(format!("'{}' is not a CPE name: {}", trimmed, why))
+        };
+
+        let lower = trimmed.to_ascii_lowercase();
+        let fields = if let Some(rest) = lower.strip_prefix("cpe:2.3:") {
+            split_unescaped(rest)
+        } else if let Some(rest) = lower.strip_prefix("cpe:/") {
+            rest.split(':').map(str::to_string).collect()
+        } else {
+            return Err(invalid("expected cpe:2.3: or cpe:/"));
+        };
+
+        let field = |i: usize| fields.get(i).map(String::as_str).unwrap_or("");
+        let part = match field(0) {
+            "a" => 'a',
+            "o" => 'o',
+            "h" => 'h',
+            other => return Err(invalid(&format!("unknown part '{}'", other))),
+        };
+        let (vendor, product) = (field(1), field(2));
+        if [vendor, product].iter().any(|f| f.is_empty() || matches!(*f, "*" | "-")) {
+            return Err(invalid("vendor and product are required"));
+        }
+        let version = match field(3) {
+            "" | "*" | "-" => None,
+            version => Some(version.to_string()),
+        };
+
+        Ok(Self {
+            part,
+            vendor: vendor.to_string(),
+            product: product.to_string(),
+            version,
+        })
+    }
+
+    /// Whether this name covers `vendor`, `product` at `version`, of the given `part`
+    pub fn covers(&self, part: char, vendor: &str, product: &str, version: &str) -> bool {
+        self.part == part
+            && self.vendor.eq_ignore_ascii_case(vendor)
+            && self.product.eq_ignore_ascii_case(product)
+            && self.version.as_deref().is_none_or(|v| v.eq_ignore_ascii_case(version))
+    }
+}
+
+/// Fields of a CPE 2.3 formatted string, split on unescaped colons
+fn split_unescaped(rest: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    fields.last_mut().unwrap().push(escaped);
+                }
+            }
+            ':' => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// CPE Source - Maps a CPE name to the CVEs that affect it
+///
+/// Traditional name: `CpeMatchProvider`
+#[async_trait]
+pub trait CpeSource: Send + Sync {
+    /// CVE IDs affecting `cpe`; empty when the source knows of none
+    async fn cves_for(&self, cpe: &Cpe) -> CryptexResult<Vec<String>>;
+}
+
+/// Affected `(part, vendor, product, version)` and the CVEs against it
+const BUILTIN_CPE_INDEX: &[(char, &str, &str, &str, &[&str])] = &[
+    ('a', "apache", "http_server", "2.4.49", &["CVE-2021-41773", "CVE-2021-42013"]),
+    ('a', "apache", "http_server", "2.4.50", &["CVE-2021-42013"]),
+    ('a', "apache", "log4j", "2.14.1", &["CVE-2021-44228", "CVE-2021-45046"]),
+    ('a', "apache", "log4j", "2.15.0", &["CVE-2021-45046"]),
+    ('a', "openssl", "openssl", "1.0.1f", &["CVE-2014-0160"]),
+    ('a', "openbsd", "openssh", "9.6", &["CVE-2024-6387"]),
+    ('a', "citrix", "netscaler_gateway", "13.1", &["CVE-2023-4966"]),
+];
+
+/// Built-in CPE index (stub - would load NVD's CPE match feed)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinCpeIndex;
+
+#[async_trait]
+impl CpeSource for BuiltinCpeIndex {
+    async fn cves_for(&self, cpe: &Cpe) -> CryptexResult<Vec<String>> {
+        Ok(BUILTIN_CPE_INDEX
+            .iter()
+            .filter(|(part, vendor, product, version, _)| {
+                cpe.covers(*part, vendor, product, version)
+            })
+            .flat_map(|(_, _, _, _, cve_ids)| cve_ids.iter().map(|id| id.to_string()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpe_forms() {
+        let formatted = Cpe::parse("cpe:2.3:a:Apache:HTTP_Server:2.4.49:*:*:*:*:*:*:*").unwrap();
+        let uri = Cpe::parse("  cpe:/a:apache:http_server:2.4.49 ").unwrap();
+        assert_eq!(formatted, uri);
+        assert_eq!(formatted.vendor, "apache");
+        assert_eq!(formatted.version.as_deref(), Some("2.4.49"));
+
+        let escaped = Cpe::parse(r"cpe:2.3:a:vendor:product\:x:1.0").unwrap();
+        assert_eq!(escaped.product, "product:x");
+        assert_eq!(Cpe::parse("cpe:2.3:o:linux:linux_kernel:*").unwrap().version, None);
+
+        let malformed =
+            ["", "apache:http_server", "cpe:2.3:x:apache:httpd", "cpe:/a:apache", "cpe:2.3:a:*:*"];
+        for raw in malformed {
+            let err = Cpe::parse(raw).unwrap_err();
+            assert!(matches!(err, CryptexError::ValidationError(_)), "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn test_covers_checks_part() {
+        let app = Cpe::parse("cpe:/a:linux:linux_kernel:6.1").unwrap();
+        let os = Cpe::parse("cpe:/o:linux:linux_kernel:6.1").unwrap();
+        assert!(!app.covers('o', "linux", "linux_kernel", "6.1"));
+        assert!(os.covers('o', "Linux", "Linux_Kernel", "6.1"));
+        assert!(!os.covers('o', "linux", "linux_kernel", "6.2"));
+    }
+}
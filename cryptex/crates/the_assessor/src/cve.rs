@@ -0,0 +1,78 @@
+//! CVE Identifiers
+//!
+//! Traditional name: `CveIdParser`
+//!
+//! CVE IDs arrive pasted from advisories, tickets and chat in whatever form
+//! the source used: lower case, spaces for dashes, stray whitespace. Every
+//! assessment entry point runs them through [`normalize_cve_id`] first so
+//! that one vulnerability has one cache key and one archive row.
+
+use the_foundation::{CryptexError, CryptexResult};
+
+/// The canonical `CVE-YYYY-NNNN` form of a CVE ID
+///
+/// Case is ignored, surrounding whitespace trimmed, and the parts may be
+/// separated by any run of non-alphanumeric characters (`cve 2021 44228`,
+/// `CVE_2021_44228`). The year must have four digits and the sequence
+/// number at least four. Anything else is a validation error.
+pub fn normalize_cve_id(raw: &str) -> CryptexResult<String> {
+    let invalid = || {
+        CryptexError::validation(format!(
+            "'{}' is not a CVE ID (expected CVE-YYYY-NNNN)",
+            raw.trim()
+        ))
+    };
+
+    let upper = raw.trim().to_ascii_uppercase();
+    let rest = upper.strip_prefix("CVE").ok_or_else(invalid)?;
+    let mut parts = rest
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty());
+
+    let (Some(year), Some(number), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if year.len() != 4 || !digits(year) || number.len() < 4 || !digits(number) {
+        return Err(invalid());
+    }
+
+    Ok(format!("CVE-{}-{}", year, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizes_messy_ids() {
+        for raw in [
+            "CVE-2021-44228",
+            "cve-2021-44228",
+            "  CVE-2021-44228 \n",
+            "CVE 2021 44228",
+            "cve_2021_44228",
+            "CVE2021-44228",
+        ] {
+            assert_eq!(normalize_cve_id(raw).unwrap(), "CVE-2021-44228", "{:?}", raw);
+        }
+        assert_eq!(normalize_cve_id("cve-2024-1234567").unwrap(), "CVE-2024-1234567");
+    }
+
+    #[test]
+    fn test_rejects_non_cve_ids() {
+        for raw in [
+            "",
+            "GHSA-jfh8-c2jp-5v3q",
+            "CVE-2021",
+            "CVE-21-44228",
+            "CVE-2021-442",
+            "CVE-2021-44228-1",
+            "CVE-2021-4422x",
+            "XCVE-2021-44228",
+        ] {
+            let err = normalize_cve_id(raw).unwrap_err();
+            assert!(matches!(err, CryptexError::ValidationError(_)), "{:?}", raw);
+        }
+    }
+}
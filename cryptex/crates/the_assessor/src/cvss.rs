@@ -9,18 +9,7 @@ use crate::types::*;
 /// Based on CVSS v3.1 specification:
 /// https://www.first.org/cvss/v3.1/specification-document
 pub fn calculate_cvss_v3_base_score(metrics: &CvssV3Base) -> f64 {
-    // Impact Sub-Score calculation
-    let isc_base = 1.0 - (
-        (1.0 - confidentiality_impact(&metrics.confidentiality)) *
-        (1.0 - integrity_impact(&metrics.integrity)) *
-        (1.0 - availability_impact(&metrics.availability))
-    );
-
-    let impact = if metrics.scope == "U" {
-        6.42 * isc_base
-    } else {
-        7.52 * (isc_base - 0.029) - 3.25 * (isc_base - 0.02).powi(15)
-    };
+    let impact = calculate_cvss_v3_impact_subscore(metrics);
 
     // Exploitability Sub-Score calculation
     let exploitability = 8.22 *
@@ -42,6 +31,24 @@ pub fn calculate_cvss_v3_base_score(metrics: &CvssV3Base) -> f64 {
     }
 }
 
+/// Calculate the CVSS v3.x Impact Sub-Score
+///
+/// Ranges from 0.0 (no impact) up to about 6.0 (total loss with changed scope).
+/// Values at or below zero mean the base score is 0.0.
+pub fn calculate_cvss_v3_impact_subscore(metrics: &CvssV3Base) -> f64 {
+    let isc_base = 1.0 - (
+        (1.0 - confidentiality_impact(&metrics.confidentiality)) *
+        (1.0 - integrity_impact(&metrics.integrity)) *
+        (1.0 - availability_impact(&metrics.availability))
+    );
+
+    if metrics.scope == "U" {
+        6.42 * isc_base
+    } else {
+        7.52 * (isc_base - 0.029) - 3.25 * (isc_base - 0.02).powi(15)
+    }
+}
+
 /// Parse CVSS v3.x vector string
 ///
 /// Example: "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"
@@ -94,9 +101,27 @@ pub fn parse_cvss_v3_vector(vector: &str) -> Result<CvssV3Base, String> {
         return Err("Missing required CVSS metrics".to_string());
     }
 
+    // Reject values the scoring tables don't define rather than scoring them as zero
+    validate_metric("AV", &metrics.attack_vector, &["N", "A", "L", "P"])?;
+    validate_metric("AC", &metrics.attack_complexity, &["L", "H"])?;
+    validate_metric("PR", &metrics.privileges_required, &["N", "L", "H"])?;
+    validate_metric("UI", &metrics.user_interaction, &["N", "R"])?;
+    validate_metric("S", &metrics.scope, &["U", "C"])?;
+    validate_metric("C", &metrics.confidentiality, &["N", "L", "H"])?;
+    validate_metric("I", &metrics.integrity, &["N", "L", "H"])?;
+    validate_metric("A", &metrics.availability, &["N", "L", "H"])?;
+
     Ok(metrics)
 }
 
+fn validate_metric(name: &str, value: &str, allowed: &[&str]) -> Result<(), String> {
+    if allowed.contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("Invalid value '{}' for CVSS metric {}", value, name))
+    }
+}
+
 /// Create full CvssV3 from vector string
 pub fn cvss_v3_from_vector(vector: &str) -> Result<CvssV3, String> {
     let metrics = parse_cvss_v3_vector(vector)?;
@@ -244,4 +269,16 @@ mod tests {
         assert_eq!(roundup(4.0), 4.0);
         assert_eq!(roundup(5.567), 5.6);
     }
+
+    #[test]
+    fn test_rejects_unknown_metric_value() {
+        let err = parse_cvss_v3_vector("CVSS:3.1/AV:X/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").unwrap_err();
+        assert!(err.contains("AV"));
+    }
+
+    #[test]
+    fn test_vector_version() {
+        let cvss = cvss_v3_from_vector("CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").unwrap();
+        assert_eq!(cvss.version(), "3.0");
+    }
 }
@@ -0,0 +1,62 @@
+//! CWE Weakness Classes
+//!
+//! Traditional name: `CweCatalog`
+//!
+//! Short names for the weaknesses NVD most often attributes to CVEs, so a
+//! report can say what a cluster of findings has in common without looking
+//! anything up. IDs not listed here are shown bare.
+
+/// Common CWE IDs and their short names (the CWE Top 25 plus frequent extras)
+pub const CWE_NAMES: &[(&str, &str)] = &[
+    ("CWE-20", "Improper Input Validation"),
+    ("CWE-22", "Path Traversal"),
+    ("CWE-77", "Command Injection"),
+    ("CWE-78", "OS Command Injection"),
+    ("CWE-79", "Cross-site Scripting"),
+    ("CWE-89", "SQL Injection"),
+    ("CWE-94", "Code Injection"),
+    ("CWE-119", "Memory Buffer Bounds Violation"),
+    ("CWE-125", "Out-of-bounds Read"),
+    ("CWE-190", "Integer Overflow or Wraparound"),
+    ("CWE-200", "Exposure of Sensitive Information"),
+    ("CWE-269", "Improper Privilege Management"),
+    ("CWE-276", "Incorrect Default Permissions"),
+    ("CWE-287", "Improper Authentication"),
+    ("CWE-295", "Improper Certificate Validation"),
+    ("CWE-306", "Missing Authentication for Critical Function"),
+    ("CWE-327", "Broken or Risky Cryptographic Algorithm"),
+    ("CWE-352", "Cross-Site Request Forgery"),
+    ("CWE-362", "Race Condition"),
+    ("CWE-400", "Uncontrolled Resource Consumption"),
+    ("CWE-416", "Use After Free"),
+    ("CWE-434", "Unrestricted File Upload"),
+    ("CWE-476", "NULL Pointer Dereference"),
+    ("CWE-502", "Deserialization of Untrusted Data"),
+    ("CWE-611", "XML External Entity Reference"),
+    ("CWE-787", "Out-of-bounds Write"),
+    ("CWE-798", "Hard-coded Credentials"),
+    ("CWE-862", "Missing Authorization"),
+    ("CWE-863", "Incorrect Authorization"),
+    ("CWE-918", "Server-Side Request Forgery"),
+];
+
+/// Short name of the weakness class `cwe_id` (e.g. "CWE-502"), if known
+pub fn cwe_name(cwe_id: &str) -> Option<&'static str> {
+    let cwe_id = cwe_id.trim();
+    CWE_NAMES
+        .iter()
+        .find(|(id, _)| id.eq_ignore_ascii_case(cwe_id))
+        .map(|(_, name)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cwe_name_lookup() {
+        assert_eq!(cwe_name("CWE-502"), Some("Deserialization of Untrusted Data"));
+        assert_eq!(cwe_name("cwe-79"), Some("Cross-site Scripting"));
+        assert_eq!(cwe_name("NVD-CWE-noinfo"), None);
+    }
+}
@@ -0,0 +1,270 @@
+//! KEV Catalog Sources
+//!
+//! Where the assessor learns whether a CVE is in CISA's Known Exploited
+//! Vulnerabilities catalog. The catalog only grows, so a CVE assessed last
+//! month may have been added since; keeping the lookup behind a trait lets
+//! re-assessment pick that up, and lets tests stand in a catalog of their own.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use the_foundation::CryptexResult;
+use tokio::sync::Mutex;
+
+use crate::types::KevInfo;
+
+/// How long a downloaded catalog is trusted before it is fetched again
+pub const DEFAULT_KEV_CATALOG_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// KEV Source - Looks up a CVE in the KEV catalog
+///
+/// Traditional name: `KevProvider`
+#[async_trait]
+pub trait KevSource: Send + Sync {
+    /// Catalog entry for `cve_id`, or `None` if it is not listed
+    async fn lookup(&self, cve_id: &str) -> CryptexResult<Option<KevInfo>>;
+
+    /// Load whatever the source needs ahead of the first lookup
+    ///
+    /// Sources without a catalog to download have nothing to do.
+    async fn warm_up(&self) -> CryptexResult<()> {
+        Ok(())
+    }
+}
+
+/// KEV Feed - Downloads the whole KEV catalog at once
+///
+/// Traditional name: `KevCatalogClient`
+#[async_trait]
+pub trait KevFeed: Send + Sync {
+    /// Every catalog entry, keyed by CVE ID
+    async fn fetch_catalog(&self) -> CryptexResult<HashMap<String, KevInfo>>;
+}
+
+/// How long to keep serving a stale catalog after a failed refresh
+pub const DEFAULT_KEV_REFRESH_RETRY: Duration = Duration::from_secs(5 * 60);
+
+/// A downloaded catalog, when it was fetched, and when a refresh last failed
+struct LoadedCatalog {
+    fetched_at: Instant,
+    failed_at: Option<Instant>,
+    entries: Arc<HashMap<String, KevInfo>>,
+}
+
+/// Cached KEV Catalog - Serves lookups from a periodically downloaded feed
+///
+/// Traditional name: `KevCatalogCache`
+///
+/// The feed is fetched on the first lookup (or by `warm_up`) and again once
+/// the copy is older than its maximum age. Only one fetch runs at a time:
+/// with nothing cached, lookups wait for it; with an old copy cached, they
+/// are answered from that copy instead. A refresh that fails keeps the old
+/// copy in service and is retried after a short delay.
+pub struct CachedKevCatalog {
+    feed: Arc<dyn KevFeed>,
+    max_age: Duration,
+    refresh_retry: Duration,
+    catalog: std::sync::Mutex<Option<LoadedCatalog>>,
+    fetching: Mutex<()>,
+}
+
+impl CachedKevCatalog {
+    pub fn new(feed: Arc<dyn KevFeed>) -> Self {
+        Self {
+            feed,
+            max_age: DEFAULT_KEV_CATALOG_MAX_AGE,
+            refresh_retry: DEFAULT_KEV_REFRESH_RETRY,
+            catalog: std::sync::Mutex::new(None),
+            fetching: Mutex::new(()),
+        }
+    }
+
+    /// Re-fetch the catalog once it is older than `max_age`
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Wait `refresh_retry` before trying again after a failed refresh
+    pub fn with_refresh_retry(mut self, refresh_retry: Duration) -> Self {
+        self.refresh_retry = refresh_retry;
+        self
+    }
+
+    /// The cached entries, and whether they are due for a refresh
+    fn cached(&self) -> Option<(Arc<HashMap<String, KevInfo>>, bool)> {
+        let catalog = self.catalog.lock().unwrap_or_else(|e| e.into_inner());
+        catalog.as_ref().map(|loaded| {
+            let backing_off =
+                loaded.failed_at.is_some_and(|at| at.elapsed() < self.refresh_retry);
+            let due = loaded.fetched_at.elapsed() >= self.max_age && !backing_off;
+            (loaded.entries.clone(), due)
+        })
+    }
+
+    async fn entries(&self) -> CryptexResult<Arc<HashMap<String, KevInfo>>> {
+        let stale = match self.cached() {
+            Some((entries, false)) => return Ok(entries),
+            Some((entries, true)) => Some(entries),
+            None => None,
+        };
+
+        let _fetching = match &stale {
+            Some(entries) => match self.fetching.try_lock() {
+                Ok(guard) => guard,
+                // Someone else is refreshing; the old copy will do meanwhile
+                Err(_) => return Ok(entries.clone()),
+            },
+            None => self.fetching.lock().await,
+        };
+
+        // A fetch may have finished while this one waited for its turn
+        if let Some((entries, false)) = self.cached() {
+            return Ok(entries);
+        }
+
+        match self.feed.fetch_catalog().await {
+            Ok(entries) => {
+                let entries = Arc::new(entries);
+                tracing::info!("Loaded KEV catalog with {} entries", entries.len());
+                *self.catalog.lock().unwrap_or_else(|e| e.into_inner()) = Some(LoadedCatalog {
+                    fetched_at: Instant::now(),
+                    failed_at: None,
+                    entries: entries.clone(),
+                });
+                Ok(entries)
+            }
+            Err(e) => {
+                let Some(entries) = stale else {
+                    return Err(e);
+                };
+                tracing::warn!("KEV catalog refresh failed; serving the previous copy: {}", e);
+                if let Some(loaded) =
+                    self.catalog.lock().unwrap_or_else(|e| e.into_inner()).as_mut()
+                {
+                    loaded.failed_at = Some(Instant::now());
+                }
+                Ok(entries)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl KevSource for CachedKevCatalog {
+    async fn lookup(&self, cve_id: &str) -> CryptexResult<Option<KevInfo>> {
+        Ok(self.entries().await?.get(cve_id).cloned())
+    }
+
+    async fn warm_up(&self) -> CryptexResult<()> {
+        self.entries().await.map(|_| ())
+    }
+}
+
+/// Built-in catalog (stub - would fetch the CISA KEV feed)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinKevCatalog;
+
+#[async_trait]
+impl KevSource for BuiltinKevCatalog {
+    async fn lookup(&self, cve_id: &str) -> CryptexResult<Option<KevInfo>> {
+        // For now, mark Log4Shell as KEV
+        if cve_id.starts_with("CVE-2021-44228") {
+            return Ok(Some(KevInfo {
+                is_kev: true,
+                date_added: Some("2021-12-10".to_string()),
+                due_date: Some("2021-12-24".to_string()),
+                required_action: Some("Apply updates per vendor instructions".to_string()),
+                known_ransomware_use: true,
+            }));
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use the_foundation::CryptexError;
+    use tokio::sync::Notify;
+
+    fn listed() -> HashMap<String, KevInfo> {
+        let entry = KevInfo {
+            is_kev: true,
+            date_added: Some("2023-10-10".to_string()),
+            due_date: None,
+            required_action: None,
+            known_ransomware_use: false,
+        };
+        HashMap::from([("CVE-2023-4966".to_string(), entry)])
+    }
+
+    /// Feed whose first download succeeds and whose later ones fail, or
+    /// hang until released
+    #[derive(Default)]
+    struct FlakyFeed {
+        fetches: AtomicU64,
+        hang: bool,
+        release: Notify,
+    }
+
+    #[async_trait]
+    impl KevFeed for FlakyFeed {
+        async fn fetch_catalog(&self) -> CryptexResult<HashMap<String, KevInfo>> {
+            if self.fetches.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Ok(listed());
+            }
+            if self.hang {
+                self.release.notified().await;
+            }
+            Err(CryptexError::upstream("CISA returned 503"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_serves_the_previous_copy() {
+        let feed = Arc::new(FlakyFeed::default());
+        let catalog = CachedKevCatalog::new(feed.clone())
+            .with_max_age(Duration::ZERO)
+            .with_refresh_retry(Duration::from_secs(60));
+
+        assert!(catalog.lookup("CVE-2023-4966").await.unwrap().is_some());
+        assert!(catalog.lookup("CVE-2023-4966").await.unwrap().is_some());
+        assert_eq!(feed.fetches.load(Ordering::SeqCst), 2);
+
+        // The failed refresh is not retried on every lookup
+        assert!(catalog.lookup("CVE-2023-4966").await.unwrap().is_some());
+        assert_eq!(feed.fetches.load(Ordering::SeqCst), 2);
+
+        // With no copy to fall back on, the failure is reported
+        let empty = CachedKevCatalog::new(feed.clone());
+        assert!(empty.lookup("CVE-2023-4966").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lookup_does_not_wait_for_a_refresh() {
+        let feed = Arc::new(FlakyFeed { hang: true, ..Default::default() });
+        let catalog = Arc::new(CachedKevCatalog::new(feed.clone()).with_max_age(Duration::ZERO));
+        catalog.warm_up().await.unwrap();
+
+        let refreshing = tokio::spawn({
+            let catalog = catalog.clone();
+            async move { catalog.lookup("CVE-2023-4966").await }
+        });
+        while feed.fetches.load(Ordering::SeqCst) < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        let during = tokio::time::timeout(Duration::from_secs(1), catalog.lookup("CVE-2023-4966"))
+            .await
+            .expect("lookup waited for the refresh");
+        assert!(during.unwrap().is_some());
+        assert_eq!(feed.fetches.load(Ordering::SeqCst), 2);
+
+        feed.release.notify_one();
+        assert!(refreshing.await.unwrap().unwrap().is_some());
+    }
+}
@@ -38,11 +38,31 @@
 
 pub mod types;
 pub mod cvss;
+pub mod cve;
+pub mod cpe;
+pub mod cwe;
 pub mod scoring;
+pub mod kev;
+pub mod limit;
+pub mod maintenance;
+pub mod warmup;
 
 pub use types::*;
 pub use cvss::*;
-pub use scoring::TheAssessor;
+pub use cve::normalize_cve_id;
+pub use cpe::{BuiltinCpeIndex, Cpe, CpeSource};
+pub use cwe::{cwe_name, CWE_NAMES};
+pub use scoring::{AssessOptions, TheAssessor, DEFAULT_REASSESS_INTERVAL};
+pub use kev::{
+    BuiltinKevCatalog, CachedKevCatalog, KevFeed, KevSource, DEFAULT_KEV_CATALOG_MAX_AGE,
+    DEFAULT_KEV_REFRESH_RETRY,
+};
+pub use limit::{
+    init_shared_fetch_limit, max_concurrent_fetches_from_env, shared_fetch_limit,
+    DEFAULT_MAX_CONCURRENT_FETCHES, MAX_CONCURRENT_FETCHES_ENV,
+};
+pub use maintenance::AssessmentStore;
+pub use warmup::{spawn_warm_up, warm_up_watchlist, WARMUP_ENV, WATCHLIST_ENV};
 
 // Re-export from foundation
 pub use the_foundation::CryptexResult;
@@ -0,0 +1,97 @@
+//! Outbound Fetch Limit
+//!
+//! Traditional name: `ConcurrencyLimiter`
+//!
+//! The scanner, the batch endpoint and re-assessment jobs each hold their own
+//! assessor. Left alone, each would open as many source requests as it had
+//! CVEs to assess. Every assessor in the process shares one semaphore by
+//! default, and an assessment holds a permit while it consults external
+//! sources, so the number of assessments hitting NVD, KEV and EPSS at once
+//! is bounded whoever started them.
+
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Environment variable sizing the process-wide fetch limit
+///
+/// Mirrors `assessor.max_concurrent_fetches` in the charter.
+pub const MAX_CONCURRENT_FETCHES_ENV: &str = "CRYPTEX_ASSESSOR__MAX_CONCURRENT_FETCHES";
+
+/// Assessments allowed to fetch at once when none is configured
+pub const DEFAULT_MAX_CONCURRENT_FETCHES: usize = 8;
+
+/// The shared semaphore and the number of permits it was created with
+static SHARED_FETCH_LIMIT: OnceLock<(usize, Arc<Semaphore>)> = OnceLock::new();
+
+/// The semaphore every assessor in this process shares by default
+///
+/// Sized by [`init_shared_fetch_limit`] if a server called it at start-up,
+/// otherwise from the environment the first time it is asked for.
+pub fn shared_fetch_limit() -> Arc<Semaphore> {
+    let permits = max_concurrent_fetches_from_env().unwrap_or(DEFAULT_MAX_CONCURRENT_FETCHES);
+    SHARED_FETCH_LIMIT
+        .get_or_init(|| (permits, Arc::new(Semaphore::new(permits))))
+        .1
+        .clone()
+}
+
+/// Size the process-wide fetch limit from the charter
+///
+/// `configured` is `assessor.max_concurrent_fetches`, which
+/// `CRYPTEX_ASSESSOR__MAX_CONCURRENT_FETCHES` overrides. Call it before the
+/// first assessor is created; once sized, the limit stays as it is and a
+/// different size asked for later is logged and ignored.
+pub fn init_shared_fetch_limit(configured: usize) -> Arc<Semaphore> {
+    let permits = max_concurrent_fetches_from_env().unwrap_or(configured);
+    let (size, limit) =
+        SHARED_FETCH_LIMIT.get_or_init(|| (permits, Arc::new(Semaphore::new(permits))));
+    if *size != permits {
+        tracing::warn!(
+            "Assessment fetch limit is already {}; ignoring max_concurrent_fetches = {}",
+            size,
+            permits
+        );
+    }
+    limit.clone()
+}
+
+/// Fetch limit configured through the environment, if set
+///
+/// Unparseable values are logged and ignored.
+pub fn max_concurrent_fetches_from_env() -> Option<usize> {
+    let value = std::env::var(MAX_CONCURRENT_FETCHES_ENV).ok()?;
+
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => Some(n),
+        _ => {
+            tracing::warn!(
+                "Ignoring {}={}: not a positive number",
+                MAX_CONCURRENT_FETCHES_ENV,
+                value
+            );
+            None
+        }
+    }
+}
+
+/// Wait for a permit to consult external sources
+pub(crate) async fn acquire(limit: &Semaphore) -> CryptexResult<SemaphorePermit<'_>> {
+    limit
+        .acquire()
+        .await
+        .map_err(|_| CryptexError::unknown("Assessment fetch limit was closed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_limit_is_sized_once() {
+        let first = init_shared_fetch_limit(3);
+        let second = init_shared_fetch_limit(5);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(Arc::ptr_eq(&first, &shared_fetch_limit()));
+    }
+}
@@ -0,0 +1,137 @@
+//! Re-assessment of stale scores
+//!
+//! Stored assessments are snapshots: a CVE that was not in KEV when it was
+//! scored stays un-flagged in the archive until something scores it again.
+//! `reassess_stale` walks the stored assessments older than a threshold,
+//! fetches every source afresh and writes the new scores back.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::time::Duration;
+use the_foundation::{CryptexError, CryptexResult};
+
+use crate::scoring::TheAssessor;
+use crate::types::VulnerabilityScore;
+
+/// Assessment Store - Persistence backend holding assessed scores
+///
+/// Traditional name: `AssessmentRepository`
+pub trait AssessmentStore: Send + Sync {
+    /// CVE IDs whose stored assessment was made before `cutoff`, oldest first
+    fn stale_assessments(&self, cutoff: DateTime<Utc>) -> CryptexResult<Vec<String>>;
+
+    /// Replace the stored assessment for `score.cve_id`
+    fn store_assessment(&self, score: &VulnerabilityScore) -> CryptexResult<()>;
+}
+
+impl TheAssessor {
+    /// Re-assess every stored score older than `older_than`
+    ///
+    /// Traditional name: `refresh_stale_assessments`
+    ///
+    /// Fetches are spaced by the re-assess interval. A rate-limited source
+    /// stops the run early - the remaining scores are still stale and are
+    /// picked up next time - while any other per-CVE failure is logged and
+    /// skipped. Returns the number of scores refreshed.
+    pub async fn reassess_stale(
+        &self,
+        store: &dyn AssessmentStore,
+        older_than: Duration,
+    ) -> CryptexResult<usize> {
+        let older_than = ChronoDuration::from_std(older_than)
+            .map_err(|_| CryptexError::validation("Re-assessment threshold is too large"))?;
+        let stale = store.stale_assessments(Utc::now() - older_than)?;
+        tracing::info!("Re-assessing {} stale vulnerabilities", stale.len());
+
+        let mut refreshed = 0;
+        for (i, cve_id) in stale.iter().enumerate() {
+            if i > 0 && !self.reassess_interval.is_zero() {
+                tokio::time::sleep(self.reassess_interval).await;
+            }
+
+            match self.fetch_and_cache(cve_id).await {
+                Ok(score) => {
+                    store.store_assessment(&score)?;
+                    refreshed += 1;
+                }
+                Err(CryptexError::RateLimited(e)) => {
+                    tracing::warn!(
+                        "Re-assessment rate limited after {} of {}: {}",
+                        refreshed,
+                        stale.len(),
+                        e
+                    );
+                    break;
+                }
+                Err(e) => tracing::warn!("Failed to re-assess {}: {}", cve_id, e),
+            }
+        }
+
+        Ok(refreshed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kev::KevSource;
+    use crate::types::KevInfo;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    /// Keeps every assessment "stale" and records what was written back
+    #[derive(Default)]
+    struct MemoryStore {
+        cve_ids: Vec<String>,
+        stored: Mutex<Vec<VulnerabilityScore>>,
+    }
+
+    impl AssessmentStore for MemoryStore {
+        fn stale_assessments(&self, _cutoff: DateTime<Utc>) -> CryptexResult<Vec<String>> {
+            Ok(self.cve_ids.clone())
+        }
+
+        fn store_assessment(&self, score: &VulnerabilityScore) -> CryptexResult<()> {
+            self.stored.lock().unwrap().push(score.clone());
+            Ok(())
+        }
+    }
+
+    /// Answers the first lookup, then reports the feed as rate limited
+    struct ThrottledKev {
+        calls: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl KevSource for ThrottledKev {
+        async fn lookup(&self, _cve_id: &str) -> CryptexResult<Option<KevInfo>> {
+            let mut calls = self.calls.lock().unwrap();
+            *calls += 1;
+            if *calls > 1 {
+                return Err(CryptexError::rate_limited("KEV feed"));
+            }
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_stops_reassessment() {
+        let kev = Arc::new(ThrottledKev {
+            calls: Mutex::new(0),
+        });
+        let assessor = TheAssessor::the_awakening()
+            .await
+            .unwrap()
+            .with_kev_source(kev.clone())
+            .with_reassess_interval(Duration::ZERO);
+        let store = MemoryStore {
+            cve_ids: vec!["CVE-2024-0001".into(), "CVE-2024-0002".into(), "CVE-2024-0003".into()],
+            ..MemoryStore::default()
+        };
+
+        let refreshed = assessor.reassess_stale(&store, Duration::ZERO).await.unwrap();
+
+        assert_eq!(refreshed, 1);
+        assert_eq!(*kev.calls.lock().unwrap(), 2);
+        assert_eq!(store.stored.lock().unwrap()[0].cve_id, "CVE-2024-0001");
+    }
+}
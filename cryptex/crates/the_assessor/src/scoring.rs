@@ -1,11 +1,44 @@
 //! Comprehensive Vulnerability Scoring and Assessment
 
 use crate::types::*;
+use crate::cve::normalize_cve_id;
+use crate::cpe::{BuiltinCpeIndex, Cpe, CpeSource};
 use crate::cvss::*;
+use crate::kev::{BuiltinKevCatalog, KevSource};
+use crate::limit;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
-use the_foundation::CryptexResult;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OnceCell, RwLock, Semaphore};
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Cache behaviour for a single assessment
+///
+/// The default reuses a cached score when there is one and fetches otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssessOptions {
+    /// Ignore any cached score and fetch fresh data, replacing the cache entry
+    pub refresh: bool,
+    /// Never fetch; a CVE that is not cached yields no score
+    pub cached_only: bool,
+}
+
+impl AssessOptions {
+    fn validate(&self) -> CryptexResult<()> {
+        if self.refresh && self.cached_only {
+            return Err(CryptexError::validation(
+                "refresh and cached_only cannot both be set",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A fetch in progress; every caller asking for the same CVE awaits the same cell
+type Flight = Arc<OnceCell<CryptexResult<VulnerabilityScore>>>;
 
 /// The Assessor - Comprehensive Vulnerability Scoring System
 ///
@@ -16,10 +49,31 @@ pub struct TheAssessor {
     /// Cache of vulnerability scores
     score_cache: Arc<RwLock<HashMap<String, VulnerabilityScore>>>,
 
+    /// Number of times external sources were consulted (cache misses)
+    source_fetches: Arc<AtomicU64>,
+
+    /// Fetches in progress by CVE ID, so concurrent misses share one fetch
+    in_flight: Arc<Mutex<HashMap<String, Flight>>>,
+
+    /// Where KEV catalog membership is looked up
+    pub(crate) kev_source: Arc<dyn KevSource>,
+
+    /// Where the CVEs affecting a CPE are looked up
+    cpe_source: Arc<dyn CpeSource>,
+
+    /// Pause between source fetches when re-assessing in bulk
+    pub(crate) reassess_interval: Duration,
+
+    /// Bounds how many assessments consult external sources at once
+    pub(crate) fetch_limit: Arc<Semaphore>,
+
     /// Configuration
     enable_ai_enhancement: bool,
 }
 
+/// Default pause between re-assessments, to stay inside source rate limits
+pub const DEFAULT_REASSESS_INTERVAL: Duration = Duration::from_millis(250);
+
 impl TheAssessor {
     /// The Awakening - Initialize The Assessor
     ///
@@ -29,16 +83,50 @@ impl TheAssessor {
 
         Ok(Self {
             score_cache: Arc::new(RwLock::new(HashMap::new())),
+            source_fetches: Arc::new(AtomicU64::new(0)),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            kev_source: Arc::new(BuiltinKevCatalog),
+            cpe_source: Arc::new(BuiltinCpeIndex),
+            reassess_interval: DEFAULT_REASSESS_INTERVAL,
+            fetch_limit: limit::shared_fetch_limit(),
             enable_ai_enhancement: true,
         })
     }
 
+    /// Look up KEV membership in `source` instead of the built-in catalog
+    pub fn with_kev_source(mut self, source: Arc<dyn KevSource>) -> Self {
+        self.kev_source = source;
+        self
+    }
+
+    /// Match CPE names against `source` instead of the built-in index
+    pub fn with_cpe_source(mut self, source: Arc<dyn CpeSource>) -> Self {
+        self.cpe_source = source;
+        self
+    }
+
+    /// Bound source fetches by `limit` instead of the process-wide semaphore
+    pub fn with_fetch_limit(mut self, limit: Arc<Semaphore>) -> Self {
+        self.fetch_limit = limit;
+        self
+    }
+
+    /// Set the pause between fetches in [`TheAssessor::reassess_stale`]
+    pub fn with_reassess_interval(mut self, interval: Duration) -> Self {
+        self.reassess_interval = interval;
+        self
+    }
+
     /// Assess a vulnerability by CVE ID
     ///
     /// Traditional name: `assess` or `score_vulnerability`
     ///
     /// Returns a comprehensive vulnerability score with CVSS, KEV, EPSS, and AI-enhanced data.
+    /// Concurrent calls for the same uncached CVE share a single fetch. The ID
+    /// is normalized first (see [`normalize_cve_id`]); one that isn't a CVE ID
+    /// is a validation error.
     pub async fn assess_vulnerability(&self, cve_id: &str) -> CryptexResult<VulnerabilityScore> {
+        let cve_id = &normalize_cve_id(cve_id)?;
         tracing::debug!("Assessing vulnerability: {}", cve_id);
 
         // Check cache first
@@ -50,13 +138,106 @@ impl TheAssessor {
             }
         }
 
+        self.coalesced_fetch(cve_id).await
+    }
+
+    /// Fetch `cve_id` once however many callers miss the cache for it at the same time
+    ///
+    /// Every caller joins the CVE's flight; only the first runs the fetch and
+    /// the rest await its result. Different CVEs fly independently. If the
+    /// fetching caller is cancelled, a waiting caller takes the fetch over.
+    async fn coalesced_fetch(&self, cve_id: &str) -> CryptexResult<VulnerabilityScore> {
+        let flight = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(in_flight.entry(cve_id.to_string()).or_default())
+        };
+
+        let result = flight
+            .get_or_init(|| async {
+                // A flight that landed after our cache check has already cached it
+                if let Some(score) = self.score_cache.read().await.get(cve_id) {
+                    return Ok(score.clone());
+                }
+                self.fetch_and_cache(cve_id).await
+            })
+            .await;
+
+        {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            if in_flight.get(cve_id).is_some_and(|f| Arc::ptr_eq(f, &flight)) {
+                in_flight.remove(cve_id);
+            }
+        }
+
+        match result {
+            Ok(score) => Ok(score.clone()),
+            Err(e) => Err(share_error(e)),
+        }
+    }
+
+    /// Candidate CVEs for the software a CPE name describes
+    ///
+    /// Traditional name: `cpe_match`
+    ///
+    /// Lets detections be driven by the product and version a scanner saw.
+    /// The IDs are normalized, sorted and free of repeats; IDs the source
+    /// returns that are not CVE IDs are dropped with a warning. A malformed
+    /// CPE name is a validation error, an unknown one yields no CVEs.
+    pub async fn match_by_cpe(&self, cpe: &str) -> CryptexResult<Vec<String>> {
+        let cpe = Cpe::parse(cpe)?;
+        let mut cve_ids: Vec<String> = self
+            .cpe_source
+            .cves_for(&cpe)
+            .await?
+            .iter()
+            .filter_map(|raw| match normalize_cve_id(raw) {
+                Ok(cve_id) => Some(cve_id),
+                Err(e) => {
+                    tracing::warn!("Ignoring CPE match for {}:{}: {}", cpe.vendor, cpe.product, e);
+                    None
+                }
+            })
+            .collect();
+        cve_ids.sort_unstable();
+        cve_ids.dedup();
+
+        tracing::debug!("{}:{} matches {} CVEs", cpe.vendor, cpe.product, cve_ids.len());
+        Ok(cve_ids)
+    }
+
+    /// Assess a vulnerability with explicit cache behaviour
+    ///
+    /// Returns `None` only for a `cached_only` miss.
+    pub async fn assess_with_options(
+        &self,
+        cve_id: &str,
+        options: &AssessOptions,
+    ) -> CryptexResult<Option<VulnerabilityScore>> {
+        options.validate()?;
+        let cve_id = &normalize_cve_id(cve_id)?;
+
+        if options.cached_only {
+            self.assess_cached_only(cve_id).await
+        } else if options.refresh {
+            tracing::debug!("Refreshing assessment for: {}", cve_id);
+            self.fetch_and_cache(cve_id).await.map(Some)
+        } else {
+            self.assess_vulnerability(cve_id).await.map(Some)
+        }
+    }
+
+    /// Fetch every source for `cve_id` and replace its cache entry
+    pub(crate) async fn fetch_and_cache(&self, cve_id: &str) -> CryptexResult<VulnerabilityScore> {
+        let permit = limit::acquire(&self.fetch_limit).await?;
+
         // Create new score
         let mut score = VulnerabilityScore::new(cve_id.to_string());
+        self.source_fetches.fetch_add(1, Ordering::Relaxed);
 
         // Fetch from NVD (stub - would make real API call)
         self.fetch_nvd_data(&mut score).await?;
 
-        // Fetch KEV data (stub)
+        // Fetch KEV data
         self.fetch_kev_data(&mut score).await?;
 
         // Fetch EPSS data (stub)
@@ -66,6 +247,7 @@ impl TheAssessor {
         if self.enable_ai_enhancement {
             self.ai_enhance_score(&mut score).await?;
         }
+        drop(permit);
 
         // Cache the score
         {
@@ -83,33 +265,115 @@ impl TheAssessor {
         Ok(score)
     }
 
+    /// Assess a vulnerability from cache only
+    ///
+    /// Traditional name: `dry_run_assess`
+    ///
+    /// Returns the cached score if present and `None` otherwise. External sources
+    /// are never consulted, so this is safe for offline bulk triage.
+    pub async fn assess_cached_only(&self, cve_id: &str) -> CryptexResult<Option<VulnerabilityScore>> {
+        let cve_id = normalize_cve_id(cve_id)?;
+        let cache = self.score_cache.read().await;
+        Ok(cache.get(&cve_id).cloned())
+    }
+
+    /// Drop `cve_id` from the in-memory cache, returning whether it was cached
+    ///
+    /// The next assessment of it fetches fresh data.
+    pub async fn evict_cached(&self, cve_id: &str) -> bool {
+        let Ok(cve_id) = normalize_cve_id(cve_id) else {
+            return false;
+        };
+        self.score_cache.write().await.remove(&cve_id).is_some()
+    }
+
+    /// Drop every cached score, returning how many there were
+    pub async fn clear_cached(&self) -> usize {
+        let mut cache = self.score_cache.write().await;
+        let count = cache.len();
+        cache.clear();
+        count
+    }
+
+    /// Number of assessments that consulted external sources
+    pub fn source_fetch_count(&self) -> u64 {
+        self.source_fetches.load(Ordering::Relaxed)
+    }
+
     /// Assess multiple vulnerabilities
     ///
     /// Traditional name: `batch_assess`
+    ///
+    /// Each distinct CVE is assessed once; repeated IDs in the input share that
+    /// result, so the output still has one entry per input ID (in input order).
+    /// IDs that fail to assess are logged and omitted.
     pub async fn assess_multiple(&self, cve_ids: &[String]) -> CryptexResult<Vec<VulnerabilityScore>> {
-        let mut scores = Vec::new();
+        let mut assessed: HashMap<&str, Option<VulnerabilityScore>> = HashMap::new();
 
         for cve_id in cve_ids {
-            match self.assess_vulnerability(cve_id).await {
-                Ok(score) => scores.push(score),
+            if assessed.contains_key(cve_id.as_str()) {
+                continue;
+            }
+
+            let score = match self.assess_vulnerability(cve_id).await {
+                Ok(score) => Some(score),
                 Err(e) => {
                     tracing::warn!("Failed to assess {}: {}", cve_id, e);
+                    None
                 }
+            };
+            assessed.insert(cve_id, score);
+        }
+
+        Ok(cve_ids
+            .iter()
+            .filter_map(|cve_id| assessed.get(cve_id.as_str()).cloned().flatten())
+            .collect())
+    }
+
+    /// Assess a batch of vulnerabilities with explicit cache behaviour
+    ///
+    /// Unlike `assess_multiple`, the output keeps one slot per input ID:
+    /// `None` marks a `cached_only` miss or a CVE that failed to assess.
+    /// Repeated IDs are assessed once.
+    pub async fn assess_batch(
+        &self,
+        cve_ids: &[String],
+        options: &AssessOptions,
+    ) -> CryptexResult<Vec<Option<VulnerabilityScore>>> {
+        options.validate()?;
+
+        let mut assessed: HashMap<&str, Option<VulnerabilityScore>> = HashMap::new();
+        for cve_id in cve_ids {
+            if assessed.contains_key(cve_id.as_str()) {
+                continue;
             }
+
+            let score = match self.assess_with_options(cve_id, options).await {
+                Ok(score) => score,
+                Err(e) => {
+                    tracing::warn!("Failed to assess {}: {}", cve_id, e);
+                    None
+                }
+            };
+            assessed.insert(cve_id, score);
         }
 
-        Ok(scores)
+        Ok(cve_ids
+            .iter()
+            .map(|cve_id| assessed.get(cve_id.as_str()).cloned().flatten())
+            .collect())
     }
 
     /// Parse and score a CVSS vector string
     ///
     /// Traditional name: `score_from_vector`
     pub async fn score_from_cvss_vector(&self, cve_id: &str, vector: &str) -> CryptexResult<VulnerabilityScore> {
-        let cvss = cvss_v3_from_vector(vector)
-            .map_err(|e| the_foundation::CryptexError::validation(e))?;
+        let cvss = cvss_v3_from_vector(vector).map_err(CryptexError::validation)?;
 
         let mut score = VulnerabilityScore::new(cve_id.to_string());
         score.cvss_v3 = Some(cvss);
+        score.data_completeness = DataCompleteness::Complete;
 
         Ok(score)
     }
@@ -121,7 +385,7 @@ impl TheAssessor {
     ) -> Vec<VulnerabilityScore> {
         scores
             .iter()
-            .filter(|s| s.severity() >= min_severity)
+            .filter(|s| s.severity().meets_minimum(min_severity))
             .cloned()
             .collect()
     }
@@ -157,11 +421,14 @@ impl TheAssessor {
     // Private methods
 
     /// Fetch NVD data (stub - would make real API call)
+    ///
+    /// NVD answers a reserved, unpublished ID with no record at all, so a
+    /// missing record is classified as `Reserved`; otherwise `vulnStatus`
+    /// and the presence of a CVSS vector decide `data_completeness`.
     async fn fetch_nvd_data(&self, score: &mut VulnerabilityScore) -> CryptexResult<()> {
         // In a real implementation, this would call the NVD API
         // For now, provide example data for testing
-
-        if score.cve_id.starts_with("CVE-2021-44228") {
+        let vuln_status = if score.cve_id.starts_with("CVE-2021-44228") {
             // Log4Shell example
             score.vulnerability_name = Some("Apache Log4j2 Remote Code Execution".to_string());
             score.description = Some(
@@ -172,25 +439,20 @@ impl TheAssessor {
                 "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H",
             ).unwrap());
             score.cwe_ids = vec!["CWE-502".to_string()];
-        }
+            "Analyzed"
+        } else {
+            "Reserved"
+        };
+
+        score.data_completeness =
+            DataCompleteness::from_nvd_status(vuln_status, score.cvss_v3.is_some());
 
         Ok(())
     }
 
-    /// Fetch KEV data (stub)
+    /// Fetch KEV data from the configured source
     async fn fetch_kev_data(&self, score: &mut VulnerabilityScore) -> CryptexResult<()> {
-        // In real implementation, fetch from CISA KEV catalog
-        // For now, mark Log4Shell as KEV
-        if score.cve_id.starts_with("CVE-2021-44228") {
-            score.kev = Some(KevInfo {
-                is_kev: true,
-                date_added: Some("2021-12-10".to_string()),
-                due_date: Some("2021-12-24".to_string()),
-                required_action: Some("Apply updates per vendor instructions".to_string()),
-                known_ransomware_use: true,
-            });
-        }
-
+        score.kev = self.kev_source.lookup(&score.cve_id).await?;
         Ok(())
     }
 
@@ -218,20 +480,29 @@ impl TheAssessor {
         let is_kev = score.is_kev();
         let epss_score = score.epss.as_ref().map(|e| e.score).unwrap_or(0.0);
 
-        // Calculate AI risk score (0-10 scale)
+        // Calculate AI risk score (0-10 scale), noting each adjustment
         let mut ai_risk = base_score;
+        let mut reasons = vec![format!("CVSS base {:.1}", base_score)];
 
         // Boost for KEV
         if is_kev {
-            ai_risk = (ai_risk * 1.3).min(10.0);
+            ai_risk *= 1.3;
+            reasons.push("boosted 1.3x due to KEV".to_string());
         }
 
         // Boost for high EPSS
         if epss_score > 0.5 {
-            ai_risk = (ai_risk + (epss_score * 2.0)).min(10.0);
+            ai_risk += epss_score * 2.0;
+            reasons.push(format!("EPSS {:.2} added {:.1}", epss_score, epss_score * 2.0));
+        }
+
+        if ai_risk > 10.0 {
+            ai_risk = 10.0;
+            reasons.push("capped at 10.0".to_string());
         }
 
         score.ai_risk_score = Some(ai_risk);
+        score.ai_explanation = Some(format!("AI risk {:.1}: {}", ai_risk, reasons.join("; ")));
 
         // Determine priority
         score.ai_priority = Some(match ai_risk {
@@ -256,6 +527,21 @@ impl TheAssessor {
     }
 }
 
+/// Copy a shared fetch's error for one of its callers
+///
+/// `CryptexError` is not `Clone`; the variants callers match on keep their
+/// kind, anything else is passed on as text.
+fn share_error(e: &CryptexError) -> CryptexError {
+    match e {
+        CryptexError::RateLimited(msg) => CryptexError::RateLimited(msg.clone()),
+        CryptexError::UpstreamError(msg) => CryptexError::UpstreamError(msg.clone()),
+        CryptexError::NotFound(msg) => CryptexError::NotFound(msg.clone()),
+        CryptexError::ValidationError(msg) => CryptexError::ValidationError(msg.clone()),
+        CryptexError::Timeout => CryptexError::Timeout,
+        other => CryptexError::Unknown(other.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -266,6 +552,43 @@ mod tests {
         assert!(assessor.enable_ai_enhancement);
     }
 
+    #[tokio::test]
+    async fn test_match_by_cpe() {
+        let assessor = TheAssessor::the_awakening().await.unwrap();
+
+        let httpd = assessor
+            .match_by_cpe("cpe:2.3:a:apache:http_server:2.4.49:*:*:*:*:*:*:*")
+            .await
+            .unwrap();
+        assert_eq!(httpd, ["CVE-2021-41773", "CVE-2021-42013"]);
+
+        // Any version of a product gathers every version's CVEs
+        let log4j = assessor.match_by_cpe("cpe:/a:apache:log4j").await.unwrap();
+        assert_eq!(log4j, ["CVE-2021-44228", "CVE-2021-45046"]);
+
+        let unknown = assessor
+            .match_by_cpe("cpe:2.3:a:example:nothing_known:1.0:*:*:*:*:*:*:*")
+            .await
+            .unwrap();
+        assert!(unknown.is_empty());
+        assert!(assessor.match_by_cpe("apache httpd 2.4.49").await.is_err());
+
+        /// A source answering in whatever shape it likes
+        struct Untidy;
+
+        #[async_trait::async_trait]
+        impl CpeSource for Untidy {
+            async fn cves_for(&self, _cpe: &Cpe) -> CryptexResult<Vec<String>> {
+                Ok(["cve-2024-0002", "CVE-2024-0001", "not a cve", "CVE-2024-0002"]
+                    .map(String::from)
+                    .to_vec())
+            }
+        }
+        let assessor = assessor.with_cpe_source(Arc::new(Untidy));
+        let cve_ids = assessor.match_by_cpe("cpe:/a:acme:widget:1.0").await.unwrap();
+        assert_eq!(cve_ids, ["CVE-2024-0001", "CVE-2024-0002"]);
+    }
+
     #[tokio::test]
     async fn test_assess_vulnerability() {
         let assessor = TheAssessor::the_awakening().await.unwrap();
@@ -276,7 +599,118 @@ mod tests {
 
         assert_eq!(score.cve_id, "CVE-2021-44228");
         assert!(score.cvss_base_score() > 0.0);
+        assert_eq!(score.data_completeness, DataCompleteness::Complete);
         assert!(score.is_kev());
+
+        let explanation = score.ai_explanation.unwrap();
+        assert!(explanation.contains("KEV"), "{}", explanation);
+    }
+
+    /// KEV source that takes a while to answer and counts its lookups
+    struct SlowKevSource {
+        lookups: AtomicU64,
+    }
+
+    #[async_trait::async_trait]
+    impl KevSource for SlowKevSource {
+        async fn lookup(&self, _cve_id: &str) -> CryptexResult<Option<KevInfo>> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_assessments_share_one_fetch() {
+        let source = Arc::new(SlowKevSource {
+            lookups: AtomicU64::new(0),
+        });
+        let assessor = Arc::new(
+            TheAssessor::the_awakening()
+                .await
+                .unwrap()
+                .with_kev_source(source.clone()),
+        );
+
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let assessor = Arc::clone(&assessor);
+                tokio::spawn(async move { assessor.assess_vulnerability("CVE-2024-0001").await })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap().unwrap().cve_id, "CVE-2024-0001");
+        }
+
+        assert_eq!(source.lookups.load(Ordering::SeqCst), 1);
+        assert_eq!(assessor.source_fetch_count(), 1);
+        assert!(assessor.in_flight.lock().unwrap().is_empty());
+
+        // Different CVEs are fetched separately
+        let (a, b) = tokio::join!(
+            assessor.assess_vulnerability("CVE-2024-0002"),
+            assessor.assess_vulnerability("CVE-2024-0003"),
+        );
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(source.lookups.load(Ordering::SeqCst), 3);
+    }
+
+    /// KEV source that tracks how many lookups are running at once
+    #[derive(Default)]
+    struct GaugedKevSource {
+        in_flight: AtomicU64,
+        peak: AtomicU64,
+    }
+
+    #[async_trait::async_trait]
+    impl KevSource for GaugedKevSource {
+        async fn lookup(&self, _cve_id: &str) -> CryptexResult<Option<KevInfo>> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_limit_bounds_concurrent_assessments() {
+        let source = Arc::new(GaugedKevSource::default());
+        let limit = Arc::new(Semaphore::new(3));
+        // Two assessors sharing one limit, as the scanner and the API do
+        let mut assessors = Vec::new();
+        for _ in 0..2 {
+            let assessor = TheAssessor::the_awakening()
+                .await
+                .unwrap()
+                .with_kev_source(source.clone())
+                .with_fetch_limit(limit.clone());
+            assessors.push(Arc::new(assessor));
+        }
+
+        let tasks: Vec<_> = (0..20)
+            .map(|i| {
+                let assessor = Arc::clone(&assessors[i % 2]);
+                tokio::spawn(async move {
+                    assessor.assess_vulnerability(&format!("CVE-2024-{:04}", i)).await
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert!(task.await.unwrap().is_ok());
+        }
+
+        assert_eq!(source.peak.load(Ordering::SeqCst), 3);
+        assert_eq!(source.in_flight.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unpublished_cve_is_reserved_not_benign() {
+        let assessor = TheAssessor::the_awakening().await.unwrap();
+        let score = assessor.assess_vulnerability("CVE-2099-0001").await.unwrap();
+
+        assert_eq!(score.cvss_base_score(), 0.0);
+        assert_eq!(score.data_completeness, DataCompleteness::Reserved);
     }
 
     #[tokio::test]
@@ -360,4 +794,124 @@ mod tests {
         assert_eq!(sorted[0].cve_id, "CVE-HIGH");
         assert_eq!(sorted[1].cve_id, "CVE-LOW");
     }
+
+    #[tokio::test]
+    async fn test_assess_cached_only() {
+        let assessor = TheAssessor::the_awakening().await.unwrap();
+
+        // Miss: no score and no source fetch
+        let missing = assessor.assess_cached_only("CVE-2021-44228").await.unwrap();
+        assert!(missing.is_none());
+        assert_eq!(assessor.source_fetch_count(), 0);
+
+        assessor.assess_vulnerability("CVE-2021-44228").await.unwrap();
+        assert_eq!(assessor.source_fetch_count(), 1);
+
+        // Hit: served from cache without another fetch
+        let cached = assessor.assess_cached_only("CVE-2021-44228").await.unwrap();
+        assert_eq!(cached.unwrap().cve_id, "CVE-2021-44228");
+        assert_eq!(assessor.source_fetch_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_assessment_normalizes_cve_id() {
+        let assessor = TheAssessor::the_awakening().await.unwrap();
+
+        let score = assessor.assess_vulnerability(" cve-2021-44228 ").await.unwrap();
+        assert_eq!(score.cve_id, "CVE-2021-44228");
+        assessor.assess_vulnerability("CVE 2021 44228").await.unwrap();
+        assert_eq!(assessor.source_fetch_count(), 1);
+        assert!(assessor.assess_cached_only("cve_2021_44228").await.unwrap().is_some());
+
+        let invalid = assessor.assess_vulnerability("log4shell").await;
+        assert!(matches!(invalid, Err(CryptexError::ValidationError(_))));
+        assert_eq!(assessor.source_fetch_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_assess_multiple_dedupes_ids() {
+        let assessor = TheAssessor::the_awakening().await.unwrap();
+        let cve_ids = vec![
+            "CVE-2021-44228".to_string(),
+            "CVE-2024-0001".to_string(),
+            "CVE-2021-44228".to_string(),
+        ];
+
+        let scores = assessor.assess_multiple(&cve_ids).await.unwrap();
+
+        assert_eq!(assessor.source_fetch_count(), 2);
+        assert_eq!(scores.len(), 3);
+        assert_eq!(scores[0].cve_id, "CVE-2021-44228");
+        assert_eq!(scores[1].cve_id, "CVE-2024-0001");
+        assert_eq!(scores[2].cve_id, "CVE-2021-44228");
+    }
+
+    #[tokio::test]
+    async fn test_assess_with_options() {
+        let assessor = TheAssessor::the_awakening().await.unwrap();
+        let cached_only = AssessOptions {
+            cached_only: true,
+            ..Default::default()
+        };
+        let refresh = AssessOptions {
+            refresh: true,
+            ..Default::default()
+        };
+
+        // cached_only misses are None, not errors
+        let missing = assessor
+            .assess_with_options("CVE-2021-44228", &cached_only)
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+
+        let default = AssessOptions::default();
+        assessor.assess_with_options("CVE-2021-44228", &default).await.unwrap();
+        assessor.assess_with_options("CVE-2021-44228", &default).await.unwrap();
+        assert_eq!(assessor.source_fetch_count(), 1);
+
+        // refresh always fetches and overwrites the cached entry
+        {
+            let mut cache = assessor.score_cache.write().await;
+            cache.get_mut("CVE-2021-44228").unwrap().ai_priority = Some("stale".to_string());
+        }
+        let refreshed = assessor
+            .assess_with_options("CVE-2021-44228", &refresh)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(assessor.source_fetch_count(), 2);
+        assert_ne!(refreshed.ai_priority.as_deref(), Some("stale"));
+        let cached = assessor.assess_cached_only("CVE-2021-44228").await.unwrap().unwrap();
+        assert_eq!(cached.ai_priority, refreshed.ai_priority);
+
+        let both = AssessOptions {
+            refresh: true,
+            cached_only: true,
+        };
+        assert!(assessor.assess_with_options("CVE-2021-44228", &both).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_assess_batch_keeps_misses() {
+        let assessor = TheAssessor::the_awakening().await.unwrap();
+        assessor.assess_vulnerability("CVE-2021-44228").await.unwrap();
+
+        let cve_ids = vec![
+            "CVE-2021-44228".to_string(),
+            "CVE-2024-0001".to_string(),
+            "CVE-2021-44228".to_string(),
+        ];
+        let options = AssessOptions {
+            cached_only: true,
+            ..Default::default()
+        };
+
+        let scores = assessor.assess_batch(&cve_ids, &options).await.unwrap();
+        assert_eq!(scores.len(), 3);
+        assert!(scores[0].is_some());
+        assert!(scores[1].is_none());
+        assert!(scores[2].is_some());
+        assert_eq!(assessor.source_fetch_count(), 1);
+    }
 }
@@ -1,8 +1,14 @@
 //! Core types for vulnerability assessment
 
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use the_foundation::CryptexError;
 
 /// CVSS Severity Levels
+///
+/// Variants are declared from least to most severe, and the derived `Ord`
+/// follows that order: `None < Low < Medium < High < Critical`. Sorting and
+/// minimum-severity filters depend on it, so new variants must keep it.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CvssSeverity {
     None,      // 0.0
@@ -13,10 +19,19 @@ pub enum CvssSeverity {
 }
 
 impl CvssSeverity {
+    /// Every severity, least severe first
+    pub const ALL: [CvssSeverity; 5] = [
+        CvssSeverity::None,
+        CvssSeverity::Low,
+        CvssSeverity::Medium,
+        CvssSeverity::High,
+        CvssSeverity::Critical,
+    ];
+
     /// Get severity from CVSS base score
     pub fn from_score(score: f64) -> Self {
         match score {
-            s if s == 0.0 => CvssSeverity::None,
+            0.0 => CvssSeverity::None,
             s if s < 4.0 => CvssSeverity::Low,
             s if s < 7.0 => CvssSeverity::Medium,
             s if s < 9.0 => CvssSeverity::High,
@@ -33,6 +48,28 @@ impl CvssSeverity {
             CvssSeverity::Critical => "Critical",
         }
     }
+
+    /// Whether this severity is at least `minimum`
+    pub fn meets_minimum(self, minimum: CvssSeverity) -> bool {
+        self >= minimum
+    }
+}
+
+impl FromStr for CvssSeverity {
+    type Err = CryptexError;
+
+    /// Parse a severity name, case-insensitive (e.g. "critical")
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        CvssSeverity::ALL
+            .into_iter()
+            .find(|severity| severity.as_str().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                CryptexError::validation(format!(
+                    "Unknown severity '{}'; expected none, low, medium, high or critical",
+                    name
+                ))
+            })
+    }
 }
 
 /// CVSS v3.x Base Metrics
@@ -59,6 +96,17 @@ pub struct CvssV3 {
     pub vector_string: String,
 }
 
+impl CvssV3 {
+    /// CVSS version declared by the vector prefix, e.g. "3.1"
+    pub fn version(&self) -> &str {
+        self.vector_string
+            .split('/')
+            .next()
+            .and_then(|prefix| prefix.strip_prefix("CVSS:"))
+            .unwrap_or("3")
+    }
+}
+
 /// KEV (Known Exploited Vulnerabilities) Information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KevInfo {
@@ -106,8 +154,55 @@ pub struct SsvcInfo {
     pub technical_impact: String,  // partial/total
 }
 
+/// How much NVD actually knows about a CVE
+///
+/// A CVE without CVSS data still reports a base score of 0.0; this says
+/// whether that zero means "scored as harmless" or "not scored at all".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataCompleteness {
+    /// NVD published the CVE with a CVSS vector
+    Complete,
+    /// Published but not (yet) scored, e.g. awaiting analysis or deferred
+    #[default]
+    NoCvss,
+    /// NVD has no record: the ID is reserved but not yet published
+    Reserved,
+    /// The CVE was rejected or withdrawn
+    Rejected,
+}
+
+impl DataCompleteness {
+    /// Classify an NVD record from its `vulnStatus` and whether it had a CVSS vector
+    pub fn from_nvd_status(vuln_status: &str, has_cvss: bool) -> Self {
+        if vuln_status.eq_ignore_ascii_case("rejected") {
+            DataCompleteness::Rejected
+        } else if vuln_status.eq_ignore_ascii_case("reserved") {
+            DataCompleteness::Reserved
+        } else if has_cvss {
+            DataCompleteness::Complete
+        } else {
+            DataCompleteness::NoCvss
+        }
+    }
+
+    /// Short label for reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataCompleteness::Complete => "complete",
+            DataCompleteness::NoCvss => "no CVSS data",
+            DataCompleteness::Reserved => "reserved",
+            DataCompleteness::Rejected => "rejected",
+        }
+    }
+}
+
 /// Comprehensive Vulnerability Score
+///
+/// The derives are generated as inherent functions (`remote = "Self"`) so
+/// the trait impls below can correct `data_completeness` of legacy rows.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct VulnerabilityScore {
     pub cve_id: String,
     pub vulnerability_name: Option<String>,
@@ -126,11 +221,36 @@ pub struct VulnerabilityScore {
     pub references: Vec<String>,
     pub published_date: Option<String>,
     pub last_modified: Option<String>,
+    /// Whether the CVSS fields are real; assessments archived before this
+    /// field existed read back as `complete` when they carry a CVSS vector
+    /// and `no_cvss` otherwise
+    #[serde(default)]
+    pub data_completeness: DataCompleteness,
 
     // AI-enhanced fields
     pub ai_risk_score: Option<f64>,
     pub ai_priority: Option<String>,
     pub ai_remediation_urgency: Option<String>,
+    /// Why the AI risk score differs from the CVSS base score
+    #[serde(default)]
+    pub ai_explanation: Option<String>,
+}
+
+impl Serialize for VulnerabilityScore {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VulnerabilityScore::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for VulnerabilityScore {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut score = VulnerabilityScore::deserialize(deserializer)?;
+        // Only a row without the field can pair `no_cvss` with a CVSS vector
+        if score.data_completeness == DataCompleteness::NoCvss && score.cvss_v3.is_some() {
+            score.data_completeness = DataCompleteness::Complete;
+        }
+        Ok(score)
+    }
 }
 
 impl VulnerabilityScore {
@@ -148,13 +268,18 @@ impl VulnerabilityScore {
             references: Vec::new(),
             published_date: None,
             last_modified: None,
+            data_completeness: DataCompleteness::default(),
             ai_risk_score: None,
             ai_priority: None,
             ai_remediation_urgency: None,
+            ai_explanation: None,
         }
     }
 
     /// Get the primary CVSS base score
+    ///
+    /// 0.0 when there is no CVSS data; check `data_completeness` to tell
+    /// an unscored CVE from a harmless one.
     pub fn cvss_base_score(&self) -> f64 {
         self.cvss_v3.as_ref().map(|c| c.base_score).unwrap_or(0.0)
     }
@@ -192,7 +317,7 @@ impl VulnerabilityScore {
         }
 
         // Clamp to 0-1
-        score.min(1.0).max(0.0)
+        score.clamp(0.0, 1.0)
     }
 }
 
@@ -209,6 +334,33 @@ mod tests {
         assert_eq!(CvssSeverity::from_score(9.8), CvssSeverity::Critical);
     }
 
+    #[test]
+    fn test_severity_ordering() {
+        use CvssSeverity::*;
+        assert!(None < Low && Low < Medium && Medium < High && High < Critical);
+
+        let mut shuffled = vec![High, None, Critical, Low, Medium];
+        shuffled.sort();
+        assert_eq!(shuffled, CvssSeverity::ALL);
+
+        assert!(Critical.meets_minimum(High));
+        assert!(High.meets_minimum(High));
+        assert!(!Medium.meets_minimum(High));
+        assert!(None.meets_minimum(None));
+    }
+
+    #[test]
+    fn test_severity_name_round_trip() {
+        for severity in CvssSeverity::ALL {
+            let name = severity.as_str();
+            assert_eq!(name.parse::<CvssSeverity>().unwrap(), severity);
+            assert_eq!(name.to_lowercase().parse::<CvssSeverity>().unwrap(), severity);
+            assert_eq!(name.to_uppercase().parse::<CvssSeverity>().unwrap(), severity);
+        }
+        assert_eq!(" critical ".parse::<CvssSeverity>().unwrap(), CvssSeverity::Critical);
+        assert!("severe".parse::<CvssSeverity>().is_err());
+    }
+
     #[test]
     fn test_vulnerability_score_creation() {
         let score = VulnerabilityScore::new("CVE-2024-0001".to_string());
@@ -217,6 +369,48 @@ mod tests {
         assert_eq!(score.severity(), CvssSeverity::None);
     }
 
+    #[test]
+    fn test_data_completeness_from_nvd_status() {
+        assert_eq!(
+            DataCompleteness::from_nvd_status("Analyzed", true),
+            DataCompleteness::Complete
+        );
+        assert_eq!(
+            DataCompleteness::from_nvd_status("Awaiting Analysis", false),
+            DataCompleteness::NoCvss
+        );
+        assert_eq!(
+            DataCompleteness::from_nvd_status("Reserved", false),
+            DataCompleteness::Reserved
+        );
+        assert_eq!(
+            DataCompleteness::from_nvd_status("Rejected", true),
+            DataCompleteness::Rejected
+        );
+    }
+
+    #[test]
+    fn test_legacy_score_completeness_follows_cvss() {
+        let mut score = VulnerabilityScore::new("CVE-2021-44228".to_string());
+        let vector = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H";
+        score.cvss_v3 = Some(crate::cvss::cvss_v3_from_vector(vector).unwrap());
+        let mut legacy = serde_json::to_value(&score).unwrap();
+        legacy.as_object_mut().unwrap().remove("data_completeness");
+
+        let read: VulnerabilityScore = serde_json::from_value(legacy.clone()).unwrap();
+        assert_eq!(read.data_completeness, DataCompleteness::Complete);
+
+        legacy["cvss_v3"] = serde_json::Value::Null;
+        let read: VulnerabilityScore = serde_json::from_value(legacy).unwrap();
+        assert_eq!(read.data_completeness, DataCompleteness::NoCvss);
+
+        // A recorded status is kept
+        score.data_completeness = DataCompleteness::Rejected;
+        let json = serde_json::to_string(&score).unwrap();
+        let read: VulnerabilityScore = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.data_completeness, DataCompleteness::Rejected);
+    }
+
     #[test]
     fn test_composite_risk_score() {
         let mut score = VulnerabilityScore::new("CVE-2024-0001".to_string());
@@ -0,0 +1,195 @@
+//! Start-up warm-up
+//!
+//! The first assessment after boot would otherwise pay for downloading the
+//! KEV catalog. Warming up loads it ahead of time, along with scores for a
+//! watchlist of CVEs the deployment expects to be asked about. It is opt-in
+//! and runs in the background, so a server is ready before it finishes.
+
+use std::sync::Arc;
+use the_foundation::CryptexResult;
+
+use crate::cve::normalize_cve_id;
+use crate::scoring::TheAssessor;
+
+/// Environment variable that turns the start-up warm-up on
+///
+/// Mirrors `assessor.warm_up` in the charter.
+pub const WARMUP_ENV: &str = "CRYPTEX_ASSESSOR__WARM_UP";
+
+/// Environment variable listing CVEs to assess during warm-up
+///
+/// Mirrors `assessor.watchlist` in the charter, as a comma-separated list.
+pub const WATCHLIST_ENV: &str = "CRYPTEX_ASSESSOR__WATCHLIST";
+
+impl TheAssessor {
+    /// Load catalogs and watchlist scores ahead of the first request
+    ///
+    /// Traditional name: `prefetch`
+    ///
+    /// A catalog that fails to load is an error, since lookups would hit the
+    /// same failure. A watchlist CVE that fails is logged and skipped.
+    /// Returns the number of watchlist CVEs now cached. EPSS scores are still
+    /// looked up per CVE, so there is no EPSS catalog to load yet.
+    pub async fn warm_up(&self, watchlist: &[String]) -> CryptexResult<usize> {
+        {
+            let _permit = crate::limit::acquire(&self.fetch_limit).await?;
+            self.kev_source.warm_up().await?;
+        }
+
+        let scores = self.assess_multiple(watchlist).await?;
+        tracing::info!("Assessor warmed up: {} watchlist CVEs cached", scores.len());
+        Ok(scores.len())
+    }
+}
+
+/// Watchlist to warm up with, if warm-up is enabled
+///
+/// `enabled` and `watchlist` are the charter's `assessor.warm_up` and
+/// `assessor.watchlist`; `CRYPTEX_ASSESSOR__WARM_UP` and
+/// `CRYPTEX_ASSESSOR__WATCHLIST` override them when set. The watchlist may
+/// be empty.
+pub fn warm_up_watchlist(enabled: bool, watchlist: &[String]) -> Option<Vec<String>> {
+    if !warm_up_enabled_from_env().unwrap_or(enabled) {
+        return None;
+    }
+    Some(watchlist_from_env().unwrap_or_else(|| watchlist.to_vec()))
+}
+
+/// `CRYPTEX_ASSESSOR__WARM_UP`, if set to `true`/`1` or `false`/`0`
+fn warm_up_enabled_from_env() -> Option<bool> {
+    let value = std::env::var(WARMUP_ENV).ok()?;
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" => Some(true),
+        "false" | "0" | "" => Some(false),
+        _ => {
+            tracing::warn!("Ignoring {}={}: expected true or false", WARMUP_ENV, value);
+            None
+        }
+    }
+}
+
+/// `CRYPTEX_ASSESSOR__WATCHLIST`, if set
+///
+/// Entries that aren't CVE IDs are logged and left out, the way the charter
+/// would refuse them.
+fn watchlist_from_env() -> Option<Vec<String>> {
+    let value = std::env::var(WATCHLIST_ENV).ok()?;
+    Some(
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|cve_id| !cve_id.is_empty())
+            .filter_map(|cve_id| match normalize_cve_id(cve_id) {
+                Ok(cve_id) => Some(cve_id),
+                Err(e) => {
+                    tracing::warn!("Ignoring {} entry: {}", WATCHLIST_ENV, e);
+                    None
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Warm `assessor` up in the background
+pub fn spawn_warm_up(
+    assessor: Arc<TheAssessor>,
+    watchlist: Vec<String>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(e) = assessor.warm_up(&watchlist).await {
+            tracing::warn!("Assessor warm-up failed; catalogs load on first use: {}", e);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kev::{CachedKevCatalog, KevFeed};
+    use crate::types::KevInfo;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use the_foundation::CryptexError;
+
+    /// KEV feed listing one CVE and counting its downloads
+    #[derive(Default)]
+    struct CountingFeed {
+        fetches: AtomicU64,
+    }
+
+    #[async_trait::async_trait]
+    impl KevFeed for CountingFeed {
+        async fn fetch_catalog(&self) -> CryptexResult<HashMap<String, KevInfo>> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let entry = KevInfo {
+                is_kev: true,
+                date_added: Some("2023-10-10".to_string()),
+                due_date: None,
+                required_action: None,
+                known_ransomware_use: false,
+            };
+            Ok(HashMap::from([("CVE-2023-4966".to_string(), entry)]))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_loads_catalog_before_first_lookup() {
+        let feed = Arc::new(CountingFeed::default());
+        let assessor = TheAssessor::the_awakening()
+            .await
+            .unwrap()
+            .with_kev_source(Arc::new(CachedKevCatalog::new(feed.clone())));
+
+        let cached = assessor.warm_up(&["CVE-2021-44228".to_string()]).await.unwrap();
+        assert_eq!(cached, 1);
+        assert_eq!(feed.fetches.load(Ordering::SeqCst), 1);
+        assert!(assessor.assess_cached_only("CVE-2021-44228").await.unwrap().is_some());
+
+        // A lookup after warm-up is served from the downloaded catalog
+        let score = assessor.assess_vulnerability("CVE-2023-4966").await.unwrap();
+        assert!(score.is_kev());
+        assert_eq!(feed.fetches.load(Ordering::SeqCst), 1);
+    }
+
+    struct FailingFeed;
+
+    #[async_trait::async_trait]
+    impl KevFeed for FailingFeed {
+        async fn fetch_catalog(&self) -> CryptexResult<HashMap<String, KevInfo>> {
+            Err(CryptexError::upstream("CISA returned 503"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_reports_catalog_failure() {
+        let assessor = TheAssessor::the_awakening()
+            .await
+            .unwrap()
+            .with_kev_source(Arc::new(CachedKevCatalog::new(Arc::new(FailingFeed))));
+
+        assert!(assessor.warm_up(&[]).await.is_err());
+    }
+
+    #[test]
+    fn test_watchlist_env_overrides_charter_and_drops_bad_ids() {
+        let charter = vec!["CVE-2021-44228".to_string()];
+        std::env::remove_var(WARMUP_ENV);
+        std::env::remove_var(WATCHLIST_ENV);
+        assert_eq!(warm_up_watchlist(false, &charter), None);
+        assert_eq!(warm_up_watchlist(true, &charter), Some(charter.clone()));
+
+        std::env::set_var(WATCHLIST_ENV, "cve-2023-4966, log4shell,,CVE-2024-3094");
+        assert_eq!(
+            warm_up_watchlist(true, &charter),
+            Some(vec!["CVE-2023-4966".to_string(), "CVE-2024-3094".to_string()])
+        );
+
+        std::env::set_var(WARMUP_ENV, "false");
+        assert_eq!(warm_up_watchlist(true, &charter), None);
+        std::env::set_var(WARMUP_ENV, "1");
+        assert!(warm_up_watchlist(false, &charter).is_some());
+
+        std::env::remove_var(WARMUP_ENV);
+        std::env::remove_var(WATCHLIST_ENV);
+    }
+}
@@ -6,15 +6,27 @@
 use crate::{
     memory::TheMemory,
     observer::TheObserver,
+    prompts::PromptLibrary,
 };
 use the_foundation::{
     AgitatorType, CryptexError, CryptexResult, TheAgitator, TheInquiry, ThePackage, TheReply,
 };
 use std::sync::Arc;
-use std::time::Instant;
-use the_agitator::{ClaudeAgitator, OpenAIAgitator};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use the_agitator::{ClaudeAgitator, GeminiAgitator, OpenAIAgitator};
 use the_coordinator::TheCharter;
 
+/// How long `validate_agitators` reuses its last answer before asking the
+/// providers again
+pub const VALIDATION_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Longest one provider may take to answer `validate_agitators`
+pub const VALIDATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Per-provider validation outcomes
+type ValidationOutcomes = Vec<(AgitatorType, CryptexResult<bool>)>;
+
 /// The Collective - Main AI service coordination
 ///
 /// Traditional name: `AIService` or `ServiceCore`
@@ -26,6 +38,9 @@ pub struct TheCollective {
     memory: Arc<TheMemory>,
     observer: Arc<TheObserver>,
     agitators: Vec<Arc<dyn TheAgitator>>,
+    prompts: Arc<PromptLibrary>,
+    validation_cache: Arc<Mutex<Option<(Instant, ValidationOutcomes)>>>,
+    validation_timeout: Duration,
 }
 
 impl TheCollective {
@@ -38,7 +53,7 @@ impl TheCollective {
         tracing::info!("The Collective is awakening...");
 
         // Validate charter
-        charter.validate().map_err(|e| CryptexError::charter(e))?;
+        charter.validate().map_err(CryptexError::charter)?;
 
         // Initialize The Memory (cache)
         let memory = Arc::new(TheMemory::the_awakening(
@@ -47,11 +62,22 @@ impl TheCollective {
         )?);
 
         // Initialize The Observer (metrics)
-        let observer = Arc::new(TheObserver::the_awakening());
+        let mut observer =
+            TheObserver::the_awakening().with_latency_alpha(charter.latency_ema_alpha);
+        if let Some(history) = &charter.metrics_history {
+            observer = observer.with_history_capacity(history.capacity);
+        }
+        let observer = Arc::new(observer);
+        if let Some(history) = &charter.metrics_history {
+            observer.spawn_sampler(Duration::from_secs(history.interval_seconds));
+        }
 
         // Initialize Agitators (AI Providers)
         let mut agitators: Vec<Arc<dyn TheAgitator>> = Vec::new();
 
+        // One client for all agitators so they share a connection pool
+        let client = the_agitator::build_http_client(&charter.connection.http_settings())?;
+
         for agitator_config in &charter.agitators {
             if !agitator_config.enabled {
                 tracing::debug!(
@@ -82,14 +108,14 @@ impl TheCollective {
                 match agitator_config.agitator_type.as_str() {
                     "openai" => "gpt-4".to_string(),
                     "claude" => "claude-3-sonnet-20240229".to_string(),
+                    "gemini" => "gemini-1.5-pro".to_string(),
                     _ => "unknown".to_string(),
                 }
             });
 
             match agitator_config.agitator_type.as_str() {
                 "openai" => {
-                    let agitator = OpenAIAgitator::the_awakening(api_key, model)
-                        .await
+                    let mut agitator = OpenAIAgitator::with_client(api_key, model, client.clone())
                         .map_err(|e| {
                             CryptexError::agitator(format!(
                                 "Failed to initialize OpenAI agitator '{}': {}",
@@ -98,6 +124,13 @@ impl TheCollective {
                         })?
                         .with_timeout(agitator_config.timeout_seconds);
 
+                    if let Some(temperature) = agitator_config.temperature {
+                        agitator = agitator.with_temperature(temperature);
+                    }
+                    if let Some(max_tokens) = agitator_config.max_tokens {
+                        agitator = agitator.with_max_tokens(max_tokens);
+                    }
+
                     agitators.push(Arc::new(agitator));
                     tracing::info!(
                         "OpenAI agitator '{}' awakened successfully",
@@ -105,8 +138,7 @@ impl TheCollective {
                     );
                 }
                 "claude" => {
-                    let agitator = ClaudeAgitator::the_awakening(api_key, model)
-                        .await
+                    let mut agitator = ClaudeAgitator::with_client(api_key, model, client.clone())
                         .map_err(|e| {
                             CryptexError::agitator(format!(
                                 "Failed to initialize Claude agitator '{}': {}",
@@ -115,12 +147,42 @@ impl TheCollective {
                         })?
                         .with_timeout(agitator_config.timeout_seconds);
 
+                    if let Some(temperature) = agitator_config.temperature {
+                        agitator = agitator.with_temperature(temperature);
+                    }
+                    if let Some(max_tokens) = agitator_config.max_tokens {
+                        agitator = agitator.with_max_tokens(max_tokens);
+                    }
+
                     agitators.push(Arc::new(agitator));
                     tracing::info!(
                         "Claude agitator '{}' awakened successfully",
                         agitator_config.name
                     );
                 }
+                "gemini" => {
+                    let mut agitator = GeminiAgitator::with_client(api_key, model, client.clone())
+                        .map_err(|e| {
+                            CryptexError::agitator(format!(
+                                "Failed to initialize Gemini agitator '{}': {}",
+                                agitator_config.name, e
+                            ))
+                        })?
+                        .with_timeout(agitator_config.timeout_seconds);
+
+                    if let Some(temperature) = agitator_config.temperature {
+                        agitator = agitator.with_temperature(temperature);
+                    }
+                    if let Some(max_tokens) = agitator_config.max_tokens {
+                        agitator = agitator.with_max_tokens(max_tokens);
+                    }
+
+                    agitators.push(Arc::new(agitator));
+                    tracing::info!(
+                        "Gemini agitator '{}' awakened successfully",
+                        agitator_config.name
+                    );
+                }
                 _ => {
                     tracing::warn!(
                         "Unknown agitator type '{}' for agitator '{}', skipping",
@@ -140,11 +202,16 @@ impl TheCollective {
             agitators.len()
         );
 
+        let prompts = Arc::new(PromptLibrary::the_awakening(charter.prompts.clone()));
+
         Ok(Self {
             charter: Arc::new(charter),
             memory,
             observer,
             agitators,
+            prompts,
+            validation_cache: Arc::new(Mutex::new(None)),
+            validation_timeout: VALIDATION_TIMEOUT,
         })
     }
 
@@ -203,19 +270,25 @@ impl TheCollective {
             agitator.the_mind()
         );
 
-        // Convert inquiry to package
+        // Convert inquiry to package; context set on the inquiry wins over
+        // the task's configured prompt
         let package = ThePackage {
             data: inquiry.package.data.clone(),
-            context: inquiry.package.context.clone(),
+            context: inquiry
+                .package
+                .context
+                .clone()
+                .or_else(|| Some(self.prompts.resolve(inquiry.task).to_string())),
         };
 
         // Send to agitator
         let agitator_name = format!("{:?}", agitator.agitator_type());
+        let agitation_start = Instant::now();
         let result = agitator.the_agitation(package).await;
 
         // Record agitator metrics
         self.observer
-            .record_agitator_request(&agitator_name, result.is_ok())
+            .record_agitator_request(&agitator_name, result.is_ok(), agitation_start.elapsed())
             .await;
 
         match result {
@@ -226,8 +299,8 @@ impl TheCollective {
                 let certainty = reply.certainty;
                 let duration = start_time.elapsed();
 
-                // Cache reply if certainty meets threshold
-                if certainty >= self.charter.cache_threshold {
+                // Cache reply if certainty meets the task's threshold
+                if certainty >= self.charter.cache_threshold_for(inquiry.task) {
                     if let Err(e) = self.memory.store(fingerprint, reply.clone()).await {
                         tracing::warn!("Failed to cache reply: {}", e);
                     } else {
@@ -283,6 +356,55 @@ impl TheCollective {
         Ok(())
     }
 
+    /// The Validation (Collective) - Health check every agitator
+    ///
+    /// Traditional name: `check_providers`
+    ///
+    /// Runs each agitator's `the_validation` concurrently and reports every
+    /// provider separately, so one broken provider does not hide the others.
+    /// A provider that does not answer within `VALIDATION_TIMEOUT` reports
+    /// `CryptexError::Timeout`. Answers are reused for `VALIDATION_CACHE_TTL`
+    /// so readiness probes do not hit the providers on every request; cached
+    /// errors keep their kind, so a rate-limited provider still reads as one.
+    pub async fn validate_agitators(&self) -> Vec<(AgitatorType, CryptexResult<bool>)> {
+        if let Some((checked_at, outcomes)) = self.validation_cache.lock().await.as_ref() {
+            if checked_at.elapsed() < VALIDATION_CACHE_TTL {
+                return replay_outcomes(outcomes);
+            }
+        }
+
+        // The cache is not locked while the providers are asked, so a slow
+        // provider never blocks callers that could be served from it
+        let checks: Vec<_> = self
+            .agitators
+            .iter()
+            .map(|agitator| {
+                let agitator = Arc::clone(agitator);
+                let limit = self.validation_timeout;
+                tokio::spawn(async move {
+                    tokio::time::timeout(limit, agitator.the_validation())
+                        .await
+                        .unwrap_or(Err(CryptexError::Timeout))
+                })
+            })
+            .collect();
+
+        let mut outcomes = Vec::with_capacity(checks.len());
+        for (agitator, check) in self.agitators.iter().zip(checks) {
+            let outcome = match check.await {
+                Ok(result) => result,
+                Err(e) => Err(CryptexError::agitator(format!("Validation task failed: {}", e))),
+            };
+            if let Err(ref e) = outcome {
+                tracing::warn!("Agitator {:?} failed validation: {}", agitator.agitator_type(), e);
+            }
+            outcomes.push((agitator.agitator_type(), outcome));
+        }
+
+        *self.validation_cache.lock().await = Some((Instant::now(), replay_outcomes(&outcomes)));
+        outcomes
+    }
+
     /// Get current metrics
     ///
     /// Traditional name: `get_metrics`
@@ -290,6 +412,40 @@ impl TheCollective {
         self.observer.the_metrics().await
     }
 
+    /// The agitator inquiries go to when they name no preference
+    pub fn primary_agitator(&self) -> Option<Arc<dyn TheAgitator>> {
+        self.agitators.first().cloned()
+    }
+
+    /// The agitator to write remediation guidance with, if the charter
+    /// enables one
+    ///
+    /// A provider that can't be set up, say for a missing API key, is logged
+    /// and left out, so servers fall back to template guidance rather than
+    /// refusing to start.
+    pub async fn remediation_agitator(charter: &TheCharter) -> Option<Arc<dyn TheAgitator>> {
+        if !charter.agitators.iter().any(|agitator| agitator.enabled) {
+            return None;
+        }
+        match Self::the_awakening(charter.clone()).await {
+            Ok(collective) => collective.primary_agitator(),
+            Err(e) => {
+                tracing::warn!("AI remediation guidance disabled: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Metrics snapshots taken strictly after `since`, oldest first
+    ///
+    /// Empty unless `metrics_history` is configured in the charter.
+    pub fn metrics_history(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Vec<crate::observer::MetricsSample> {
+        self.observer.metrics_history(since)
+    }
+
     /// Get cache statistics
     pub async fn the_memory_statistics(&self) -> crate::memory::CacheStatistics {
         self.memory.statistics().await
@@ -322,16 +478,41 @@ impl TheCollective {
             memory: Arc::clone(&self.memory),
             observer: Arc::clone(&self.observer),
             agitators: self.agitators.clone(),
+            prompts: Arc::clone(&self.prompts),
+            validation_cache: Arc::clone(&self.validation_cache),
+            validation_timeout: self.validation_timeout,
         })
     }
 }
 
+/// Copy cached outcomes for another caller
+///
+/// `CryptexError` is not `Clone`; the variants a provider check reports are
+/// rebuilt as they are, anything else becomes an agitator error with the
+/// same message.
+fn replay_outcomes(outcomes: &ValidationOutcomes) -> ValidationOutcomes {
+    outcomes
+        .iter()
+        .map(|(agitator_type, outcome)| {
+            let outcome = match outcome {
+                Ok(valid) => Ok(*valid),
+                Err(CryptexError::RateLimited(msg)) => Err(CryptexError::rate_limited(msg.clone())),
+                Err(CryptexError::UpstreamError(msg)) => Err(CryptexError::upstream(msg.clone())),
+                Err(CryptexError::AgitatorError(msg)) => Err(CryptexError::agitator(msg.clone())),
+                Err(CryptexError::Timeout) => Err(CryptexError::Timeout),
+                Err(e) => Err(CryptexError::agitator(e.to_string())),
+            };
+            (*agitator_type, outcome)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use the_foundation::TaskType;
     use the_coordinator::{
-        AgitatorConfig, ArchiveConfig, CommuneConfig, MemoryConfig,
+        AgitatorConfig, ArchiveConfig, CommuneConfig, ConnectionConfig, MemoryConfig,
     };
 
     fn create_test_charter() -> TheCharter {
@@ -341,6 +522,9 @@ mod tests {
                 host: "127.0.0.1".to_string(),
                 port: 8080,
                 ws_port: 9090,
+                completion_webhook_url: None,
+                completion_webhook_secret: None,
+                report_concurrency: 4,
             },
             agitators: vec![AgitatorConfig {
                 name: "test-openai".to_string(),
@@ -350,17 +534,31 @@ mod tests {
                 api_key_env: None,
                 model: Some("gpt-4".to_string()),
                 timeout_seconds: 30,
+                temperature: None,
+                max_tokens: None,
             }],
             archive: ArchiveConfig {
                 path: "./test-archive.redb".into(),
                 max_size_gb: 1,
+                retain_days: None,
             },
             memory: MemoryConfig {
                 max_size_mb: 256,
                 ttl_seconds: 3600,
             },
             infiltrator: None,
+            connection: ConnectionConfig::default(),
             cache_threshold: 0.8,
+            cache_thresholds: Default::default(),
+            latency_ema_alpha: 0.2,
+            prompts: Default::default(),
+            metrics_history: None,
+            report: Default::default(),
+            assessor: Default::default(),
+            http: Default::default(),
+            object_storage: None,
+            notifications: Default::default(),
+            redaction: Default::default(),
         }
     }
 
@@ -372,6 +570,21 @@ mod tests {
         assert!(collective.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_remediation_agitator() {
+        let mut charter = create_test_charter();
+        let agitator = TheCollective::remediation_agitator(&charter).await.unwrap();
+        assert_eq!(agitator.agitator_type(), AgitatorType::OpenAI);
+
+        // A provider that can't start leaves guidance to the templates
+        charter.agitators[0].api_key = None;
+        charter.agitators[0].api_key_env = Some("CRYPTEX_TEST_UNSET_AGITATOR_KEY".to_string());
+        assert!(TheCollective::remediation_agitator(&charter).await.is_none());
+
+        charter.agitators[0].enabled = false;
+        assert!(TheCollective::remediation_agitator(&charter).await.is_none());
+    }
+
     #[tokio::test]
     async fn test_collective_metrics() {
         let charter = create_test_charter();
@@ -392,18 +605,33 @@ mod tests {
                 host: "127.0.0.1".to_string(),
                 port: 8080,
                 ws_port: 9090,
+                completion_webhook_url: None,
+                completion_webhook_secret: None,
+                report_concurrency: 4,
             },
             agitators: vec![], // No agitators configured
             archive: ArchiveConfig {
                 path: "./test-archive.redb".into(),
                 max_size_gb: 1,
+                retain_days: None,
             },
             memory: MemoryConfig {
                 max_size_mb: 256,
                 ttl_seconds: 3600,
             },
             infiltrator: None,
+            connection: ConnectionConfig::default(),
             cache_threshold: 0.8,
+            cache_thresholds: Default::default(),
+            latency_ema_alpha: 0.2,
+            prompts: Default::default(),
+            metrics_history: None,
+            report: Default::default(),
+            assessor: Default::default(),
+            http: Default::default(),
+            object_storage: None,
+            notifications: Default::default(),
+            redaction: Default::default(),
         };
 
         let collective = TheCollective::the_awakening(charter).await.unwrap();
@@ -423,6 +651,233 @@ mod tests {
             .contains("No agitators available"));
     }
 
+    struct StubAgitator {
+        agitator_type: AgitatorType,
+        healthy: bool,
+        /// Answer only after this long
+        delay: Duration,
+        checks: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl TheAgitator for StubAgitator {
+        fn agitator_type(&self) -> AgitatorType {
+            self.agitator_type
+        }
+
+        fn the_mind(&self) -> &str {
+            "stub"
+        }
+
+        async fn the_agitation(&self, _package: ThePackage) -> CryptexResult<TheReply> {
+            Err(CryptexError::agitator("stub does not answer"))
+        }
+
+        async fn the_validation(&self) -> CryptexResult<bool> {
+            self.checks.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            if self.healthy {
+                Ok(true)
+            } else {
+                Err(CryptexError::upstream("invalid API key"))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_agitators_reports_each_provider() {
+        let mut charter = create_test_charter();
+        charter.agitators.clear();
+        let mut collective = TheCollective::the_awakening(charter).await.unwrap();
+        collective.validation_timeout = Duration::from_millis(200);
+
+        let stub = |agitator_type, healthy, delay| {
+            Arc::new(StubAgitator {
+                agitator_type,
+                healthy,
+                delay,
+                checks: Default::default(),
+            })
+        };
+        let claude = stub(AgitatorType::Claude, true, Duration::ZERO);
+        let gemini = stub(AgitatorType::Gemini, false, Duration::ZERO);
+        let openai = stub(AgitatorType::OpenAI, true, Duration::from_secs(60));
+        collective.agitators = vec![claude.clone(), gemini.clone(), openai.clone()];
+
+        let started = Instant::now();
+        let results = collective.validate_agitators().await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, AgitatorType::Claude);
+        assert!(matches!(results[0].1, Ok(true)));
+        assert_eq!(results[1].0, AgitatorType::Gemini);
+        let gemini_result = &results[1].1;
+        assert!(
+            matches!(gemini_result, Err(CryptexError::UpstreamError(m)) if m == "invalid API key"),
+            "{:?}",
+            gemini_result
+        );
+        assert!(matches!(results[2].1, Err(CryptexError::Timeout)));
+
+        // The second round comes from the cache, error kinds intact
+        let again = collective.validate_agitators().await;
+        assert!(matches!(again[0].1, Ok(true)));
+        assert!(matches!(again[1].1, Err(CryptexError::UpstreamError(_))));
+        assert!(matches!(again[2].1, Err(CryptexError::Timeout)));
+        assert_eq!(claude.checks.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(gemini.checks.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(openai.checks.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    /// Answers every inquiry, remembering the context it was given
+    #[derive(Default)]
+    struct RecordingAgitator {
+        contexts: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl TheAgitator for RecordingAgitator {
+        fn agitator_type(&self) -> AgitatorType {
+            AgitatorType::Claude
+        }
+
+        fn the_mind(&self) -> &str {
+            "recorder"
+        }
+
+        async fn the_agitation(&self, package: ThePackage) -> CryptexResult<TheReply> {
+            self.contexts.lock().unwrap().push(package.context);
+            Ok(TheReply {
+                inquiry_id: String::new(),
+                content: "ok".to_string(),
+                certainty: 0.5,
+                agitator_used: AgitatorType::Claude,
+                the_mind: "recorder".to_string(),
+                duration_ms: 0,
+                tokens_used: None,
+                replied_at_ts: 0,
+            })
+        }
+
+        async fn the_validation(&self) -> CryptexResult<bool> {
+            Ok(true)
+        }
+    }
+
+    /// Answers every inquiry with the same certainty, counting the calls
+    struct CertainAgitator {
+        certainty: f64,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl TheAgitator for CertainAgitator {
+        fn agitator_type(&self) -> AgitatorType {
+            AgitatorType::Claude
+        }
+
+        fn the_mind(&self) -> &str {
+            "certain"
+        }
+
+        async fn the_agitation(&self, _package: ThePackage) -> CryptexResult<TheReply> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(TheReply {
+                inquiry_id: String::new(),
+                content: "ok".to_string(),
+                certainty: self.certainty,
+                agitator_used: AgitatorType::Claude,
+                the_mind: "certain".to_string(),
+                duration_ms: 0,
+                tokens_used: None,
+                replied_at_ts: 0,
+            })
+        }
+
+        async fn the_validation(&self) -> CryptexResult<bool> {
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_threshold_per_task_type() {
+        let mut charter = create_test_charter();
+        charter.agitators.clear();
+        charter.cache_thresholds.insert(TaskType::ThreatModeling, 0.5);
+        charter.cache_thresholds.insert(TaskType::ScanOptimization, 1.1);
+        let mut collective = TheCollective::the_awakening(charter).await.unwrap();
+
+        let agitator = Arc::new(CertainAgitator {
+            certainty: 0.6,
+            calls: Default::default(),
+        });
+        collective.agitators = vec![agitator.clone()];
+
+        let calls_for = |task: TaskType| {
+            let (collective, agitator) = (&collective, &agitator);
+            async move {
+                let data = serde_json::json!({"cve": "CVE-2021-44228"});
+                let before = agitator.calls.load(std::sync::atomic::Ordering::SeqCst);
+                for _ in 0..2 {
+                    collective
+                        .the_inquiry_sync(TheInquiry::new(task, data.clone()))
+                        .await
+                        .unwrap();
+                }
+                agitator.calls.load(std::sync::atomic::Ordering::SeqCst) - before
+            }
+        };
+
+        // 0.6 meets the 0.5 override: the second inquiry is a cache hit
+        assert_eq!(calls_for(TaskType::ThreatModeling).await, 1);
+        // Nothing reaches 1.1
+        assert_eq!(calls_for(TaskType::ScanOptimization).await, 2);
+        // Without an override the global 0.8 applies
+        assert_eq!(calls_for(TaskType::VulnerabilityAnalysis).await, 2);
+        assert_eq!(collective.the_metrics().await.cache_hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_inquiry_uses_prompt_for_its_task() {
+        let mut charter = create_test_charter();
+        charter.agitators.clear();
+        charter
+            .prompts
+            .insert(TaskType::ThreatModeling, "Map the attack paths.".to_string());
+        let mut collective = TheCollective::the_awakening(charter).await.unwrap();
+
+        let recorder = Arc::new(RecordingAgitator::default());
+        collective.agitators = vec![recorder.clone()];
+
+        let data = serde_json::json!({"cve": "CVE-2021-44228"});
+        collective
+            .the_inquiry_sync(TheInquiry::new(TaskType::ThreatModeling, data.clone()))
+            .await
+            .unwrap();
+        collective
+            .the_inquiry_sync(TheInquiry::new(TaskType::ScanOptimization, data.clone()))
+            .await
+            .unwrap();
+        // Context supplied by the caller is passed through untouched
+        collective
+            .the_inquiry_sync(
+                TheInquiry::new(TaskType::ReportGeneration, data)
+                    .with_context("Write for executives.".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let contexts = recorder.contexts.lock().unwrap().clone();
+        assert_eq!(
+            contexts,
+            vec![
+                Some("Map the attack paths.".to_string()),
+                Some(the_foundation::DEFAULT_SYSTEM_PROMPT.to_string()),
+                Some("Write for executives.".to_string()),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_collective_rest() {
         let charter = create_test_charter();
@@ -67,6 +67,7 @@
 pub mod api;
 pub mod memory;
 pub mod observer;
+pub mod prompts;
 
 // Re-exports from the_foundation
 pub use the_foundation::{
@@ -77,7 +78,10 @@ pub use the_foundation::{
 // Re-exports from this crate
 pub use api::TheCollective;
 pub use memory::{CacheStatistics, TheMemory};
-pub use observer::{ObserverMetrics, TheObserver};
+pub use observer::{
+    Clock, MetricsSample, ObserverMetrics, ProviderMetrics, SystemClock, TheObserver,
+};
+pub use prompts::PromptLibrary;
 pub use the_coordinator::TheCharter;
 
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -3,11 +3,38 @@
 //! Traditional name: `Metrics` or `Monitor`
 //!
 //! Tracks performance metrics, request statistics, and system health.
+//!
+//! Counters are cumulative. For a view over time, a sampler can snapshot
+//! them at a fixed interval into a bounded history.
 
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Source of sample timestamps, swappable in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Default number of samples kept in the metrics history
+pub const DEFAULT_HISTORY_CAPACITY: usize = 360;
+
+/// A metrics snapshot and when it was taken
+pub type MetricsSample = (DateTime<Utc>, ObserverMetrics);
 
 /// The Observer - Metrics collection and monitoring
 ///
@@ -21,18 +48,50 @@ pub struct TheObserver {
     cache_hits: Arc<AtomicU64>,
     cache_misses: Arc<AtomicU64>,
     total_duration_ms: Arc<AtomicU64>,
-    agitator_stats: Arc<RwLock<AgitatorStatistics>>,
+    agitator_stats: Arc<RwLock<HashMap<String, ProviderStat>>>,
+    latency_alpha: f64,
+    history: Mutex<VecDeque<MetricsSample>>,
+    history_capacity: usize,
+    clock: Arc<dyn Clock>,
 }
 
-/// Statistics per agitator (AI provider)
+/// Default smoothing factor for agitator latency averages
+pub const DEFAULT_LATENCY_ALPHA: f64 = 0.2;
+
+/// Statistics for one agitator (AI provider)
 #[derive(Debug, Clone, Default)]
-struct AgitatorStatistics {
-    openai_requests: u64,
-    openai_successes: u64,
-    openai_failures: u64,
-    claude_requests: u64,
-    claude_successes: u64,
-    claude_failures: u64,
+struct ProviderStat {
+    requests: u64,
+    successes: u64,
+    failures: u64,
+    /// Exponential moving average of request latency, unset until the first request
+    avg_latency_ms: Option<f64>,
+}
+
+impl ProviderStat {
+    fn record(&mut self, success: bool, duration: Duration, alpha: f64) {
+        self.requests += 1;
+        if success {
+            self.successes += 1;
+        } else {
+            self.failures += 1;
+        }
+
+        let sample = duration.as_secs_f64() * 1000.0;
+        self.avg_latency_ms = Some(match self.avg_latency_ms {
+            Some(avg) => alpha * sample + (1.0 - alpha) * avg,
+            None => sample,
+        });
+    }
+
+    fn snapshot(&self, name: &str) -> ProviderMetrics {
+        ProviderMetrics {
+            name: name.to_string(),
+            requests: self.requests,
+            success_rate: calculate_success_rate(self.successes, self.requests),
+            avg_latency_ms: self.avg_latency_ms.unwrap_or(0.0),
+        }
+    }
 }
 
 impl TheObserver {
@@ -49,10 +108,35 @@ impl TheObserver {
             cache_hits: Arc::new(AtomicU64::new(0)),
             cache_misses: Arc::new(AtomicU64::new(0)),
             total_duration_ms: Arc::new(AtomicU64::new(0)),
-            agitator_stats: Arc::new(RwLock::new(AgitatorStatistics::default())),
+            agitator_stats: Arc::new(RwLock::new(HashMap::new())),
+            latency_alpha: DEFAULT_LATENCY_ALPHA,
+            history: Mutex::new(VecDeque::new()),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Keep at most `capacity` samples in the metrics history (at least one)
+    pub fn with_history_capacity(mut self, capacity: usize) -> Self {
+        self.history_capacity = capacity.max(1);
+        self
+    }
+
+    /// Timestamp samples with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set the smoothing factor for agitator latency averages
+    ///
+    /// Each new sample contributes `alpha` of the average; values are clamped
+    /// to (0.0, 1.0].
+    pub fn with_latency_alpha(mut self, alpha: f64) -> Self {
+        self.latency_alpha = alpha.clamp(f64::EPSILON, 1.0);
+        self
+    }
+
     /// Record the start of an inquiry
     ///
     /// Traditional name: `record_request_start`
@@ -93,28 +177,15 @@ impl TheObserver {
     }
 
     /// Record agitator (AI provider) usage
-    pub async fn record_agitator_request(&self, agitator: &str, success: bool) {
+    ///
+    /// `duration` covers the provider call alone. Failed calls count toward
+    /// latency too, so a provider that keeps timing out shows it.
+    pub async fn record_agitator_request(&self, agitator: &str, success: bool, duration: Duration) {
         let mut stats = self.agitator_stats.write().await;
-
-        match agitator.to_lowercase().as_str() {
-            "openai" => {
-                stats.openai_requests += 1;
-                if success {
-                    stats.openai_successes += 1;
-                } else {
-                    stats.openai_failures += 1;
-                }
-            }
-            "claude" => {
-                stats.claude_requests += 1;
-                if success {
-                    stats.claude_successes += 1;
-                } else {
-                    stats.claude_failures += 1;
-                }
-            }
-            _ => {}
-        }
+        stats
+            .entry(agitator.to_lowercase())
+            .or_default()
+            .record(success, duration, self.latency_alpha);
     }
 
     /// Get current metrics snapshot
@@ -140,13 +211,25 @@ impl TheObserver {
             0.0
         };
 
-        let avg_duration_ms = if success > 0 {
-            total_duration / success
-        } else {
-            0
+        let avg_duration_ms = total_duration.checked_div(success).unwrap_or(0);
+
+        let mut providers: Vec<ProviderMetrics> = self
+            .agitator_stats
+            .read()
+            .await
+            .iter()
+            .map(|(name, stats)| stats.snapshot(name))
+            .collect();
+        providers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let provider = |name: &str| {
+            providers
+                .iter()
+                .find(|p| p.name == name)
+                .cloned()
+                .unwrap_or_default()
         };
-
-        let agitator_stats = self.agitator_stats.read().await.clone();
+        let (openai, claude, gemini) = (provider("openai"), provider("claude"), provider("gemini"));
 
         ObserverMetrics {
             inquiries_total: total,
@@ -157,17 +240,63 @@ impl TheObserver {
             cache_misses,
             cache_hit_rate,
             avg_response_time_ms: avg_duration_ms,
-            openai_requests: agitator_stats.openai_requests,
-            openai_success_rate: calculate_success_rate(
-                agitator_stats.openai_successes,
-                agitator_stats.openai_requests,
-            ),
-            claude_requests: agitator_stats.claude_requests,
-            claude_success_rate: calculate_success_rate(
-                agitator_stats.claude_successes,
-                agitator_stats.claude_requests,
-            ),
+            openai_requests: openai.requests,
+            openai_success_rate: openai.success_rate,
+            openai_avg_latency_ms: openai.avg_latency_ms,
+            claude_requests: claude.requests,
+            claude_success_rate: claude.success_rate,
+            claude_avg_latency_ms: claude.avg_latency_ms,
+            gemini_requests: gemini.requests,
+            gemini_success_rate: gemini.success_rate,
+            gemini_avg_latency_ms: gemini.avg_latency_ms,
+            providers,
+        }
+    }
+
+    /// Snapshot the current metrics into the history
+    ///
+    /// The oldest sample is dropped once the history is full.
+    pub async fn record_sample(&self) {
+        let metrics = self.the_metrics().await;
+        let taken_at = self.clock.now();
+
+        let mut history = self.history.lock().unwrap();
+        while history.len() >= self.history_capacity {
+            history.pop_front();
         }
+        history.push_back((taken_at, metrics));
+    }
+
+    /// Samples taken strictly after `since`, oldest first; all of them without it
+    pub fn metrics_history(&self, since: Option<DateTime<Utc>>) -> Vec<MetricsSample> {
+        self.history
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(taken_at, _)| since.is_none_or(|since| *taken_at > since))
+            .cloned()
+            .collect()
+    }
+
+    /// Sample the metrics every `interval` in the background
+    ///
+    /// The first sample is taken straight away. The task holds only a weak
+    /// reference and stops once the observer is dropped.
+    pub fn spawn_sampler(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let observer: Weak<Self> = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                ticker.tick().await;
+                match observer.upgrade() {
+                    Some(observer) => observer.record_sample().await,
+                    None => break,
+                }
+            }
+        })
     }
 
     /// Record final metrics (called during shutdown)
@@ -211,6 +340,36 @@ pub struct ObserverMetrics {
     pub openai_success_rate: f64,
     pub claude_requests: u64,
     pub claude_success_rate: f64,
+    pub gemini_requests: u64,
+    pub gemini_success_rate: f64,
+    pub openai_avg_latency_ms: f64,
+    pub claude_avg_latency_ms: f64,
+    pub gemini_avg_latency_ms: f64,
+    /// Every provider seen so far, sorted by name
+    ///
+    /// The per-provider fields above are kept for existing consumers; new
+    /// agitators only show up here.
+    pub providers: Vec<ProviderMetrics>,
+}
+
+impl ObserverMetrics {
+    /// Metrics for one provider, matched case-insensitively
+    pub fn provider(&self, name: &str) -> Option<&ProviderMetrics> {
+        self.providers
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Metrics for a single agitator (AI provider)
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ProviderMetrics {
+    /// Lowercase provider name
+    pub name: String,
+    pub requests: u64,
+    pub success_rate: f64,
+    /// Exponential moving average of request latency
+    pub avg_latency_ms: f64,
 }
 
 #[cfg(test)]
@@ -264,11 +423,13 @@ mod tests {
     async fn test_observer_agitator_stats() {
         let observer = TheObserver::the_awakening();
 
-        observer.record_agitator_request("openai", true).await;
-        observer.record_agitator_request("openai", true).await;
-        observer.record_agitator_request("openai", false).await;
+        observer.record_agitator_request("openai", true, Duration::from_millis(100)).await;
+        observer.record_agitator_request("openai", true, Duration::from_millis(100)).await;
+        observer.record_agitator_request("openai", false, Duration::from_millis(100)).await;
+
+        observer.record_agitator_request("claude", true, Duration::from_millis(100)).await;
 
-        observer.record_agitator_request("claude", true).await;
+        observer.record_agitator_request("Gemini", false, Duration::from_millis(100)).await;
 
         let metrics = observer.the_metrics().await;
 
@@ -276,5 +437,117 @@ mod tests {
         assert!((metrics.openai_success_rate - 66.67).abs() < 0.1);
         assert_eq!(metrics.claude_requests, 1);
         assert_eq!(metrics.claude_success_rate, 100.0);
+        assert_eq!(metrics.gemini_requests, 1);
+        assert_eq!(metrics.gemini_success_rate, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_observer_agitator_latency_ema() {
+        let observer = TheObserver::the_awakening().with_latency_alpha(0.5);
+
+        observer
+            .record_agitator_request("openai", true, Duration::from_millis(100))
+            .await;
+        observer
+            .record_agitator_request("openai", true, Duration::from_millis(300))
+            .await;
+        observer
+            .record_agitator_request("claude", true, Duration::from_millis(1000))
+            .await;
+        observer
+            .record_agitator_request("ollama", false, Duration::from_millis(40))
+            .await;
+
+        let metrics = observer.the_metrics().await;
+
+        // First sample seeds the average, then 0.5 * 300 + 0.5 * 100
+        assert!((metrics.openai_avg_latency_ms - 200.0).abs() < 1e-6);
+        assert!((metrics.claude_avg_latency_ms - 1000.0).abs() < 1e-6);
+        assert_eq!(metrics.gemini_avg_latency_ms, 0.0);
+
+        // Providers without a dedicated field are still tracked
+        let ollama = metrics.provider("ollama").unwrap();
+        assert_eq!(ollama.requests, 1);
+        assert_eq!(ollama.success_rate, 0.0);
+        assert!((ollama.avg_latency_ms - 40.0).abs() < 1e-6);
+    }
+
+    /// Wall time that follows tokio's (pausable) clock
+    struct TokioClock {
+        origin: DateTime<Utc>,
+        start: tokio::time::Instant,
+    }
+
+    impl Clock for TokioClock {
+        fn now(&self) -> DateTime<Utc> {
+            let elapsed = self.start.elapsed();
+            self.origin + chrono::Duration::from_std(elapsed).unwrap()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sampler_history() {
+        let origin = Utc::now();
+        let observer = Arc::new(
+            TheObserver::the_awakening()
+                .with_history_capacity(5)
+                .with_clock(Arc::new(TokioClock {
+                    origin,
+                    start: tokio::time::Instant::now(),
+                })),
+        );
+        let minute = Duration::from_secs(60);
+        let sampler = observer.spawn_sampler(minute);
+
+        // Samples at 0, 1, 2 and 3 minutes
+        tokio::time::sleep(minute * 3 + Duration::from_secs(1)).await;
+        observer.record_inquiry_start("late");
+        let history = observer.metrics_history(None);
+        assert_eq!(history.len(), 4);
+        assert!(history.iter().all(|(_, metrics)| metrics.inquiries_total == 0));
+
+        let since = origin + chrono::Duration::seconds(90);
+        let recent = observer.metrics_history(Some(since));
+        let offsets: Vec<i64> = recent.iter().map(|(at, _)| (*at - origin).num_seconds()).collect();
+        assert_eq!(offsets, vec![120, 180]);
+
+        // Four more samples overflow the capacity; the oldest are dropped
+        tokio::time::sleep(minute * 4).await;
+        let history = observer.metrics_history(None);
+        assert_eq!(history.len(), 5);
+        assert_eq!((history[0].0 - origin).num_seconds(), 180);
+        assert_eq!(history[4].1.inquiries_total, 1);
+
+        // The sampler winds down with its observer
+        drop(observer);
+        tokio::time::sleep(minute).await;
+        assert!(sampler.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_observer_tracks_unknown_provider() {
+        let observer = TheObserver::the_awakening();
+
+        observer
+            .record_agitator_request("Acme-LLM", true, Duration::from_millis(10))
+            .await;
+        observer
+            .record_agitator_request("acme-llm", false, Duration::from_millis(10))
+            .await;
+        observer
+            .record_agitator_request("openai", true, Duration::from_millis(10))
+            .await;
+
+        let metrics = observer.the_metrics().await;
+
+        let names: Vec<&str> = metrics.providers.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["acme-llm", "openai"]);
+
+        let acme = metrics.provider("ACME-LLM").unwrap();
+        assert_eq!(acme.requests, 2);
+        assert_eq!(acme.success_rate, 50.0);
+        assert_eq!(metrics.openai_requests, 1);
+        assert_eq!(metrics.claude_requests, 0);
+        assert!(metrics.provider("claude").is_none());
     }
 }
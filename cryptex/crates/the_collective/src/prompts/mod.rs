@@ -0,0 +1,56 @@
+//! The Prompt Library - System prompts per task type
+//!
+//! Traditional name: `PromptTemplates`
+//!
+//! Threat modeling and remediation writing want different framing, so the
+//! charter may map each `TaskType` to its own system prompt. Inquiries whose
+//! task has no entry get the default security-analyst prompt.
+
+use std::collections::HashMap;
+
+use the_foundation::{TaskType, DEFAULT_SYSTEM_PROMPT};
+
+/// System prompts keyed by task type
+#[derive(Debug, Clone, Default)]
+pub struct PromptLibrary {
+    templates: HashMap<TaskType, String>,
+}
+
+impl PromptLibrary {
+    /// The Awakening - Build a library from the charter's prompt map
+    ///
+    /// Blank templates are dropped so they fall back to the default.
+    pub fn the_awakening(templates: HashMap<TaskType, String>) -> Self {
+        let templates = templates
+            .into_iter()
+            .filter(|(_, template)| !template.trim().is_empty())
+            .collect();
+
+        Self { templates }
+    }
+
+    /// System prompt for `task`
+    pub fn resolve(&self, task: TaskType) -> &str {
+        self.templates
+            .get(&task)
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_SYSTEM_PROMPT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_falls_back_to_default() {
+        let library = PromptLibrary::the_awakening(HashMap::from([
+            (TaskType::ThreatModeling, "Map the attack paths.".to_string()),
+            (TaskType::ReportGeneration, "   ".to_string()),
+        ]));
+
+        assert_eq!(library.resolve(TaskType::ThreatModeling), "Map the attack paths.");
+        assert_eq!(library.resolve(TaskType::ReportGeneration), DEFAULT_SYSTEM_PROMPT);
+        assert_eq!(library.resolve(TaskType::Custom), DEFAULT_SYSTEM_PROMPT);
+    }
+}
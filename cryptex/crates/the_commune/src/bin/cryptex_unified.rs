@@ -0,0 +1,347 @@
+// CRYPTEX Unified CLI
+// Operator entry point for maintenance tasks and for running the servers
+
+use clap::{Parser, Subcommand, ValueEnum};
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use the_archive::{KdfParams, TheArchive};
+use the_foundation::{CryptexError, CryptexResult};
+
+#[derive(Parser)]
+#[command(name = "cryptex_unified", version, about = "CRYPTEX operator CLI")]
+struct Cli {
+    /// Unlock a passphrase-protected archive for verify or prune, prompting
+    /// for the passphrase
+    #[arg(long, global = true)]
+    encrypt: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check that every row in an archive deserializes (read-only)
+    Verify {
+        /// Archive to check; defaults to the platform data directory
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+    /// Delete scans (and their results) older than a number of days
+    Prune {
+        /// Keep scans that ended within this many days
+        #[arg(long)]
+        days: u64,
+        /// Only report what would be deleted
+        #[arg(long)]
+        dry_run: bool,
+        /// Archive to prune; defaults to the platform data directory
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+    /// Protect an archive with a passphrase, prompting for it twice
+    ///
+    /// Verify and prune then need `--encrypt`, and the servers refuse it.
+    SetPassphrase {
+        /// Archive to protect; defaults to the platform data directory
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+    /// Run a server in this process
+    Serve {
+        #[arg(value_enum)]
+        server: Server,
+        /// Spawn the standalone server binary installed next to this one
+        /// instead of running the server in-process
+        #[arg(long)]
+        external: bool,
+        /// Address the REST API listens on; defaults to CRYPTEX_BIND_ADDR,
+        /// then 0.0.0.0:8080
+        #[arg(long)]
+        bind: Option<String>,
+        /// Archive to serve; defaults to cryptex.db (http) or cryptex-mcp.db
+        /// (mcp) in the platform data directory, as the two cannot share one
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Server {
+    /// MCP over stdin/stdout
+    Mcp,
+    /// REST API
+    Http,
+}
+
+impl Server {
+    /// Standalone binary running this server, used with `--external`
+    fn binary(self) -> &'static str {
+        match self {
+            Server::Mcp => "cryptex-mcp-server",
+            Server::Http => "the_interface_server",
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    let cli = Cli::parse();
+    let unlock = cli.encrypt;
+
+    match cli.command {
+        Command::Verify { db } => verify(db, unlock),
+        Command::Prune { days, dry_run, db } => prune(days, dry_run, db, unlock),
+        Command::SetPassphrase { .. } | Command::Serve { .. } if unlock => {
+            eprintln!("--encrypt only applies to verify and prune");
+            ExitCode::from(2)
+        }
+        Command::SetPassphrase { db } => set_passphrase(db),
+        Command::Serve {
+            server,
+            external,
+            bind,
+            db,
+        } => serve(server, external, bind, db),
+    }
+}
+
+/// Open an existing archive, unlocking it first if it is passphrase-protected
+///
+/// `unlock` is set by `--encrypt`. A protected archive is never opened without
+/// it. It never protects an unprotected archive; `set-passphrase` does that.
+fn open_archive(path: PathBuf, unlock: bool, read_only: bool) -> CryptexResult<TheArchive> {
+    let archive = if read_only {
+        TheArchive::the_awakening_readonly(path)?
+    } else {
+        TheArchive::open_existing(path)?
+    };
+
+    match (unlock, archive.is_passphrase_protected()?) {
+        (true, true) => {
+            // The stored KDF parameters are used; these are never written
+            archive.unlock(&prompt_passphrase, KdfParams::default())?;
+        }
+        (true, false) => {
+            return Err(CryptexError::validation(
+                "archive is not passphrase-protected; run set-passphrase to protect it",
+            ));
+        }
+        (false, true) => {
+            return Err(CryptexError::validation(
+                "archive is passphrase-protected; pass --encrypt to unlock it",
+            ));
+        }
+        (false, false) => {}
+    }
+
+    Ok(archive)
+}
+
+/// Read the passphrase from the terminal without echoing it
+///
+/// A new passphrase is asked for twice, so a typo cannot lock the archive.
+fn prompt_passphrase(first_use: bool) -> CryptexResult<String> {
+    if !first_use {
+        return Ok(rpassword::prompt_password("Archive passphrase: ")?);
+    }
+
+    let passphrase = rpassword::prompt_password("New archive passphrase: ")?;
+    let confirmation = rpassword::prompt_password("Confirm passphrase: ")?;
+    if passphrase != confirmation {
+        return Err(CryptexError::validation("passphrases do not match"));
+    }
+    Ok(passphrase)
+}
+
+fn set_passphrase(db: Option<PathBuf>) -> ExitCode {
+    let path = the_archive::resolve_archive_path(db);
+
+    let outcome = TheArchive::open_existing(path.clone()).and_then(|archive| {
+        if archive.is_passphrase_protected()? {
+            return Err(CryptexError::validation("archive is already passphrase-protected"));
+        }
+        archive.unlock(&prompt_passphrase, KdfParams::default()).map(|_| ())
+    });
+    if let Err(e) = outcome {
+        eprintln!("Failed to protect {}: {}", path.display(), e);
+        return ExitCode::from(2);
+    }
+
+    println!("Archive: {}", path.display());
+    println!("Passphrase set; verify and prune now need --encrypt, and the servers refuse it");
+    ExitCode::SUCCESS
+}
+
+fn verify(db: Option<PathBuf>, unlock: bool) -> ExitCode {
+    let path = the_archive::resolve_archive_path(db);
+
+    let report = match open_archive(path.clone(), unlock, true).and_then(|a| a.verify()) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Failed to verify {}: {}", path.display(), e);
+            return ExitCode::from(2);
+        }
+    };
+
+    println!("Archive: {}", path.display());
+    println!("Rows:    {} total, {} ok, {} failed", report.total, report.ok, report.failed);
+    for failure in &report.failures {
+        println!("  {} [{}]: {}", failure.table, failure.key, failure.error);
+    }
+
+    if report.is_clean() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn prune(days: u64, dry_run: bool, db: Option<PathBuf>, unlock: bool) -> ExitCode {
+    let path = the_archive::resolve_archive_path(db);
+    let cutoff = match the_archive::retention_cutoff(days) {
+        Ok(cutoff) => cutoff,
+        Err(e) => {
+            eprintln!("Invalid --days: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let stats = match open_archive(path.clone(), unlock, false).and_then(|archive| {
+        if dry_run {
+            archive.count_older_than(cutoff)
+        } else {
+            archive.prune_older_than(cutoff)
+        }
+    }) {
+        Ok(stats) => stats,
+        Err(e) => {
+            eprintln!("Failed to prune {}: {}", path.display(), e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let verb = if stats.dry_run { "Would delete" } else { "Deleted" };
+    println!("Archive: {}", path.display());
+    println!(
+        "{} {} scans and {} results older than {}",
+        verb, stats.scans, stats.results, cutoff
+    );
+    ExitCode::SUCCESS
+}
+
+fn serve(server: Server, external: bool, bind: Option<String>, db: Option<PathBuf>) -> ExitCode {
+    let path = match server {
+        Server::Mcp => db.unwrap_or_else(the_foundation::platform::get_mcp_db_path),
+        Server::Http => the_archive::resolve_archive_path(db),
+    };
+    let bind = bind
+        .or_else(|| std::env::var("CRYPTEX_BIND_ADDR").ok())
+        .unwrap_or_else(|| "0.0.0.0:8080".to_string());
+
+    let outcome = if external {
+        serve_external(server, &bind, &path)
+    } else {
+        tokio::runtime::Runtime::new()
+            .map_err(Box::<dyn Error>::from)
+            .and_then(|runtime| runtime.block_on(serve_embedded(server, &bind, path)))
+    };
+
+    match outcome {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{:?} server failed: {}", server, e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Run the server on this process's runtime through its library entry point
+async fn serve_embedded(server: Server, bind: &str, path: PathBuf) -> Result<(), Box<dyn Error>> {
+    match server {
+        Server::Mcp => the_commune::run(path).await,
+        Server::Http => Ok(the_interface::run_server(&path.to_string_lossy(), bind).await?),
+    }
+}
+
+/// Run the standalone server binary and wait for it to exit
+///
+/// The binary is looked up next to this executable; the archive path and
+/// bind address are handed over through its environment.
+fn serve_external(server: Server, bind: &str, path: &std::path::Path) -> Result<(), Box<dyn Error>> {
+    let exe = std::env::current_exe()?
+        .with_file_name(format!("{}{}", server.binary(), std::env::consts::EXE_SUFFIX));
+
+    let db_var = match server {
+        Server::Mcp => "CRYPTEX_MCP_DB_PATH",
+        Server::Http => "CRYPTEX_DB_PATH",
+    };
+    let status = std::process::Command::new(&exe)
+        .env(db_var, path)
+        .env("CRYPTEX_BIND_ADDR", bind)
+        .status()
+        .map_err(|e| format!("failed to start {}: {}", exe.display(), e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", exe.display(), status).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_embedded_http_serves_health() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let bind = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        let url = format!("http://{}/health", bind);
+
+        let probe = async {
+            for _ in 0..50 {
+                if let Ok(response) = reqwest::get(&url).await {
+                    return response.status().as_u16();
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            panic!("server never answered {}", url);
+        };
+
+        tokio::select! {
+            outcome = serve_embedded(Server::Http, &bind, temp_dir.path().join("cryptex.db")) => {
+                panic!("server stopped: {:?}", outcome.err().map(|e| e.to_string()));
+            }
+            status = probe => assert_eq!(status, 200),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_servers_refuse_protected_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("protected.db");
+        {
+            let archive = TheArchive::the_awakening(path.clone()).unwrap();
+            let params = KdfParams {
+                memory_kib: 64,
+                iterations: 1,
+                parallelism: 1,
+            };
+            archive.unlock(&|_| Ok("correct horse".to_string()), params).unwrap();
+        }
+
+        for server in [Server::Http, Server::Mcp] {
+            let err = serve_embedded(server, "127.0.0.1:0", path.clone()).await.unwrap_err();
+            assert!(err.to_string().contains("passphrase-protected"), "{:?}: {}", server, err);
+        }
+    }
+}
@@ -0,0 +1,985 @@
+//! # The Commune - MCP Server for CRYPTEX
+//!
+//! Model Context Protocol (MCP) server that exposes CRYPTEX functionality
+//! as tools for integration with PYRO_Platform_Ignition and other AI systems.
+//!
+//! Traditional name: `MCPServer` or `RPCServer`
+//!
+//! The server speaks JSON-RPC over stdin/stdout. `run` is the whole server;
+//! the `cryptex-mcp-server` binary and `cryptex_unified serve mcp` both call it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use the_archive::query::DEFAULT_QUERY_LIMIT;
+use the_archive::{ArchiveQuery, ExportFormat, QueryFilters, ScanMetadata, TheArchive};
+use the_assessor::{normalize_cve_id, TheAssessor};
+use the_collective::TheCollective;
+use the_coordinator::TheCharter;
+use the_infiltrator::TheInfiltrator;
+use the_propagandist::ThePropagandist;
+
+mod schema;
+
+/// JSON-RPC 2.0 Request
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    id: Option<Value>,
+    method: String,
+    params: Option<Value>,
+}
+
+/// JSON-RPC 2.0 Response
+#[derive(Debug, Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: String,
+    id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<JsonRpcError>,
+}
+
+/// JSON-RPC 2.0 Error
+#[derive(Debug, Serialize)]
+struct JsonRpcError {
+    code: i32,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+/// MCP Server
+struct MCPServer {
+    assessor: Arc<TheAssessor>,
+    infiltrator: TheInfiltrator,
+    propagandist: ThePropagandist,
+    archive: Arc<TheArchive>,
+    /// REST server's archive that `get_archive_stats` reports on; `None` when
+    /// it is this server's own archive
+    stats_path: Option<PathBuf>,
+}
+
+impl MCPServer {
+    /// Initialize the MCP server with an archive at the given path and
+    /// settings from `charter`
+    ///
+    /// A setting's dedicated environment variable, where it has one, still
+    /// takes precedence over the charter.
+    async fn with_charter(
+        archive_path: PathBuf,
+        charter: &TheCharter,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        tracing::info!("Initializing CRYPTEX MCP Server...");
+        the_foundation::init_redaction(&charter.redaction.headers, &charter.redaction.key_prefixes);
+
+        the_assessor::init_shared_fetch_limit(charter.assessor.max_concurrent_fetches);
+        let assessor = Arc::new(TheAssessor::the_awakening().await?);
+        let mut infiltrator = TheInfiltrator::the_awakening().await?;
+        if let Some(agitator) = TheCollective::remediation_agitator(charter).await {
+            infiltrator = infiltrator.with_agitator(agitator);
+        }
+        let infiltrator = infiltrator.with_notifier(the_infiltrator::Notifier::from_charter(
+            &charter.notifications,
+            &charter.connection.http_settings(),
+        ));
+        let propagandist = ThePropagandist::the_awakening()
+            .await?
+            .with_render_concurrency(
+                the_propagandist::render_concurrency_from_env()
+                    .unwrap_or(charter.commune.report_concurrency),
+            )
+            .with_audience(the_propagandist::report_audience(&charter.report.audience)?)
+            .with_sections(the_propagandist::report_sections(&charter.report.sections)?);
+        let archive = TheArchive::the_awakening(archive_path.clone()).map_err(|e| {
+            format!(
+                "Cannot open archive at {} (set CRYPTEX_MCP_DB_PATH; the REST server's archive \
+                 is locked while it runs): {}",
+                archive_path.display(),
+                e
+            )
+        })?;
+        let archive = Arc::new(archive);
+        let stats_path = Some(the_foundation::platform::get_default_db_path())
+            .filter(|stats_path| *stats_path != archive_path);
+
+        tracing::info!("CRYPTEX MCP Server initialized successfully");
+
+        Ok(Self {
+            assessor,
+            infiltrator,
+            propagandist,
+            archive,
+            stats_path,
+        })
+    }
+
+    /// Handle JSON-RPC request
+    async fn handle_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        match request.method.as_str() {
+            "initialize" => self.handle_initialize(request.id).await,
+            "tools/list" => self.handle_list_tools(request.id).await,
+            "tools/call" => self.handle_tool_call(request.id, request.params).await,
+            _ => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32601,
+                    message: "Method not found".to_string(),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Handle MCP initialize request
+    async fn handle_initialize(&self, id: Option<Value>) -> JsonRpcResponse {
+        JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "serverInfo": {
+                    "name": "cryptex-mcp-server",
+                    "version": "1.0.0"
+                },
+                "capabilities": {
+                    "tools": {
+                        "listChanged": false
+                    }
+                }
+            })),
+            error: None,
+        }
+    }
+
+    /// Handle tools/list request
+    async fn handle_list_tools(&self, id: Option<Value>) -> JsonRpcResponse {
+        let tools = schema::tool_definitions();
+
+        JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(json!({ "tools": tools })),
+            error: None,
+        }
+    }
+
+    /// Handle tools/call request
+    async fn handle_tool_call(&self, id: Option<Value>, params: Option<Value>) -> JsonRpcResponse {
+        let params = match params {
+            Some(p) => p,
+            None => {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message: "Invalid params".to_string(),
+                        data: None,
+                    }),
+                };
+            }
+        };
+
+        let tool_name = params["name"].as_str().unwrap_or("");
+        let arguments = &params["arguments"];
+
+        // Validate arguments against the advertised schema before dispatch
+        if let Some(input_schema) = schema::input_schema(tool_name) {
+            if let Err(violation) = schema::validate_arguments(&input_schema, arguments) {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message: format!("Invalid params: {}", violation),
+                        data: Some(json!({
+                            "tool": tool_name,
+                            "field": violation.field,
+                            "reason": violation.reason,
+                        })),
+                    }),
+                };
+            }
+        }
+
+        match tool_name {
+            "assess_vulnerability" => self.call_assess_vulnerability(id, arguments).await,
+            "score_cvss_vector" => self.call_score_cvss_vector(id, arguments).await,
+            "start_scan" => self.call_start_scan(id, arguments).await,
+            "end_scan" => self.call_end_scan(id, arguments).await,
+            "generate_report" => self.call_generate_report(id, arguments).await,
+            "get_executive_summary" => self.call_executive_summary(id, arguments).await,
+            "export_findings" => self.call_export_findings(id, arguments).await,
+            "get_archive_stats" => self.call_archive_stats(id),
+            _ => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32602,
+                    message: format!("Unknown tool: {}", tool_name),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Call assess_vulnerability tool
+    async fn call_assess_vulnerability(
+        &self,
+        id: Option<Value>,
+        args: &Value,
+    ) -> JsonRpcResponse {
+        let cve_id = match args["cve_id"].as_str().map(normalize_cve_id) {
+            Some(Ok(cve_id)) => cve_id,
+            invalid => {
+                let message = match invalid {
+                    Some(Err(e)) => format!("Invalid cve_id parameter: {}", e),
+                    _ => "Missing cve_id parameter".to_string(),
+                };
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message,
+                        data: None,
+                    }),
+                };
+            }
+        };
+
+        match self.assessor.assess_vulnerability(&cve_id).await {
+            Ok(score) => {
+                let result = json!({
+                    "cve_id": score.cve_id,
+                    "cvss_base_score": score.cvss_base_score(),
+                    "data_completeness": score.data_completeness,
+                    "severity": score.severity().as_str(),
+                    "is_kev": score.is_kev(),
+                    "composite_risk_score": score.composite_risk_score(),
+                    "ai_risk_score": score.ai_risk_score,
+                    "ai_priority": score.ai_priority,
+                    "remediation_urgency": score.ai_remediation_urgency,
+                    "ai_explanation": score.ai_explanation,
+                });
+
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: Some(json!({ "content": [{ "type": "text", "text": serde_json::to_string_pretty(&result).unwrap() }] })),
+                    error: None,
+                }
+            }
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32000,
+                    message: format!("Assessment failed: {}", e),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Call score_cvss_vector tool
+    async fn call_score_cvss_vector(&self, id: Option<Value>, args: &Value) -> JsonRpcResponse {
+        let vector = args["vector"].as_str().unwrap_or_default();
+        let cve_id = args["cve_id"].as_str();
+
+        match self.assessor.score_from_cvss_vector(cve_id.unwrap_or_default(), vector).await {
+            Ok(score) => {
+                let cvss = score.cvss_v3.as_ref();
+                let result = json!({
+                    "cve_id": cve_id,
+                    "version": cvss.map(|c| c.version()),
+                    "vector": vector,
+                    "base_score": score.cvss_base_score(),
+                    "severity": score.severity().as_str(),
+                    "metrics": cvss.map(|c| &c.base_metrics),
+                });
+
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: Some(json!({ "content": [{ "type": "text", "text": serde_json::to_string_pretty(&result).unwrap() }] })),
+                    error: None,
+                }
+            }
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32602,
+                    message: format!("Invalid CVSS vector: {}", e),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Call start_scan tool
+    async fn call_start_scan(&self, id: Option<Value>, args: &Value) -> JsonRpcResponse {
+        let target = match args["target"].as_str() {
+            Some(t) => t,
+            None => {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message: "Missing target parameter".to_string(),
+                        data: None,
+                    }),
+                };
+            }
+        };
+
+        match self.infiltrator.start_scan(target).await {
+            Ok(scan_id) => {
+                let metadata = ScanMetadata::new(scan_id.clone(), target.to_string());
+                if let Err(e) = self.archive.store_scan_metadata(&metadata) {
+                    tracing::warn!("Failed to archive scan {}: {}", scan_id, e);
+                }
+
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: Some(json!({ "content": [{ "type": "text", "text": format!("Scan started: {}", scan_id) }] })),
+                    error: None,
+                }
+            }
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32000,
+                    message: format!("Scan start failed: {}", e),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Call end_scan tool
+    async fn call_end_scan(&self, id: Option<Value>, args: &Value) -> JsonRpcResponse {
+        let scan_id = match args["scan_id"].as_str() {
+            Some(s) => s,
+            None => {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message: "Missing scan_id parameter".to_string(),
+                        data: None,
+                    }),
+                };
+            }
+        };
+
+        match self.infiltrator.end_scan(scan_id).await {
+            Ok(report) => {
+                // Archive the findings so they can be exported later
+                if let Err(e) = self.archive_report(&report) {
+                    tracing::warn!("Failed to archive results for scan {}: {}", scan_id, e);
+                }
+
+                let summary = format!(
+                    "Scan {} completed\nVulnerabilities: {}\nCritical: {}\nHigh: {}\nMedium: {}\nLow: {}\nKEV: {}",
+                    report.scan_id,
+                    report.total_vulnerabilities,
+                    report.critical_count,
+                    report.high_count,
+                    report.medium_count,
+                    report.low_count,
+                    report.kev_count
+                );
+
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: Some(json!({ "content": [{ "type": "text", "text": summary }] })),
+                    error: None,
+                }
+            }
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32000,
+                    message: format!("Scan end failed: {}", e),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Call generate_report tool
+    async fn call_generate_report(&self, id: Option<Value>, args: &Value) -> JsonRpcResponse {
+        let scan_id = match args["scan_id"].as_str() {
+            Some(s) => s,
+            None => {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message: "Missing scan_id parameter".to_string(),
+                        data: None,
+                    }),
+                };
+            }
+        };
+
+        let format = match args["format"].as_str() {
+            Some("json") => the_propagandist::ReportFormat::Json,
+            Some("html") => the_propagandist::ReportFormat::Html,
+            Some("markdown") => the_propagandist::ReportFormat::Markdown,
+            Some("text") => the_propagandist::ReportFormat::Text,
+            Some("csv") => the_propagandist::ReportFormat::Csv,
+            _ => {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message: "Invalid format parameter".to_string(),
+                        data: None,
+                    }),
+                };
+            }
+        };
+
+        // Get scan report first
+        match self.infiltrator.get_scan_context(scan_id).await {
+            Ok(scan_report) => {
+                match self.propagandist.generate_report(&scan_report, format).await {
+                    Ok(report) => JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id,
+                        result: Some(json!({ "content": [{ "type": "text", "text": report }] })),
+                        error: None,
+                    },
+                    Err(e) => JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id,
+                        result: None,
+                        error: Some(JsonRpcError {
+                            code: -32000,
+                            message: format!("Report generation failed: {}", e),
+                            data: None,
+                        }),
+                    },
+                }
+            }
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32000,
+                    message: format!("Scan not found: {}", e),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Call get_executive_summary tool
+    async fn call_executive_summary(&self, id: Option<Value>, args: &Value) -> JsonRpcResponse {
+        let scan_id = match args["scan_id"].as_str() {
+            Some(s) => s,
+            None => {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message: "Missing scan_id parameter".to_string(),
+                        data: None,
+                    }),
+                };
+            }
+        };
+
+        match self.infiltrator.get_scan_context(scan_id).await {
+            Ok(scan_report) => {
+                match self.propagandist.generate_executive_summary(&scan_report).await {
+                    Ok(summary) => JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id,
+                        result: Some(json!({ "content": [{ "type": "text", "text": summary }] })),
+                        error: None,
+                    },
+                    Err(e) => JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id,
+                        result: None,
+                        error: Some(JsonRpcError {
+                            code: -32000,
+                            message: format!("Summary generation failed: {}", e),
+                            data: None,
+                        }),
+                    },
+                }
+            }
+            Err(e) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32000,
+                    message: format!("Scan not found: {}", e),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Call export_findings tool
+    async fn call_export_findings(&self, id: Option<Value>, args: &Value) -> JsonRpcResponse {
+        let format = match args["format"].as_str().map(ExportFormat::parse) {
+            Some(Ok(format)) => format,
+            _ => {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32602,
+                        message: "Invalid format parameter".to_string(),
+                        data: None,
+                    }),
+                };
+            }
+        };
+
+        let mut filters: QueryFilters = if args["filters"].is_null() {
+            QueryFilters::default()
+        } else {
+            match serde_json::from_value(args["filters"].clone()) {
+                Ok(filters) => filters,
+                Err(e) => {
+                    return JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id,
+                        result: None,
+                        error: Some(JsonRpcError {
+                            code: -32602,
+                            message: format!("Invalid filters: {}", e),
+                            data: None,
+                        }),
+                    };
+                }
+            }
+        };
+
+        // One call returns the whole export as a single message; keep it bounded
+        filters.limit = Some(filters.limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(DEFAULT_QUERY_LIMIT));
+
+        let query = ArchiveQuery::from_archive(self.archive.clone());
+
+        match query.export_findings(&filters, format) {
+            Ok(content) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: Some(json!({ "content": [{ "type": "text", "text": content }] })),
+                error: None,
+            },
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32000,
+                    message: format!("Export failed: {}", e),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Call get_archive_stats tool
+    ///
+    /// Reports on the REST server's archive (`CRYPTEX_DB_PATH`), opened
+    /// read-only for the call. redb locks the file, so this fails while the
+    /// REST server has it open.
+    fn call_archive_stats(&self, id: Option<Value>) -> JsonRpcResponse {
+        let stats = match &self.stats_path {
+            Some(stats_path) => TheArchive::the_awakening_readonly(stats_path.clone())
+                .and_then(|archive| archive.get_stats())
+                .map_err(|e| format!("{}: {}", stats_path.display(), e)),
+            None => self.archive.get_stats().map_err(|e| e.to_string()),
+        };
+        match stats {
+            Ok(stats) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: Some(json!({ "content": [{ "type": "text", "text": serde_json::to_string_pretty(&stats).unwrap() }] })),
+                error: None,
+            },
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32000,
+                    message: format!("Archive unavailable: {}", e),
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    /// Persist a completed scan report to the archive in one transaction
+    fn archive_report(&self, report: &the_infiltrator::ScanReport) -> the_foundation::CryptexResult<()> {
+        let mut batch = self.archive.begin_batch()?;
+        for result in &report.scan_results {
+            batch.store_scan_result(&report.scan_id, result)?;
+        }
+
+        let mut metadata = batch
+            .scan_metadata(&report.scan_id)?
+            .unwrap_or_else(|| ScanMetadata::new(report.scan_id.clone(), report.target.clone()));
+
+        metadata.status = "completed".to_string();
+        metadata.ended_at = Some(chrono::Utc::now());
+        metadata.total_vulnerabilities = report.total_vulnerabilities;
+        metadata.critical = report.critical_count;
+        metadata.high = report.high_count;
+        metadata.medium = report.medium_count;
+        metadata.low = report.low_count;
+        batch.store_scan_metadata(&metadata)?;
+
+        batch.commit()?;
+        Ok(())
+    }
+}
+
+/// Serve MCP requests from stdin until it closes
+///
+/// Traditional name: `run_server`
+pub async fn run(archive_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    tracing::info!("Starting CRYPTEX MCP Server for PYRO integration...");
+
+    // Initialize MCP server
+    let charter = the_interface::load_charter()?;
+    let server = MCPServer::with_charter(archive_path, &charter).await?;
+    server.archive.ensure_unprotected()?;
+    let retain_days = the_archive::retain_days_from_env().or(charter.archive.retain_days);
+    if let Some(retain_days) = retain_days {
+        the_archive::spawn_retention(server.archive.clone(), retain_days)?;
+    }
+    let warm_up =
+        the_assessor::warm_up_watchlist(charter.assessor.warm_up, &charter.assessor.watchlist);
+    if let Some(watchlist) = warm_up {
+        the_assessor::spawn_warm_up(server.assessor.clone(), watchlist);
+    }
+
+    tracing::info!("CRYPTEX MCP Server ready - listening on stdin/stdout");
+    eprintln!("CRYPTEX MCP Server initialized - ready for PYRO integration");
+
+    // Process JSON-RPC requests from stdin
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+
+        // Parse JSON-RPC request
+        let request: JsonRpcRequest = match serde_json::from_str(&line) {
+            Ok(req) => req,
+            Err(e) => {
+                let error_response = JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: None,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32700,
+                        message: format!("Parse error: {}", e),
+                        data: None,
+                    }),
+                };
+
+                let response_json = serde_json::to_string(&error_response)?;
+                writeln!(stdout, "{}", response_json)?;
+                stdout.flush()?;
+                continue;
+            }
+        };
+
+        // Handle request
+        let response = server.handle_request(request).await;
+
+        // Send response
+        let response_json = serde_json::to_string(&response)?;
+        writeln!(stdout, "{}", response_json)?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_server() -> (tempfile::TempDir, MCPServer) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let charter = TheCharter::builtin();
+        let server = MCPServer::with_charter(temp_dir.path().join("cryptex.db"), &charter)
+            .await
+            .unwrap();
+        (temp_dir, server)
+    }
+
+    fn tool_call(name: &str, arguments: Value) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(json!(1)),
+            method: "tools/call".to_string(),
+            params: Some(json!({ "name": name, "arguments": arguments })),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_scan_rejects_numeric_target() {
+        let (_temp_dir, server) = test_server().await;
+
+        let response = server
+            .handle_request(tool_call("start_scan", json!({ "target": 42 })))
+            .await;
+
+        assert!(response.result.is_none());
+        let error = response.error.unwrap();
+        assert_eq!(error.code, -32602);
+        assert_eq!(
+            error.message,
+            "Invalid params: 'target' must be of type string, got number"
+        );
+        assert_eq!(error.data.unwrap()["field"], "target");
+    }
+
+    #[tokio::test]
+    async fn test_assess_vulnerability_normalizes_cve_id() {
+        let (_temp_dir, server) = test_server().await;
+
+        let response = server
+            .handle_request(tool_call("assess_vulnerability", json!({ "cve_id": "cve-2021-44228 " })))
+            .await;
+        let text = response.result.unwrap()["content"][0]["text"].as_str().unwrap().to_string();
+        let result: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(result["cve_id"], "CVE-2021-44228");
+
+        let response = server
+            .handle_request(tool_call("assess_vulnerability", json!({ "cve_id": "log4shell" })))
+            .await;
+        assert!(response.result.is_none());
+        assert_eq!(response.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_valid_arguments_dispatch() {
+        let (_temp_dir, server) = test_server().await;
+
+        let response = server
+            .handle_request(tool_call("start_scan", json!({ "target": "10.0.0.0/24" })))
+            .await;
+
+        assert!(response.error.is_none());
+        assert!(response.result.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_list_tools_uses_shared_schemas() {
+        let (_temp_dir, server) = test_server().await;
+
+        let response = server.handle_list_tools(Some(json!(1))).await;
+        let tools = response.result.unwrap()["tools"].as_array().unwrap().len();
+
+        assert_eq!(tools, schema::tool_definitions().len());
+    }
+
+    #[tokio::test]
+    async fn test_export_findings_csv_with_severity_filter() {
+        let (_temp_dir, server) = test_server().await;
+
+        let response = server
+            .handle_request(tool_call("start_scan", json!({ "target": "10.0.0.0/24" })))
+            .await;
+        let text = response.result.unwrap()["content"][0]["text"].as_str().unwrap().to_string();
+        let scan_id = text.trim_start_matches("Scan started: ").to_string();
+
+        server
+            .infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "10.0.0.5", 443)
+            .await
+            .unwrap();
+        server
+            .infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.6", 80)
+            .await
+            .unwrap();
+
+        let response = server
+            .handle_request(tool_call("end_scan", json!({ "scan_id": scan_id })))
+            .await;
+        assert!(response.error.is_none());
+
+        let response = server
+            .handle_request(tool_call(
+                "export_findings",
+                json!({ "filters": { "severity": "critical" }, "format": "csv" }),
+            ))
+            .await;
+
+        assert!(response.error.is_none());
+        let csv = response.result.unwrap()["content"][0]["text"].as_str().unwrap().to_string();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], the_archive::export::CSV_HEADER);
+        assert!(lines[0].starts_with("scan_id,cve_id,host,port,severity"));
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains("CVE-2021-44228"));
+    }
+
+    #[tokio::test]
+    async fn test_export_findings_limit_is_capped() {
+        let (_temp_dir, server) = test_server().await;
+
+        let mut batch = server.archive.begin_batch().unwrap();
+        for i in 0..DEFAULT_QUERY_LIMIT + 5 {
+            let result = the_infiltrator::ScanResult::new(
+                format!("CVE-2024-{:04}", i),
+                "10.0.0.5".to_string(),
+                443,
+                "https".to_string(),
+                "finding".to_string(),
+            );
+            batch.store_scan_result("scan_big", &result).unwrap();
+        }
+        batch.commit().unwrap();
+
+        let response = server
+            .handle_request(tool_call(
+                "export_findings",
+                json!({ "filters": { "limit": 1_000_000 }, "format": "csv" }),
+            ))
+            .await;
+
+        let csv = response.result.unwrap()["content"][0]["text"].as_str().unwrap().to_string();
+        assert_eq!(csv.lines().count(), DEFAULT_QUERY_LIMIT + 1);
+    }
+
+    #[tokio::test]
+    async fn test_archive_stats_tool() {
+        let (temp_dir, mut server) = test_server().await;
+
+        // The REST server's archive, not this server's own
+        let rest_path = temp_dir.path().join("rest.db");
+        {
+            let rest = TheArchive::the_awakening(rest_path.clone()).unwrap();
+            let score = server.assessor.assess_vulnerability("CVE-2021-44228").await.unwrap();
+            rest.store_vulnerability(&score).unwrap();
+            let scan = ScanMetadata::new("scan-001".to_string(), "10.0.0.0/24".to_string());
+            rest.store_scan_metadata(&scan).unwrap();
+            for host in ["10.0.0.5", "10.0.0.6"] {
+                let result = the_infiltrator::ScanResult::new(
+                    "CVE-2021-44228".to_string(),
+                    host.to_string(),
+                    443,
+                    "1.3.6.1.4.1.25623".to_string(),
+                    "Log4Shell".to_string(),
+                );
+                rest.store_scan_result("scan-001", &result).unwrap();
+            }
+        }
+        let mcp_only = ScanMetadata::new("mcp-only".to_string(), "10.1.0.0/24".to_string());
+        server.archive.store_scan_metadata(&mcp_only).unwrap();
+
+        server.stats_path = Some(temp_dir.path().join("missing.db"));
+        let response = server
+            .handle_request(tool_call("get_archive_stats", Value::Null))
+            .await;
+        assert_eq!(response.error.unwrap().code, -32000);
+
+        server.stats_path = Some(rest_path);
+        let response = server
+            .handle_request(tool_call("get_archive_stats", Value::Null))
+            .await;
+
+        assert!(response.error.is_none());
+        let text = response.result.unwrap()["content"][0]["text"].as_str().unwrap().to_string();
+        let stats: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(stats["total_scans"], 1);
+        assert_eq!(stats["total_vulnerabilities"], 1);
+        assert_eq!(stats["total_results"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_archive_path_is_reported() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let corrupt = temp_dir.path().join("cryptex.db");
+        std::fs::write(&corrupt, "not a database").unwrap();
+
+        let err = MCPServer::with_charter(corrupt.clone(), &TheCharter::builtin())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(err.to_string().contains(&corrupt.display().to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_export_findings_rejects_unknown_format() {
+        let (_temp_dir, server) = test_server().await;
+
+        let response = server
+            .handle_request(tool_call("export_findings", json!({ "format": "xlsx" })))
+            .await;
+
+        assert_eq!(response.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_score_cvss_vector_tool() {
+        let (_temp_dir, server) = test_server().await;
+
+        let response = server
+            .handle_request(tool_call(
+                "score_cvss_vector",
+                json!({ "vector": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" }),
+            ))
+            .await;
+        let text = response.result.unwrap()["content"][0]["text"].as_str().unwrap().to_string();
+        let result: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(result["base_score"], 9.8);
+        assert_eq!(result["severity"], "Critical");
+
+        let response = server
+            .handle_request(tool_call("score_cvss_vector", json!({ "vector": "not-a-vector" })))
+            .await;
+        assert_eq!(response.error.unwrap().code, -32602);
+    }
+}
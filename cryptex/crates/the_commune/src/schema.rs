@@ -0,0 +1,302 @@
+//! Tool schemas for The Commune
+//!
+//! The `inputSchema` of every tool lives here so that `tools/list` and argument
+//! validation in `tools/call` share one definition.
+
+use serde_json::{json, Value};
+
+/// All tool definitions advertised by the MCP server
+pub fn tool_definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "assess_vulnerability",
+            "description": "Assess a CVE vulnerability with comprehensive CVSS, KEV, EPSS, and AI-enhanced scoring",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "cve_id": {
+                        "type": "string",
+                        "description": "CVE identifier (e.g., CVE-2021-44228)"
+                    }
+                },
+                "required": ["cve_id"]
+            }
+        }),
+        json!({
+            "name": "score_cvss_vector",
+            "description": "Compute the base score and severity of a CVSS vector without a CVE lookup",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "vector": {
+                        "type": "string",
+                        "description": "CVSS vector string (e.g., CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H)"
+                    },
+                    "cve_id": {
+                        "type": "string",
+                        "description": "Optional CVE identifier to label the result"
+                    }
+                },
+                "required": ["vector"]
+            }
+        }),
+        json!({
+            "name": "start_scan",
+            "description": "Start a new vulnerability scan on a target",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "target": {
+                        "type": "string",
+                        "description": "Scan target (IP, CIDR, or hostname)"
+                    }
+                },
+                "required": ["target"]
+            }
+        }),
+        json!({
+            "name": "end_scan",
+            "description": "End a scan and generate comprehensive report",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "scan_id": {
+                        "type": "string",
+                        "description": "Scan identifier"
+                    }
+                },
+                "required": ["scan_id"]
+            }
+        }),
+        json!({
+            "name": "generate_report",
+            "description": "Generate a vulnerability assessment report in various formats",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "scan_id": {
+                        "type": "string",
+                        "description": "Scan identifier"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["json", "html", "markdown", "text", "csv"],
+                        "description": "Report format"
+                    }
+                },
+                "required": ["scan_id", "format"]
+            }
+        }),
+        json!({
+            "name": "get_executive_summary",
+            "description": "Generate executive-level vulnerability summary",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "scan_id": {
+                        "type": "string",
+                        "description": "Scan identifier"
+                    }
+                },
+                "required": ["scan_id"]
+            }
+        }),
+        json!({
+            "name": "export_findings",
+            "description": "Export archived findings matching filters as JSON, CSV, or SARIF",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "filters": {
+                        "type": "object",
+                        "description": "Optional filters: scan_id, severity, min_severity, min_confidence, kev_only, host, cve_id, limit (at most 1000 rows)",
+                        "properties": {
+                            "scan_id": { "type": "string" },
+                            "severity": { "type": "string" },
+                            "min_severity": { "type": "string" },
+                            "min_confidence": { "type": "string", "enum": ["low", "medium", "high"] },
+                            "kev_only": { "type": "boolean" },
+                            "host": { "type": "string" },
+                            "cve_id": { "type": "string" },
+                            "limit": { "type": "integer", "minimum": 1, "maximum": 1000 }
+                        }
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["json", "ndjson", "csv", "sarif"],
+                        "description": "Export format"
+                    }
+                },
+                "required": ["format"]
+            }
+        }),
+        json!({
+            "name": "get_archive_stats",
+            "description": "Report how many scans, assessed vulnerabilities, and findings the REST server's archive (CRYPTEX_DB_PATH) holds",
+            "inputSchema": {
+                "type": "object",
+                "properties": {}
+            }
+        }),
+    ]
+}
+
+/// Look up the input schema for a tool by name
+pub fn input_schema(tool_name: &str) -> Option<Value> {
+    tool_definitions()
+        .into_iter()
+        .find(|tool| tool["name"] == tool_name)
+        .map(|tool| tool["inputSchema"].clone())
+}
+
+/// A single argument that failed validation
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    pub field: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' {}", self.field, self.reason)
+    }
+}
+
+/// Validate tool arguments against an input schema
+///
+/// Supports the subset of JSON Schema the tool definitions use: an object with
+/// `required` fields and per-property `type`, `enum`, `minimum` and `maximum`.
+pub fn validate_arguments(schema: &Value, args: &Value) -> Result<(), SchemaViolation> {
+    let object = match args {
+        Value::Object(map) => map,
+        Value::Null => &serde_json::Map::new(),
+        _ => {
+            return Err(SchemaViolation {
+                field: "arguments".to_string(),
+                reason: format!("must be an object, got {}", type_name(args)),
+            })
+        }
+    };
+
+    if let Some(required) = schema["required"].as_array() {
+        for field in required.iter().filter_map(Value::as_str) {
+            if object.get(field).is_none_or(Value::is_null) {
+                return Err(SchemaViolation {
+                    field: field.to_string(),
+                    reason: "is required".to_string(),
+                });
+            }
+        }
+    }
+
+    let Some(properties) = schema["properties"].as_object() else {
+        return Ok(());
+    };
+
+    for (field, value) in object {
+        let Some(property) = properties.get(field) else {
+            continue;
+        };
+
+        if value.is_null() {
+            continue;
+        }
+
+        if let Some(expected) = property["type"].as_str() {
+            if !matches_type(expected, value) {
+                return Err(SchemaViolation {
+                    field: field.clone(),
+                    reason: format!("must be of type {}, got {}", expected, type_name(value)),
+                });
+            }
+        }
+
+        if let Some(allowed) = property["enum"].as_array() {
+            if !allowed.contains(value) {
+                let options: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+                return Err(SchemaViolation {
+                    field: field.clone(),
+                    reason: format!("must be one of {}", options.join(", ")),
+                });
+            }
+        }
+
+        if let Some(number) = value.as_f64() {
+            if let Some(minimum) = property["minimum"].as_f64() {
+                if number < minimum {
+                    return Err(SchemaViolation {
+                        field: field.clone(),
+                        reason: format!("must be at least {}", minimum),
+                    });
+                }
+            }
+            if let Some(maximum) = property["maximum"].as_f64() {
+                if number > maximum {
+                    return Err(SchemaViolation {
+                        field: field.clone(),
+                        reason: format!("must be at most {}", maximum),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_tool_has_schema() {
+        for tool in tool_definitions() {
+            let name = tool["name"].as_str().unwrap();
+            assert!(input_schema(name).is_some(), "{} has no schema", name);
+        }
+        assert!(input_schema("no_such_tool").is_none());
+    }
+
+    #[test]
+    fn test_validate_arguments() {
+        let schema = input_schema("generate_report").unwrap();
+
+        assert!(validate_arguments(&schema, &json!({"scan_id": "s1", "format": "html"})).is_ok());
+
+        let missing = validate_arguments(&schema, &json!({"scan_id": "s1"})).unwrap_err();
+        assert_eq!(missing.field, "format");
+
+        let wrong_type = validate_arguments(&schema, &json!({"scan_id": 7, "format": "html"})).unwrap_err();
+        assert_eq!(wrong_type.field, "scan_id");
+        assert_eq!(wrong_type.reason, "must be of type string, got number");
+
+        let bad_enum = validate_arguments(&schema, &json!({"scan_id": "s1", "format": "pdf"})).unwrap_err();
+        assert_eq!(bad_enum.field, "format");
+
+        let not_object = validate_arguments(&schema, &json!("s1")).unwrap_err();
+        assert_eq!(not_object.field, "arguments");
+    }
+}
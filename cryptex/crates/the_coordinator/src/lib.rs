@@ -11,9 +11,13 @@
 //! Configuration is consensus - not control. The Charter represents agreed-upon
 //! rules of engagement, not imposed restrictions.
 
+use chrono::NaiveDate;
 use config::{Config, ConfigError, Environment, File};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
+use the_foundation::{CryptexError, CryptexResult, HttpSettings, TaskType};
 
 /// The Charter - Complete configuration for CRYPTEX
 ///
@@ -39,15 +43,70 @@ pub struct TheCharter {
     #[serde(default)]
     pub infiltrator: Option<InfiltratorConfig>,
 
+    /// HTTP connection settings shared by all agitators
+    #[serde(default)]
+    pub connection: ConnectionConfig,
+
     /// Cache threshold for storing AI replies
     #[serde(default = "default_cache_threshold")]
     pub cache_threshold: f64,
+
+    /// Cache threshold per task type, e.g. `threat_modeling = 0.5`
+    ///
+    /// Tasks without an entry use `cache_threshold`. Replies never reach a
+    /// threshold above 1.0, so one disables caching for its task.
+    #[serde(default)]
+    pub cache_thresholds: HashMap<TaskType, f64>,
+
+    /// Smoothing factor for per-agitator latency averages (0.0 exclusive to 1.0)
+    ///
+    /// Higher values track recent requests more closely.
+    #[serde(default = "default_latency_ema_alpha")]
+    pub latency_ema_alpha: f64,
+
+    /// System prompt per task type, e.g. `threat_modeling = "..."`
+    ///
+    /// Tasks without an entry use the default security-analyst prompt.
+    #[serde(default)]
+    pub prompts: HashMap<TaskType, String>,
+
+    /// Periodic snapshots of the collective's metrics; off when unset
+    #[serde(default)]
+    pub metrics_history: Option<MetricsHistoryConfig>,
+
+    /// Defaults for generated reports
+    #[serde(default)]
+    pub report: ReportDefaultsConfig,
+
+    /// Vulnerability assessment settings
+    #[serde(default)]
+    pub assessor: AssessorConfig,
+
+    /// Limits on incoming REST API requests
+    #[serde(default)]
+    pub http: HttpServerConfig,
+
+    /// Bucket that reports are uploaded to; presigned URLs only when unset
+    #[serde(default)]
+    pub object_storage: Option<ObjectStorageConfig>,
+
+    /// Alerts for severe findings as scans run
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// Credentials masked in logs and error messages, beyond the built-in ones
+    #[serde(default)]
+    pub redaction: RedactionConfig,
 }
 
 fn default_cache_threshold() -> f64 {
     0.8
 }
 
+fn default_latency_ema_alpha() -> f64 {
+    0.2
+}
+
 /// Commune Configuration
 ///
 /// Traditional name: `ServerConfig`
@@ -68,6 +127,18 @@ pub struct CommuneConfig {
     /// WebSocket port
     #[serde(default = "default_ws_port")]
     pub ws_port: u16,
+
+    /// URL that receives the scan report when a scan completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_webhook_url: Option<String>,
+
+    /// Secret used to sign completion webhook payloads (HMAC-SHA256)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_webhook_secret: Option<String>,
+
+    /// Reports rendered at once on blocking threads; further requests queue
+    #[serde(default = "default_report_concurrency")]
+    pub report_concurrency: usize,
 }
 
 fn default_commune_name() -> String {
@@ -86,6 +157,10 @@ fn default_ws_port() -> u16 {
     9090
 }
 
+fn default_report_concurrency() -> usize {
+    4
+}
+
 /// Agitator Configuration
 ///
 /// Traditional name: `AIProviderConfig`
@@ -113,9 +188,17 @@ pub struct AgitatorConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
 
-    /// Request timeout in seconds
+    /// Request (read) timeout in seconds
     #[serde(default = "default_timeout")]
     pub timeout_seconds: u64,
+
+    /// Sampling temperature (provider default when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Maximum tokens in a reply (provider default when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
 }
 
 fn default_true() -> bool {
@@ -126,22 +209,91 @@ fn default_timeout() -> u64 {
     30
 }
 
+/// Connection Configuration
+///
+/// Traditional name: `HttpClientConfig`
+///
+/// All agitators share one HTTP client built from these settings, so they
+/// reuse a single connection pool. Per-agitator `timeout_seconds` still bounds
+/// each request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionConfig {
+    /// Time allowed to establish a connection, in seconds
+    #[serde(default = "default_connect_timeout")]
+    pub connect_timeout_seconds: u64,
+
+    /// How long idle pooled connections are kept, in seconds
+    #[serde(default = "default_pool_idle_timeout")]
+    pub pool_idle_timeout_seconds: u64,
+
+    /// Maximum idle connections kept per host
+    #[serde(default = "default_pool_max_idle_per_host")]
+    pub pool_max_idle_per_host: usize,
+
+    /// Proxy for all outbound traffic, e.g. `http://proxy.corp:3128`
+    ///
+    /// Unset leaves the standard `HTTPS_PROXY`/`NO_PROXY` variables in charge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_seconds: default_connect_timeout(),
+            pool_idle_timeout_seconds: default_pool_idle_timeout(),
+            pool_max_idle_per_host: default_pool_max_idle_per_host(),
+            proxy: None,
+        }
+    }
+}
+
+impl ConnectionConfig {
+    /// Settings for `the_foundation::build_http_client`
+    pub fn http_settings(&self) -> HttpSettings {
+        HttpSettings {
+            connect_timeout: Duration::from_secs(self.connect_timeout_seconds),
+            pool_idle_timeout: Duration::from_secs(self.pool_idle_timeout_seconds),
+            pool_max_idle_per_host: self.pool_max_idle_per_host,
+            proxy: self.proxy.clone(),
+        }
+    }
+}
+
+fn default_connect_timeout() -> u64 {
+    10
+}
+
+fn default_pool_idle_timeout() -> u64 {
+    90
+}
+
+fn default_pool_max_idle_per_host() -> usize {
+    32
+}
+
 /// Archive Configuration
 ///
 /// Traditional name: `DatabaseConfig`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArchiveConfig {
-    /// Path to the redb database file
+    /// Path to the redb database file, used as given
+    ///
+    /// Defaults to `cryptex.db` in the platform data directory.
     #[serde(default = "default_archive_path")]
     pub path: PathBuf,
 
     /// Maximum database size in GB
     #[serde(default = "default_max_size_gb")]
     pub max_size_gb: u64,
+
+    /// Days to keep scans and their results; unset keeps them forever
+    #[serde(default)]
+    pub retain_days: Option<u64>,
 }
 
 fn default_archive_path() -> PathBuf {
-    PathBuf::from("./cryptex-archive.redb")
+    the_foundation::platform::get_default_db_path()
 }
 
 fn default_max_size_gb() -> u64 {
@@ -170,6 +322,268 @@ fn default_ttl_seconds() -> u64 {
     3600
 }
 
+/// Metrics History Configuration
+///
+/// Traditional name: `MetricsSamplingConfig`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsHistoryConfig {
+    /// Seconds between snapshots
+    #[serde(default = "default_metrics_interval")]
+    pub interval_seconds: u64,
+
+    /// Snapshots kept; the oldest is dropped first
+    #[serde(default = "default_metrics_capacity")]
+    pub capacity: usize,
+}
+
+fn default_metrics_interval() -> u64 {
+    60
+}
+
+fn default_metrics_capacity() -> usize {
+    360
+}
+
+/// Report Defaults Configuration
+///
+/// Traditional name: `ReportConfig`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDefaultsConfig {
+    /// `technical`, `executive` or `compliance`
+    #[serde(default = "default_report_audience")]
+    pub audience: String,
+
+    /// Sections rendered unless a request names its own
+    #[serde(default = "default_report_sections")]
+    pub sections: Vec<String>,
+}
+
+impl Default for ReportDefaultsConfig {
+    fn default() -> Self {
+        Self {
+            audience: default_report_audience(),
+            sections: default_report_sections(),
+        }
+    }
+}
+
+/// Report audiences the propagandist knows
+const REPORT_AUDIENCES: [&str; 3] = ["technical", "executive", "compliance"];
+
+/// Report sections the propagandist knows
+const REPORT_SECTIONS: [&str; 5] = ["overview", "stats", "top", "hosts", "remediation"];
+
+fn default_report_audience() -> String {
+    "technical".to_string()
+}
+
+fn default_report_sections() -> Vec<String> {
+    REPORT_SECTIONS.iter().map(|name| name.to_string()).collect()
+}
+
+/// Assessor Configuration
+///
+/// Traditional name: `ScoringConfig`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssessorConfig {
+    /// Load the KEV catalog in the background at start-up
+    #[serde(default)]
+    pub warm_up: bool,
+
+    /// CVEs to assess during warm-up so their first lookup is a cache hit
+    #[serde(default)]
+    pub watchlist: Vec<String>,
+
+    /// Assessments allowed to query external sources at once, process-wide
+    #[serde(default = "default_max_concurrent_fetches")]
+    pub max_concurrent_fetches: usize,
+}
+
+impl Default for AssessorConfig {
+    fn default() -> Self {
+        Self {
+            warm_up: false,
+            watchlist: Vec::new(),
+            max_concurrent_fetches: default_max_concurrent_fetches(),
+        }
+    }
+}
+
+fn default_max_concurrent_fetches() -> usize {
+    8
+}
+
+/// HTTP Server Configuration
+///
+/// Traditional name: `RequestLimits`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpServerConfig {
+    /// Largest request body accepted; bigger bodies get `413 Payload Too Large`
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+
+    /// Largest OpenVAS report accepted by `POST /api/v1/import/openvas`
+    #[serde(default = "default_max_import_bytes")]
+    pub max_import_bytes: usize,
+
+    /// Time allowed to answer a request before it gets `408 Request Timeout`
+    #[serde(default = "default_request_timeout_seconds")]
+    pub request_timeout_seconds: u64,
+
+    /// Routes answered with `Deprecation` (and `Sunset`) headers, as `path`
+    /// or `path@YYYY-MM-DD`, e.g. `/api/v1/activity@2027-01-31`
+    #[serde(default)]
+    pub deprecated_routes: Vec<String>,
+}
+
+impl Default for HttpServerConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: default_max_body_bytes(),
+            max_import_bytes: default_max_import_bytes(),
+            request_timeout_seconds: default_request_timeout_seconds(),
+            deprecated_routes: Vec::new(),
+        }
+    }
+}
+
+fn default_max_body_bytes() -> usize {
+    4 * 1024 * 1024
+}
+
+fn default_max_import_bytes() -> usize {
+    64 * 1024 * 1024
+}
+
+fn default_request_timeout_seconds() -> u64 {
+    30
+}
+
+/// A route marked deprecated
+///
+/// Traditional name: `DeprecationNotice`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeprecatedRoute {
+    /// Route pattern, e.g. `/api/v1/activity`
+    pub path: String,
+    /// Date the route is due to be removed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<NaiveDate>,
+}
+
+impl DeprecatedRoute {
+    /// Parse a `path` or `path@YYYY-MM-DD` entry
+    pub fn parse(entry: &str) -> CryptexResult<Self> {
+        let entry = entry.trim();
+        let (path, sunset) = match entry.split_once('@') {
+            Some((path, date)) => {
+                let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+                    CryptexError::validation(format!(
+                        "Sunset date of deprecated route '{}' must be YYYY-MM-DD",
+                        entry
+                    ))
+                })?;
+                (path.trim(), Some(date))
+            }
+            None => (entry, None),
+        };
+        if !path.starts_with('/') {
+            return Err(CryptexError::validation(format!(
+                "Deprecated route '{}' must be a path starting with '/'",
+                entry
+            )));
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            sunset,
+        })
+    }
+}
+
+/// Object Storage Configuration
+///
+/// Traditional name: `S3Config`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectStorageConfig {
+    /// S3-compatible endpoint, e.g. `https://s3.amazonaws.com`
+    pub endpoint: String,
+
+    /// Bucket that object keys name objects in
+    pub bucket: String,
+
+    /// Region the upload is signed for
+    #[serde(default = "default_object_storage_region")]
+    pub region: String,
+
+    pub access_key_id: String,
+
+    pub secret_access_key: String,
+
+    /// Hosts besides the endpoint that presigned upload URLs may point at,
+    /// as `host` or `host:port`
+    #[serde(default)]
+    pub presigned_hosts: Vec<String>,
+}
+
+fn default_object_storage_region() -> String {
+    "us-east-1".to_string()
+}
+
+/// Notifications Configuration
+///
+/// Traditional name: `AlertingConfig`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Least severe finding that alerts: none, low, medium, high or critical
+    #[serde(default = "default_notification_min_severity")]
+    pub min_severity: String,
+
+    /// Alert on known-exploited (KEV) findings whatever their severity
+    #[serde(default = "default_true")]
+    pub notify_kev: bool,
+
+    /// Slack incoming webhook alerts are posted to
+    #[serde(default)]
+    pub slack_webhook_url: Option<String>,
+
+    /// URL alerts are POSTed to as JSON
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            min_severity: default_notification_min_severity(),
+            notify_kev: true,
+            slack_webhook_url: None,
+            webhook_url: None,
+        }
+    }
+}
+
+/// Severities a notification threshold can name
+const SEVERITIES: [&str; 5] = ["none", "low", "medium", "high", "critical"];
+
+fn default_notification_min_severity() -> String {
+    "critical".to_string()
+}
+
+/// Redaction Configuration
+///
+/// Traditional name: `SecretMaskingConfig`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    /// More headers recorded as `[REDACTED]`, e.g. `x-vault-token`
+    #[serde(default)]
+    pub headers: Vec<String>,
+
+    /// More key prefixes masked in free text, e.g. `ghp_` for GitHub tokens
+    #[serde(default)]
+    pub key_prefixes: Vec<String>,
+}
+
 /// Infiltrator Configuration
 ///
 /// Traditional name: `ScannerConfig`
@@ -181,13 +595,75 @@ pub struct InfiltratorConfig {
     /// Maximum concurrent scans
     #[serde(default = "default_max_concurrent_scans")]
     pub max_concurrent_scans: usize,
+
+    /// `manual` (scanners report findings to CRYPTEX) or `live` (CRYPTEX
+    /// drives gvmd over GMP and pulls results itself)
+    #[serde(default = "default_scanner_mode")]
+    pub scanner_mode: String,
+
+    /// gvmd host for live mode
+    #[serde(default)]
+    pub scanner_host: Option<String>,
+
+    /// gvmd GMP port for live mode
+    #[serde(default = "default_scanner_port")]
+    pub scanner_port: u16,
+
+    /// gvmd Unix socket; takes precedence over host and port when set
+    #[serde(default)]
+    pub scanner_socket: Option<PathBuf>,
+
+    /// GMP username for live mode
+    #[serde(default)]
+    pub scanner_username: Option<String>,
+
+    /// GMP password for live mode; prefer setting it through the environment
+    #[serde(default)]
+    pub scanner_password: Option<String>,
+
+    /// Checkpoint a running scan after this many new results
+    #[serde(default = "default_commit_every")]
+    pub commit_every: usize,
+
+    /// Also checkpoint once this many seconds pass with uncommitted results
+    #[serde(default)]
+    pub commit_interval_seconds: Option<u64>,
 }
 
 fn default_max_concurrent_scans() -> usize {
     3
 }
 
+fn default_scanner_mode() -> String {
+    "manual".to_string()
+}
+
+fn default_scanner_port() -> u16 {
+    9390
+}
+
+fn default_commit_every() -> usize {
+    10
+}
+
 impl TheCharter {
+    /// Certainty a reply to `task` needs before it is cached
+    pub fn cache_threshold_for(&self, task: TaskType) -> f64 {
+        self.cache_thresholds
+            .get(&task)
+            .copied()
+            .unwrap_or(self.cache_threshold)
+    }
+
+    /// The built-in defaults, without reading any file or the environment
+    pub fn builtin() -> Self {
+        Config::builder()
+            .add_source(File::from_str(DEFAULT_CONFIG, config::FileFormat::Toml))
+            .build()
+            .and_then(Config::try_deserialize)
+            .expect("built-in charter deserializes")
+    }
+
     /// The Charter Loading - Load configuration from file and environment
     ///
     /// Traditional name: `load_config` or `load_configuration`
@@ -262,6 +738,130 @@ impl TheCharter {
             }
         }
 
+        if let Some(history) = &self.metrics_history {
+            if history.interval_seconds == 0 || history.capacity == 0 {
+                return Err("metrics_history interval_seconds and capacity must be positive".to_string());
+            }
+        }
+
+        if self.commune.report_concurrency == 0 {
+            return Err("commune report_concurrency must be positive".to_string());
+        }
+
+        if !REPORT_AUDIENCES.contains(&self.report.audience.to_ascii_lowercase().as_str()) {
+            return Err(format!("Unknown report audience '{}'", self.report.audience));
+        }
+        if let Some(section) = self
+            .report
+            .sections
+            .iter()
+            .find(|name| !REPORT_SECTIONS.contains(&name.to_ascii_lowercase().as_str()))
+        {
+            return Err(format!("Unknown report section '{}'", section));
+        }
+        if self.report.sections.is_empty() {
+            return Err("report sections must name at least one section".to_string());
+        }
+
+        if let Some(cve_id) = self
+            .assessor
+            .watchlist
+            .iter()
+            .find(|cve_id| !cve_id.to_ascii_uppercase().starts_with("CVE-"))
+        {
+            return Err(format!("Watchlist entry '{}' is not a CVE ID", cve_id));
+        }
+        if self.assessor.max_concurrent_fetches == 0 {
+            return Err("assessor max_concurrent_fetches must be positive".to_string());
+        }
+
+        let http = &self.http;
+        if [http.max_body_bytes, http.max_import_bytes].contains(&0)
+            || http.request_timeout_seconds == 0
+        {
+            return Err(
+                "http max_body_bytes, max_import_bytes and request_timeout_seconds must be positive"
+                    .to_string(),
+            );
+        }
+        for entry in &self.http.deprecated_routes {
+            DeprecatedRoute::parse(entry).map_err(|e| e.to_string())?;
+        }
+
+        if let Some(storage) = &self.object_storage {
+            if !storage.endpoint.starts_with("http://") && !storage.endpoint.starts_with("https://") {
+                return Err(format!(
+                    "object_storage endpoint must use http or https, got '{}'",
+                    storage.endpoint
+                ));
+            }
+            if storage.bucket.is_empty() {
+                return Err("object_storage bucket must not be empty".to_string());
+            }
+            let malformed =
+                storage.presigned_hosts.iter().find(|h| h.is_empty() || h.contains('/'));
+            if let Some(host) = malformed {
+                return Err(format!(
+                    "object_storage presigned_hosts entries must be a host or host:port, got '{}'",
+                    host
+                ));
+            }
+        }
+
+        let notifications = &self.notifications;
+        if !SEVERITIES.contains(&notifications.min_severity.to_ascii_lowercase().as_str()) {
+            return Err(format!(
+                "Unknown notifications min_severity '{}'",
+                notifications.min_severity
+            ));
+        }
+        if let Some(url) = [&notifications.slack_webhook_url, &notifications.webhook_url]
+            .into_iter()
+            .flatten()
+            .find(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+        {
+            return Err(format!("Notification URL must use http or https, got '{}'", url));
+        }
+
+        let redaction = &self.redaction;
+        if let Some(header) = redaction.headers.iter().find(|h| {
+            h.is_empty() || !h.bytes().all(|b| b.is_ascii_alphanumeric() || b"-_.".contains(&b))
+        }) {
+            return Err(format!("redaction headers must be header names, got '{}'", header));
+        }
+        if let Some(prefix) = redaction.key_prefixes.iter().find(|p| p.trim().len() < 2) {
+            return Err(format!(
+                "redaction key_prefixes must be at least 2 characters, got '{}'",
+                prefix
+            ));
+        }
+
+        if let Some(infiltrator) = &self.infiltrator {
+            if infiltrator.commit_every == 0 || infiltrator.commit_interval_seconds == Some(0) {
+                return Err(
+                    "infiltrator commit_every and commit_interval_seconds must be positive".to_string(),
+                );
+            }
+        }
+
+        if let Some((task, threshold)) = self
+            .cache_thresholds
+            .iter()
+            .find(|(_, threshold)| threshold.is_nan() || **threshold < 0.0)
+        {
+            return Err(format!(
+                "cache_thresholds entry for {:?} must not be negative, got {}",
+                task, threshold
+            ));
+        }
+
+        if !(self.latency_ema_alpha > 0.0 && self.latency_ema_alpha <= 1.0) {
+            return Err(format!(
+                "latency_ema_alpha must be in (0.0, 1.0], got {}",
+                self.latency_ema_alpha
+            ));
+        }
+
         Ok(())
     }
 }
@@ -282,7 +882,6 @@ port = 8080
 ws_port = 9090
 
 [archive]
-path = "./cryptex-archive.redb"
 max_size_gb = 10
 
 [memory]
@@ -303,6 +902,14 @@ mod tests {
         assert!(charter.is_ok() || charter.is_err());
     }
 
+    #[test]
+    fn test_builtin_charter_is_valid() {
+        let charter = TheCharter::builtin();
+        assert_eq!(charter.commune.port, 8080);
+        assert!(charter.agitators.is_empty());
+        assert!(charter.validate().is_ok());
+    }
+
     #[test]
     fn test_commune_config_defaults() {
         let commune = CommuneConfig {
@@ -310,10 +917,245 @@ mod tests {
             host: default_host(),
             port: default_port(),
             ws_port: default_ws_port(),
+            completion_webhook_url: None,
+            completion_webhook_secret: None,
+            report_concurrency: default_report_concurrency(),
         };
 
         assert_eq!(commune.port, 8080);
         assert_eq!(commune.ws_port, 9090);
         assert_eq!(commune.host, "127.0.0.1");
     }
+
+    #[test]
+    fn test_agitator_sampling_config() {
+        let config: AgitatorConfig = serde_json::from_str(
+            r#"{"name": "openai", "agitator_type": "openai", "temperature": 0.0, "max_tokens": 2048}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.temperature, Some(0.0));
+        assert_eq!(config.max_tokens, Some(2048));
+
+        let config: AgitatorConfig =
+            serde_json::from_str(r#"{"name": "claude", "agitator_type": "claude"}"#).unwrap();
+
+        assert!(config.temperature.is_none());
+        assert!(config.max_tokens.is_none());
+    }
+
+    #[test]
+    fn test_connection_config_defaults() {
+        let config: ConnectionConfig =
+            serde_json::from_str(r#"{"connect_timeout_seconds": 3}"#).unwrap();
+
+        assert_eq!(config.connect_timeout_seconds, 3);
+        assert_eq!(config.pool_idle_timeout_seconds, 90);
+        assert_eq!(config.pool_max_idle_per_host, 32);
+        assert_eq!(config.http_settings().proxy, None);
+
+        let config: ConnectionConfig =
+            serde_json::from_str(r#"{"proxy": "http://proxy.corp:3128"}"#).unwrap();
+        assert_eq!(config.http_settings().proxy.as_deref(), Some("http://proxy.corp:3128"));
+    }
+
+    #[test]
+    fn test_archive_path_defaults_to_platform_data_dir() {
+        let config: ArchiveConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.path, the_foundation::platform::get_default_db_path());
+
+        let config: ArchiveConfig =
+            serde_json::from_str(r#"{"path": "data/archive.redb"}"#).unwrap();
+        assert_eq!(config.path, PathBuf::from("data/archive.redb"));
+    }
+
+    #[test]
+    fn test_latency_ema_alpha_validation() {
+        let mut charter: TheCharter = serde_json::from_str(
+            r#"{"commune": {}, "archive": {}, "memory": {"max_size_mb": 16, "ttl_seconds": 60}}"#,
+        )
+        .unwrap();
+        assert_eq!(charter.latency_ema_alpha, 0.2);
+        assert!(charter.validate().is_ok());
+
+        charter.latency_ema_alpha = 0.0;
+        assert!(charter.validate().is_err());
+        charter.latency_ema_alpha = 1.5;
+        assert!(charter.validate().is_err());
+    }
+
+    #[test]
+    fn test_report_defaults_validation() {
+        let mut charter: TheCharter = serde_json::from_str(
+            r#"{"commune": {}, "archive": {}, "memory": {"max_size_mb": 16, "ttl_seconds": 60},
+                "report": {"audience": "Executive"}}"#,
+        )
+        .unwrap();
+        assert_eq!(charter.report.sections.len(), 5);
+        assert!(charter.validate().is_ok());
+
+        charter.report.sections = vec!["overview".to_string(), "appendix".to_string()];
+        assert!(charter.validate().is_err());
+        charter.report.sections.clear();
+        assert!(charter.validate().is_err());
+        charter.report = ReportDefaultsConfig {
+            audience: "board".to_string(),
+            ..Default::default()
+        };
+        assert!(charter.validate().is_err());
+    }
+
+    #[test]
+    fn test_assessor_watchlist_validation() {
+        let mut charter: TheCharter = serde_json::from_str(
+            r#"{"commune": {}, "archive": {}, "memory": {"max_size_mb": 16, "ttl_seconds": 60},
+                "assessor": {"warm_up": true, "watchlist": ["CVE-2021-44228"]}}"#,
+        )
+        .unwrap();
+        assert!(charter.assessor.warm_up);
+        assert!(charter.validate().is_ok());
+
+        assert_eq!(charter.assessor.max_concurrent_fetches, 8);
+
+        charter.assessor.watchlist.push("log4shell".to_string());
+        assert!(charter.validate().is_err());
+        charter.assessor.watchlist.pop();
+        charter.assessor.max_concurrent_fetches = 0;
+        assert!(charter.validate().is_err());
+    }
+
+    #[test]
+    fn test_http_limits_validation() {
+        let mut charter: TheCharter = serde_json::from_str(
+            r#"{"commune": {}, "archive": {}, "memory": {"max_size_mb": 16, "ttl_seconds": 60},
+                "http": {"max_body_bytes": 65536}}"#,
+        )
+        .unwrap();
+        assert_eq!(charter.http.max_body_bytes, 65536);
+        assert_eq!(charter.http.request_timeout_seconds, 30);
+        assert_eq!(charter.http.max_import_bytes, 64 * 1024 * 1024);
+        assert!(charter.validate().is_ok());
+
+        charter.http.max_import_bytes = 0;
+        assert!(charter.validate().is_err());
+        charter.http.max_import_bytes = 1024;
+
+        charter.http.request_timeout_seconds = 0;
+        assert!(charter.validate().is_err());
+        charter.http.request_timeout_seconds = 30;
+
+        charter.http.deprecated_routes = vec![
+            "/api/v1/activity".to_string(),
+            "/api/v1/audit@2027-01-31".to_string(),
+        ];
+        assert!(charter.validate().is_ok());
+        charter.http.deprecated_routes.push("/api/v1/jobs/:job_id@soon".to_string());
+        assert!(charter.validate().is_err());
+
+        // Date-shaped is not enough; start-up would refuse it
+        charter.http.deprecated_routes.pop();
+        charter.http.deprecated_routes.push("/api/v1/jobs/:job_id@2027-13-45".to_string());
+        assert!(charter.validate().is_err());
+    }
+
+    #[test]
+    fn test_object_storage_validation() {
+        let mut charter: TheCharter = serde_json::from_str(
+            r#"{"commune": {}, "archive": {}, "memory": {"max_size_mb": 16, "ttl_seconds": 60},
+                "object_storage": {"endpoint": "https://s3.example.com", "bucket": "reports",
+                                   "access_key_id": "id", "secret_access_key": "secret"}}"#,
+        )
+        .unwrap();
+        let storage = charter.object_storage.as_mut().unwrap();
+        assert_eq!(storage.region, "us-east-1");
+        assert!(charter.validate().is_ok());
+
+        let storage = charter.object_storage.as_mut().unwrap();
+        storage.presigned_hosts = vec!["https://cdn.example.com".to_string()];
+        assert!(charter.validate().is_err());
+
+        let storage = charter.object_storage.as_mut().unwrap();
+        storage.presigned_hosts = vec!["cdn.example.com:9000".to_string()];
+        assert!(charter.validate().is_ok());
+        charter.object_storage.as_mut().unwrap().endpoint = "s3.example.com".to_string();
+        assert!(charter.validate().is_err());
+    }
+
+    #[test]
+    fn test_notifications_validation() {
+        let mut charter: TheCharter = serde_json::from_str(
+            r#"{"commune": {}, "archive": {}, "memory": {"max_size_mb": 16, "ttl_seconds": 60},
+                "notifications": {"min_severity": "High",
+                                  "slack_webhook_url": "https://hooks.slack.com/services/T/B/x"}}"#,
+        )
+        .unwrap();
+        assert!(charter.notifications.notify_kev);
+        assert!(charter.validate().is_ok());
+
+        charter.notifications.min_severity = "severe".to_string();
+        assert!(charter.validate().is_err());
+        charter.notifications.min_severity = "critical".to_string();
+
+        charter.notifications.webhook_url = Some("alerts.example.com/hook".to_string());
+        assert!(charter.validate().is_err());
+    }
+
+    #[test]
+    fn test_redaction_validation() {
+        let mut charter: TheCharter = serde_json::from_str(
+            r#"{"commune": {}, "archive": {}, "memory": {"max_size_mb": 16, "ttl_seconds": 60},
+                "redaction": {"headers": ["X-Vault-Token"], "key_prefixes": ["ghp_"]}}"#,
+        )
+        .unwrap();
+        assert!(charter.validate().is_ok());
+
+        charter.redaction.headers.push("x-vault token".to_string());
+        assert!(charter.validate().is_err());
+        charter.redaction.headers.pop();
+
+        charter.redaction.key_prefixes.push("g".to_string());
+        assert!(charter.validate().is_err());
+    }
+
+    #[test]
+    fn test_cache_thresholds_by_task_type() {
+        let toml = format!(
+            "{}\n[cache_thresholds]\nthreat_modeling = 0.5\nscan_optimization = 1.1\n",
+            DEFAULT_CONFIG
+        );
+        let mut charter: TheCharter = Config::builder()
+            .add_source(File::from_str(&toml, config::FileFormat::Toml))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+
+        assert_eq!(charter.cache_threshold_for(TaskType::ThreatModeling), 0.5);
+        assert_eq!(charter.cache_threshold_for(TaskType::ScanOptimization), 1.1);
+        assert_eq!(charter.cache_threshold_for(TaskType::ReportGeneration), 0.8);
+        assert!(charter.validate().is_ok());
+
+        charter.cache_thresholds.insert(TaskType::Custom, -0.1);
+        assert!(charter.validate().is_err());
+    }
+
+    #[test]
+    fn test_prompts_keyed_by_task_type() {
+        let toml = format!(
+            "{}\n[prompts]\nthreat_modeling = \"Map the attack paths.\"\n",
+            DEFAULT_CONFIG
+        );
+        let charter: TheCharter = Config::builder()
+            .add_source(File::from_str(&toml, config::FileFormat::Toml))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+
+        assert_eq!(charter.prompts.len(), 1);
+        assert_eq!(
+            charter.prompts.get(&TaskType::ThreatModeling).map(String::as_str),
+            Some("Map the attack paths.")
+        );
+    }
 }
@@ -46,6 +46,14 @@ pub enum CryptexError {
     #[error("Not found: {0}")]
     NotFound(String),
 
+    /// An upstream provider is throttling requests
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    /// An upstream service answered with an error
+    #[error("Upstream error: {0}")]
+    UpstreamError(String),
+
     /// IO error
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
@@ -98,10 +106,42 @@ impl CryptexError {
         CryptexError::ValidationError(msg.into())
     }
 
+    /// Create a Rate Limited error
+    pub fn rate_limited<S: Into<String>>(msg: S) -> Self {
+        CryptexError::RateLimited(msg.into())
+    }
+
+    /// Create an Upstream error
+    pub fn upstream<S: Into<String>>(msg: S) -> Self {
+        CryptexError::UpstreamError(msg.into())
+    }
+
     /// Create an Unknown error
     pub fn unknown<S: Into<String>>(msg: S) -> Self {
         CryptexError::Unknown(msg.into())
     }
+
+    /// Stable machine-readable code for this kind of error
+    ///
+    /// Clients branch on these, so a code never changes once published;
+    /// the display message is for people and may be reworded.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CryptexError::CharterError(_) => "CONFIGURATION_ERROR",
+            CryptexError::AgitatorError(_) => "AI_PROVIDER_ERROR",
+            CryptexError::NetworkError(_) => "NETWORK_ERROR",
+            CryptexError::SerializationError(_) => "SERIALIZATION_ERROR",
+            CryptexError::ArchiveError(_) => "STORAGE_ERROR",
+            CryptexError::InvalidInquiry(_) => "INVALID_INQUIRY",
+            CryptexError::Timeout => "TIMEOUT",
+            CryptexError::ValidationError(_) => "VALIDATION_FAILED",
+            CryptexError::NotFound(_) => "NOT_FOUND",
+            CryptexError::RateLimited(_) => "RATE_LIMITED",
+            CryptexError::UpstreamError(_) => "UPSTREAM_ERROR",
+            CryptexError::IoError(_) => "IO_ERROR",
+            CryptexError::Unknown(_) => "INTERNAL_ERROR",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -121,6 +161,24 @@ mod tests {
         assert!(matches!(err, CryptexError::AgitatorError(_)));
     }
 
+    #[test]
+    fn test_upstream_errors() {
+        let err = CryptexError::rate_limited("OpenAI returned 429");
+        assert!(matches!(err, CryptexError::RateLimited(_)));
+        assert_eq!(err.to_string(), "Rate limited: OpenAI returned 429");
+
+        let err = CryptexError::upstream("Claude returned 503");
+        assert!(matches!(err, CryptexError::UpstreamError(_)));
+    }
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(CryptexError::not_found("scan").code(), "NOT_FOUND");
+        assert_eq!(CryptexError::validation("bad").code(), "VALIDATION_FAILED");
+        assert_eq!(CryptexError::rate_limited("429").code(), "RATE_LIMITED");
+        assert_eq!(CryptexError::Timeout.code(), "TIMEOUT");
+    }
+
     #[test]
     fn test_result_type() {
         fn returns_result() -> CryptexResult<String> {
@@ -0,0 +1,110 @@
+//! Outbound HTTP clients
+//!
+//! Every client CRYPTEX uses to reach the outside world - AI providers and
+//! vulnerability data sources alike - is built here, so egress settings such
+//! as a corporate proxy apply to all of them at once.
+//!
+//! Without a configured proxy, reqwest honors the standard `HTTP_PROXY`,
+//! `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables. A
+//! configured proxy takes over from those, but hosts in `NO_PROXY` still
+//! bypass it.
+
+use reqwest::{Client, NoProxy, Proxy};
+use std::time::Duration;
+
+use crate::{CryptexError, CryptexResult};
+
+/// Default time allowed to establish a connection
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Default time an idle pooled connection is kept open
+pub const DEFAULT_POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+
+/// Settings shared by every outbound HTTP client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpSettings {
+    pub connect_timeout: Duration,
+    pub pool_idle_timeout: Duration,
+    pub pool_max_idle_per_host: usize,
+    /// Proxy for all outbound traffic, e.g. `http://proxy.corp:3128`
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            pool_idle_timeout: Duration::from_secs(DEFAULT_POOL_IDLE_TIMEOUT_SECS),
+            pool_max_idle_per_host: usize::MAX,
+            proxy: None,
+        }
+    }
+}
+
+impl HttpSettings {
+    /// Send all traffic through `proxy`
+    pub fn with_proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+}
+
+/// Build an outbound HTTP client
+///
+/// Clones of the returned client share one connection pool. Request (read)
+/// timeouts are left to the caller, since they differ per use.
+pub fn build_http_client(settings: &HttpSettings) -> CryptexResult<Client> {
+    let mut builder = Client::builder()
+        .connect_timeout(settings.connect_timeout)
+        .pool_idle_timeout(settings.pool_idle_timeout)
+        .pool_max_idle_per_host(settings.pool_max_idle_per_host);
+
+    if let Some(url) = &settings.proxy {
+        let proxy = Proxy::all(url)
+            .map_err(|e| CryptexError::charter(format!("Invalid proxy URL '{}': {}", url, e)))?
+            .no_proxy(NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+
+    builder
+        .build()
+        .map_err(|e| CryptexError::network(format!("Failed to create HTTP client: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_configured_proxy_carries_requests() {
+        let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let settings =
+            HttpSettings::default().with_proxy(format!("http://{}", proxy.local_addr().unwrap()));
+        let client = build_http_client(&settings).unwrap();
+
+        let request = tokio::spawn(async move {
+            client.get("http://nvd.example.invalid/rest/json/cves/2.0").send().await
+        });
+
+        // The request reaches the proxy in absolute form instead of resolving the host
+        let (mut stream, _) = proxy.accept().await.unwrap();
+        let mut head = vec![0u8; 1024];
+        let read = stream.read(&mut head).await.unwrap();
+        let head = String::from_utf8_lossy(&head[..read]).to_string();
+        stream
+            .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+            .await
+            .unwrap();
+
+        assert!(head.starts_with("GET http://nvd.example.invalid/rest/json/cves/2.0 HTTP/1.1"));
+        assert_eq!(request.await.unwrap().unwrap().status(), 204);
+    }
+
+    #[test]
+    fn test_invalid_proxy_is_rejected() {
+        let settings = HttpSettings::default().with_proxy("not a url");
+        assert!(build_http_client(&settings).is_err());
+    }
+}
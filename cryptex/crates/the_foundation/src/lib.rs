@@ -4,7 +4,9 @@
 //! used across all CRYPTEX crates, breaking circular dependencies.
 
 pub mod error;
+pub mod http;
 pub mod models;
+pub mod redact;
 pub mod traits;
 pub mod platform;
 
@@ -12,8 +14,11 @@ pub mod platform;
 pub use error::{CryptexError, CryptexResult};
 pub use models::{
     AgitatorType, TaskType, TheCertainty, TheInquiry, ThePackage, TheReply,
+    DEFAULT_SYSTEM_PROMPT,
 };
 pub use traits::TheAgitator;
+pub use http::{build_http_client, HttpSettings};
+pub use redact::{init_redaction, is_sensitive_header, redact_secrets, Redactor};
 pub use platform::{
     get_data_dir, get_default_db_path, get_config_dir, get_cache_dir,
     ensure_dir_exists, get_platform, get_arch, is_windows, is_macos, is_linux,
@@ -109,6 +109,9 @@ pub struct TheReply {
     pub replied_at_ts: u64,
 }
 
+/// System prompt an agitator uses when a package carries no context
+pub const DEFAULT_SYSTEM_PROMPT: &str = "You are a security expert assistant helping to analyze vulnerabilities and security issues. Provide accurate, detailed, and actionable security analysis.";
+
 /// The Package - Data payload for inquiries
 ///
 /// Traditional name: `Payload` or `RequestData`
@@ -164,6 +167,9 @@ pub enum AgitatorType {
     /// Anthropic Claude
     Claude,
 
+    /// Google Gemini
+    Gemini,
+
     /// Automatic selection based on availability/health
     Auto,
 }
@@ -173,6 +179,7 @@ impl std::fmt::Display for AgitatorType {
         match self {
             AgitatorType::OpenAI => write!(f, "OpenAI"),
             AgitatorType::Claude => write!(f, "Claude"),
+            AgitatorType::Gemini => write!(f, "Gemini"),
             AgitatorType::Auto => write!(f, "Auto"),
         }
     }
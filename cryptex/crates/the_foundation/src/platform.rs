@@ -52,6 +52,19 @@ pub fn get_default_db_path() -> PathBuf {
     get_data_dir().join("cryptex.db")
 }
 
+/// Get the database path for the MCP server
+///
+/// redb locks a database file exclusively, so the MCP server cannot share the
+/// REST server's archive. It uses `CRYPTEX_MCP_DB_PATH`, else `cryptex-mcp.db`
+/// next to the default database.
+pub fn get_mcp_db_path() -> PathBuf {
+    if let Ok(db_path) = env::var("CRYPTEX_MCP_DB_PATH") {
+        return PathBuf::from(db_path);
+    }
+
+    get_data_dir().join("cryptex-mcp.db")
+}
+
 /// Get the platform-specific config directory
 pub fn get_config_dir() -> PathBuf {
     if let Ok(custom_path) = env::var("CRYPTEX_CONFIG_DIR") {
@@ -199,6 +212,14 @@ mod tests {
         println!("DB path: {:?}", db_path);
     }
 
+    #[test]
+    fn test_mcp_db_path_is_separate() {
+        if env::var_os("CRYPTEX_MCP_DB_PATH").is_none() {
+            assert!(get_mcp_db_path().ends_with("cryptex-mcp.db"));
+            assert_ne!(get_mcp_db_path(), get_default_db_path());
+        }
+    }
+
     #[test]
     fn test_platform_detection() {
         println!("Platform: {}", get_platform());
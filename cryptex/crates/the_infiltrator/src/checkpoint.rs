@@ -0,0 +1,180 @@
+//! Scan Checkpointing
+//!
+//! Periodically persists in-progress scan results so that a crash mid-scan
+//! loses at most one checkpoint's worth of findings. A checkpoint is taken
+//! once `commit_every` results have arrived since the last one, or, with a
+//! `commit_interval`, once a result has waited that long, whether or not more
+//! arrive; ending a scan flushes whatever is left.
+//!
+//! The Infiltrator only knows the [`CheckpointStore`] trait; The Archive
+//! provides the persistent implementation.
+
+use crate::context::{ScanContext, ScanOptions};
+use crate::types::ScanResult;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use the_coordinator::InfiltratorConfig;
+use the_foundation::CryptexResult;
+
+/// Default number of detections between checkpoints
+pub const DEFAULT_CHECKPOINT_INTERVAL: usize = 10;
+
+/// Environment variable overriding the detections between checkpoints
+///
+/// Mirrors `infiltrator.commit_every` in the charter.
+pub const COMMIT_EVERY_ENV: &str = "CRYPTEX_INFILTRATOR__COMMIT_EVERY";
+
+/// Environment variable bounding the time between checkpoints, in seconds
+///
+/// Mirrors `infiltrator.commit_interval_seconds` in the charter.
+pub const COMMIT_INTERVAL_ENV: &str = "CRYPTEX_INFILTRATOR__COMMIT_INTERVAL_SECONDS";
+
+/// Checkpoint thresholds from the environment, else the charter's
+/// `[infiltrator]` section
+///
+/// Returns `(commit_every, commit_interval)`; a threshold set in neither
+/// place falls back to `DEFAULT_CHECKPOINT_INTERVAL` and no time bound.
+/// Unparseable environment values are ignored with a warning.
+pub fn commit_policy(charter: Option<&InfiltratorConfig>) -> (usize, Option<Duration>) {
+    let parse = |name: &str| {
+        let value = std::env::var(name).ok()?;
+        match value.parse::<u64>() {
+            Ok(n) if n > 0 => Some(n),
+            _ => {
+                tracing::warn!("Ignoring {}={}: not a positive number", name, value);
+                None
+            }
+        }
+    };
+
+    let commit_every = parse(COMMIT_EVERY_ENV)
+        .map(|n| n as usize)
+        .or(charter.map(|c| c.commit_every))
+        .unwrap_or(DEFAULT_CHECKPOINT_INTERVAL);
+    let commit_interval = parse(COMMIT_INTERVAL_ENV)
+        .or(charter.and_then(|c| c.commit_interval_seconds))
+        .map(Duration::from_secs);
+
+    (commit_every, commit_interval)
+}
+
+/// Snapshot of an in-progress scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCheckpoint {
+    pub scan_id: String,
+    pub target: String,
+    /// Targets of a multi-target scan; empty means just `target`
+    #[serde(default)]
+    pub targets: Vec<String>,
+    pub start_time: u64,
+    pub paused: bool,
+    /// Results not covered by an earlier checkpoint
+    pub results: Vec<ScanResult>,
+    /// Results saved by earlier checkpoints, which precede `results`
+    #[serde(default)]
+    pub persisted: usize,
+    #[serde(default)]
+    pub options: ScanOptions,
+}
+
+impl ScanCheckpoint {
+    /// Snapshot a live scan context with all of its results
+    pub fn from_context(ctx: &ScanContext) -> Self {
+        Self::since(ctx, 0)
+    }
+
+    /// Snapshot a live scan context, leaving out the first `persisted` results
+    ///
+    /// Checkpoints taken this way only carry what an earlier one did not, so
+    /// each costs as much as the detections since then.
+    pub fn since(ctx: &ScanContext, persisted: usize) -> Self {
+        let persisted = persisted.min(ctx.results.len());
+        Self {
+            scan_id: ctx.scan_id.clone(),
+            target: ctx.target.clone(),
+            targets: ctx.targets.clone(),
+            start_time: ctx.start_time,
+            paused: ctx.paused,
+            results: ctx.results[persisted..].to_vec(),
+            persisted,
+            options: ctx.options,
+        }
+    }
+
+    /// Snapshot what a live scan has not checkpointed yet and restart its
+    /// checkpoint thresholds
+    pub(crate) fn take(ctx: &mut ScanContext) -> Self {
+        ctx.uncommitted = 0;
+        ctx.last_commit = tokio::time::Instant::now();
+        Self::since(ctx, ctx.checkpointed)
+    }
+
+    /// Results of the scan so far, saved earlier or carried here
+    pub fn total_results(&self) -> usize {
+        self.persisted + self.results.len()
+    }
+
+    /// Rebuild a scan context from the snapshot
+    ///
+    /// Expects a complete checkpoint, as [`CheckpointStore::load_incomplete`]
+    /// returns; its results count as already saved.
+    pub fn into_context(self) -> ScanContext {
+        let mut ctx = if self.targets.is_empty() {
+            ScanContext::new(self.scan_id, self.target)
+        } else {
+            ScanContext::new_multi(self.scan_id, self.targets)
+        };
+        ctx.start_time = self.start_time;
+        ctx.paused = self.paused;
+        ctx.options = self.options;
+
+        // Budgets spent before the restart stay spent
+        for result in self.results {
+            if result.vulnerability_score.is_some() {
+                ctx.assessed_cves.insert(result.cve_id.clone());
+            }
+            if result.remediation_guidance.is_some() {
+                ctx.enhanced_cves.insert(result.cve_id.clone());
+            }
+            ctx.add_result(result);
+        }
+        ctx.checkpointed = ctx.results.len();
+
+        ctx
+    }
+}
+
+/// Checkpoint Store - Persistence backend for scan checkpoints
+///
+/// Traditional name: `CheckpointRepository`
+pub trait CheckpointStore: Send + Sync {
+    /// Persist the current state of an in-progress scan
+    ///
+    /// Only `checkpoint.results` need writing; the `persisted` results before
+    /// them were saved by earlier checkpoints of the same scan.
+    fn save_checkpoint(&self, checkpoint: &ScanCheckpoint) -> CryptexResult<()>;
+
+    /// Load checkpoints of scans that never completed
+    fn load_incomplete(&self) -> CryptexResult<Vec<ScanCheckpoint>>;
+
+    /// Mark a scan as finished so it is not recovered again
+    fn mark_complete(&self, scan_id: &str) -> CryptexResult<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_policy_reads_the_charter() {
+        assert_eq!(commit_policy(None), (DEFAULT_CHECKPOINT_INTERVAL, None));
+
+        let charter: InfiltratorConfig = serde_json::from_value(serde_json::json!({
+            "scanner_path": "/usr/bin/openvas",
+            "commit_every": 25,
+            "commit_interval_seconds": 15,
+        }))
+        .unwrap();
+        assert_eq!(commit_policy(Some(&charter)), (25, Some(Duration::from_secs(15))));
+    }
+}
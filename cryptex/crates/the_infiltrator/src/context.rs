@@ -1,22 +1,134 @@
 //! Scan and Host Context Management
 
+use crate::target::count_target_hosts;
 use crate::types::*;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::Ipv4Addr;
 use the_assessor::VulnerabilityScore;
 
+/// Scan Options - Per-scan enrichment settings
+///
+/// Both default to on. A fast, cheap scan can turn off AI remediation
+/// guidance, or skip assessment entirely and keep only raw detections.
+///
+/// The `max_*` budgets bound what one scan spends on NVD lookups and AI
+/// calls. They count distinct CVEs, since a CVE seen again on another host is
+/// answered from cache. Once a budget is spent, later CVEs are stored with
+/// whatever enrichment is left and flagged `enrichment_skipped`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanOptions {
+    /// Generate remediation guidance and plans for assessed findings
+    pub ai_enhancement: bool,
+    /// Score findings with CVSS, KEV and EPSS data
+    ///
+    /// Without assessment there is nothing to base guidance on, so this also
+    /// turns off AI enhancement.
+    pub assess: bool,
+    /// Distinct CVEs to assess; unlimited when unset
+    pub max_assessments: Option<usize>,
+    /// Distinct CVEs to generate remediation guidance for; unlimited when unset
+    pub max_ai_enhancements: Option<usize>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            ai_enhancement: true,
+            assess: true,
+            max_assessments: None,
+            max_ai_enhancements: None,
+        }
+    }
+}
+
+/// Scan options as a caller sends them, with every field optional
+///
+/// Fields left out keep the value of the defaults they are merged with, so
+/// a request that only sets `assess` does not turn AI enhancement back on
+/// for a server configured without it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanOptionsOverride {
+    pub ai_enhancement: Option<bool>,
+    pub assess: Option<bool>,
+    pub max_assessments: Option<usize>,
+    pub max_ai_enhancements: Option<usize>,
+}
+
+impl ScanOptionsOverride {
+    /// Fill the fields left out from `defaults`
+    pub fn merge(self, defaults: ScanOptions) -> ScanOptions {
+        ScanOptions {
+            ai_enhancement: self.ai_enhancement.unwrap_or(defaults.ai_enhancement),
+            assess: self.assess.unwrap_or(defaults.assess),
+            max_assessments: self.max_assessments.or(defaults.max_assessments),
+            max_ai_enhancements: self.max_ai_enhancements.or(defaults.max_ai_enhancements),
+        }
+    }
+}
+
+impl ScanOptions {
+    /// Assess at most `max` distinct CVEs
+    pub fn max_assessments(mut self, max: usize) -> Self {
+        self.max_assessments = Some(max);
+        self
+    }
+
+    /// Generate guidance for at most `max` distinct CVEs
+    pub fn max_ai_enhancements(mut self, max: usize) -> Self {
+        self.max_ai_enhancements = Some(max);
+        self
+    }
+
+    /// Whether `granted` falls short of the enrichment these options ask for
+    pub fn is_cut_short_by(&self, granted: &ScanOptions) -> bool {
+        self.assess && (!granted.assess || (self.ai_enhancement && !granted.ai_enhancement))
+    }
+}
+
 /// Scan Context - Tracks state for an active scan
 #[derive(Debug)]
 pub struct ScanContext {
     pub scan_id: String,
+    /// Display form of the scan's targets, comma-separated for multi-target scans
     pub target: String,
+    /// Every target the scan covers; a single-target scan has just one
+    pub targets: Vec<String>,
     pub start_time: u64,
+    /// Addresses the targets cover
+    pub hosts_total: u64,
+    /// Hosts the scanner has reported on, with or without findings
     pub hosts: HashMap<String, HostContext>,
     pub results: Vec<ScanResult>,
+    /// `(cve_id, host, port)` of every entry in `results`
+    findings: HashSet<(String, String, u16)>,
+    pub paused: bool,
+    pub options: ScanOptions,
+    /// CVEs charged against `options.max_assessments`
+    pub(crate) assessed_cves: HashSet<String>,
+    /// CVEs charged against `options.max_ai_enhancements`
+    pub(crate) enhanced_cves: HashSet<String>,
+    /// Results added since the last checkpoint
+    pub(crate) uncommitted: usize,
+    /// Leading results already saved by a checkpoint
+    pub(crate) checkpointed: usize,
+    /// When the scan was last checkpointed (or started)
+    pub(crate) last_commit: tokio::time::Instant,
+    /// Replayed from a report file; never checkpointed, since an interrupted
+    /// import is simply run again
+    pub(crate) imported: bool,
 }
 
 impl ScanContext {
     /// Create a new scan context
     pub fn new(scan_id: String, target: String) -> Self {
+        Self::new_multi(scan_id, vec![target])
+    }
+
+    /// Create a scan context covering several targets
+    pub fn new_multi(scan_id: String, targets: Vec<String>) -> Self {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -24,13 +136,67 @@ impl ScanContext {
 
         Self {
             scan_id,
-            target,
+            target: targets.join(", "),
+            hosts_total: targets
+                .iter()
+                .map(|target| count_target_hosts(target))
+                .fold(0, u64::saturating_add),
+            targets,
             start_time: now,
             hosts: HashMap::new(),
             results: Vec::new(),
+            findings: HashSet::new(),
+            paused: false,
+            options: ScanOptions::default(),
+            assessed_cves: HashSet::new(),
+            enhanced_cves: HashSet::new(),
+            uncommitted: 0,
+            checkpointed: 0,
+            last_commit: tokio::time::Instant::now(),
+            imported: false,
         }
     }
 
+    /// Use these enrichment settings for the scan's detections
+    pub fn with_options(mut self, options: ScanOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Charge enrichment of `cve_id` to the scan's budgets
+    ///
+    /// Returns the options the detection gets: `assess` or `ai_enhancement`
+    /// are turned off once their budget is spent. A CVE already charged is
+    /// admitted again without using more budget.
+    pub fn claim_enrichment(&mut self, cve_id: &str) -> ScanOptions {
+        fn admit(charged: &mut HashSet<String>, max: Option<usize>, cve_id: &str) -> bool {
+            if charged.contains(cve_id) || max.is_none_or(|max| charged.len() < max) {
+                charged.insert(cve_id.to_string());
+                true
+            } else {
+                false
+            }
+        }
+
+        let mut granted = self.options;
+        granted.assess =
+            granted.assess && admit(&mut self.assessed_cves, granted.max_assessments, cve_id);
+        granted.ai_enhancement = granted.assess
+            && granted.ai_enhancement
+            && admit(&mut self.enhanced_cves, granted.max_ai_enhancements, cve_id);
+        granted
+    }
+
+    /// Pause the scan - accumulated results are kept
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume a paused scan
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
     /// Get or create host context
     pub fn get_or_create_host(&mut self, host: String) -> &mut HostContext {
         self.hosts.entry(host.clone()).or_insert_with(|| {
@@ -39,21 +205,99 @@ impl ScanContext {
         })
     }
 
-    /// Add a scan result
-    pub fn add_result(&mut self, result: ScanResult) {
+    /// Add a scan result, returning whether it was new
+    ///
+    /// The host is registered on first sight so `total_hosts` counts every
+    /// host with a finding, whichever target it belongs to.
+    ///
+    /// A finding is identified by `(cve_id, host, port)`. Scanners repeat
+    /// detections on retries or when several plugins test the same flaw, so a
+    /// repeat is dropped and the first occurrence kept, even though
+    /// `ScanResult::confidence` could rank them. Detections reach here without
+    /// a scanner confidence, and a repeat is turned away before it is enriched,
+    /// so the only repeats that get this far are concurrent copies of one
+    /// detection, enriched alike. The first is also the one that may already
+    /// be checkpointed, which swapping in a later copy would leave stale.
+    pub fn add_result(&mut self, mut result: ScanResult) -> bool {
+        let finding = (result.cve_id.clone(), result.host.clone(), result.port);
+        if !self.findings.insert(finding) {
+            tracing::debug!(
+                "Dropping duplicate finding {} on {}:{}",
+                result.cve_id,
+                result.host,
+                result.port
+            );
+            return false;
+        }
+
         // Update host context
-        let host = result.host.clone();
-        if let Some(host_ctx) = self.hosts.get_mut(&host) {
-            host_ctx.add_vulnerability(result.cve_id.clone());
+        let host_ctx = self.get_or_create_host(result.host.clone());
+        host_ctx.add_vulnerability(result.cve_id.clone());
+
+        if result.service.is_none() || result.service_inferred {
+            if let Some(service) = host_ctx.services.get(&result.port) {
+                result.set_service(service.service_name.clone());
+            }
         }
+        let banner = result.banner.take();
+        result.infer_service(banner.as_deref());
+        result.banner = banner;
 
         self.results.push(result);
+        true
+    }
+
+    /// Whether this CVE was already recorded on the host and port
+    pub fn contains_finding(&self, cve_id: &str, host: &str, port: u16) -> bool {
+        self.findings
+            .contains(&(cve_id.to_string(), host.to_string(), port))
+    }
+
+    /// Record a service the scanner found on a host
+    ///
+    /// Results already on that port whose service was only inferred take
+    /// the reported name.
+    pub fn add_service(&mut self, host: &str, service: ServiceInfo) {
+        for result in &mut self.results {
+            if result.host == host && result.port == service.port && result.service_inferred {
+                result.set_service(service.service_name.clone());
+            }
+        }
+
+        self.get_or_create_host(host.to_string())
+            .add_service(service.port, service);
+    }
+
+    /// Findings and hosts seen so far for each target
+    ///
+    /// A host counts toward every target that covers it: the target itself,
+    /// or an IPv4 CIDR range containing it.
+    pub fn target_progress(&self) -> Vec<TargetProgress> {
+        self.targets
+            .iter()
+            .map(|target| TargetProgress {
+                target: target.clone(),
+                hosts: self.hosts.keys().filter(|h| target_covers(target, h)).count(),
+                results: self
+                    .results
+                    .iter()
+                    .filter(|r| target_covers(target, &r.host))
+                    .count(),
+            })
+            .collect()
+    }
+
+    /// How far along the scan is
+    pub fn progress(&self) -> ScanProgress {
+        ScanProgress::running(self.hosts_total, self.hosts.len() as u64, self.results.len())
     }
 
     /// Generate scan report
     pub fn generate_report(&self) -> ScanReport {
         let mut report = ScanReport::new(self.scan_id.clone(), self.target.clone());
         report.start_time = self.start_time;
+        report.targets = self.targets.clone();
+        report.target_progress = self.target_progress();
         report.total_hosts = self.hosts.len();
 
         for result in &self.results {
@@ -65,6 +309,83 @@ impl ScanContext {
     }
 }
 
+/// Per-target progress within a scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetProgress {
+    pub target: String,
+    pub hosts: usize,
+    pub results: usize,
+}
+
+/// How far along a scan is
+///
+/// A host counts as completed once the scanner has reported on it, whether
+/// with a finding, a service or an explicit completion. Host names in the
+/// results need not match the targets' addresses, so the completed count is
+/// capped at the total, and a running scan never shows more than 99%: only
+/// ending it makes the result final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanProgress {
+    pub hosts_total: u64,
+    pub hosts_completed: u64,
+    pub findings_so_far: usize,
+    /// Whole percent of hosts completed, 0-100
+    pub percent: u8,
+}
+
+impl ScanProgress {
+    /// Progress of a scan that is still running
+    pub fn running(hosts_total: u64, hosts_completed: u64, findings_so_far: usize) -> Self {
+        let hosts_completed = hosts_completed.min(hosts_total);
+        let percent = if hosts_total == 0 {
+            0
+        } else {
+            (u128::from(hosts_completed) * 100 / u128::from(hosts_total)).min(99) as u8
+        };
+
+        Self {
+            hosts_total,
+            hosts_completed,
+            findings_so_far,
+            percent,
+        }
+    }
+
+    /// Progress of a scan that has ended
+    pub fn finished(hosts_total: u64, findings: usize) -> Self {
+        Self {
+            hosts_total,
+            hosts_completed: hosts_total,
+            findings_so_far: findings,
+            percent: 100,
+        }
+    }
+}
+
+/// Whether `host` falls under `target` (exact match or IPv4 CIDR containment)
+fn target_covers(target: &str, host: &str) -> bool {
+    if target == host {
+        return true;
+    }
+
+    let Some((network, prefix)) = target.split_once('/') else {
+        return false;
+    };
+    let (Ok(network), Ok(prefix), Ok(host)) = (
+        network.parse::<Ipv4Addr>(),
+        prefix.parse::<u32>(),
+        host.parse::<Ipv4Addr>(),
+    ) else {
+        return false;
+    };
+    if prefix > 32 {
+        return false;
+    }
+
+    let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+    u32::from(network) & mask == u32::from(host) & mask
+}
+
 /// Host Context - Tracks vulnerability profile for a specific host
 #[derive(Debug, Clone)]
 pub struct HostContext {
@@ -144,6 +465,27 @@ impl ServiceInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_options_override_keeps_omitted_defaults() {
+        let defaults = ScanOptions {
+            ai_enhancement: false,
+            ..ScanOptions::default()
+        }
+        .max_assessments(50);
+        let partial: ScanOptionsOverride =
+            serde_json::from_value(serde_json::json!({ "assess": true })).unwrap();
+        assert_eq!(partial.merge(defaults), defaults);
+
+        let explicit: ScanOptionsOverride = serde_json::from_value(serde_json::json!({
+            "ai_enhancement": true,
+            "max_assessments": 5
+        }))
+        .unwrap();
+        let merged = explicit.merge(defaults);
+        assert!(merged.ai_enhancement);
+        assert_eq!(merged.max_assessments, Some(5));
+    }
+
     #[test]
     fn test_scan_context_creation() {
         let ctx = ScanContext::new(
@@ -155,6 +497,96 @@ mod tests {
         assert_eq!(ctx.target, "192.168.1.0/24");
         assert!(ctx.hosts.is_empty());
         assert!(ctx.results.is_empty());
+        assert!(!ctx.paused);
+    }
+
+    #[test]
+    fn test_pause_resume() {
+        let mut ctx = ScanContext::new(
+            "scan-001".to_string(),
+            "192.168.1.0/24".to_string(),
+        );
+
+        ctx.pause();
+        assert!(ctx.paused);
+
+        ctx.resume();
+        assert!(!ctx.paused);
+    }
+
+    #[test]
+    fn test_reported_service_replaces_inferred() {
+        let mut ctx = ScanContext::new(
+            "scan-001".to_string(),
+            "192.168.1.0/24".to_string(),
+        );
+
+        ctx.add_result(ScanResult::new(
+            "CVE-2024-0001".to_string(),
+            "192.168.1.100".to_string(),
+            443,
+            "unknown".to_string(),
+            "Test vulnerability".to_string(),
+        ));
+        assert_eq!(ctx.results[0].service.as_deref(), Some("https"));
+
+        ctx.add_service(
+            "192.168.1.100",
+            ServiceInfo::new(443, "tcp".to_string(), "nginx".to_string()),
+        );
+        assert_eq!(ctx.results[0].service.as_deref(), Some("nginx"));
+        assert!(!ctx.results[0].service_inferred);
+
+        // Later findings on the port take the reported service directly
+        ctx.add_result(ScanResult::new(
+            "CVE-2024-0002".to_string(),
+            "192.168.1.100".to_string(),
+            443,
+            "unknown".to_string(),
+            "Test vulnerability".to_string(),
+        ));
+        assert_eq!(ctx.results[1].service.as_deref(), Some("nginx"));
+    }
+
+    fn finding(cve_id: &str, port: u16) -> ScanResult {
+        ScanResult::new(
+            cve_id.to_string(),
+            "192.168.1.100".to_string(),
+            port,
+            "unknown".to_string(),
+            "Test vulnerability".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_duplicate_finding_counted_once() {
+        let mut ctx = ScanContext::new(
+            "scan-001".to_string(),
+            "192.168.1.0/24".to_string(),
+        );
+
+        let mut first = finding("CVE-2024-0001", 443);
+        first.plugin_oid = "1.3.6.1.4.1.25623.1.0.1".to_string();
+        assert!(ctx.add_result(first));
+        assert!(!ctx.add_result(finding("CVE-2024-0001", 443)));
+
+        assert_eq!(ctx.results.len(), 1);
+        assert_eq!(ctx.results[0].plugin_oid, "1.3.6.1.4.1.25623.1.0.1");
+        assert_eq!(ctx.generate_report().total_vulnerabilities, 1);
+    }
+
+    #[test]
+    fn test_same_cve_on_other_port_is_kept() {
+        let mut ctx = ScanContext::new(
+            "scan-001".to_string(),
+            "192.168.1.0/24".to_string(),
+        );
+
+        assert!(ctx.add_result(finding("CVE-2024-0001", 443)));
+        assert!(ctx.add_result(finding("CVE-2024-0001", 8443)));
+        assert!(ctx.add_result(finding("CVE-2024-0002", 443)));
+
+        assert_eq!(ctx.generate_report().total_vulnerabilities, 3);
     }
 
     #[test]
@@ -199,4 +631,46 @@ mod tests {
         assert_eq!(service.port, 443);
         assert_eq!(service.vulnerabilities.len(), 1);
     }
+
+    #[test]
+    fn test_multi_target_progress() {
+        let mut ctx = ScanContext::new_multi(
+            "scan-001".to_string(),
+            vec!["192.168.1.0/24".to_string(), "10.0.0.5".to_string()],
+        );
+        assert_eq!(ctx.target, "192.168.1.0/24, 10.0.0.5");
+
+        for (host, cve) in [
+            ("192.168.1.10", "CVE-2024-0001"),
+            ("192.168.1.11", "CVE-2024-0002"),
+            ("10.0.0.5", "CVE-2024-0003"),
+            ("10.0.0.5", "CVE-2024-0004"),
+        ] {
+            ctx.add_result(ScanResult::new(
+                cve.to_string(),
+                host.to_string(),
+                443,
+                "unknown".to_string(),
+                cve.to_string(),
+            ));
+        }
+
+        let progress = ctx.target_progress();
+        assert_eq!(progress[0].target, "192.168.1.0/24");
+        assert_eq!((progress[0].hosts, progress[0].results), (2, 2));
+        assert_eq!((progress[1].hosts, progress[1].results), (1, 2));
+
+        let report = ctx.generate_report();
+        assert_eq!(report.total_hosts, 3);
+        assert_eq!(report.targets.len(), 2);
+    }
+
+    #[test]
+    fn test_target_covers() {
+        assert!(target_covers("10.0.0.0/8", "10.200.3.4"));
+        assert!(!target_covers("10.0.0.0/8", "11.0.0.1"));
+        assert!(target_covers("0.0.0.0/0", "8.8.8.8"));
+        assert!(target_covers("host.example", "host.example"));
+        assert!(!target_covers("10.0.0.0/33", "10.0.0.1"));
+    }
 }
@@ -0,0 +1,998 @@
+//! GMP Client - Drives an external gvmd
+//!
+//! Traditional name: `GvmClient`
+//!
+//! By default the infiltrator is fed: the scanner calls
+//! `on_vulnerability_detected` for every finding. In live mode it does the
+//! driving itself. `start_scan` creates a target and a task on gvmd over the
+//! Greenbone Management Protocol and starts the task. A background poller
+//! then passes every new result that carries a CVE reference to
+//! `on_vulnerability_detected`.
+//!
+//! GMP is plain XML over a stream, one response per command. This client
+//! speaks it over gvmd's Unix socket, or over plain TCP to a loopback host
+//! only: the password goes over the wire in the clear, and gvmd's own TCP
+//! listener requires TLS anyway. For a remote gvmd, terminate TLS locally
+//! (e.g. with stunnel) and point the client at the local end. Responses are parsed
+//! as they stream in, results are fetched a page at a time, and every
+//! command is bounded by the configured I/O timeout, so neither a large
+//! report nor a stalled gvmd can hold a poller forever.
+
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, ReadHalf, WriteHalf};
+use the_coordinator::InfiltratorConfig;
+use the_foundation::{CryptexError, CryptexResult};
+
+/// gvmd's default GMP port
+///
+/// gvmd serves TLS there, which this client does not speak; over TCP, set
+/// the port of the local TLS terminator instead.
+pub const DEFAULT_GMP_PORT: u16 = 9390;
+
+/// "Full and fast", the stock scan configuration
+pub const DEFAULT_SCAN_CONFIG_ID: &str = "daba56c8-73ec-11df-a475-002264764cea";
+
+/// "OpenVAS Default", the stock scanner
+pub const DEFAULT_SCANNER_ID: &str = "08b69003-5fc2-4037-a479-93b440211c73";
+
+/// "All IANA assigned TCP", the stock port list
+pub const DEFAULT_PORT_LIST_ID: &str = "33d0cd82-57c6-11e1-8ed1-406186ea4fc5";
+
+/// How often a live scan's task is polled for new results
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Longest gvmd may take to accept a connection or answer one command
+pub const DEFAULT_IO_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Results fetched per `get_results` command
+pub const DEFAULT_RESULTS_PAGE_SIZE: usize = 500;
+
+/// Environment variables the servers read live-mode settings from
+///
+/// They mirror the `[infiltrator]` section of the charter.
+pub const SCANNER_MODE_ENV: &str = "CRYPTEX_INFILTRATOR__SCANNER_MODE";
+pub const SCANNER_HOST_ENV: &str = "CRYPTEX_INFILTRATOR__SCANNER_HOST";
+pub const SCANNER_PORT_ENV: &str = "CRYPTEX_INFILTRATOR__SCANNER_PORT";
+pub const SCANNER_SOCKET_ENV: &str = "CRYPTEX_INFILTRATOR__SCANNER_SOCKET";
+pub const SCANNER_USERNAME_ENV: &str = "CRYPTEX_INFILTRATOR__SCANNER_USERNAME";
+pub const SCANNER_PASSWORD_ENV: &str = "CRYPTEX_INFILTRATOR__SCANNER_PASSWORD";
+
+/// Task states after which no more results will appear
+const FINISHED_STATES: [&str; 3] = ["Done", "Stopped", "Interrupted"];
+
+/// Where gvmd listens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GmpEndpoint {
+    Tcp { host: String, port: u16 },
+    Unix(PathBuf),
+}
+
+impl GmpEndpoint {
+    /// Refuse a TCP endpoint that is not on this machine
+    ///
+    /// GMP goes over TCP unencrypted, including the password, so only
+    /// `localhost` and loopback addresses are accepted; names that merely
+    /// resolve to one are not.
+    pub fn ensure_local(&self) -> CryptexResult<()> {
+        let GmpEndpoint::Tcp { host, .. } = self else {
+            return Ok(());
+        };
+        let bare = host.trim_start_matches('[').trim_end_matches(']');
+        let loopback = host.eq_ignore_ascii_case("localhost")
+            || bare.parse::<std::net::IpAddr>().is_ok_and(|ip| ip.is_loopback());
+        if loopback {
+            return Ok(());
+        }
+
+        Err(CryptexError::charter(format!(
+            "gvmd host {} is not local; GMP over TCP is unencrypted, so use gvmd's Unix \
+             socket or a local TLS terminator (e.g. stunnel) for a remote gvmd",
+            host
+        )))
+    }
+}
+
+impl std::fmt::Display for GmpEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GmpEndpoint::Tcp { host, port } => write!(f, "{}:{}", host, port),
+            GmpEndpoint::Unix(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Connection and scan settings for live mode
+#[derive(Clone)]
+pub struct GmpConfig {
+    pub endpoint: GmpEndpoint,
+    pub username: String,
+    pub password: String,
+    pub scan_config_id: String,
+    pub scanner_id: String,
+    pub port_list_id: String,
+    pub poll_interval: Duration,
+    pub io_timeout: Duration,
+    pub results_page_size: usize,
+}
+
+impl std::fmt::Debug for GmpConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GmpConfig")
+            .field("endpoint", &self.endpoint)
+            .field("username", &self.username)
+            .field("scan_config_id", &self.scan_config_id)
+            .field("scanner_id", &self.scanner_id)
+            .field("port_list_id", &self.port_list_id)
+            .field("poll_interval", &self.poll_interval)
+            .field("io_timeout", &self.io_timeout)
+            .field("results_page_size", &self.results_page_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GmpConfig {
+    /// Settings for `endpoint` using gvmd's stock scan config, scanner and port list
+    pub fn new(endpoint: GmpEndpoint, username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            endpoint,
+            username: username.into(),
+            password: password.into(),
+            scan_config_id: DEFAULT_SCAN_CONFIG_ID.to_string(),
+            scanner_id: DEFAULT_SCANNER_ID.to_string(),
+            port_list_id: DEFAULT_PORT_LIST_ID.to_string(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            io_timeout: DEFAULT_IO_TIMEOUT,
+            results_page_size: DEFAULT_RESULTS_PAGE_SIZE,
+        }
+    }
+
+    /// Poll live scans every `interval` instead of every 10 seconds
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Give up on a connection attempt or command after `timeout`
+    pub fn with_io_timeout(mut self, timeout: Duration) -> Self {
+        self.io_timeout = timeout;
+        self
+    }
+
+    /// Fetch at most `rows` results per command (at least one)
+    pub fn with_results_page_size(mut self, rows: usize) -> Self {
+        self.results_page_size = rows.max(1);
+        self
+    }
+
+    /// Live-mode settings from the `CRYPTEX_INFILTRATOR__*` variables
+    ///
+    /// `None` unless `SCANNER_MODE` is `live`. Live mode needs a username,
+    /// a password, and either a socket path or a loopback host (port defaults
+    /// to 9390); anything missing is a charter error rather than a silent
+    /// fallback to manual mode, and so is a host that is not local.
+    pub fn from_env() -> CryptexResult<Option<Self>> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let port = match var(SCANNER_PORT_ENV) {
+            Some(port) => port.parse().map_err(|_| {
+                CryptexError::charter(format!("{}={} is not a port", SCANNER_PORT_ENV, port))
+            })?,
+            None => DEFAULT_GMP_PORT,
+        };
+
+        Self::live(
+            LiveSettings {
+                mode: var(SCANNER_MODE_ENV),
+                socket: var(SCANNER_SOCKET_ENV).map(PathBuf::from),
+                host: var(SCANNER_HOST_ENV),
+                port,
+                username: var(SCANNER_USERNAME_ENV),
+                password: var(SCANNER_PASSWORD_ENV),
+            },
+            [SCANNER_MODE_ENV, SCANNER_HOST_ENV, SCANNER_USERNAME_ENV, SCANNER_PASSWORD_ENV],
+        )
+    }
+
+    /// Live-mode settings from the charter's `[infiltrator]` section
+    ///
+    /// Same rules as [`GmpConfig::from_env`].
+    pub fn from_charter(config: &InfiltratorConfig) -> CryptexResult<Option<Self>> {
+        let set = |value: &Option<String>| value.clone().filter(|v| !v.trim().is_empty());
+
+        Self::live(
+            LiveSettings {
+                mode: Some(config.scanner_mode.clone()),
+                socket: config.scanner_socket.clone(),
+                host: set(&config.scanner_host),
+                port: config.scanner_port,
+                username: set(&config.scanner_username),
+                password: set(&config.scanner_password),
+            },
+            [
+                "infiltrator.scanner_mode",
+                "infiltrator.scanner_host",
+                "infiltrator.scanner_username",
+                "infiltrator.scanner_password",
+            ],
+        )
+    }
+
+    /// `names` are what the mode, host, username and password settings are
+    /// called where they came from, for error messages
+    fn live(settings: LiveSettings, names: [&str; 4]) -> CryptexResult<Option<Self>> {
+        let [mode_name, host_name, username_name, password_name] = names;
+
+        match settings.mode.as_deref().map(str::to_ascii_lowercase).as_deref() {
+            None | Some("manual") => return Ok(None),
+            Some("live") => {}
+            Some(other) => {
+                return Err(CryptexError::charter(format!(
+                    "{}={} is not a scanner mode (expected manual or live)",
+                    mode_name, other
+                )))
+            }
+        }
+
+        let required = |value: Option<String>, name: &str| {
+            value.ok_or_else(|| {
+                CryptexError::charter(format!("Live scanner mode needs {} to be set", name))
+            })
+        };
+
+        let endpoint = match settings.socket {
+            Some(path) => GmpEndpoint::Unix(path),
+            None => GmpEndpoint::Tcp {
+                host: required(settings.host, host_name)?,
+                port: settings.port,
+            },
+        };
+        let config = Self::new(
+            endpoint,
+            required(settings.username, username_name)?,
+            required(settings.password, password_name)?,
+        );
+        config.endpoint.ensure_local()?;
+
+        Ok(Some(config))
+    }
+}
+
+/// Live-mode settings before validation, wherever they were read from
+struct LiveSettings {
+    mode: Option<String>,
+    socket: Option<PathBuf>,
+    host: Option<String>,
+    port: u16,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+/// One gvmd result, reduced to what the infiltrator records
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GmpResult {
+    pub id: String,
+    pub host: String,
+    /// 0 for results not tied to a port, e.g. `general/tcp`
+    pub port: u16,
+    pub cve_ids: Vec<String>,
+}
+
+/// GMP Client - Opens authenticated sessions to gvmd
+///
+/// Traditional name: `GvmClient`
+#[derive(Debug, Clone)]
+pub struct GmpClient {
+    config: GmpConfig,
+}
+
+impl GmpClient {
+    pub fn new(config: GmpConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &GmpConfig {
+        &self.config
+    }
+
+    /// Connect to gvmd and authenticate
+    pub async fn connect(&self) -> CryptexResult<GmpSession> {
+        let timeout = self.config.io_timeout;
+        let stream = tokio::time::timeout(timeout, self.open_stream())
+            .await
+            .map_err(|_| {
+                CryptexError::upstream(format!(
+                    "gvmd at {} did not accept a connection within {:?}",
+                    self.config.endpoint, timeout
+                ))
+            })??;
+
+        let (read, write) = tokio::io::split(stream);
+        let mut reader = Reader::from_reader(BufReader::new(read));
+        reader.trim_text(true);
+        let mut session = GmpSession {
+            reader,
+            writer: write,
+            buffer: Vec::new(),
+            timeout,
+        };
+        session
+            .command(
+                "authenticate",
+                format!(
+                    "<authenticate><credentials><username>{}</username><password>{}</password></credentials></authenticate>",
+                    escape(&self.config.username),
+                    escape(&self.config.password)
+                ),
+            )
+            .await?;
+
+        Ok(session)
+    }
+
+    async fn open_stream(&self) -> CryptexResult<Box<dyn GmpStream>> {
+        let unreachable = |e: std::io::Error| {
+            CryptexError::upstream(format!("Cannot reach gvmd at {}: {}", self.config.endpoint, e))
+        };
+
+        self.config.endpoint.ensure_local()?;
+        Ok(match &self.config.endpoint {
+            GmpEndpoint::Tcp { host, port } => Box::new(
+                tokio::net::TcpStream::connect((host.as_str(), *port))
+                    .await
+                    .map_err(unreachable)?,
+            ),
+            #[cfg(unix)]
+            GmpEndpoint::Unix(path) => {
+                Box::new(tokio::net::UnixStream::connect(path).await.map_err(unreachable)?)
+            }
+            #[cfg(not(unix))]
+            GmpEndpoint::Unix(_) => {
+                return Err(CryptexError::charter(
+                    "gvmd Unix sockets are not supported on this platform",
+                ))
+            }
+        })
+    }
+}
+
+trait GmpStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> GmpStream for T {}
+
+/// An authenticated connection to gvmd
+pub struct GmpSession {
+    reader: Reader<BufReader<ReadHalf<Box<dyn GmpStream>>>>,
+    writer: WriteHalf<Box<dyn GmpStream>>,
+    /// Scratch space for the reader's events
+    buffer: Vec<u8>,
+    timeout: Duration,
+}
+
+impl GmpSession {
+    /// Create a target covering `hosts`; returns its id
+    pub async fn create_target(
+        &mut self,
+        name: &str,
+        hosts: &[String],
+        port_list_id: &str,
+    ) -> CryptexResult<String> {
+        let response = self
+            .command(
+                "create_target",
+                format!(
+                    "<create_target><name>{}</name><hosts>{}</hosts><port_list id=\"{}\"/></create_target>",
+                    escape(name),
+                    escape(&hosts.join(",")),
+                    escape(port_list_id)
+                ),
+            )
+            .await?;
+        created_id(&response, "create_target")
+    }
+
+    /// Create a task scanning `target_id`; returns its id
+    pub async fn create_task(
+        &mut self,
+        name: &str,
+        target_id: &str,
+        config_id: &str,
+        scanner_id: &str,
+    ) -> CryptexResult<String> {
+        let response = self
+            .command(
+                "create_task",
+                format!(
+                    "<create_task><name>{}</name><config id=\"{}\"/><target id=\"{}\"/><scanner id=\"{}\"/></create_task>",
+                    escape(name),
+                    escape(config_id),
+                    escape(target_id),
+                    escape(scanner_id)
+                ),
+            )
+            .await?;
+        created_id(&response, "create_task")
+    }
+
+    /// Start a task; returns the id of the report it writes to
+    pub async fn start_task(&mut self, task_id: &str) -> CryptexResult<String> {
+        let response = self
+            .command("start_task", format!("<start_task task_id=\"{}\"/>", escape(task_id)))
+            .await?;
+        response
+            .child("report_id")
+            .map(|report| report.text.clone())
+            .ok_or_else(|| CryptexError::upstream("gvmd started the task but returned no report id"))
+    }
+
+    /// The task's status, e.g. `Requested`, `Running` or `Done`
+    pub async fn task_status(&mut self, task_id: &str) -> CryptexResult<String> {
+        let response = self
+            .command("get_tasks", format!("<get_tasks task_id=\"{}\"/>", escape(task_id)))
+            .await?;
+        response
+            .child("task")
+            .and_then(|task| task.child("status"))
+            .map(|status| status.text.clone())
+            .ok_or_else(|| CryptexError::not_found(format!("gvmd has no task {}", task_id)))
+    }
+
+    /// Up to `rows` of the task's results, oldest first, starting at the
+    /// 1-based position `first`
+    ///
+    /// Sorting by creation time keeps positions stable while the task adds
+    /// results, so a poller can resume where its last page ended.
+    pub async fn get_results(
+        &mut self,
+        task_id: &str,
+        first: usize,
+        rows: usize,
+    ) -> CryptexResult<Vec<GmpResult>> {
+        let response = self
+            .command(
+                "get_results",
+                format!(
+                    "<get_results task_id=\"{}\" filter=\"first={} rows={} sort=created\" details=\"1\"/>",
+                    escape(task_id),
+                    first,
+                    rows
+                ),
+            )
+            .await?;
+        Ok(response.children("result").map(parse_result).collect())
+    }
+
+    /// Stop a running task
+    pub async fn stop_task(&mut self, task_id: &str) -> CryptexResult<()> {
+        self.command("stop_task", format!("<stop_task task_id=\"{}\"/>", escape(task_id)))
+            .await
+            .map(drop)
+    }
+
+    /// Delete a task for good, its reports included
+    pub async fn delete_task(&mut self, task_id: &str) -> CryptexResult<()> {
+        self.command(
+            "delete_task",
+            format!("<delete_task task_id=\"{}\" ultimate=\"1\"/>", escape(task_id)),
+        )
+        .await
+        .map(drop)
+    }
+
+    /// Delete a target for good; gvmd refuses while a task still uses it
+    pub async fn delete_target(&mut self, target_id: &str) -> CryptexResult<()> {
+        self.command(
+            "delete_target",
+            format!("<delete_target target_id=\"{}\" ultimate=\"1\"/>", escape(target_id)),
+        )
+        .await
+        .map(drop)
+    }
+
+    /// Stop a task if it is still running, then delete it and its target
+    ///
+    /// Stopping a task that already finished fails harmlessly, so only the
+    /// deletions count.
+    pub async fn discard_task(&mut self, task_id: &str, target_id: &str) -> CryptexResult<()> {
+        if let Err(e) = self.stop_task(task_id).await {
+            tracing::debug!("Not stopping gvmd task {}: {}", task_id, e);
+        }
+        self.delete_task(task_id).await?;
+        self.delete_target(target_id).await
+    }
+
+    /// Send one command and read its response, failing on a non-2xx status
+    /// or when gvmd does not answer within the I/O timeout
+    async fn command(&mut self, name: &str, request: String) -> CryptexResult<Element> {
+        let timeout = self.timeout;
+        let response = tokio::time::timeout(timeout, self.exchange(name, &request))
+            .await
+            .map_err(|_| {
+                CryptexError::upstream(format!("gvmd did not answer {} within {:?}", name, timeout))
+            })??;
+
+        let status = response.attr("status").unwrap_or_default();
+        if !status.starts_with('2') {
+            return Err(CryptexError::upstream(format!(
+                "GMP {} failed: {} {}",
+                name,
+                status,
+                response.attr("status_text").unwrap_or_default()
+            )));
+        }
+
+        Ok(response)
+    }
+
+    /// Write `request` and build the response from events as they are read
+    async fn exchange(&mut self, name: &str, request: &str) -> CryptexResult<Element> {
+        let io_error =
+            |e: std::io::Error| CryptexError::upstream(format!("GMP {} failed: {}", name, e));
+
+        self.writer.write_all(request.as_bytes()).await.map_err(io_error)?;
+        self.writer.flush().await.map_err(io_error)?;
+
+        let mut tree = ElementTree::default();
+        loop {
+            self.buffer.clear();
+            let event = match self.reader.read_event_into_async(&mut self.buffer).await {
+                Ok(Event::Eof) | Err(quick_xml::Error::UnexpectedEof(_)) => {
+                    return Err(CryptexError::upstream(format!(
+                        "gvmd closed the connection during {}",
+                        name
+                    )))
+                }
+                Ok(event) => event,
+                Err(e) => return Err(xml_error(e)),
+            };
+            if let Some(response) = tree.push(event)? {
+                return Ok(response);
+            }
+        }
+    }
+}
+
+/// Whether a task in `status` is finished producing results
+pub(crate) fn is_finished(status: &str) -> bool {
+    FINISHED_STATES.contains(&status)
+}
+
+fn created_id(response: &Element, command: &str) -> CryptexResult<String> {
+    response
+        .attr("id")
+        .map(str::to_string)
+        .ok_or_else(|| CryptexError::upstream(format!("GMP {} returned no id", command)))
+}
+
+pub(crate) fn parse_result(result: &Element) -> GmpResult {
+    let nvt = result.child("nvt");
+
+    let mut cve_ids: Vec<String> = nvt
+        .and_then(|nvt| nvt.child("refs"))
+        .map(|refs| {
+            refs.children("ref")
+                .filter(|r| r.attr("type").is_some_and(|t| t.eq_ignore_ascii_case("cve")))
+                .filter_map(|r| r.attr("id").map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Older gvmd versions list CVEs as text instead of refs
+    if cve_ids.is_empty() {
+        if let Some(cves) = nvt.and_then(|nvt| nvt.child("cve")) {
+            cve_ids = cves
+                .text
+                .split(',')
+                .map(str::trim)
+                .filter(|cve| cve.starts_with("CVE-"))
+                .map(str::to_string)
+                .collect();
+        }
+    }
+
+    let port = result
+        .child("port")
+        .and_then(|port| port.text.split('/').next())
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(0);
+
+    GmpResult {
+        id: result.attr("id").unwrap_or_default().to_string(),
+        host: result.child("host").map(|host| host.text.clone()).unwrap_or_default(),
+        port,
+        cve_ids,
+    }
+}
+
+/// Minimal XML element tree for GMP responses
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    attributes: Vec<(String, String)>,
+    pub(crate) text: String,
+    pub(crate) children: Vec<Element>,
+}
+
+impl Element {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub(crate) fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+fn xml_error(e: impl std::fmt::Display) -> CryptexError {
+    CryptexError::upstream(format!("Malformed GMP response: {}", e))
+}
+
+fn start_element(start: &BytesStart) -> CryptexResult<Element> {
+    let mut element = Element {
+        name: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+        ..Element::default()
+    };
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(xml_error)?;
+        element.attributes.push((
+            String::from_utf8_lossy(attribute.key.as_ref()).into_owned(),
+            attribute.unescape_value().map_err(xml_error)?.into_owned(),
+        ));
+    }
+    Ok(element)
+}
+
+/// Builds one element from XML events as they arrive
+#[derive(Default)]
+struct ElementTree {
+    open: Vec<Element>,
+}
+
+impl ElementTree {
+    /// Add `event`; returns the root element once it is closed
+    fn push(&mut self, event: Event) -> CryptexResult<Option<Element>> {
+        let finished = match event {
+            Event::Start(start) => {
+                self.open.push(start_element(&start)?);
+                None
+            }
+            Event::Empty(start) => Some(start_element(&start)?),
+            Event::End(_) => self.open.pop(),
+            Event::Text(text) => {
+                if let Some(element) = self.open.last_mut() {
+                    element.text.push_str(&text.unescape().map_err(xml_error)?);
+                }
+                None
+            }
+            Event::CData(data) => {
+                if let Some(element) = self.open.last_mut() {
+                    element.text.push_str(&String::from_utf8_lossy(&data));
+                }
+                None
+            }
+            _ => None,
+        };
+
+        Ok(finished.and_then(|element| match self.open.last_mut() {
+            Some(parent) => {
+                parent.children.push(element);
+                None
+            }
+            None => Some(element),
+        }))
+    }
+}
+
+/// Parse the response at the start of `data`
+///
+/// `None` means the root element has not been closed yet and more input is
+/// needed.
+pub(crate) fn parse_element(data: &[u8]) -> CryptexResult<Option<Element>> {
+    let mut reader = Reader::from_reader(data);
+    reader.trim_text(true);
+    let mut tree = ElementTree::default();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Eof) | Err(quick_xml::Error::UnexpectedEof(_)) => return Ok(None),
+            Ok(event) => {
+                if let Some(element) = tree.push(event)? {
+                    return Ok(Some(element));
+                }
+            }
+            Err(e) => return Err(xml_error(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// A gvmd stand-in that answers every command from a fixed script
+    ///
+    /// Records the commands it receives; `get_results` pages through the
+    /// canned results and `get_tasks` reports the task done. The password
+    /// `silent` gets no answer at all, like a stalled gvmd.
+    pub(crate) struct MockGvmd {
+        pub(crate) address: std::net::SocketAddr,
+        pub(crate) commands: Arc<Mutex<Vec<String>>>,
+    }
+
+    pub(crate) const MOCK_RESULT_ROWS: [&str; 3] = [
+        "<result id=\"r1\"><host>10.0.0.5<asset asset_id=\"a1\"/></host><port>443/tcp</port>\
+        <nvt oid=\"1.3.6.1.4.1.25623.1.0.1\"><refs><ref type=\"cve\" id=\"CVE-2021-44228\"/>\
+        <ref type=\"url\" id=\"https://example.com\"/></refs></nvt></result>",
+        "<result id=\"r2\"><host>10.0.0.6</host><port>general/tcp</port>\
+        <nvt oid=\"1.3.6.1.4.1.25623.1.0.2\"><cve>CVE-2024-0001, CVE-2024-0002</cve></nvt>\
+        </result>",
+        "<result id=\"r3\"><host>10.0.0.6</host><port>22/tcp</port>\
+        <nvt oid=\"1.3.6.1.4.1.25623.1.0.3\"><cve>NOCVE</cve></nvt></result>",
+    ];
+
+    /// The page of canned results a `get_results` filter asks for
+    fn results_response(filter: &str) -> String {
+        let keyword = |name: &str| {
+            filter
+                .split_whitespace()
+                .find_map(|term| term.strip_prefix(name)?.strip_prefix('=')?.parse::<usize>().ok())
+        };
+        let first = keyword("first").unwrap_or(1).max(1);
+        let rows = keyword("rows").unwrap_or(MOCK_RESULT_ROWS.len());
+        let page: String =
+            MOCK_RESULT_ROWS.iter().skip(first - 1).take(rows).copied().collect();
+        format!(
+            "<get_results_response status=\"200\" status_text=\"OK\">{}</get_results_response>",
+            page
+        )
+    }
+
+    impl MockGvmd {
+        pub(crate) async fn start() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap();
+            let commands = Arc::new(Mutex::new(Vec::new()));
+
+            let recorded = commands.clone();
+            tokio::spawn(async move {
+                while let Ok((mut socket, _)) = listener.accept().await {
+                    let recorded = recorded.clone();
+                    tokio::spawn(async move {
+                        let mut buffer = Vec::new();
+                        let mut chunk = [0u8; 4096];
+                        loop {
+                            let Ok(read) = socket.read(&mut chunk).await else { return };
+                            if read == 0 {
+                                return;
+                            }
+                            buffer.extend_from_slice(&chunk[..read]);
+                            let Ok(Some(request)) = parse_element(&buffer) else { continue };
+                            recorded
+                                .lock()
+                                .unwrap()
+                                .push(String::from_utf8_lossy(&buffer).into_owned());
+                            buffer.clear();
+
+                            let response = match request.name.as_str() {
+                                "authenticate" if request
+                                    .child("credentials")
+                                    .and_then(|c| c.child("password"))
+                                    .is_some_and(|p| p.text == "silent") =>
+                                {
+                                    continue;
+                                }
+                                "authenticate" if request
+                                    .child("credentials")
+                                    .and_then(|c| c.child("password"))
+                                    .is_some_and(|p| p.text == "secret") =>
+                                {
+                                    "<authenticate_response status=\"200\" status_text=\"OK\"/>"
+                                        .to_string()
+                                }
+                                "authenticate" => "<authenticate_response status=\"400\" \
+                                    status_text=\"Authentication failed\"/>"
+                                    .to_string(),
+                                "create_target" => "<create_target_response status=\"201\" \
+                                    status_text=\"OK, resource created\" id=\"target-1\"/>"
+                                    .to_string(),
+                                "create_task" => "<create_task_response status=\"201\" \
+                                    status_text=\"OK, resource created\" id=\"task-1\"/>"
+                                    .to_string(),
+                                "start_task" => "<start_task_response status=\"202\" \
+                                    status_text=\"OK, request submitted\">\
+                                    <report_id>report-1</report_id></start_task_response>"
+                                    .to_string(),
+                                "get_tasks" => "<get_tasks_response status=\"200\" status_text=\"OK\">\
+                                    <task id=\"task-1\"><name>t</name><status>Done</status></task>\
+                                    </get_tasks_response>"
+                                    .to_string(),
+                                "get_results" => {
+                                    results_response(request.attr("filter").unwrap_or_default())
+                                }
+                                // The mock's task is always done already
+                                "stop_task" => "<stop_task_response status=\"400\" \
+                                    status_text=\"Task is not running\"/>"
+                                    .to_string(),
+                                "delete_task" | "delete_target" => format!(
+                                    "<{}_response status=\"200\" status_text=\"OK\"/>",
+                                    request.name
+                                ),
+                                other => format!(
+                                    "<{}_response status=\"400\" status_text=\"Bogus command\"/>",
+                                    other
+                                ),
+                            };
+                            // Split the reply to exercise reassembly on the client
+                            let (head, tail) = response.as_bytes().split_at(response.len() / 2);
+                            if socket.write_all(head).await.is_err() {
+                                return;
+                            }
+                            tokio::time::sleep(Duration::from_millis(5)).await;
+                            if socket.write_all(tail).await.is_err() {
+                                return;
+                            }
+                        }
+                    });
+                }
+            });
+
+            Self { address, commands }
+        }
+
+        pub(crate) fn config(&self, password: &str) -> GmpConfig {
+            GmpConfig::new(
+                GmpEndpoint::Tcp {
+                    host: self.address.ip().to_string(),
+                    port: self.address.port(),
+                },
+                "admin",
+                password,
+            )
+            .with_poll_interval(Duration::from_millis(20))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_creation_against_mock_gvmd() {
+        let gvmd = MockGvmd::start().await;
+        let client = GmpClient::new(gvmd.config("secret"));
+
+        let mut session = client.connect().await.unwrap();
+        let targets = vec!["10.0.0.0/24".to_string(), "db<1>.example".to_string()];
+        let target_id = session
+            .create_target("CRYPTEX scan-1", &targets, DEFAULT_PORT_LIST_ID)
+            .await
+            .unwrap();
+        assert_eq!(target_id, "target-1");
+
+        let task_id = session
+            .create_task("CRYPTEX scan-1", &target_id, DEFAULT_SCAN_CONFIG_ID, DEFAULT_SCANNER_ID)
+            .await
+            .unwrap();
+        assert_eq!(task_id, "task-1");
+        assert_eq!(session.start_task(&task_id).await.unwrap(), "report-1");
+        assert!(is_finished(&session.task_status(&task_id).await.unwrap()));
+
+        let commands = gvmd.commands.lock().unwrap().clone();
+        assert!(commands[1].contains("<hosts>10.0.0.0/24,db&lt;1&gt;.example</hosts>"));
+        assert!(commands[2].contains("<target id=\"target-1\"/>"));
+        assert!(commands[2].contains(DEFAULT_SCAN_CONFIG_ID));
+
+        // A rejected command surfaces gvmd's status text
+        let err = session.command("bogus", "<bogus/>".to_string()).await.unwrap_err();
+        assert!(err.to_string().contains("Bogus command"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_bad_credentials_are_reported() {
+        let gvmd = MockGvmd::start().await;
+        let err = GmpClient::new(gvmd.config("wrong")).connect().await.err().unwrap();
+        assert!(err.to_string().contains("Authentication failed"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_results_are_paged_and_tasks_discarded() {
+        let gvmd = MockGvmd::start().await;
+        let mut session = GmpClient::new(gvmd.config("secret")).connect().await.unwrap();
+
+        let first = session.get_results("task-1", 1, 2).await.unwrap();
+        let rest = session.get_results("task-1", 3, 2).await.unwrap();
+        let ids: Vec<&str> = first.iter().chain(&rest).map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["r1", "r2", "r3"]);
+        assert!(session.get_results("task-1", 4, 2).await.unwrap().is_empty());
+
+        // Stopping a finished task fails, which does not keep it around
+        session.discard_task("task-1", "target-1").await.unwrap();
+
+        let commands = gvmd.commands.lock().unwrap().clone();
+        assert!(commands[1].contains("filter=\"first=1 rows=2 sort=created\""));
+        assert!(commands[2].contains("filter=\"first=3 rows=2 sort=created\""));
+        assert!(commands[4].starts_with("<stop_task task_id=\"task-1\""));
+        assert!(commands[5].contains("<delete_task task_id=\"task-1\" ultimate=\"1\"/>"));
+        assert!(commands[6].contains("<delete_target target_id=\"target-1\" ultimate=\"1\"/>"));
+    }
+
+    #[tokio::test]
+    async fn test_stalled_gvmd_times_out() {
+        let gvmd = MockGvmd::start().await;
+        let config = gvmd.config("silent").with_io_timeout(Duration::from_millis(100));
+
+        let started = std::time::Instant::now();
+        let err = GmpClient::new(config).connect().await.err().unwrap();
+        assert!(err.to_string().contains("did not answer authenticate"), "{}", err);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_config_from_charter() {
+        let scanner = serde_json::json!({ "scanner_path": "/usr/bin/openvas" });
+        let mut charter: InfiltratorConfig = serde_json::from_value(scanner).unwrap();
+        assert!(GmpConfig::from_charter(&charter).unwrap().is_none());
+
+        charter.scanner_mode = "live".to_string();
+        charter.scanner_host = Some("gvmd.internal".to_string());
+        charter.scanner_port = 9391;
+        charter.scanner_username = Some("admin".to_string());
+        let err = GmpConfig::from_charter(&charter).unwrap_err();
+        assert!(err.to_string().contains("infiltrator.scanner_password"), "{}", err);
+
+        charter.scanner_password = Some("secret".to_string());
+        let err = GmpConfig::from_charter(&charter).unwrap_err();
+        assert!(err.to_string().contains("not local"), "{}", err);
+
+        for host in ["localhost", "127.0.0.1", "::1", "[::1]"] {
+            charter.scanner_host = Some(host.to_string());
+            let config = GmpConfig::from_charter(&charter).unwrap().unwrap();
+            assert_eq!(
+                config.endpoint,
+                GmpEndpoint::Tcp {
+                    host: host.to_string(),
+                    port: 9391
+                }
+            );
+        }
+
+        // Sessions opened from a hand-built config are held to the same rule
+        let remote = GmpEndpoint::Tcp {
+            host: "gvmd.internal".to_string(),
+            port: 9391,
+        };
+        let client = GmpClient::new(GmpConfig::new(remote, "admin", "secret"));
+        let err = client.connect().await.err().unwrap();
+        assert!(err.to_string().contains("not local"), "{}", err);
+
+        charter.scanner_socket = Some(PathBuf::from("/run/gvmd/gvmd.sock"));
+        let config = GmpConfig::from_charter(&charter).unwrap().unwrap();
+        assert_eq!(config.endpoint, GmpEndpoint::Unix(PathBuf::from("/run/gvmd/gvmd.sock")));
+    }
+
+    #[test]
+    fn test_parse_results() {
+        let response = parse_element(results_response("").as_bytes()).unwrap().unwrap();
+        let results: Vec<GmpResult> = response.children("result").map(parse_result).collect();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].host, "10.0.0.5");
+        assert_eq!(results[0].port, 443);
+        assert_eq!(results[0].cve_ids, vec!["CVE-2021-44228"]);
+        assert_eq!(results[1].port, 0);
+        assert_eq!(results[1].cve_ids, vec!["CVE-2024-0001", "CVE-2024-0002"]);
+        assert!(results[2].cve_ids.is_empty());
+
+        // Truncated input asks for more rather than failing
+        assert!(parse_element(&results_response("").as_bytes()[..40]).unwrap().is_none());
+    }
+}
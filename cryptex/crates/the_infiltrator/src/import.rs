@@ -0,0 +1,222 @@
+//! OpenVAS Report Import
+//!
+//! Traditional name: `GvmReportImporter`
+//!
+//! Reports exported from gvmd before CRYPTEX was deployed are still worth
+//! enriching. A GVM report file is the same XML gvmd answers `get_reports`
+//! with, so its `<results>` are read with the GMP parser and replayed through
+//! the normal detection path as one finished scan.
+//!
+//! Results without a CVE reference, without a usable host, or with a CVE ID
+//! that does not parse are skipped with a warning; one bad entry does not
+//! spoil the rest of the file.
+
+use chrono::{DateTime, Utc};
+use std::io::Read;
+
+use crate::gmp::{parse_element, parse_result, Element};
+use crate::target::validate_target;
+use the_assessor::normalize_cve_id;
+use the_foundation::{CryptexError, CryptexResult};
+
+/// One CVE finding read from a report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedFinding {
+    pub cve_id: String,
+    pub host: String,
+    /// 0 for results not tied to a port, e.g. `general/tcp`
+    pub port: u16,
+}
+
+/// What a GVM report file holds, reduced to what an import needs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenvasReport {
+    /// When gvmd started the scan, if the report says
+    pub scan_start: Option<DateTime<Utc>>,
+    /// When gvmd finished the scan, if the report says
+    pub scan_end: Option<DateTime<Utc>>,
+    pub findings: Vec<ImportedFinding>,
+    /// Results that were left out
+    pub skipped: usize,
+}
+
+impl OpenvasReport {
+    /// Distinct hosts with findings, in first-seen order
+    pub fn hosts(&self) -> Vec<String> {
+        let mut hosts: Vec<String> = Vec::new();
+        for finding in &self.findings {
+            if !hosts.contains(&finding.host) {
+                hosts.push(finding.host.clone());
+            }
+        }
+        hosts
+    }
+}
+
+/// Parse a GVM report XML document
+///
+/// Accepts a bare `<report>` as exported from the web UI as well as a whole
+/// `<get_reports_response>`. A document that is not well-formed XML is a
+/// validation error.
+pub fn parse_openvas_report<R: Read>(mut reader: R) -> CryptexResult<OpenvasReport> {
+    let mut data = Vec::new();
+    reader
+        .read_to_end(&mut data)
+        .map_err(|e| CryptexError::validation(format!("Failed to read OpenVAS report: {}", e)))?;
+
+    let root = parse_element(&data)
+        .map_err(|e| CryptexError::validation(format!("Malformed OpenVAS report: {}", e)))?
+        .ok_or_else(|| CryptexError::validation("OpenVAS report is truncated"))?;
+
+    let timestamp = |name: &str| {
+        find(&root, name)
+            .and_then(|element| DateTime::parse_from_rfc3339(element.text.trim()).ok())
+            .map(|time| time.with_timezone(&Utc))
+    };
+    let mut report = OpenvasReport {
+        scan_start: timestamp("scan_start"),
+        scan_end: timestamp("scan_end"),
+        ..OpenvasReport::default()
+    };
+
+    let mut results = Vec::new();
+    collect_results(&root, &mut results);
+    for result in results {
+        let parsed = parse_result(result);
+        let label = if parsed.id.is_empty() { "without an id" } else { parsed.id.as_str() };
+
+        if parsed.cve_ids.is_empty() {
+            tracing::debug!("Skipping OpenVAS result {}: no CVE reference", label);
+            report.skipped += 1;
+            continue;
+        }
+        // A finding belongs to one host, not a network or a list
+        let host = parsed.host.trim();
+        if host.is_empty() || host.contains(['/', ',']) || validate_target(host).is_err() {
+            tracing::warn!("Skipping OpenVAS result {}: '{}' is not a host", label, host);
+            report.skipped += 1;
+            continue;
+        }
+
+        let mut cve_ids = Vec::new();
+        for raw in &parsed.cve_ids {
+            match normalize_cve_id(raw) {
+                Ok(cve_id) => cve_ids.push(cve_id),
+                Err(e) => tracing::warn!("Skipping CVE reference of OpenVAS result {}: {}", label, e),
+            }
+        }
+        if cve_ids.is_empty() {
+            report.skipped += 1;
+            continue;
+        }
+
+        report.findings.extend(cve_ids.into_iter().map(|cve_id| ImportedFinding {
+            cve_id,
+            host: host.to_string(),
+            port: parsed.port,
+        }));
+    }
+
+    Ok(report)
+}
+
+/// Every `<result>` listed under a `<results>` element, at any depth
+///
+/// Results nested elsewhere (a result's own `<detection>` details) are not
+/// findings of the report.
+fn collect_results<'a>(element: &'a Element, results: &mut Vec<&'a Element>) {
+    for child in &element.children {
+        if child.name == "results" {
+            results.extend(child.children("result"));
+        } else {
+            collect_results(child, results);
+        }
+    }
+}
+
+/// First element named `name` below `element`, depth first
+fn find<'a>(element: &'a Element, name: &str) -> Option<&'a Element> {
+    element.children.iter().find_map(|child| {
+        if child.name == name {
+            Some(child)
+        } else {
+            find(child, name)
+        }
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A report as exported from the GSA, trimmed to the parts that are read
+    pub(crate) const FIXTURE_REPORT: &str = r#"<report id="7b2f" format_id="a994" extension="xml">
+  <name>2023-05-02T09:14:27Z</name>
+  <report id="7b2f">
+    <scan_start>2023-05-02T09:14:27Z</scan_start>
+    <scan_end>2023-05-02T10:02:51Z</scan_end>
+    <results start="1" max="100">
+      <result id="r1">
+        <name>Apache Log4j RCE</name>
+        <host>10.0.0.5<asset asset_id="a1"/><hostname>web01</hostname></host>
+        <port>443/tcp</port>
+        <nvt oid="1.3.6.1.4.1.25623.1.0.1">
+          <refs><ref type="cve" id="CVE-2021-44228"/><ref type="url" id="https://example.com"/></refs>
+        </nvt>
+        <detection><result id="d1"><details/></result></detection>
+      </result>
+      <result id="r2">
+        <host>10.0.0.6</host>
+        <port>general/tcp</port>
+        <nvt oid="1.3.6.1.4.1.25623.1.0.2"><cve>CVE-2024-0001, CVE-2024-0002</cve></nvt>
+      </result>
+      <result id="r3">
+        <host>10.0.0.6</host>
+        <port>22/tcp</port>
+        <nvt oid="1.3.6.1.4.1.25623.1.0.3"><cve>NOCVE</cve></nvt>
+      </result>
+      <result id="r4">
+        <host></host>
+        <port>80/tcp</port>
+        <nvt oid="1.3.6.1.4.1.25623.1.0.4"><refs><ref type="cve" id="CVE-2023-4966"/></refs></nvt>
+      </result>
+      <result id="r5">
+        <host>10.0.0.7</host>
+        <port>80/tcp</port>
+        <nvt oid="1.3.6.1.4.1.25623.1.0.5"><refs><ref type="cve" id="CVE-20X4-1"/></refs></nvt>
+      </result>
+    </results>
+  </report>
+</report>"#;
+
+    #[test]
+    fn test_parse_fixture_report() {
+        let report = parse_openvas_report(FIXTURE_REPORT.as_bytes()).unwrap();
+
+        let found: Vec<(&str, &str, u16)> = report
+            .findings
+            .iter()
+            .map(|f| (f.cve_id.as_str(), f.host.as_str(), f.port))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("CVE-2021-44228", "10.0.0.5", 443),
+                ("CVE-2024-0001", "10.0.0.6", 0),
+                ("CVE-2024-0002", "10.0.0.6", 0),
+            ]
+        );
+        assert_eq!(report.skipped, 3);
+        assert_eq!(report.hosts(), ["10.0.0.5", "10.0.0.6"]);
+        assert_eq!(report.scan_start.unwrap().to_rfc3339(), "2023-05-02T09:14:27+00:00");
+        assert_eq!(report.scan_end.unwrap().to_rfc3339(), "2023-05-02T10:02:51+00:00");
+    }
+
+    #[test]
+    fn test_rejects_malformed_xml() {
+        for xml in ["<report><results>", "not xml at all </report>"] {
+            let err = parse_openvas_report(xml.as_bytes()).unwrap_err();
+            assert!(matches!(err, CryptexError::ValidationError(_)), "{:?}: {}", xml, err);
+        }
+    }
+}
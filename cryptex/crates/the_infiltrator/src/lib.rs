@@ -12,6 +12,7 @@
 //! - **Scan Lifecycle Management**: Tracks active scans and their context
 //! - **Performance Monitoring**: Comprehensive statistics and metrics
 //! - **Host Context Tracking**: Maintains per-host vulnerability profiles
+//! - **Live Mode**: Optionally runs scans on gvmd over GMP (see [`gmp`])
 //!
 //! ## Example
 //!
@@ -46,10 +47,27 @@
 pub mod types;
 pub mod scanner;
 pub mod context;
+pub mod checkpoint;
+pub mod target;
+pub mod gmp;
+pub mod import;
+pub mod service;
+pub mod notify;
 
 pub use types::*;
-pub use scanner::TheInfiltrator;
+pub use scanner::{validate_scan_id, OpenvasImport, TheInfiltrator};
 pub use context::*;
+pub use checkpoint::{
+    commit_policy, CheckpointStore, ScanCheckpoint, COMMIT_EVERY_ENV,
+    COMMIT_INTERVAL_ENV, DEFAULT_CHECKPOINT_INTERVAL,
+};
+pub use target::{count_target_hosts, validate_target};
+pub use gmp::{GmpClient, GmpConfig, GmpEndpoint, GmpResult, GmpSession};
+pub use import::{parse_openvas_report, ImportedFinding, OpenvasReport};
+pub use service::{infer_service, WELL_KNOWN_PORTS};
+pub use notify::{
+    FindingAlert, GenericWebhookSink, NoopSink, NotificationSink, Notifier, SlackSink,
+};
 
 // Re-export from foundation
 pub use the_foundation::CryptexResult;
@@ -0,0 +1,476 @@
+//! Finding Notifications
+//!
+//! Traditional name: `AlertDispatcher`
+//!
+//! A critical or known-exploited finding should reach someone while the scan
+//! is still running, not when the report is read. Each new finding that
+//! meets the threshold is queued for every configured sink, and one
+//! background worker per sink delivers its queue: delivery is best-effort,
+//! and a slow or failing sink never holds up the detection that triggered it.
+//!
+//! A scan turning up hundreds of criticals must not flood a channel, so each
+//! worker sends at most one delivery per interval, carrying up to
+//! `MAX_ALERT_BATCH` alerts queued in the meantime. A queue that is full
+//! drops further alerts with a warning.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::types::ScanResult;
+use the_assessor::CvssSeverity;
+use the_coordinator::NotificationsConfig;
+use the_foundation::{build_http_client, CryptexError, CryptexResult, HttpSettings};
+
+/// Environment variable naming the least severe finding that alerts
+pub const MIN_SEVERITY_ENV: &str = "CRYPTEX_NOTIFICATIONS__MIN_SEVERITY";
+
+/// Environment variable choosing whether KEV findings alert whatever their severity
+pub const NOTIFY_KEV_ENV: &str = "CRYPTEX_NOTIFICATIONS__NOTIFY_KEV";
+
+/// Environment variable holding a Slack incoming webhook URL
+pub const SLACK_WEBHOOK_URL_ENV: &str = "CRYPTEX_NOTIFICATIONS__SLACK_WEBHOOK_URL";
+
+/// Environment variable holding a URL that receives alerts as JSON
+pub const WEBHOOK_URL_ENV: &str = "CRYPTEX_NOTIFICATIONS__WEBHOOK_URL";
+
+/// Time allowed for each delivery
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Alerts waiting per sink before new ones are dropped
+pub const ALERT_QUEUE_CAPACITY: usize = 256;
+
+/// Most alerts a sink is handed in one delivery
+pub const MAX_ALERT_BATCH: usize = 20;
+
+/// Least time between two deliveries to the same sink
+pub const DEFAULT_DELIVERY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What a sink is told about a finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FindingAlert {
+    pub scan_id: String,
+    pub cve_id: String,
+    pub host: String,
+    pub port: u16,
+    pub severity: CvssSeverity,
+    pub cvss_base_score: Option<f64>,
+    pub known_exploited: bool,
+}
+
+impl FindingAlert {
+    /// Alert for an assessed finding; `None` if it has no assessment
+    pub fn from_result(scan_id: &str, result: &ScanResult) -> Option<Self> {
+        let score = result.vulnerability_score.as_ref()?;
+
+        Some(Self {
+            scan_id: scan_id.to_string(),
+            cve_id: result.cve_id.clone(),
+            host: result.host.clone(),
+            port: result.port,
+            severity: score.severity(),
+            cvss_base_score: result.cvss_base_score(),
+            known_exploited: score.is_kev(),
+        })
+    }
+
+    /// One-line human-readable summary
+    pub fn summary(&self) -> String {
+        let kev = if self.known_exploited { ", known exploited" } else { "" };
+        format!(
+            "{} finding {} on {}:{} (scan {}{})",
+            self.severity.as_str(),
+            self.cve_id,
+            self.host,
+            self.port,
+            self.scan_id,
+            kev
+        )
+    }
+}
+
+/// Notification Sink - Somewhere alerts are delivered
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    /// Deliver one alert
+    async fn notify(&self, alert: &FindingAlert) -> CryptexResult<()>;
+
+    /// Deliver alerts that queued up together
+    ///
+    /// Sinks read by people should override this to send a single message.
+    /// By default each alert is delivered in turn, and the last failure is
+    /// returned once all have been tried.
+    async fn notify_batch(&self, alerts: &[FindingAlert]) -> CryptexResult<()> {
+        let mut outcome = Ok(());
+        for alert in alerts {
+            if let Err(e) = self.notify(alert).await {
+                outcome = Err(e);
+            }
+        }
+        outcome
+    }
+}
+
+/// Sink that drops every alert
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopSink;
+
+#[async_trait]
+impl NotificationSink for NoopSink {
+    async fn notify(&self, _alert: &FindingAlert) -> CryptexResult<()> {
+        Ok(())
+    }
+}
+
+/// POSTs each alert as JSON to a URL
+pub struct GenericWebhookSink {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl GenericWebhookSink {
+    pub fn new(url: impl Into<String>, http: &HttpSettings) -> CryptexResult<Self> {
+        Ok(Self {
+            url: http_url(url.into())?,
+            client: build_http_client(http)?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationSink for GenericWebhookSink {
+    async fn notify(&self, alert: &FindingAlert) -> CryptexResult<()> {
+        post(self.client.post(&self.url).json(alert)).await
+    }
+}
+
+/// Posts each alert to a Slack incoming webhook
+pub struct SlackSink {
+    webhook_url: String,
+    client: reqwest::Client,
+}
+
+impl SlackSink {
+    pub fn new(webhook_url: impl Into<String>, http: &HttpSettings) -> CryptexResult<Self> {
+        Ok(Self {
+            webhook_url: http_url(webhook_url.into())?,
+            client: build_http_client(http)?,
+        })
+    }
+
+    /// Message text for `alerts`, one line each
+    fn text(alerts: &[FindingAlert]) -> String {
+        match alerts {
+            [alert] => format!(":rotating_light: {}", alert.summary()),
+            _ => {
+                let lines: Vec<String> =
+                    alerts.iter().map(|alert| format!("• {}", alert.summary())).collect();
+                format!(":rotating_light: {} findings\n{}", alerts.len(), lines.join("\n"))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationSink for SlackSink {
+    async fn notify(&self, alert: &FindingAlert) -> CryptexResult<()> {
+        self.notify_batch(std::slice::from_ref(alert)).await
+    }
+
+    async fn notify_batch(&self, alerts: &[FindingAlert]) -> CryptexResult<()> {
+        let body = serde_json::json!({ "text": Self::text(alerts) });
+        post(self.client.post(&self.webhook_url).json(&body)).await
+    }
+}
+
+/// Notifier - Decides which findings alert and fans them out to the sinks
+///
+/// The default has no sinks and alerts nobody. Clones share the sinks'
+/// queues and workers, which start with the first alert.
+#[derive(Clone)]
+pub struct Notifier {
+    sinks: Vec<Arc<dyn NotificationSink>>,
+    min_severity: CvssSeverity,
+    notify_kev: bool,
+    delivery_interval: Duration,
+    queues: Arc<OnceLock<Vec<mpsc::Sender<FindingAlert>>>>,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self {
+            sinks: Vec::new(),
+            min_severity: CvssSeverity::Critical,
+            notify_kev: true,
+            delivery_interval: DEFAULT_DELIVERY_INTERVAL,
+            queues: Arc::default(),
+        }
+    }
+}
+
+impl Notifier {
+    /// Alert on findings at least this severe
+    pub fn with_min_severity(mut self, min_severity: CvssSeverity) -> Self {
+        self.min_severity = min_severity;
+        self
+    }
+
+    /// Whether KEV findings alert whatever their severity
+    pub fn with_notify_kev(mut self, notify_kev: bool) -> Self {
+        self.notify_kev = notify_kev;
+        self
+    }
+
+    /// Also deliver alerts to `sink`
+    pub fn with_sink(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.sinks.push(sink);
+        self.queues = Arc::default();
+        self
+    }
+
+    /// Wait at least `interval` between two deliveries to the same sink
+    pub fn with_delivery_interval(mut self, interval: Duration) -> Self {
+        self.delivery_interval = interval;
+        self
+    }
+
+    /// Notifier for the charter's `[notifications]` section
+    ///
+    /// Each setting's `CRYPTEX_NOTIFICATIONS__*` variable overrides it.
+    /// Unparseable settings and unusable URLs are logged and skipped. The
+    /// sinks' clients are built from `http`, so a configured proxy applies.
+    pub fn from_charter(config: &NotificationsConfig, http: &HttpSettings) -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let mut notifier = Self::default().with_notify_kev(config.notify_kev);
+
+        let min_severity = var(MIN_SEVERITY_ENV).unwrap_or_else(|| config.min_severity.clone());
+        match min_severity.parse() {
+            Ok(min_severity) => notifier.min_severity = min_severity,
+            Err(e) => tracing::warn!("Ignoring notifications min_severity: {}", e),
+        }
+        if let Some(value) = var(NOTIFY_KEV_ENV) {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => notifier.notify_kev = true,
+                "false" | "0" => notifier.notify_kev = false,
+                _ => tracing::warn!(
+                    "Ignoring {}={}: expected true or false",
+                    NOTIFY_KEV_ENV,
+                    value
+                ),
+            }
+        }
+
+        let slack_url = var(SLACK_WEBHOOK_URL_ENV).or_else(|| config.slack_webhook_url.clone());
+        if let Some(url) = slack_url {
+            match SlackSink::new(url, http) {
+                Ok(sink) => notifier.sinks.push(Arc::new(sink)),
+                Err(e) => tracing::warn!("Slack notifications disabled: {}", e),
+            }
+        }
+        if let Some(url) = var(WEBHOOK_URL_ENV).or_else(|| config.webhook_url.clone()) {
+            match GenericWebhookSink::new(url, http) {
+                Ok(sink) => notifier.sinks.push(Arc::new(sink)),
+                Err(e) => tracing::warn!("Webhook notifications disabled: {}", e),
+            }
+        }
+
+        notifier
+    }
+
+    /// Whether a finding is worth an alert
+    pub fn should_notify(&self, alert: &FindingAlert) -> bool {
+        alert.severity.meets_minimum(self.min_severity)
+            || (self.notify_kev && alert.known_exploited)
+    }
+
+    /// The alert for a finding, if it meets the threshold and anyone is listening
+    pub fn alert_for(&self, scan_id: &str, result: &ScanResult) -> Option<FindingAlert> {
+        if self.sinks.is_empty() {
+            return None;
+        }
+        FindingAlert::from_result(scan_id, result).filter(|alert| self.should_notify(alert))
+    }
+
+    /// Queue an alert for every sink
+    ///
+    /// Returns at once; delivery failures are only logged, and a sink whose
+    /// queue is full misses the alert.
+    pub fn dispatch(&self, alert: FindingAlert) {
+        let queues = self.queues.get_or_init(|| {
+            self.sinks
+                .iter()
+                .map(|sink| spawn_sink_worker(sink.clone(), self.delivery_interval))
+                .collect()
+        });
+        for queue in queues {
+            if let Err(mpsc::error::TrySendError::Full(alert)) = queue.try_send(alert.clone()) {
+                tracing::warn!("Alert queue full; dropping the alert for {}", alert.cve_id);
+            }
+        }
+    }
+}
+
+/// Deliver `sink`'s queue in batches, at most one delivery per `interval`
+///
+/// The worker ends once every notifier holding its queue is dropped.
+fn spawn_sink_worker(
+    sink: Arc<dyn NotificationSink>,
+    interval: Duration,
+) -> mpsc::Sender<FindingAlert> {
+    let (queue, mut alerts) = mpsc::channel(ALERT_QUEUE_CAPACITY);
+    tokio::spawn(async move {
+        let mut batch = Vec::with_capacity(MAX_ALERT_BATCH);
+        while alerts.recv_many(&mut batch, MAX_ALERT_BATCH).await > 0 {
+            if let Err(e) = sink.notify_batch(&batch).await {
+                tracing::warn!("Failed to send {} alert(s): {}", batch.len(), e);
+            }
+            batch.clear();
+            tokio::time::sleep(interval).await;
+        }
+    });
+    queue
+}
+
+fn http_url(url: String) -> CryptexResult<String> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(url)
+    } else {
+        Err(CryptexError::validation(format!(
+            "Notification URL must use http or https, got '{}'",
+            url
+        )))
+    }
+}
+
+async fn post(request: reqwest::RequestBuilder) -> CryptexResult<()> {
+    let response = request
+        .timeout(DELIVERY_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| CryptexError::network(format!("Failed to send notification: {}", e)))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(CryptexError::upstream(format!(
+            "Notification endpoint returned {}",
+            response.status()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(severity: CvssSeverity, known_exploited: bool) -> FindingAlert {
+        FindingAlert {
+            scan_id: "scan-001".to_string(),
+            cve_id: "CVE-2024-0001".to_string(),
+            host: "10.0.0.5".to_string(),
+            port: 443,
+            severity,
+            cvss_base_score: None,
+            known_exploited,
+        }
+    }
+
+    #[test]
+    fn test_threshold() {
+        let notifier = Notifier::default();
+        assert!(notifier.should_notify(&alert(CvssSeverity::Critical, false)));
+        assert!(!notifier.should_notify(&alert(CvssSeverity::High, false)));
+        assert!(notifier.should_notify(&alert(CvssSeverity::Low, true)));
+
+        let notifier = notifier.with_min_severity(CvssSeverity::High).with_notify_kev(false);
+        assert!(notifier.should_notify(&alert(CvssSeverity::High, false)));
+        assert!(!notifier.should_notify(&alert(CvssSeverity::Low, true)));
+    }
+
+    #[test]
+    fn test_rejects_non_http_url() {
+        let http = HttpSettings::default();
+        assert!(SlackSink::new("hooks.slack.com/services/x", &http).is_err());
+        assert!(GenericWebhookSink::new("https://alerts.example.com/hook", &http).is_ok());
+    }
+
+    #[test]
+    fn test_from_charter() {
+        let config = NotificationsConfig {
+            min_severity: "high".to_string(),
+            notify_kev: false,
+            slack_webhook_url: Some("https://hooks.slack.com/services/x".to_string()),
+            webhook_url: Some("alerts.example.com/hook".to_string()),
+        };
+        let notifier = Notifier::from_charter(&config, &HttpSettings::default());
+        // The webhook URL has no scheme and is skipped
+        assert_eq!(notifier.sinks.len(), 1);
+        assert!(notifier.should_notify(&alert(CvssSeverity::High, false)));
+        assert!(!notifier.should_notify(&alert(CvssSeverity::Low, true)));
+    }
+
+    /// Sink counting what it is handed
+    #[derive(Default)]
+    struct CountingSink {
+        alerts: std::sync::atomic::AtomicUsize,
+        deliveries: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl NotificationSink for CountingSink {
+        async fn notify(&self, alert: &FindingAlert) -> CryptexResult<()> {
+            self.notify_batch(std::slice::from_ref(alert)).await
+        }
+
+        async fn notify_batch(&self, alerts: &[FindingAlert]) -> CryptexResult<()> {
+            use std::sync::atomic::Ordering;
+            self.alerts.fetch_add(alerts.len(), Ordering::SeqCst);
+            self.deliveries.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    async fn delivered(sink: &CountingSink, expected: usize) -> (usize, usize) {
+        use std::sync::atomic::Ordering;
+        for _ in 0..200 {
+            if sink.alerts.load(Ordering::SeqCst) >= expected {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        // Leave time for anything beyond the expected count to show up
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        (sink.alerts.load(Ordering::SeqCst), sink.deliveries.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_burst_is_delivered_in_batches() {
+        let sink = Arc::new(CountingSink::default());
+        let notifier = Notifier::default()
+            .with_sink(sink.clone())
+            .with_delivery_interval(Duration::from_millis(20));
+        for _ in 0..45 {
+            notifier.dispatch(alert(CvssSeverity::Critical, false));
+        }
+
+        let (alerts, deliveries) = delivered(&sink, 45).await;
+        assert_eq!(alerts, 45);
+        assert_eq!(deliveries, 45usize.div_ceil(MAX_ALERT_BATCH));
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops_alerts() {
+        let sink = Arc::new(CountingSink::default());
+        let notifier = Notifier::default()
+            .with_sink(sink.clone())
+            .with_delivery_interval(Duration::ZERO);
+        // The worker cannot run before this loop yields, so the queue fills
+        for _ in 0..ALERT_QUEUE_CAPACITY + 44 {
+            notifier.dispatch(alert(CvssSeverity::Critical, false));
+        }
+
+        let (alerts, _) = delivered(&sink, ALERT_QUEUE_CAPACITY).await;
+        assert_eq!(alerts, ALERT_QUEUE_CAPACITY);
+    }
+}
@@ -1,19 +1,27 @@
 //! Scanner Bridge - Main API
 
+use crate::checkpoint::*;
 use crate::context::*;
+use crate::gmp::{is_finished, GmpClient, GmpResult, GmpSession};
+use crate::import::{parse_openvas_report, OpenvasReport};
+use crate::notify::Notifier;
+use crate::target::validate_target;
 use crate::types::*;
 use std::collections::HashMap;
+use std::io::Read;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use the_assessor::TheAssessor;
-use the_foundation::{CryptexError, CryptexResult};
+use the_assessor::{Cpe, TheAssessor, VulnerabilityScore};
+use the_foundation::{CryptexError, CryptexResult, TheAgitator, ThePackage, TheReply};
 
 /// The Infiltrator - Scanner Integration Bridge
 ///
 /// Traditional name: `ScannerBridge`
 ///
 /// Coordinates between OpenVAS scanner core and CRYPTEX's vulnerability assessment.
+/// Clones share the same scans, statistics and connections.
+#[derive(Clone)]
 pub struct TheInfiltrator {
     /// Active scan contexts
     scans: Arc<RwLock<HashMap<String, ScanContext>>>,
@@ -24,10 +32,94 @@ pub struct TheInfiltrator {
     /// Bridge statistics
     stats: Arc<RwLock<BridgeStatistics>>,
 
-    /// Enable AI enhancement
+    /// Enable AI enhancement for scans that don't choose for themselves
     enable_ai_enhancement: bool,
+
+    /// Optional persistence for in-progress scans
+    checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+
+    /// Detections between checkpoints
+    checkpoint_interval: usize,
+
+    /// Longest a detection waits for a checkpoint
+    commit_interval: Option<Duration>,
+
+    /// gvmd to run scans on in live mode; `None` waits for reported detections
+    gmp: Option<Arc<GmpClient>>,
+
+    /// gvmd task and target of each active live scan, removed when it ends
+    live_tasks: Arc<RwLock<HashMap<String, LiveTask>>>,
+
+    /// AI provider writing remediation guidance; `None` keeps the templates
+    agitator: Option<Arc<dyn TheAgitator>>,
+
+    /// Where alerts for severe findings go; alerts nobody by default
+    notifier: Notifier,
+}
+
+/// What a live scan created on gvmd
+#[derive(Debug, Clone)]
+struct LiveTask {
+    task_id: String,
+    target_id: String,
+}
+
+/// An OpenVAS report registered as a scan, waiting to be enriched
+///
+/// Traditional name: `PendingImport`
+///
+/// Dropping it before [`TheInfiltrator::finish_openvas_import`] completes
+/// unregisters the scan.
+pub struct OpenvasImport {
+    report: OpenvasReport,
+    guard: ImportGuard,
+}
+
+impl OpenvasImport {
+    /// Id of the scan the report was registered as
+    pub fn scan_id(&self) -> &str {
+        &self.guard.scan_id
+    }
+
+    /// CVE findings still to be enriched
+    pub fn findings(&self) -> usize {
+        self.report.findings.len()
+    }
+}
+
+/// Unregisters an imported scan whose import never reached `end_scan`
+struct ImportGuard {
+    scan_id: String,
+    scans: Arc<RwLock<HashMap<String, ScanContext>>>,
+    stats: Arc<RwLock<BridgeStatistics>>,
+    armed: bool,
+}
+
+impl Drop for ImportGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let scan_id = std::mem::take(&mut self.scan_id);
+        let (scans, stats) = (self.scans.clone(), self.stats.clone());
+        runtime.spawn(async move {
+            if scans.write().await.remove(&scan_id).is_some() {
+                let mut stats = stats.write().await;
+                stats.active_scans = stats.active_scans.saturating_sub(1);
+                tracing::warn!("Import of scan {} was cancelled; scan discarded", scan_id);
+            }
+        });
+    }
 }
 
+/// Instructions sent with each finding when an agitator writes the guidance
+const REMEDIATION_PROMPT: &str = "You are a security engineer. Write concise, actionable \
+remediation guidance for the vulnerability described in the data: what to patch or \
+reconfigure, how urgently, and how to verify the fix.";
+
 impl TheInfiltrator {
     /// The Awakening - Initialize The Infiltrator
     ///
@@ -42,31 +134,522 @@ impl TheInfiltrator {
             assessor: Arc::new(assessor),
             stats: Arc::new(RwLock::new(BridgeStatistics::default())),
             enable_ai_enhancement: true,
+            checkpoint_store: None,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            commit_interval: None,
+            gmp: None,
+            live_tasks: Arc::new(RwLock::new(HashMap::new())),
+            agitator: None,
+            notifier: Notifier::default(),
         })
     }
 
+    /// The Awakening with checkpointing - Initialize and recover incomplete scans
+    ///
+    /// Scans left unfinished by a previous process are restored from the store.
+    pub async fn the_awakening_with_checkpoints(
+        store: Arc<dyn CheckpointStore>,
+        interval: usize,
+    ) -> CryptexResult<Self> {
+        let infiltrator = Self::the_awakening().await?.with_checkpointing(store, interval);
+        infiltrator.recover_scans().await?;
+
+        Ok(infiltrator)
+    }
+
+    /// Persist scan results to `store` after every `interval` detections
+    pub fn with_checkpointing(mut self, store: Arc<dyn CheckpointStore>, interval: usize) -> Self {
+        self.checkpoint_store = Some(store);
+        self.checkpoint_interval = interval.max(1);
+        self
+    }
+
+    /// Also checkpoint a scan once a detection has waited `interval` since
+    /// its last checkpoint
+    ///
+    /// Bounds how long a slow trickle of results stays unpersisted, even
+    /// when the trickle stops.
+    pub fn with_commit_interval(mut self, interval: Duration) -> Self {
+        self.commit_interval = Some(interval);
+        self
+    }
+
+    /// Run scans on gvmd ("live" mode)
+    ///
+    /// Every scan started from now on creates and starts a gvmd task, and
+    /// the task's results are fed to `on_vulnerability_detected` as they
+    /// appear. Without this the infiltrator stays in manual mode and only
+    /// records detections reported to it.
+    pub fn with_gmp(mut self, client: GmpClient) -> Self {
+        self.gmp = Some(Arc::new(client));
+        self
+    }
+
+    /// Have `agitator` write the remediation guidance of AI-enhanced findings
+    ///
+    /// Its certainty is blended into the confidence of findings that have one
+    /// (see [`ScanResult::blend_ai_certainty`]). When it fails, the template
+    /// guidance is used and the confidence left alone.
+    pub fn with_agitator(mut self, agitator: Arc<dyn TheAgitator>) -> Self {
+        self.agitator = Some(agitator);
+        self
+    }
+
+    /// Alert `notifier`'s sinks when a new finding meets its threshold
+    ///
+    /// Alerts are sent in the background and never delay the detection.
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Whether scans run on gvmd rather than waiting for reported detections
+    pub fn is_live(&self) -> bool {
+        self.gmp.is_some()
+    }
+
+    /// Restore incomplete scans from the checkpoint store
+    ///
+    /// Returns the number of scans recovered. Scans already active are left untouched.
+    pub async fn recover_scans(&self) -> CryptexResult<usize> {
+        let Some(store) = &self.checkpoint_store else {
+            return Ok(0);
+        };
+
+        let checkpoints = store.load_incomplete()?;
+        let mut recovered = 0;
+
+        let mut scans = self.scans.write().await;
+        for checkpoint in checkpoints {
+            if scans.contains_key(&checkpoint.scan_id) {
+                continue;
+            }
+
+            tracing::info!(
+                "Recovered scan {} with {} checkpointed results",
+                checkpoint.scan_id,
+                checkpoint.results.len()
+            );
+            scans.insert(checkpoint.scan_id.clone(), checkpoint.into_context());
+            recovered += 1;
+        }
+
+        if recovered > 0 {
+            let mut stats = self.stats.write().await;
+            stats.active_scans += recovered as u64;
+        }
+
+        Ok(recovered)
+    }
+
     /// Start a new scan
     ///
     /// Traditional name: `start_scan`
+    ///
+    /// `target` may list several comma-separated addresses, networks or host
+    /// names; a malformed one is a validation error and no scan is started.
     pub async fn start_scan(&self, target: &str) -> CryptexResult<String> {
+        self.start_scan_multi(&[target.to_string()]).await
+    }
+
+    /// Start one scan covering several targets
+    ///
+    /// Returns a single scan id; the report counts hosts across all targets
+    /// and breaks findings down per target.
+    pub async fn start_scan_multi(&self, targets: &[String]) -> CryptexResult<String> {
+        self.start_scan_with_options(targets, self.default_scan_options()).await
+    }
+
+    /// Options a scan gets when it is started without any
+    pub fn default_scan_options(&self) -> ScanOptions {
+        ScanOptions {
+            ai_enhancement: self.enable_ai_enhancement,
+            ..ScanOptions::default()
+        }
+    }
+
+    /// Start a scan with its own enrichment settings
+    ///
+    /// `options` replaces the infiltrator-wide defaults for this scan only.
+    pub async fn start_scan_with_options(
+        &self,
+        targets: &[String],
+        options: ScanOptions,
+    ) -> CryptexResult<String> {
         let scan_id = uuid::Uuid::new_v4().to_string();
+        self.begin_scan(scan_id, targets, options).await
+    }
 
-        tracing::info!("Starting scan {} for target: {}", scan_id, target);
+    /// Start a scan under a caller-chosen id
+    ///
+    /// Traditional name: `start_named_scan`
+    ///
+    /// Lets a pipeline re-run a scan under the same id instead of adding a
+    /// new one each time. Ids are 1-128 letters, digits, `.`, `_` or `-`;
+    /// an id that belongs to a scan still in progress is rejected.
+    pub async fn start_scan_with_id(&self, scan_id: &str, target: &str) -> CryptexResult<String> {
+        let targets = [target.to_string()];
+        self.start_scan_with_id_and_options(scan_id, &targets, self.default_scan_options())
+            .await
+    }
 
-        let context = ScanContext::new(scan_id.clone(), target.to_string());
+    /// Start a scan under a caller-chosen id with its own enrichment settings
+    pub async fn start_scan_with_id_and_options(
+        &self,
+        scan_id: &str,
+        targets: &[String],
+        options: ScanOptions,
+    ) -> CryptexResult<String> {
+        validate_scan_id(scan_id)?;
+        self.begin_scan(scan_id.to_string(), targets, options).await
+    }
 
-        {
-            let mut scans = self.scans.write().await;
-            scans.insert(scan_id.clone(), context);
+    async fn begin_scan(
+        &self,
+        scan_id: String,
+        targets: &[String],
+        options: ScanOptions,
+    ) -> CryptexResult<String> {
+        self.register_scan(&scan_id, targets, options).await?;
 
-            let mut stats = self.stats.write().await;
-            stats.total_scans += 1;
-            stats.active_scans += 1;
+        if let Some(gmp) = self.gmp.clone() {
+            if let Err(e) = self.launch_live_scan(&gmp, &scan_id, targets).await {
+                // Nothing will ever report into a scan gvmd did not start
+                self.scans.write().await.remove(&scan_id);
+                let mut stats = self.stats.write().await;
+                stats.active_scans = stats.active_scans.saturating_sub(1);
+                return Err(e);
+            }
         }
 
         Ok(scan_id)
     }
 
+    /// Validate the targets and track a new scan context, without scanning
+    async fn register_scan(
+        &self,
+        scan_id: &str,
+        targets: &[String],
+        options: ScanOptions,
+    ) -> CryptexResult<()> {
+        if targets.is_empty() {
+            return Err(CryptexError::validation("A scan needs at least one target"));
+        }
+        if let Some(blank) = targets.iter().position(|t| t.trim().is_empty()) {
+            return Err(CryptexError::validation(format!("Target {} is empty", blank + 1)));
+        }
+        for target in targets {
+            validate_target(target)?;
+        }
+
+        let context =
+            ScanContext::new_multi(scan_id.to_string(), targets.to_vec()).with_options(options);
+
+        tracing::info!("Starting scan {} for target: {}", scan_id, context.target);
+
+        let mut scans = self.scans.write().await;
+        if scans.contains_key(scan_id) {
+            return Err(CryptexError::validation(format!(
+                "Scan {} is already in progress; end it before reusing its id",
+                scan_id
+            )));
+        }
+        scans.insert(scan_id.to_string(), context);
+
+        let mut stats = self.stats.write().await;
+        stats.total_scans += 1;
+        stats.active_scans += 1;
+
+        Ok(())
+    }
+
+    /// Import a GVM report XML file as a finished scan, returning its id
+    ///
+    /// Traditional name: `import_report`
+    ///
+    /// See [`TheInfiltrator::import_openvas_report`].
+    pub async fn import_openvas_xml<R: Read>(&self, reader: R) -> CryptexResult<String> {
+        Ok(self.import_openvas_report(reader).await?.scan_id)
+    }
+
+    /// Import a GVM report XML file as a finished scan, returning its report
+    ///
+    /// Every CVE finding in the file goes through `on_vulnerability_detected`
+    /// and so is enriched like a live one. The scan covers the hosts that had
+    /// findings and starts and ends when the original scan did. It is never
+    /// run on gvmd, even in live mode, and never checkpointed. A file with no
+    /// usable finding is a validation error and creates no scan.
+    pub async fn import_openvas_report<R: Read>(&self, reader: R) -> CryptexResult<ScanReport> {
+        let import = self.begin_openvas_import(reader).await?;
+        self.finish_openvas_import(import).await
+    }
+
+    /// Parse a GVM report XML file and register its scan, without enriching
+    ///
+    /// The first half of [`TheInfiltrator::import_openvas_report`], for
+    /// callers that answer with the scan id before the slow half runs.
+    pub async fn begin_openvas_import<R: Read>(&self, reader: R) -> CryptexResult<OpenvasImport> {
+        let report = parse_openvas_report(reader)?;
+        let hosts = report.hosts();
+        if hosts.is_empty() {
+            return Err(CryptexError::validation(format!(
+                "OpenVAS report has no importable CVE findings ({} results skipped)",
+                report.skipped
+            )));
+        }
+
+        let scan_id = uuid::Uuid::new_v4().to_string();
+        self.register_scan(&scan_id, &hosts, self.default_scan_options()).await?;
+        if let Some(ctx) = self.scans.write().await.get_mut(&scan_id) {
+            ctx.imported = true;
+            if let Some(started) = report.scan_start {
+                ctx.start_time = started.timestamp().max(0) as u64;
+            }
+        }
+
+        Ok(OpenvasImport {
+            report,
+            guard: ImportGuard {
+                scan_id,
+                scans: self.scans.clone(),
+                stats: self.stats.clone(),
+                armed: true,
+            },
+        })
+    }
+
+    /// Enrich an import's findings and end its scan
+    ///
+    /// If this is cancelled part-way, the scan is unregistered again.
+    pub async fn finish_openvas_import(&self, import: OpenvasImport) -> CryptexResult<ScanReport> {
+        let OpenvasImport { report: imported, mut guard } = import;
+        let scan_id = guard.scan_id.clone();
+
+        let mut failed = 0;
+        for finding in &imported.findings {
+            if let Err(e) = self
+                .on_vulnerability_detected(&scan_id, &finding.cve_id, &finding.host, finding.port)
+                .await
+            {
+                tracing::warn!(
+                    "Skipping imported {} on {}:{}: {}",
+                    finding.cve_id,
+                    finding.host,
+                    finding.port,
+                    e
+                );
+                failed += 1;
+            }
+        }
+
+        tracing::info!(
+            "Imported OpenVAS report as scan {}: {} findings, {} skipped",
+            scan_id,
+            imported.findings.len() - failed,
+            imported.skipped + failed
+        );
+        let mut report = self.end_scan(&scan_id).await;
+        guard.armed = false;
+
+        if let (Ok(report), Some(ended)) = (&mut report, imported.scan_end) {
+            report.end_time = Some((ended.timestamp().max(0) as u64).max(report.start_time));
+        }
+        report
+    }
+
+    /// Create and start the scan's task on gvmd, then follow it in the background
+    ///
+    /// Whatever was created on gvmd before a failure is deleted again.
+    async fn launch_live_scan(
+        &self,
+        gmp: &GmpClient,
+        scan_id: &str,
+        targets: &[String],
+    ) -> CryptexResult<()> {
+        let config = gmp.config();
+        let name = format!("CRYPTEX {}", scan_id);
+
+        let mut session = gmp.connect().await?;
+        let target_id = session.create_target(&name, targets, &config.port_list_id).await?;
+        let task_id = match session
+            .create_task(&name, &target_id, &config.scan_config_id, &config.scanner_id)
+            .await
+        {
+            Ok(task_id) => task_id,
+            Err(e) => {
+                if let Err(cleanup) = session.delete_target(&target_id).await {
+                    tracing::warn!("Failed to delete gvmd target {}: {}", target_id, cleanup);
+                }
+                return Err(e);
+            }
+        };
+        let report_id = match session.start_task(&task_id).await {
+            Ok(report_id) => report_id,
+            Err(e) => {
+                if let Err(cleanup) = session.discard_task(&task_id, &target_id).await {
+                    tracing::warn!("Failed to delete gvmd task {}: {}", task_id, cleanup);
+                }
+                return Err(e);
+            }
+        };
+
+        tracing::info!(
+            "Scan {} running on gvmd as task {} (report {})",
+            scan_id,
+            task_id,
+            report_id
+        );
+        self.live_tasks.write().await.insert(
+            scan_id.to_string(),
+            LiveTask {
+                task_id: task_id.clone(),
+                target_id,
+            },
+        );
+
+        let infiltrator = self.clone();
+        let scan_id = scan_id.to_string();
+        let poll_interval = config.poll_interval;
+        let page_size = config.results_page_size;
+        tokio::spawn(async move {
+            infiltrator
+                .follow_live_scan(session, &scan_id, &task_id, poll_interval, page_size)
+                .await
+        });
+
+        Ok(())
+    }
+
+    /// Stop and delete the gvmd task of a live scan that is no longer tracked
+    ///
+    /// Runs in the background; gvmd being unreachable is only logged.
+    async fn release_live_task(&self, scan_id: &str) {
+        let Some(task) = self.live_tasks.write().await.remove(scan_id) else {
+            return;
+        };
+        let Some(gmp) = self.gmp.clone() else {
+            return;
+        };
+
+        let scan_id = scan_id.to_string();
+        tokio::spawn(async move {
+            let discarded = match gmp.connect().await {
+                Ok(mut session) => session.discard_task(&task.task_id, &task.target_id).await,
+                Err(e) => Err(e),
+            };
+            match discarded {
+                Ok(()) => tracing::info!("Deleted gvmd task {} of scan {}", task.task_id, scan_id),
+                Err(e) => tracing::warn!(
+                    "Failed to delete gvmd task {} of scan {}: {}",
+                    task.task_id,
+                    scan_id,
+                    e
+                ),
+            }
+        });
+    }
+
+    /// Feed a gvmd task's new results into the scan until the task finishes
+    ///
+    /// Results are read a page at a time from where the last poll stopped.
+    /// Stops early if the scan is ended here first or gvmd stops answering.
+    /// Results that arrive while the scan is paused are retried on the next
+    /// poll rather than dropped.
+    async fn follow_live_scan(
+        &self,
+        mut session: GmpSession,
+        scan_id: &str,
+        task_id: &str,
+        poll_interval: std::time::Duration,
+        page_size: usize,
+    ) {
+        // Results read so far, which is where the next page starts
+        let mut read = 0;
+        let mut delivered = 0;
+        let mut pending: Vec<GmpResult> = Vec::new();
+
+        loop {
+            if !self.scans.read().await.contains_key(scan_id) {
+                tracing::info!("Scan {} ended; no longer following gvmd task {}", scan_id, task_id);
+                return;
+            }
+
+            // Status first: results read after a finished status are complete
+            let status = match session.task_status(task_id).await {
+                Ok(status) => status,
+                Err(e) => {
+                    tracing::warn!("Lost track of gvmd task {} for scan {}: {}", task_id, scan_id, e);
+                    return;
+                }
+            };
+            loop {
+                match session.get_results(task_id, read + 1, page_size).await {
+                    Ok(page) => {
+                        let full = page.len() >= page_size;
+                        read += page.len();
+                        pending.extend(page);
+                        if !full {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Lost track of gvmd task {} for scan {}: {}",
+                            task_id,
+                            scan_id,
+                            e
+                        );
+                        return;
+                    }
+                }
+            }
+
+            for result in std::mem::take(&mut pending) {
+                if result.cve_ids.is_empty() {
+                    tracing::debug!("Skipping gvmd result {} without a CVE reference", result.id);
+                    continue;
+                }
+
+                let mut complete = true;
+                for cve_id in &result.cve_ids {
+                    match self
+                        .on_vulnerability_detected(scan_id, cve_id, &result.host, result.port)
+                        .await
+                    {
+                        Ok(()) => {}
+                        Err(CryptexError::NotFound(_)) => {
+                            tracing::info!("Scan {} ended; no longer following gvmd task {}", scan_id, task_id);
+                            return;
+                        }
+                        Err(e) => {
+                            tracing::debug!("Deferring gvmd result {}: {}", result.id, e);
+                            complete = false;
+                            break;
+                        }
+                    }
+                }
+                if complete {
+                    delivered += 1;
+                } else {
+                    pending.push(result);
+                }
+            }
+
+            if is_finished(&status) && pending.is_empty() {
+                tracing::info!(
+                    "gvmd task {} for scan {} finished ({}) with {} results",
+                    task_id,
+                    scan_id,
+                    status,
+                    delivered
+                );
+                return;
+            }
+
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
     /// Report a vulnerability detection
     ///
     /// Traditional name: `on_vulnerability_detected`
@@ -79,9 +662,87 @@ impl TheInfiltrator {
         cve_id: &str,
         host: &str,
         port: u16,
+    ) -> CryptexResult<()> {
+        self.record_detection(scan_id, cve_id, host, port, None).await
+    }
+
+    /// Report a vulnerability detection along with the banner the port answered with
+    ///
+    /// The banner is stored on the finding and the product and version it
+    /// advertises are parsed out (see [`crate::service::parse_banner`]).
+    pub async fn on_vulnerability_detected_with_banner(
+        &self,
+        scan_id: &str,
+        cve_id: &str,
+        host: &str,
+        port: u16,
+        banner: &str,
+    ) -> CryptexResult<()> {
+        self.record_detection(scan_id, cve_id, host, port, Some(banner)).await
+    }
+
+    /// Report software the scanner identified, by CPE name
+    ///
+    /// Traditional name: `on_software_detected`
+    ///
+    /// Every CVE the assessor matches to the CPE (see
+    /// [`TheAssessor::match_by_cpe`]) is recorded as a detection on the
+    /// host and port. Returns the matched CVE IDs; software with no known
+    /// CVEs records nothing.
+    ///
+    /// The CPE must name a concrete version. Without one it matches the CVEs
+    /// of every release, which would record each of them as a finding.
+    pub async fn on_cpe_detected(
+        &self,
+        scan_id: &str,
+        cpe: &str,
+        host: &str,
+        port: u16,
+    ) -> CryptexResult<Vec<String>> {
+        if Cpe::parse(cpe)?.version.is_none() {
+            return Err(CryptexError::validation(format!(
+                "CPE '{}' has no version; detections need the version that was found",
+                cpe.trim()
+            )));
+        }
+        let cve_ids = self.assessor.match_by_cpe(cpe).await?;
+        for cve_id in &cve_ids {
+            self.record_detection(scan_id, cve_id, host, port, None).await?;
+        }
+        Ok(cve_ids)
+    }
+
+    async fn record_detection(
+        &self,
+        scan_id: &str,
+        cve_id: &str,
+        host: &str,
+        port: u16,
+        banner: Option<&str>,
     ) -> CryptexResult<()> {
         let start = Instant::now();
 
+        // Reject detections for unknown or paused scans before paying for
+        // any enrichment
+        let (options, granted) = {
+            let mut scans = self.scans.write().await;
+            let ctx = scans
+                .get_mut(scan_id)
+                .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
+            if ctx.paused {
+                return Err(CryptexError::validation(format!(
+                    "Scan {} is paused - detection of {} rejected",
+                    scan_id, cve_id
+                )));
+            }
+            // A repeated detection adds nothing, so skip enrichment for it too
+            if ctx.contains_finding(cve_id, host, port) {
+                tracing::debug!("Ignoring repeated detection of {} on {}:{}", cve_id, host, port);
+                return Ok(());
+            }
+            (ctx.options, ctx.claim_enrichment(cve_id))
+        };
+
         tracing::debug!(
             "Vulnerability detected in scan {}: {} on {}:{}",
             scan_id,
@@ -98,18 +759,38 @@ impl TheInfiltrator {
             "unknown".to_string(), // Would be provided by scanner
             format!("Vulnerability {} detected", cve_id),
         );
+        if let Some(banner) = banner.filter(|banner| !banner.trim().is_empty()) {
+            result.set_banner(banner);
+        }
+
+        if options.is_cut_short_by(&granted) {
+            tracing::debug!("Enrichment budget of scan {} spent - not enriching {}", scan_id, cve_id);
+            result.enrichment_skipped = true;
+        }
 
         // Enhance with vulnerability assessment
-        match self.assessor.assess_vulnerability(cve_id).await {
-            Ok(score) => {
+        let assessment = if granted.assess {
+            Some(self.assessor.assess_vulnerability(cve_id).await)
+        } else {
+            None
+        };
+
+        match assessment {
+            Some(Ok(score)) => {
                 result.vulnerability_score = Some(score.clone());
 
                 // Generate AI remediation guidance if enabled
-                if self.enable_ai_enhancement {
-                    result.remediation_guidance = Some(self.generate_remediation_guidance(&score).await);
-
-                    let mut stats = self.stats.write().await;
-                    stats.ai_enhanced_results += 1;
+                let enhanced = granted.ai_enhancement;
+                if enhanced {
+                    let (guidance, plan) = self.generate_remediation_guidance(&score).await;
+                    result.remediation_plan = Some(plan);
+                    match self.ai_guidance(&score).await {
+                        Some(reply) => {
+                            result.remediation_guidance = Some(reply.content);
+                            result.blend_ai_certainty(reply.certainty);
+                        }
+                        None => result.remediation_guidance = Some(guidance),
+                    }
                 }
 
                 // Update statistics
@@ -126,11 +807,14 @@ impl TheInfiltrator {
                         stats.critical_vulnerabilities_detected += 1;
                     }
 
-                    // Update average enhancement time
-                    let elapsed = start.elapsed().as_millis() as f64;
-                    let total = stats.ai_enhanced_results as f64;
-                    stats.avg_enhancement_time_ms =
-                        (stats.avg_enhancement_time_ms * (total - 1.0) + elapsed) / total;
+                    // Update average enhancement time over enhanced results only
+                    if enhanced {
+                        stats.ai_enhanced_results += 1;
+                        let elapsed = start.elapsed().as_millis() as f64;
+                        let total = stats.ai_enhanced_results as f64;
+                        stats.avg_enhancement_time_ms =
+                            (stats.avg_enhancement_time_ms * (total - 1.0) + elapsed) / total;
+                    }
                 }
 
                 tracing::info!(
@@ -141,37 +825,186 @@ impl TheInfiltrator {
                     score.is_kev()
                 );
             }
-            Err(e) => {
+            Some(Err(e)) => {
                 tracing::warn!("Failed to assess {}: {}", cve_id, e);
             }
+            None => {
+                tracing::debug!("Assessment disabled for scan {} - keeping raw detection", scan_id);
+                self.stats.write().await.total_vulnerabilities_detected += 1;
+            }
         }
 
+        let alert = self.notifier.alert_for(scan_id, &result);
+
         // Add result to scan context
-        {
+        let (checkpoint, flush_at) = {
             let mut scans = self.scans.write().await;
             if let Some(ctx) = scans.get_mut(scan_id) {
-                ctx.add_result(result);
+                if !ctx.add_result(result) {
+                    return Ok(());
+                }
+                ctx.uncommitted += 1;
+
+                let due = ctx.uncommitted >= self.checkpoint_interval
+                    || self
+                        .commit_interval
+                        .is_some_and(|interval| ctx.last_commit.elapsed() >= interval);
+                if self.checkpoint_store.is_none() || ctx.imported {
+                    (None, None)
+                } else if due {
+                    (Some(ScanCheckpoint::take(ctx)), None)
+                } else if ctx.uncommitted == 1 {
+                    // The first result since the last checkpoint starts the clock
+                    (None, self.commit_interval.map(|interval| ctx.last_commit + interval))
+                } else {
+                    (None, None)
+                }
             } else {
                 return Err(CryptexError::not_found(format!("Scan not found: {}", scan_id)));
             }
+        };
+
+        // Persist outside the lock; a failed checkpoint must not lose the detection
+        if let Some(checkpoint) = checkpoint {
+            self.persist_checkpoint(scan_id, checkpoint).await;
+        }
+        if let Some(deadline) = flush_at {
+            self.spawn_commit_timer(scan_id, deadline);
+        }
+
+        if let Some(alert) = alert {
+            self.notifier.dispatch(alert);
         }
 
         Ok(())
     }
 
-    /// End a scan and generate report
+    /// Save a checkpoint taken from a scan's context
     ///
-    /// Traditional name: `end_scan`
-    pub async fn end_scan(&self, scan_id: &str) -> CryptexResult<ScanReport> {
-        tracing::info!("Ending scan: {}", scan_id);
-
-        let report = {
-            let mut scans = self.scans.write().await;
-            let ctx = scans
-                .remove(scan_id)
-                .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
-
-            ctx.generate_report()
+    /// A failed checkpoint is logged and leaves its results to the next one.
+    async fn persist_checkpoint(&self, scan_id: &str, checkpoint: ScanCheckpoint) {
+        let Some(store) = &self.checkpoint_store else {
+            return;
+        };
+        match store.save_checkpoint(&checkpoint) {
+            Ok(()) => {
+                if let Some(ctx) = self.scans.write().await.get_mut(scan_id) {
+                    ctx.checkpointed = ctx.checkpointed.max(checkpoint.total_results());
+                }
+            }
+            Err(e) => tracing::warn!("Failed to checkpoint scan {}: {}", scan_id, e),
+        }
+    }
+
+    /// Checkpoint a scan at `deadline` if results are still waiting then
+    ///
+    /// Without it, results after the last detection of a quiet spell would
+    /// stay unpersisted until the next detection or the end of the scan.
+    fn spawn_commit_timer(&self, scan_id: &str, deadline: tokio::time::Instant) {
+        let Some(interval) = self.commit_interval else {
+            return;
+        };
+        let infiltrator = self.clone();
+        let scan_id = scan_id.to_string();
+
+        tokio::spawn(async move {
+            tokio::time::sleep_until(deadline).await;
+            let checkpoint = {
+                let mut scans = infiltrator.scans.write().await;
+                // Ended, or checkpointed since; a later result set its own timer
+                let Some(ctx) = scans.get_mut(&scan_id) else {
+                    return;
+                };
+                if ctx.uncommitted == 0 || ctx.last_commit.elapsed() < interval {
+                    return;
+                }
+                ScanCheckpoint::take(ctx)
+            };
+            infiltrator.persist_checkpoint(&scan_id, checkpoint).await;
+        });
+    }
+
+    /// Record the service the scanner identified on a host's port
+    ///
+    /// Findings on that port so far carry an inferred service at best; the
+    /// reported one replaces it, and later findings pick it up directly.
+    pub async fn on_service_detected(
+        &self,
+        scan_id: &str,
+        host: &str,
+        port: u16,
+        service_name: &str,
+    ) -> CryptexResult<()> {
+        let mut scans = self.scans.write().await;
+        let ctx = scans
+            .get_mut(scan_id)
+            .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
+
+        tracing::debug!("Service {} detected on {}:{} in scan {}", service_name, host, port, scan_id);
+        ctx.add_service(host, ServiceInfo::new(port, "tcp".to_string(), service_name.to_string()));
+
+        Ok(())
+    }
+
+    /// Record that the scanner has finished with a host
+    ///
+    /// Hosts with findings or services already count toward progress; this
+    /// makes a clean host count too.
+    pub async fn on_host_completed(&self, scan_id: &str, host: &str) -> CryptexResult<()> {
+        let mut scans = self.scans.write().await;
+        let ctx = scans
+            .get_mut(scan_id)
+            .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
+
+        tracing::debug!("Host {} completed in scan {}", host, scan_id);
+        ctx.get_or_create_host(host.to_string());
+
+        Ok(())
+    }
+
+    /// Pause an active scan
+    ///
+    /// Accumulated results are kept; detections are rejected until the scan is resumed.
+    pub async fn pause_scan(&self, scan_id: &str) -> CryptexResult<()> {
+        let mut scans = self.scans.write().await;
+        let ctx = scans
+            .get_mut(scan_id)
+            .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
+
+        ctx.pause();
+        tracing::info!("Scan {} paused with {} results", scan_id, ctx.results.len());
+
+        Ok(())
+    }
+
+    /// Resume a paused scan
+    pub async fn resume_scan(&self, scan_id: &str) -> CryptexResult<()> {
+        let mut scans = self.scans.write().await;
+        let ctx = scans
+            .get_mut(scan_id)
+            .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
+
+        ctx.resume();
+        tracing::info!("Scan {} resumed", scan_id);
+
+        Ok(())
+    }
+
+    /// End a scan and generate report
+    ///
+    /// Traditional name: `end_scan`
+    pub async fn end_scan(&self, scan_id: &str) -> CryptexResult<ScanReport> {
+        tracing::info!("Ending scan: {}", scan_id);
+
+        let (report, final_flush, imported) = {
+            let mut scans = self.scans.write().await;
+            let ctx = scans
+                .remove(scan_id)
+                .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
+
+            let final_flush = (ctx.results.len() > ctx.checkpointed)
+                .then(|| ScanCheckpoint::since(&ctx, ctx.checkpointed));
+            (ctx.generate_report(), final_flush, ctx.imported)
         };
 
         // Update statistics
@@ -179,6 +1012,18 @@ impl TheInfiltrator {
             let mut stats = self.stats.write().await;
             stats.active_scans = stats.active_scans.saturating_sub(1);
         }
+        self.release_live_task(scan_id).await;
+
+        if let Some(store) = self.checkpoint_store.as_ref().filter(|_| !imported) {
+            if let Some(checkpoint) = final_flush {
+                if let Err(e) = store.save_checkpoint(&checkpoint) {
+                    tracing::warn!("Failed to flush final results of scan {}: {}", scan_id, e);
+                }
+            }
+            if let Err(e) = store.mark_complete(scan_id) {
+                tracing::warn!("Failed to mark scan {} complete: {}", scan_id, e);
+            }
+        }
 
         tracing::info!(
             "Scan {} complete: {} vulnerabilities ({} critical, {} KEV)",
@@ -191,6 +1036,26 @@ impl TheInfiltrator {
         Ok(report)
     }
 
+    /// Stop tracking an active scan without reporting or persisting it
+    ///
+    /// For scans about to be deleted. A live scan's gvmd task is stopped and
+    /// deleted along with its target.
+    pub async fn discard_scan(&self, scan_id: &str) -> CryptexResult<()> {
+        self.scans
+            .write()
+            .await
+            .remove(scan_id)
+            .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
+        {
+            let mut stats = self.stats.write().await;
+            stats.active_scans = stats.active_scans.saturating_sub(1);
+        }
+        self.release_live_task(scan_id).await;
+
+        tracing::info!("Scan {} discarded", scan_id);
+        Ok(())
+    }
+
     /// Get active scan context
     pub async fn get_scan_context(&self, scan_id: &str) -> CryptexResult<ScanReport> {
         let scans = self.scans.read().await;
@@ -201,6 +1066,18 @@ impl TheInfiltrator {
         Ok(ctx.generate_report())
     }
 
+    /// How far along an active scan is
+    ///
+    /// Ended scans are no longer tracked here; their progress is final.
+    pub async fn scan_progress(&self, scan_id: &str) -> CryptexResult<ScanProgress> {
+        let scans = self.scans.read().await;
+        let ctx = scans
+            .get(scan_id)
+            .ok_or_else(|| CryptexError::not_found(format!("Scan not found: {}", scan_id)))?;
+
+        Ok(ctx.progress())
+    }
+
     /// Get bridge statistics
     pub async fn get_statistics(&self) -> BridgeStatistics {
         let stats = self.stats.read().await;
@@ -216,11 +1093,41 @@ impl TheInfiltrator {
 
     // Private methods
 
+    /// Ask the agitator, if there is one, for guidance on a finding
+    async fn ai_guidance(&self, score: &VulnerabilityScore) -> Option<TheReply> {
+        let agitator = self.agitator.as_ref()?;
+        let package = ThePackage {
+            data: serde_json::json!({
+                "cve_id": score.cve_id,
+                "name": score.vulnerability_name,
+                "severity": score.severity().as_str(),
+                "cvss_base_score": score.cvss_base_score(),
+                "known_exploited": score.is_kev(),
+                "epss": score.epss.as_ref().map(|epss| epss.score),
+            }),
+            context: Some(REMEDIATION_PROMPT.to_string()),
+        };
+
+        match agitator.the_agitation(package).await {
+            Ok(reply) => Some(reply),
+            Err(e) => {
+                tracing::warn!("AI guidance for {} failed, using template: {}", score.cve_id, e);
+                None
+            }
+        }
+    }
+
     /// Generate AI remediation guidance
-    async fn generate_remediation_guidance(&self, score: &the_assessor::VulnerabilityScore) -> String {
+    ///
+    /// Returns the legacy free-text guidance alongside the structured plan it is rendered from.
+    async fn generate_remediation_guidance(
+        &self,
+        score: &the_assessor::VulnerabilityScore,
+    ) -> (String, RemediationPlan) {
         // In a real implementation, this would call The Collective for AI-generated guidance
         // For now, use template-based guidance
 
+        let plan = RemediationPlan::from_score(score);
         let mut guidance = String::new();
 
         guidance.push_str(&format!(
@@ -244,7 +1151,7 @@ impl TheInfiltrator {
                     guidance.push_str(&format!("   Action: {}\n", action));
                 }
             }
-            guidance.push_str("\n");
+            guidance.push('\n');
         }
 
         if let Some(priority) = &score.ai_priority {
@@ -264,25 +1171,40 @@ impl TheInfiltrator {
         }
 
         guidance.push_str("\nRecommended Actions:\n");
-        match score.severity() {
-            the_assessor::CvssSeverity::Critical | the_assessor::CvssSeverity::High => {
-                guidance.push_str("1. Apply vendor patches immediately\n");
-                guidance.push_str("2. Implement network segmentation if patching is delayed\n");
-                guidance.push_str("3. Monitor for exploitation attempts\n");
-            }
-            the_assessor::CvssSeverity::Medium => {
-                guidance.push_str("1. Schedule patching within 30 days\n");
-                guidance.push_str("2. Verify patch availability\n");
-            }
-            _ => {
-                guidance.push_str("1. Include in regular patch cycle\n");
-            }
+        for step in &plan.steps {
+            guidance.push_str(&format!("{}. {}\n", step.order, step.action));
         }
 
-        guidance
+        (guidance, plan)
     }
 }
 
+/// Longest caller-supplied scan id
+const MAX_SCAN_ID_LEN: usize = 128;
+
+/// Check a caller-supplied scan id
+///
+/// The archive keys results as `scan_id:cve:host:port`, so ids are kept to
+/// characters that cannot be confused with that separator.
+pub fn validate_scan_id(scan_id: &str) -> CryptexResult<()> {
+    if scan_id.is_empty() || scan_id.len() > MAX_SCAN_ID_LEN {
+        return Err(CryptexError::validation(format!(
+            "Scan id must be 1-{} characters",
+            MAX_SCAN_ID_LEN
+        )));
+    }
+    if let Some(c) = scan_id
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !matches!(c, '.' | '_' | '-'))
+    {
+        return Err(CryptexError::validation(format!(
+            "Scan id '{}' contains '{}'; use letters, digits, '.', '_' or '-'",
+            scan_id, c
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,6 +1243,357 @@ mod tests {
         assert!(report.kev_count > 0); // Log4Shell is KEV
     }
 
+    #[tokio::test]
+    async fn test_kev_remediation_plan() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        let scan_id = infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "192.168.1.100", 443)
+            .await
+            .unwrap();
+
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+        let result = &report.scan_results[0];
+
+        // Legacy string is still populated
+        assert!(result.remediation_guidance.is_some());
+
+        let plan = result.remediation_plan.as_ref().unwrap();
+        assert_eq!(plan.estimated_effort, RemediationEffort::Immediate);
+        assert!(!plan.steps.is_empty());
+        assert!(plan.references.iter().any(|r| r.starts_with("https://")));
+        assert!(plan
+            .references
+            .contains(&"https://nvd.nist.gov/vuln/detail/CVE-2021-44228".to_string()));
+
+        let json = serde_json::to_value(result).unwrap();
+        assert_eq!(json["remediation_plan"]["estimated_effort"], "immediate");
+    }
+
+    /// Answers every inquiry with the same certainty
+    struct SureAgitator(f64);
+
+    #[async_trait::async_trait]
+    impl TheAgitator for SureAgitator {
+        fn agitator_type(&self) -> the_foundation::AgitatorType {
+            the_foundation::AgitatorType::Claude
+        }
+
+        fn the_mind(&self) -> &str {
+            "stub"
+        }
+
+        async fn the_agitation(&self, package: ThePackage) -> CryptexResult<TheReply> {
+            Ok(TheReply {
+                inquiry_id: String::new(),
+                content: format!("Patch {}", package.data["cve_id"].as_str().unwrap_or("it")),
+                certainty: self.0,
+                agitator_used: the_foundation::AgitatorType::Claude,
+                the_mind: "stub".to_string(),
+                duration_ms: 0,
+                tokens_used: None,
+                replied_at_ts: 0,
+            })
+        }
+
+        async fn the_validation(&self) -> CryptexResult<bool> {
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ai_guidance_leaves_missing_confidence_unset() {
+        let enhance = |certainty: f64| async move {
+            let infiltrator = TheInfiltrator::the_awakening()
+                .await
+                .unwrap()
+                .with_agitator(Arc::new(SureAgitator(certainty)));
+            let scan_id = infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+            infiltrator
+                .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "192.168.1.100", 443)
+                .await
+                .unwrap();
+            infiltrator.end_scan(&scan_id).await.unwrap().scan_results.remove(0)
+        };
+
+        let sure = enhance(0.95).await;
+        let unsure = enhance(0.2).await;
+        assert_eq!(sure.remediation_guidance.as_deref(), Some("Patch CVE-2021-44228"));
+        assert!(sure.remediation_plan.is_some());
+
+        // The scanner reported no confidence, so there is nothing to lower
+        // and no level for certainty to invent
+        assert_eq!(sure.confidence, None);
+        assert_eq!(unsure.confidence, None);
+        assert!(!unsure.meets_confidence(ConfidenceLevel::Low));
+
+        // Without an agitator the template guidance leaves confidence unset
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+        let scan_id = infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "192.168.1.100", 443)
+            .await
+            .unwrap();
+        let template = infiltrator.end_scan(&scan_id).await.unwrap().scan_results.remove(0);
+        assert!(template.confidence.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_critical_finding_notifies() {
+        use crate::notify::{FindingAlert, GenericWebhookSink, Notifier};
+        use axum::{extract::State, routing::post, Json, Router};
+        use tokio::sync::mpsc;
+
+        let (sent, mut received) = mpsc::unbounded_channel::<FindingAlert>();
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(|State(sent): State<mpsc::UnboundedSender<FindingAlert>>,
+                      Json(alert): Json<FindingAlert>| async move {
+                    let _ = sent.send(alert);
+                }),
+            )
+            .with_state(sent);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let sink = GenericWebhookSink::new(url, &the_foundation::HttpSettings::default()).unwrap();
+        let infiltrator = TheInfiltrator::the_awakening()
+            .await
+            .unwrap()
+            .with_notifier(Notifier::default().with_sink(Arc::new(sink)));
+        let scan_id = infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+
+        // No CVSS data and not in KEV: below the critical threshold
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "192.168.1.101", 80)
+            .await
+            .unwrap();
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "192.168.1.100", 443)
+            .await
+            .unwrap();
+
+        let alert = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alert.scan_id, scan_id);
+        assert_eq!(alert.cve_id, "CVE-2021-44228");
+        assert_eq!(alert.severity, the_assessor::CvssSeverity::Critical);
+        assert!(alert.known_exploited);
+
+        // Give a stray alert for the unscored finding time to arrive
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(received.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scan_options_override_enrichment() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+        let targets = ["192.168.1.0/24".to_string()];
+
+        let detect_one = |scan_id: String| {
+            let infiltrator = &infiltrator;
+            async move {
+                infiltrator
+                    .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "192.168.1.100", 443)
+                    .await
+                    .unwrap();
+                infiltrator.end_scan(&scan_id).await.unwrap().scan_results.remove(0)
+            }
+        };
+
+        let default_scan = infiltrator.start_scan(&targets[0]).await.unwrap();
+        let deep = detect_one(default_scan).await;
+        assert!(deep.remediation_guidance.is_some());
+
+        let fast = ScanOptions {
+            ai_enhancement: false,
+            ..ScanOptions::default()
+        };
+        let fast_scan = infiltrator.start_scan_with_options(&targets, fast).await.unwrap();
+        let fast = detect_one(fast_scan).await;
+        assert!(fast.remediation_guidance.is_none());
+        assert!(fast.remediation_plan.is_none());
+        assert!(fast.vulnerability_score.is_some());
+
+        let raw = ScanOptions {
+            ai_enhancement: true,
+            assess: false,
+            ..ScanOptions::default()
+        };
+        let raw_scan = infiltrator.start_scan_with_options(&targets, raw).await.unwrap();
+        let raw = detect_one(raw_scan).await;
+        assert!(raw.vulnerability_score.is_none());
+        assert!(raw.remediation_guidance.is_none());
+
+        let stats = infiltrator.get_statistics().await;
+        assert_eq!(stats.total_vulnerabilities_detected, 3);
+        assert_eq!(stats.ai_enhanced_results, 1);
+        assert!(stats.avg_enhancement_time_ms.is_finite());
+    }
+
+    #[tokio::test]
+    async fn test_enrichment_budget_caps_ai_enhancements() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+        let targets = ["10.0.0.0/24".to_string()];
+        let cve_ids: Vec<String> = (1..=5).map(|i| format!("CVE-2024-{:04}", i)).collect();
+
+        let options = ScanOptions::default().max_ai_enhancements(2);
+        let scan_id = infiltrator.start_scan_with_options(&targets, options).await.unwrap();
+        for cve_id in &cve_ids {
+            infiltrator
+                .on_vulnerability_detected(&scan_id, cve_id, "10.0.0.5", 443)
+                .await
+                .unwrap();
+        }
+        // A CVE already enhanced costs nothing more on another host
+        infiltrator
+            .on_vulnerability_detected(&scan_id, &cve_ids[0], "10.0.0.6", 443)
+            .await
+            .unwrap();
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+
+        let enhanced: Vec<&str> = report
+            .scan_results
+            .iter()
+            .filter(|r| r.remediation_guidance.is_some())
+            .map(|r| r.cve_id.as_str())
+            .collect();
+        assert_eq!(enhanced, [&cve_ids[0], &cve_ids[1], &cve_ids[0]]);
+        for result in &report.scan_results {
+            assert!(result.vulnerability_score.is_some(), "{}", result.cve_id);
+            assert_eq!(result.enrichment_skipped, result.remediation_guidance.is_none());
+        }
+        assert_eq!(report.ai_enhanced_count, 3);
+        assert_eq!(report.enrichment_skipped_count, 3);
+
+        // Without assessments left, findings are kept as raw detections
+        let options = ScanOptions::default().max_assessments(1);
+        let scan_id = infiltrator.start_scan_with_options(&targets, options).await.unwrap();
+        for cve_id in &cve_ids[..3] {
+            infiltrator
+                .on_vulnerability_detected(&scan_id, cve_id, "10.0.0.5", 443)
+                .await
+                .unwrap();
+        }
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+        let assessed = report.scan_results.iter().filter(|r| r.vulnerability_score.is_some());
+        assert_eq!(assessed.count(), 1);
+        assert_eq!(report.enrichment_skipped_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_cpe_detection_records_matched_cves() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+        let scan_id = infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+
+        let cpe = "cpe:2.3:a:apache:http_server:2.4.49:*:*:*:*:*:*:*";
+        let matched = infiltrator.on_cpe_detected(&scan_id, cpe, "10.0.0.5", 80).await.unwrap();
+        assert_eq!(matched, ["CVE-2021-41773", "CVE-2021-42013"]);
+        let unknown = "cpe:/a:example:quiet_daemon:1.0";
+        let nothing = infiltrator.on_cpe_detected(&scan_id, unknown, "10.0.0.5", 81).await.unwrap();
+        assert!(nothing.is_empty());
+        let versionless = "cpe:/a:apache:log4j";
+        let err = infiltrator.on_cpe_detected(&scan_id, versionless, "10.0.0.5", 82).await;
+        assert!(matches!(err, Err(CryptexError::ValidationError(_))));
+
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+        let found: Vec<&str> = report.scan_results.iter().map(|r| r.cve_id.as_str()).collect();
+        assert_eq!(found, matched);
+    }
+
+    #[tokio::test]
+    async fn test_detection_banner_is_parsed() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+        let scan_id = infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+
+        let banner = "Apache/2.4.49 (Unix) OpenSSL/1.1.1k mod_fcgid/2.3.9";
+        infiltrator
+            .on_vulnerability_detected_with_banner(
+                &scan_id,
+                "CVE-2021-41773",
+                "10.0.0.5",
+                8081,
+                banner,
+            )
+            .await
+            .unwrap();
+        infiltrator
+            .on_vulnerability_detected_with_banner(
+                &scan_id,
+                "CVE-2024-0001",
+                "10.0.0.5",
+                2222,
+                "Welcome to the lab - authorized use only",
+            )
+            .await
+            .unwrap();
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+
+        let apache = &report.scan_results[0];
+        assert_eq!(apache.banner.as_deref(), Some(banner));
+        assert_eq!(apache.product.as_deref(), Some("Apache"));
+        assert_eq!(apache.version.as_deref(), Some("2.4.49"));
+
+        let free_form = &report.scan_results[1];
+        assert_eq!(free_form.banner.as_deref(), Some("Welcome to the lab - authorized use only"));
+        assert_eq!((free_form.product.as_ref(), free_form.version.as_ref()), (None, None));
+    }
+
+    #[tokio::test]
+    async fn test_start_scan_validates_targets() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        for target in ["10.0.0.5", "10.0.0.0/8", "fe80::1", "scanner.example.org, 10.0.0.6"] {
+            assert!(infiltrator.start_scan(target).await.is_ok(), "{}", target);
+        }
+
+        for target in ["192.168.1.0/33", "htp://x", "10.0.0.5, bad host"] {
+            let err = infiltrator.start_scan(target).await.unwrap_err();
+            assert!(matches!(err, CryptexError::ValidationError(_)), "{}", target);
+        }
+        let multi = ["10.0.0.0/24".to_string(), "not_a_host".to_string()];
+        assert!(infiltrator.start_scan_multi(&multi).await.is_err());
+
+        // Rejected targets never become scans
+        assert_eq!(infiltrator.get_statistics().await.total_scans, 4);
+    }
+
+    #[tokio::test]
+    async fn test_start_scan_with_id() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        let scan_id = infiltrator
+            .start_scan_with_id("nightly-web.2026", "10.0.0.0/24")
+            .await
+            .unwrap();
+        assert_eq!(scan_id, "nightly-web.2026");
+        assert_eq!(infiltrator.get_scan_context(&scan_id).await.unwrap().target, "10.0.0.0/24");
+
+        // Taken while the scan runs, free again once it has ended
+        let err = infiltrator
+            .start_scan_with_id("nightly-web.2026", "10.0.1.0/24")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CryptexError::ValidationError(_)));
+        assert_eq!(infiltrator.get_scan_context(&scan_id).await.unwrap().target, "10.0.0.0/24");
+
+        infiltrator.end_scan(&scan_id).await.unwrap();
+        assert!(infiltrator
+            .start_scan_with_id("nightly-web.2026", "10.0.1.0/24")
+            .await
+            .is_ok());
+
+        for bad in ["", "scan:1", "scan 1", &"x".repeat(129)] {
+            assert!(infiltrator.start_scan_with_id(bad, "10.0.0.5").await.is_err(), "{:?}", bad);
+        }
+    }
+
     #[tokio::test]
     async fn test_statistics() {
         let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
@@ -337,6 +1610,45 @@ mod tests {
         assert!(stats.total_vulnerabilities_detected > 0);
     }
 
+    #[tokio::test]
+    async fn test_pause_rejects_detections() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        let scan_id = infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "192.168.1.100", 443)
+            .await
+            .unwrap();
+
+        infiltrator.pause_scan(&scan_id).await.unwrap();
+
+        let result = infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "192.168.1.101", 80)
+            .await;
+        assert!(matches!(result, Err(CryptexError::ValidationError(_))));
+
+        infiltrator.resume_scan(&scan_id).await.unwrap();
+
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "192.168.1.101", 80)
+            .await
+            .unwrap();
+
+        // Results accumulated before the pause survive
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+        assert_eq!(report.total_vulnerabilities, 2);
+        assert_eq!(report.scan_results[0].cve_id, "CVE-2021-44228");
+    }
+
+    #[tokio::test]
+    async fn test_pause_unknown_scan() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        assert!(infiltrator.pause_scan("non-existent-scan").await.is_err());
+        assert!(infiltrator.resume_scan("non-existent-scan").await.is_err());
+    }
+
     #[tokio::test]
     async fn test_scan_not_found() {
         let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
@@ -344,4 +1656,436 @@ mod tests {
         let result = infiltrator.end_scan("non-existent-scan").await;
         assert!(result.is_err());
     }
+
+    /// In-memory checkpoint store for tests
+    #[derive(Default)]
+    struct MemoryStore {
+        checkpoints: std::sync::Mutex<HashMap<String, ScanCheckpoint>>,
+        saves: std::sync::atomic::AtomicUsize,
+    }
+
+    impl CheckpointStore for MemoryStore {
+        fn save_checkpoint(&self, checkpoint: &ScanCheckpoint) -> CryptexResult<()> {
+            self.saves.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let mut checkpoints = self.checkpoints.lock().unwrap();
+            let saved = checkpoints
+                .entry(checkpoint.scan_id.clone())
+                .or_insert_with(|| ScanCheckpoint { results: Vec::new(), ..checkpoint.clone() });
+            saved.paused = checkpoint.paused;
+            saved.results.truncate(checkpoint.persisted);
+            saved.results.extend(checkpoint.results.iter().cloned());
+            Ok(())
+        }
+
+        fn load_incomplete(&self) -> CryptexResult<Vec<ScanCheckpoint>> {
+            Ok(self.checkpoints.lock().unwrap().values().cloned().collect())
+        }
+
+        fn mark_complete(&self, scan_id: &str) -> CryptexResult<()> {
+            self.checkpoints.lock().unwrap().remove(scan_id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_recovery() {
+        let store = Arc::new(MemoryStore::default());
+
+        let infiltrator = TheInfiltrator::the_awakening_with_checkpoints(store.clone(), 2)
+            .await
+            .unwrap();
+        let scan_id = infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+
+        for (i, cve) in ["CVE-2021-44228", "CVE-2024-0001", "CVE-2024-0002"].iter().enumerate() {
+            infiltrator
+                .on_vulnerability_detected(&scan_id, cve, &format!("192.168.1.{}", i), 443)
+                .await
+                .unwrap();
+        }
+
+        // Checkpoint taken after the second detection only
+        assert_eq!(store.saves.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+        // Simulate a crash
+        drop(infiltrator);
+
+        let infiltrator = TheInfiltrator::the_awakening_with_checkpoints(store.clone(), 2)
+            .await
+            .unwrap();
+        let report = infiltrator.get_scan_context(&scan_id).await.unwrap();
+        assert_eq!(report.total_vulnerabilities, 2);
+        assert_eq!(infiltrator.get_statistics().await.active_scans, 1);
+
+        // Completed scans are not recovered again
+        infiltrator.end_scan(&scan_id).await.unwrap();
+        assert!(store.load_incomplete().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_commit_every_with_final_flush() {
+        let store = Arc::new(MemoryStore::default());
+        let infiltrator = TheInfiltrator::the_awakening()
+            .await
+            .unwrap()
+            .with_checkpointing(store.clone(), 10);
+        let scan_id = infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+
+        for i in 0..15 {
+            infiltrator
+                .on_vulnerability_detected(&scan_id, "CVE-2024-0001", &format!("10.0.0.{}", i), 443)
+                .await
+                .unwrap();
+        }
+
+        // One commit mid-scan, after the tenth result
+        assert_eq!(store.saves.load(std::sync::atomic::Ordering::Relaxed), 1);
+        assert_eq!(store.checkpoints.lock().unwrap()[&scan_id].results.len(), 10);
+
+        // Ending the scan flushes the remaining five before completing it
+        let flushed = Arc::new(std::sync::Mutex::new(None));
+        struct Spy(Arc<MemoryStore>, Arc<std::sync::Mutex<Option<usize>>>);
+        impl CheckpointStore for Spy {
+            fn save_checkpoint(&self, checkpoint: &ScanCheckpoint) -> CryptexResult<()> {
+                *self.1.lock().unwrap() = Some(checkpoint.results.len());
+                self.0.save_checkpoint(checkpoint)
+            }
+            fn load_incomplete(&self) -> CryptexResult<Vec<ScanCheckpoint>> {
+                self.0.load_incomplete()
+            }
+            fn mark_complete(&self, scan_id: &str) -> CryptexResult<()> {
+                self.0.mark_complete(scan_id)
+            }
+        }
+        let infiltrator = TheInfiltrator {
+            checkpoint_store: Some(Arc::new(Spy(store.clone(), flushed.clone()))),
+            ..infiltrator
+        };
+        infiltrator.end_scan(&scan_id).await.unwrap();
+
+        assert_eq!(store.saves.load(std::sync::atomic::Ordering::Relaxed), 2);
+        assert_eq!(*flushed.lock().unwrap(), Some(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_commit_interval_bounds_checkpoint_age() {
+        let store = Arc::new(MemoryStore::default());
+        let infiltrator = TheInfiltrator::the_awakening()
+            .await
+            .unwrap()
+            .with_checkpointing(store.clone(), 100)
+            .with_commit_interval(Duration::from_secs(30));
+        let scan_id = infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+        let saves = || store.saves.load(std::sync::atomic::Ordering::Relaxed);
+
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.1", 443)
+            .await
+            .unwrap();
+        assert_eq!(saves(), 0);
+
+        tokio::time::advance(Duration::from_secs(31)).await;
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.2", 443)
+            .await
+            .unwrap();
+        assert_eq!(saves(), 1);
+
+        // The clock restarts at each checkpoint
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.3", 443)
+            .await
+            .unwrap();
+        assert_eq!(saves(), 1);
+
+        // Nothing left to flush after a checkpoint covering every result
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+        assert_eq!(report.total_vulnerabilities, 3);
+        assert_eq!(saves(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_commit_interval_flushes_quiet_scan() {
+        let store = Arc::new(MemoryStore::default());
+        let infiltrator = TheInfiltrator::the_awakening()
+            .await
+            .unwrap()
+            .with_checkpointing(store.clone(), 100)
+            .with_commit_interval(Duration::from_secs(30));
+        let scan_id = infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+        let saves = || store.saves.load(std::sync::atomic::Ordering::Relaxed);
+        let detect = |host: &'static str| {
+            let infiltrator = infiltrator.clone();
+            let scan_id = scan_id.clone();
+            async move {
+                infiltrator
+                    .on_vulnerability_detected(&scan_id, "CVE-2024-0001", host, 443)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        // No further detection arrives, yet the result is saved in time
+        detect("10.0.0.1").await;
+        tokio::time::sleep(Duration::from_secs(29)).await;
+        assert_eq!(saves(), 0);
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(saves(), 1);
+        assert_eq!(store.checkpoints.lock().unwrap()[&scan_id].results.len(), 1);
+
+        // The next result waits its own interval, counted from that checkpoint
+        detect("10.0.0.2").await;
+        detect("10.0.0.3").await;
+        tokio::time::sleep(Duration::from_secs(29)).await;
+        assert_eq!(saves(), 1);
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(saves(), 2);
+        assert_eq!(store.checkpoints.lock().unwrap()[&scan_id].results.len(), 3);
+
+        // Everything was saved already, so ending the scan flushes nothing
+        infiltrator.end_scan(&scan_id).await.unwrap();
+        assert_eq!(saves(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_multi_target_scan() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        let targets = vec!["192.168.1.0/24".to_string(), "10.0.0.0/24".to_string()];
+        let scan_id = infiltrator.start_scan_multi(&targets).await.unwrap();
+        assert_eq!(infiltrator.get_statistics().await.total_scans, 1);
+
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "192.168.1.100", 443)
+            .await
+            .unwrap();
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.7", 80)
+            .await
+            .unwrap();
+
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+        assert_eq!(report.scan_id, scan_id);
+        assert_eq!(report.targets, targets);
+        assert_eq!(report.total_hosts, 2);
+        assert_eq!(report.total_vulnerabilities, 2);
+        assert!(report.target_progress.iter().all(|p| p.hosts == 1 && p.results == 1));
+
+        assert!(infiltrator.start_scan_multi(&[]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_detection_for_unknown_scan_is_not_enriched() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        let err = infiltrator
+            .on_vulnerability_detected("no-such-scan", "CVE-2021-44228", "10.0.0.5", 443)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CryptexError::NotFound(_)));
+
+        let stats = infiltrator.get_statistics().await;
+        assert_eq!(stats.total_vulnerabilities_detected, 0);
+        assert_eq!(stats.cache_hits + stats.cache_misses, 0);
+    }
+
+    #[tokio::test]
+    async fn test_import_openvas_report() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        let report = infiltrator
+            .import_openvas_report(crate::import::tests::FIXTURE_REPORT.as_bytes())
+            .await
+            .unwrap();
+        assert_eq!(report.total_vulnerabilities, 3);
+        assert_eq!(report.targets, ["10.0.0.5", "10.0.0.6"]);
+        assert_eq!(report.start_time, 1683018867);
+        assert!(report.scan_results.iter().all(|r| r.vulnerability_score.is_some()));
+        assert!(report.kev_count > 0); // Log4Shell is KEV
+
+        // The scan is already over
+        assert!(infiltrator.get_scan_context(&report.scan_id).await.is_err());
+        assert_eq!(infiltrator.get_statistics().await.active_scans, 0);
+
+        let err = infiltrator
+            .import_openvas_xml("<report><results/></report>".as_bytes())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CryptexError::ValidationError(_)));
+        assert_eq!(infiltrator.get_statistics().await.total_scans, 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_ends_when_the_report_did() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+
+        let report = infiltrator
+            .import_openvas_report(crate::import::tests::FIXTURE_REPORT.as_bytes())
+            .await
+            .unwrap();
+        // scan_end in the fixture
+        assert_eq!(report.end_time, Some(1683021771));
+        assert_eq!(report.status, ScanStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_import_unregisters_its_scan() {
+        let store = Arc::new(MemoryStore::default());
+        let infiltrator = TheInfiltrator::the_awakening()
+            .await
+            .unwrap()
+            .with_checkpointing(store.clone(), 1);
+
+        let import = infiltrator
+            .begin_openvas_import(crate::import::tests::FIXTURE_REPORT.as_bytes())
+            .await
+            .unwrap();
+        let scan_id = import.scan_id().to_string();
+        assert_eq!(import.findings(), 3);
+        assert_eq!(infiltrator.get_statistics().await.active_scans, 1);
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "10.0.0.5", 443)
+            .await
+            .unwrap();
+
+        // Dropped half-imported, as when the task running it is cancelled
+        drop(import);
+        for _ in 0..100 {
+            if infiltrator.get_statistics().await.active_scans == 0 {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+
+        assert_eq!(infiltrator.get_statistics().await.active_scans, 0);
+        assert!(infiltrator.get_scan_context(&scan_id).await.is_err());
+        // Imports are never checkpointed, so a restart resumes nothing
+        assert!(store.load_incomplete().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scan_progress_advances_with_detections() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+        let targets = vec!["10.0.0.0/31".to_string(), "10.0.1.5".to_string()];
+        let scan_id = infiltrator.start_scan_multi(&targets).await.unwrap();
+
+        let progress = infiltrator.scan_progress(&scan_id).await.unwrap();
+        assert_eq!(progress, ScanProgress::running(3, 0, 0));
+        assert_eq!(progress.percent, 0);
+
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "10.0.0.0", 443)
+            .await
+            .unwrap();
+        infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.0", 80)
+            .await
+            .unwrap();
+        let progress = infiltrator.scan_progress(&scan_id).await.unwrap();
+        assert_eq!((progress.hosts_completed, progress.findings_so_far), (1, 2));
+        assert_eq!(progress.percent, 33);
+
+        // Every host done is still short of 100% until the scan ends
+        infiltrator.on_host_completed(&scan_id, "10.0.0.1").await.unwrap();
+        infiltrator.on_host_completed(&scan_id, "10.0.1.5").await.unwrap();
+        let progress = infiltrator.scan_progress(&scan_id).await.unwrap();
+        assert_eq!((progress.hosts_completed, progress.hosts_total), (3, 3));
+        assert_eq!(progress.percent, 99);
+
+        infiltrator.end_scan(&scan_id).await.unwrap();
+        assert!(matches!(
+            infiltrator.scan_progress(&scan_id).await,
+            Err(CryptexError::NotFound(_))
+        ));
+        assert!(infiltrator.on_host_completed(&scan_id, "10.0.0.1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_detected_service_wins_over_inference() {
+        let infiltrator = TheInfiltrator::the_awakening().await.unwrap();
+        let scan_id = infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+
+        for port in [443, 8443] {
+            infiltrator
+                .on_vulnerability_detected(&scan_id, "CVE-2021-44228", "192.168.1.100", port)
+                .await
+                .unwrap();
+        }
+        infiltrator
+            .on_service_detected(&scan_id, "192.168.1.100", 8443, "tomcat")
+            .await
+            .unwrap();
+
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+        let services: Vec<Option<&str>> =
+            report.scan_results.iter().map(|r| r.service.as_deref()).collect();
+        assert_eq!(services, [Some("https"), Some("tomcat")]);
+    }
+
+    #[tokio::test]
+    async fn test_live_scan_ingests_gvmd_results() {
+        use crate::gmp::tests::MockGvmd;
+
+        let gvmd = MockGvmd::start().await;
+        let infiltrator = TheInfiltrator::the_awakening()
+            .await
+            .unwrap()
+            .with_gmp(GmpClient::new(gvmd.config("secret")));
+        assert!(infiltrator.is_live());
+
+        let scan_id = infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+
+        let mut report = infiltrator.get_scan_context(&scan_id).await.unwrap();
+        for _ in 0..100 {
+            if report.total_vulnerabilities == 3 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            report = infiltrator.get_scan_context(&scan_id).await.unwrap();
+        }
+
+        // Two CVEs on one result become two findings; the CVE-less one is skipped
+        let mut found: Vec<(&str, &str, u16)> = report
+            .scan_results
+            .iter()
+            .map(|r| (r.cve_id.as_str(), r.host.as_str(), r.port))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("CVE-2021-44228", "10.0.0.5", 443),
+                ("CVE-2024-0001", "10.0.0.6", 0),
+                ("CVE-2024-0002", "10.0.0.6", 0),
+            ]
+        );
+
+        let commands = gvmd.commands.lock().unwrap().clone();
+        assert!(commands[1].contains(&format!("<name>CRYPTEX {}</name>", scan_id)));
+
+        // The task is done, so nothing is delivered twice
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let report = infiltrator.end_scan(&scan_id).await.unwrap();
+        assert_eq!(report.total_vulnerabilities, 3);
+
+        // Ending the scan removes its task and target from gvmd
+        let cleaned_up = |commands: &[String]| {
+            commands.iter().any(|c| c.contains("<delete_task task_id=\"task-1\""))
+                && commands.iter().any(|c| c.contains("<delete_target target_id=\"target-1\""))
+        };
+        for _ in 0..100 {
+            if cleaned_up(&gvmd.commands.lock().unwrap()) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(cleaned_up(&gvmd.commands.lock().unwrap()));
+
+        // A scan gvmd refuses is not left behind
+        let refused = TheInfiltrator::the_awakening()
+            .await
+            .unwrap()
+            .with_gmp(GmpClient::new(gvmd.config("wrong")));
+        assert!(refused.start_scan("10.0.0.0/24").await.is_err());
+        assert_eq!(refused.get_statistics().await.active_scans, 0);
+    }
 }
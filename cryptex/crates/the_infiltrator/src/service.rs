@@ -0,0 +1,206 @@
+//! Service Inference - Naming the service behind a port
+//!
+//! Traditional name: `ServiceDetector`
+//!
+//! Detections arrive as host and port; the scanner only sometimes says what
+//! is listening there. Until it does, findings are labeled from a table of
+//! well-known ports, or from a banner when one was captured. An inferred
+//! label is marked as such on the result so a service the scanner reports
+//! later replaces it.
+//!
+//! A banner usually names the software behind the service as well. The
+//! product and version it advertises are pulled out as hints for matching
+//! against CPEs; the banner itself is kept as captured.
+
+/// Services conventionally bound to well-known ports
+pub const WELL_KNOWN_PORTS: &[(u16, &str)] = &[
+    (21, "ftp"),
+    (22, "ssh"),
+    (23, "telnet"),
+    (25, "smtp"),
+    (53, "dns"),
+    (80, "http"),
+    (110, "pop3"),
+    (111, "rpcbind"),
+    (135, "msrpc"),
+    (139, "netbios-ssn"),
+    (143, "imap"),
+    (161, "snmp"),
+    (389, "ldap"),
+    (443, "https"),
+    (445, "microsoft-ds"),
+    (465, "smtps"),
+    (587, "submission"),
+    (636, "ldaps"),
+    (993, "imaps"),
+    (995, "pop3s"),
+    (1433, "ms-sql"),
+    (1521, "oracle"),
+    (2049, "nfs"),
+    (3306, "mysql"),
+    (3389, "rdp"),
+    (5432, "postgresql"),
+    (5900, "vnc"),
+    (6379, "redis"),
+    (8080, "http-alt"),
+    (8443, "https-alt"),
+    (9200, "elasticsearch"),
+    (27017, "mongodb"),
+];
+
+/// Banner prefixes that identify a service regardless of port
+const BANNER_HINTS: &[(&str, &str)] = &[
+    ("ssh-", "ssh"),
+    ("http/", "http"),
+    ("+ok", "pop3"),
+    ("* ok", "imap"),
+    ("rfb ", "vnc"),
+    ("220", "smtp"),
+];
+
+/// Name of the service most likely listening on `port`
+///
+/// A captured banner is a stronger hint than the port, so it is tried first.
+/// A `220` greeting mentioning FTP is told apart from an SMTP one.
+pub fn infer_service(port: u16, banner: Option<&str>) -> Option<&'static str> {
+    banner
+        .and_then(service_from_banner)
+        .or_else(|| service_from_port(port))
+}
+
+fn service_from_port(port: u16) -> Option<&'static str> {
+    WELL_KNOWN_PORTS
+        .iter()
+        .find(|(known, _)| *known == port)
+        .map(|(_, service)| *service)
+}
+
+fn service_from_banner(banner: &str) -> Option<&'static str> {
+    let banner = banner.trim_start().to_ascii_lowercase();
+    if banner.starts_with("220") && banner.contains("ftp") {
+        return Some("ftp");
+    }
+
+    BANNER_HINTS
+        .iter()
+        .find(|(prefix, _)| banner.starts_with(prefix))
+        .map(|(_, service)| *service)
+}
+
+/// Protocols whose `NAME/version` tokens describe the protocol, not the software
+const PROTOCOL_TOKENS: &[&str] = &["http", "https", "rtsp", "sip"];
+
+/// Software a banner advertises
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductHint {
+    pub product: String,
+    pub version: Option<String>,
+}
+
+/// Product and version advertised by a banner, if it names any
+///
+/// Three shapes are recognized, in this order:
+/// - SSH identification strings: `SSH-2.0-OpenSSH_9.6p1 Ubuntu-3`
+/// - `Product/version` tokens, as in HTTP `Server` headers: `Apache/2.4.49 (Unix)`.
+///   The first one wins; protocol tokens such as `HTTP/1.1` are passed over.
+/// - A word followed by a version number: `220 ProFTPD 1.3.5 Server`
+pub fn parse_banner(banner: &str) -> Option<ProductHint> {
+    let banner = banner.trim();
+    if let Some(hint) = ssh_software(banner) {
+        return Some(hint);
+    }
+
+    let words: Vec<&str> = banner
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| "()[],;\"".contains(c)))
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let slashed = words.iter().find_map(|word| {
+        let (product, version) = word.split_once('/')?;
+        let is_product = is_product_name(product)
+            && !PROTOCOL_TOKENS.iter().any(|p| p.eq_ignore_ascii_case(product));
+        (is_product && is_version(version)).then(|| hint(product, Some(version)))
+    });
+    if slashed.is_some() {
+        return slashed;
+    }
+
+    words.windows(2).find_map(|pair| {
+        (is_product_name(pair[0]) && is_version(pair[1])).then(|| hint(pair[0], Some(pair[1])))
+    })
+}
+
+/// Software field of an SSH identification string
+fn ssh_software(banner: &str) -> Option<ProductHint> {
+    let rest = banner.strip_prefix("SSH-").or_else(|| banner.strip_prefix("ssh-"))?;
+    let (_protocol, software) = rest.split_once('-')?;
+    let software = software.split_whitespace().next()?;
+
+    match software.split_once('_') {
+        Some((product, version)) if is_version(version) => Some(hint(product, Some(version))),
+        _ => Some(hint(software, None)),
+    }
+}
+
+fn hint(product: &str, version: Option<&str>) -> ProductHint {
+    ProductHint {
+        product: product.to_string(),
+        version: version.map(str::to_string),
+    }
+}
+
+/// A name starting with a letter, e.g. `Apache` or `Microsoft-IIS`
+fn is_product_name(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_alphabetic())
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// A dotted version number, possibly with a suffix, e.g. `2.4.49` or `9.6p1`
+fn is_version(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_digit())
+        && word.contains('.')
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+' | '~'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_from_port_and_banner() {
+        assert_eq!(infer_service(443, None), Some("https"));
+        assert_eq!(infer_service(22, None), Some("ssh"));
+        assert_eq!(infer_service(31337, None), None);
+
+        // The banner wins over the port
+        assert_eq!(infer_service(2222, Some("SSH-2.0-OpenSSH_9.6")), Some("ssh"));
+        assert_eq!(infer_service(80, Some("220 ProFTPD Server ready")), Some("ftp"));
+        assert_eq!(infer_service(2525, Some("220 mail.example.com ESMTP")), Some("smtp"));
+        assert_eq!(infer_service(443, Some("garbage")), Some("https"));
+    }
+
+    #[test]
+    fn test_parse_common_banners() {
+        let parsed = |banner| parse_banner(banner).map(|h| (h.product, h.version));
+        let found = |product: &str, version: &str| {
+            Some((product.to_string(), Some(version.to_string())))
+        };
+
+        assert_eq!(parsed("Apache/2.4.49 (Unix) OpenSSL/1.1.1k"), found("Apache", "2.4.49"));
+        assert_eq!(parsed("HTTP/1.1 200 OK Server: nginx/1.18.0"), found("nginx", "1.18.0"));
+        assert_eq!(parsed("SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13"), found("OpenSSH", "9.6p1"));
+        assert_eq!(parsed("220 ProFTPD 1.3.5 Server (Debian)"), found("ProFTPD", "1.3.5"));
+        assert_eq!(parsed("220 (vsFTPd 3.0.3)"), found("vsFTPd", "3.0.3"));
+        assert_eq!(parsed("SSH-2.0-dropbear"), Some(("dropbear".to_string(), None)));
+    }
+
+    #[test]
+    fn test_free_form_banner_has_no_product() {
+        for banner in ["", "HTTP/1.1 400 Bad Request", "Welcome! Authorized users only.", "* OK"] {
+            assert_eq!(parse_banner(banner), None, "{:?}", banner);
+        }
+    }
+}
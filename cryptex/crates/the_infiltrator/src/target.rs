@@ -0,0 +1,164 @@
+//! Scan Target Validation
+//!
+//! A target is an IP address, a CIDR network or a host name; one string may
+//! list several, separated by commas. Checking them when the scan starts
+//! turns a typo such as `192.168.1.0/33` into an immediate error instead of
+//! a scan that quietly finds nothing.
+
+use std::net::IpAddr;
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Longest host name DNS allows, without the trailing dot
+const MAX_HOSTNAME_LEN: usize = 253;
+
+/// Longest single label in a host name
+const MAX_LABEL_LEN: usize = 63;
+
+/// Check every comma-separated entry in `target`
+///
+/// Traditional name: `parse_target`
+pub fn validate_target(target: &str) -> CryptexResult<()> {
+    for entry in target.split(',').map(str::trim) {
+        if entry.is_empty() {
+            return Err(CryptexError::validation(format!(
+                "Target '{}' has an empty entry in its list",
+                target
+            )));
+        }
+        validate_entry(entry).map_err(|reason| {
+            CryptexError::validation(format!("Invalid target '{}': {}", entry, reason))
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Number of addresses a validated target covers
+///
+/// An address or host name is one host and a CIDR network every address in
+/// it, network and broadcast included; counts too large for a `u64`
+/// saturate.
+pub fn count_target_hosts(target: &str) -> u64 {
+    target
+        .split(',')
+        .map(str::trim)
+        .map(|entry| {
+            let Some((address, prefix)) = entry.split_once('/') else {
+                return 1;
+            };
+            let bits = if address.contains(':') { 128 } else { 32 };
+            match prefix.parse::<u32>() {
+                Ok(prefix) if prefix <= bits => 1u64.checked_shl(bits - prefix).unwrap_or(u64::MAX),
+                _ => 1,
+            }
+        })
+        .fold(0, u64::saturating_add)
+}
+
+fn validate_entry(entry: &str) -> Result<(), String> {
+    if entry.contains("://") {
+        return Err("looks like a URL; give the host name or address without a scheme".into());
+    }
+
+    if let Some((address, prefix)) = entry.split_once('/') {
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| format!("'{}' is not an IP address", address))?;
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        return match prefix.parse::<u8>() {
+            Ok(prefix) if prefix <= max_prefix => Ok(()),
+            _ => Err(format!("CIDR prefix must be between 0 and {}", max_prefix)),
+        };
+    }
+
+    if entry.parse::<IpAddr>().is_ok() {
+        return Ok(());
+    }
+    if entry.contains(':') {
+        // Host names never contain ':', so this was meant as IPv6 (or host:port)
+        return Err("not a valid IPv6 address; ports are not part of a target".into());
+    }
+
+    validate_hostname(entry)
+}
+
+fn validate_hostname(host: &str) -> Result<(), String> {
+    let name = host.strip_suffix('.').unwrap_or(host);
+    if name.len() > MAX_HOSTNAME_LEN {
+        return Err(format!("host names are at most {} characters", MAX_HOSTNAME_LEN));
+    }
+
+    let labels: Vec<&str> = name.split('.').collect();
+    for label in &labels {
+        if label.is_empty() || label.len() > MAX_LABEL_LEN {
+            return Err(format!(
+                "each part of a host name must be 1-{} characters",
+                MAX_LABEL_LEN
+            ));
+        }
+        if let Some(c) = label.chars().find(|c| !c.is_ascii_alphanumeric() && *c != '-') {
+            return Err(format!("'{}' is not allowed in a host name", c));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err("host name parts cannot start or end with '-'".into());
+        }
+    }
+
+    // A top-level domain is never all digits, so this is a mistyped address
+    if labels.last().is_some_and(|tld| tld.chars().all(|c| c.is_ascii_digit())) {
+        return Err("not a valid IPv4 address".into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepts_addresses_networks_and_hosts() {
+        for target in [
+            "10.0.0.5",
+            "192.168.1.0/24",
+            "0.0.0.0/0",
+            "2001:db8::1",
+            "2001:db8::/32",
+            "scanme.example.org",
+            "db-01.internal.",
+            "localhost",
+            "10.0.0.5, 10.0.1.0/24,gateway.lan",
+        ] {
+            assert!(validate_target(target).is_ok(), "{} should be valid", target);
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_targets() {
+        for (target, reason) in [
+            ("192.168.1.0/33", "between 0 and 32"),
+            ("2001:db8::/129", "between 0 and 128"),
+            ("10.0.0.0/x", "between 0 and 32"),
+            ("htp://x", "without a scheme"),
+            ("host_name.lan", "'_' is not allowed"),
+            ("-bad.example.org", "start or end with '-'"),
+            ("example..org", "1-63 characters"),
+            ("192.168.1.300", "IPv4"),
+            ("10.0.0.5:443", "ports"),
+            ("10.0.0.5,,10.0.0.6", "empty entry"),
+        ] {
+            let err = validate_target(target).unwrap_err();
+            assert!(matches!(err, CryptexError::ValidationError(_)));
+            assert!(err.to_string().contains(reason), "{}: {}", target, err);
+        }
+    }
+
+    #[test]
+    fn test_count_target_hosts() {
+        assert_eq!(count_target_hosts("10.0.0.5"), 1);
+        assert_eq!(count_target_hosts("192.168.1.0/24"), 256);
+        assert_eq!(count_target_hosts("10.0.0.5, 10.0.1.0/30,gateway.lan"), 6);
+        assert_eq!(count_target_hosts("2001:db8::/120"), 256);
+        assert_eq!(count_target_hosts("2001:db8::/32"), u64::MAX);
+    }
+}
@@ -1,7 +1,81 @@
 //! Core types for scanner integration
 
-use serde::{Deserialize, Serialize};
-use the_assessor::VulnerabilityScore;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use std::str::FromStr;
+use crate::context::TargetProgress;
+use crate::service::{infer_service, parse_banner};
+use the_assessor::{CvssSeverity, DataCompleteness, VulnerabilityScore};
+use the_foundation::{CryptexError, TheCertainty};
+
+/// Confidence Level - How reliable a detection is
+///
+/// Traditional name: `QualityOfDetection`
+///
+/// Ordered from least to most reliable, so `>=` against a threshold reads as
+/// "at least this confident".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfidenceLevel {
+    /// Guesswork such as an unreliable banner
+    Low,
+    /// Version or banner checks that can be wrong about backports
+    Medium,
+    /// Package checks, confirmed probes and exploits
+    High,
+}
+
+impl ConfidenceLevel {
+    /// Every level, least reliable first
+    pub const ALL: [ConfidenceLevel; 3] =
+        [ConfidenceLevel::Low, ConfidenceLevel::Medium, ConfidenceLevel::High];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConfidenceLevel::Low => "low",
+            ConfidenceLevel::Medium => "medium",
+            ConfidenceLevel::High => "high",
+        }
+    }
+
+    /// Level for an OpenVAS quality-of-detection percentage
+    ///
+    /// 70 is the scanner's own default cut-off for reliable results.
+    pub fn from_qod(qod: u8) -> Self {
+        match qod {
+            70.. => ConfidenceLevel::High,
+            30..=69 => ConfidenceLevel::Medium,
+            _ => ConfidenceLevel::Low,
+        }
+    }
+
+    /// Quality-of-detection percentage standing for the level: the middle of
+    /// the band `from_qod` maps to it
+    pub fn representative_qod(self) -> u8 {
+        match self {
+            ConfidenceLevel::Low => 15,
+            ConfidenceLevel::Medium => 50,
+            ConfidenceLevel::High => 85,
+        }
+    }
+}
+
+impl FromStr for ConfidenceLevel {
+    type Err = CryptexError;
+
+    /// Parse a level name, case-insensitive (e.g. "high")
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ConfidenceLevel::ALL
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                CryptexError::validation(format!(
+                    "Unknown confidence '{}'; expected low, medium or high",
+                    name
+                ))
+            })
+    }
+}
 
 /// Scan Result - Enhanced vulnerability detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,12 +85,35 @@ pub struct ScanResult {
     pub port: u16,
     pub plugin_oid: String,
     pub description: String,
+    /// Service name on the port, reported by the scanner or inferred
+    #[serde(default)]
+    pub service: Option<String>,
+    /// Whether `service` was inferred rather than reported by the scanner
+    #[serde(default)]
+    pub service_inferred: bool,
+    /// Banner the service answered with, as captured
+    #[serde(default)]
+    pub banner: Option<String>,
+    /// Software the banner advertises, e.g. `Apache`
+    #[serde(default)]
+    pub product: Option<String>,
+    /// Version of `product`, e.g. `2.4.49`
+    #[serde(default)]
+    pub version: Option<String>,
+    /// How reliable the detection is, when the scanner says
+    #[serde(default)]
+    pub confidence: Option<ConfidenceLevel>,
 
     // Enhanced with external data
     pub vulnerability_score: Option<VulnerabilityScore>,
 
     // AI-enhanced fields
     pub remediation_guidance: Option<String>,
+    #[serde(default)]
+    pub remediation_plan: Option<RemediationPlan>,
+    /// Whether enrichment was cut short because the scan's budget ran out
+    #[serde(default)]
+    pub enrichment_skipped: bool,
 
     // Metadata
     pub detection_time: u64,
@@ -43,18 +140,120 @@ impl ScanResult {
             port,
             plugin_oid,
             description,
+            service: None,
+            service_inferred: false,
+            banner: None,
+            product: None,
+            version: None,
+            confidence: None,
             vulnerability_score: None,
             remediation_guidance: None,
+            remediation_plan: None,
+            enrichment_skipped: false,
             detection_time: now,
             scanner_version: "CRYPTEX 1.0.0".to_string(),
         }
     }
 
+    /// Record the service the scanner found on the port
+    ///
+    /// Replaces any inferred label.
+    pub fn set_service(&mut self, service: impl Into<String>) {
+        self.service = Some(service.into());
+        self.service_inferred = false;
+    }
+
+    /// Label the service from the port or a banner, unless one is known
+    ///
+    /// A service already reported by the scanner is kept as it is.
+    pub fn infer_service(&mut self, banner: Option<&str>) {
+        if self.service.is_some() && !self.service_inferred {
+            return;
+        }
+        if let Some(service) = infer_service(self.port, banner) {
+            self.service = Some(service.to_string());
+            self.service_inferred = true;
+        }
+    }
+
+    /// Record the banner captured from the port
+    ///
+    /// The product and version it advertises are filled in, and the service
+    /// is inferred from it unless the scanner reported one.
+    pub fn set_banner(&mut self, banner: impl Into<String>) {
+        let banner = banner.into();
+        if let Some(hint) = parse_banner(&banner) {
+            self.product = Some(hint.product);
+            self.version = hint.version;
+        }
+        self.infer_service(Some(&banner));
+        self.banner = Some(banner);
+    }
+
+    /// Set how reliable the detection is
+    pub fn with_confidence(mut self, confidence: ConfidenceLevel) -> Self {
+        self.confidence = Some(confidence);
+        self
+    }
+
+    /// Fold the AI's certainty about the finding's enrichment into its confidence
+    ///
+    /// The confidence is turned into a quality-of-detection percentage
+    /// (`representative_qod`), scaled by `0.5 + 0.5 * certainty` and mapped
+    /// back with `from_qod`:
+    ///
+    /// ```text
+    /// qod' = qod(confidence) * (0.5 + 0.5 * certainty)
+    /// ```
+    ///
+    /// Full certainty keeps the scanner's level and no certainty halves it,
+    /// so the AI can lower a finding's confidence but never raise it. A
+    /// finding the scanner gave no confidence keeps none, since any level
+    /// would let it through `min_confidence` filters it fails today.
+    pub fn blend_ai_certainty(&mut self, certainty: TheCertainty) {
+        let Some(scanner) = self.confidence else {
+            return;
+        };
+        let certainty = if certainty.is_finite() { certainty.clamp(0.0, 1.0) } else { 0.0 };
+        let qod = f64::from(scanner.representative_qod()) * (0.5 + 0.5 * certainty);
+
+        self.confidence = Some(ConfidenceLevel::from_qod(qod.round() as u8));
+    }
+
+    /// Whether the detection is at least `minimum` confident
+    ///
+    /// A finding without a confidence never meets a threshold.
+    pub fn meets_confidence(&self, minimum: ConfidenceLevel) -> bool {
+        self.confidence.is_some_and(|confidence| confidence >= minimum)
+    }
+
     /// Get CVSS base score if available
+    ///
+    /// `None` both before assessment and when the assessment found no CVSS
+    /// data; `unscored_reason` tells the two apart.
     pub fn cvss_base_score(&self) -> Option<f64> {
         self.vulnerability_score
             .as_ref()
-            .map(|s| s.cvss_base_score())
+            .and_then(|s| s.cvss_v3.as_ref())
+            .map(|cvss| cvss.base_score)
+    }
+
+    /// Why an assessed finding has no CVSS score, if it has none
+    pub fn unscored_reason(&self) -> Option<DataCompleteness> {
+        let score = self.vulnerability_score.as_ref()?;
+        if score.cvss_v3.is_some() {
+            return None;
+        }
+        // A record claiming completeness without CVSS data is still unscored
+        Some(match score.data_completeness {
+            DataCompleteness::Complete => DataCompleteness::NoCvss,
+            reason => reason,
+        })
+    }
+
+    /// Get CVSS severity, if the finding has been assessed
+    pub fn severity(&self) -> Option<CvssSeverity> {
+        self.vulnerability_score.as_ref().map(|s| s.severity())
     }
 
     /// Check if this is a KEV vulnerability
@@ -74,11 +273,139 @@ impl ScanResult {
     }
 }
 
+/// Remediation Plan - Structured, ticket-ready remediation steps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemediationPlan {
+    pub summary: String,
+    pub steps: Vec<RemediationStep>,
+    pub references: Vec<String>,
+    pub estimated_effort: RemediationEffort,
+}
+
+/// A single ordered remediation step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemediationStep {
+    pub order: usize,
+    pub action: String,
+}
+
+/// Remediation Effort - How soon the plan must be carried out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemediationEffort {
+    Immediate,  // KEV or critical
+    Urgent,     // High
+    Scheduled,  // Medium
+    Routine,    // Low / None
+}
+
+impl RemediationEffort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemediationEffort::Immediate => "immediate",
+            RemediationEffort::Urgent => "urgent",
+            RemediationEffort::Scheduled => "scheduled",
+            RemediationEffort::Routine => "routine",
+        }
+    }
+}
+
+impl RemediationPlan {
+    /// Build a plan from a vulnerability score
+    ///
+    /// Effort is derived from severity (KEV always escalates to immediate) and
+    /// references from the CVE, its CWEs and any references the assessor supplied.
+    pub fn from_score(score: &VulnerabilityScore) -> Self {
+
+        let estimated_effort = if score.is_kev() {
+            RemediationEffort::Immediate
+        } else {
+            match score.severity() {
+                CvssSeverity::Critical => RemediationEffort::Immediate,
+                CvssSeverity::High => RemediationEffort::Urgent,
+                CvssSeverity::Medium => RemediationEffort::Scheduled,
+                CvssSeverity::Low | CvssSeverity::None => RemediationEffort::Routine,
+            }
+        };
+
+        let actions: Vec<String> = match score.severity() {
+            CvssSeverity::Critical | CvssSeverity::High => vec![
+                "Apply vendor patches immediately".to_string(),
+                "Implement network segmentation if patching is delayed".to_string(),
+                "Monitor for exploitation attempts".to_string(),
+            ],
+            CvssSeverity::Medium => vec![
+                "Schedule patching within 30 days".to_string(),
+                "Verify patch availability".to_string(),
+            ],
+            _ => vec!["Include in regular patch cycle".to_string()],
+        };
+
+        let mut steps: Vec<RemediationStep> = Vec::new();
+
+        // KEV required action goes first - it is mandated, not advisory
+        if let Some(action) = score.kev.as_ref().and_then(|k| k.required_action.as_ref()) {
+            steps.push(RemediationStep {
+                order: 1,
+                action: action.clone(),
+            });
+        }
+
+        for action in actions {
+            steps.push(RemediationStep {
+                order: steps.len() + 1,
+                action,
+            });
+        }
+
+        let mut references = vec![format!("https://nvd.nist.gov/vuln/detail/{}", score.cve_id)];
+
+        for cwe in &score.cwe_ids {
+            if let Some(id) = cwe.strip_prefix("CWE-") {
+                references.push(format!("https://cwe.mitre.org/data/definitions/{}.html", id));
+            }
+        }
+
+        if score.is_kev() {
+            references.push(
+                "https://www.cisa.gov/known-exploited-vulnerabilities-catalog".to_string(),
+            );
+        }
+
+        for reference in &score.references {
+            if !references.contains(reference) {
+                references.push(reference.clone());
+            }
+        }
+
+        let summary = format!(
+            "Remediate {} ({} severity, CVSS {:.1}){}",
+            score.vulnerability_name.as_deref().unwrap_or(&score.cve_id),
+            score.severity().as_str(),
+            score.cvss_base_score(),
+            if score.is_kev() { " - known exploited" } else { "" }
+        );
+
+        Self {
+            summary,
+            steps,
+            references,
+            estimated_effort,
+        }
+    }
+}
+
 /// Scan Report - Comprehensive scan results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanReport {
     pub scan_id: String,
     pub target: String,
+    /// Individual targets of a multi-target scan; empty for older reports
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// Findings per target
+    #[serde(default)]
+    pub target_progress: Vec<TargetProgress>,
     pub start_time: u64,
     pub end_time: Option<u64>,
     pub status: ScanStatus,
@@ -95,11 +422,44 @@ pub struct ScanReport {
     // Statistics
     pub total_hosts: usize,
     pub ai_enhanced_count: usize,
+    /// Findings stored without full enrichment once the scan's budget ran out
+    #[serde(default)]
+    pub enrichment_skipped_count: usize,
 
     // Summary
+    #[serde(serialize_with = "serialize_sorted_summary")]
     pub summary: Option<serde_json::Value>,
 }
 
+/// Serialize a report summary with object keys in sorted order at every level
+///
+/// serde_json keeps insertion order instead once any crate in the build
+/// enables its `preserve_order` feature. Sorting here keeps a report's JSON
+/// byte-stable however its summary was assembled.
+pub fn serialize_sorted_summary<S>(summary: &Option<Value>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    summary.as_ref().map(sorted_keys).serialize(serializer)
+}
+
+fn sorted_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), sorted_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(sorted_keys).collect()),
+        other => other.clone(),
+    }
+}
+
 impl ScanReport {
     /// Create a new scan report
     pub fn new(scan_id: String, target: String) -> Self {
@@ -109,6 +469,8 @@ impl ScanReport {
             .as_secs();
 
         Self {
+            targets: vec![target.clone()],
+            target_progress: Vec::new(),
             scan_id,
             target,
             start_time: now,
@@ -123,6 +485,7 @@ impl ScanReport {
             kev_count: 0,
             total_hosts: 0,
             ai_enhanced_count: 0,
+            enrichment_skipped_count: 0,
             summary: None,
         }
     }
@@ -131,7 +494,6 @@ impl ScanReport {
     pub fn add_result(&mut self, result: ScanResult) {
         // Update severity counts
         if let Some(ref score) = result.vulnerability_score {
-            use the_assessor::CvssSeverity;
             match score.severity() {
                 CvssSeverity::Critical => self.critical_count += 1,
                 CvssSeverity::High => self.high_count += 1,
@@ -150,11 +512,31 @@ impl ScanReport {
         if result.remediation_guidance.is_some() {
             self.ai_enhanced_count += 1;
         }
+        if result.enrichment_skipped {
+            self.enrichment_skipped_count += 1;
+        }
 
         self.total_vulnerabilities += 1;
         self.scan_results.push(result);
     }
 
+    /// Keep only the findings `keep` accepts, recounting the totals
+    pub fn retain_results(&mut self, keep: impl Fn(&ScanResult) -> bool) {
+        let results = std::mem::take(&mut self.scan_results);
+        self.total_vulnerabilities = 0;
+        self.critical_count = 0;
+        self.high_count = 0;
+        self.medium_count = 0;
+        self.low_count = 0;
+        self.kev_count = 0;
+        self.ai_enhanced_count = 0;
+        self.enrichment_skipped_count = 0;
+
+        for result in results.into_iter().filter(|r| keep(r)) {
+            self.add_result(result);
+        }
+    }
+
     /// Mark scan as completed
     pub fn complete(&mut self) {
         let now = std::time::SystemTime::now()
@@ -182,6 +564,49 @@ impl ScanReport {
         results.into_iter().take(count).collect()
     }
 
+    /// Findings grouped by service, most affected service first
+    ///
+    /// Findings with no known service are grouped under `unknown`.
+    pub fn group_by_service(&self) -> Vec<(&str, Vec<&ScanResult>)> {
+        let mut groups: Vec<(&str, Vec<&ScanResult>)> = Vec::new();
+        for result in &self.scan_results {
+            let service = result.service.as_deref().unwrap_or("unknown");
+            match groups.iter_mut().find(|(name, _)| *name == service) {
+                Some((_, results)) => results.push(result),
+                None => groups.push((service, vec![result])),
+            }
+        }
+
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+        groups
+    }
+
+    /// Findings grouped by the weakness (CWE) behind them, largest group first
+    ///
+    /// A finding attributed to several CWEs appears under each of them;
+    /// findings without an assessment or without CWE data are left out.
+    pub fn group_by_cwe(&self) -> Vec<(&str, Vec<&ScanResult>)> {
+        let mut groups: Vec<(&str, Vec<&ScanResult>)> = Vec::new();
+        for result in &self.scan_results {
+            let Some(score) = &result.vulnerability_score else {
+                continue;
+            };
+            let mut cwe_ids: Vec<&str> = score.cwe_ids.iter().map(String::as_str).collect();
+            cwe_ids.sort_unstable();
+            cwe_ids.dedup();
+
+            for cwe_id in cwe_ids {
+                match groups.iter_mut().find(|(id, _)| *id == cwe_id) {
+                    Some((_, results)) => results.push(result),
+                    None => groups.push((cwe_id, vec![result])),
+                }
+            }
+        }
+
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+        groups
+    }
+
     /// Get only KEV vulnerabilities
     pub fn kev_vulnerabilities(&self) -> Vec<&ScanResult> {
         self.scan_results.iter().filter(|r| r.is_kev()).collect()
@@ -245,6 +670,7 @@ mod tests {
         assert_eq!(result.host, "192.168.1.100");
         assert_eq!(result.port, 443);
         assert!(result.cvss_base_score().is_none());
+        assert!(result.severity().is_none());
     }
 
     #[test]
@@ -280,6 +706,155 @@ mod tests {
         assert_eq!(report.scan_results.len(), 1);
     }
 
+    #[test]
+    fn test_confidence_threshold() {
+        assert!(ConfidenceLevel::High > ConfidenceLevel::Medium);
+        assert_eq!("HIGH".parse::<ConfidenceLevel>().unwrap(), ConfidenceLevel::High);
+        assert!("certain".parse::<ConfidenceLevel>().is_err());
+        assert_eq!(ConfidenceLevel::from_qod(97), ConfidenceLevel::High);
+        assert_eq!(ConfidenceLevel::from_qod(30), ConfidenceLevel::Medium);
+
+        let result = ScanResult::new(
+            "CVE-2024-0001".to_string(),
+            "192.168.1.100".to_string(),
+            443,
+            "1.3.6.1.4.1.25623.1.0.12345".to_string(),
+            "Test vulnerability".to_string(),
+        );
+        assert!(!result.meets_confidence(ConfidenceLevel::Low));
+
+        let result = result.with_confidence(ConfidenceLevel::Medium);
+        assert!(result.meets_confidence(ConfidenceLevel::Low));
+        assert!(!result.meets_confidence(ConfidenceLevel::High));
+    }
+
+    #[test]
+    fn test_ai_certainty_only_lowers_confidence() {
+        let blended = |scanner: Option<ConfidenceLevel>, certainty: f64| {
+            let mut result = ScanResult::new(
+                "CVE-2024-0001".to_string(),
+                "192.168.1.100".to_string(),
+                443,
+                "1.3.6.1.4.1.25623.1.0.12345".to_string(),
+                "Test vulnerability".to_string(),
+            );
+            result.confidence = scanner;
+            result.blend_ai_certainty(certainty);
+            result.confidence
+        };
+
+        // 85 * 0.95 = 81 stays high; 85 * 0.65 = 55 drops to medium
+        assert_eq!(blended(Some(ConfidenceLevel::High), 0.9), Some(ConfidenceLevel::High));
+        assert_eq!(blended(Some(ConfidenceLevel::High), 0.3), Some(ConfidenceLevel::Medium));
+        // 50 * 0.55 = 27.5 drops to low
+        assert_eq!(blended(Some(ConfidenceLevel::Medium), 0.1), Some(ConfidenceLevel::Low));
+        assert_eq!(blended(Some(ConfidenceLevel::Low), 1.5), Some(ConfidenceLevel::Low));
+        assert_eq!(blended(Some(ConfidenceLevel::Medium), f64::NAN), Some(ConfidenceLevel::Low));
+
+        // No scanner confidence stays none, even with full certainty
+        assert_eq!(blended(None, 1.0), None);
+        assert_eq!(blended(None, 0.3), None);
+    }
+
+    #[test]
+    fn test_explicit_service_overrides_inferred() {
+        let mut result = ScanResult::new(
+            "CVE-2024-0001".to_string(),
+            "192.168.1.100".to_string(),
+            443,
+            "1.3.6.1.4.1.25623.1.0.12345".to_string(),
+            "Test vulnerability".to_string(),
+        );
+
+        result.infer_service(None);
+        assert_eq!(result.service.as_deref(), Some("https"));
+        assert!(result.service_inferred);
+
+        result.set_service("nginx");
+        assert_eq!(result.service.as_deref(), Some("nginx"));
+        assert!(!result.service_inferred);
+
+        // Inference never replaces what the scanner reported
+        result.infer_service(Some("SSH-2.0-OpenSSH_9.6"));
+        assert_eq!(result.service.as_deref(), Some("nginx"));
+    }
+
+    #[test]
+    fn test_group_by_service() {
+        let mut report = ScanReport::new("scan-001".to_string(), "10.0.0.0/24".to_string());
+        let findings = [("10.0.0.1", 443), ("10.0.0.2", 443), ("10.0.0.1", 22), ("10.0.0.3", 31337)];
+        for (host, port) in findings {
+            let mut result = ScanResult::new(
+                "CVE-2024-0001".to_string(),
+                host.to_string(),
+                port,
+                "1.3.6.1.4.1.25623.1.0.12345".to_string(),
+                "Test vulnerability".to_string(),
+            );
+            result.infer_service(None);
+            report.add_result(result);
+        }
+
+        let groups: Vec<(&str, usize)> = report
+            .group_by_service()
+            .into_iter()
+            .map(|(service, results)| (service, results.len()))
+            .collect();
+        assert_eq!(groups, [("https", 2), ("ssh", 1), ("unknown", 1)]);
+    }
+
+    #[test]
+    fn test_group_by_cwe() {
+        let mut report = ScanReport::new("scan-001".to_string(), "10.0.0.0/24".to_string());
+        let findings: [(&str, &[&str]); 4] = [
+            ("CVE-2021-44228", &["CWE-502", "CWE-20"]),
+            ("CVE-2017-9805", &["CWE-502"]),
+            ("CVE-2020-11022", &["CWE-79"]),
+            ("CVE-2024-0001", &[]),
+        ];
+        for (cve_id, cwe_ids) in findings {
+            let mut result = ScanResult::new(
+                cve_id.to_string(),
+                "10.0.0.1".to_string(),
+                443,
+                "1.3.6.1.4.1.25623.1.0.12345".to_string(),
+                "Test vulnerability".to_string(),
+            );
+            let mut score = VulnerabilityScore::new(cve_id.to_string());
+            score.cwe_ids = cwe_ids.iter().map(|id| id.to_string()).collect();
+            result.vulnerability_score = Some(score);
+            report.add_result(result);
+        }
+
+        let groups: Vec<(&str, Vec<&str>)> = report
+            .group_by_cwe()
+            .into_iter()
+            .map(|(cwe_id, results)| (cwe_id, results.iter().map(|r| r.cve_id.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("CWE-502", vec!["CVE-2021-44228", "CVE-2017-9805"]),
+                ("CWE-20", vec!["CVE-2021-44228"]),
+                ("CWE-79", vec!["CVE-2020-11022"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remediation_plan_low_severity() {
+        let score = VulnerabilityScore::new("CVE-2024-0001".to_string());
+        let plan = RemediationPlan::from_score(&score);
+
+        assert_eq!(plan.estimated_effort, RemediationEffort::Routine);
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(plan.steps[0].order, 1);
+        assert_eq!(
+            plan.references,
+            vec!["https://nvd.nist.gov/vuln/detail/CVE-2024-0001".to_string()]
+        );
+    }
+
     #[test]
     fn test_scan_report_completion() {
         let mut report = ScanReport::new(
@@ -0,0 +1,39 @@
+//! Audit Actors
+//!
+//! Mutating handlers record who called them in the archive's audit log. The
+//! caller's identity is whatever an authentication layer put in the request
+//! extensions as an `Actor`; without one, requests are recorded as
+//! anonymous. Client-supplied headers are deliberately not consulted, since
+//! anyone could claim to be anyone through them.
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use std::convert::Infallible;
+
+use the_archive::{TheArchive, ANONYMOUS_ACTOR};
+
+/// Authenticated caller of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Actor(pub String);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Actor {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<Actor>()
+            .cloned()
+            .unwrap_or_else(|| Actor(ANONYMOUS_ACTOR.to_string())))
+    }
+}
+
+/// Record a completed operation
+///
+/// The operation has already happened by the time it is audited, so a
+/// failure to write the entry is logged rather than failing the request.
+pub fn record(archive: &TheArchive, actor: &Actor, action: &str, target: &str) {
+    if let Err(e) = archive.record_audit(&actor.0, action, target) {
+        tracing::warn!("Failed to audit {} {} by {}: {}", action, target, actor.0, e);
+    }
+}
@@ -16,18 +16,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .init();
 
     // Get configuration from environment
-    let db_path = std::env::var("CRYPTEX_DB_PATH")
-        .unwrap_or_else(|_| "./data/cryptex.db".to_string());
+    // CRYPTEX_DB_PATH, else the platform data directory
+    let db_path = the_foundation::platform::get_default_db_path();
 
     let bind_addr = std::env::var("CRYPTEX_BIND_ADDR")
         .unwrap_or_else(|_| "0.0.0.0:8080".to_string());
 
     tracing::info!("Starting CRYPTEX API Server");
-    tracing::info!("Database path: {}", db_path);
+    tracing::info!("Database path: {}", db_path.display());
     tracing::info!("Binding to: {}", bind_addr);
 
     // Run the server
-    run_server(&db_path, &bind_addr).await?;
+    run_server(&db_path.to_string_lossy(), &bind_addr).await?;
 
     Ok(())
 }
@@ -0,0 +1,574 @@
+//! Report Exporter
+//!
+//! Traditional name: `ObjectStorageUploader`
+//!
+//! Large engagements keep their reports in S3-compatible object storage
+//! rather than on the API server's disk. A destination is either a presigned
+//! URL, which is used as given, or an object key in the configured bucket,
+//! which is uploaded with a SigV4-signed path-style `PUT` so MinIO, Ceph and
+//! AWS all accept it.
+//!
+//! Presigned URLs come from the caller, so they are only accepted for the
+//! configured endpoint and the hosts listed in `presigned_hosts`; otherwise
+//! any client could have the server `PUT` a report to a host of its choosing,
+//! internal ones included.
+//!
+//! A report goes up as a single `PUT`, which the store applies atomically, so
+//! an interrupted upload is resumed by sending it again: connection failures,
+//! `408`, `429` and `5xx` answers are retried with a doubling backoff, while
+//! any other rejection is final.
+
+use async_trait::async_trait;
+use axum::body::Bytes;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{StatusCode, Url};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+use the_coordinator::ObjectStorageConfig;
+use the_foundation::{build_http_client, CryptexError, CryptexResult, HttpSettings};
+
+/// Environment variable naming the object storage endpoint, e.g. `https://s3.amazonaws.com`
+pub const ENDPOINT_ENV: &str = "CRYPTEX_OBJECT_STORAGE__ENDPOINT";
+
+/// Environment variable naming the bucket reports are uploaded to
+pub const BUCKET_ENV: &str = "CRYPTEX_OBJECT_STORAGE__BUCKET";
+
+/// Environment variable naming the bucket's region
+pub const REGION_ENV: &str = "CRYPTEX_OBJECT_STORAGE__REGION";
+
+/// Environment variable holding the access key id
+pub const ACCESS_KEY_ID_ENV: &str = "CRYPTEX_OBJECT_STORAGE__ACCESS_KEY_ID";
+
+/// Environment variable holding the secret access key
+pub const SECRET_ACCESS_KEY_ENV: &str = "CRYPTEX_OBJECT_STORAGE__SECRET_ACCESS_KEY";
+
+/// Environment variable listing other hosts presigned URLs may point at,
+/// comma-separated
+pub const PRESIGNED_HOSTS_ENV: &str = "CRYPTEX_OBJECT_STORAGE__PRESIGNED_HOSTS";
+
+/// Region signed for when none is configured
+pub const DEFAULT_REGION: &str = "us-east-1";
+
+/// Time allowed for each upload attempt
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Attempts made before an upload is abandoned
+const MAX_ATTEMPTS: u32 = 4;
+
+/// Pause before the first retry; doubled for each one after it
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Report Exporter - Ships rendered reports off the server
+#[async_trait]
+pub trait ReportExporter: Send + Sync {
+    /// Store `body` at `destination` and return the URL of the stored object
+    async fn upload(
+        &self,
+        destination: &str,
+        content_type: &str,
+        body: Vec<u8>,
+    ) -> CryptexResult<String>;
+}
+
+/// Bucket that object keys are uploaded to
+#[derive(Debug, Clone)]
+pub struct S3Bucket {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl S3Bucket {
+    /// Bucket configured through the environment, if any
+    ///
+    /// Needs the endpoint, bucket and both keys; the region defaults to
+    /// `us-east-1`.
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var(ENDPOINT_ENV).ok()?;
+        let bucket = std::env::var(BUCKET_ENV).ok()?;
+        let (Ok(access_key_id), Ok(secret_access_key)) = (
+            std::env::var(ACCESS_KEY_ID_ENV),
+            std::env::var(SECRET_ACCESS_KEY_ENV),
+        ) else {
+            tracing::warn!(
+                "Object storage bucket disabled: {} and {} must both be set",
+                ACCESS_KEY_ID_ENV,
+                SECRET_ACCESS_KEY_ENV
+            );
+            return None;
+        };
+
+        Some(Self {
+            endpoint,
+            bucket,
+            region: std::env::var(REGION_ENV).unwrap_or_else(|_| DEFAULT_REGION.to_string()),
+            access_key_id,
+            secret_access_key,
+        })
+    }
+
+    /// Bucket from the charter's `[object_storage]` section
+    pub fn from_charter(storage: &ObjectStorageConfig) -> Self {
+        Self {
+            endpoint: storage.endpoint.clone(),
+            bucket: storage.bucket.clone(),
+            region: storage.region.clone(),
+            access_key_id: storage.access_key_id.clone(),
+            secret_access_key: storage.secret_access_key.clone(),
+        }
+    }
+
+    /// Path-style URL of `key`
+    fn object_url(&self, key: &str) -> CryptexResult<Url> {
+        if key.is_empty() || key.starts_with('/') {
+            return Err(CryptexError::validation(format!(
+                "Object key must be non-empty and relative, got '{}'",
+                key
+            )));
+        }
+
+        let url = format!(
+            "{}/{}/{}",
+            self.endpoint.trim_end_matches('/'),
+            uri_encode(&self.bucket, true),
+            uri_encode(key, false)
+        );
+        parse_http_url(&url)
+    }
+
+    /// SigV4 headers for a `PUT` of a payload with the given SHA-256
+    fn sign_put(&self, url: &Url, payload_sha256: &str) -> Vec<(&'static str, String)> {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            url.path(),
+            host,
+            payload_sha256,
+            amz_date,
+            signed_headers,
+            payload_sha256
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = [date.as_str(), self.region.as_str(), "s3", "aws4_request"]
+            .iter()
+            .fold(format!("AWS4{}", self.secret_access_key).into_bytes(), |key, part| {
+                hmac(&key, part.as_bytes())
+            });
+        let signature = hex(&hmac(&key, string_to_sign.as_bytes()));
+
+        vec![
+            ("x-amz-date", amz_date),
+            ("x-amz-content-sha256", payload_sha256.to_string()),
+            (
+                "authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                    self.access_key_id, scope, signed_headers, signature
+                ),
+            ),
+        ]
+    }
+}
+
+/// S3 Exporter - Uploads reports to S3-compatible object storage
+///
+/// Traditional name: `S3Client`
+#[derive(Clone)]
+pub struct S3Exporter {
+    bucket: Option<S3Bucket>,
+    presigned_hosts: Vec<String>,
+    client: reqwest::Client,
+    retry_backoff: Duration,
+}
+
+impl S3Exporter {
+    /// Create an exporter; without a bucket, only presigned URLs for the
+    /// hosts added with [`S3Exporter::with_presigned_hosts`] are accepted
+    pub fn new(bucket: Option<S3Bucket>, http: &HttpSettings) -> CryptexResult<Self> {
+        if let Some(bucket) = &bucket {
+            parse_http_url(&bucket.endpoint)?;
+        }
+
+        Ok(Self {
+            bucket,
+            presigned_hosts: Vec::new(),
+            client: build_http_client(http)?,
+            retry_backoff: RETRY_BACKOFF,
+        })
+    }
+
+    /// Also accept presigned URLs for these hosts, given as `host` or `host:port`
+    pub fn with_presigned_hosts(mut self, hosts: Vec<String>) -> Self {
+        self.presigned_hosts = hosts;
+        self
+    }
+
+    /// Exporter for the charter's `[object_storage]` section, if any
+    ///
+    /// A bucket configured through the environment replaces the charter's,
+    /// and `CRYPTEX_OBJECT_STORAGE__PRESIGNED_HOSTS` its `presigned_hosts`.
+    pub fn from_charter(
+        storage: Option<&ObjectStorageConfig>,
+        http: &HttpSettings,
+    ) -> CryptexResult<Self> {
+        let bucket = S3Bucket::from_env().or_else(|| storage.map(S3Bucket::from_charter));
+        let presigned_hosts = presigned_hosts_from_env().unwrap_or_else(|| {
+            storage.map(|storage| storage.presigned_hosts.clone()).unwrap_or_default()
+        });
+
+        let exporter = Self::new(bucket, http).or_else(|e| {
+            tracing::warn!("Object storage bucket disabled: {}", e);
+            Self::new(None, http)
+        })?;
+        Ok(exporter.with_presigned_hosts(presigned_hosts))
+    }
+
+    /// Refuse presigned URLs for hosts other than the endpoint and the allowlist
+    fn check_presigned_host(&self, url: &Url) -> CryptexResult<()> {
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        let port = url.port_or_known_default();
+        let endpoint = self
+            .bucket
+            .as_ref()
+            .and_then(|bucket| Url::parse(&bucket.endpoint).ok());
+        let is_endpoint = endpoint.is_some_and(|endpoint| {
+            endpoint.host_str().map(str::to_ascii_lowercase).as_deref() == Some(host.as_str())
+                && endpoint.port_or_known_default() == port
+        });
+        let is_listed = self.presigned_hosts.iter().any(|allowed| {
+            let allowed = allowed.to_ascii_lowercase();
+            match allowed.rsplit_once(':') {
+                Some((allowed_host, allowed_port)) => {
+                    allowed_host == host && port.is_some_and(|p| p.to_string() == allowed_port)
+                }
+                None => allowed == host,
+            }
+        });
+
+        if is_endpoint || is_listed {
+            return Ok(());
+        }
+        Err(CryptexError::validation(format!(
+            "Presigned URLs must point at the object storage endpoint or one of {}: {}",
+            PRESIGNED_HOSTS_ENV,
+            redacted(url)
+        )))
+    }
+
+    /// `PUT` the body, retrying transient failures
+    async fn put(
+        &self,
+        url: &Url,
+        content_type: &str,
+        body: Bytes,
+        signed_by: Option<&S3Bucket>,
+    ) -> CryptexResult<()> {
+        let payload_sha256 = hex(&Sha256::digest(&body));
+        let mut backoff = self.retry_backoff;
+        let mut last_failure = String::new();
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut request = self
+                .client
+                .put(url.clone())
+                .timeout(UPLOAD_TIMEOUT)
+                .header(reqwest::header::CONTENT_TYPE, content_type)
+                .body(body.clone());
+            // Signed per attempt, as the signature covers the request time
+            if let Some(bucket) = signed_by {
+                for (name, value) in bucket.sign_put(url, &payload_sha256) {
+                    request = request.header(name, value);
+                }
+            }
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) if !is_transient(response.status()) => {
+                    let status = response.status();
+                    let detail = response.text().await.unwrap_or_default();
+                    return Err(CryptexError::upstream(format!(
+                        "Object storage rejected the upload with {}: {}",
+                        status,
+                        detail.trim()
+                    )));
+                }
+                Ok(response) => last_failure = response.status().to_string(),
+                Err(e) => last_failure = e.to_string(),
+            }
+            tracing::warn!(
+                "Report upload to {} failed: {} (attempt {}/{})",
+                redacted(url),
+                last_failure,
+                attempt,
+                MAX_ATTEMPTS
+            );
+
+            if attempt < MAX_ATTEMPTS {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+
+        Err(CryptexError::upstream(format!(
+            "Report upload gave up after {} attempts: {}",
+            MAX_ATTEMPTS, last_failure
+        )))
+    }
+}
+
+#[async_trait]
+impl ReportExporter for S3Exporter {
+    async fn upload(
+        &self,
+        destination: &str,
+        content_type: &str,
+        body: Vec<u8>,
+    ) -> CryptexResult<String> {
+        let body = Bytes::from(body);
+
+        if destination.starts_with("http://") || destination.starts_with("https://") {
+            let url = parse_http_url(destination)?;
+            self.check_presigned_host(&url)?;
+            self.put(&url, content_type, body, None).await?;
+            return Ok(redacted(&url));
+        }
+
+        let bucket = self.bucket.as_ref().ok_or_else(|| {
+            CryptexError::validation(
+                "No object storage bucket is configured; pass a presigned URL as the destination",
+            )
+        })?;
+        let url = bucket.object_url(destination)?;
+        self.put(&url, content_type, body, Some(bucket)).await?;
+        Ok(url.to_string())
+    }
+}
+
+/// `CRYPTEX_OBJECT_STORAGE__PRESIGNED_HOSTS`, if set
+fn presigned_hosts_from_env() -> Option<Vec<String>> {
+    let value = std::env::var(PRESIGNED_HOSTS_ENV).ok()?;
+    Some(
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Whether a failed upload is worth sending again
+fn is_transient(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+fn parse_http_url(url: &str) -> CryptexResult<Url> {
+    Url::parse(url)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .ok_or_else(|| {
+            CryptexError::validation(format!("Object storage URL must use http or https: {}", url))
+        })
+}
+
+/// URL without its query, which for a presigned URL carries the credentials
+fn redacted(url: &Url) -> String {
+    let mut url = url.clone();
+    url.set_query(None);
+    url.set_fragment(None);
+    url.to_string()
+}
+
+/// Percent-encode all but RFC 3986 unreserved characters, as SigV4 requires
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b'/' if !encode_slash => "/".to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, StatusCode as HttpStatus};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct MockStore {
+        /// Answers for the first requests; later ones succeed
+        failures: Arc<Mutex<Vec<HttpStatus>>>,
+        attempts: Arc<AtomicUsize>,
+        stored: Arc<Mutex<Option<(String, HeaderMap, Bytes)>>>,
+    }
+
+    async fn mock_s3(store: MockStore) -> String {
+        let app = axum::Router::new().fallback(
+            move |uri: axum::http::Uri, headers: HeaderMap, body: Bytes| {
+                let store = store.clone();
+                async move {
+                    store.attempts.fetch_add(1, Ordering::SeqCst);
+                    let mut failures = store.failures.lock().unwrap();
+                    if !failures.is_empty() {
+                        return failures.remove(0);
+                    }
+                    *store.stored.lock().unwrap() = Some((uri.to_string(), headers, body));
+                    HttpStatus::OK
+                }
+            },
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    fn bucket(endpoint: &str) -> S3Bucket {
+        S3Bucket {
+            endpoint: endpoint.to_string(),
+            bucket: "reports".to_string(),
+            region: DEFAULT_REGION.to_string(),
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "secret".to_string(),
+        }
+    }
+
+    fn exporter(endpoint: &str) -> S3Exporter {
+        let mut exporter =
+            S3Exporter::new(Some(bucket(endpoint)), &HttpSettings::default()).unwrap();
+        exporter.retry_backoff = Duration::from_millis(1);
+        exporter
+    }
+
+    #[tokio::test]
+    async fn test_upload_signs_and_stores_object() {
+        let store = MockStore::default();
+        let endpoint = mock_s3(store.clone()).await;
+
+        let url = exporter(&endpoint)
+            .upload("acme/scan 1.json", "application/json", b"{}".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(url, format!("{}/reports/acme/scan%201.json", endpoint));
+
+        let (path, headers, body) = store.stored.lock().unwrap().clone().unwrap();
+        assert_eq!(path, "/reports/acme/scan%201.json");
+        assert_eq!(body.as_ref(), b"{}");
+        assert_eq!(headers["content-type"], "application/json");
+        assert_eq!(headers["x-amz-content-sha256"], hex(&Sha256::digest(b"{}")));
+        let authorization = headers["authorization"].to_str().unwrap();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+        assert!(authorization.contains("/us-east-1/s3/aws4_request"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_retries_transient_failures() {
+        let store = MockStore::default();
+        store
+            .failures
+            .lock()
+            .unwrap()
+            .extend([HttpStatus::SERVICE_UNAVAILABLE, HttpStatus::TOO_MANY_REQUESTS]);
+        let endpoint = mock_s3(store.clone()).await;
+
+        let url = exporter(&endpoint)
+            .upload(
+                &format!("{}/reports/scan.html?X-Amz-Signature=abc", endpoint),
+                "text/html",
+                b"<html></html>".to_vec(),
+            )
+            .await
+            .unwrap();
+
+        // The presigned query stays out of the returned URL
+        assert_eq!(url, format!("{}/reports/scan.html", endpoint));
+        assert_eq!(store.attempts.load(Ordering::SeqCst), 3);
+        let (path, headers, _) = store.stored.lock().unwrap().clone().unwrap();
+        assert_eq!(path, "/reports/scan.html?X-Amz-Signature=abc");
+        assert!(!headers.contains_key("authorization"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_stops_on_rejection() {
+        let store = MockStore::default();
+        store.failures.lock().unwrap().push(HttpStatus::FORBIDDEN);
+        let endpoint = mock_s3(store.clone()).await;
+
+        let result = exporter(&endpoint).upload("scan.json", "application/json", Vec::new()).await;
+
+        assert!(matches!(result, Err(CryptexError::UpstreamError(_))));
+        assert_eq!(store.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_object_key_needs_a_bucket() {
+        let exporter = S3Exporter::new(None, &HttpSettings::default()).unwrap();
+
+        let result = exporter.upload("scan.json", "application/json", Vec::new()).await;
+        assert!(matches!(result, Err(CryptexError::ValidationError(_))));
+        let ftp = bucket("ftp://example.com");
+        assert!(S3Exporter::new(Some(ftp), &HttpSettings::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_presigned_url_must_name_an_allowed_host() {
+        let store = MockStore::default();
+        let endpoint = mock_s3(store.clone()).await;
+        let elsewhere = endpoint.replace("127.0.0.1", "localhost");
+        let presigned = format!("{}/reports/scan.json?X-Amz-Signature=abc", elsewhere);
+        let unbucketed = || S3Exporter::new(None, &HttpSettings::default()).unwrap();
+
+        for exporter in [exporter(&endpoint), unbucketed()] {
+            let result = exporter.upload(&presigned, "application/json", Vec::new()).await;
+            assert!(matches!(result, Err(CryptexError::ValidationError(_))));
+        }
+        let wrong_port = unbucketed().with_presigned_hosts(vec!["localhost:1".to_string()]);
+        let result = wrong_port.upload(&presigned, "application/json", Vec::new()).await;
+        assert!(matches!(result, Err(CryptexError::ValidationError(_))));
+        assert_eq!(store.attempts.load(Ordering::SeqCst), 0);
+
+        let host = elsewhere.trim_start_matches("http://").to_string();
+        for allowed in [host, "LocalHost".to_string()] {
+            let listed = unbucketed().with_presigned_hosts(vec![allowed]);
+            listed.upload(&presigned, "application/json", Vec::new()).await.unwrap();
+        }
+        assert_eq!(store.attempts.load(Ordering::SeqCst), 2);
+    }
+}
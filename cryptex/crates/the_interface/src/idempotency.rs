@@ -0,0 +1,200 @@
+//! Idempotency Keys
+//!
+//! A client that retries `POST /api/v1/scans` after a dropped connection
+//! cannot tell whether the first attempt went through. Sending the same
+//! `Idempotency-Key` header on every attempt makes the retry safe: the first
+//! request creates the scan, later ones within the TTL get its original
+//! response back. Keys live in memory only and are forgotten on restart.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+/// Request header carrying the key
+pub const IDEMPOTENCY_HEADER: &str = "idempotency-key";
+
+/// How long a key is remembered after first use
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Result of running a request under an idempotency key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Idempotent<T> {
+    /// First use of the key; the request ran
+    Created(T),
+    /// The key was seen with the same request; this is the original response
+    Replayed(T),
+    /// The key was seen with a different request
+    Conflict,
+}
+
+/// One key: its request, and its response once a request under it succeeds
+struct Slot<T> {
+    fingerprint: String,
+    response: Arc<OnceCell<T>>,
+    /// Requests currently running or waiting under the key
+    in_flight: usize,
+    created_at: Instant,
+}
+
+impl<T> Slot<T> {
+    /// Whether the slot still holds anything worth keeping
+    ///
+    /// A slot nobody is using and without a response belongs to a request
+    /// that failed, which is not remembered.
+    fn is_live(&self, ttl: Duration) -> bool {
+        self.in_flight > 0 || (self.response.initialized() && self.created_at.elapsed() < ttl)
+    }
+}
+
+type Slots<T> = Arc<Mutex<HashMap<String, Slot<T>>>>;
+
+/// Releases a request's hold on its slot, even if the request is dropped
+struct InFlight<'a, T> {
+    slots: &'a Slots<T>,
+    key: &'a str,
+}
+
+impl<T> Drop for InFlight<'_, T> {
+    fn drop(&mut self) {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(slot) = slots.get_mut(self.key) {
+            slot.in_flight -= 1;
+        }
+    }
+}
+
+/// Idempotency Cache - Remembers responses by client-supplied key
+///
+/// Traditional name: `IdempotencyStore`
+#[derive(Clone)]
+pub struct IdempotencyCache<T> {
+    slots: Slots<T>,
+    ttl: Duration,
+}
+
+impl<T: Clone> Default for IdempotencyCache<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+impl<T: Clone> IdempotencyCache<T> {
+    /// Create a cache whose keys expire `ttl` after first use
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            slots: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    /// Run `create` unless `key` was already used
+    ///
+    /// `fingerprint` identifies the request; reusing a key for a different
+    /// one is a conflict. Failed requests are not remembered, so the client
+    /// may retry them under the same key. Concurrent requests under one key
+    /// wait for the first and get its response; requests under other keys
+    /// are not held up.
+    pub async fn run<F, E>(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        create: F,
+    ) -> Result<Idempotent<T>, E>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        let response = {
+            let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+            slots.retain(|_, slot| slot.is_live(self.ttl));
+
+            let slot = slots.entry(key.to_string()).or_insert_with(|| Slot {
+                fingerprint: fingerprint.to_string(),
+                response: Arc::new(OnceCell::new()),
+                in_flight: 0,
+                created_at: Instant::now(),
+            });
+            if slot.fingerprint != fingerprint {
+                return Ok(Idempotent::Conflict);
+            }
+            slot.in_flight += 1;
+            Arc::clone(&slot.response)
+        };
+        let _in_flight = InFlight {
+            slots: &self.slots,
+            key,
+        };
+
+        // Only one request per key runs at a time; if it fails, the next
+        // waiter runs its own
+        let mut created = false;
+        let response = response
+            .get_or_try_init(|| {
+                created = true;
+                create
+            })
+            .await?
+            .clone();
+
+        Ok(if created {
+            Idempotent::Created(response)
+        } else {
+            Idempotent::Replayed(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use the_foundation::{CryptexError, CryptexResult};
+
+    #[tokio::test]
+    async fn test_failures_are_not_remembered() {
+        let cache = IdempotencyCache::<u32>::default();
+
+        let failed = cache
+            .run("key-1", "a", async { Err(CryptexError::unknown("boom")) })
+            .await;
+        assert!(failed.is_err());
+
+        let retried = cache.run("key-1", "a", async { CryptexResult::Ok(7) }).await.unwrap();
+        assert_eq!(retried, Idempotent::Created(7));
+    }
+
+    #[tokio::test]
+    async fn test_keys_expire() {
+        let cache = IdempotencyCache::<u32>::new(Duration::from_millis(10));
+
+        cache.run("key-1", "a", async { CryptexResult::Ok(1) }).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let again = cache.run("key-1", "b", async { CryptexResult::Ok(2) }).await.unwrap();
+        assert_eq!(again, Idempotent::Created(2));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_one_run() {
+        let cache = IdempotencyCache::<u32>::default();
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+
+        let first = cache.run("key-1", "a", async {
+            released.await.unwrap();
+            CryptexResult::Ok(1)
+        });
+        let retry = cache.run("key-1", "a", async { CryptexResult::Ok(2) });
+        // Another key is not held up by the request still running
+        let other = async {
+            let other = cache.run("key-2", "a", async { CryptexResult::Ok(3) }).await;
+            release.send(()).unwrap();
+            other
+        };
+
+        let (first, retry, other) = tokio::join!(first, retry, other);
+        assert_eq!(first.unwrap(), Idempotent::Created(1));
+        assert_eq!(retry.unwrap(), Idempotent::Replayed(1));
+        assert_eq!(other.unwrap(), Idempotent::Created(3));
+        assert!(cache.slots.lock().unwrap().values().all(|slot| slot.in_flight == 0));
+    }
+}
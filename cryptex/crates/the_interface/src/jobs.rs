@@ -0,0 +1,337 @@
+//! Background Jobs
+//!
+//! Large CVE batches can take minutes to assess, and so can enriching every
+//! finding of an imported OpenVAS report. Instead of holding the request
+//! open, the work runs as a background task and the client polls its job id.
+//! Job state is kept in memory only; finished jobs are swept after a TTL.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use the_archive::TheArchive;
+use the_assessor::{TheAssessor, VulnerabilityScore};
+use the_infiltrator::{OpenvasImport, TheInfiltrator};
+
+/// How long finished jobs remain retrievable
+pub const DEFAULT_JOB_TTL: Duration = Duration::from_secs(3600);
+
+/// How often the sweeper looks for expired jobs
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Shortest sweep period, however short the TTL
+const MIN_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lifecycle of a background job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl JobStatus {
+    pub(crate) fn is_finished(self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed)
+    }
+}
+
+/// Pollable state of a background job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobState {
+    pub job_id: String,
+    pub status: JobStatus,
+    pub progress: usize,
+    pub total: usize,
+    /// Scan an import job is filling in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan_id: Option<String>,
+    /// Present once an assessment job has finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results: Option<Vec<VulnerabilityScore>>,
+    /// CVEs that could not be assessed, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    #[serde(skip)]
+    finished_at: Option<Instant>,
+}
+
+/// Job Registry - In-memory store of background jobs
+///
+/// Traditional name: `JobQueue`
+#[derive(Clone)]
+pub struct JobRegistry {
+    jobs: Arc<RwLock<HashMap<String, JobState>>>,
+    ttl: Duration,
+}
+
+impl Default for JobRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_JOB_TTL)
+    }
+}
+
+impl JobRegistry {
+    /// Create a registry whose finished jobs expire after `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            jobs: Arc::new(RwLock::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    /// Register a new pending job
+    pub async fn create(&self, total: usize) -> String {
+        let job_id = uuid::Uuid::new_v4().to_string();
+
+        let job = JobState {
+            job_id: job_id.clone(),
+            status: JobStatus::Pending,
+            progress: 0,
+            total,
+            scan_id: None,
+            results: None,
+            errors: Vec::new(),
+            finished_at: None,
+        };
+        self.jobs.write().await.insert(job_id.clone(), job);
+
+        job_id
+    }
+
+    /// Current state of a job
+    pub async fn get(&self, job_id: &str) -> Option<JobState> {
+        self.jobs.read().await.get(job_id).cloned()
+    }
+
+    /// Queue a batch assessment and return its job id immediately
+    pub async fn spawn_assessment(
+        &self,
+        assessor: Arc<TheAssessor>,
+        archive: Arc<TheArchive>,
+        cve_ids: Vec<String>,
+    ) -> String {
+        let job_id = self.create(cve_ids.len()).await;
+
+        let registry = self.clone();
+        let id = job_id.clone();
+        tokio::spawn(async move {
+            registry.run_assessment(&id, &assessor, &archive, cve_ids).await;
+        });
+
+        job_id
+    }
+
+    /// Assess each CVE in turn, publishing progress after every one
+    async fn run_assessment(
+        &self,
+        job_id: &str,
+        assessor: &TheAssessor,
+        archive: &TheArchive,
+        cve_ids: Vec<String>,
+    ) {
+        self.update(job_id, |job| job.status = JobStatus::Running).await;
+
+        let mut results = Vec::with_capacity(cve_ids.len());
+        let mut errors = Vec::new();
+
+        for cve_id in &cve_ids {
+            match assessor.assess_vulnerability(cve_id).await {
+                Ok(score) => {
+                    if let Err(e) = archive.store_vulnerability(&score) {
+                        tracing::warn!("Failed to archive {}: {}", cve_id, e);
+                    }
+                    results.push(score);
+                }
+                Err(e) => errors.push(format!("{}: {}", cve_id, e)),
+            }
+
+            self.update(job_id, |job| job.progress += 1).await;
+
+            // Let pollers observe progress even on a single-threaded runtime
+            tokio::task::yield_now().await;
+        }
+
+        let failed = results.is_empty() && !errors.is_empty();
+        self.update(job_id, |job| {
+            job.status = if failed { JobStatus::Failed } else { JobStatus::Completed };
+            job.results = Some(results);
+            job.errors = errors;
+            job.finished_at = Some(Instant::now());
+        })
+        .await;
+
+        tracing::info!("Job {} finished", job_id);
+    }
+
+    /// Queue the enrichment of a registered OpenVAS import and return its job id
+    ///
+    /// The job's total is the number of findings; progress jumps to it once
+    /// the scan is archived. Live progress is the scan's own.
+    pub async fn spawn_import(
+        &self,
+        infiltrator: Arc<TheInfiltrator>,
+        archive: Arc<TheArchive>,
+        import: OpenvasImport,
+    ) -> String {
+        let job_id = self.create(import.findings()).await;
+        let scan_id = import.scan_id().to_string();
+        self.update(&job_id, |job| job.scan_id = Some(scan_id)).await;
+
+        let registry = self.clone();
+        let id = job_id.clone();
+        tokio::spawn(async move {
+            registry.update(&id, |job| job.status = JobStatus::Running).await;
+
+            let outcome = match infiltrator.finish_openvas_import(import).await {
+                Ok(report) => crate::archive_scan_report(&archive, &report),
+                Err(e) => Err(e),
+            };
+            registry
+                .update(&id, |job| {
+                    match outcome {
+                        Ok(_) => {
+                            job.status = JobStatus::Completed;
+                            job.progress = job.total;
+                        }
+                        Err(e) => {
+                            job.status = JobStatus::Failed;
+                            job.errors.push(e.to_string());
+                        }
+                    }
+                    job.finished_at = Some(Instant::now());
+                })
+                .await;
+
+            tracing::info!("Job {} finished", id);
+        });
+
+        job_id
+    }
+
+    async fn update(&self, job_id: &str, apply: impl FnOnce(&mut JobState)) {
+        if let Some(job) = self.jobs.write().await.get_mut(job_id) {
+            apply(job);
+        }
+    }
+
+    /// Drop finished jobs older than the TTL, returning how many were removed
+    pub async fn sweep(&self) -> usize {
+        let mut jobs = self.jobs.write().await;
+        let before = jobs.len();
+
+        jobs.retain(|_, job| match job.finished_at {
+            Some(finished_at) if job.status.is_finished() => finished_at.elapsed() < self.ttl,
+            _ => true,
+        });
+
+        before - jobs.len()
+    }
+
+    /// Run the sweeper in the background for the lifetime of the server
+    pub fn spawn_sweeper(&self) -> tokio::task::JoinHandle<()> {
+        let registry = self.clone();
+        tokio::spawn(async move {
+            // A zero period would panic
+            let period = SWEEP_INTERVAL.min(registry.ttl).max(MIN_SWEEP_INTERVAL);
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                let removed = registry.sweep().await;
+                if removed > 0 {
+                    tracing::debug!("Swept {} expired jobs", removed);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = Arc::new(TheArchive::the_awakening(temp_dir.path().join("jobs.db")).unwrap());
+        let assessor = Arc::new(TheAssessor::the_awakening().await.unwrap());
+        let registry = JobRegistry::default();
+
+        let cve_ids = vec![
+            "CVE-2021-44228".to_string(),
+            "CVE-2024-0001".to_string(),
+            "CVE-2024-0002".to_string(),
+        ];
+        let job_id = registry
+            .spawn_assessment(assessor, archive.clone(), cve_ids)
+            .await;
+
+        let mut seen = vec![registry.get(&job_id).await.unwrap().status];
+        let poll = async {
+            loop {
+                tokio::task::yield_now().await;
+                let job = registry.get(&job_id).await.unwrap();
+                if seen.last() != Some(&job.status) {
+                    seen.push(job.status);
+                }
+                if job.status.is_finished() {
+                    break job;
+                }
+            }
+        };
+        let job = tokio::time::timeout(Duration::from_secs(30), poll)
+            .await
+            .expect("job did not finish within 30s");
+
+        assert_eq!(seen, vec![JobStatus::Pending, JobStatus::Running, JobStatus::Completed]);
+        assert_eq!(job.progress, 3);
+        assert_eq!(job.results.unwrap().len(), 3);
+        assert!(archive.get_vulnerability("CVE-2021-44228").unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_sweep_removes_expired_jobs() {
+        let registry = JobRegistry::new(Duration::ZERO);
+
+        let pending = registry.create(1).await;
+        let finished = registry.create(1).await;
+        registry
+            .update(&finished, |job| {
+                job.status = JobStatus::Completed;
+                job.finished_at = Some(Instant::now());
+            })
+            .await;
+
+        assert_eq!(registry.sweep().await, 1);
+        assert!(registry.get(&pending).await.is_some());
+        assert!(registry.get(&finished).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sweeper_runs_with_a_zero_ttl() {
+        let registry = JobRegistry::new(Duration::ZERO);
+        let finished = registry.create(1).await;
+        registry
+            .update(&finished, |job| {
+                job.status = JobStatus::Completed;
+                job.finished_at = Some(Instant::now());
+            })
+            .await;
+
+        let sweeper = registry.spawn_sweeper();
+        // The first tick is immediate
+        for _ in 0..100 {
+            if registry.get(&finished).await.is_none() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!sweeper.is_finished());
+        assert!(registry.get(&finished).await.is_none());
+        sweeper.abort();
+    }
+}
@@ -35,24 +35,62 @@
 //! }
 //! ```
 
+pub mod audit;
+#[cfg(feature = "object-storage")]
+pub mod exporter;
+pub mod idempotency;
+pub mod jobs;
+pub mod limits;
+pub mod metrics;
+pub mod negotiation;
+pub mod readiness;
+pub mod trace;
+pub mod versioning;
+pub mod webhook;
+
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Response},
-    routing::{get, post},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post, put},
     Json, Router,
 };
+use futures::Stream;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::{Component, Path as FsPath, PathBuf};
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
-use tower_http::trace::TraceLayer;
 
 pub use the_foundation::{CryptexError, CryptexResult};
-use the_archive::{ArchiveStats, ScanMetadata, TheArchive};
-use the_assessor::{TheAssessor, VulnerabilityScore};
-use the_infiltrator::{ScanReport, TheInfiltrator};
-use the_propagandist::{ReportFormat, ThePropagandist};
+use the_archive::{
+    ActivityEvent, ArchiveQuery, ArchiveStats, AuditEntry, KevCoverage, QueryFilters,
+    ScanFilters, ScanMetadata, StoredScanResult, StoredVulnerability, TheArchive,
+    VulnerabilitySort,
+};
+use the_assessor::{
+    normalize_cve_id, AssessOptions, CvssSeverity, CvssV3Base, TheAssessor, VulnerabilityScore,
+};
+use the_collective::{ObserverMetrics, TheCollective};
+use the_coordinator::TheCharter;
+use the_infiltrator::{ScanOptionsOverride, ScanProgress, ScanReport, TheInfiltrator};
+use the_propagandist::{
+    decode_signing_key, ReportFormat, SectionToggles, SigningKey, ThePropagandist,
+};
+use tokio::sync::broadcast::error::RecvError;
+
+use audit::Actor;
+use idempotency::{IdempotencyCache, Idempotent, IDEMPOTENCY_HEADER};
+use jobs::{JobRegistry, JobState};
+use limits::RequestLimits;
+use negotiation::ReportOutput;
+use readiness::ReadyState;
+use versioning::{DeprecatedRoute, Deprecations};
+use webhook::CompletionWebhook;
 
 /// Application state shared across handlers
 #[derive(Clone)]
@@ -61,12 +99,25 @@ pub struct AppState {
     infiltrator: Arc<TheInfiltrator>,
     propagandist: Arc<ThePropagandist>,
     archive: Arc<TheArchive>,
+    export_root: PathBuf,
+    jobs: JobRegistry,
+    webhook: Option<CompletionWebhook>,
+    scan_keys: IdempotencyCache<StartScanResponse>,
+    signing_key: Option<Arc<SigningKey>>,
+    ready: Arc<ReadyState>,
+    deprecations: Arc<Deprecations>,
+    collective: Option<Arc<TheCollective>>,
+    #[cfg(feature = "object-storage")]
+    exporter: Arc<dyn exporter::ReportExporter>,
 }
 
 /// The Interface - Main REST API server
 pub struct TheInterface {
     bind_address: String,
     state: AppState,
+    limits: RequestLimits,
+    retain_days: Option<u64>,
+    warm_up: Option<Vec<String>>,
 }
 
 impl TheInterface {
@@ -74,35 +125,195 @@ impl TheInterface {
     pub async fn the_awakening(
         bind_address: String,
         archive_path: PathBuf,
+    ) -> CryptexResult<Self> {
+        let archive = Arc::new(TheArchive::the_awakening(archive_path)?);
+
+        Self::with_archive(bind_address, archive).await
+    }
+
+    /// Initialize The Interface on an already-open archive
+    ///
+    /// Use this when other components in the process share the same archive
+    /// handle; redb allows only one open handle per database file. Settings
+    /// come from the environment and the built-in charter.
+    pub async fn with_archive(
+        bind_address: String,
+        archive: Arc<TheArchive>,
+    ) -> CryptexResult<Self> {
+        Self::with_charter(bind_address, archive, &TheCharter::builtin()).await
+    }
+
+    /// Initialize The Interface with settings from `charter`
+    ///
+    /// A setting's dedicated environment variable, where it has one, still
+    /// takes precedence over the charter.
+    pub async fn with_charter(
+        bind_address: String,
+        archive: Arc<TheArchive>,
+        charter: &TheCharter,
     ) -> CryptexResult<Self> {
         tracing::info!("The Interface awakening on {}", bind_address);
+        the_foundation::init_redaction(&charter.redaction.headers, &charter.redaction.key_prefixes);
 
         // Initialize components
+        the_assessor::init_shared_fetch_limit(charter.assessor.max_concurrent_fetches);
         let assessor = Arc::new(TheAssessor::the_awakening().await?);
-        let infiltrator = Arc::new(TheInfiltrator::the_awakening().await?);
-        let propagandist = Arc::new(ThePropagandist::the_awakening().await?);
-        let archive = Arc::new(TheArchive::the_awakening(archive_path)?);
+        // Scans interrupted by a restart resume from their last checkpoint
+        let (commit_every, commit_interval) =
+            the_infiltrator::commit_policy(charter.infiltrator.as_ref());
+        let mut infiltrator =
+            TheInfiltrator::the_awakening_with_checkpoints(archive.clone(), commit_every).await?;
+        if let Some(interval) = commit_interval {
+            infiltrator = infiltrator.with_commit_interval(interval);
+        }
+        let gmp = match the_infiltrator::GmpConfig::from_env()? {
+            Some(config) => Some(config),
+            None => charter
+                .infiltrator
+                .as_ref()
+                .map(the_infiltrator::GmpConfig::from_charter)
+                .transpose()?
+                .flatten(),
+        };
+        if let Some(config) = gmp {
+            tracing::info!("Live scanner mode: scans run on gvmd at {:?}", config.endpoint);
+            infiltrator = infiltrator.with_gmp(the_infiltrator::GmpClient::new(config));
+        }
+        // Only a charter asking for metrics history needs the collective here
+        let collective = match charter.metrics_history {
+            Some(_) => Some(Arc::new(TheCollective::the_awakening(charter.clone()).await?)),
+            None => None,
+        };
+        let agitator = match &collective {
+            Some(collective) => collective.primary_agitator(),
+            None => TheCollective::remediation_agitator(charter).await,
+        };
+        if let Some(agitator) = agitator {
+            infiltrator = infiltrator.with_agitator(agitator);
+        }
+        // Outbound clients built here share the charter's connection settings
+        let http = charter.connection.http_settings();
+        let infiltrator = infiltrator.with_notifier(the_infiltrator::Notifier::from_charter(
+            &charter.notifications,
+            &http,
+        ));
+        let infiltrator = Arc::new(infiltrator);
+        let propagandist = Arc::new(
+            ThePropagandist::the_awakening()
+                .await?
+                .with_render_concurrency(
+                    the_propagandist::render_concurrency_from_env()
+                        .unwrap_or(charter.commune.report_concurrency),
+                )
+                .with_audience(the_propagandist::report_audience(&charter.report.audience)?)
+                .with_sections(the_propagandist::report_sections(&charter.report.sections)?),
+        );
+
+        // Reports exported to disk are confined to this directory
+        let export_root = std::env::var("CRYPTEX_EXPORT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("./exports"));
 
         let state = AppState {
             assessor,
             infiltrator,
             propagandist,
             archive,
+            export_root,
+            jobs: JobRegistry::default(),
+            webhook: CompletionWebhook::from_env(&http)
+                .or_else(|| CompletionWebhook::from_charter(&charter.commune, &http)),
+            scan_keys: IdempotencyCache::default(),
+            signing_key: signing_key_from_env(),
+            ready: Arc::new(ReadyState::default()),
+            deprecations: Arc::new(Deprecations::from_charter(
+                &charter.http.deprecated_routes,
+            )),
+            collective,
+            #[cfg(feature = "object-storage")]
+            exporter: Arc::new(exporter::S3Exporter::from_charter(
+                charter.object_storage.as_ref(),
+                &http,
+            )?),
         };
 
         Ok(Self {
             bind_address,
             state,
+            limits: RequestLimits::from_charter(&charter.http),
+            retain_days: the_archive::retain_days_from_env().or(charter.archive.retain_days),
+            warm_up: the_assessor::warm_up_watchlist(
+                charter.assessor.warm_up,
+                &charter.assessor.watchlist,
+            ),
         })
     }
 
+    /// The archive handle shared by all handlers
+    pub fn archive(&self) -> Arc<TheArchive> {
+        self.state.archive.clone()
+    }
+
+    /// Set the directory report exports are written to
+    pub fn with_export_root(mut self, export_root: PathBuf) -> Self {
+        self.state.export_root = export_root;
+        self
+    }
+
+    /// POST each scan report to this webhook when the scan ends
+    pub fn with_completion_webhook(mut self, webhook: CompletionWebhook) -> Self {
+        self.state.webhook = Some(webhook);
+        self
+    }
+
+    /// Upload reports requested through `report/upload` with this exporter
+    #[cfg(feature = "object-storage")]
+    pub fn with_exporter(mut self, exporter: impl exporter::ReportExporter + 'static) -> Self {
+        self.state.exporter = Arc::new(exporter);
+        self
+    }
+
+    /// Mark a route deprecated, so its responses carry `Deprecation` and `Sunset`
+    pub fn with_deprecated_route(mut self, route: DeprecatedRoute) -> Self {
+        Arc::make_mut(&mut self.state.deprecations).insert(route);
+        self
+    }
+
+    /// Cap request body sizes and request durations
+    pub fn with_request_limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Serve `/api/v1/metrics/history` from this collective's observer
+    pub fn with_collective(mut self, collective: Arc<TheCollective>) -> Self {
+        self.state.collective = Some(collective);
+        self
+    }
+
+    /// Sign reports requested with `?sign=true` using this key
+    pub fn with_signing_key(mut self, key: SigningKey) -> Self {
+        self.state.signing_key = Some(Arc::new(key));
+        self
+    }
+
     /// The Manifestation - Start the HTTP server
     ///
     /// Traditional name: `serve` or `run`
     pub async fn the_manifestation(self) -> CryptexResult<()> {
         tracing::info!("The Interface manifesting at {}", self.bind_address);
 
-        let app = create_router(self.state);
+        self.state.jobs.spawn_sweeper();
+        if let Some(retain_days) = self.retain_days {
+            the_archive::spawn_retention(self.state.archive.clone(), retain_days)?;
+        }
+        if let Some(watchlist) = self.warm_up {
+            the_assessor::spawn_warm_up(self.state.assessor.clone(), watchlist);
+        }
+
+        let ready = self.state.ready.clone();
+        let archive = self.state.archive.clone();
+        let app = create_router(self.state, self.limits);
 
         let listener = tokio::net::TcpListener::bind(&self.bind_address)
             .await
@@ -110,37 +321,141 @@ impl TheInterface {
                 CryptexError::ArchiveError(format!("Failed to bind to {}: {}", self.bind_address, e))
             })?;
 
-        tracing::info!("The Interface ready at {}", self.bind_address);
-
-        axum::serve(listener, app).await.map_err(|e| {
-            CryptexError::ArchiveError(format!("Server error: {}", e))
-        })?;
+        tracing::info!("The Interface listening at {}", self.bind_address);
+
+        // Requests other than /health get 503 until the archive answers a read
+        let self_check = readiness::run_self_check(
+            ready,
+            readiness::SELF_CHECK_ATTEMPTS,
+            readiness::SELF_CHECK_FIRST_DELAY,
+            move || archive.get_stats().map(|_| ()),
+        );
+        let server = axum::serve(listener, app);
+
+        tokio::pin!(self_check);
+        tokio::select! {
+            result = server => {
+                result.map_err(|e| CryptexError::ArchiveError(format!("Server error: {}", e)))?;
+            }
+            // A passing check leaves the server running; only a failure ends it
+            Err(e) = &mut self_check => {
+                tracing::error!("{}", e);
+                return Err(e);
+            }
+        }
 
         Ok(())
     }
 }
 
+/// Run the API server with the given database path and bind address
+///
+/// Convenience entry point used by the standalone server binary. Settings
+/// are read from the charter at `CRYPTEX_CONFIG`, else the default config
+/// locations; an invalid charter stops start-up.
+pub async fn run_server(db_path: &str, bind_address: &str) -> CryptexResult<()> {
+    let charter = load_charter()?;
+    let archive = Arc::new(TheArchive::the_awakening(PathBuf::from(db_path))?);
+    archive.ensure_unprotected()?;
+    let interface =
+        TheInterface::with_charter(bind_address.to_string(), archive, &charter).await?;
+
+    interface.the_manifestation().await
+}
+
+/// Environment variable naming the charter file
+pub const CONFIG_PATH_ENV: &str = "CRYPTEX_CONFIG";
+
+/// Load and validate the charter the servers run with
+///
+/// Read from the file named by `CRYPTEX_CONFIG`, else the default config
+/// locations, with environment overrides applied.
+pub fn load_charter() -> CryptexResult<TheCharter> {
+    let path = std::env::var_os(CONFIG_PATH_ENV).map(PathBuf::from);
+    let charter = TheCharter::the_charter_loading(path)
+        .map_err(|e| CryptexError::validation(format!("Failed to load charter: {}", e)))?;
+    charter
+        .validate()
+        .map_err(|e| CryptexError::validation(format!("Invalid charter: {}", e)))?;
+    Ok(charter)
+}
+
 /// Create the API router
-fn create_router(state: AppState) -> Router {
-    Router::new()
+fn create_router(state: AppState, limits: RequestLimits) -> Router {
+    // Long-running reports, exports, event streams and re-assessment skip the request limits
+    let streaming = Router::new()
+        .route("/api/v1/scans/:scan_id/bundle", get(get_scan_bundle))
+        .route("/api/v1/scans/:scan_id/report", get(generate_report))
+        .route("/api/v1/scans/:scan_id/report/export", post(export_report))
+        .route("/api/v1/reports/consolidated", post(generate_consolidated_report))
+        .route("/api/v1/events", get(stream_archive_events))
+        .route("/api/v1/maintenance/reassess", post(reassess_stale));
+    #[cfg(feature = "object-storage")]
+    let streaming = streaming.route("/api/v1/scans/:scan_id/report/upload", post(upload_report));
+
+    // GVM reports outgrow the API body limit; the enrichment runs as a job
+    let imports = Router::new().route("/api/v1/import/openvas", post(import_openvas));
+
+    let api = Router::new()
         // Health check
         .route("/health", get(health_check))
+        .route(
+            "/health/ready",
+            get(readiness::readiness_check).with_state(state.ready.clone()),
+        )
+        .route(
+            "/api/versions",
+            get(versioning::list_versions).with_state(state.deprecations.clone()),
+        )
         // Vulnerability assessment
-        .route("/api/v1/vulnerabilities/:cve_id", get(assess_vulnerability))
+        .route(
+            "/api/v1/vulnerabilities",
+            get(list_vulnerabilities).delete(clear_vulnerabilities),
+        )
+        .route(
+            "/api/v1/vulnerabilities/:cve_id",
+            get(assess_vulnerability).delete(delete_vulnerability),
+        )
+        .route("/api/v1/vulnerabilities/lookup", post(lookup_vulnerabilities))
+        .route("/api/v1/cvss/score", post(score_cvss_vector))
+        // Background jobs
+        .route("/api/v1/jobs/assess", post(start_assessment_job))
+        .route("/api/v1/jobs/:job_id", get(get_job))
         // Scans
         .route("/api/v1/scans", post(start_scan))
         .route("/api/v1/scans", get(list_scans))
-        .route("/api/v1/scans/:scan_id", get(get_scan))
+        .route("/api/v1/scans/:scan_id", get(get_scan).delete(delete_scan))
+        .route("/api/v1/scans/:scan_id/tags", put(set_scan_tags))
         .route("/api/v1/scans/:scan_id/end", post(end_scan))
+        .route("/api/v1/scans/:scan_id/pause", post(pause_scan))
+        .route("/api/v1/scans/:scan_id/resume", post(resume_scan))
+        .route("/api/v1/scans/:scan_id/progress", get(get_scan_progress))
         .route("/api/v1/scans/:scan_id/results", get(get_scan_results))
         // Reports
-        .route("/api/v1/scans/:scan_id/report", get(generate_report))
         .route("/api/v1/scans/:scan_id/executive-summary", get(get_executive_summary))
         // Archive
         .route("/api/v1/archive/stats", get(get_archive_stats))
+        .route("/metrics", get(metrics::metrics_handler).with_state(state.archive.clone()))
+        .route("/api/v1/activity", get(get_recent_activity))
+        .route("/api/v1/audit", get(get_audit_log))
+        .route("/api/v1/metrics/history", get(get_metrics_history))
+        .route("/api/v1/analytics/kev-coverage", get(get_kev_coverage));
+
+    limits
+        .apply(api)
+        .merge(limits.apply_to_imports(imports))
+        .merge(streaming)
+        .layer(axum::middleware::from_fn_with_state(
+            state.ready.clone(),
+            readiness::require_ready,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.deprecations.clone(),
+            versioning::version_headers,
+        ))
         .with_state(state)
         .layer(CorsLayer::new().allow_origin(Any))
-        .layer(TraceLayer::new_for_http())
+        .layer(trace::trace_layer())
 }
 
 // ============================================================================
@@ -153,21 +468,221 @@ struct HealthResponse {
     version: String,
 }
 
+/// Either `target` or `targets`, not both
 #[derive(Debug, Deserialize)]
 struct StartScanRequest {
+    #[serde(default)]
     target: String,
+    #[serde(default)]
+    targets: Vec<String>,
+    /// Per-scan enrichment settings; omitted fields keep the server defaults
+    #[serde(default)]
+    options: ScanOptionsOverride,
+    /// Caller-chosen scan id; a fresh UUID when absent
+    scan_id: Option<String>,
+    /// Replace an ended scan archived under `scan_id` instead of rejecting it
+    #[serde(default)]
+    replace: bool,
+    /// Labels for filtering, e.g. `["prod", "pci-scope"]`
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct StartScanResponse {
     scan_id: String,
     target: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    targets: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
     started_at: String,
 }
 
 #[derive(Debug, Deserialize)]
+struct ScanTagsRequest {
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConsolidatedReportRequest {
+    scan_ids: Vec<String>,
+    format: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
 struct ReportQuery {
     format: Option<String>,
+    /// HTML branding overrides
+    company_name: Option<String>,
+    logo_url: Option<String>,
+    accent_color: Option<String>,
+    /// Sign the report and return the signature in `X-Report-Signature`
+    sign: Option<bool>,
+    /// Comma-separated sections to render, e.g. `overview,stats`
+    sections: Option<String>,
+    /// Leave out findings detected with less confidence, e.g. `high`
+    min_confidence: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssessQuery {
+    cached_only: Option<bool>,
+    refresh: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReassessQuery {
+    older_than_hours: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReassessResponse {
+    refreshed: usize,
+    older_than_hours: u64,
+}
+
+/// Assessments older than this are re-assessed when no threshold is given
+const DEFAULT_REASSESS_HOURS: u64 = 24;
+
+#[derive(Debug, Deserialize)]
+struct SinceQuery {
+    since: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ClearVulnerabilitiesResponse {
+    removed: usize,
+}
+
+/// Request header that must be set to `vulnerabilities` to clear the cache
+const CONFIRM_CLEAR_HEADER: &str = "x-confirm-clear";
+
+/// Response header carrying the timestamp to pass as `since` on the next sync
+const SYNC_TIMESTAMP_HEADER: &str = "x-sync-timestamp";
+
+/// Response header set to `bypass` when an assessment was served without the archive
+pub const CACHE_HEADER: &str = "x-cache";
+
+/// Response header carrying the detached signature of a signed report
+pub const REPORT_SIGNATURE_HEADER: &str = "x-report-signature";
+
+/// Environment variable holding the report signing key (base64 32-byte seed)
+pub const REPORT_SIGNING_KEY_ENV: &str = "CRYPTEX_REPORT_SIGNING_KEY";
+
+/// Signing key configured through the environment, if any
+fn signing_key_from_env() -> Option<Arc<SigningKey>> {
+    let encoded = std::env::var(REPORT_SIGNING_KEY_ENV).ok()?;
+
+    match decode_signing_key(&encoded) {
+        Ok(key) => Some(Arc::new(key)),
+        Err(e) => {
+            tracing::warn!("Report signing disabled: {}", e);
+            None
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CvssScoreRequest {
+    cve_id: Option<String>,
+    vector: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CvssScoreResponse {
+    cve_id: Option<String>,
+    version: String,
+    vector: String,
+    base_score: f64,
+    severity: CvssSeverity,
+    metrics: CvssV3Base,
+}
+
+#[derive(Debug, Deserialize)]
+struct VulnerabilityLookupRequest {
+    cve_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssessmentJobRequest {
+    cve_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AssessmentJobResponse {
+    job_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ImportJobResponse {
+    job_id: String,
+    scan_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResultsQuery {
+    include_assessment: Option<bool>,
+}
+
+/// Scan result joined with its full vulnerability assessment
+#[derive(Debug, Serialize)]
+struct EnrichedScanResult {
+    #[serde(flatten)]
+    result: the_infiltrator::ScanResult,
+    assessment: Option<VulnerabilityScore>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ActivityQuery {
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuditQuery {
+    since: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetricsHistoryQuery {
+    since: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct MetricsSampleResponse {
+    taken_at: chrono::DateTime<chrono::Utc>,
+    metrics: ObserverMetrics,
+}
+
+/// Audit entries returned when no limit is given
+const DEFAULT_AUDIT_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct ExportReportRequest {
+    format: Option<String>,
+    path: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportReportResponse {
+    bytes_written: usize,
+    path: String,
+}
+
+/// `destination` is a presigned URL or an object key in the configured bucket
+#[cfg(feature = "object-storage")]
+#[derive(Debug, Deserialize)]
+struct UploadReportRequest {
+    format: Option<String>,
+    destination: String,
+}
+
+#[cfg(feature = "object-storage")]
+#[derive(Debug, Serialize)]
+struct UploadReportResponse {
+    bytes_uploaded: usize,
+    url: String,
 }
 
 // ============================================================================
@@ -183,52 +698,403 @@ async fn health_check() -> Json<HealthResponse> {
 }
 
 /// Assess a CVE vulnerability
+///
+/// With `?cached_only=true` only the archive and the assessor cache are consulted;
+/// a CVE found in neither returns 404 instead of triggering a fetch.
+/// With `?refresh=true` both are bypassed and the fresh result replaces them.
+/// The CVE ID may be in any case or separator style (`cve 2021 44228`).
+///
+/// The archive is best-effort here: if it can't be read or written the
+/// assessment is still served, marked `X-Cache: bypass`.
 async fn assess_vulnerability(
     State(state): State<AppState>,
     Path(cve_id): Path<String>,
-) -> Result<Json<VulnerabilityScore>, ApiError> {
+    Query(query): Query<AssessQuery>,
+) -> Result<(HeaderMap, Json<VulnerabilityScore>), ApiError> {
+    let cve_id = normalize_cve_id(&cve_id)?;
     tracing::info!("Assessing vulnerability: {}", cve_id);
 
+    let options = AssessOptions {
+        refresh: query.refresh.unwrap_or(false),
+        cached_only: query.cached_only.unwrap_or(false),
+    };
+    let mut headers = HeaderMap::new();
+
     // Check archive first
-    if let Some(stored) = state.archive.get_vulnerability(&cve_id)? {
-        tracing::debug!("Vulnerability {} found in archive", cve_id);
-        return Ok(Json(stored.score));
+    if !options.refresh {
+        match state.archive.get_vulnerability(&cve_id) {
+            Ok(Some(stored)) => {
+                tracing::debug!("Vulnerability {} found in archive", cve_id);
+                return Ok((headers, Json(stored.score)));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("Archive unreadable, assessing {} without it: {}", cve_id, e);
+                headers.insert(CACHE_HEADER, HeaderValue::from_static("bypass"));
+            }
+        }
+    }
+
+    let score = state
+        .assessor
+        .assess_with_options(&cve_id, &options)
+        .await?
+        .ok_or_else(|| assessment_not_found(&cve_id))?;
+
+    // Anything not served from cache is new; store it
+    if !options.cached_only {
+        if let Err(e) = state.archive.store_vulnerability(&score) {
+            tracing::warn!("Failed to archive assessment of {}: {}", cve_id, e);
+            headers.insert(CACHE_HEADER, HeaderValue::from_static("bypass"));
+        }
+    }
+
+    Ok((headers, Json(score)))
+}
+
+fn assessment_not_found(cve_id: &str) -> ApiError {
+    ApiError::not_found(&format!("No cached assessment for {}", cve_id))
+        .with_code(ASSESSMENT_NOT_FOUND)
+        .with_details(serde_json::json!({ "cve_id": cve_id }))
+}
+
+/// Evict one cached assessment so the next request for it fetches fresh data
+async fn delete_vulnerability(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(cve_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let cve_id = normalize_cve_id(&cve_id)?;
+    let archived = state.archive.delete_vulnerability(&cve_id)?;
+    let cached = state.assessor.evict_cached(&cve_id).await;
+
+    if !(archived || cached) {
+        return Err(assessment_not_found(&cve_id));
+    }
+
+    tracing::info!("Evicted cached assessment for {}", cve_id);
+    audit::record(&state.archive, &actor, "vulnerability.delete", &cve_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Evict every cached assessment
+///
+/// Requires `X-Confirm-Clear: vulnerabilities` so a stray DELETE cannot
+/// empty the cache. Scans and their results are kept.
+async fn clear_vulnerabilities(
+    State(state): State<AppState>,
+    actor: Actor,
+    headers: HeaderMap,
+) -> Result<Json<ClearVulnerabilitiesResponse>, ApiError> {
+    let confirmed = headers
+        .get(CONFIRM_CLEAR_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("vulnerabilities"));
+    if !confirmed {
+        return Err(ApiError::bad_request(&format!(
+            "Clearing all assessments requires the {}: vulnerabilities header",
+            CONFIRM_CLEAR_HEADER
+        )));
+    }
+
+    let removed = state.archive.clear_vulnerabilities()?;
+    state.assessor.clear_cached().await;
+    audit::record(&state.archive, &actor, "vulnerabilities.clear", "*");
+
+    Ok(Json(ClearVulnerabilitiesResponse { removed }))
+}
+
+/// List archived assessments, optionally only those cached since a timestamp
+///
+/// Delta-sync contract: store the `X-Sync-Timestamp` response header and send it
+/// back as `?since=` to receive only assessments cached after this response.
+/// The header is the newest `cached_at` in the response, so it comes from the
+/// same snapshot as the items; cache times are stamped under the write lock
+/// and only move forward, so nothing committed later can sort before it.
+/// Deleted assessments are not reported in deltas.
+async fn list_vulnerabilities(
+    State(state): State<AppState>,
+    Query(query): Query<SinceQuery>,
+) -> Result<Response, ApiError> {
+    let changed_since = parse_since(query.since.as_deref())?;
+
+    tracing::info!("Listing vulnerabilities changed since {:?}", changed_since);
+
+    let filters = QueryFilters {
+        sort: VulnerabilitySort::CachedAt,
+        changed_since,
+        ..Default::default()
+    };
+    let items = ArchiveQuery::from_archive(state.archive.clone()).all_vulnerabilities(&filters)?;
+    let sync_timestamp = items
+        .iter()
+        .map(|item| item.cached_at)
+        .max()
+        .or(changed_since)
+        .unwrap_or(chrono::DateTime::UNIX_EPOCH);
+
+    Ok((
+        [(SYNC_TIMESTAMP_HEADER, sync_timestamp.to_rfc3339())],
+        Json(items),
+    )
+        .into_response())
+}
+
+/// Fetch archived assessments for a set of CVEs, keyed by CVE ID
+///
+/// Only the archive is read, in one transaction; CVEs without an archived
+/// assessment are left out of the response rather than assessed.
+async fn lookup_vulnerabilities(
+    State(state): State<AppState>,
+    Json(request): Json<VulnerabilityLookupRequest>,
+) -> Result<Json<HashMap<String, StoredVulnerability>>, ApiError> {
+    let cve_ids = request
+        .cve_ids
+        .iter()
+        .map(|cve_id| normalize_cve_id(cve_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    tracing::info!("Looking up {} archived vulnerabilities", cve_ids.len());
+
+    let found = ArchiveQuery::from_archive(state.archive.clone()).get_vulnerabilities(&cve_ids)?;
+
+    Ok(Json(found))
+}
+
+/// Parse an optional RFC 3339 `since` query parameter
+fn parse_since(since: Option<&str>) -> CryptexResult<Option<chrono::DateTime<chrono::Utc>>> {
+    since
+        .map(|since| {
+            chrono::DateTime::parse_from_rfc3339(since)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|e| CryptexError::validation(format!("Invalid since timestamp '{}': {}", since, e)))
+        })
+        .transpose()
+}
+
+/// Score a CVSS vector without looking up the CVE
+///
+/// Only the supplied vector is parsed; no external sources are consulted.
+async fn score_cvss_vector(
+    State(state): State<AppState>,
+    Json(request): Json<CvssScoreRequest>,
+) -> Result<Json<CvssScoreResponse>, ApiError> {
+    tracing::info!("Scoring CVSS vector: {}", request.vector);
+
+    let invalid_vector = |message: &str| {
+        ApiError::bad_request(message)
+            .with_code(INVALID_CVSS_VECTOR)
+            .with_details(serde_json::json!({ "vector": request.vector }))
+    };
+    let score = state
+        .assessor
+        .score_from_cvss_vector(request.cve_id.as_deref().unwrap_or_default(), &request.vector)
+        .await
+        .map_err(|e| match e {
+            CryptexError::ValidationError(_) => invalid_vector(&e.to_string()),
+            e => e.into(),
+        })?;
+    let cvss = score
+        .cvss_v3
+        .ok_or_else(|| invalid_vector("Vector produced no CVSS score"))?;
+
+    Ok(Json(CvssScoreResponse {
+        cve_id: request.cve_id,
+        version: cvss.version().to_string(),
+        vector: cvss.vector_string,
+        base_score: cvss.base_score,
+        severity: cvss.severity,
+        metrics: cvss.base_metrics,
+    }))
+}
+
+/// Queue a batch assessment as a background job
+///
+/// Returns `202 Accepted` with the job id straight away; poll `GET /api/v1/jobs/:job_id`.
+async fn start_assessment_job(
+    State(state): State<AppState>,
+    Json(request): Json<AssessmentJobRequest>,
+) -> Result<(StatusCode, Json<AssessmentJobResponse>), ApiError> {
+    if request.cve_ids.is_empty() {
+        return Err(ApiError::bad_request("cve_ids must not be empty"));
     }
+    let cve_ids = request
+        .cve_ids
+        .iter()
+        .map(|cve_id| normalize_cve_id(cve_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    tracing::info!("Queueing assessment job for {} CVEs", cve_ids.len());
+
+    let job_id = state
+        .jobs
+        .spawn_assessment(state.assessor.clone(), state.archive.clone(), cve_ids)
+        .await;
 
-    // Assess and store
-    let score = state.assessor.assess_vulnerability(&cve_id).await?;
-    state.archive.store_vulnerability(&score)?;
+    Ok((StatusCode::ACCEPTED, Json(AssessmentJobResponse { job_id })))
+}
 
-    Ok(Json(score))
+/// Get the status of a background job
+async fn get_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<Json<JobState>, ApiError> {
+    state
+        .jobs
+        .get(&job_id)
+        .await
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(&format!("Job not found: {}", job_id))
+                .with_code(JOB_NOT_FOUND)
+                .with_details(serde_json::json!({ "job_id": job_id }))
+        })
 }
 
 /// Start a new scan
+///
+/// With an `Idempotency-Key` header, a repeat of the same request within the
+/// key's TTL returns the original scan with 200 instead of starting another.
 async fn start_scan(
     State(state): State<AppState>,
-    Json(request): Json<StartScanRequest>,
-) -> Result<Json<StartScanResponse>, ApiError> {
-    tracing::info!("Starting scan on target: {}", request.target);
+    actor: Actor,
+    headers: HeaderMap,
+    Json(mut request): Json<StartScanRequest>,
+) -> Result<(StatusCode, Json<StartScanResponse>), ApiError> {
+    if request.target.is_empty() == request.targets.is_empty() {
+        return Err(ApiError::bad_request(
+            "Provide either 'target' or a non-empty 'targets' array",
+        ));
+    }
+    request.tags = ScanMetadata::normalize_tags(request.tags)?;
+
+    let key = match headers.get(IDEMPOTENCY_HEADER) {
+        Some(value) => Some(value.to_str().map_err(|_| {
+            ApiError::bad_request("Idempotency-Key must be visible ASCII")
+        })?),
+        None => None,
+    };
+    let Some(key) = key else {
+        let response = create_scan(&state, &actor, request).await?;
+        audit::record(&state.archive, &actor, "scan.start", &response.scan_id);
+        return Ok((StatusCode::CREATED, Json(response)));
+    };
+
+    let fingerprint = format!(
+        "{}\n{:?}\n{:?}\n{}\n{:?}",
+        if request.targets.is_empty() {
+            request.target.clone()
+        } else {
+            request.targets.join("\n")
+        },
+        request.options.merge(state.infiltrator.default_scan_options()),
+        request.scan_id,
+        request.replace,
+        request.tags
+    );
+    match state
+        .scan_keys
+        .run(key, &fingerprint, create_scan(&state, &actor, request))
+        .await?
+    {
+        Idempotent::Created(response) => {
+            audit::record(&state.archive, &actor, "scan.start", &response.scan_id);
+            Ok((StatusCode::CREATED, Json(response)))
+        }
+        Idempotent::Replayed(response) => {
+            tracing::info!("Idempotency key replayed for scan {}", response.scan_id);
+            Ok((StatusCode::OK, Json(response)))
+        }
+        Idempotent::Conflict => Err(ApiError::conflict(
+            "Idempotency-Key was already used for a different scan request",
+        )
+        .with_code(IDEMPOTENCY_CONFLICT)),
+    }
+}
 
-    let scan_id = state.infiltrator.start_scan(&request.target).await?;
+/// Start the scan and record its metadata
+///
+/// A caller-chosen `scan_id` that is already archived is a conflict unless
+/// `replace` is set. Then the old scan and its results are deleted in the
+/// same transaction that records the new one, and the replacement is audited,
+/// so a re-run pipeline leaves one scan behind rather than one per run.
+async fn create_scan(
+    state: &AppState,
+    actor: &Actor,
+    request: StartScanRequest,
+) -> Result<StartScanResponse, ApiError> {
+    let StartScanRequest {
+        target,
+        targets,
+        options,
+        scan_id,
+        replace,
+        tags,
+    } = request;
+    let options = options.merge(state.infiltrator.default_scan_options());
+    let scan_targets = if targets.is_empty() {
+        tracing::info!("Starting scan on target: {}", target);
+        std::slice::from_ref(&target)
+    } else {
+        tracing::info!("Starting scan on {} targets", targets.len());
+        targets.as_slice()
+    };
+    let scan_id = match scan_id {
+        Some(scan_id) => {
+            // An id that is still active is rejected by the infiltrator
+            let archived = state.archive.get_scan_metadata(&scan_id)?.is_some();
+            if archived && !replace && state.infiltrator.scan_progress(&scan_id).await.is_err() {
+                return Err(ApiError::conflict(&format!(
+                    "Scan {} is already archived; set \"replace\": true to replace it",
+                    scan_id
+                ))
+                .with_code(SCAN_ID_IN_USE)
+                .with_details(serde_json::json!({ "scan_id": scan_id })));
+            }
+            state
+                .infiltrator
+                .start_scan_with_id_and_options(&scan_id, scan_targets, options)
+                .await?
+        }
+        None => state.infiltrator.start_scan_with_options(scan_targets, options).await?,
+    };
 
     // Store scan metadata
-    let metadata = ScanMetadata::new(scan_id.clone(), request.target.clone());
-    state.archive.store_scan_metadata(&metadata)?;
+    let target = if targets.is_empty() { target } else { targets.join(", ") };
+    let mut metadata = ScanMetadata::new(scan_id.clone(), target.clone());
+    metadata.targets = targets.clone();
+    metadata.options = options;
+    metadata.tags = tags.clone();
+    let mut batch = state.archive.begin_batch()?;
+    let replaced = if replace && batch.scan_metadata(&scan_id)?.is_some() {
+        Some(batch.delete_scan(&scan_id)?)
+    } else {
+        None
+    };
+    batch.store_scan_metadata(&metadata)?;
+    batch.commit()?;
+    if let Some(results) = replaced {
+        tracing::info!("Scan {} replaced; dropped {} archived results", scan_id, results);
+        audit::record(&state.archive, actor, "scan.replace", &scan_id);
+    }
 
-    Ok(Json(StartScanResponse {
-        scan_id: scan_id.clone(),
-        target: request.target,
+    Ok(StartScanResponse {
+        scan_id,
+        target,
+        targets,
+        tags,
         started_at: chrono::Utc::now().to_rfc3339(),
-    }))
+    })
 }
 
 /// List all scans
 async fn list_scans(
     State(state): State<AppState>,
+    Query(filters): Query<ScanFilters>,
 ) -> Result<Json<Vec<ScanMetadata>>, ApiError> {
-    tracing::info!("Listing all scans");
+    tracing::info!("Listing scans matching {:?}", filters);
 
-    let scans = state.archive.list_scans()?;
+    let scans = ArchiveQuery::from_archive(state.archive.clone()).find_scans(&filters)?;
 
     Ok(Json(scans))
 }
@@ -243,7 +1109,48 @@ async fn get_scan(
     let metadata = state
         .archive
         .get_scan_metadata(&scan_id)?
-        .ok_or_else(|| ApiError::not_found("Scan not found"))?;
+        .ok_or_else(|| ApiError::scan_not_found(&scan_id))?;
+
+    Ok(Json(metadata))
+}
+
+/// Delete an archived scan and its results
+async fn delete_scan(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(scan_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    if state.archive.get_scan_metadata(&scan_id)?.is_none() {
+        return Err(ApiError::scan_not_found(&scan_id));
+    }
+
+    // A scan still running would write its results back after the delete
+    match state.infiltrator.discard_scan(&scan_id).await {
+        Ok(()) | Err(CryptexError::NotFound(_)) => {}
+        Err(e) => return Err(e.into()),
+    }
+    let removed = state.archive.delete_scan(&scan_id)?;
+    tracing::info!("Deleted scan {} and {} results", scan_id, removed);
+    audit::record(&state.archive, &actor, "scan.delete", &scan_id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Replace the tags of an archived scan
+async fn set_scan_tags(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(scan_id): Path<String>,
+    Json(request): Json<ScanTagsRequest>,
+) -> Result<Json<ScanMetadata>, ApiError> {
+    let tags = ScanMetadata::normalize_tags(request.tags)?;
+    tracing::info!("Tagging scan {} with {:?}", scan_id, tags);
+
+    let metadata = state
+        .archive
+        .update_scan_metadata(&scan_id, |metadata| metadata.tags = tags)
+        .map_err(scan_error(&scan_id))?;
+    audit::record(&state.archive, &actor, "scan.tag", &scan_id);
 
     Ok(Json(metadata))
 }
@@ -251,85 +1158,392 @@ async fn get_scan(
 /// End a scan
 async fn end_scan(
     State(state): State<AppState>,
+    actor: Actor,
     Path(scan_id): Path<String>,
 ) -> Result<Json<ScanReport>, ApiError> {
     tracing::info!("Ending scan: {}", scan_id);
 
-    let report = state.infiltrator.end_scan(&scan_id).await?;
+    let report = state.infiltrator.end_scan(&scan_id).await.map_err(scan_error(&scan_id))?;
+    archive_scan_report(&state.archive, &report)?;
+    audit::record(&state.archive, &actor, "scan.end", &scan_id);
 
-    // Update metadata in archive
-    let mut metadata = state
-        .archive
-        .get_scan_metadata(&scan_id)?
-        .ok_or_else(|| ApiError::not_found("Scan not found"))?;
+    // Best-effort notification; delivery failures are only logged
+    if let Some(webhook) = &state.webhook {
+        webhook.spawn_delivery(report.clone());
+    }
+
+    Ok(Json(report))
+}
+
+/// Import a GVM report XML file as a completed scan
+///
+/// The report is parsed and its scan registered before answering
+/// `202 Accepted`; enriching the findings runs as a background job, and the
+/// scan is archived under its new id when the job completes.
+async fn import_openvas(
+    State(state): State<AppState>,
+    actor: Actor,
+    body: axum::body::Bytes,
+) -> Result<(StatusCode, Json<ImportJobResponse>), ApiError> {
+    tracing::info!("Importing OpenVAS report ({} bytes)", body.len());
+
+    let import = state.infiltrator.begin_openvas_import(body.as_ref()).await?;
+    let scan_id = import.scan_id().to_string();
+    audit::record(&state.archive, &actor, "scan.import", &scan_id);
+
+    let job_id = state
+        .jobs
+        .spawn_import(state.infiltrator.clone(), state.archive.clone(), import)
+        .await;
+
+    Ok((StatusCode::ACCEPTED, Json(ImportJobResponse { job_id, scan_id })))
+}
 
+/// Archive an ended scan as completed
+///
+/// Final counts and every finding, not just those caught by the last
+/// checkpoint, land in one transaction so the scan can be reported on later.
+/// A scan the archive has no record of yet, such as an import, gets one
+/// built from the report.
+pub(crate) fn archive_scan_report(archive: &TheArchive, report: &ScanReport) -> CryptexResult<()> {
+    let mut batch = archive.begin_batch()?;
+    let mut metadata = match batch.scan_metadata(&report.scan_id)? {
+        Some(metadata) => metadata,
+        None => {
+            let mut metadata = ScanMetadata::new(report.scan_id.clone(), report.target.clone());
+            if report.targets.len() > 1 {
+                metadata.targets = report.targets.clone();
+            }
+            if let Some(started_at) = timestamp(report.start_time) {
+                metadata.started_at = started_at;
+            }
+            metadata
+        }
+    };
     metadata.status = "completed".to_string();
-    metadata.ended_at = Some(chrono::Utc::now());
+    metadata.ended_at = report.end_time.and_then(timestamp).or_else(|| Some(chrono::Utc::now()));
     metadata.total_vulnerabilities = report.total_vulnerabilities;
     metadata.critical = report.critical_count;
     metadata.high = report.high_count;
     metadata.medium = report.medium_count;
     metadata.low = report.low_count;
+    batch.store_scan_metadata(&metadata)?;
+    for result in &report.scan_results {
+        batch.store_scan_result(&report.scan_id, result)?;
+    }
+    batch.commit()?;
+    Ok(())
+}
 
-    state.archive.store_scan_metadata(&metadata)?;
-
-    Ok(Json(report))
+/// A report's Unix timestamp as a date
+fn timestamp(seconds: u64) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::from_timestamp(i64::try_from(seconds).ok()?, 0)
 }
 
-/// Get scan results
-async fn get_scan_results(
+/// Pause a running scan
+async fn pause_scan(
     State(state): State<AppState>,
+    actor: Actor,
     Path(scan_id): Path<String>,
-) -> Result<Json<Vec<the_infiltrator::ScanResult>>, ApiError> {
-    tracing::info!("Getting results for scan: {}", scan_id);
+) -> Result<Json<ScanMetadata>, ApiError> {
+    tracing::info!("Pausing scan: {}", scan_id);
 
-    let results = state.archive.get_scan_results(&scan_id)?;
+    state.infiltrator.pause_scan(&scan_id).await.map_err(scan_error(&scan_id))?;
 
-    Ok(Json(results))
+    let metadata = state.archive.update_scan_metadata(&scan_id, |metadata| {
+        metadata.status = "paused".to_string();
+    })?;
+    audit::record(&state.archive, &actor, "scan.pause", &scan_id);
+
+    Ok(Json(metadata))
 }
 
-/// Generate report
-async fn generate_report(
+/// Resume a paused scan
+async fn resume_scan(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(scan_id): Path<String>,
+) -> Result<Json<ScanMetadata>, ApiError> {
+    tracing::info!("Resuming scan: {}", scan_id);
+
+    state.infiltrator.resume_scan(&scan_id).await.map_err(scan_error(&scan_id))?;
+
+    let metadata = state.archive.update_scan_metadata(&scan_id, |metadata| {
+        metadata.status = "running".to_string();
+    })?;
+    audit::record(&state.archive, &actor, "scan.resume", &scan_id);
+
+    Ok(Json(metadata))
+}
+
+/// How far along a scan is
+///
+/// Active scans report live progress; a scan that has ended is complete.
+async fn get_scan_progress(
+    State(state): State<AppState>,
+    Path(scan_id): Path<String>,
+) -> Result<Json<ScanProgress>, ApiError> {
+    match state.infiltrator.scan_progress(&scan_id).await {
+        Ok(progress) => Ok(Json(progress)),
+        Err(CryptexError::NotFound(_)) => match state.archive.get_scan_metadata(&scan_id)? {
+            Some(metadata) if metadata.ended_at.is_some() => Ok(Json(ScanProgress::finished(
+                the_infiltrator::count_target_hosts(&metadata.target),
+                metadata.total_vulnerabilities,
+            ))),
+            _ => Err(ApiError::scan_not_found(&scan_id)),
+        },
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Get scan results
+///
+/// With `?include_assessment=true` each result carries an `assessment` field holding
+/// the archived assessment for its CVE (falling back to the embedded score), or
+/// `null` when the finding has not been assessed.
+async fn get_scan_results(
+    State(state): State<AppState>,
+    Path(scan_id): Path<String>,
+    Query(query): Query<ResultsQuery>,
+) -> Result<Response, ApiError> {
+    tracing::info!("Getting results for scan: {}", scan_id);
+
+    let results = state.archive.get_scan_results(&scan_id)?;
+
+    if !query.include_assessment.unwrap_or(false) {
+        return Ok(Json(results).into_response());
+    }
+
+    let mut enriched = Vec::with_capacity(results.len());
+    for result in results {
+        let assessment = match state.archive.get_vulnerability(&result.cve_id)? {
+            Some(stored) => Some(stored.score),
+            None => result.vulnerability_score.clone(),
+        };
+
+        enriched.push(EnrichedScanResult { result, assessment });
+    }
+
+    Ok(Json(enriched).into_response())
+}
+
+/// Download a scan with its results and assessments as one portable JSON bundle
+async fn get_scan_bundle(
+    State(state): State<AppState>,
+    Path(scan_id): Path<String>,
+) -> Result<Response, ApiError> {
+    tracing::info!("Exporting bundle for scan: {}", scan_id);
+
+    let bundle = ArchiveQuery::from_archive(state.archive.clone())
+        .export_scan_bundle(&scan_id)
+        .map_err(scan_error(&scan_id))?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (axum::http::header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                axum::http::header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.bundle.json\"", scan_id),
+            ),
+        ],
+        bundle,
+    )
+        .into_response())
+}
+
+/// Generate report
+async fn generate_report(
     State(state): State<AppState>,
     Path(scan_id): Path<String>,
     Query(query): Query<ReportQuery>,
+    headers: HeaderMap,
 ) -> Result<Response, ApiError> {
     tracing::info!("Generating report for scan: {}", scan_id);
 
-    // Get scan report
-    let report = state.infiltrator.end_scan(&scan_id).await?;
+    // Determine format and branding, rejecting a bad theme before ending the scan
+    let accept = headers
+        .get(axum::http::header::ACCEPT)
+        .and_then(|value| value.to_str().ok());
+    let output = ReportOutput::negotiate(query.format.as_deref(), accept);
+    let format = match output {
+        ReportOutput::Report(format) => format,
+        ReportOutput::Sarif => ReportFormat::Json,
+    };
+    let mut config = state.propagandist.report_config(format);
+    if query.company_name.is_some() {
+        config.theme.company_name = query.company_name;
+    }
+    if query.logo_url.is_some() {
+        config.theme.logo_url = query.logo_url;
+    }
+    if let Some(accent_color) = query.accent_color {
+        config.theme.accent_color = accent_color;
+    }
+    config.theme.validate()?;
+    if let Some(sections) = query.sections.as_deref() {
+        config.sections = SectionToggles::parse(sections)?;
+    }
+    if let Some(minimum) = query.min_confidence.as_deref() {
+        config.min_confidence = Some(minimum.parse()?);
+    }
 
-    // Determine format
-    let format_str = query.format.as_deref().unwrap_or("json");
-    let format = match format_str {
-        "html" => ReportFormat::Html,
-        "markdown" | "md" => ReportFormat::Markdown,
-        "text" | "txt" => ReportFormat::Text,
-        _ => ReportFormat::Json,
+    let signing_key = if query.sign.unwrap_or(false) {
+        let key = state.signing_key.clone().ok_or_else(|| {
+            ApiError::bad_request("Report signing is not configured on this server")
+        })?;
+        Some(key)
+    } else {
+        None
     };
 
-    // Generate report
-    let content = state
-        .propagandist
-        .generate_report(&report, format)
-        .await?;
+    // Get scan report
+    let mut report = state.infiltrator.end_scan(&scan_id).await.map_err(scan_error(&scan_id))?;
+
+    // The format can depend on Accept, so caches must key on it
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        axum::http::header::CONTENT_TYPE,
+        HeaderValue::from_static(output.mime_type()),
+    );
+    response_headers.insert(axum::http::header::VARY, HeaderValue::from_static("accept"));
+
+    // A signature covers the whole document, and a SARIF log is one JSON
+    // object, so both are built in full before anything is sent
+    if signing_key.is_some() || output == ReportOutput::Sarif {
+        let content = if output == ReportOutput::Sarif {
+            if let Some(minimum) = config.min_confidence {
+                report.retain_results(|result| result.meets_confidence(minimum));
+            }
+            let findings: Vec<StoredScanResult> = report
+                .scan_results
+                .into_iter()
+                .map(|result| StoredScanResult {
+                    scan_id: scan_id.clone(),
+                    result,
+                })
+                .collect();
+            the_archive::export::export_sarif(&findings)?
+        } else {
+            state.propagandist.generate_with_config(&report, &config).await?
+        };
 
-    // Set appropriate content type
-    let content_type = match format {
-        ReportFormat::Html => "text/html",
-        ReportFormat::Markdown => "text/markdown",
-        ReportFormat::Text => "text/plain",
-        ReportFormat::Json => "application/json",
-    };
+        if let Some(key) = signing_key {
+            let signature = state.propagandist.sign_report(&content, &key);
+            response_headers.insert(
+                REPORT_SIGNATURE_HEADER,
+                HeaderValue::from_str(&signature).expect("base64 is a valid header value"),
+            );
+        }
+
+        return Ok((StatusCode::OK, response_headers, content).into_response());
+    }
+
+    // Stream the report section by section instead of buffering it
+    let sections = state.propagandist.generate_stream(report, &config)?;
+
+    Ok((
+        StatusCode::OK,
+        response_headers,
+        axum::body::Body::from_stream(sections),
+    )
+        .into_response())
+}
+
+/// Generate one report across several archived scans
+///
+/// Findings reported by more than one scan are counted once; each scan's own
+/// numbers appear in a per-scan breakdown.
+async fn generate_consolidated_report(
+    State(state): State<AppState>,
+    Json(request): Json<ConsolidatedReportRequest>,
+) -> Result<Response, ApiError> {
+    tracing::info!("Generating consolidated report for {} scans", request.scan_ids.len());
+
+    if request.scan_ids.is_empty() {
+        return Err(ApiError::bad_request("scan_ids must not be empty"));
+    }
+
+    let reports = request
+        .scan_ids
+        .iter()
+        .map(|scan_id| {
+            state.archive.load_scan_report(scan_id)?.ok_or_else(|| ApiError::scan_not_found(scan_id))
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    let format = parse_report_format(request.format.as_deref());
+    let content = state.propagandist.generate_consolidated(&reports, format).await?;
 
     Ok((
         StatusCode::OK,
-        [(axum::http::header::CONTENT_TYPE, content_type)],
+        [(axum::http::header::CONTENT_TYPE, format.mime_type())],
         content,
     )
         .into_response())
 }
 
+/// Export a report to a file under the export root
+async fn export_report(
+    State(state): State<AppState>,
+    Path(scan_id): Path<String>,
+    Json(request): Json<ExportReportRequest>,
+) -> Result<Json<ExportReportResponse>, ApiError> {
+    tracing::info!("Exporting report for scan {} to {}", scan_id, request.path);
+
+    // Validate the destination before doing any work
+    let destination = resolve_export_path(&state.export_root, &request.path)
+        .map_err(|e| ApiError::bad_request(&e.to_string()))?;
+    let (root, target) = (state.export_root.clone(), destination.clone());
+    blocking(move || prepare_export_destination(&root, &target))
+        .await
+        .map_err(|e| ApiError::bad_request(&e.to_string()))?;
+
+    let report = state.infiltrator.end_scan(&scan_id).await.map_err(scan_error(&scan_id))?;
+    let format = parse_report_format(request.format.as_deref());
+
+    let content = state
+        .propagandist
+        .generate_report(&report, format)
+        .await?;
+
+    let bytes_written = content.len();
+    let target = destination.clone();
+    blocking(move || write_export_file(&target, content.as_bytes())).await?;
+
+    Ok(Json(ExportReportResponse {
+        bytes_written,
+        path: destination.display().to_string(),
+    }))
+}
+
+/// Render a report and upload it to object storage
+#[cfg(feature = "object-storage")]
+async fn upload_report(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(scan_id): Path<String>,
+    Json(request): Json<UploadReportRequest>,
+) -> Result<(StatusCode, Json<UploadReportResponse>), ApiError> {
+    if request.destination.trim().is_empty() {
+        return Err(ApiError::bad_request("destination must not be empty"));
+    }
+    tracing::info!("Uploading report for scan {}", scan_id);
+
+    let report = state.infiltrator.end_scan(&scan_id).await.map_err(scan_error(&scan_id))?;
+    let format = parse_report_format(request.format.as_deref());
+    let content = state.propagandist.generate_report(&report, format).await?;
+
+    let bytes_uploaded = content.len();
+    let url = state
+        .exporter
+        .upload(&request.destination, format.mime_type(), content.into_bytes())
+        .await?;
+    audit::record(&state.archive, &actor, "scan.report_upload", &scan_id);
+
+    Ok((StatusCode::CREATED, Json(UploadReportResponse { bytes_uploaded, url })))
+}
+
 /// Get executive summary
 async fn get_executive_summary(
     State(state): State<AppState>,
@@ -338,7 +1552,7 @@ async fn get_executive_summary(
     tracing::info!("Generating executive summary for scan: {}", scan_id);
 
     // Get scan report
-    let report = state.infiltrator.end_scan(&scan_id).await?;
+    let report = state.infiltrator.end_scan(&scan_id).await.map_err(scan_error(&scan_id))?;
 
     // Generate executive summary
     let summary = state
@@ -360,38 +1574,329 @@ async fn get_archive_stats(
     Ok(Json(stats))
 }
 
+/// Get the recent activity feed
+async fn get_recent_activity(
+    State(state): State<AppState>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<Vec<ActivityEvent>>, ApiError> {
+    let limit = query.limit.unwrap_or(20);
+    tracing::info!("Getting recent activity (limit {})", limit);
+
+    let events = ArchiveQuery::from_archive(state.archive.clone()).recent_activity(limit)?;
+
+    Ok(Json(events))
+}
+
+/// Read the audit log, oldest first
+async fn get_audit_log(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<Vec<AuditEntry>>, ApiError> {
+    let since = parse_since(query.since.as_deref())?;
+    let limit = query.limit.unwrap_or(DEFAULT_AUDIT_LIMIT);
+    tracing::info!("Reading audit log since {:?} (limit {})", since, limit);
+
+    Ok(Json(state.archive.audit_log(since, limit)?))
+}
+
+/// AI metrics snapshots taken after `since`, oldest first
+async fn get_metrics_history(
+    State(state): State<AppState>,
+    Query(query): Query<MetricsHistoryQuery>,
+) -> Result<Json<Vec<MetricsSampleResponse>>, ApiError> {
+    let collective = state.collective.as_ref().ok_or_else(|| {
+        ApiError::not_found("Metrics history is off; add a [metrics_history] charter section")
+            .with_code("METRICS_HISTORY_DISABLED")
+    })?;
+    let since = parse_since(query.since.as_deref())?;
+
+    let samples = collective
+        .metrics_history(since)
+        .into_iter()
+        .map(|(taken_at, metrics)| MetricsSampleResponse { taken_at, metrics })
+        .collect();
+    Ok(Json(samples))
+}
+
+/// Stream archive changes as server-sent events
+///
+/// Each event's data is the JSON `ArchiveEvent`. A client that falls too far
+/// behind is sent `{"event": "lagged", "missed": n}` in place of the changes
+/// it missed, and should re-fetch whatever it displays.
+async fn stream_archive_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    tracing::info!("Client subscribed to archive events");
+
+    let events = futures::stream::unfold(state.archive.subscribe(), |mut events| async move {
+        let data = match events.recv().await {
+            Ok(change) => serde_json::to_value(change).unwrap_or_default(),
+            Err(RecvError::Lagged(missed)) => {
+                serde_json::json!({ "event": "lagged", "missed": missed })
+            }
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok(Event::default().data(data.to_string())), events))
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// How many archived findings are known to be exploited
+async fn get_kev_coverage(
+    State(state): State<AppState>,
+) -> Result<Json<KevCoverage>, ApiError> {
+    tracing::info!("Computing KEV coverage");
+
+    let coverage = ArchiveQuery::from_archive(state.archive.clone()).kev_coverage()?;
+
+    Ok(Json(coverage))
+}
+
+/// Re-assess archived vulnerabilities whose stored score has gone stale
+///
+/// Runs to completion before responding, so a large archive makes for a slow
+/// request; fetches are paced to stay inside source rate limits.
+async fn reassess_stale(
+    State(state): State<AppState>,
+    actor: Actor,
+    Query(query): Query<ReassessQuery>,
+) -> Result<Json<ReassessResponse>, ApiError> {
+    let older_than_hours = query.older_than_hours.unwrap_or(DEFAULT_REASSESS_HOURS);
+    tracing::info!("Re-assessing vulnerabilities older than {}h", older_than_hours);
+
+    let older_than = std::time::Duration::from_secs(older_than_hours.saturating_mul(60 * 60));
+    let refreshed = state
+        .assessor
+        .reassess_stale(state.archive.as_ref(), older_than)
+        .await?;
+    audit::record(&state.archive, &actor, "vulnerabilities.reassess", "*");
+
+    Ok(Json(ReassessResponse {
+        refreshed,
+        older_than_hours,
+    }))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Map a format query string to a report format, defaulting to JSON
+fn parse_report_format(format: Option<&str>) -> ReportFormat {
+    match format.unwrap_or("json") {
+        "html" => ReportFormat::Html,
+        "markdown" | "md" => ReportFormat::Markdown,
+        "text" | "txt" => ReportFormat::Text,
+        "csv" => ReportFormat::Csv,
+        _ => ReportFormat::Json,
+    }
+}
+
+/// Resolve a requested export path against the export root
+///
+/// Relative paths are joined onto the root; absolute paths are only accepted if
+/// they already lie inside it. Any `..` component is rejected outright so a
+/// request can never escape the root.
+fn resolve_export_path(export_root: &FsPath, requested: &str) -> CryptexResult<PathBuf> {
+    let requested_path = FsPath::new(requested);
+
+    if requested.is_empty() || requested_path.file_name().is_none() {
+        return Err(CryptexError::validation("Export path must name a file"));
+    }
+
+    if requested_path
+        .components()
+        .any(|c| matches!(c, Component::ParentDir))
+    {
+        return Err(CryptexError::validation(format!(
+            "Export path may not contain '..': {}",
+            requested
+        )));
+    }
+
+    if requested_path.is_absolute() {
+        return if requested_path.starts_with(export_root) {
+            Ok(requested_path.to_path_buf())
+        } else {
+            Err(CryptexError::validation(format!(
+                "Export path escapes the export directory: {}",
+                requested
+            )))
+        };
+    }
+
+    Ok(export_root.join(requested_path))
+}
+
+/// Create the directory of a resolved export path and check it on disk
+///
+/// [`resolve_export_path`] is lexical, so a symlinked directory under the
+/// export root could still lead outside it. Each directory is checked with
+/// symlinks resolved, before anything is created beneath it, and a symlink
+/// in place of the file itself is refused.
+fn prepare_export_destination(export_root: &FsPath, destination: &FsPath) -> CryptexResult<()> {
+    std::fs::create_dir_all(export_root)?;
+    let root = std::fs::canonicalize(export_root)?;
+    let escapes = || {
+        CryptexError::validation(format!(
+            "Export path escapes the export directory: {}",
+            destination.display()
+        ))
+    };
+
+    let parent = destination.parent().unwrap_or(export_root);
+    let existing = parent
+        .ancestors()
+        .find(|dir| dir.symlink_metadata().is_ok())
+        .unwrap_or(export_root);
+    if !std::fs::canonicalize(existing)?.starts_with(&root) {
+        return Err(escapes());
+    }
+    std::fs::create_dir_all(parent)?;
+    if !std::fs::canonicalize(parent)?.starts_with(&root) {
+        return Err(escapes());
+    }
+
+    match destination.symlink_metadata() {
+        Ok(metadata) if metadata.file_type().is_symlink() => Err(CryptexError::validation(
+            format!("Export path is a symbolic link: {}", destination.display()),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Write an export through a temporary file renamed into place, so a failed
+/// write never leaves a truncated report behind
+fn write_export_file(destination: &FsPath, content: &[u8]) -> CryptexResult<()> {
+    use std::io::Write;
+
+    let dir = destination.parent().unwrap_or(FsPath::new("."));
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(content)?;
+    file.persist(destination).map_err(|e| CryptexError::from(e.error))?;
+    Ok(())
+}
+
+/// Run filesystem work off the async workers
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> CryptexResult<T> + Send + 'static,
+) -> CryptexResult<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .unwrap_or_else(|e| Err(CryptexError::unknown(format!("Export task panicked: {}", e))))
+}
+
 // ============================================================================
 // Error Handling
 // ============================================================================
 
+/// Codes more specific than the `CryptexError` kind, for the cases clients
+/// most often need to tell apart; see "Error Codes" in API_REFERENCE.md
+const SCAN_NOT_FOUND: &str = "SCAN_NOT_FOUND";
+const JOB_NOT_FOUND: &str = "JOB_NOT_FOUND";
+const ASSESSMENT_NOT_FOUND: &str = "ASSESSMENT_NOT_FOUND";
+const INVALID_CVSS_VECTOR: &str = "INVALID_CVSS_VECTOR";
+const IDEMPOTENCY_CONFLICT: &str = "IDEMPOTENCY_CONFLICT";
+const SCAN_ID_IN_USE: &str = "SCAN_ID_IN_USE";
+
 /// API error response
 #[derive(Debug, Serialize)]
 struct ErrorResponse {
+    /// Stable machine-readable code, e.g. `SCAN_NOT_FOUND`
+    code: &'static str,
+    /// HTTP status line, kept for clients that predate `code`
     error: String,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
 }
 
 /// API error wrapper
+#[derive(Debug)]
 struct ApiError {
     status: StatusCode,
+    code: &'static str,
     message: String,
+    details: Option<serde_json::Value>,
 }
 
 impl ApiError {
-    fn not_found(message: &str) -> Self {
+    fn new(status: StatusCode, code: &'static str, message: &str) -> Self {
         Self {
-            status: StatusCode::NOT_FOUND,
+            status,
+            code,
             message: message.to_string(),
+            details: None,
         }
     }
+
+    fn not_found(message: &str) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "NOT_FOUND", message)
+    }
+
+    fn bad_request(message: &str) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "VALIDATION_FAILED", message)
+    }
+
+    fn conflict(message: &str) -> Self {
+        Self::new(StatusCode::CONFLICT, "CONFLICT", message)
+    }
+
+    /// Replace the generic code with a more specific one
+    fn with_code(mut self, code: &'static str) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Attach structured context, e.g. the id that was not found
+    fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Error for a scan id that does not exist
+    fn scan_not_found(scan_id: &str) -> Self {
+        Self::not_found(&format!("Scan not found: {}", scan_id))
+            .with_code(SCAN_NOT_FOUND)
+            .with_details(serde_json::json!({ "scan_id": scan_id }))
+    }
+}
+
+/// Convert an error from a scan lookup, naming the scan if it was missing
+fn scan_error(scan_id: &str) -> impl FnOnce(CryptexError) -> ApiError + '_ {
+    move |err| match err {
+        CryptexError::NotFound(_) => ApiError::scan_not_found(scan_id),
+        err => err.into(),
+    }
 }
 
 impl From<CryptexError> for ApiError {
     fn from(err: CryptexError) -> Self {
-        tracing::error!("API error: {:?}", err);
+        let status = match &err {
+            CryptexError::NotFound(_) => StatusCode::NOT_FOUND,
+            CryptexError::ValidationError(_) | CryptexError::InvalidInquiry(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            CryptexError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            CryptexError::NetworkError(_) | CryptexError::UpstreamError(_) => StatusCode::BAD_GATEWAY,
+            CryptexError::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        // Client mistakes are routine; only log server-side failures as errors
+        let logged = the_foundation::redact_secrets(&format!("{:?}", err));
+        if status.is_server_error() {
+            tracing::error!("API error: {}", logged);
+        } else {
+            tracing::debug!("API error: {}", logged);
+        }
+
         Self {
-            status: StatusCode::INTERNAL_SERVER_ERROR,
-            message: err.to_string(),
+            status,
+            code: err.code(),
+            message: the_foundation::redact_secrets(&err.to_string()),
+            details: None,
         }
     }
 }
@@ -399,8 +1904,10 @@ impl From<CryptexError> for ApiError {
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let body = Json(ErrorResponse {
+            code: self.code,
             error: self.status.to_string(),
             message: self.message,
+            details: self.details,
         });
 
         (self.status, body).into_response()
@@ -411,6 +1918,10 @@ impl IntoResponse for ApiError {
 mod tests {
     use super::*;
 
+    fn anonymous() -> Actor {
+        Actor(the_archive::ANONYMOUS_ACTOR.to_string())
+    }
+
     #[test]
     fn test_health_response_creation() {
         let response = HealthResponse {
@@ -434,8 +1945,1895 @@ mod tests {
     fn test_report_query_deserialization() {
         let query = ReportQuery {
             format: Some("html".to_string()),
+            ..Default::default()
         };
 
         assert_eq!(query.format.unwrap(), "html");
     }
+
+    #[test]
+    fn test_resolve_export_path() {
+        let root = FsPath::new("/var/cryptex/exports");
+
+        let resolved = resolve_export_path(root, "reports/scan.html").unwrap();
+        assert_eq!(resolved, root.join("reports/scan.html"));
+
+        let inside = resolve_export_path(root, "/var/cryptex/exports/scan.json").unwrap();
+        assert_eq!(inside, root.join("scan.json"));
+
+        assert!(resolve_export_path(root, "../../etc/passwd").is_err());
+        assert!(resolve_export_path(root, "reports/../../escape.html").is_err());
+        assert!(resolve_export_path(root, "/etc/passwd").is_err());
+        assert!(resolve_export_path(root, "").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_report() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let export_root = temp_dir.path().join("exports");
+
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap()
+        .with_export_root(export_root.clone());
+        let state = interface.state.clone();
+
+        let scan_id = state.infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+
+        let Json(response) = export_report(
+            State(state.clone()),
+            Path(scan_id),
+            Json(ExportReportRequest {
+                format: Some("markdown".to_string()),
+                path: "reports/scan.md".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+
+        let written = std::fs::read_to_string(export_root.join("reports/scan.md")).unwrap();
+        assert_eq!(response.bytes_written, written.len());
+        assert!(response.bytes_written > 0);
+
+        // Traversal is rejected before the scan is touched
+        let scan_id = state.infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+        let err = export_report(
+            State(state.clone()),
+            Path(scan_id.clone()),
+            Json(ExportReportRequest {
+                format: None,
+                path: "../escape.json".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(!temp_dir.path().join("escape.json").exists());
+        assert!(state.infiltrator.get_scan_context(&scan_id).await.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_export_destination_must_not_leave_the_root_through_symlinks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("exports");
+        let outside = temp_dir.path().join("outside");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("linked")).unwrap();
+        std::os::unix::fs::symlink(outside.join("x.html"), root.join("file.html")).unwrap();
+
+        for requested in ["linked/scan.html", "linked/deeper/scan.html", "file.html"] {
+            let destination = resolve_export_path(&root, requested).unwrap();
+            assert!(prepare_export_destination(&root, &destination).is_err(), "{}", requested);
+        }
+        assert!(!outside.join("deeper").exists());
+
+        let destination = resolve_export_path(&root, "reports/scan.html").unwrap();
+        prepare_export_destination(&root, &destination).unwrap();
+        write_export_file(&destination, b"first").unwrap();
+        write_export_file(&destination, b"second").unwrap();
+        assert_eq!(std::fs::read_to_string(&destination).unwrap(), "second");
+        assert_eq!(std::fs::read_dir(root.join("reports")).unwrap().count(), 1);
+    }
+
+    #[cfg(feature = "object-storage")]
+    #[tokio::test]
+    async fn test_upload_report() {
+        type Uploads = Arc<std::sync::Mutex<Vec<(String, String, usize)>>>;
+
+        struct RecordingExporter(Uploads);
+
+        #[async_trait::async_trait]
+        impl exporter::ReportExporter for RecordingExporter {
+            async fn upload(
+                &self,
+                destination: &str,
+                content_type: &str,
+                body: Vec<u8>,
+            ) -> CryptexResult<String> {
+                self.0.lock().unwrap().push((
+                    destination.to_string(),
+                    content_type.to_string(),
+                    body.len(),
+                ));
+                Ok(format!("https://storage.example/{}", destination))
+            }
+        }
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let uploads = Uploads::default();
+        let interface =
+            TheInterface::the_awakening("127.0.0.1:0".to_string(), temp_dir.path().join("c.db"))
+                .await
+                .unwrap()
+                .with_exporter(RecordingExporter(uploads.clone()));
+        let state = interface.state.clone();
+        let scan_id = state.infiltrator.start_scan("192.168.1.0/24").await.unwrap();
+
+        let (status, Json(response)) = upload_report(
+            State(state.clone()),
+            anonymous(),
+            Path(scan_id.clone()),
+            Json(UploadReportRequest {
+                format: Some("html".to_string()),
+                destination: "acme/scan.html".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(response.url, "https://storage.example/acme/scan.html");
+        assert_eq!(
+            *uploads.lock().unwrap(),
+            vec![("acme/scan.html".to_string(), "text/html".to_string(), response.bytes_uploaded)]
+        );
+
+        let err = upload_report(
+            State(state),
+            anonymous(),
+            Path("missing".to_string()),
+            Json(UploadReportRequest {
+                format: None,
+                destination: "scan.json".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, SCAN_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_shared_archive_handle() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = Arc::new(TheArchive::the_awakening(temp_dir.path().join("cryptex.db")).unwrap());
+
+        let interface = TheInterface::with_archive("127.0.0.1:0".to_string(), archive.clone())
+            .await
+            .unwrap();
+        let query = ArchiveQuery::from_archive(archive.clone());
+
+        assert!(Arc::ptr_eq(&interface.archive(), &archive));
+
+        // A scan started through the handlers is visible to the query layer
+        let (_, Json(started)) = start_scan(
+            State(interface.state.clone()),
+            anonymous(),
+            HeaderMap::new(),
+            Json(StartScanRequest {
+                target: "10.0.0.0/24".to_string(),
+                targets: Vec::new(),
+                options: ScanOptionsOverride::default(),
+                scan_id: None,
+                replace: false,
+                tags: Vec::new(),
+            }),
+        )
+        .await
+        .unwrap();
+
+        let events = query.recent_activity(10).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            ActivityEvent::ScanStarted { scan_id, .. } if *scan_id == started.scan_id
+        ));
+
+        // Opening a second handle on the same file is refused
+        assert!(TheArchive::the_awakening(temp_dir.path().join("cryptex.db")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scan_tags() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let start = |target: &str, tags: &[&str]| {
+            let request: StartScanRequest = serde_json::from_value(serde_json::json!({
+                "target": target,
+                "tags": tags,
+            }))
+            .unwrap();
+            start_scan(State(state.clone()), anonymous(), HeaderMap::new(), Json(request))
+        };
+        let (_, Json(tagged)) = start("10.0.0.0/24", &["pci-scope", " prod", "pci-scope"])
+            .await
+            .unwrap();
+        let (_, Json(untagged)) = start("10.0.1.0/24", &[]).await.unwrap();
+        assert_eq!(tagged.tags, ["pci-scope", "prod"]);
+
+        let pci_scope = || ScanFilters::default().tags(["pci-scope"]);
+        let Json(scans) = list_scans(State(state.clone()), Query(pci_scope())).await.unwrap();
+        let ids: Vec<&str> = scans.iter().map(|scan| scan.scan_id.as_str()).collect();
+        assert_eq!(ids, [tagged.scan_id.as_str()]);
+
+        // Edited tags are stored and take part in filtering
+        let Json(edited) = set_scan_tags(
+            State(state.clone()),
+            anonymous(),
+            Path(untagged.scan_id.clone()),
+            Json(ScanTagsRequest {
+                tags: vec!["pci-scope".to_string(), "customer-acme".to_string()],
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(edited.tags, ["pci-scope", "customer-acme"]);
+        let Json(stored) = get_scan(State(state.clone()), Path(untagged.scan_id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(stored.tags, edited.tags);
+        let Json(scans) = list_scans(State(state.clone()), Query(pci_scope())).await.unwrap();
+        assert_eq!(scans.len(), 2);
+
+        let err = set_scan_tags(
+            State(state.clone()),
+            anonymous(),
+            Path(untagged.scan_id.clone()),
+            Json(ScanTagsRequest {
+                tags: vec!["a,b".to_string()],
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err = set_scan_tags(
+            State(state.clone()),
+            anonymous(),
+            Path("missing".to_string()),
+            Json(ScanTagsRequest { tags: Vec::new() }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, "SCAN_NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_start_multi_target_scan() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let request: StartScanRequest =
+            serde_json::from_str(r#"{"targets": ["10.0.0.0/24", "192.168.1.5"]}"#).unwrap();
+        let (status, Json(started)) =
+            start_scan(State(state.clone()), anonymous(), HeaderMap::new(), Json(request))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(started.target, "10.0.0.0/24, 192.168.1.5");
+        assert_eq!(started.targets.len(), 2);
+
+        let Json(metadata) = get_scan(State(state.clone()), Path(started.scan_id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(metadata.targets, started.targets);
+
+        state
+            .infiltrator
+            .on_vulnerability_detected(&started.scan_id, "CVE-2024-0001", "192.168.1.5", 22)
+            .await
+            .unwrap();
+        let Json(report) = end_scan(State(state.clone()), anonymous(), Path(started.scan_id))
+            .await
+            .unwrap();
+        assert_eq!(report.targets.len(), 2);
+        assert_eq!(report.total_hosts, 1);
+
+        // Both or neither is a bad request
+        let both: StartScanRequest =
+            serde_json::from_str(r#"{"target": "10.0.0.1", "targets": ["10.0.0.2"]}"#).unwrap();
+        let err = start_scan(State(state.clone()), anonymous(), HeaderMap::new(), Json(both))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        let neither: StartScanRequest = serde_json::from_str("{}").unwrap();
+        assert!(start_scan(State(state), anonymous(), HeaderMap::new(), Json(neither))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scan_progress_completes_at_end() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let request: StartScanRequest =
+            serde_json::from_str(r#"{"target": "10.0.0.0/30"}"#).unwrap();
+        let (_, Json(started)) =
+            start_scan(State(state.clone()), anonymous(), HeaderMap::new(), Json(request))
+                .await
+                .unwrap();
+        let scan_id = started.scan_id;
+        let progress = || get_scan_progress(State(state.clone()), Path(scan_id.clone()));
+
+        let Json(before) = progress().await.unwrap();
+        assert_eq!((before.hosts_total, before.percent), (4, 0));
+
+        state
+            .infiltrator
+            .on_vulnerability_detected(&scan_id, "CVE-2024-0001", "10.0.0.1", 22)
+            .await
+            .unwrap();
+        let Json(during) = progress().await.unwrap();
+        assert_eq!((during.hosts_completed, during.findings_so_far), (1, 1));
+        assert_eq!(during.percent, 25);
+
+        let Json(report) = end_scan(State(state.clone()), anonymous(), Path(scan_id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(report.total_vulnerabilities, 1);
+        let Json(after) = progress().await.unwrap();
+        assert_eq!(after, ScanProgress::finished(4, 1));
+
+        let err = get_scan_progress(State(state), Path("missing".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, SCAN_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_start_scan_with_supplied_id() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let request = |replace: bool| -> Json<StartScanRequest> {
+            let body = serde_json::json!({
+                "target": "10.0.0.0/24",
+                "scan_id": "ci-nightly",
+                "replace": replace,
+            });
+            Json(serde_json::from_value(body).unwrap())
+        };
+
+        let (_, Json(started)) =
+            start_scan(State(state.clone()), anonymous(), HeaderMap::new(), request(false))
+                .await
+                .unwrap();
+        assert_eq!(started.scan_id, "ci-nightly");
+        state
+            .infiltrator
+            .on_vulnerability_detected("ci-nightly", "CVE-2024-0001", "10.0.0.5", 22)
+            .await
+            .unwrap();
+
+        // The id is taken while the scan is active, replace or not
+        for replace in [false, true] {
+            let err =
+                start_scan(State(state.clone()), anonymous(), HeaderMap::new(), request(replace))
+                    .await
+                    .unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        }
+
+        // A re-run after the scan ended must ask to replace the archived run
+        let Json(report) =
+            end_scan(State(state.clone()), anonymous(), Path("ci-nightly".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(report.total_vulnerabilities, 1);
+        assert_eq!(state.archive.get_scan_results("ci-nightly").unwrap().len(), 1);
+        let err = start_scan(State(state.clone()), anonymous(), HeaderMap::new(), request(false))
+            .await
+            .unwrap_err();
+        assert_eq!((err.status, err.code), (StatusCode::CONFLICT, SCAN_ID_IN_USE));
+        assert_eq!(state.archive.get_scan_results("ci-nightly").unwrap().len(), 1);
+        assert!(state.infiltrator.scan_progress("ci-nightly").await.is_err());
+
+        let (status, _) =
+            start_scan(State(state.clone()), anonymous(), HeaderMap::new(), request(true))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(state.archive.get_scan_results("ci-nightly").unwrap().is_empty());
+        let metadata = state.archive.get_scan_metadata("ci-nightly").unwrap().unwrap();
+        assert_eq!(metadata.status, "running");
+        assert_eq!(state.archive.get_stats().unwrap().total_scans, 1);
+        let audited = state.archive.audit_log(None, 100).unwrap();
+        assert!(audited.iter().any(|e| e.action == "scan.replace" && e.target == "ci-nightly"));
+    }
+
+    #[tokio::test]
+    async fn test_start_scan_idempotency_key() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_HEADER, "retry-me".parse().unwrap());
+        let request = |target: &str| {
+            Json(StartScanRequest {
+                target: target.to_string(),
+                targets: Vec::new(),
+                options: ScanOptionsOverride::default(),
+                scan_id: None,
+                replace: false,
+                tags: Vec::new(),
+            })
+        };
+
+        let (status, Json(first)) =
+            start_scan(State(state.clone()), anonymous(), headers.clone(), request("10.0.0.0/24"))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, Json(second)) =
+            start_scan(State(state.clone()), anonymous(), headers.clone(), request("10.0.0.0/24"))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(second.scan_id, first.scan_id);
+        assert_eq!(state.infiltrator.get_statistics().await.total_scans, 1);
+
+        let err = start_scan(State(state.clone()), anonymous(), headers, request("192.168.0.0/16"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        // Without a key every request starts a scan
+        let (_, Json(third)) =
+            start_scan(State(state.clone()), anonymous(), HeaderMap::new(), request("10.0.0.0/24"))
+                .await
+                .unwrap();
+        assert_ne!(third.scan_id, first.scan_id);
+    }
+
+    #[tokio::test]
+    async fn test_start_scan_options_from_body() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let request: StartScanRequest = serde_json::from_value(serde_json::json!({
+            "target": "10.0.0.0/24",
+            "options": { "ai_enhancement": false }
+        }))
+        .unwrap();
+        assert_eq!(
+            request.options,
+            ScanOptionsOverride {
+                ai_enhancement: Some(false),
+                ..ScanOptionsOverride::default()
+            }
+        );
+
+        let (_, Json(started)) =
+            start_scan(State(state.clone()), anonymous(), HeaderMap::new(), Json(request))
+                .await
+                .unwrap();
+        state
+            .infiltrator
+            .on_vulnerability_detected(&started.scan_id, "CVE-2021-44228", "10.0.0.5", 443)
+            .await
+            .unwrap();
+
+        let Json(report) = end_scan(State(state), anonymous(), Path(started.scan_id))
+            .await
+            .unwrap();
+        assert!(report.scan_results[0].remediation_guidance.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_consolidated_report_from_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let mut scan_ids = Vec::new();
+        let scans = [
+            ("10.0.0.0/24", ["10.0.0.5", "10.0.0.6"]),
+            ("10.0.0.0/23", ["10.0.0.5", "10.0.1.7"]),
+        ];
+        for (target, hosts) in scans {
+            let (_, Json(started)) = start_scan(
+                State(state.clone()),
+                anonymous(),
+                HeaderMap::new(),
+                Json(StartScanRequest {
+                    target: target.to_string(),
+                    targets: Vec::new(),
+                    options: ScanOptionsOverride::default(),
+                    scan_id: None,
+                    replace: false,
+                    tags: Vec::new(),
+                }),
+            )
+            .await
+            .unwrap();
+            for host in hosts {
+                state
+                    .infiltrator
+                    .on_vulnerability_detected(&started.scan_id, "CVE-2021-44228", host, 443)
+                    .await
+                    .unwrap();
+            }
+            let Json(ended) =
+                end_scan(State(state.clone()), anonymous(), Path(started.scan_id.clone()))
+                    .await
+                    .unwrap();
+            assert_eq!(ended.total_vulnerabilities, 2);
+            scan_ids.push(started.scan_id);
+        }
+
+        let response = generate_consolidated_report(
+            State(state.clone()),
+            Json(ConsolidatedReportRequest {
+                scan_ids: scan_ids.clone(),
+                format: Some("json".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        // 10.0.0.5 was found by both scans and is counted once
+        assert_eq!(report["total_vulnerabilities"], 3);
+        assert_eq!(report["total_hosts"], 3);
+        assert_eq!(report["summary"]["duplicates_removed"], 1);
+
+        let err = generate_consolidated_report(
+            State(state),
+            Json(ConsolidatedReportRequest {
+                scan_ids: vec![scan_ids[0].clone(), "missing".to_string()],
+                format: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_scan_results_include_assessment() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let assessed = the_infiltrator::ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "10.0.0.5".to_string(),
+            443,
+            "unknown".to_string(),
+            "Log4Shell".to_string(),
+        );
+        let unassessed = the_infiltrator::ScanResult::new(
+            "CVE-2024-0001".to_string(),
+            "10.0.0.6".to_string(),
+            80,
+            "unknown".to_string(),
+            "Unassessed".to_string(),
+        );
+        state.archive.store_scan_result("scan_1", &assessed).unwrap();
+        state.archive.store_scan_result("scan_1", &unassessed).unwrap();
+
+        let score = state.assessor.assess_vulnerability("CVE-2021-44228").await.unwrap();
+        state.archive.store_vulnerability(&score).unwrap();
+
+        let response = get_scan_results(
+            State(state.clone()),
+            Path("scan_1".to_string()),
+            Query(ResultsQuery {
+                include_assessment: Some(true),
+            }),
+        )
+        .await
+        .unwrap();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let results: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let results = results.as_array().unwrap();
+        assert_eq!(results.len(), 2);
+
+        let by_cve = |cve: &str| results.iter().find(|r| r["cve_id"] == cve).unwrap().clone();
+
+        let log4shell = by_cve("CVE-2021-44228");
+        assert_eq!(log4shell["host"], "10.0.0.5");
+        assert_eq!(log4shell["assessment"]["cve_id"], "CVE-2021-44228");
+        assert!(log4shell["assessment"]["kev"]["is_kev"].as_bool().unwrap());
+
+        let other = by_cve("CVE-2024-0001");
+        assert!(other.get("assessment").unwrap().is_null());
+
+        // Without the flag the plain payload is returned
+        let response = get_scan_results(
+            State(state),
+            Path("scan_1".to_string()),
+            Query(ResultsQuery {
+                include_assessment: None,
+            }),
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let results: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(results[0].get("assessment").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_routes_unavailable_until_ready() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let ready = interface.state.ready.clone();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, create_router(interface.state, interface.limits))
+                .await
+                .unwrap();
+        });
+
+        let client = reqwest::Client::new();
+        let status = |path: &'static str| {
+            let request = client.get(format!("{}{}", base, path));
+            async move { request.send().await.unwrap().status().as_u16() }
+        };
+
+        assert_eq!(status("/api/v1/archive/stats").await, 503);
+        assert_eq!(status("/health/ready").await, 503);
+        assert_eq!(status("/health").await, 200);
+
+        let waiter = tokio::spawn({
+            let ready = ready.clone();
+            async move { ready.wait_ready().await }
+        });
+        ready.mark_ready();
+        tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(status("/api/v1/archive/stats").await, 200);
+        assert_eq!(status("/health/ready").await, 200);
+    }
+
+    #[tokio::test]
+    async fn test_self_check_retries_then_gives_up() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        let delay = std::time::Duration::from_millis(1);
+
+        // Two failures, then the archive answers
+        let calls = Arc::new(AtomicU32::new(0));
+        let ready = Arc::new(ReadyState::default());
+        let check = {
+            let calls = calls.clone();
+            move || match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(CryptexError::ArchiveError("archive locked".to_string())),
+                _ => Ok(()),
+            }
+        };
+        readiness::run_self_check(ready.clone(), 5, delay, check).await.unwrap();
+        assert!(ready.is_ready());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // A check that never passes ends with an error instead of waiting forever
+        let calls = Arc::new(AtomicU32::new(0));
+        let ready = Arc::new(ReadyState::default());
+        let check = {
+            let calls = calls.clone();
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(CryptexError::ArchiveError("archive locked".to_string()))
+            }
+        };
+        let err = readiness::run_self_check(ready.clone(), 3, delay, check).await.unwrap_err();
+        assert!(err.to_string().contains("failed 3 times"), "{}", err);
+        assert!(!ready.is_ready());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_is_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap()
+        .with_request_limits(RequestLimits {
+            max_body_bytes: 1024,
+            ..Default::default()
+        });
+        interface.state.ready.mark_ready();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, create_router(interface.state, interface.limits))
+                .await
+                .unwrap();
+        });
+
+        let client = reqwest::Client::new();
+        let cve_ids: Vec<String> = (0..200).map(|i| format!("CVE-2024-{:05}", i)).collect();
+        let oversized = serde_json::json!({ "cve_ids": cve_ids }).to_string();
+        assert!(oversized.len() > 1024);
+
+        let response = client
+            .post(format!("{}/api/v1/jobs/assess", base))
+            .header("content-type", "application/json")
+            .body(oversized)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 413);
+
+        let response = client
+            .post(format!("{}/api/v1/jobs/assess", base))
+            .json(&serde_json::json!({ "cve_ids": ["CVE-2021-44228"] }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 202);
+
+        // Report exports are exempt: the request reaches the handler
+        let padding = "x".repeat(2048);
+        let response = client
+            .post(format!("{}/api/v1/scans/missing/report/export", base))
+            .json(&serde_json::json!({ "path": "report.html", "format": padding }))
+            .send()
+            .await
+            .unwrap();
+        assert_ne!(response.status(), 413);
+
+        // Imports have a limit of their own: this one is read and rejected as
+        // a report without findings
+        let report = format!("<report><results/>{}</report>", " ".repeat(2048));
+        let response = client
+            .post(format!("{}/api/v1/import/openvas", base))
+            .body(report)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_routes_have_no_timeout() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap()
+        .with_request_limits(RequestLimits {
+            timeout: std::time::Duration::from_millis(100),
+            ..Default::default()
+        });
+        interface.state.ready.mark_ready();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, create_router(interface.state, interface.limits))
+                .await
+                .unwrap();
+        });
+
+        // The body arrives well after the timeout, so the handler is still
+        // waiting for it when a timed route gives up
+        let slow_post = |path: &'static str, body: &'static str| async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            let head = format!(
+                "POST {} HTTP/1.1\r\nhost: cryptex\r\ncontent-type: application/json\r\n\
+                 content-length: {}\r\nconnection: close\r\n\r\n",
+                path,
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            let _ = stream.write_all(body.as_bytes()).await;
+            let mut response = String::new();
+            let _ = stream.read_to_string(&mut response).await;
+            response
+        };
+
+        let response = slow_post("/api/v1/jobs/assess", r#"{"cve_ids":["CVE-2021-44228"]}"#).await;
+        assert!(response.starts_with("HTTP/1.1 408"), "{}", response);
+
+        let export = "/api/v1/scans/missing/report/export";
+        let response = slow_post(export, r#"{"path":"r.html"}"#).await;
+        assert!(response.starts_with("HTTP/1.1 404"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_route_headers_and_versions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap()
+        .with_deprecated_route(
+            DeprecatedRoute::parse("/api/v1/vulnerabilities/:cve_id@2027-01-31").unwrap(),
+        );
+        interface.state.ready.mark_ready();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, create_router(interface.state, interface.limits))
+                .await
+                .unwrap();
+        });
+        let client = reqwest::Client::new();
+
+        // The deprecation follows the route pattern, whatever the path parameter
+        let response = client
+            .get(format!("{}/api/v1/vulnerabilities/CVE-2021-44228?cached_only=true", base))
+            .send()
+            .await
+            .unwrap();
+        let headers = response.headers();
+        assert_eq!(headers[versioning::API_VERSION_HEADER], "v1");
+        assert_eq!(headers[versioning::DEPRECATION_HEADER], "true");
+        assert_eq!(headers[versioning::SUNSET_HEADER], "Sun, 31 Jan 2027 00:00:00 GMT");
+
+        let response = client.get(format!("{}/api/versions", base)).send().await.unwrap();
+        assert_eq!(response.headers()[versioning::API_VERSION_HEADER], "v1");
+        assert!(!response.headers().contains_key(versioning::DEPRECATION_HEADER));
+        let versions: versioning::VersionsResponse = response.json().await.unwrap();
+        assert_eq!(versions.current, "v1");
+        assert_eq!(versions.versions.len(), 1);
+        assert_eq!(versions.versions[0].status, versioning::VersionStatus::Current);
+        assert_eq!(versions.deprecated_routes[0].path, "/api/v1/vulnerabilities/:cve_id");
+    }
+
+    #[tokio::test]
+    async fn test_scan_bundle_endpoint() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        state
+            .archive
+            .store_scan_metadata(&ScanMetadata::new("scan_1".to_string(), "10.0.0.5".to_string()))
+            .unwrap();
+        let result = the_infiltrator::ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "10.0.0.5".to_string(),
+            443,
+            "https".to_string(),
+            "Log4Shell".to_string(),
+        );
+        state.archive.store_scan_result("scan_1", &result).unwrap();
+        let score = state.assessor.assess_vulnerability("CVE-2021-44228").await.unwrap();
+        state.archive.store_vulnerability(&score).unwrap();
+
+        let response = get_scan_bundle(State(state.clone()), Path("scan_1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_DISPOSITION],
+            "attachment; filename=\"scan_1.bundle.json\""
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let bundle: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(bundle["version"], the_archive::BUNDLE_VERSION);
+        assert_eq!(bundle["metadata"]["scan_id"], "scan_1");
+        assert_eq!(bundle["results"].as_array().unwrap().len(), 1);
+        assert_eq!(bundle["assessments"][0]["cve_id"], "CVE-2021-44228");
+
+        let err = get_scan_bundle(State(state), Path("missing".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_assess_vulnerability_cached_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+        let cve_id = || Path("CVE-2021-44228".to_string());
+        let cached_only = || {
+            Query(AssessQuery {
+                cached_only: Some(true),
+                refresh: None,
+            })
+        };
+
+        let err = assess_vulnerability(State(state.clone()), cve_id(), cached_only())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        assert_eq!(state.assessor.source_fetch_count(), 0);
+
+        let score = state.assessor.assess_vulnerability("CVE-2021-44228").await.unwrap();
+        state.archive.store_vulnerability(&score).unwrap();
+
+        let (_, Json(found)) = assess_vulnerability(State(state.clone()), cve_id(), cached_only())
+            .await
+            .unwrap();
+        assert_eq!(found.cve_id, "CVE-2021-44228");
+        assert_eq!(state.assessor.source_fetch_count(), 1);
+
+        // Pasted IDs find the same assessment; non-CVE IDs are rejected
+        let messy = Path(" cve 2021 44228 ".to_string());
+        let (_, Json(found)) = assess_vulnerability(State(state.clone()), messy, cached_only())
+            .await
+            .unwrap();
+        assert_eq!(found.cve_id, "CVE-2021-44228");
+        let invalid = Path("log4shell".to_string());
+        let err = assess_vulnerability(State(state.clone()), invalid, cached_only())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        // refresh bypasses the archive and fetches again
+        let refresh = Query(AssessQuery {
+            cached_only: None,
+            refresh: Some(true),
+        });
+        let (_, Json(refreshed)) = assess_vulnerability(State(state.clone()), cve_id(), refresh)
+            .await
+            .unwrap();
+        assert_eq!(refreshed.cve_id, "CVE-2021-44228");
+        assert_eq!(state.assessor.source_fetch_count(), 2);
+
+        let both = Query(AssessQuery {
+            cached_only: Some(true),
+            refresh: Some(true),
+        });
+        let err = assess_vulnerability(State(state), cve_id(), both).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_assessment_survives_archive_failure() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+
+        // A read-only archive with no tables: every read and write fails
+        let broken_path = temp_dir.path().join("broken.db");
+        drop(redb::Database::create(&broken_path).unwrap());
+        let broken = TheArchive::the_awakening_readonly(broken_path).unwrap();
+        assert!(broken.get_vulnerability("CVE-2021-44228").is_err());
+        let mut state = interface.state.clone();
+        state.archive = Arc::new(broken);
+        state.ready.mark_ready();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, create_router(state, interface.limits))
+                .await
+                .unwrap();
+        });
+
+        let response = reqwest::get(format!("{}/api/v1/vulnerabilities/CVE-2021-44228", base))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_HEADER], "bypass");
+        let score: VulnerabilityScore = response.json().await.unwrap();
+        assert_eq!(score.cve_id, "CVE-2021-44228");
+        assert!(score.is_kev());
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_clear_vulnerabilities() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        for cve in ["CVE-2021-44228", "CVE-2023-4966"] {
+            let score = state.assessor.assess_vulnerability(cve).await.unwrap();
+            state.archive.store_vulnerability(&score).unwrap();
+        }
+
+        let status = delete_vulnerability(
+            State(state.clone()),
+            anonymous(),
+            Path("CVE-2021-44228".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(state.archive.get_vulnerability("CVE-2021-44228").unwrap().is_none());
+        assert!(state.assessor.assess_cached_only("CVE-2021-44228").await.unwrap().is_none());
+
+        let err = delete_vulnerability(
+            State(state.clone()),
+            anonymous(),
+            Path("CVE-2021-44228".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        // Clearing without the confirmation header removes nothing
+        let err = clear_vulnerabilities(State(state.clone()), anonymous(), HeaderMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(state.archive.get_vulnerability("CVE-2023-4966").unwrap().is_some());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONFIRM_CLEAR_HEADER, "vulnerabilities".parse().unwrap());
+        let Json(response) = clear_vulnerabilities(State(state.clone()), anonymous(), headers)
+            .await
+            .unwrap();
+        assert_eq!(response.removed, 1);
+        assert!(state.archive.list_vulnerabilities().unwrap().is_empty());
+        assert!(state.assessor.assess_cached_only("CVE-2023-4966").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_archive_events_stream() {
+        use futures::StreamExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let response = stream_archive_events(State(state.clone())).await.into_response();
+        let mut body = response.into_body().into_data_stream();
+
+        state
+            .archive
+            .store_scan_metadata(&ScanMetadata::new("scan-001".to_string(), "10.0.0.0/24".to_string()))
+            .unwrap();
+
+        let frame = body.next().await.unwrap().unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        assert_eq!(frame, "data: {\"event\":\"scan_stored\",\"scan_id\":\"scan-001\"}\n\n");
+    }
+
+    #[tokio::test]
+    async fn test_scan_lifecycle_is_audited() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let request: StartScanRequest =
+            serde_json::from_value(serde_json::json!({ "target": "10.0.0.0/24" })).unwrap();
+        let (_, Json(started)) = start_scan(
+            State(state.clone()),
+            Actor("alice".to_string()),
+            HeaderMap::new(),
+            Json(request),
+        )
+        .await
+        .unwrap();
+        let bob = || Actor("bob".to_string());
+        let scan_id = || Path(started.scan_id.clone());
+        let Json(paused) = pause_scan(State(state.clone()), bob(), scan_id()).await.unwrap();
+        assert_eq!(paused.status, "paused");
+        let Json(resumed) = resume_scan(State(state.clone()), bob(), scan_id()).await.unwrap();
+        assert_eq!(resumed.status, "running");
+        let status = delete_scan(State(state.clone()), anonymous(), Path(started.scan_id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(state.archive.get_scan_metadata(&started.scan_id).unwrap().is_none());
+
+        // Deleting it again fails and is not audited
+        let err = delete_scan(State(state.clone()), anonymous(), Path(started.scan_id.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let Json(entries) = get_audit_log(
+            State(state.clone()),
+            Query(AuditQuery {
+                since: None,
+                limit: None,
+            }),
+        )
+        .await
+        .unwrap();
+        let entries: Vec<_> = entries
+            .iter()
+            .map(|e| (e.actor.as_str(), e.action.as_str(), e.target.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("alice", "scan.start", started.scan_id.as_str()),
+                ("bob", "scan.pause", started.scan_id.as_str()),
+                ("bob", "scan.resume", started.scan_id.as_str()),
+                ("anonymous", "scan.delete", started.scan_id.as_str()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metrics_history() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = Arc::new(TheArchive::the_awakening(temp_dir.path().join("m.db")).unwrap());
+        let interface = TheInterface::with_archive("127.0.0.1:0".to_string(), archive.clone())
+            .await
+            .unwrap();
+        let history = |state: AppState, since: Option<&str>| {
+            let query = MetricsHistoryQuery {
+                since: since.map(str::to_string),
+            };
+            async move { get_metrics_history(State(state), Query(query)).await }
+        };
+
+        // Without a [metrics_history] section there is nothing to serve
+        let err = history(interface.state.clone(), None).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        // The sampler takes its first snapshot right away
+        let mut charter = TheCharter::builtin();
+        charter.metrics_history = Some(the_coordinator::MetricsHistoryConfig {
+            interval_seconds: 3600,
+            capacity: 10,
+        });
+        let interface = TheInterface::with_charter("127.0.0.1:0".to_string(), archive, &charter)
+            .await
+            .unwrap();
+        let state = interface.state.clone();
+        let mut samples = Vec::new();
+        for _ in 0..100 {
+            samples = history(state.clone(), None).await.unwrap().0;
+            if !samples.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].metrics.inquiries_total, 0);
+
+        // `since` excludes the sample taken at that instant
+        let taken_at = samples[0].taken_at.to_rfc3339();
+        assert!(history(state.clone(), Some(&taken_at)).await.unwrap().0.is_empty());
+
+        let err = history(state, Some("yesterday")).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_score_cvss_vector() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let Json(scored) = score_cvss_vector(
+            State(state.clone()),
+            Json(CvssScoreRequest {
+                cve_id: Some("CVE-2021-44228".to_string()),
+                vector: "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(scored.version, "3.1");
+        assert_eq!(scored.base_score, 10.0);
+        assert_eq!(scored.severity, CvssSeverity::Critical);
+        assert_eq!(scored.metrics.scope, "C");
+        assert_eq!(scored.cve_id.as_deref(), Some("CVE-2021-44228"));
+
+        let err = score_cvss_vector(
+            State(state),
+            Json(CvssScoreRequest {
+                cve_id: None,
+                vector: "CVSS:3.1/AV:N/AC:L".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert_eq!(err.code, INVALID_CVSS_VECTOR);
+        assert!(err.message.contains("Missing required CVSS metrics"));
+    }
+
+    #[tokio::test]
+    async fn test_assessment_job_endpoints() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let err = start_assessment_job(
+            State(state.clone()),
+            Json(AssessmentJobRequest { cve_ids: vec![] }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let (status, Json(created)) = start_assessment_job(
+            State(state.clone()),
+            Json(AssessmentJobRequest {
+                cve_ids: vec!["CVE-2021-44228".to_string()],
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+
+        let poll = async {
+            loop {
+                let Json(job) = get_job(State(state.clone()), Path(created.job_id.clone()))
+                    .await
+                    .unwrap();
+                if job.status.is_finished() {
+                    break job;
+                }
+                tokio::task::yield_now().await;
+            }
+        };
+        let job = tokio::time::timeout(std::time::Duration::from_secs(30), poll)
+            .await
+            .expect("job did not finish within 30s");
+        assert_eq!(job.status, jobs::JobStatus::Completed);
+        assert_eq!(job.results.unwrap()[0].cve_id, "CVE-2021-44228");
+
+        let err = get_job(State(state), Path("missing".to_string())).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_mapping() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+
+        // The infiltrator reports an unknown scan as NotFound
+        let err = end_scan(State(interface.state.clone()), anonymous(), Path("missing".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        assert!(err.message.contains("missing"));
+
+        // An unknown CVE is a 404 whether it is looked up or evicted
+        let unknown = || Path("CVE-2099-0001".to_string());
+        let cached_only = Query(AssessQuery {
+            cached_only: Some(true),
+            refresh: None,
+        });
+        let err = assess_vulnerability(State(interface.state.clone()), unknown(), cached_only)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        assert!(err.message.contains("CVE-2099-0001"));
+        let err = delete_vulnerability(State(interface.state.clone()), anonymous(), unknown())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let status = |err: CryptexError| ApiError::from(err).status;
+        assert_eq!(status(CryptexError::validation("bad")), StatusCode::BAD_REQUEST);
+        assert_eq!(status(CryptexError::rate_limited("slow down")), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(status(CryptexError::upstream("503")), StatusCode::BAD_GATEWAY);
+        assert_eq!(status(CryptexError::archive("disk")), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let err = ApiError::from(CryptexError::upstream("rejected key sk-abcdef0123456789"));
+        assert_eq!(err.message, "Upstream error: rejected key [REDACTED]");
+    }
+
+    #[tokio::test]
+    async fn test_error_codes_in_response_body() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let body = |err: ApiError| async move {
+            let response = err.into_response();
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        };
+
+        let err = end_scan(State(interface.state.clone()), anonymous(), Path("missing".to_string()))
+            .await
+            .unwrap_err();
+        let json = body(err).await;
+        assert_eq!(json["code"], "SCAN_NOT_FOUND");
+        assert_eq!(json["error"], "404 Not Found");
+        assert_eq!(json["details"]["scan_id"], "missing");
+
+        let err = get_job(State(interface.state.clone()), Path("nope".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(body(err).await["code"], "JOB_NOT_FOUND");
+
+        // Without a specific code, the CryptexError kind decides
+        let json = body(CryptexError::validation("Target must not be empty").into()).await;
+        assert_eq!(json["code"], "VALIDATION_FAILED");
+        assert_eq!(json["message"], "Validation failed: Target must not be empty");
+        assert!(json.get("details").is_none());
+
+        let json = body(CryptexError::rate_limited("OpenAI returned 429").into()).await;
+        assert_eq!(json["code"], "RATE_LIMITED");
+    }
+
+    #[tokio::test]
+    async fn test_list_vulnerabilities_since() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let before_insert = chrono::Utc::now() - chrono::Duration::seconds(1);
+        let score = state.assessor.assess_vulnerability("CVE-2021-44228").await.unwrap();
+        state.archive.store_vulnerability(&score).unwrap();
+
+        let list = |since: Option<String>| {
+            list_vulnerabilities(State(state.clone()), Query(SinceQuery { since }))
+        };
+
+        let response = list(Some(before_insert.to_rfc3339())).await.unwrap();
+        let sync = response.headers()[SYNC_TIMESTAMP_HEADER].to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let items: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(items.as_array().unwrap().len(), 1);
+        assert_eq!(items[0]["cve_id"], "CVE-2021-44228");
+
+        // The header is the newest cache time in the response itself
+        let cached_at = items[0]["cached_at"].as_str().unwrap();
+        let cached_at = chrono::DateTime::parse_from_rfc3339(cached_at).unwrap();
+        assert_eq!(chrono::DateTime::parse_from_rfc3339(&sync).unwrap(), cached_at);
+
+        // Syncing again from the returned timestamp yields nothing new
+        let response = list(Some(sync.clone())).await.unwrap();
+        assert_eq!(response.headers()[SYNC_TIMESTAMP_HEADER].to_str().unwrap(), sync);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let items: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(items.as_array().unwrap().is_empty());
+
+        // A later write is always cached after the previous header
+        let score = VulnerabilityScore::new("CVE-2023-4966".to_string());
+        state.archive.store_vulnerability(&score).unwrap();
+        let response = list(Some(sync)).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let items: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(items.as_array().unwrap().len(), 1);
+        assert_eq!(items[0]["cve_id"], "CVE-2023-4966");
+
+        let err = list(Some("yesterday".to_string())).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_vulnerabilities() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        for cve_id in ["CVE-2021-44228", "CVE-2023-4966"] {
+            let score = VulnerabilityScore::new(cve_id.to_string());
+            state.archive.store_vulnerability(&score).unwrap();
+        }
+
+        let lookup = |cve_ids: &[&str]| {
+            let cve_ids = cve_ids.iter().map(|id| id.to_string()).collect();
+            let request = VulnerabilityLookupRequest { cve_ids };
+            lookup_vulnerabilities(State(state.clone()), Json(request))
+        };
+
+        let Json(found) =
+            lookup(&["cve-2021-44228", "CVE-2099-0001", "CVE-2023-4966"]).await.unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found["CVE-2021-44228"].cve_id, "CVE-2021-44228");
+        assert!(found.contains_key("CVE-2023-4966"));
+
+        let err = lookup(&["CVE-2021-44228", "not-a-cve"]).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_kev_coverage_endpoint() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let request = serde_json::from_str(r#"{"target": "10.0.0.0/24"}"#).unwrap();
+        let (_, Json(started)) =
+            start_scan(State(state.clone()), anonymous(), HeaderMap::new(), Json(request))
+                .await
+                .unwrap();
+        for (cve_id, host) in [("CVE-2021-44228", "10.0.0.5"), ("CVE-2024-0001", "10.0.0.6")] {
+            state
+                .infiltrator
+                .on_vulnerability_detected(&started.scan_id, cve_id, host, 443)
+                .await
+                .unwrap();
+        }
+        let Json(report) = end_scan(State(state.clone()), anonymous(), Path(started.scan_id))
+            .await
+            .unwrap();
+        assert_eq!(report.total_vulnerabilities, 2);
+
+        // Log4Shell's CISA due date (2021-12-24) is long past
+        let Json(coverage) = get_kev_coverage(State(state)).await.unwrap();
+        assert_eq!(coverage.total_findings, 2);
+        assert_eq!(coverage.kev_findings, 1);
+        assert_eq!(coverage.kev_hosts_affected, 1);
+        assert_eq!(coverage.overdue_kev, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reassess_stale_threshold() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let score = state.assessor.assess_vulnerability("CVE-2021-44228").await.unwrap();
+        state.archive.store_vulnerability(&score).unwrap();
+
+        let reassess = |older_than_hours: Option<u64>| {
+            let query = Query(ReassessQuery { older_than_hours });
+            reassess_stale(State(state.clone()), anonymous(), query)
+        };
+
+        // Just stored, so nothing is a day old yet
+        let Json(response) = reassess(None).await.unwrap();
+        assert_eq!((response.refreshed, response.older_than_hours), (0, 24));
+
+        let Json(response) = reassess(Some(0)).await.unwrap();
+        assert_eq!(response.refreshed, 1);
+
+        let audited = state.archive.audit_log(None, 10).unwrap();
+        assert_eq!(audited.len(), 2);
+        assert!(audited.iter().all(|entry| entry.action == "vulnerabilities.reassess"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_report_theme_overrides() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let scan_id = state.infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+
+        let err = generate_report(
+            State(state.clone()),
+            Path(scan_id.clone()),
+            Query(ReportQuery {
+                format: Some("html".to_string()),
+                accent_color: Some("red".to_string()),
+                ..Default::default()
+            }),
+            HeaderMap::new(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let response = generate_report(
+            State(state),
+            Path(scan_id),
+            Query(ReportQuery {
+                format: Some("html".to_string()),
+                company_name: Some("Acme Corp".to_string()),
+                accent_color: Some("#1abc9c".to_string()),
+                ..Default::default()
+            }),
+            HeaderMap::new(),
+        )
+        .await
+        .unwrap();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(html.contains("Acme Corp"));
+        assert!(html.contains("#1abc9c"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_report_sections() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let scan_id = state.infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+        let report = |state: AppState, sections: &str| {
+            generate_report(
+                State(state),
+                Path(scan_id.clone()),
+                Query(ReportQuery {
+                    format: Some("html".to_string()),
+                    sections: Some(sections.to_string()),
+                    ..Default::default()
+                }),
+                HeaderMap::new(),
+            )
+        };
+
+        // An unknown section is refused before the scan is ended
+        let err = report(state.clone(), "overview,appendix").await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        // So is `?sections=` naming none at all
+        for empty in ["", " , "] {
+            let err = report(state.clone(), empty).await.unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        }
+
+        let err = generate_report(
+            State(state.clone()),
+            Path(scan_id.clone()),
+            Query(ReportQuery {
+                min_confidence: Some("certain".to_string()),
+                ..Default::default()
+            }),
+            HeaderMap::new(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let response = report(state, "overview").await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(html.contains("Scan Overview"));
+        assert!(!html.contains("Vulnerability Statistics"));
+    }
+
+    #[tokio::test]
+    async fn test_report_format_follows_accept_header() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let report = |accept: &'static str, format: Option<&str>| {
+            let state = state.clone();
+            let format = format.map(str::to_string);
+            async move {
+                let scan_id = state.infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+                let mut headers = HeaderMap::new();
+                headers.insert(axum::http::header::ACCEPT, accept.parse().unwrap());
+                let response = generate_report(
+                    State(state),
+                    Path(scan_id),
+                    Query(ReportQuery {
+                        format,
+                        ..Default::default()
+                    }),
+                    headers,
+                )
+                .await
+                .unwrap();
+                let content_type = response.headers()[axum::http::header::CONTENT_TYPE]
+                    .to_str()
+                    .unwrap()
+                    .to_string();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (content_type, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        let (content_type, body) = report("text/html", None).await;
+        assert_eq!(content_type, "text/html");
+        assert!(body.contains("<html"));
+
+        let (content_type, body) = report("*/*", None).await;
+        assert_eq!(content_type, "application/json");
+        assert!(serde_json::from_str::<ScanReport>(&body).is_ok());
+
+        let (content_type, body) = report("application/sarif+json", None).await;
+        assert_eq!(content_type, "application/sarif+json");
+        let sarif: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+
+        // An explicit format wins over the header
+        let (content_type, _) = report("text/html", Some("markdown")).await;
+        assert_eq!(content_type, "text/markdown");
+    }
+
+    #[tokio::test]
+    async fn test_signed_report_carries_verifiable_signature() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let key = SigningKey::from_bytes(&[42u8; 32]);
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let scan_id = state.infiltrator.start_scan("10.0.0.0/24").await.unwrap();
+        let signed = |state: AppState, scan_id: String| {
+            generate_report(
+                State(state),
+                Path(scan_id),
+                Query(ReportQuery {
+                    format: Some("markdown".to_string()),
+                    sign: Some(true),
+                    ..Default::default()
+                }),
+                HeaderMap::new(),
+            )
+        };
+
+        // Without a configured key the request is refused
+        let err = signed(state.clone(), scan_id.clone()).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let state = interface.with_signing_key(key.clone()).state;
+        let response = signed(state, scan_id).await.unwrap();
+        let signature = response.headers()[REPORT_SIGNATURE_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let content = String::from_utf8(body.to_vec()).unwrap();
+        assert!(ThePropagandist::verify_report(&content, &signature, &key.verifying_key()));
+    }
+
+    #[tokio::test]
+    async fn test_import_openvas_report_is_archived() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap();
+        let state = interface.state.clone();
+
+        let xml = r#"<report id="r"><report id="r">
+            <scan_start>2023-05-02T09:14:27Z</scan_start>
+            <scan_end>2023-05-02T10:02:51Z</scan_end>
+            <results>
+              <result id="1"><host>10.0.0.5</host><port>443/tcp</port>
+                <nvt oid="1"><refs><ref type="cve" id="CVE-2021-44228"/></refs></nvt></result>
+              <result id="2"><host>10.0.0.6</host><port>80/tcp</port>
+                <nvt oid="2"><refs><ref type="cve" id="CVE-2023-4966"/></refs></nvt></result>
+              <result id="3"><host>10.0.0.6</host><port>22/tcp</port>
+                <nvt oid="3"><cve>NOCVE</cve></nvt></result>
+            </results>
+        </report></report>"#;
+        let (status, Json(accepted)) =
+            import_openvas(State(state.clone()), anonymous(), axum::body::Bytes::from(xml))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+
+        let poll = async {
+            loop {
+                let job = state.jobs.get(&accepted.job_id).await.unwrap();
+                if job.status.is_finished() {
+                    break job;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        };
+        let job = tokio::time::timeout(std::time::Duration::from_secs(30), poll)
+            .await
+            .expect("import did not finish within 30s");
+        assert_eq!(job.status, jobs::JobStatus::Completed);
+        assert_eq!(job.scan_id.as_deref(), Some(accepted.scan_id.as_str()));
+        assert_eq!((job.progress, job.total), (2, 2));
+
+        let metadata = state.archive.get_scan_metadata(&accepted.scan_id).unwrap().unwrap();
+        assert_eq!(metadata.status, "completed");
+        assert_eq!(metadata.total_vulnerabilities, 2);
+        assert_eq!(metadata.started_at.to_rfc3339(), "2023-05-02T09:14:27+00:00");
+        assert_eq!(metadata.ended_at.unwrap().to_rfc3339(), "2023-05-02T10:02:51+00:00");
+        assert_eq!(state.archive.get_scan_results(&accepted.scan_id).unwrap().len(), 2);
+
+        let err = import_openvas(State(state), anonymous(), axum::body::Bytes::from("<report>"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_end_scan_fires_completion_webhook() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let receiver = Router::new().route(
+            "/hook",
+            post(move |headers: axum::http::HeaderMap, body: axum::body::Bytes| {
+                let tx = tx.clone();
+                async move {
+                    tx.send((headers, body)).unwrap();
+                    StatusCode::NO_CONTENT
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, receiver).await.unwrap() });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let webhook = CompletionWebhook::new(
+            format!("http://{}/hook", addr),
+            Some("s3cret".to_string()),
+            &the_foundation::HttpSettings::default(),
+        )
+        .unwrap();
+        let interface = TheInterface::the_awakening(
+            "127.0.0.1:0".to_string(),
+            temp_dir.path().join("cryptex.db"),
+        )
+        .await
+        .unwrap()
+        .with_completion_webhook(webhook);
+        let state = interface.state.clone();
+
+        let (_, Json(started)) = start_scan(
+            State(state.clone()),
+            anonymous(),
+            HeaderMap::new(),
+            Json(StartScanRequest {
+                target: "10.0.0.0/24".to_string(),
+                targets: Vec::new(),
+                options: ScanOptionsOverride::default(),
+                scan_id: None,
+                replace: false,
+                tags: Vec::new(),
+            }),
+        )
+        .await
+        .unwrap();
+        let Json(report) = end_scan(State(state), anonymous(), Path(started.scan_id.clone()))
+            .await
+            .unwrap();
+
+        let (headers, body) = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let delivered: ScanReport = serde_json::from_slice(&body).unwrap();
+        assert_eq!(delivered.scan_id, report.scan_id);
+        assert_eq!(delivered.target, "10.0.0.0/24");
+        assert_eq!(
+            headers[webhook::SIGNATURE_HEADER].to_str().unwrap(),
+            webhook::sign("s3cret", &body)
+        );
+    }
 }
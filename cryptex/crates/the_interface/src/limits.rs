@@ -0,0 +1,115 @@
+//! Request Limits
+//!
+//! Traditional name: `RequestGuard`
+//!
+//! Without a cap, a JSON body such as an assessment batch naming a million
+//! CVEs is buffered whole before any handler can reject it. Bodies larger
+//! than `max_body_bytes` are answered with `413 Payload Too Large`, and a
+//! request still unanswered after `timeout` gets `408 Request Timeout`.
+//! Report, export and event streaming routes are mounted without either
+//! limit, since a large export can legitimately take longer than any API
+//! call and an event stream stays open indefinitely. OpenVAS report imports
+//! get a body limit of their own: a GVM report is far larger than any JSON
+//! request, and only its upload and parsing happen within the request.
+
+use axum::{extract::DefaultBodyLimit, Router};
+use std::time::Duration;
+use the_coordinator::HttpServerConfig;
+use tower_http::{limit::RequestBodyLimitLayer, timeout::TimeoutLayer};
+
+/// Environment variable overriding the request body limit, in bytes
+pub const MAX_BODY_BYTES_ENV: &str = "CRYPTEX_HTTP__MAX_BODY_BYTES";
+
+/// Environment variable overriding the OpenVAS import body limit, in bytes
+pub const MAX_IMPORT_BYTES_ENV: &str = "CRYPTEX_HTTP__MAX_IMPORT_BYTES";
+
+/// Environment variable overriding the request timeout, in seconds
+pub const REQUEST_TIMEOUT_ENV: &str = "CRYPTEX_HTTP__REQUEST_TIMEOUT_SECONDS";
+
+/// Largest request body accepted when none is configured
+pub const DEFAULT_MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+/// Largest OpenVAS report accepted when none is configured
+pub const DEFAULT_MAX_IMPORT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Time allowed per request when none is configured
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Size and time limits applied to incoming requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    pub max_body_bytes: usize,
+    pub max_import_bytes: usize,
+    pub timeout: Duration,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_import_bytes: DEFAULT_MAX_IMPORT_BYTES,
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Limits from the charter's `[http]` section
+    ///
+    /// `CRYPTEX_HTTP__MAX_BODY_BYTES`, `CRYPTEX_HTTP__MAX_IMPORT_BYTES` and
+    /// `CRYPTEX_HTTP__REQUEST_TIMEOUT_SECONDS` override the charter; unset or
+    /// unparseable values are ignored.
+    pub fn from_charter(http: &HttpServerConfig) -> Self {
+        let bytes_from_env =
+            |name| positive_from_env(name).and_then(|n| usize::try_from(n).ok());
+        Self {
+            max_body_bytes: bytes_from_env(MAX_BODY_BYTES_ENV).unwrap_or(http.max_body_bytes),
+            max_import_bytes: bytes_from_env(MAX_IMPORT_BYTES_ENV)
+                .unwrap_or(http.max_import_bytes),
+            timeout: Duration::from_secs(
+                positive_from_env(REQUEST_TIMEOUT_ENV).unwrap_or(http.request_timeout_seconds),
+            ),
+        }
+    }
+
+    /// Apply the body limit and the timeout to every route of `router`
+    ///
+    /// axum's own 2 MB default for extractors is lifted so the configured
+    /// limit is the only one in force.
+    pub(crate) fn apply<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        self.apply_with_body_limit(router, self.max_body_bytes)
+    }
+
+    /// Apply the import body limit and the timeout to every route of `router`
+    pub(crate) fn apply_to_imports<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        self.apply_with_body_limit(router, self.max_import_bytes)
+    }
+
+    fn apply_with_body_limit<S>(&self, router: Router<S>, max_body_bytes: usize) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        router
+            .layer(DefaultBodyLimit::disable())
+            .layer(RequestBodyLimitLayer::new(max_body_bytes))
+            .layer(TimeoutLayer::new(self.timeout))
+    }
+}
+
+fn positive_from_env(name: &str) -> Option<u64> {
+    let value = std::env::var(name).ok()?;
+
+    match value.parse::<u64>() {
+        Ok(n) if n > 0 => Some(n),
+        _ => {
+            tracing::warn!("Ignoring {}={}: not a positive number", name, value);
+            None
+        }
+    }
+}
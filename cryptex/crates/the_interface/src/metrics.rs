@@ -0,0 +1,96 @@
+//! Prometheus Metrics
+//!
+//! Traditional name: `MetricsExporter`
+//!
+//! `GET /metrics` answers in the Prometheus text exposition format so a
+//! scraper can watch archive transaction latency without parsing JSON.
+//! Time is exported as running totals in seconds next to the transaction
+//! counts, so `rate()` of one over the other gives the recent mean latency.
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use std::fmt::Write as _;
+use std::sync::Arc;
+use the_archive::{ArchiveMetrics, TheArchive};
+
+/// Content type of the text exposition format
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Render archive metrics as Prometheus text
+pub fn render_archive_metrics(metrics: &ArchiveMetrics) -> String {
+    let families: [(&str, &str, &str, String); 5] = [
+        (
+            "cryptex_archive_write_transactions_total",
+            "counter",
+            "Committed archive write transactions",
+            metrics.write_count.to_string(),
+        ),
+        (
+            "cryptex_archive_write_seconds_total",
+            "counter",
+            "Time spent in committed archive write transactions, lock wait included",
+            metrics.write_seconds.to_string(),
+        ),
+        (
+            "cryptex_archive_write_seconds_max",
+            "gauge",
+            "Slowest archive write since start-up",
+            (metrics.max_write_ms / 1000.0).to_string(),
+        ),
+        (
+            "cryptex_archive_read_transactions_total",
+            "counter",
+            "Finished archive read transactions",
+            metrics.read_count.to_string(),
+        ),
+        (
+            "cryptex_archive_read_seconds_total",
+            "counter",
+            "Time archive read transactions stayed open",
+            metrics.read_seconds.to_string(),
+        ),
+    ];
+
+    let mut out = String::new();
+    for (name, kind, help, value) in families {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(out, "{} {}", name, value);
+    }
+    out
+}
+
+/// `GET /metrics`
+pub async fn metrics_handler(State(archive): State<Arc<TheArchive>>) -> Response {
+    let body = render_archive_metrics(&archive.archive_metrics());
+    ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use the_archive::ScanMetadata;
+
+    #[tokio::test]
+    async fn test_metrics_handler_reports_archive_writes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = Arc::new(TheArchive::the_awakening(temp_dir.path().join("m.db")).unwrap());
+        let metadata = ScanMetadata::new("scan_m".to_string(), "10.0.0.1".to_string());
+        archive.store_scan_metadata(&metadata).unwrap();
+
+        let response = metrics_handler(State(archive.clone())).await;
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROMETHEUS_CONTENT_TYPE);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        let expected = archive.archive_metrics().write_count;
+        assert!(expected >= 1);
+        assert!(text.contains(&format!("cryptex_archive_write_transactions_total {}\n", expected)));
+        assert!(text.contains("# TYPE cryptex_archive_write_seconds_total counter\n"));
+        assert!(text.contains("# TYPE cryptex_archive_write_seconds_max gauge\n"));
+        assert_eq!(text.lines().filter(|l| l.starts_with("# HELP")).count(), 5);
+    }
+}
@@ -0,0 +1,122 @@
+//! Report Content Negotiation
+//!
+//! Traditional name: `ContentNegotiator`
+//!
+//! `GET /report` picks its format from `?format=` when given, and otherwise
+//! from the `Accept` header, so a browser gets HTML and a plain API client
+//! gets JSON without either spelling out a format. Media ranges are tried in
+//! order of their `q` weight; `*/*`, a missing header and a header naming
+//! nothing we produce all fall back to JSON.
+
+use the_propagandist::ReportFormat;
+
+/// MIME type of a SARIF log
+pub const SARIF_MIME_TYPE: &str = "application/sarif+json";
+
+/// What the report endpoint sends back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportOutput {
+    /// A rendered report
+    Report(ReportFormat),
+    /// A SARIF 2.1.0 log of the report's findings
+    Sarif,
+}
+
+impl ReportOutput {
+    /// Choose the output from the `format` query parameter, else from `Accept`
+    pub fn negotiate(format: Option<&str>, accept: Option<&str>) -> Self {
+        match format {
+            Some(format) if format.eq_ignore_ascii_case("sarif") => ReportOutput::Sarif,
+            Some(format) => ReportOutput::Report(crate::parse_report_format(Some(format))),
+            None => accept.map_or(ReportOutput::Report(ReportFormat::Json), from_accept),
+        }
+    }
+
+    /// `Content-Type` of the response
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ReportOutput::Report(format) => format.mime_type(),
+            ReportOutput::Sarif => SARIF_MIME_TYPE,
+        }
+    }
+}
+
+/// Best output for an `Accept` header value
+fn from_accept(accept: &str) -> ReportOutput {
+    let mut ranges: Vec<(&str, f32)> = accept
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let media_type = parts.next()?.trim();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (!media_type.is_empty() && quality > 0.0).then_some((media_type, quality))
+        })
+        .collect();
+    // Stable, so equally weighted ranges keep the client's order
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges
+        .into_iter()
+        .find_map(|(media_type, _)| output_for(media_type))
+        .unwrap_or(ReportOutput::Report(ReportFormat::Json))
+}
+
+fn output_for(media_type: &str) -> Option<ReportOutput> {
+    let output = match media_type.to_ascii_lowercase().as_str() {
+        "application/json" | "application/*" | "*/*" => ReportOutput::Report(ReportFormat::Json),
+        "text/html" => ReportOutput::Report(ReportFormat::Html),
+        "text/markdown" => ReportOutput::Report(ReportFormat::Markdown),
+        "text/plain" | "text/*" => ReportOutput::Report(ReportFormat::Text),
+        "text/csv" => ReportOutput::Report(ReportFormat::Csv),
+        SARIF_MIME_TYPE => ReportOutput::Sarif,
+        _ => return None,
+    };
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> ReportOutput {
+        ReportOutput::negotiate(None, Some(value))
+    }
+
+    #[test]
+    fn test_accept_header_selects_format() {
+        assert_eq!(accept("text/html"), ReportOutput::Report(ReportFormat::Html));
+        assert_eq!(accept("*/*"), ReportOutput::Report(ReportFormat::Json));
+        assert_eq!(accept("application/sarif+json"), ReportOutput::Sarif);
+        assert_eq!(accept("image/png"), ReportOutput::Report(ReportFormat::Json));
+        assert_eq!(
+            ReportOutput::negotiate(None, None),
+            ReportOutput::Report(ReportFormat::Json)
+        );
+
+        // Weights decide, then the client's order; q=0 rules a type out
+        assert_eq!(
+            accept("text/plain;q=0.5, text/markdown"),
+            ReportOutput::Report(ReportFormat::Markdown)
+        );
+        assert_eq!(
+            accept("text/html;q=0, */*;q=0.1"),
+            ReportOutput::Report(ReportFormat::Json)
+        );
+        assert_eq!(
+            accept("text/html,application/xhtml+xml,*/*;q=0.8"),
+            ReportOutput::Report(ReportFormat::Html)
+        );
+    }
+
+    #[test]
+    fn test_format_query_wins_over_accept() {
+        assert_eq!(
+            ReportOutput::negotiate(Some("markdown"), Some("text/html")),
+            ReportOutput::Report(ReportFormat::Markdown)
+        );
+        assert_eq!(ReportOutput::negotiate(Some("SARIF"), None), ReportOutput::Sarif);
+    }
+}
@@ -0,0 +1,146 @@
+//! Readiness Gate
+//!
+//! The listener comes up before the start-up self-check has finished, so
+//! a load balancer can already see `/health`. Until the check passes, every
+//! other route answers `503 Service Unavailable` instead of failing in
+//! whatever half-initialized way the component behind it would.
+//! `/health/ready` reports the current state for orchestrators.
+//!
+//! A failed check is retried with backoff; once the attempts run out the
+//! server shuts down rather than staying up and unready forever.
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use the_foundation::{CryptexError, CryptexResult};
+use tokio::sync::Notify;
+
+/// How often the start-up self-check runs before the server gives up
+pub const SELF_CHECK_ATTEMPTS: u32 = 6;
+
+/// Wait before the first retry; it doubles after every failure
+pub const SELF_CHECK_FIRST_DELAY: Duration = Duration::from_millis(500);
+
+/// Whether the server has finished starting up
+#[derive(Debug, Default)]
+pub struct ReadyState {
+    ready: AtomicBool,
+    notify: Notify,
+}
+
+impl ReadyState {
+    /// Has readiness been signaled
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// Signal readiness and wake everyone waiting for it
+    pub fn mark_ready(&self) {
+        self.ready.store(true, Ordering::Release);
+        self.notify.notify_waiters();
+    }
+
+    /// Resolve once readiness has been signaled
+    pub async fn wait_ready(&self) {
+        loop {
+            // Register before checking so a signal in between is not missed
+            let notified = self.notify.notified();
+            if self.is_ready() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Run the blocking `check` until it passes, then mark `ready`
+///
+/// Waits `first_delay` after the first failure, doubling after each one.
+/// The last failure is returned once `attempts` are used up.
+pub async fn run_self_check<F>(
+    ready: Arc<ReadyState>,
+    attempts: u32,
+    first_delay: Duration,
+    check: F,
+) -> CryptexResult<()>
+where
+    F: Fn() -> CryptexResult<()> + Clone + Send + 'static,
+{
+    let mut delay = first_delay;
+    let mut attempt = 1;
+    loop {
+        let result = tokio::task::spawn_blocking(check.clone()).await.unwrap_or_else(|e| {
+            Err(CryptexError::validation(format!("Self-check panicked: {}", e)))
+        });
+        match result {
+            Ok(()) => {
+                ready.mark_ready();
+                return Ok(());
+            }
+            Err(e) if attempt >= attempts => {
+                return Err(CryptexError::validation(format!(
+                    "Start-up self-check failed {} times, giving up: {}",
+                    attempts, e
+                )))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Start-up self-check failed (attempt {}/{}), retrying in {:?}: {}",
+                    attempt,
+                    attempts,
+                    delay,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Health routes answer during start-up; they describe it
+fn is_health_route(path: &str) -> bool {
+    path == "/health" || path.starts_with("/health/")
+}
+
+/// Middleware rejecting non-health requests until the server is ready
+pub async fn require_ready(
+    State(ready): State<Arc<ReadyState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if ready.is_ready() || is_health_route(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({
+            "code": "NOT_READY",
+            "error": StatusCode::SERVICE_UNAVAILABLE.to_string(),
+            "message": "Server is still starting up",
+        })),
+    )
+        .into_response()
+}
+
+/// `GET /health/ready`: 200 once ready, 503 while starting
+pub async fn readiness_check(State(ready): State<Arc<ReadyState>>) -> Response {
+    if ready.is_ready() {
+        (StatusCode::OK, Json(serde_json::json!({ "status": "ready" }))).into_response()
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({ "status": "starting" })),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,190 @@
+//! API Versioning
+//!
+//! Traditional name: `ApiVersionPolicy`
+//!
+//! Every response names the API version that served it in `X-API-Version`,
+//! and `GET /api/versions` lists the versions this server speaks. Routes an
+//! operator marks deprecated also carry `Deprecation: true` and, when a
+//! removal date is set, an RFC 8594 `Sunset` header, so integrators see the
+//! warning in their own logs long before the route goes away.
+//!
+//! Deprecations are configured as `path` or `path@YYYY-MM-DD` entries, where
+//! `path` is the route pattern as registered, e.g.
+//! `/api/v1/scans/:scan_id/executive-summary@2027-01-31`.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub use the_coordinator::DeprecatedRoute;
+
+/// Response header naming the API version that served the request
+pub const API_VERSION_HEADER: &str = "x-api-version";
+
+/// Response header marking a deprecated route
+pub const DEPRECATION_HEADER: &str = "deprecation";
+
+/// Response header giving the date a deprecated route will be removed
+pub const SUNSET_HEADER: &str = "sunset";
+
+/// Version served under `/api/v1`
+pub const CURRENT_API_VERSION: &str = "v1";
+
+/// Environment variable listing deprecated routes, comma-separated
+pub const DEPRECATED_ROUTES_ENV: &str = "CRYPTEX_HTTP__DEPRECATED_ROUTES";
+
+/// Where an API version is in its life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionStatus {
+    Current,
+    Deprecated,
+}
+
+/// One API version this server speaks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiVersion {
+    pub version: String,
+    pub status: VersionStatus,
+    /// Path prefix of the version's routes
+    pub base_path: String,
+}
+
+/// `Sunset` header value of `route`, an HTTP date
+fn sunset_header(route: &DeprecatedRoute) -> Option<HeaderValue> {
+    let sunset = route.sunset?.and_hms_opt(0, 0, 0)?.and_utc();
+    HeaderValue::from_str(&sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string()).ok()
+}
+
+/// Deprecated routes by path pattern
+#[derive(Debug, Clone, Default)]
+pub struct Deprecations {
+    routes: HashMap<String, DeprecatedRoute>,
+}
+
+impl Deprecations {
+    /// Deprecations from the charter's `http.deprecated_routes`
+    ///
+    /// `CRYPTEX_HTTP__DEPRECATED_ROUTES`, when set, replaces the charter's
+    /// list. Malformed entries are logged and skipped.
+    pub fn from_charter(configured: &[String]) -> Self {
+        let from_env = std::env::var(DEPRECATED_ROUTES_ENV).ok().map(|value| {
+            value
+                .split(',')
+                .filter(|entry| !entry.trim().is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        });
+
+        let mut deprecations = Self::default();
+        for entry in from_env.as_deref().unwrap_or(configured) {
+            match DeprecatedRoute::parse(entry) {
+                Ok(route) => deprecations.insert(route),
+                Err(e) => tracing::warn!("Ignoring deprecated route: {}", e),
+            }
+        }
+        deprecations
+    }
+
+    /// Mark a route deprecated, replacing any earlier entry for its path
+    pub fn insert(&mut self, route: DeprecatedRoute) {
+        self.routes.insert(route.path.clone(), route);
+    }
+
+    /// The deprecation for a route pattern, if it has one
+    pub fn get(&self, path: &str) -> Option<&DeprecatedRoute> {
+        self.routes.get(path)
+    }
+
+    /// Every deprecated route, ordered by path
+    pub fn routes(&self) -> Vec<DeprecatedRoute> {
+        let mut routes: Vec<DeprecatedRoute> = self.routes.values().cloned().collect();
+        routes.sort_by(|a, b| a.path.cmp(&b.path));
+        routes
+    }
+}
+
+/// Body of `GET /api/versions`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionsResponse {
+    pub current: String,
+    pub versions: Vec<ApiVersion>,
+    pub deprecated_routes: Vec<DeprecatedRoute>,
+}
+
+/// List the API versions this server speaks
+pub async fn list_versions(
+    State(deprecations): State<Arc<Deprecations>>,
+) -> Json<VersionsResponse> {
+    Json(VersionsResponse {
+        current: CURRENT_API_VERSION.to_string(),
+        versions: vec![ApiVersion {
+            version: CURRENT_API_VERSION.to_string(),
+            status: VersionStatus::Current,
+            base_path: format!("/api/{}", CURRENT_API_VERSION),
+        }],
+        deprecated_routes: deprecations.routes(),
+    })
+}
+
+/// Middleware adding the version header, and deprecation headers where configured
+///
+/// Must be added with `Router::layer` so the matched route pattern is known.
+pub async fn version_headers(
+    State(deprecations): State<Arc<Deprecations>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let deprecation = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|matched| deprecations.get(matched.as_str()))
+        .cloned();
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(API_VERSION_HEADER, HeaderValue::from_static(CURRENT_API_VERSION));
+    if let Some(route) = deprecation {
+        headers.insert(DEPRECATION_HEADER, HeaderValue::from_static("true"));
+        if let Some(sunset) = sunset_header(&route) {
+            headers.insert(SUNSET_HEADER, sunset);
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deprecated_route() {
+        let route = DeprecatedRoute::parse(" /api/v1/activity@2027-01-31 ").unwrap();
+        assert_eq!(route.path, "/api/v1/activity");
+        assert_eq!(
+            sunset_header(&route).unwrap(),
+            "Sun, 31 Jan 2027 00:00:00 GMT"
+        );
+
+        assert_eq!(DeprecatedRoute::parse("/api/v1/audit").unwrap().sunset, None);
+        assert!(DeprecatedRoute::parse("api/v1/audit").is_err());
+        assert!(DeprecatedRoute::parse("/api/v1/audit@31/01/2027").is_err());
+    }
+
+    #[test]
+    fn test_deprecations_from_charter() {
+        let configured = vec!["/api/v1/activity@2027-01-31".to_string(), "audit".to_string()];
+        let deprecations = Deprecations::from_charter(&configured);
+
+        // The malformed entry is skipped rather than failing start-up
+        assert_eq!(deprecations.routes().len(), 1);
+        assert!(deprecations.get("/api/v1/activity").unwrap().sunset.is_some());
+    }
+}
@@ -0,0 +1,271 @@
+//! Completion Webhook
+//!
+//! When a scan ends, its report is POSTed to a configured URL so automation
+//! can react without polling. Delivery is best-effort: it runs in the
+//! background, retries a few times, and only logs when it gives up - a dead
+//! receiver never fails the scan.
+//!
+//! If a secret is configured, each delivery carries an HMAC-SHA256 of the
+//! request body in the `X-Cryptex-Signature` header (`sha256=<hex>`).
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::Duration;
+
+use the_coordinator::CommuneConfig;
+use the_foundation::{build_http_client, CryptexError, CryptexResult, HttpSettings};
+use the_infiltrator::ScanReport;
+
+/// Header carrying the payload signature
+pub const SIGNATURE_HEADER: &str = "x-cryptex-signature";
+
+/// Time allowed for each delivery attempt
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Attempts made before a delivery is abandoned
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Pause before the first retry; doubled for each one after it
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Completion Webhook - Notifies an external endpoint when scans finish
+///
+/// Traditional name: `WebhookNotifier`
+#[derive(Clone)]
+pub struct CompletionWebhook {
+    url: String,
+    secret: Option<String>,
+    client: reqwest::Client,
+    retry_backoff: Duration,
+}
+
+impl CompletionWebhook {
+    /// Create a webhook posting to `url`, signing payloads with `secret` if given
+    ///
+    /// Deliveries go out through a client built from `http`, so a configured
+    /// proxy applies to them.
+    pub fn new(
+        url: impl Into<String>,
+        secret: Option<String>,
+        http: &HttpSettings,
+    ) -> CryptexResult<Self> {
+        let url = url.into();
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(CryptexError::validation(format!(
+                "Completion webhook URL must use http or https, got '{}'",
+                url
+            )));
+        }
+
+        Ok(Self {
+            url,
+            secret,
+            client: build_http_client(http)?,
+            retry_backoff: RETRY_BACKOFF,
+        })
+    }
+
+    /// Webhook configured through the environment, if any
+    ///
+    /// Reads `CRYPTEX_COMMUNE__COMPLETION_WEBHOOK_URL` and
+    /// `CRYPTEX_COMMUNE__COMPLETION_WEBHOOK_SECRET`, the same variables that
+    /// override `commune.completion_webhook_*` in the charter.
+    pub fn from_env(http: &HttpSettings) -> Option<Self> {
+        let url = std::env::var("CRYPTEX_COMMUNE__COMPLETION_WEBHOOK_URL").ok()?;
+        let secret = std::env::var("CRYPTEX_COMMUNE__COMPLETION_WEBHOOK_SECRET").ok();
+
+        Self::configured(url, secret, http)
+    }
+
+    /// Webhook set in the charter's `[commune]` section, if any
+    pub fn from_charter(commune: &CommuneConfig, http: &HttpSettings) -> Option<Self> {
+        let url = commune.completion_webhook_url.clone()?;
+
+        Self::configured(url, commune.completion_webhook_secret.clone(), http)
+    }
+
+    /// A configured webhook, or `None` with a warning if it is unusable
+    fn configured(url: String, secret: Option<String>, http: &HttpSettings) -> Option<Self> {
+        match Self::new(url, secret, http) {
+            Ok(webhook) => Some(webhook),
+            Err(e) => {
+                tracing::warn!("Completion webhook disabled: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Deliver the report in the background
+    pub fn spawn_delivery(&self, report: ScanReport) {
+        let webhook = self.clone();
+        tokio::spawn(async move {
+            webhook.deliver(&report).await;
+        });
+    }
+
+    /// POST the report, retrying on failure; returns whether it was accepted
+    pub async fn deliver(&self, report: &ScanReport) -> bool {
+        let body = match serde_json::to_vec(report) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("Failed to serialize report {} for webhook: {}", report.scan_id, e);
+                return false;
+            }
+        };
+
+        let mut backoff = self.retry_backoff;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut request = self
+                .client
+                .post(&self.url)
+                .timeout(DELIVERY_TIMEOUT)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(secret) = &self.secret {
+                request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+            }
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    tracing::debug!("Delivered completion webhook for scan {}", report.scan_id);
+                    return true;
+                }
+                Ok(response) => tracing::warn!(
+                    "Completion webhook for scan {} returned {} (attempt {}/{})",
+                    report.scan_id,
+                    response.status(),
+                    attempt,
+                    MAX_ATTEMPTS
+                ),
+                Err(e) => tracing::warn!(
+                    "Completion webhook for scan {} failed: {} (attempt {}/{})",
+                    report.scan_id,
+                    e,
+                    attempt,
+                    MAX_ATTEMPTS
+                ),
+            }
+
+            if attempt < MAX_ATTEMPTS {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+
+        tracing::error!(
+            "Giving up on completion webhook for scan {} after {} attempts",
+            report.scan_id,
+            MAX_ATTEMPTS
+        );
+        false
+    }
+}
+
+/// Signature of `body` under `secret`, formatted as `sha256=<hex>`
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body);
+
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("sha256={}", digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sign_matches_rfc_4231() {
+        // RFC 4231 test case 2
+        let signature = sign("Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            signature,
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_http_url() {
+        let http = HttpSettings::default();
+        assert!(CompletionWebhook::new("ftp://example.com/hook", None, &http).is_err());
+        assert!(CompletionWebhook::new("https://example.com/hook", None, &http).is_ok());
+    }
+
+    #[test]
+    fn test_webhook_from_charter() {
+        let http = HttpSettings::default();
+        let mut commune = the_coordinator::TheCharter::builtin().commune;
+        assert!(CompletionWebhook::from_charter(&commune, &http).is_none());
+
+        commune.completion_webhook_url = Some("https://hooks.example.com/cryptex".to_string());
+        commune.completion_webhook_secret = Some("s3cret".to_string());
+        let webhook = CompletionWebhook::from_charter(&commune, &http).unwrap();
+        assert_eq!(webhook.url, "https://hooks.example.com/cryptex");
+        assert_eq!(webhook.secret.as_deref(), Some("s3cret"));
+
+        commune.completion_webhook_url = Some("hooks.example.com/cryptex".to_string());
+        assert!(CompletionWebhook::from_charter(&commune, &http).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_goes_through_the_proxy() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let proxy = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let http =
+            HttpSettings::default().with_proxy(format!("http://{}", proxy.local_addr().unwrap()));
+        let webhook =
+            CompletionWebhook::new("http://hooks.example.invalid/cryptex", None, &http).unwrap();
+
+        let report = ScanReport::new("scan-001".to_string(), "10.0.0.0/24".to_string());
+        let delivery = tokio::spawn(async move { webhook.deliver(&report).await });
+
+        let (mut stream, _) = proxy.accept().await.unwrap();
+        let mut head = vec![0u8; 1024];
+        let read = stream.read(&mut head).await.unwrap();
+        let head = String::from_utf8_lossy(&head[..read]).to_string();
+        stream
+            .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+            .await
+            .unwrap();
+
+        assert!(head.starts_with("POST http://hooks.example.invalid/cryptex HTTP/1.1"), "{}", head);
+        assert!(delivery.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries_then_gives_up() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let app = axum::Router::new().route(
+            "/hook",
+            axum::routing::post(move || {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    axum::http::StatusCode::SERVICE_UNAVAILABLE
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut webhook =
+            CompletionWebhook::new(format!("http://{}/hook", addr), None, &HttpSettings::default())
+                .unwrap();
+        webhook.retry_backoff = Duration::from_millis(1);
+
+        let report = ScanReport::new("scan-001".to_string(), "10.0.0.0/24".to_string());
+        assert!(!webhook.deliver(&report).await);
+        assert_eq!(attempts.load(Ordering::SeqCst), MAX_ATTEMPTS as usize);
+    }
+}
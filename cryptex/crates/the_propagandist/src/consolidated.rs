@@ -0,0 +1,133 @@
+//! Consolidated Reports - One assessment across several scans
+//!
+//! Scans of overlapping ranges report the same finding more than once. The
+//! consolidated report keeps each `(cve_id, host, port)` once - the first
+//! scan to report it wins - and recomputes every total from what is left,
+//! so the numbers describe the estate rather than the sum of the scans.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use the_foundation::{CryptexError, CryptexResult};
+use the_infiltrator::{ScanReport, ScanStatus};
+
+/// One source scan's contribution to a consolidated report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanBreakdown {
+    pub scan_id: String,
+    pub target: String,
+    /// Findings in the scan as reported, duplicates included
+    pub total_vulnerabilities: usize,
+    pub critical_count: usize,
+    pub high_count: usize,
+    pub kev_count: usize,
+    /// Findings already reported by an earlier scan in the set
+    pub duplicates: usize,
+}
+
+/// Merge `reports` into a single report, plus a breakdown per source scan
+///
+/// The merged report's `summary` carries the breakdown as
+/// `{"consolidated_from": [...], "duplicates_removed": n}` so JSON output
+/// includes it without a separate document.
+pub fn consolidate(reports: &[ScanReport]) -> CryptexResult<(ScanReport, Vec<ScanBreakdown>)> {
+    if reports.is_empty() {
+        return Err(CryptexError::validation(
+            "A consolidated report needs at least one scan",
+        ));
+    }
+
+    let scan_ids: Vec<&str> = reports.iter().map(|r| r.scan_id.as_str()).collect();
+    let mut merged = ScanReport::new(scan_ids.join("+"), String::new());
+    merged.targets.clear();
+
+    let mut seen: HashSet<(&str, &str, u16)> = HashSet::new();
+    let mut hosts: HashSet<&str> = HashSet::new();
+    let mut breakdown = Vec::with_capacity(reports.len());
+
+    for report in reports {
+        let mut duplicates = 0;
+        for result in &report.scan_results {
+            if seen.insert((&result.cve_id, &result.host, result.port)) {
+                hosts.insert(&result.host);
+                merged.add_result(result.clone());
+            } else {
+                duplicates += 1;
+            }
+        }
+
+        for target in &report.targets {
+            if !merged.targets.contains(target) {
+                merged.targets.push(target.clone());
+            }
+        }
+
+        breakdown.push(ScanBreakdown {
+            scan_id: report.scan_id.clone(),
+            target: report.target.clone(),
+            total_vulnerabilities: report.total_vulnerabilities,
+            critical_count: report.critical_count,
+            high_count: report.high_count,
+            kev_count: report.kev_count,
+            duplicates,
+        });
+    }
+
+    let duplicates_removed: usize = breakdown.iter().map(|b| b.duplicates).sum();
+    merged.target = merged.targets.join(", ");
+    merged.start_time = reports.iter().map(|r| r.start_time).min().unwrap_or_default();
+    merged.end_time = reports.iter().filter_map(|r| r.end_time).max();
+    merged.status = if reports.iter().all(|r| r.status == ScanStatus::Completed) {
+        ScanStatus::Completed
+    } else {
+        ScanStatus::Running
+    };
+    merged.total_hosts = hosts.len();
+    merged.summary = Some(serde_json::json!({
+        "consolidated_from": breakdown,
+        "duplicates_removed": duplicates_removed,
+    }));
+
+    Ok((merged, breakdown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use the_infiltrator::ScanResult;
+
+    fn finding(cve_id: &str, host: &str, port: u16) -> ScanResult {
+        ScanResult::new(
+            cve_id.to_string(),
+            host.to_string(),
+            port,
+            "unknown".to_string(),
+            cve_id.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_consolidate_dedupes_shared_findings() {
+        let mut first = ScanReport::new("scan-a".to_string(), "10.0.0.0/24".to_string());
+        first.add_result(finding("CVE-2021-44228", "10.0.0.5", 443));
+        first.add_result(finding("CVE-2024-0001", "10.0.0.6", 22));
+        first.start_time = 100;
+
+        let mut second = ScanReport::new("scan-b".to_string(), "10.0.0.0/23".to_string());
+        second.add_result(finding("CVE-2021-44228", "10.0.0.5", 443));
+        second.add_result(finding("CVE-2021-44228", "10.0.1.9", 443));
+        second.start_time = 50;
+
+        let (merged, breakdown) = consolidate(&[first, second]).unwrap();
+
+        assert_eq!(merged.total_vulnerabilities, 3);
+        assert_eq!(merged.total_hosts, 3);
+        assert_eq!(merged.start_time, 50);
+        assert_eq!(merged.target, "10.0.0.0/24, 10.0.0.0/23");
+        assert_eq!(breakdown[0].duplicates, 0);
+        assert_eq!(breakdown[1].duplicates, 1);
+        assert_eq!(breakdown[1].total_vulnerabilities, 2);
+        assert_eq!(merged.summary.unwrap()["duplicates_removed"], 1);
+
+        assert!(consolidate(&[]).is_err());
+    }
+}
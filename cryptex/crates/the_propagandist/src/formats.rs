@@ -1,6 +1,57 @@
 //! Report format types and utilities
 
 use serde::{Deserialize, Serialize};
+use the_foundation::{CryptexError, CryptexResult};
+use the_infiltrator::{ConfidenceLevel, ScanReport};
+
+/// Accent color of the stock HTML report
+pub const DEFAULT_ACCENT_COLOR: &str = "#e74c3c";
+
+/// Environment variable overriding the default report audience
+///
+/// Takes precedence over `report.audience` in the charter.
+pub const REPORT_AUDIENCE_ENV: &str = "CRYPTEX_REPORT__AUDIENCE";
+
+/// Environment variable overriding the default report sections
+///
+/// Takes precedence over `report.sections` in the charter; a comma-separated
+/// list.
+pub const REPORT_SECTIONS_ENV: &str = "CRYPTEX_REPORT__SECTIONS";
+
+/// Default report audience configured through the environment
+///
+/// `None` when unset; unknown values are ignored with a warning.
+pub fn report_audience_from_env() -> Option<ReportAudience> {
+    let value = std::env::var(REPORT_AUDIENCE_ENV).ok()?;
+
+    value
+        .parse()
+        .map_err(|e| tracing::warn!("Ignoring {}={}: {}", REPORT_AUDIENCE_ENV, value, e))
+        .ok()
+}
+
+/// Default report sections configured through the environment
+///
+/// `None` when unset; invalid values are ignored with a warning.
+pub fn report_sections_from_env() -> Option<SectionToggles> {
+    let value = std::env::var(REPORT_SECTIONS_ENV).ok()?;
+
+    SectionToggles::parse(&value)
+        .map_err(|e| tracing::warn!("Ignoring {}={}: {}", REPORT_SECTIONS_ENV, value, e))
+        .ok()
+}
+
+/// Default report audience: the environment's, else `configured` (the
+/// charter's `report.audience`)
+pub fn report_audience(configured: &str) -> CryptexResult<ReportAudience> {
+    report_audience_from_env().map_or_else(|| configured.parse(), Ok)
+}
+
+/// Default report sections: the environment's, else `configured` (the
+/// charter's `report.sections`)
+pub fn report_sections(configured: &[String]) -> CryptexResult<SectionToggles> {
+    report_sections_from_env().map_or_else(|| SectionToggles::parse(&configured.join(",")), Ok)
+}
 
 /// Report Format
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,6 +64,8 @@ pub enum ReportFormat {
     Markdown,
     /// Plain text format
     Text,
+    /// CSV format, one row per finding, for spreadsheets and ticketing imports
+    Csv,
 }
 
 impl ReportFormat {
@@ -23,6 +76,7 @@ impl ReportFormat {
             ReportFormat::Html => "html",
             ReportFormat::Markdown => "md",
             ReportFormat::Text => "txt",
+            ReportFormat::Csv => "csv",
         }
     }
 
@@ -33,6 +87,7 @@ impl ReportFormat {
             ReportFormat::Html => "text/html",
             ReportFormat::Markdown => "text/markdown",
             ReportFormat::Text => "text/plain",
+            ReportFormat::Csv => "text/csv",
         }
     }
 }
@@ -48,6 +103,108 @@ pub enum ReportAudience {
     Compliance,
 }
 
+impl std::str::FromStr for ReportAudience {
+    type Err = CryptexError;
+
+    /// Parse an audience name, case-insensitive (e.g. "executive")
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "technical" => Ok(ReportAudience::Technical),
+            "executive" => Ok(ReportAudience::Executive),
+            "compliance" => Ok(ReportAudience::Compliance),
+            _ => Err(CryptexError::validation(format!(
+                "Unknown report audience '{}'; expected technical, executive or compliance",
+                name
+            ))),
+        }
+    }
+}
+
+/// Section Toggles - Which parts of a document report to render
+///
+/// Applies to HTML, Markdown and text reports; JSON and CSV exports are data
+/// and always complete. A section a format does not have is simply absent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SectionToggles {
+    /// Scan ID, target, hosts and duration
+    pub overview: bool,
+    /// Counts by severity, KEV alert and overall risk level
+    pub stats: bool,
+    /// Highest-risk findings (and the HTML risk matrix) of executive reports
+    pub top: bool,
+    /// Findings grouped by host
+    pub hosts: bool,
+    /// Recommended actions
+    pub remediation: bool,
+}
+
+impl Default for SectionToggles {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl SectionToggles {
+    /// Section names accepted by `parse`
+    pub const NAMES: [&'static str; 5] = ["overview", "stats", "top", "hosts", "remediation"];
+
+    /// Every section
+    pub fn all() -> Self {
+        Self {
+            overview: true,
+            stats: true,
+            top: true,
+            hosts: true,
+            remediation: true,
+        }
+    }
+
+    fn none() -> Self {
+        Self {
+            overview: false,
+            stats: false,
+            top: false,
+            hosts: false,
+            remediation: false,
+        }
+    }
+
+    /// Only the sections in a comma-separated list, e.g. `overview,stats`
+    ///
+    /// A list naming no section is rejected rather than rendering an empty
+    /// report.
+    pub fn parse(list: &str) -> CryptexResult<Self> {
+        let mut toggles = Self::none();
+
+        for name in list.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            let toggle = match name.to_ascii_lowercase().as_str() {
+                "overview" => &mut toggles.overview,
+                "stats" => &mut toggles.stats,
+                "top" => &mut toggles.top,
+                "hosts" => &mut toggles.hosts,
+                "remediation" => &mut toggles.remediation,
+                _ => {
+                    return Err(CryptexError::validation(format!(
+                        "Unknown report section '{}'; expected one of {}",
+                        name,
+                        Self::NAMES.join(", ")
+                    )))
+                }
+            };
+            *toggle = true;
+        }
+
+        if toggles == Self::none() {
+            return Err(CryptexError::validation(format!(
+                "No report section named; expected one or more of {}",
+                Self::NAMES.join(", ")
+            )));
+        }
+        Ok(toggles)
+    }
+}
+
 /// Report Configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportConfig {
@@ -64,10 +221,80 @@ pub struct ReportConfig {
     pub include_compliance: bool,
 
     /// Maximum vulnerabilities to include in detail
+    ///
+    /// HTML, Markdown and text reports beyond this size list only the
+    /// highest-risk findings and say how many were left out; summary counts
+    /// always cover every finding. JSON and CSV exports are never cut.
     pub max_detailed_vulnerabilities: Option<usize>,
 
     /// Only include vulnerabilities above this severity
     pub min_severity: Option<the_assessor::CvssSeverity>,
+
+    /// Only include findings detected with at least this confidence
+    ///
+    /// Findings the scanner gave no confidence are left out too. Summary
+    /// counts cover only the findings that remain.
+    #[serde(default)]
+    pub min_confidence: Option<ConfidenceLevel>,
+
+    /// Branding for HTML reports
+    #[serde(default)]
+    pub theme: ReportTheme,
+
+    /// Sections to render
+    #[serde(default)]
+    pub sections: SectionToggles,
+}
+
+/// Report Theme - Branding for white-labeled HTML reports
+///
+/// The default theme reproduces the stock CRYPTEX look.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportTheme {
+    /// Company name shown in the report header
+    pub company_name: Option<String>,
+
+    /// Logo image URL shown in the report header (http, https, or data:image)
+    pub logo_url: Option<String>,
+
+    /// Hex accent color, e.g. `#1abc9c`
+    pub accent_color: String,
+}
+
+impl Default for ReportTheme {
+    fn default() -> Self {
+        Self {
+            company_name: None,
+            logo_url: None,
+            accent_color: DEFAULT_ACCENT_COLOR.to_string(),
+        }
+    }
+}
+
+impl ReportTheme {
+    /// Check that theme values are safe to embed in HTML and CSS
+    pub fn validate(&self) -> CryptexResult<()> {
+        let hex = self.accent_color.strip_prefix('#').unwrap_or("");
+        if !matches!(hex.len(), 3 | 6) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(CryptexError::validation(format!(
+                "Accent color must be a hex color like #1abc9c, got '{}'",
+                self.accent_color
+            )));
+        }
+
+        if let Some(logo_url) = &self.logo_url {
+            let allowed = ["https://", "http://", "data:image/"];
+            if !allowed.iter().any(|scheme| logo_url.starts_with(scheme)) {
+                return Err(CryptexError::validation(format!(
+                    "Logo URL must use http, https, or data:image, got '{}'",
+                    logo_url
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for ReportConfig {
@@ -79,6 +306,9 @@ impl Default for ReportConfig {
             include_compliance: false,
             max_detailed_vulnerabilities: None,
             min_severity: None,
+            min_confidence: None,
+            theme: ReportTheme::default(),
+            sections: SectionToggles::default(),
         }
     }
 }
@@ -106,6 +336,25 @@ impl ReportConfig {
         }
     }
 
+    /// Cap the findings listed in detail at `max` (see `max_detailed_vulnerabilities`)
+    pub fn max_findings(mut self, max: usize) -> Self {
+        self.max_detailed_vulnerabilities = Some(max);
+        self
+    }
+
+    /// Leave out findings less confident than `minimum`
+    pub fn min_confidence(mut self, minimum: ConfidenceLevel) -> Self {
+        self.min_confidence = Some(minimum);
+        self
+    }
+
+    /// Drop the findings this configuration excludes from `report`
+    pub(crate) fn select_findings(&self, report: &mut ScanReport) {
+        if let Some(minimum) = self.min_confidence {
+            report.retain_results(|result| result.meets_confidence(minimum));
+        }
+    }
+
     /// Create a compliance report configuration
     pub fn compliance() -> Self {
         Self {
@@ -126,6 +375,7 @@ mod tests {
         assert_eq!(ReportFormat::Json.extension(), "json");
         assert_eq!(ReportFormat::Html.extension(), "html");
         assert_eq!(ReportFormat::Markdown.extension(), "md");
+        assert_eq!(ReportFormat::Csv.extension(), "csv");
     }
 
     #[test]
@@ -136,6 +386,34 @@ mod tests {
         assert!(config.include_remediation);
     }
 
+    #[test]
+    fn test_theme_validation() {
+        assert!(ReportTheme::default().validate().is_ok());
+
+        let theme = ReportTheme {
+            accent_color: "red;}body{display:none".to_string(),
+            ..Default::default()
+        };
+        assert!(theme.validate().is_err());
+
+        let theme = ReportTheme {
+            logo_url: Some("javascript:alert(1)".to_string()),
+            ..Default::default()
+        };
+        assert!(theme.validate().is_err());
+    }
+
+    #[test]
+    fn test_section_toggles_parse() {
+        let toggles = SectionToggles::parse("overview, STATS").unwrap();
+        assert!(toggles.overview && toggles.stats);
+        assert!(!toggles.top && !toggles.hosts && !toggles.remediation);
+
+        assert_eq!(SectionToggles::parse(&SectionToggles::NAMES.join(",")).unwrap(), SectionToggles::all());
+        assert!(SectionToggles::parse("overview,appendix").is_err());
+        assert_eq!("Executive".parse::<ReportAudience>().unwrap(), ReportAudience::Executive);
+    }
+
     #[test]
     fn test_executive_config() {
         let config = ReportConfig::executive();
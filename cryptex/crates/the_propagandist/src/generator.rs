@@ -1,8 +1,12 @@
 //! Report Generator - Main API
 
+use crate::consolidated::consolidate;
 use crate::formats::*;
-use chrono::{DateTime, Utc};
-use the_foundation::{CryptexError, CryptexResult};
+use crate::pool::RenderPool;
+use crate::sections::{render_executive_summary, ReportSections};
+use chrono::Utc;
+use futures::Stream;
+use the_foundation::CryptexResult;
 use the_infiltrator::ScanReport;
 
 /// The Propagandist - Report Generation System
@@ -11,6 +15,8 @@ use the_infiltrator::ScanReport;
 pub struct ThePropagandist {
     /// Default configuration
     default_config: ReportConfig,
+    /// Blocking threads that rendering runs on
+    pool: RenderPool,
 }
 
 impl ThePropagandist {
@@ -22,9 +28,42 @@ impl ThePropagandist {
 
         Ok(Self {
             default_config: ReportConfig::default(),
+            pool: RenderPool::default(),
         })
     }
 
+    /// Run at most `concurrency` report renders at once; more requests queue
+    pub fn with_render_concurrency(mut self, concurrency: usize) -> Self {
+        self.pool = RenderPool::new(concurrency);
+        self
+    }
+
+    /// Write reports for this audience unless a request overrides it
+    pub fn with_audience(mut self, audience: ReportAudience) -> Self {
+        self.default_config.audience = audience;
+        self
+    }
+
+    /// Render only these sections unless a request overrides them
+    pub fn with_sections(mut self, sections: SectionToggles) -> Self {
+        self.default_config.sections = sections;
+        self
+    }
+
+    /// Use this theme for HTML reports unless a request overrides it
+    pub fn with_theme(mut self, theme: ReportTheme) -> CryptexResult<Self> {
+        theme.validate()?;
+        self.default_config.theme = theme;
+        Ok(self)
+    }
+
+    /// The default configuration with the given format applied
+    pub fn report_config(&self, format: ReportFormat) -> ReportConfig {
+        let mut config = self.default_config.clone();
+        config.format = format;
+        config
+    }
+
     /// Generate a report in the specified format
     ///
     /// Traditional name: `generate_report`
@@ -33,13 +72,14 @@ impl ThePropagandist {
         scan_report: &ScanReport,
         format: ReportFormat,
     ) -> CryptexResult<String> {
-        let mut config = self.default_config.clone();
-        config.format = format;
+        let config = self.report_config(format);
 
         self.generate_with_config(scan_report, &config).await
     }
 
     /// Generate a report with custom configuration
+    ///
+    /// Rendering runs on the render pool, off the async executor.
     pub async fn generate_with_config(
         &self,
         scan_report: &ScanReport,
@@ -51,272 +91,80 @@ impl ThePropagandist {
             scan_report.scan_id
         );
 
-        match config.format {
-            ReportFormat::Json => self.generate_json(scan_report, config).await,
-            ReportFormat::Html => self.generate_html(scan_report, config).await,
-            ReportFormat::Markdown => self.generate_markdown(scan_report, config).await,
-            ReportFormat::Text => self.generate_text(scan_report, config).await,
-        }
+        let mut scan_report = scan_report.clone();
+        config.select_findings(&mut scan_report);
+
+        let sections = ReportSections::new(scan_report, config.clone(), Utc::now())?;
+        self.pool.run(move || sections.collect()).await
     }
 
-    /// Generate executive summary
+    /// Generate a report as a stream of sections
     ///
-    /// Traditional name: `generate_summary`
-    pub async fn generate_executive_summary(
+    /// Traditional name: `generate_report_stream`
+    ///
+    /// Yields the same document as `generate_with_config`, but one section at
+    /// a time (header, statistics, findings per host or per result, footer),
+    /// so large reports never have to be held in memory in full. Invalid
+    /// configuration is reported before the first section is produced.
+    pub fn generate_stream(
         &self,
-        scan_report: &ScanReport,
-    ) -> CryptexResult<String> {
-        let mut summary = String::new();
-
-        summary.push_str("# CRYPTEX Vulnerability Assessment - Executive Summary\n\n");
-
-        // Overview
-        summary.push_str("## Scan Overview\n\n");
-        summary.push_str(&format!("**Scan ID**: {}\n", scan_report.scan_id));
-        summary.push_str(&format!("**Target**: {}\n", scan_report.target));
-
-        if let Some(duration) = scan_report.duration_seconds() {
-            summary.push_str(&format!("**Duration**: {} seconds\n", duration));
-        }
-
-        summary.push_str(&format!("**Total Hosts**: {}\n\n", scan_report.total_hosts));
-
-        // Key Findings
-        summary.push_str("## Key Findings\n\n");
-        summary.push_str(&format!(
-            "- **Total Vulnerabilities**: {}\n",
-            scan_report.total_vulnerabilities
-        ));
-        summary.push_str(&format!(
-            "- **Critical**: {} vulnerabilities requiring immediate action\n",
-            scan_report.critical_count
-        ));
-        summary.push_str(&format!(
-            "- **High**: {} vulnerabilities requiring prompt attention\n",
-            scan_report.high_count
-        ));
-        summary.push_str(&format!("- **Medium**: {}\n", scan_report.medium_count));
-        summary.push_str(&format!("- **Low**: {}\n\n", scan_report.low_count));
-
-        // KEV Alert
-        if scan_report.kev_count > 0 {
-            summary.push_str("### ⚠️ Known Exploited Vulnerabilities (KEV)\n\n");
-            summary.push_str(&format!(
-                "**{}** vulnerabilities detected are on the CISA KEV catalog, \
-                 indicating active exploitation in the wild. \
-                 These require immediate remediation.\n\n",
-                scan_report.kev_count
-            ));
-        }
-
-        // Risk Assessment
-        summary.push_str("## Risk Assessment\n\n");
-        let risk_level = if scan_report.critical_count > 0 || scan_report.kev_count > 0 {
-            "**CRITICAL** - Immediate action required"
-        } else if scan_report.high_count > 0 {
-            "**HIGH** - Prompt remediation needed"
-        } else if scan_report.medium_count > 0 {
-            "**MEDIUM** - Address in regular patch cycle"
-        } else {
-            "**LOW** - Minimal risk"
-        };
-
-        summary.push_str(&format!("Overall Risk Level: {}\n\n", risk_level));
-
-        // Top Vulnerabilities
-        if !scan_report.scan_results.is_empty() {
-            summary.push_str("## Top Vulnerabilities (By Risk)\n\n");
-            let top = scan_report.top_vulnerabilities(5);
-            for (i, result) in top.iter().enumerate() {
-                summary.push_str(&format!("{}. **{}** - {}\n", i + 1, result.cve_id, result.host));
-                if let Some(score) = result.cvss_base_score() {
-                    summary.push_str(&format!("   - CVSS Score: {:.1}\n", score));
-                }
-                if result.is_kev() {
-                    summary.push_str("   - ⚠️ Known Exploited Vulnerability\n");
-                }
-                summary.push('\n');
-            }
-        }
-
-        // Recommendations
-        summary.push_str("## Recommended Actions\n\n");
-        if scan_report.kev_count > 0 {
-            summary.push_str("1. **Immediate**: Patch all KEV vulnerabilities within 24-48 hours\n");
-        }
-        if scan_report.critical_count > 0 {
-            summary.push_str("2. **High Priority**: Address critical vulnerabilities within 7 days\n");
-        }
-        if scan_report.high_count > 0 {
-            summary.push_str("3. **Standard**: Remediate high severity issues within 30 days\n");
-        }
-        summary.push_str("4. **Ongoing**: Implement continuous vulnerability scanning\n");
-        summary.push_str("5. **Process**: Review and update security policies based on findings\n\n");
-
-        summary.push_str("---\n");
-        summary.push_str(&format!(
-            "Report generated: {}\n",
-            Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-        ));
-
-        Ok(summary)
-    }
+        mut scan_report: ScanReport,
+        config: &ReportConfig,
+    ) -> CryptexResult<impl Stream<Item = CryptexResult<String>> + Send + 'static> {
+        tracing::debug!(
+            "Streaming {:?} report for scan: {}",
+            config.format,
+            scan_report.scan_id
+        );
 
-    // Private format generators
+        config.select_findings(&mut scan_report);
 
-    /// Generate JSON format report
-    async fn generate_json(
-        &self,
-        scan_report: &ScanReport,
-        _config: &ReportConfig,
-    ) -> CryptexResult<String> {
-        serde_json::to_string_pretty(scan_report)
-            .map_err(|e| CryptexError::validation(format!("JSON serialization failed: {}", e)))
+        let sections = ReportSections::new(scan_report, config.clone(), Utc::now())?;
+        Ok(self.pool.stream(sections))
     }
 
-    /// Generate HTML format report
-    async fn generate_html(
+    /// Generate one report covering several scans
+    ///
+    /// Traditional name: `generate_merged_report`
+    ///
+    /// Findings shared between scans are counted once (see
+    /// [`consolidate`](crate::consolidate)); the report adds a per-scan
+    /// breakdown after the statistics.
+    pub async fn generate_consolidated(
         &self,
-        scan_report: &ScanReport,
-        config: &ReportConfig,
+        reports: &[ScanReport],
+        format: ReportFormat,
     ) -> CryptexResult<String> {
-        let mut html = String::new();
-
-        // HTML header
-        html.push_str("<!DOCTYPE html>\n");
-        html.push_str("<html lang=\"en\">\n<head>\n");
-        html.push_str("  <meta charset=\"UTF-8\">\n");
-        html.push_str("  <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n");
-        html.push_str("  <title>CRYPTEX Vulnerability Report</title>\n");
-        html.push_str("  <style>\n");
-        html.push_str("    body { font-family: sans-serif; margin: 20px; background: #f5f5f5; }\n");
-        html.push_str("    .container { max-width: 1200px; margin: 0 auto; background: white; padding: 20px; border-radius: 8px; }\n");
-        html.push_str("    h1 { color: #333; border-bottom: 3px solid #e74c3c; }\n");
-        html.push_str("    .critical { color: #e74c3c; font-weight: bold; }\n");
-        html.push_str("    .high { color: #f39c12; font-weight: bold; }\n");
-        html.push_str("    .medium { color: #3498db; }\n");
-        html.push_str("    .kev-badge { background: #e74c3c; color: white; padding: 2px 8px; border-radius: 4px; font-size: 0.8em; }\n");
-        html.push_str("    table { width: 100%; border-collapse: collapse; margin: 20px 0; }\n");
-        html.push_str("    th, td { padding: 12px; text-align: left; border-bottom: 1px solid #ddd; }\n");
-        html.push_str("    th { background: #34495e; color: white; }\n");
-        html.push_str("  </style>\n</head>\n<body>\n");
-        html.push_str("<div class=\"container\">\n");
-
-        // Title
-        html.push_str("  <h1>CRYPTEX Vulnerability Assessment Report</h1>\n");
-
-        // Overview
-        html.push_str("  <h2>Scan Overview</h2>\n");
-        html.push_str("  <table>\n");
-        html.push_str(&format!("    <tr><td><strong>Scan ID</strong></td><td>{}</td></tr>\n", scan_report.scan_id));
-        html.push_str(&format!("    <tr><td><strong>Target</strong></td><td>{}</td></tr>\n", scan_report.target));
-        html.push_str(&format!("    <tr><td><strong>Total Hosts</strong></td><td>{}</td></tr>\n", scan_report.total_hosts));
-        if let Some(duration) = scan_report.duration_seconds() {
-            html.push_str(&format!("    <tr><td><strong>Duration</strong></td><td>{} seconds</td></tr>\n", duration));
-        }
-        html.push_str("  </table>\n");
-
-        // Statistics
-        html.push_str("  <h2>Vulnerability Statistics</h2>\n");
-        html.push_str("  <table>\n");
-        html.push_str(&format!("    <tr><td><strong>Total Vulnerabilities</strong></td><td>{}</td></tr>\n", scan_report.total_vulnerabilities));
-        html.push_str(&format!("    <tr><td><strong>Critical</strong></td><td class=\"critical\">{}</td></tr>\n", scan_report.critical_count));
-        html.push_str(&format!("    <tr><td><strong>High</strong></td><td class=\"high\">{}</td></tr>\n", scan_report.high_count));
-        html.push_str(&format!("    <tr><td><strong>Medium</strong></td><td class=\"medium\">{}</td></tr>\n", scan_report.medium_count));
-        html.push_str(&format!("    <tr><td><strong>Low</strong></td><td>{}</td></tr>\n", scan_report.low_count));
-        if scan_report.kev_count > 0 {
-            html.push_str(&format!("    <tr><td><strong>KEV Vulnerabilities</strong></td><td><span class=\"kev-badge\">{} KEV</span></td></tr>\n", scan_report.kev_count));
-        }
-        html.push_str("  </table>\n");
-
-        // Top vulnerabilities (if executive)
-        if config.audience == ReportAudience::Executive {
-            let top = scan_report.top_vulnerabilities(10);
-            if !top.is_empty() {
-                html.push_str("  <h2>Top Vulnerabilities by Risk</h2>\n");
-                html.push_str("  <table>\n");
-                html.push_str("    <tr><th>CVE</th><th>Host</th><th>Port</th><th>CVSS</th><th>Status</th></tr>\n");
-                for result in top {
-                    html.push_str("    <tr>");
-                    html.push_str(&format!("<td>{}</td>", result.cve_id));
-                    html.push_str(&format!("<td>{}</td>", result.host));
-                    html.push_str(&format!("<td>{}</td>", result.port));
-                    if let Some(score) = result.cvss_base_score() {
-                        let class = if score >= 9.0 { "critical" } else if score >= 7.0 { "high" } else { "medium" };
-                        html.push_str(&format!("<td class=\"{}\">{:.1}</td>", class, score));
-                    } else {
-                        html.push_str("<td>-</td>");
-                    }
-                    if result.is_kev() {
-                        html.push_str("<td><span class=\"kev-badge\">KEV</span></td>");
-                    } else {
-                        html.push_str("<td>-</td>");
-                    }
-                    html.push_str("</tr>\n");
-                }
-                html.push_str("  </table>\n");
-            }
-        }
-
-        // Footer
-        html.push_str(&format!("  <p style=\"margin-top: 40px; color: #7f8c8d; font-size: 0.9em;\">Report generated: {}</p>\n",
-                              Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
-        html.push_str("</div>\n</body>\n</html>");
+        let (mut merged, breakdown) = consolidate(reports)?;
+        let config = self.report_config(format);
+        config.select_findings(&mut merged);
 
-        Ok(html)
-    }
+        tracing::debug!(
+            "Generating {:?} consolidated report across {} scans",
+            config.format,
+            reports.len()
+        );
 
-    /// Generate Markdown format report
-    async fn generate_markdown(
-        &self,
-        scan_report: &ScanReport,
-        _config: &ReportConfig,
-    ) -> CryptexResult<String> {
-        // Similar to executive summary but in pure markdown
-        self.generate_executive_summary(scan_report).await
+        let sections = ReportSections::new(merged, config, Utc::now())?.with_breakdown(breakdown);
+        self.pool.run(move || sections.collect()).await
     }
 
-    /// Generate plain text format report
-    async fn generate_text(
+    /// Generate executive summary
+    ///
+    /// Traditional name: `generate_summary`
+    pub async fn generate_executive_summary(
         &self,
         scan_report: &ScanReport,
-        _config: &ReportConfig,
     ) -> CryptexResult<String> {
-        let mut text = String::new();
-
-        text.push_str("CRYPTEX VULNERABILITY ASSESSMENT REPORT\n");
-        text.push_str("=======================================\n\n");
-
-        text.push_str(&format!("Scan ID:      {}\n", scan_report.scan_id));
-        text.push_str(&format!("Target:       {}\n", scan_report.target));
-        text.push_str(&format!("Total Hosts:  {}\n", scan_report.total_hosts));
-        if let Some(duration) = scan_report.duration_seconds() {
-            text.push_str(&format!("Duration:     {} seconds\n", duration));
-        }
-        text.push_str("\n");
-
-        text.push_str("VULNERABILITY SUMMARY\n");
-        text.push_str("---------------------\n");
-        text.push_str(&format!("Total:     {}\n", scan_report.total_vulnerabilities));
-        text.push_str(&format!("Critical:  {}\n", scan_report.critical_count));
-        text.push_str(&format!("High:      {}\n", scan_report.high_count));
-        text.push_str(&format!("Medium:    {}\n", scan_report.medium_count));
-        text.push_str(&format!("Low:       {}\n", scan_report.low_count));
-        if scan_report.kev_count > 0 {
-            text.push_str(&format!("KEV:       {} (Known Exploited Vulnerabilities)\n", scan_report.kev_count));
-        }
-        text.push_str("\n");
-
-        text.push_str(&format!("Report generated: {}\n",
-                              Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
-
-        Ok(text)
+        Ok(render_executive_summary(scan_report, &Utc::now()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sections::CSV_HEADERS;
+    use futures::StreamExt;
     use the_infiltrator::ScanReport;
 
     #[tokio::test]
@@ -339,6 +187,37 @@ mod tests {
         assert!(json.contains("192.168.1.0/24"));
     }
 
+    #[tokio::test]
+    async fn test_concurrent_reports_through_the_render_pool() {
+        let propagandist = std::sync::Arc::new(
+            ThePropagandist::the_awakening()
+                .await
+                .unwrap()
+                .with_render_concurrency(1),
+        );
+        let report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let renders = (0..4).map(|_| {
+            let (propagandist, report) = (propagandist.clone(), report.clone());
+            tokio::spawn(async move {
+                propagandist.generate_report(&report, ReportFormat::Html).await
+            })
+        });
+        for html in futures::future::join_all(renders).await {
+            let html = html.unwrap().unwrap();
+            assert!(html.contains("<html"));
+            assert!(html.contains("scan-001"));
+        }
+
+        let streamed: Vec<String> = propagandist
+            .generate_stream(report, &propagandist.report_config(ReportFormat::Csv))
+            .unwrap()
+            .map(|section| section.unwrap())
+            .collect()
+            .await;
+        assert!(streamed[0].starts_with(CSV_HEADERS[0]));
+    }
+
     #[tokio::test]
     async fn test_generate_executive_summary() {
         let propagandist = ThePropagandist::the_awakening().await.unwrap();
@@ -365,6 +244,93 @@ mod tests {
 
         assert!(html.contains("<!DOCTYPE html>"));
         assert!(html.contains("scan-001"));
+        assert!(html.contains("border-bottom: 3px solid #e74c3c"));
+        assert!(!html.contains("class=\"brand\""));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_sections_are_omitted() {
+        let propagandist = ThePropagandist::the_awakening()
+            .await
+            .unwrap()
+            .with_sections(SectionToggles::parse("overview,hosts").unwrap());
+        let report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let html = propagandist
+            .generate_report(&report, ReportFormat::Html)
+            .await
+            .unwrap();
+        assert!(html.contains("Scan Overview"));
+        assert!(!html.contains("Vulnerability Statistics"));
+
+        let markdown = propagandist
+            .generate_report(&report, ReportFormat::Markdown)
+            .await
+            .unwrap();
+        assert!(markdown.contains("## Scan Overview"));
+        assert!(!markdown.contains("## Key Findings"));
+        assert!(!markdown.contains("## Recommended Actions"));
+    }
+
+    #[tokio::test]
+    async fn test_min_confidence_excludes_uncertain_findings() {
+        use the_infiltrator::{ConfidenceLevel, ScanResult};
+
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+        for (cve_id, confidence) in [
+            ("CVE-2024-0001", ConfidenceLevel::High),
+            ("CVE-2024-0002", ConfidenceLevel::Low),
+        ] {
+            let result = ScanResult::new(
+                cve_id.to_string(),
+                "192.168.1.10".to_string(),
+                443,
+                "1.3.6.1.4.1.25623".to_string(),
+                cve_id.to_string(),
+            );
+            report.add_result(result.with_confidence(confidence));
+        }
+
+        let generate = |minimum| {
+            let config = ReportConfig::default().min_confidence(minimum);
+            let propagandist = &propagandist;
+            let report = &report;
+            async move {
+                let json = propagandist.generate_with_config(report, &config).await.unwrap();
+                serde_json::from_str::<serde_json::Value>(&json).unwrap()
+            }
+        };
+
+        let high_only = generate(ConfidenceLevel::High).await;
+        assert_eq!(high_only["total_vulnerabilities"], 1);
+        assert_eq!(high_only["scan_results"][0]["cve_id"], "CVE-2024-0001");
+
+        let everything = generate(ConfidenceLevel::Low).await;
+        assert_eq!(everything["total_vulnerabilities"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_generate_themed_html_report() {
+        let propagandist = ThePropagandist::the_awakening()
+            .await
+            .unwrap()
+            .with_theme(ReportTheme {
+                company_name: Some("Acme <Security>".to_string()),
+                logo_url: Some("https://acme.example/logo.png".to_string()),
+                accent_color: "#1abc9c".to_string(),
+            })
+            .unwrap();
+        let report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let html = propagandist
+            .generate_report(&report, ReportFormat::Html)
+            .await
+            .unwrap();
+
+        assert!(html.contains("border-bottom: 3px solid #1abc9c"));
+        assert!(html.contains("<strong>Acme &lt;Security&gt;</strong>"));
+        assert!(html.contains("<img src=\"https://acme.example/logo.png\""));
     }
 
     #[tokio::test]
@@ -380,4 +346,443 @@ mod tests {
         assert!(text.contains("CRYPTEX VULNERABILITY ASSESSMENT REPORT"));
         assert!(text.contains("scan-001"));
     }
+
+    #[tokio::test]
+    async fn test_unscored_findings_are_labelled() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let mut result = the_infiltrator::ScanResult::new(
+            "CVE-2099-0001".to_string(),
+            "192.168.1.10".to_string(),
+            443,
+            "1.3.6.1.4.1.25623".to_string(),
+            "Reserved".to_string(),
+        );
+        let mut score = the_assessor::VulnerabilityScore::new("CVE-2099-0001".to_string());
+        score.data_completeness = the_assessor::DataCompleteness::Reserved;
+        result.vulnerability_score = Some(score);
+        report.add_result(result);
+
+        let text = propagandist
+            .generate_report(&report, ReportFormat::Text)
+            .await
+            .unwrap();
+        assert!(text.contains("CVSS unscored (reserved)"), "{}", text);
+        assert!(!text.contains("CVSS 0.0"));
+
+        let csv = propagandist
+            .generate_report(&report, ReportFormat::Csv)
+            .await
+            .unwrap();
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let row = reader.records().next().unwrap().unwrap();
+        assert_eq!(&row[4], "");
+        assert_eq!(&row[5], "Unscored (reserved)");
+    }
+
+    #[tokio::test]
+    async fn test_generate_csv_report() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let mut result = the_infiltrator::ScanResult::new(
+            "CVE-2021-44228".to_string(),
+            "192.168.1.10".to_string(),
+            8080,
+            "1.3.6.1.4.1.25623".to_string(),
+            "Log4Shell".to_string(),
+        );
+        result.service = Some("http".to_string());
+        result.remediation_guidance = Some("Upgrade log4j, then \"restart\",\nverify".to_string());
+        report.add_result(result);
+
+        let csv = propagandist
+            .generate_report(&report, ReportFormat::Csv)
+            .await
+            .unwrap();
+
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        assert_eq!(reader.headers().unwrap(), &csv::StringRecord::from(CSV_HEADERS.to_vec()));
+
+        let rows: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].len(), CSV_HEADERS.len());
+        assert_eq!(&rows[0][0], "CVE-2021-44228");
+        assert_eq!(&rows[0][2], "8080");
+        assert_eq!(&rows[0][3], "http");
+        assert_eq!(&rows[0][6], "false");
+        assert_eq!(&rows[0][7], "Upgrade log4j, then \"restart\",\nverify");
+    }
+
+    #[tokio::test]
+    async fn test_findings_by_service_section() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+        for host in ["192.168.1.10", "192.168.1.11"] {
+            let mut result = the_infiltrator::ScanResult::new(
+                "CVE-2021-44228".to_string(),
+                host.to_string(),
+                443,
+                "1.3.6.1.4.1.25623".to_string(),
+                "Log4Shell".to_string(),
+            );
+            result.infer_service(None);
+            report.add_result(result);
+        }
+
+        let html = propagandist
+            .generate_report(&report, ReportFormat::Html)
+            .await
+            .unwrap();
+        assert!(html.contains("<h2>Findings by Service</h2>"));
+        assert!(html.contains("<tr><td>https</td><td>2</td><td>2</td></tr>"));
+
+        let markdown = propagandist
+            .generate_report(&report, ReportFormat::Markdown)
+            .await
+            .unwrap();
+        assert!(markdown.contains("| https | 2 | 2 |"));
+    }
+
+    #[tokio::test]
+    async fn test_findings_by_weakness_section() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+        for (cve_id, cwe_id) in [
+            ("CVE-2021-44228", "CWE-502"),
+            ("CVE-2017-9805", "CWE-502"),
+            ("CVE-2020-11022", "CWE-79"),
+        ] {
+            let mut result = the_infiltrator::ScanResult::new(
+                cve_id.to_string(),
+                "192.168.1.10".to_string(),
+                443,
+                "1.3.6.1.4.1.25623".to_string(),
+                "Test vulnerability".to_string(),
+            );
+            let mut score = the_assessor::VulnerabilityScore::new(cve_id.to_string());
+            score.cwe_ids = vec![cwe_id.to_string()];
+            result.vulnerability_score = Some(score);
+            report.add_result(result);
+        }
+
+        let html = propagandist
+            .generate_report(&report, ReportFormat::Html)
+            .await
+            .unwrap();
+        assert!(html.contains("<h2>Findings by Weakness</h2>"));
+        assert!(html.contains(
+            "<tr><td>CWE-502</td><td>Deserialization of Untrusted Data</td><td>2</td>\
+             <td>CVE-2021-44228, CVE-2017-9805</td></tr>"
+        ));
+
+        let markdown = propagandist
+            .generate_report(&report, ReportFormat::Markdown)
+            .await
+            .unwrap();
+        assert!(markdown.contains("| CWE-79 | Cross-site Scripting | 1 | CVE-2020-11022 |"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_empty_csv_report() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let csv = propagandist
+            .generate_report(&report, ReportFormat::Csv)
+            .await
+            .unwrap();
+
+        assert_eq!(csv, format!("{}\n", CSV_HEADERS.join(",")));
+    }
+
+    fn scored_result(
+        cve_id: &str,
+        vector: &str,
+        epss_percentile: Option<f64>,
+    ) -> the_infiltrator::ScanResult {
+        let mut result = the_infiltrator::ScanResult::new(
+            cve_id.to_string(),
+            "192.168.1.10".to_string(),
+            443,
+            "1.3.6.1.4.1.25623".to_string(),
+            cve_id.to_string(),
+        );
+
+        let mut score = the_assessor::VulnerabilityScore::new(cve_id.to_string());
+        score.cvss_v3 = Some(the_assessor::cvss_v3_from_vector(vector).unwrap());
+        score.epss = epss_percentile.map(|percentile| the_assessor::EpssInfo {
+            score: 0.1,
+            percentile,
+            date: "2024-01-01".to_string(),
+        });
+        result.vulnerability_score = Some(score);
+        result
+    }
+
+    #[tokio::test]
+    async fn test_executive_html_risk_matrix() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let total = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H";
+        let partial = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:L/I:N/A:N";
+        report.add_result(scored_result("CVE-2024-0001", total, Some(0.97)));
+        report.add_result(scored_result("CVE-2024-0002", total, Some(0.95)));
+        report.add_result(scored_result("CVE-2024-0003", partial, Some(0.2)));
+        report.add_result(scored_result("CVE-2024-0004", total, None));
+
+        let config = ReportConfig {
+            format: ReportFormat::Html,
+            ..ReportConfig::executive()
+        };
+        let html = propagandist
+            .generate_with_config(&report, &config)
+            .await
+            .unwrap();
+
+        assert!(html.contains("<div class=\"risk-matrix\">"));
+        assert!(html.contains(
+            "<div class=\"risk-cell risk-critical\" data-likelihood=\"high\" data-impact=\"high\">2</div>"
+        ));
+        assert!(html.contains(
+            "<div class=\"risk-cell risk-medium\" data-likelihood=\"low\" data-impact=\"low\">1</div>"
+        ));
+        assert!(html.contains(
+            "<div class=\"risk-cell risk-critical\" data-likelihood=\"unknown\" data-impact=\"high\">1</div>"
+        ));
+        assert!(html.contains(
+            "<div class=\"risk-cell\" data-likelihood=\"medium\" data-impact=\"medium\">0</div>"
+        ));
+
+        // Technical reports keep the plain layout
+        let html = propagandist
+            .generate_report(&report, ReportFormat::Html)
+            .await
+            .unwrap();
+        assert!(!html.contains("risk-matrix"));
+    }
+
+    #[tokio::test]
+    async fn test_max_findings_truncates_details_not_totals() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let critical = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H";
+        let low = "CVSS:3.1/AV:N/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N";
+        for i in 0..20 {
+            // Every fourth finding is critical; those are the ones kept
+            let vector = if i % 4 == 0 { critical } else { low };
+            let mut result = scored_result(&format!("CVE-2024-{:04}", i), vector, None);
+            result.host = format!("192.168.1.{}", i % 3);
+            report.add_result(result);
+        }
+
+        let config = ReportConfig::technical().max_findings(5);
+        let notice = "Showing top 5 of 20 findings; export full results via API.";
+
+        let html = propagandist
+            .generate_with_config(&report, &ReportConfig { format: ReportFormat::Html, ..config.clone() })
+            .await
+            .unwrap();
+        assert_eq!(html.matches("<tr><td>CVE-2024-").count(), 5);
+        for i in [0, 4, 8, 12, 16] {
+            assert!(html.contains(&format!("<tr><td>CVE-2024-{:04}</td>", i)));
+        }
+        assert!(html.contains("<tr><td><strong>Total Vulnerabilities</strong></td><td>20</td></tr>"));
+        assert!(html.contains(notice));
+
+        let text = propagandist
+            .generate_with_config(&report, &ReportConfig { format: ReportFormat::Text, ..config.clone() })
+            .await
+            .unwrap();
+        assert_eq!(text.lines().filter(|l| l.starts_with("  CVE-2024-")).count(), 5);
+        assert!(text.contains("Total:     20\n"));
+        assert!(text.contains(notice));
+
+        let markdown = propagandist
+            .generate_with_config(&report, &ReportConfig { format: ReportFormat::Markdown, ..config.clone() })
+            .await
+            .unwrap();
+        assert!(markdown.contains("- **Total Vulnerabilities**: 20\n"));
+        assert!(markdown.contains(notice));
+
+        // Exports carry every finding, and an uncapped report has no notice
+        let json = propagandist
+            .generate_with_config(&report, &ReportConfig { format: ReportFormat::Json, ..config })
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["scan_results"].as_array().unwrap().len(), 20);
+        let html = propagandist.generate_report(&report, ReportFormat::Html).await.unwrap();
+        assert_eq!(html.matches("<tr><td>CVE-2024-").count(), 20);
+        assert!(!html.contains("truncation-notice"));
+    }
+
+    #[tokio::test]
+    async fn test_executive_report_at_cap_has_no_notice() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+        let vector = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H";
+        for i in 0..10 {
+            report.add_result(scored_result(&format!("CVE-2024-{:04}", i), vector, None));
+        }
+
+        // Exactly as many findings as the default cap of 10: nothing is cut
+        let config = ReportConfig { format: ReportFormat::Html, ..ReportConfig::executive() };
+        let html = propagandist.generate_with_config(&report, &config).await.unwrap();
+        assert!(!html.contains("truncation-notice"));
+
+        report.add_result(scored_result("CVE-2024-0010", vector, None));
+        let html = propagandist.generate_with_config(&report, &config).await.unwrap();
+        assert!(html.contains("Showing top 10 of 11 findings"));
+    }
+
+    /// Drop the generation timestamp, which differs between two runs
+    fn without_timestamp(report: &str) -> String {
+        report
+            .lines()
+            .filter(|line| !line.contains("Report generated:"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[tokio::test]
+    async fn test_streamed_report_matches_buffered() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let mut report = ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+
+        let total = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H";
+        report.add_result(scored_result("CVE-2024-0001", total, Some(0.97)));
+        let mut other_host = scored_result("CVE-2024-0002", total, None);
+        other_host.host = "192.168.1.20".to_string();
+        report.add_result(other_host);
+        report.add_result(scored_result("CVE-2024-0003", total, Some(0.2)));
+        report.complete();
+
+        let formats = [
+            ReportFormat::Json,
+            ReportFormat::Html,
+            ReportFormat::Markdown,
+            ReportFormat::Text,
+            ReportFormat::Csv,
+        ];
+        for format in formats {
+            let config = propagandist.report_config(format);
+            let buffered = propagandist
+                .generate_with_config(&report, &config)
+                .await
+                .unwrap();
+
+            let chunks: Vec<String> = propagandist
+                .generate_stream(report.clone(), &config)
+                .unwrap()
+                .map(|chunk| chunk.unwrap())
+                .collect()
+                .await;
+            if format != ReportFormat::Markdown {
+                assert!(chunks.len() > 2, "{:?} report was not split into sections", format);
+            }
+
+            assert_eq!(without_timestamp(&chunks.concat()), without_timestamp(&buffered));
+        }
+
+        // The streamed JSON is exactly what serde would produce in one go
+        let config = propagandist.report_config(ReportFormat::Json);
+        let streamed: Vec<String> = propagandist
+            .generate_stream(report.clone(), &config)
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        assert_eq!(streamed.concat(), serde_json::to_string_pretty(&report).unwrap());
+
+        let empty = ScanReport::new("scan-002".to_string(), "10.0.0.0/8".to_string());
+        let streamed: Vec<String> = propagandist
+            .generate_stream(empty.clone(), &config)
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        assert_eq!(streamed.concat(), serde_json::to_string_pretty(&empty).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_json_report_is_byte_stable() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let report_with_summary = |keys: &[&str]| {
+            let mut report =
+                ScanReport::new("scan-001".to_string(), "192.168.1.0/24".to_string());
+            let mut severity = serde_json::Map::new();
+            for key in keys {
+                severity.insert(key.to_string(), serde_json::json!(key.len()));
+            }
+            let mut summary = serde_json::Map::new();
+            summary.insert("severity".to_string(), serde_json::Value::Object(severity));
+            summary.insert("scan_count".to_string(), serde_json::json!(2));
+            report.summary = Some(serde_json::Value::Object(summary));
+            report
+        };
+
+        let report = report_with_summary(&["critical", "high", "medium"]);
+        let first = propagandist.generate_report(&report, ReportFormat::Json).await.unwrap();
+        let second = propagandist.generate_report(&report, ReportFormat::Json).await.unwrap();
+        assert_eq!(first, second);
+
+        // Summary keys come out sorted whatever order they were inserted in
+        let mut reordered = report_with_summary(&["medium", "critical", "high"]);
+        reordered.start_time = report.start_time;
+        let reordered = propagandist.generate_report(&reordered, ReportFormat::Json).await.unwrap();
+        assert_eq!(first, reordered);
+        assert!(first.find("\"scan_count\"").unwrap() < first.find("\"severity\"").unwrap());
+        assert!(first.find("\"critical\"").unwrap() < first.find("\"medium\"").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_generate_consolidated_report() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let log4shell = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H";
+
+        let mut first = ScanReport::new("scan-a".to_string(), "10.0.0.0/24".to_string());
+        first.add_result(scored_result("CVE-2021-44228", log4shell, None));
+        let mut shared = scored_result("CVE-2024-0001", log4shell, None);
+        shared.host = "10.0.0.9".to_string();
+        first.add_result(shared.clone());
+
+        let mut second = ScanReport::new("scan-b".to_string(), "10.0.1.0/24".to_string());
+        second.add_result(shared);
+
+        let reports = [first, second];
+        let json = propagandist
+            .generate_consolidated(&reports, ReportFormat::Json)
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["total_vulnerabilities"], 2);
+        assert_eq!(value["critical_count"], 2);
+        assert_eq!(value["summary"]["duplicates_removed"], 1);
+        assert_eq!(value["summary"]["consolidated_from"][1]["scan_id"], "scan-b");
+
+        let text = propagandist
+            .generate_consolidated(&reports, ReportFormat::Text)
+            .await
+            .unwrap();
+        assert!(text.contains("PER-SCAN BREAKDOWN"));
+        assert!(text.contains("scan-b (10.0.1.0/24)"));
+
+        let markdown = propagandist
+            .generate_consolidated(&reports, ReportFormat::Markdown)
+            .await
+            .unwrap();
+        let breakdown = markdown.find("## Per-Scan Breakdown").unwrap();
+        assert!(breakdown < markdown.find("Report generated").unwrap());
+
+        // Single-scan reports have no breakdown section
+        let html = propagandist
+            .generate_report(&reports[0], ReportFormat::Html)
+            .await
+            .unwrap();
+        assert!(!html.contains("Per-Scan Breakdown"));
+    }
 }
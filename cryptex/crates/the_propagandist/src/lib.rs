@@ -7,7 +7,9 @@
 //!
 //! ## Features
 //!
-//! - **Multi-Format Support**: JSON, HTML, Markdown, Executive Summary
+//! - **Multi-Format Support**: JSON, HTML, Markdown, CSV, Executive Summary
+//! - **Streaming**: Reports can be produced section by section for large scans
+//! - **Signing**: Detached Ed25519 signatures make delivered reports tamper-evident
 //! - **Audience-Specific**: Technical, executive, compliance-focused reports
 //! - **Risk Prioritization**: Automatic vulnerability ranking
 //! - **Actionable Insights**: Clear remediation recommendations
@@ -43,11 +45,21 @@
 //! }
 //! ```
 
+pub mod consolidated;
 pub mod formats;
 pub mod generator;
+pub mod pool;
+mod risk_matrix;
+mod sections;
+pub mod signing;
 
+pub use consolidated::{consolidate, ScanBreakdown};
 pub use formats::*;
 pub use generator::ThePropagandist;
+pub use pool::{
+    render_concurrency_from_env, RenderPool, DEFAULT_RENDER_CONCURRENCY, REPORT_CONCURRENCY_ENV,
+};
+pub use signing::{decode_signing_key, SigningKey, VerifyingKey};
 
 // Re-export from foundation
 pub use the_foundation::CryptexResult;
@@ -0,0 +1,243 @@
+//! Render Pool - Report rendering off the async runtime
+//!
+//! Traditional name: `BlockingPool`
+//!
+//! Rendering a large HTML report is pure CPU work. Done inline it holds an
+//! executor thread for as long as it takes, and a handful of concurrent report
+//! requests can stall every other handler, `/health` included. The pool runs
+//! rendering on tokio's blocking threads instead, and a semaphore caps how
+//! many renders run at once: requests beyond the cap wait for a permit rather
+//! than each claiming a blocking thread of its own.
+
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
+use tokio::sync::{mpsc, OwnedSemaphorePermit, Semaphore};
+
+use the_foundation::{CryptexError, CryptexResult};
+
+/// Renders allowed to run at once unless configured otherwise
+pub const DEFAULT_RENDER_CONCURRENCY: usize = 4;
+
+/// Environment variable overriding the render concurrency
+///
+/// Takes precedence over `commune.report_concurrency` in the charter.
+pub const REPORT_CONCURRENCY_ENV: &str = "CRYPTEX_COMMUNE__REPORT_CONCURRENCY";
+
+/// Sections a streamed render may run ahead of the client
+const STREAM_BUFFER: usize = 8;
+
+/// Render concurrency configured through the environment
+///
+/// `None` when unset; unparseable values are ignored with a warning.
+pub fn render_concurrency_from_env() -> Option<usize> {
+    let value = std::env::var(REPORT_CONCURRENCY_ENV).ok()?;
+
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => Some(n),
+        _ => {
+            tracing::warn!(
+                "Ignoring {}={}: not a positive number",
+                REPORT_CONCURRENCY_ENV,
+                value
+            );
+            None
+        }
+    }
+}
+
+/// Bounded pool of blocking threads for report rendering
+#[derive(Debug, Clone)]
+pub struct RenderPool {
+    permits: Arc<Semaphore>,
+    concurrency: usize,
+}
+
+impl Default for RenderPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_RENDER_CONCURRENCY)
+    }
+}
+
+impl RenderPool {
+    /// Create a pool running at most `concurrency` renders at once
+    ///
+    /// A concurrency of zero would queue every render forever, so it is
+    /// treated as one.
+    pub fn new(concurrency: usize) -> Self {
+        let concurrency = concurrency.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(concurrency)),
+            concurrency,
+        }
+    }
+
+    /// Maximum renders running at once
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    async fn permit(&self) -> CryptexResult<OwnedSemaphorePermit> {
+        self.permits
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| CryptexError::Unknown(format!("Render pool closed: {}", e)))
+    }
+
+    /// Run `render` on a blocking thread once a permit is free
+    pub async fn run<F>(&self, render: F) -> CryptexResult<String>
+    where
+        F: FnOnce() -> CryptexResult<String> + Send + 'static,
+    {
+        let permit = self.permit().await?;
+
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            render()
+        })
+        .await
+        .map_err(|e| CryptexError::Unknown(format!("Report rendering failed: {}", e)))?
+    }
+
+    /// Drive a section iterator on a blocking thread, yielding its sections
+    ///
+    /// Nothing runs until the stream is first polled. The permit is held until
+    /// the last section is produced or the stream is dropped, and the renderer
+    /// stays at most `STREAM_BUFFER` sections ahead of the consumer.
+    pub fn stream<I>(
+        &self,
+        sections: I,
+    ) -> impl Stream<Item = CryptexResult<String>> + Send + 'static
+    where
+        I: IntoIterator<Item = CryptexResult<String>> + Send + 'static,
+        I::IntoIter: Send,
+    {
+        let pool = self.clone();
+
+        futures::stream::once(async move {
+            let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+
+            match pool.permit().await {
+                Ok(permit) => {
+                    tokio::task::spawn_blocking(move || {
+                        for section in sections {
+                            // A closed channel means the client went away
+                            if tx.blocking_send(section).is_err() {
+                                break;
+                            }
+                        }
+                        // Free the permit before the consumer sees the end
+                        drop(permit);
+                    });
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e)).await;
+                }
+            }
+
+            futures::stream::unfold(rx, |mut rx| async move {
+                rx.recv().await.map(|section| (section, rx))
+            })
+        })
+        .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_renders_beyond_the_cap_queue() {
+        let pool = RenderPool::new(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let started = Arc::new(AtomicUsize::new(0));
+        let release = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        // Every render blocks until released, so the in-flight count is exact
+        let renders: Vec<_> = (0..8)
+            .map(|i| {
+                let pool = pool.clone();
+                let (running, peak) = (running.clone(), peak.clone());
+                let (started, release) = (started.clone(), release.clone());
+                tokio::spawn(async move {
+                    pool.run(move || {
+                        started.fetch_add(1, Ordering::SeqCst);
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        while !release.load(Ordering::SeqCst) {
+                            std::thread::sleep(Duration::from_millis(1));
+                        }
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(format!("report {}", i))
+                    })
+                    .await
+                })
+            })
+            .collect();
+
+        for _ in 0..500 {
+            if started.load(Ordering::SeqCst) == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        // The other six wait for a permit instead of taking a thread each
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(started.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.permits.available_permits(), 0);
+
+        release.store(true, Ordering::SeqCst);
+        let outputs = futures::future::join_all(renders).await;
+        for (i, output) in outputs.into_iter().enumerate() {
+            assert_eq!(output.unwrap().unwrap(), format!("report {}", i));
+        }
+        assert_eq!(started.load(Ordering::SeqCst), 8);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_render_does_not_hold_the_executor() {
+        // A single-threaded runtime: an inline render would stall everything
+        let pool = RenderPool::new(1);
+        let render = tokio::spawn({
+            let pool = pool.clone();
+            async move {
+                pool.run(|| {
+                    std::thread::sleep(Duration::from_millis(300));
+                    Ok("done".to_string())
+                })
+                .await
+            }
+        });
+        tokio::task::yield_now().await;
+
+        let started = std::time::Instant::now();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(started.elapsed() < Duration::from_millis(250));
+
+        assert_eq!(render.await.unwrap().unwrap(), "done");
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_every_section_in_order() {
+        let pool = RenderPool::new(1);
+        let sections: Vec<CryptexResult<String>> =
+            (0..20).map(|i| Ok(format!("section {}\n", i))).collect();
+
+        let collected: Vec<String> = pool
+            .stream(sections)
+            .map(|section| section.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(collected.len(), 20);
+        assert_eq!(collected[0], "section 0\n");
+        assert_eq!(collected[19], "section 19\n");
+        // The permit is released once the stream is drained
+        assert_eq!(pool.permits.available_permits(), 1);
+    }
+}
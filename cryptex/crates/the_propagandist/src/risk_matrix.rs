@@ -0,0 +1,115 @@
+//! Risk Matrix - Likelihood × impact grid for executive HTML reports
+//!
+//! Impact comes from the CVSS v3 impact sub-score, likelihood from the EPSS
+//! percentile. Findings without EPSS data land in an "unknown likelihood" row;
+//! findings without a CVSS vector have no impact and are left off the grid.
+
+use the_assessor::{calculate_cvss_v3_impact_subscore, CvssSeverity, VulnerabilityScore};
+use the_infiltrator::ScanReport;
+
+/// Likelihood rows, top to bottom: (key, label)
+const LIKELIHOOD_ROWS: [(&str, &str); 4] = [
+    ("high", "High"),
+    ("medium", "Medium"),
+    ("low", "Low"),
+    ("unknown", "Unknown"),
+];
+
+/// Impact columns, left to right: (key, label)
+const IMPACT_COLUMNS: [(&str, &str); 3] = [("low", "Low"), ("medium", "Medium"), ("high", "High")];
+
+/// CSS for the matrix, appended to the report stylesheet
+pub(crate) const RISK_MATRIX_CSS: &str = "    .risk-matrix { display: grid; grid-template-columns: 140px repeat(3, 1fr); gap: 4px; margin: 20px 0; }
+    .risk-matrix > div { padding: 12px; text-align: center; border-radius: 4px; }
+    .risk-label { font-weight: bold; background: #ecf0f1; }
+    .risk-cell { background: #f8f9f9; color: #7f8c8d; }
+    .risk-cell.risk-critical { background: #e74c3c; color: white; }
+    .risk-cell.risk-high { background: #f39c12; color: white; }
+    .risk-cell.risk-medium { background: #f1c40f; color: #333; }
+    .risk-cell.risk-low { background: #3498db; color: white; }
+";
+
+/// One grid cell: how many findings fall in it and the worst severity among them
+#[derive(Clone, Copy)]
+struct Cell {
+    count: usize,
+    severity: CvssSeverity,
+}
+
+/// EPSS percentile bucket, as an index into `LIKELIHOOD_ROWS`
+fn likelihood_row(score: &VulnerabilityScore) -> usize {
+    match &score.epss {
+        Some(epss) if epss.percentile >= 0.9 => 0,
+        Some(epss) if epss.percentile >= 0.5 => 1,
+        Some(_) => 2,
+        None => 3,
+    }
+}
+
+/// Impact sub-score bucket, as an index into `IMPACT_COLUMNS`
+fn impact_column(impact: f64) -> usize {
+    if impact >= 4.0 {
+        2
+    } else if impact >= 2.0 {
+        1
+    } else {
+        0
+    }
+}
+
+/// Render the risk matrix, or `None` if no finding can be plotted
+pub(crate) fn render_risk_matrix(scan_report: &ScanReport) -> Option<String> {
+    let empty = Cell {
+        count: 0,
+        severity: CvssSeverity::None,
+    };
+    let mut grid = [[empty; IMPACT_COLUMNS.len()]; LIKELIHOOD_ROWS.len()];
+    let mut plotted = 0;
+
+    for score in scan_report
+        .scan_results
+        .iter()
+        .filter_map(|r| r.vulnerability_score.as_ref())
+    {
+        let Some(cvss) = &score.cvss_v3 else {
+            continue;
+        };
+
+        let impact = calculate_cvss_v3_impact_subscore(&cvss.base_metrics);
+        let cell = &mut grid[likelihood_row(score)][impact_column(impact)];
+        cell.count += 1;
+        cell.severity = cell.severity.max(cvss.severity);
+        plotted += 1;
+    }
+
+    if plotted == 0 {
+        return None;
+    }
+
+    let mut html = String::new();
+    html.push_str("  <div class=\"risk-matrix\">\n");
+    html.push_str("    <div class=\"risk-label\">Likelihood \\ Impact</div>");
+    for (_, label) in IMPACT_COLUMNS {
+        html.push_str(&format!("<div class=\"risk-label\">{}</div>", label));
+    }
+    html.push('\n');
+
+    for (row, (likelihood, label)) in LIKELIHOOD_ROWS.iter().enumerate() {
+        html.push_str(&format!("    <div class=\"risk-label\">{}</div>", label));
+        for (column, (impact, _)) in IMPACT_COLUMNS.iter().enumerate() {
+            let cell = grid[row][column];
+            let class = match cell.severity {
+                CvssSeverity::None => "risk-cell".to_string(),
+                severity => format!("risk-cell risk-{}", severity.as_str().to_lowercase()),
+            };
+            html.push_str(&format!(
+                "<div class=\"{}\" data-likelihood=\"{}\" data-impact=\"{}\">{}</div>",
+                class, likelihood, impact, cell.count
+            ));
+        }
+        html.push('\n');
+    }
+
+    html.push_str("  </div>\n");
+    Some(html)
+}
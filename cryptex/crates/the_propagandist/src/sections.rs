@@ -0,0 +1,1046 @@
+//! Report Sections - Incremental report rendering
+//!
+//! A report is rendered as a sequence of sections - header, statistics,
+//! per-host findings, footer - so large reports can be sent to a client while
+//! they are still being produced. Buffered generation concatenates the very
+//! same sections, which keeps both paths byte-for-byte identical.
+
+use crate::consolidated::ScanBreakdown;
+use crate::formats::*;
+use crate::risk_matrix::{render_risk_matrix, RISK_MATRIX_CSS};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use the_assessor::cwe_name;
+use the_foundation::{CryptexError, CryptexResult};
+use the_infiltrator::{ScanReport, ScanResult, ScanStatus, TargetProgress};
+
+/// Column headers of the CSV report
+pub(crate) const CSV_HEADERS: [&str; 8] = [
+    "cve_id",
+    "host",
+    "port",
+    "service",
+    "cvss",
+    "severity",
+    "is_kev",
+    "remediation_summary",
+];
+
+/// Findings listed in the executive HTML table
+const EXECUTIVE_TOP_COUNT: usize = 10;
+
+/// Findings listed in the Markdown summary
+const MARKDOWN_TOP_COUNT: usize = 5;
+
+/// Position of a `ReportSections` iterator within the document
+#[derive(Clone, Copy)]
+enum Section {
+    Header,
+    Stats,
+    /// Per-scan table of a consolidated report; empty otherwise
+    Breakdown,
+    /// Index of a host group (HTML, text) or a scan result (JSON, CSV)
+    Findings(usize),
+    Footer,
+    Done,
+}
+
+/// Report Sections - Iterator over the rendered chunks of one report
+///
+/// Traditional name: `ReportChunker`
+///
+/// Generic over how the scan report is held so buffered generation can borrow
+/// it while a stream owns it.
+pub(crate) struct ReportSections<R> {
+    report: R,
+    config: ReportConfig,
+    generated_at: DateTime<Utc>,
+    next: Section,
+    /// Result indices grouped by host, in order of first appearance
+    hosts: Vec<Vec<usize>>,
+    /// Findings shown in detail when the configured cap cuts the list short
+    shown: Option<usize>,
+    /// Remainder of the JSON document after the scan results array
+    json_tail: String,
+    /// Source scans of a consolidated report
+    breakdown: Vec<ScanBreakdown>,
+}
+
+impl<R: Borrow<ScanReport>> ReportSections<R> {
+    pub(crate) fn new(
+        report: R,
+        config: ReportConfig,
+        generated_at: DateTime<Utc>,
+    ) -> CryptexResult<Self> {
+        if config.format == ReportFormat::Html {
+            config.theme.validate()?;
+        }
+
+        // Executive HTML reports show a top-N table instead of every finding
+        let executive_html =
+            config.format == ReportFormat::Html && config.audience == ReportAudience::Executive;
+        let per_host = match config.format {
+            ReportFormat::Html => !executive_html,
+            ReportFormat::Text => true,
+            _ => false,
+        } && config.sections.hosts;
+
+        let results = &report.borrow().scan_results;
+        let shown = match config.max_detailed_vulnerabilities {
+            Some(max) if results.len() > max => match config.format {
+                ReportFormat::Html if executive_html => Some(max.min(EXECUTIVE_TOP_COUNT)),
+                ReportFormat::Markdown => Some(max.min(MARKDOWN_TOP_COUNT)),
+                ReportFormat::Html | ReportFormat::Text => Some(max),
+                ReportFormat::Json | ReportFormat::Csv => None,
+            },
+            _ => None,
+        };
+        let hosts = match (per_host, shown) {
+            (false, _) => Vec::new(),
+            (true, Some(shown)) => group_by_host(results, top_indices(results, shown)),
+            (true, None) => group_by_host(results, 0..results.len()),
+        };
+
+        Ok(Self {
+            report,
+            config,
+            generated_at,
+            next: Section::Header,
+            hosts,
+            shown,
+            json_tail: String::new(),
+            breakdown: Vec::new(),
+        })
+    }
+
+    /// Add a per-scan breakdown section after the statistics
+    pub(crate) fn with_breakdown(mut self, breakdown: Vec<ScanBreakdown>) -> Self {
+        self.breakdown = breakdown;
+        self
+    }
+
+    /// Notice that the detailed findings were cut short, if they were
+    ///
+    /// Nothing to say when the cut list is not rendered at all.
+    fn truncation_notice(&self) -> Option<String> {
+        let shown = self.shown?;
+        let sections = &self.config.sections;
+        let listed = match self.config.format {
+            ReportFormat::Html if self.config.audience == ReportAudience::Executive => sections.top,
+            ReportFormat::Html | ReportFormat::Text => sections.hosts,
+            ReportFormat::Markdown => sections.top,
+            ReportFormat::Json | ReportFormat::Csv => true,
+        };
+        if !listed {
+            return None;
+        }
+        Some(format!(
+            "Showing top {} of {} findings; export full results via API.",
+            group_thousands(shown),
+            group_thousands(self.report.borrow().scan_results.len())
+        ))
+    }
+
+    /// Number of findings sections in this report
+    fn findings_len(&self) -> usize {
+        match self.config.format {
+            ReportFormat::Html | ReportFormat::Text => self.hosts.len(),
+            ReportFormat::Json | ReportFormat::Csv => self.report.borrow().scan_results.len(),
+            ReportFormat::Markdown => 0,
+        }
+    }
+
+    fn following(&self, section: Section) -> Section {
+        match section {
+            // The Markdown summary is short and rendered in one piece
+            Section::Header if self.config.format == ReportFormat::Markdown => Section::Breakdown,
+            Section::Header => Section::Stats,
+            Section::Stats => Section::Breakdown,
+            Section::Breakdown if self.findings_len() > 0 => Section::Findings(0),
+            Section::Findings(i) if i + 1 < self.findings_len() => Section::Findings(i + 1),
+            Section::Breakdown | Section::Findings(_) => Section::Footer,
+            Section::Footer | Section::Done => Section::Done,
+        }
+    }
+
+    fn render(&mut self, section: Section) -> CryptexResult<String> {
+        let report = self.report.borrow();
+        let config = &self.config;
+
+        match (config.format, section) {
+            (ReportFormat::Json, Section::Header) => {
+                let (head, tail) = json_shell(report)?;
+                self.json_tail = tail;
+                Ok(head)
+            }
+            (ReportFormat::Json, Section::Findings(i)) => {
+                let item = serde_json::to_string_pretty(&report.scan_results[i])
+                    .map_err(json_error)?;
+                let separator = if i > 0 { "," } else { "" };
+                Ok(format!("{}\n{}", separator, indent(&item, "    ")))
+            }
+            (ReportFormat::Json, Section::Footer) if !report.scan_results.is_empty() => {
+                Ok(format!("\n  {}", self.json_tail))
+            }
+
+            (ReportFormat::Html, Section::Header) => Ok(html_header(config)),
+            (ReportFormat::Html, Section::Stats) => {
+                Ok(html_stats(report, config, self.shown.unwrap_or(EXECUTIVE_TOP_COUNT)))
+            }
+            (ReportFormat::Html, Section::Breakdown) => Ok(html_breakdown(&self.breakdown)),
+            (ReportFormat::Html, Section::Findings(i)) => {
+                Ok(html_host_findings(report, &self.hosts[i], i == 0))
+            }
+            (ReportFormat::Html, Section::Footer) => {
+                let notice = self.truncation_notice().map(|notice| {
+                    format!("  <p class=\"truncation-notice\"><strong>{}</strong></p>\n", notice)
+                });
+                Ok(notice.unwrap_or_default() + &html_footer(&self.generated_at))
+            }
+
+            (ReportFormat::Markdown, Section::Header) => Ok(markdown_summary(
+                report,
+                self.shown.unwrap_or(MARKDOWN_TOP_COUNT),
+                &config.sections,
+            )),
+            (ReportFormat::Markdown, Section::Breakdown) => Ok(markdown_breakdown(&self.breakdown)),
+            (ReportFormat::Markdown, Section::Footer) => {
+                let notice = self.truncation_notice().map(|notice| format!("> **Note:** {}\n\n", notice));
+                Ok(notice.unwrap_or_default() + &markdown_footer(&self.generated_at))
+            }
+
+            (ReportFormat::Text, Section::Header) => Ok(text_header(report, config.sections.overview)),
+            (ReportFormat::Text, Section::Stats) if config.sections.stats => Ok(text_stats(report)),
+            (ReportFormat::Text, Section::Breakdown) => Ok(text_breakdown(&self.breakdown)),
+            (ReportFormat::Text, Section::Findings(i)) => {
+                Ok(text_host_findings(report, &self.hosts[i], i == 0))
+            }
+            (ReportFormat::Text, Section::Footer) => {
+                let notice = self.truncation_notice().map(|notice| format!("NOTE: {}\n\n", notice));
+                Ok(format!(
+                    "{}Report generated: {}\n",
+                    notice.unwrap_or_default(),
+                    self.generated_at.format("%Y-%m-%d %H:%M:%S UTC")
+                ))
+            }
+
+            (ReportFormat::Csv, Section::Header) => csv_record(CSV_HEADERS),
+            (ReportFormat::Csv, Section::Findings(i)) => csv_row(&report.scan_results[i]),
+
+            _ => Ok(String::new()),
+        }
+    }
+}
+
+impl<R: Borrow<ScanReport>> Iterator for ReportSections<R> {
+    type Item = CryptexResult<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let section = self.next;
+            if let Section::Done = section {
+                return None;
+            }
+            self.next = self.following(section);
+
+            match self.render(section) {
+                Ok(chunk) if chunk.is_empty() => continue,
+                Ok(chunk) => return Some(Ok(chunk)),
+                Err(e) => {
+                    self.next = Section::Done;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// Group the given result indices by host, keeping hosts in order of first appearance
+fn group_by_host(
+    results: &[ScanResult],
+    indices: impl IntoIterator<Item = usize>,
+) -> Vec<Vec<usize>> {
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    let mut hosts: Vec<Vec<usize>> = Vec::new();
+
+    for i in indices {
+        let position = *positions.entry(results[i].host.as_str()).or_insert_with(|| {
+            hosts.push(Vec::new());
+            hosts.len() - 1
+        });
+        hosts[position].push(i);
+    }
+
+    hosts
+}
+
+/// Indices of the `count` highest-risk results, in their original order
+fn top_indices(results: &[ScanResult], count: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..results.len()).collect();
+    indices.sort_by(|&a, &b| {
+        results[b]
+            .risk_score()
+            .partial_cmp(&results[a].risk_score())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    indices.truncate(count);
+    indices.sort_unstable();
+    indices
+}
+
+/// Format a count with thousands separators, e.g. `12,340`
+fn group_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+// JSON
+
+/// Every `ScanReport` field, with the results left out so they can follow one
+/// at a time. Fields are in the order `ScanReport` declares them.
+#[derive(Serialize)]
+struct JsonShell<'a> {
+    scan_id: &'a str,
+    target: &'a str,
+    targets: &'a [String],
+    target_progress: &'a [TargetProgress],
+    start_time: u64,
+    end_time: Option<u64>,
+    status: ScanStatus,
+    scan_results: &'a [ScanResult],
+    total_vulnerabilities: usize,
+    critical_count: usize,
+    high_count: usize,
+    medium_count: usize,
+    low_count: usize,
+    kev_count: usize,
+    total_hosts: usize,
+    ai_enhanced_count: usize,
+    enrichment_skipped_count: usize,
+    #[serde(serialize_with = "serialize_summary")]
+    summary: &'a Option<serde_json::Value>,
+}
+
+fn serialize_summary<S: serde::Serializer>(
+    summary: &&Option<serde_json::Value>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    the_infiltrator::serialize_sorted_summary(summary, serializer)
+}
+
+fn json_error(e: serde_json::Error) -> CryptexError {
+    CryptexError::validation(format!("JSON serialization failed: {}", e))
+}
+
+/// Split the report document around its scan results array
+///
+/// Returns everything up to and including the opening `[`, and everything from
+/// the closing `]` on. A report without results is returned whole.
+///
+/// The report is destructured without `..`, so a field added to `ScanReport`
+/// does not compile until it is added to [`JsonShell`] too.
+fn json_shell(report: &ScanReport) -> CryptexResult<(String, String)> {
+    let ScanReport {
+        scan_id,
+        target,
+        targets,
+        target_progress,
+        start_time,
+        end_time,
+        status,
+        scan_results,
+        total_vulnerabilities,
+        critical_count,
+        high_count,
+        medium_count,
+        low_count,
+        kev_count,
+        total_hosts,
+        ai_enhanced_count,
+        enrichment_skipped_count,
+        summary,
+    } = report;
+    let shell = JsonShell {
+        scan_id,
+        target,
+        targets,
+        target_progress,
+        start_time: *start_time,
+        end_time: *end_time,
+        status: *status,
+        scan_results: &[],
+        total_vulnerabilities: *total_vulnerabilities,
+        critical_count: *critical_count,
+        high_count: *high_count,
+        medium_count: *medium_count,
+        low_count: *low_count,
+        kev_count: *kev_count,
+        total_hosts: *total_hosts,
+        ai_enhanced_count: *ai_enhanced_count,
+        enrichment_skipped_count: *enrichment_skipped_count,
+        summary,
+    };
+    let document = serde_json::to_string_pretty(&shell).map_err(json_error)?;
+
+    if scan_results.is_empty() {
+        return Ok((document, String::new()));
+    }
+
+    // Top-level keys are indented by exactly two spaces
+    let marker = "\n  \"scan_results\": []";
+    let split = document
+        .find(marker)
+        .map(|at| at + marker.len() - 1)
+        .ok_or_else(|| CryptexError::validation("JSON report is missing scan_results"))?;
+
+    Ok((document[..split].to_string(), document[split..].to_string()))
+}
+
+fn indent(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", prefix, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// HTML
+
+fn html_header(config: &ReportConfig) -> String {
+    let theme = &config.theme;
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n");
+    html.push_str("<html lang=\"en\">\n<head>\n");
+    html.push_str("  <meta charset=\"UTF-8\">\n");
+    html.push_str("  <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n");
+    html.push_str("  <title>CRYPTEX Vulnerability Report</title>\n");
+    html.push_str("  <style>\n");
+    html.push_str("    body { font-family: sans-serif; margin: 20px; background: #f5f5f5; }\n");
+    html.push_str("    .container { max-width: 1200px; margin: 0 auto; background: white; padding: 20px; border-radius: 8px; }\n");
+    html.push_str(&format!("    h1 {{ color: #333; border-bottom: 3px solid {}; }}\n", theme.accent_color));
+    html.push_str("    .critical { color: #e74c3c; font-weight: bold; }\n");
+    html.push_str("    .high { color: #f39c12; font-weight: bold; }\n");
+    html.push_str("    .medium { color: #3498db; }\n");
+    html.push_str("    .kev-badge { background: #e74c3c; color: white; padding: 2px 8px; border-radius: 4px; font-size: 0.8em; }\n");
+    html.push_str("    table { width: 100%; border-collapse: collapse; margin: 20px 0; }\n");
+    html.push_str("    th, td { padding: 12px; text-align: left; border-bottom: 1px solid #ddd; }\n");
+    html.push_str("    th { background: #34495e; color: white; }\n");
+    html.push_str("    .brand { display: flex; align-items: center; gap: 12px; margin-bottom: 10px; }\n");
+    html.push_str("    .brand img { max-height: 48px; }\n");
+    if config.audience == ReportAudience::Executive {
+        html.push_str(RISK_MATRIX_CSS);
+    }
+    html.push_str("  </style>\n</head>\n<body>\n");
+    html.push_str("<div class=\"container\">\n");
+
+    // Branding header (white-labeled reports only)
+    if theme.company_name.is_some() || theme.logo_url.is_some() {
+        html.push_str("  <div class=\"brand\">");
+        if let Some(logo_url) = &theme.logo_url {
+            html.push_str(&format!("<img src=\"{}\" alt=\"logo\">", escape_html(logo_url)));
+        }
+        if let Some(company_name) = &theme.company_name {
+            html.push_str(&format!("<strong>{}</strong>", escape_html(company_name)));
+        }
+        html.push_str("</div>\n");
+    }
+
+    // Title
+    html.push_str("  <h1>CRYPTEX Vulnerability Assessment Report</h1>\n");
+
+    html
+}
+
+fn html_stats(scan_report: &ScanReport, config: &ReportConfig, top_count: usize) -> String {
+    let mut html = String::new();
+    let sections = &config.sections;
+
+    if sections.overview {
+        html.push_str(&html_overview(scan_report));
+    }
+
+    if sections.stats {
+        html.push_str(&html_statistics(scan_report));
+    }
+
+    // Top vulnerabilities (if executive)
+    if sections.top && config.audience == ReportAudience::Executive {
+        html.push_str(&html_top_vulnerabilities(scan_report, top_count));
+    }
+
+    html
+}
+
+fn html_overview(scan_report: &ScanReport) -> String {
+    let mut html = String::new();
+
+    html.push_str("  <h2>Scan Overview</h2>\n");
+    html.push_str("  <table>\n");
+    html.push_str(&format!("    <tr><td><strong>Scan ID</strong></td><td>{}</td></tr>\n", scan_report.scan_id));
+    html.push_str(&format!("    <tr><td><strong>Target</strong></td><td>{}</td></tr>\n", scan_report.target));
+    html.push_str(&format!("    <tr><td><strong>Total Hosts</strong></td><td>{}</td></tr>\n", scan_report.total_hosts));
+    if let Some(duration) = scan_report.duration_seconds() {
+        html.push_str(&format!("    <tr><td><strong>Duration</strong></td><td>{} seconds</td></tr>\n", duration));
+    }
+    html.push_str("  </table>\n");
+
+    html
+}
+
+fn html_statistics(scan_report: &ScanReport) -> String {
+    let mut html = String::new();
+
+    html.push_str("  <h2>Vulnerability Statistics</h2>\n");
+    html.push_str("  <table>\n");
+    html.push_str(&format!("    <tr><td><strong>Total Vulnerabilities</strong></td><td>{}</td></tr>\n", scan_report.total_vulnerabilities));
+    html.push_str(&format!("    <tr><td><strong>Critical</strong></td><td class=\"critical\">{}</td></tr>\n", scan_report.critical_count));
+    html.push_str(&format!("    <tr><td><strong>High</strong></td><td class=\"high\">{}</td></tr>\n", scan_report.high_count));
+    html.push_str(&format!("    <tr><td><strong>Medium</strong></td><td class=\"medium\">{}</td></tr>\n", scan_report.medium_count));
+    html.push_str(&format!("    <tr><td><strong>Low</strong></td><td>{}</td></tr>\n", scan_report.low_count));
+    if scan_report.kev_count > 0 {
+        html.push_str(&format!("    <tr><td><strong>KEV Vulnerabilities</strong></td><td><span class=\"kev-badge\">{} KEV</span></td></tr>\n", scan_report.kev_count));
+    }
+    html.push_str("  </table>\n");
+
+    let services = scan_report.group_by_service();
+    if !services.is_empty() {
+        html.push_str("  <h2>Findings by Service</h2>\n");
+        html.push_str("  <table>\n");
+        html.push_str("    <tr><th>Service</th><th>Findings</th><th>Hosts</th></tr>\n");
+        for (service, results) in &services {
+            html.push_str(&format!(
+                "    <tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(service),
+                results.len(),
+                distinct_hosts(results)
+            ));
+        }
+        html.push_str("  </table>\n");
+    }
+
+    let weaknesses = scan_report.group_by_cwe();
+    if !weaknesses.is_empty() {
+        html.push_str("  <h2>Findings by Weakness</h2>\n");
+        html.push_str("  <table>\n");
+        html.push_str("    <tr><th>CWE</th><th>Weakness</th><th>Findings</th><th>CVEs</th></tr>\n");
+        for (cwe_id, results) in &weaknesses {
+            html.push_str(&format!(
+                "    <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(cwe_id),
+                escape_html(cwe_name(cwe_id).unwrap_or("-")),
+                results.len(),
+                escape_html(&distinct_cves(results).join(", "))
+            ));
+        }
+        html.push_str("  </table>\n");
+    }
+
+    html
+}
+
+/// Hosts a group of findings is spread over
+fn distinct_hosts(results: &[&ScanResult]) -> usize {
+    let mut hosts: Vec<&str> = results.iter().map(|r| r.host.as_str()).collect();
+    hosts.sort_unstable();
+    hosts.dedup();
+    hosts.len()
+}
+
+/// CVEs in a group of findings, each listed once in first-seen order
+fn distinct_cves<'a>(results: &[&'a ScanResult]) -> Vec<&'a str> {
+    let mut cves: Vec<&str> = Vec::new();
+    for result in results {
+        if !cves.contains(&result.cve_id.as_str()) {
+            cves.push(&result.cve_id);
+        }
+    }
+    cves
+}
+
+fn html_top_vulnerabilities(scan_report: &ScanReport, top_count: usize) -> String {
+    let mut html = String::new();
+
+    if let Some(matrix) = render_risk_matrix(scan_report) {
+        html.push_str("  <h2>Risk Matrix</h2>\n");
+        html.push_str(&matrix);
+    }
+
+    let top = scan_report.top_vulnerabilities(top_count);
+    if !top.is_empty() {
+        html.push_str("  <h2>Top Vulnerabilities by Risk</h2>\n");
+        html.push_str("  <table>\n");
+        html.push_str("    <tr><th>CVE</th><th>Host</th><th>Port</th><th>CVSS</th><th>Status</th></tr>\n");
+        for result in top {
+            html.push_str("    <tr>");
+            html.push_str(&format!("<td>{}</td>", result.cve_id));
+            html.push_str(&format!("<td>{}</td>", result.host));
+            html.push_str(&format!("<td>{}</td>", result.port));
+            html.push_str(&html_cvss_cell(result));
+            html.push_str(&html_kev_cell(result));
+            html.push_str("</tr>\n");
+        }
+        html.push_str("  </table>\n");
+    }
+
+    html
+}
+
+fn html_breakdown(breakdown: &[ScanBreakdown]) -> String {
+    if breakdown.is_empty() {
+        return String::new();
+    }
+
+    let mut html = String::new();
+    html.push_str("  <h2>Per-Scan Breakdown</h2>\n");
+    html.push_str("  <table>\n");
+    html.push_str("    <tr><th>Scan</th><th>Target</th><th>Findings</th><th>Critical</th><th>High</th><th>KEV</th><th>Duplicates</th></tr>\n");
+    for scan in breakdown {
+        html.push_str(&format!(
+            "    <tr><td>{}</td><td>{}</td><td>{}</td><td class=\"critical\">{}</td><td class=\"high\">{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&scan.scan_id),
+            escape_html(&scan.target),
+            scan.total_vulnerabilities,
+            scan.critical_count,
+            scan.high_count,
+            scan.kev_count,
+            scan.duplicates
+        ));
+    }
+    html.push_str("  </table>\n");
+
+    html
+}
+
+fn html_host_findings(scan_report: &ScanReport, indices: &[usize], first: bool) -> String {
+    let mut html = String::new();
+
+    if first {
+        html.push_str("  <h2>Findings by Host</h2>\n");
+    }
+
+    let host = &scan_report.scan_results[indices[0]].host;
+    html.push_str(&format!("  <h3>{}</h3>\n", escape_html(host)));
+    html.push_str("  <table>\n");
+    html.push_str("    <tr><th>CVE</th><th>Port</th><th>Service</th><th>CVSS</th><th>Status</th></tr>\n");
+    for &i in indices {
+        let result = &scan_report.scan_results[i];
+        html.push_str("    <tr>");
+        html.push_str(&format!("<td>{}</td>", escape_html(&result.cve_id)));
+        html.push_str(&format!("<td>{}</td>", result.port));
+        html.push_str(&format!("<td>{}</td>", escape_html(result.service.as_deref().unwrap_or("-"))));
+        html.push_str(&html_cvss_cell(result));
+        html.push_str(&html_kev_cell(result));
+        html.push_str("</tr>\n");
+    }
+    html.push_str("  </table>\n");
+
+    html
+}
+
+fn html_cvss_cell(result: &ScanResult) -> String {
+    match result.cvss_base_score() {
+        Some(score) => {
+            let class = if score >= 9.0 { "critical" } else if score >= 7.0 { "high" } else { "medium" };
+            format!("<td class=\"{}\">{:.1}</td>", class, score)
+        }
+        None => format!("<td>{}</td>", unscored_label(result).unwrap_or_else(|| "-".to_string())),
+    }
+}
+
+/// "unscored (reserved)" and the like for an assessed finding without CVSS
+///
+/// Keeps a missing score from reading as a zero, i.e. as benign.
+fn unscored_label(result: &ScanResult) -> Option<String> {
+    result
+        .unscored_reason()
+        .map(|reason| format!("unscored ({})", reason.as_str()))
+}
+
+fn html_kev_cell(result: &ScanResult) -> String {
+    if result.is_kev() {
+        "<td><span class=\"kev-badge\">KEV</span></td>".to_string()
+    } else {
+        "<td>-</td>".to_string()
+    }
+}
+
+fn html_footer(generated_at: &DateTime<Utc>) -> String {
+    let mut html = String::new();
+
+    html.push_str(&format!("  <p style=\"margin-top: 40px; color: #7f8c8d; font-size: 0.9em;\">Report generated: {}</p>\n",
+                          generated_at.format("%Y-%m-%d %H:%M:%S UTC")));
+    html.push_str("</div>\n</body>\n</html>");
+
+    html
+}
+
+/// Escape text for safe inclusion in HTML content and attributes
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// Text
+
+fn text_header(scan_report: &ScanReport, overview: bool) -> String {
+    let mut text = String::new();
+
+    text.push_str("CRYPTEX VULNERABILITY ASSESSMENT REPORT\n");
+    text.push_str("=======================================\n\n");
+    if !overview {
+        return text;
+    }
+
+    text.push_str(&format!("Scan ID:      {}\n", scan_report.scan_id));
+    text.push_str(&format!("Target:       {}\n", scan_report.target));
+    text.push_str(&format!("Total Hosts:  {}\n", scan_report.total_hosts));
+    if let Some(duration) = scan_report.duration_seconds() {
+        text.push_str(&format!("Duration:     {} seconds\n", duration));
+    }
+    text.push('\n');
+
+    text
+}
+
+fn text_stats(scan_report: &ScanReport) -> String {
+    let mut text = String::new();
+
+    text.push_str("VULNERABILITY SUMMARY\n");
+    text.push_str("---------------------\n");
+    text.push_str(&format!("Total:     {}\n", scan_report.total_vulnerabilities));
+    text.push_str(&format!("Critical:  {}\n", scan_report.critical_count));
+    text.push_str(&format!("High:      {}\n", scan_report.high_count));
+    text.push_str(&format!("Medium:    {}\n", scan_report.medium_count));
+    text.push_str(&format!("Low:       {}\n", scan_report.low_count));
+    if scan_report.kev_count > 0 {
+        text.push_str(&format!("KEV:       {} (Known Exploited Vulnerabilities)\n", scan_report.kev_count));
+    }
+    text.push('\n');
+
+    let services = scan_report.group_by_service();
+    if !services.is_empty() {
+        text.push_str("FINDINGS BY SERVICE\n");
+        text.push_str("-------------------\n");
+        for (service, results) in &services {
+            text.push_str(&format!(
+                "{:<16} {} findings on {} hosts\n",
+                service,
+                results.len(),
+                distinct_hosts(results)
+            ));
+        }
+        text.push('\n');
+    }
+
+    let weaknesses = scan_report.group_by_cwe();
+    if !weaknesses.is_empty() {
+        text.push_str("FINDINGS BY WEAKNESS\n");
+        text.push_str("--------------------\n");
+        for (cwe_id, results) in &weaknesses {
+            text.push_str(&format!(
+                "{:<10} {} findings: {}",
+                cwe_id,
+                results.len(),
+                distinct_cves(results).join(", ")
+            ));
+            if let Some(name) = cwe_name(cwe_id) {
+                text.push_str(&format!(" ({})", name));
+            }
+            text.push('\n');
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+fn text_breakdown(breakdown: &[ScanBreakdown]) -> String {
+    if breakdown.is_empty() {
+        return String::new();
+    }
+
+    let mut text = String::new();
+    text.push_str("PER-SCAN BREAKDOWN\n");
+    text.push_str("------------------\n");
+    for scan in breakdown {
+        text.push_str(&format!("{} ({})\n", scan.scan_id, scan.target));
+        text.push_str(&format!(
+            "  Findings: {}  Critical: {}  High: {}  KEV: {}  Duplicates: {}\n",
+            scan.total_vulnerabilities,
+            scan.critical_count,
+            scan.high_count,
+            scan.kev_count,
+            scan.duplicates
+        ));
+    }
+    text.push('\n');
+
+    text
+}
+
+fn text_host_findings(scan_report: &ScanReport, indices: &[usize], first: bool) -> String {
+    let mut text = String::new();
+
+    if first {
+        text.push_str("FINDINGS BY HOST\n");
+        text.push_str("----------------\n");
+    }
+
+    text.push_str(&format!("{}\n", scan_report.scan_results[indices[0]].host));
+    for &i in indices {
+        let result = &scan_report.scan_results[i];
+        text.push_str(&format!("  {:<18} port {:<6}", result.cve_id, result.port));
+        match (result.cvss_base_score(), unscored_label(result)) {
+            (Some(score), _) => text.push_str(&format!(" CVSS {:.1}", score)),
+            (None, Some(label)) => text.push_str(&format!(" CVSS {}", label)),
+            (None, None) => text.push_str(" CVSS -"),
+        }
+        if result.is_kev() {
+            text.push_str("  [KEV]");
+        }
+        text.push('\n');
+    }
+    text.push('\n');
+
+    text
+}
+
+// CSV
+
+fn csv_error(e: csv::Error) -> CryptexError {
+    CryptexError::validation(format!("CSV serialization failed: {}", e))
+}
+
+/// Encode a single CSV record, including its line terminator
+fn csv_record<I, T>(record: I) -> CryptexResult<String>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(record).map_err(csv_error)?;
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| csv_error(e.into_error().into()))?;
+    String::from_utf8(bytes).map_err(|e| CryptexError::validation(format!("CSV serialization failed: {}", e)))
+}
+
+fn csv_row(result: &ScanResult) -> CryptexResult<String> {
+    let severity = match (result.severity(), result.unscored_reason()) {
+        (_, Some(reason)) => format!("Unscored ({})", reason.as_str()),
+        (Some(severity), None) => severity.as_str().to_string(),
+        (None, None) => String::new(),
+    };
+    let remediation = result
+        .remediation_plan
+        .as_ref()
+        .map(|plan| plan.summary.as_str())
+        .or(result.remediation_guidance.as_deref())
+        .unwrap_or_default();
+
+    csv_record([
+        result.cve_id.as_str(),
+        result.host.as_str(),
+        &result.port.to_string(),
+        result.service.as_deref().unwrap_or_default(),
+        &result.cvss_base_score().map(|s| format!("{:.1}", s)).unwrap_or_default(),
+        &severity,
+        &result.is_kev().to_string(),
+        remediation,
+    ])
+}
+
+// Markdown
+
+/// Render the Markdown executive summary
+pub(crate) fn render_executive_summary(
+    scan_report: &ScanReport,
+    generated_at: &DateTime<Utc>,
+) -> String {
+    markdown_summary(scan_report, MARKDOWN_TOP_COUNT, &SectionToggles::all())
+        + &markdown_footer(generated_at)
+}
+
+fn markdown_summary(
+    scan_report: &ScanReport,
+    top_count: usize,
+    sections: &SectionToggles,
+) -> String {
+    let mut summary = String::new();
+
+    summary.push_str("# CRYPTEX Vulnerability Assessment - Executive Summary\n\n");
+
+    // Overview
+    if sections.overview {
+        summary.push_str("## Scan Overview\n\n");
+        summary.push_str(&format!("**Scan ID**: {}\n", scan_report.scan_id));
+        summary.push_str(&format!("**Target**: {}\n", scan_report.target));
+
+        if let Some(duration) = scan_report.duration_seconds() {
+            summary.push_str(&format!("**Duration**: {} seconds\n", duration));
+        }
+
+        summary.push_str(&format!("**Total Hosts**: {}\n\n", scan_report.total_hosts));
+    }
+
+    // Key Findings
+    if sections.stats {
+        summary.push_str("## Key Findings\n\n");
+        summary.push_str(&format!(
+            "- **Total Vulnerabilities**: {}\n",
+            scan_report.total_vulnerabilities
+        ));
+        summary.push_str(&format!(
+            "- **Critical**: {} vulnerabilities requiring immediate action\n",
+            scan_report.critical_count
+        ));
+        summary.push_str(&format!(
+            "- **High**: {} vulnerabilities requiring prompt attention\n",
+            scan_report.high_count
+        ));
+        summary.push_str(&format!("- **Medium**: {}\n", scan_report.medium_count));
+        summary.push_str(&format!("- **Low**: {}\n\n", scan_report.low_count));
+
+        // KEV Alert
+        if scan_report.kev_count > 0 {
+            summary.push_str("### ⚠️ Known Exploited Vulnerabilities (KEV)\n\n");
+            summary.push_str(&format!(
+                "**{}** vulnerabilities detected are on the CISA KEV catalog, \
+                 indicating active exploitation in the wild. \
+                 These require immediate remediation.\n\n",
+                scan_report.kev_count
+            ));
+        }
+
+        // Risk Assessment
+        summary.push_str("## Risk Assessment\n\n");
+        let risk_level = if scan_report.critical_count > 0 || scan_report.kev_count > 0 {
+            "**CRITICAL** - Immediate action required"
+        } else if scan_report.high_count > 0 {
+            "**HIGH** - Prompt remediation needed"
+        } else if scan_report.medium_count > 0 {
+            "**MEDIUM** - Address in regular patch cycle"
+        } else {
+            "**LOW** - Minimal risk"
+        };
+
+        summary.push_str(&format!("Overall Risk Level: {}\n\n", risk_level));
+
+        // Findings by Service
+        let services = scan_report.group_by_service();
+        if !services.is_empty() {
+            summary.push_str("## Findings by Service\n\n");
+            summary.push_str("| Service | Findings | Hosts |\n");
+            summary.push_str("|---------|----------|-------|\n");
+            for (service, results) in &services {
+                summary.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    service,
+                    results.len(),
+                    distinct_hosts(results)
+                ));
+            }
+            summary.push('\n');
+        }
+
+        // Findings by Weakness
+        let weaknesses = scan_report.group_by_cwe();
+        if !weaknesses.is_empty() {
+            summary.push_str("## Findings by Weakness\n\n");
+            summary.push_str("| CWE | Weakness | Findings | CVEs |\n");
+            summary.push_str("|-----|----------|----------|------|\n");
+            for (cwe_id, results) in &weaknesses {
+                summary.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    cwe_id,
+                    cwe_name(cwe_id).unwrap_or("-"),
+                    results.len(),
+                    distinct_cves(results).join(", ")
+                ));
+            }
+            summary.push('\n');
+        }
+    }
+
+    // Top Vulnerabilities
+    if sections.top && !scan_report.scan_results.is_empty() {
+        summary.push_str("## Top Vulnerabilities (By Risk)\n\n");
+        let top = scan_report.top_vulnerabilities(top_count);
+        for (i, result) in top.iter().enumerate() {
+            summary.push_str(&format!("{}. **{}** - {}\n", i + 1, result.cve_id, result.host));
+            if let Some(score) = result.cvss_base_score() {
+                summary.push_str(&format!("   - CVSS Score: {:.1}\n", score));
+            } else if let Some(label) = unscored_label(result) {
+                summary.push_str(&format!("   - CVSS Score: {}\n", label));
+            }
+            if result.is_kev() {
+                summary.push_str("   - ⚠️ Known Exploited Vulnerability\n");
+            }
+            summary.push('\n');
+        }
+    }
+
+    // Recommendations
+    if sections.remediation {
+        summary.push_str("## Recommended Actions\n\n");
+        if scan_report.kev_count > 0 {
+            summary.push_str("1. **Immediate**: Patch all KEV vulnerabilities within 24-48 hours\n");
+        }
+        if scan_report.critical_count > 0 {
+            summary.push_str("2. **High Priority**: Address critical vulnerabilities within 7 days\n");
+        }
+        if scan_report.high_count > 0 {
+            summary.push_str("3. **Standard**: Remediate high severity issues within 30 days\n");
+        }
+        summary.push_str("4. **Ongoing**: Implement continuous vulnerability scanning\n");
+        summary.push_str("5. **Process**: Review and update security policies based on findings\n\n");
+    }
+
+    summary
+}
+
+fn markdown_breakdown(breakdown: &[ScanBreakdown]) -> String {
+    if breakdown.is_empty() {
+        return String::new();
+    }
+
+    let mut summary = String::new();
+    summary.push_str("## Per-Scan Breakdown\n\n");
+    summary.push_str("| Scan | Target | Findings | Critical | High | KEV | Duplicates |\n");
+    summary.push_str("|------|--------|----------|----------|------|-----|------------|\n");
+    for scan in breakdown {
+        summary.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            scan.scan_id,
+            scan.target,
+            scan.total_vulnerabilities,
+            scan.critical_count,
+            scan.high_count,
+            scan.kev_count,
+            scan.duplicates
+        ));
+    }
+    summary.push('\n');
+
+    summary
+}
+
+fn markdown_footer(generated_at: &DateTime<Utc>) -> String {
+    format!(
+        "---\nReport generated: {}\n",
+        generated_at.format("%Y-%m-%d %H:%M:%S UTC")
+    )
+}
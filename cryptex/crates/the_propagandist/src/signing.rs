@@ -0,0 +1,81 @@
+//! Report Signing - Detached Ed25519 signatures
+//!
+//! A signed report is delivered as-is, with the signature alongside it, so
+//! the recipient can check that not a byte changed since it left the server.
+//! Signatures cover the exact bytes of the rendered report and are exchanged
+//! base64-encoded.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, Verifier};
+use the_foundation::{CryptexError, CryptexResult};
+
+use crate::generator::ThePropagandist;
+
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+/// Decode a signing key from its base64-encoded 32-byte seed
+pub fn decode_signing_key(encoded: &str) -> CryptexResult<SigningKey> {
+    let bytes = BASE64
+        .decode(encoded.trim())
+        .map_err(|e| CryptexError::validation(format!("Signing key is not valid base64: {}", e)))?;
+    let seed: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+        CryptexError::validation(format!(
+            "Signing key must be 32 bytes, got {}",
+            bytes.len()
+        ))
+    })?;
+
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+impl ThePropagandist {
+    /// Sign a rendered report, returning the base64 detached signature
+    pub fn sign_report(&self, content: &str, key: &SigningKey) -> String {
+        BASE64.encode(key.sign(content.as_bytes()).to_bytes())
+    }
+
+    /// Check a detached signature produced by `sign_report`
+    ///
+    /// Malformed signatures are treated as not matching.
+    pub fn verify_report(content: &str, signature: &str, public_key: &VerifyingKey) -> bool {
+        let Ok(bytes) = BASE64.decode(signature.trim()) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_slice(&bytes) else {
+            return false;
+        };
+
+        public_key.verify(content.as_bytes(), &signature).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_signature_detects_single_byte_change() {
+        let propagandist = ThePropagandist::the_awakening().await.unwrap();
+        let key = decode_signing_key(&BASE64.encode([7u8; 32])).unwrap();
+        let public_key = key.verifying_key();
+
+        let report = "# Vulnerability Report\n\nCVE-2021-44228 on 10.0.0.5:443\n";
+        let signature = propagandist.sign_report(report, &key);
+        assert!(ThePropagandist::verify_report(report, &signature, &public_key));
+
+        let tampered = report.replacen("443", "444", 1);
+        assert_eq!(tampered.len(), report.len());
+        assert!(!ThePropagandist::verify_report(&tampered, &signature, &public_key));
+
+        let other_key = SigningKey::from_bytes(&[8u8; 32]).verifying_key();
+        assert!(!ThePropagandist::verify_report(report, &signature, &other_key));
+        assert!(!ThePropagandist::verify_report(report, "not a signature", &public_key));
+    }
+
+    #[test]
+    fn test_decode_signing_key_rejects_wrong_length() {
+        assert!(decode_signing_key(&BASE64.encode([1u8; 16])).is_err());
+        assert!(decode_signing_key("***").is_err());
+    }
+}